@@ -395,6 +395,22 @@ function generateSummary(result, context) {
     }
     return summary;
 }
+// Same format as the delta's `metric_breakdown`: the metric that drove ΔLRS
+// with its before and after values, then every other metric that changed
+function metricBreakdown(d) {
+    const driver = d.delta.driver;
+    const parts = [];
+    if (driver && d.before && d.after) {
+        parts.push(`${driver.toUpperCase()} ${d.before.metrics[driver]}→${d.after.metrics[driver]}`);
+    }
+    ['cc', 'nd', 'fo', 'ns']
+        .filter(name => name !== driver && d.delta[name] !== 0)
+        .forEach(name => {
+        const change = d.delta[name];
+        parts.push(`${name.toUpperCase()} ${change > 0 ? '+' : ''}${change}`);
+    });
+    return parts.length > 0 ? parts.join(' · ') : '-';
+}
 function generateDiffSummary(result, baseSha, headSha) {
     const deltas = result.deltas || [];
     const policy = result.policy || { failed: [], warnings: [] };
@@ -404,6 +420,18 @@ function generateDiffSummary(result, baseSha, headSha) {
     let summary = '# Hotspots Analysis Results\n\n';
     summary += `**Mode:** Diff (${baseSha.slice(0, 7)} → ${headSha.slice(0, 7)})\n\n`;
     summary += `**Changes:** ${modified.length} modified, ${newFns.length} new, ${deleted.length} deleted\n\n`;
+    const regressions = modified
+        .filter((d) => d.delta && d.delta.lrs > 0)
+        .sort((a, b) => b.delta.lrs - a.delta.lrs);
+    if (regressions.length > 0) {
+        summary += '## Top Regressions\n\n';
+        summary += '| Function | ΔLRS | Breakdown |\n';
+        summary += '|----------|------|-----------|\n';
+        regressions.slice(0, 10).forEach((d) => {
+            summary += `| ${d.function_id} | +${d.delta.lrs.toFixed(2)} | ${metricBreakdown(d)} |\n`;
+        });
+        summary += '\n';
+    }
     if (policy.failed.length === 0 && policy.warnings.length === 0) {
         summary += deltas.length === 0
             ? '✅ **No function changes detected.**\n'
@@ -433,6 +433,23 @@ function generateSummary(result: any, context: 'pr' | 'push'): string {
   return summary;
 }
 
+// Same format as the delta's `metric_breakdown`: the metric that drove ΔLRS
+// with its before and after values, then every other metric that changed
+function metricBreakdown(d: any): string {
+  const driver = d.delta.driver;
+  const parts: string[] = [];
+  if (driver && d.before && d.after) {
+    parts.push(`${driver.toUpperCase()} ${d.before.metrics[driver]}→${d.after.metrics[driver]}`);
+  }
+  ['cc', 'nd', 'fo', 'ns']
+    .filter(name => name !== driver && d.delta[name] !== 0)
+    .forEach(name => {
+      const change = d.delta[name];
+      parts.push(`${name.toUpperCase()} ${change > 0 ? '+' : ''}${change}`);
+    });
+  return parts.length > 0 ? parts.join(' · ') : '-';
+}
+
 function generateDiffSummary(result: any, baseSha: string, headSha: string): string {
   const deltas = result.deltas || [];
   const policy = result.policy || { failed: [], warnings: [] };
@@ -445,6 +462,19 @@ function generateDiffSummary(result: any, baseSha: string, headSha: string): str
   summary += `**Mode:** Diff (${baseSha.slice(0, 7)} → ${headSha.slice(0, 7)})\n\n`;
   summary += `**Changes:** ${modified.length} modified, ${newFns.length} new, ${deleted.length} deleted\n\n`;
 
+  const regressions = modified
+    .filter((d: any) => d.delta && d.delta.lrs > 0)
+    .sort((a: any, b: any) => b.delta.lrs - a.delta.lrs);
+  if (regressions.length > 0) {
+    summary += '## Top Regressions\n\n';
+    summary += '| Function | ΔLRS | Breakdown |\n';
+    summary += '|----------|------|-----------|\n';
+    regressions.slice(0, 10).forEach((d: any) => {
+      summary += `| ${d.function_id} | +${d.delta.lrs.toFixed(2)} | ${metricBreakdown(d)} |\n`;
+    });
+    summary += '\n';
+  }
+
   if (policy.failed.length === 0 && policy.warnings.length === 0) {
     summary += deltas.length === 0
       ? '✅ **No function changes detected.**\n'
@@ -1,9 +1,13 @@
-use crate::output::{explain, policy};
-use crate::util::{find_repo_root, write_html_report};
-use crate::{OutputFormat, OutputLevel, OutputMode};
+use crate::output::{explain, policy, why};
+use crate::util::{default_report_path, find_repo_root, warn_version_skew, write_html_report};
+use crate::{
+    EmitTarget, FunctionOrder, Granularity, OutputFormat, OutputLevel, OutputMode, SortKey,
+};
 use anyhow::Context;
 use hotspots_core::delta::Delta;
+use hotspots_core::diagnostics::{self, Level};
 use hotspots_core::gate::{check_gate, GateConfig, GateVerdict};
+use hotspots_core::redact::Redactor;
 use hotspots_core::snapshot::{self, Snapshot};
 use hotspots_core::TouchMode;
 use hotspots_core::{analyze_with_progress, AnalysisOptions};
@@ -17,12 +21,18 @@ pub(crate) struct AnalyzeArgs {
     pub mode: Option<OutputMode>,
     pub policy: bool,
     pub top: Option<usize>,
+    /// Truncate emitted snapshot JSON/JSONL functions after aggregation (`--top-output`).
+    pub top_output: Option<usize>,
     pub min_lrs: Option<f64>,
     pub config_path: Option<PathBuf>,
     pub output: Option<PathBuf>,
+    /// Artifacts rendered from one snapshot in place of `format`/`output` (`--emit`).
+    pub emit: Vec<EmitTarget>,
     pub explain: bool,
     pub force: bool,
     pub no_persist: bool,
+    /// Summary-and-exit-code only; never persists or emits reports (`--check`).
+    pub check: bool,
     pub level: Option<OutputLevel>,
     pub per_function_touches: bool,
     pub all_functions: bool,
@@ -30,6 +40,10 @@ pub(crate) struct AnalyzeArgs {
     pub explain_patterns: bool,
     /// URL of the corresponding written analysis post, embedded as a banner in HTML output.
     pub source_url: Option<String>,
+    /// Stylesheet file appended after the built-in HTML report CSS (`--html-css`).
+    pub html_css: Option<PathBuf>,
+    /// Custom HTML report title (`--html-title`).
+    pub html_title: Option<String>,
     /// Number of rayon worker threads; None = use all logical CPUs.
     pub jobs: Option<usize>,
     /// CLI override for callgraph_skip_above; None = use resolved config value.
@@ -38,6 +52,10 @@ pub(crate) struct AnalyzeArgs {
     pub no_per_function_touches: bool,
     /// When true, skip all touch metrics (no git log calls at all).
     pub skip_touch_metrics: bool,
+    /// Structural metrics only, with no git-derived enrichment (`--metrics-only`).
+    pub metrics_only: bool,
+    /// Derive recency from file mtimes in a `--metrics-only` snapshot (`--use-mtime`).
+    pub use_mtime: bool,
     /// Hybrid touch threshold: file-level first, per-function for files with ≥N touches/30d.
     pub hybrid_touches: Option<usize>,
     /// Skip the suppression gate check entirely.
@@ -45,25 +63,135 @@ pub(crate) struct AnalyzeArgs {
     /// Rank via Gini-gated cold-start routing (F62/F63) instead of a trained ranker.
     /// Explicit opt-in only; reads no fix-commit label data.
     pub cold_start: bool,
+    /// Function ID to explain in detail (`--why`); bypasses normal output.
+    pub why: Option<String>,
+    /// Function name to print a detailed breakdown for (`--function`); bypasses normal output.
+    pub function: Option<String>,
+    /// Path suffix narrowing `--function` matches to one file (`--file`).
+    pub file: Option<String>,
+    /// List skipped paths with reasons instead of analyzing (`--list-skipped`).
+    pub list_skipped: bool,
+    /// CLI override for max_file_bytes; None = use resolved config value.
+    pub max_file_size: Option<u64>,
+    /// CLI override for max_function_loc; None = use resolved config value.
+    pub max_function_loc: Option<usize>,
+    /// CLI override for granularity; None = use resolved config value.
+    pub granularity: Option<Granularity>,
+    /// `--include` globs merged into (or replacing) the config's include list.
+    pub include_globs: Vec<String>,
+    /// `--exclude` globs merged into (or replacing) the config's exclude list.
+    pub exclude_globs: Vec<String>,
+    /// `--include-lang` names merged into the config's include_languages.
+    pub include_langs: Vec<String>,
+    /// `--exclude-lang` names merged into the config's exclude_languages.
+    pub exclude_langs: Vec<String>,
+    /// Replace, rather than merge with, the config's include/exclude lists.
+    pub exclude_only: bool,
+    /// Error out (instead of warning) on shallow clones in git-history modes.
+    pub require_full_history: bool,
+    /// Error out when analysis finds no functions (`--require-functions`).
+    pub require_functions: bool,
+    /// Throttled line-based file progress on stderr, regardless of TTY.
+    pub progress: bool,
+    /// Annotate file risk with git-authorship bus factor (snapshot mode only).
+    pub with_authors: bool,
+    /// Restrict output to functions in files primarily owned by this author (`--author`).
+    pub author: Option<String>,
+    /// Restrict explain output to functions touched since the parent commit
+    /// (`--only-changed-functions`).
+    pub only_changed_functions: bool,
+    /// LCOV tracefile to join onto snapshot functions (`--coverage`).
+    pub coverage: Option<PathBuf>,
+    /// Re-analyze only files changed since the parent commit's snapshot (`--incremental`).
+    pub incremental: bool,
+    /// Skip the tool-version mismatch warning against the parent snapshot (`--ignore-version-skew`).
+    pub ignore_version_skew: bool,
+    /// Compare against a fresh analysis of the merge-base with this branch (`--baseline-branch`).
+    pub baseline_branch: Option<String>,
+    /// Analyze only files changed in this `A..B` range (`--paths-from-rev-range`).
+    pub paths_from_rev_range: Option<String>,
+    /// Presentation ordering for text/HTML function lists (`--order`).
+    pub order: Option<FunctionOrder>,
+    /// Metric the default output is ranked by before `--top` truncation (`--sort`).
+    pub sort: Option<SortKey>,
+    /// `--baseline-diff [PATH]`; `Some(None)` means the default baseline location.
+    pub baseline_diff: Option<Option<PathBuf>>,
+    /// Hash identifiers in the output with the config's `redact_salt` (`--redact`).
+    pub redact: bool,
+    /// Suppress non-error stderr diagnostics (`--quiet`).
+    pub quiet: bool,
 }
 
 /// Validate flag combinations that are mode/format-specific.
 pub(crate) fn validate_analyze_flags(args: &AnalyzeArgs) -> anyhow::Result<()> {
     let AnalyzeArgs {
+        path,
         mode,
         format,
         policy,
         explain,
         per_function_touches,
         no_persist,
+        check,
         force,
+        output,
         level,
         all_functions,
         include_models,
         explain_patterns,
         cold_start,
+        why,
+        function,
+        list_skipped,
+        with_authors,
+        author,
+        only_changed_functions,
+        coverage,
+        incremental,
+        ignore_version_skew,
+        baseline_branch,
+        order,
+        sort,
+        baseline_diff,
+        redact,
+        top_output,
+        html_css,
+        html_title,
+        metrics_only,
+        use_mtime,
+        paths_from_rev_range,
+        emit,
         ..
     } = args;
+    // Snapshot output renders every `--emit` format, or `--format` alone
+    let formats: Vec<OutputFormat> = if emit.is_empty() {
+        vec![*format]
+    } else {
+        emit.iter().map(|target| target.format).collect()
+    };
+    // `--metrics-only --mode snapshot` reads nothing from git, so it is the one
+    // mode an archive supports
+    if hotspots_core::archive::is_archive(path)
+        && ((mode.is_some() && !*metrics_only)
+            || *cold_start
+            || baseline_diff.is_some()
+            || paths_from_rev_range.is_some()
+            || *list_skipped)
+    {
+        anyhow::bail!(
+            "archives have no git history: --mode (without --metrics-only), --cold-start, \
+             --baseline-diff, --paths-from-rev-range, and --list-skipped need a checkout"
+        );
+    }
+    if why.is_some() && !matches!(format, OutputFormat::Text) {
+        anyhow::bail!("--why only supports --format text");
+    }
+    if function.is_some() && !matches!(format, OutputFormat::Text | OutputFormat::Json) {
+        anyhow::bail!("--function supports --format text or --format json");
+    }
+    if *list_skipped && !matches!(format, OutputFormat::Text | OutputFormat::Json) {
+        anyhow::bail!("--list-skipped supports --format text or --format json");
+    }
     if *cold_start && mode.is_some() {
         anyhow::bail!("--cold-start is not compatible with --mode (it bypasses the trained-ranker/snapshot pipeline entirely)");
     }
@@ -89,6 +217,17 @@ pub(crate) fn validate_analyze_flags(args: &AnalyzeArgs) -> anyhow::Result<()> {
             anyhow::bail!("--no-persist and --force are mutually exclusive");
         }
     }
+    if *check {
+        if !matches!(mode, Some(OutputMode::Snapshot | OutputMode::Delta)) {
+            anyhow::bail!("--check is only valid with --mode snapshot or --mode delta");
+        }
+        if *force {
+            anyhow::bail!("--check and --force are mutually exclusive");
+        }
+        if output.is_some() {
+            anyhow::bail!("--check never writes output; drop --output");
+        }
+    }
     if level.is_some() {
         if *mode != Some(OutputMode::Snapshot) {
             anyhow::bail!("--level is only valid with --mode snapshot");
@@ -101,10 +240,14 @@ pub(crate) fn validate_analyze_flags(args: &AnalyzeArgs) -> anyhow::Result<()> {
         }
     }
     if *all_functions
-        && (*mode != Some(OutputMode::Snapshot) || !matches!(format, OutputFormat::Json))
+        && (*mode != Some(OutputMode::Snapshot)
+            || !formats.iter().any(|f| matches!(f, OutputFormat::Json)))
     {
         anyhow::bail!("--all-functions is only valid with --mode snapshot --format json");
     }
+    if *all_functions && *explain {
+        anyhow::bail!("--all-functions and --explain are mutually exclusive");
+    }
     if *mode == Some(OutputMode::Models)
         && !matches!(format, OutputFormat::Text | OutputFormat::Json)
     {
@@ -112,16 +255,173 @@ pub(crate) fn validate_analyze_flags(args: &AnalyzeArgs) -> anyhow::Result<()> {
     }
     if *include_models
         && (*mode != Some(OutputMode::Snapshot)
-            || !matches!(format, OutputFormat::Json | OutputFormat::Html))
+            || !formats
+                .iter()
+                .any(|f| matches!(f, OutputFormat::Json | OutputFormat::Html)))
     {
         anyhow::bail!("--include-models is only valid with --mode snapshot --format json/html");
     }
     if *explain_patterns && *mode != Some(OutputMode::Snapshot) && mode.is_some() {
         anyhow::bail!("--explain-patterns is only valid with --mode snapshot or without --mode");
     }
+    if order.is_some()
+        && !formats
+            .iter()
+            .any(|f| matches!(f, OutputFormat::Text | OutputFormat::Html))
+    {
+        anyhow::bail!("--order only applies to --format text or --format html");
+    }
+    if *order == Some(FunctionOrder::Priority) && *mode != Some(OutputMode::Snapshot) {
+        anyhow::bail!("--order priority is only valid with --mode snapshot");
+    }
+    if *order == Some(FunctionOrder::Leverage) && *mode != Some(OutputMode::Snapshot) {
+        anyhow::bail!("--order leverage is only valid with --mode snapshot");
+    }
+    if sort.is_some() {
+        if mode.is_some() {
+            anyhow::bail!("--sort is only valid without --mode");
+        }
+        if !matches!(format, OutputFormat::Text | OutputFormat::Json) {
+            anyhow::bail!("--sort supports --format text or --format json");
+        }
+        if *cold_start
+            || why.is_some()
+            || function.is_some()
+            || *list_skipped
+            || baseline_diff.is_some()
+        {
+            anyhow::bail!("--sort only applies to the default function list");
+        }
+    }
+    if baseline_diff.is_some() && !matches!(format, OutputFormat::Text | OutputFormat::Json) {
+        anyhow::bail!("--baseline-diff supports --format text or --format json");
+    }
+    if *redact {
+        let supported = match mode {
+            None => matches!(format, OutputFormat::Text | OutputFormat::Json),
+            Some(OutputMode::Snapshot) => formats.iter().all(|f| match f {
+                OutputFormat::Jsonl => true,
+                OutputFormat::Json => *all_functions,
+                _ => false,
+            }),
+            Some(_) => false,
+        };
+        if !supported
+            || *cold_start
+            || why.is_some()
+            || function.is_some()
+            || *list_skipped
+            || baseline_diff.is_some()
+        {
+            anyhow::bail!(
+                "--redact supports default text/JSON output, or --mode snapshot with --format jsonl or --all-functions"
+            );
+        }
+    }
+    if top_output.is_some()
+        && (*mode != Some(OutputMode::Snapshot)
+            || !(formats.iter().any(|f| matches!(f, OutputFormat::Jsonl)) || *all_functions))
+    {
+        anyhow::bail!(
+            "--top-output is only valid with --mode snapshot and --format jsonl or --all-functions"
+        );
+    }
+    if *with_authors && *mode != Some(OutputMode::Snapshot) {
+        anyhow::bail!("--with-authors is only valid with --mode snapshot");
+    }
+    if author.is_some() && *mode != Some(OutputMode::Snapshot) {
+        anyhow::bail!("--author is only valid with --mode snapshot");
+    }
+    if *only_changed_functions && (*mode != Some(OutputMode::Snapshot) || !*explain) {
+        anyhow::bail!("--only-changed-functions is only valid with --mode snapshot --explain");
+    }
+    if coverage.is_some() && *mode != Some(OutputMode::Snapshot) {
+        anyhow::bail!("--coverage is only valid with --mode snapshot");
+    }
+    if *incremental && *mode != Some(OutputMode::Snapshot) {
+        anyhow::bail!("--incremental is only valid with --mode snapshot");
+    }
+    if *ignore_version_skew && *mode != Some(OutputMode::Delta) {
+        anyhow::bail!("--ignore-version-skew is only valid with --mode delta");
+    }
+    if baseline_branch.is_some() && *mode != Some(OutputMode::Delta) {
+        anyhow::bail!("--baseline-branch is only valid with --mode delta");
+    }
+    if !emit.is_empty() {
+        if *mode != Some(OutputMode::Snapshot) || *metrics_only {
+            anyhow::bail!("--emit is only valid with --mode snapshot");
+        }
+        if *check || *explain || level.is_some() {
+            anyhow::bail!("--emit can't be combined with --check, --explain, or --level");
+        }
+        let mut paths = std::collections::HashSet::new();
+        if let Some(dup) = emit.iter().find(|target| !paths.insert(&target.path)) {
+            anyhow::bail!("--emit writes {} more than once", dup.path.display());
+        }
+    }
+    if paths_from_rev_range.is_some() && (mode.is_some() || *metrics_only) {
+        anyhow::bail!("--paths-from-rev-range is only valid without --mode and --metrics-only");
+    }
     if matches!(format, OutputFormat::Sarif) && *mode != Some(OutputMode::Snapshot) {
         anyhow::bail!("--format sarif requires --mode snapshot");
     }
+    if matches!(format, OutputFormat::Canonical) && *mode != Some(OutputMode::Snapshot) {
+        anyhow::bail!("--format canonical requires --mode snapshot");
+    }
+    if (html_css.is_some() || html_title.is_some()) && !matches!(format, OutputFormat::Html) {
+        anyhow::bail!("--html-css and --html-title are only valid with --format html");
+    }
+    if *metrics_only {
+        if mode.is_some() && *mode != Some(OutputMode::Snapshot) {
+            anyhow::bail!("--metrics-only is only valid with --mode snapshot or without --mode");
+        }
+        if mode.is_some() && !matches!(format, OutputFormat::Json | OutputFormat::Jsonl) {
+            anyhow::bail!(
+                "--metrics-only with --mode snapshot supports --format json or --format jsonl"
+            );
+        }
+        if *cold_start
+            || *check
+            || *force
+            || *explain
+            || *include_models
+            || *with_authors
+            || author.is_some()
+            || *incremental
+        {
+            anyhow::bail!(
+                "--metrics-only skips git history and snapshot enrichment; it can't be combined \
+                 with --cold-start, --check, --force, --explain, --include-models, \
+                 --with-authors, --author, or --incremental"
+            );
+        }
+    }
+    if *use_mtime && !(*metrics_only && *mode == Some(OutputMode::Snapshot)) {
+        anyhow::bail!("--use-mtime is only valid with --metrics-only --mode snapshot");
+    }
+    Ok(())
+}
+
+/// Warn once (or, with `--require-full-history`, fail) when the repo is a shallow
+/// clone, since churn, touch, and co-change metrics would read truncated history.
+fn check_history_depth(repo_root: &Path, require_full_history: bool) -> anyhow::Result<()> {
+    if !git::is_shallow_repository(repo_root) {
+        return Ok(());
+    }
+    if require_full_history {
+        anyhow::bail!(
+            "{} is a shallow clone; churn, touch, and co-change metrics need full history \
+             (run `git fetch --unshallow`, or use `fetch-depth: 0` in CI)",
+            repo_root.display()
+        );
+    }
+    if diagnostics::enabled(Level::Warn) {
+        eprintln!(
+            "warning: shallow clone detected — churn, touch, and co-change metrics will be \
+             incomplete (run `git fetch --unshallow` for full history, or pass \
+             --require-full-history to fail instead)"
+        );
+    }
     Ok(())
 }
 
@@ -134,27 +434,66 @@ pub(crate) fn handle_analyze(args: AnalyzeArgs) -> anyhow::Result<()> {
         mode,
         policy,
         top,
+        top_output,
         min_lrs,
         config_path,
         output,
+        emit,
         explain,
         force,
         no_persist,
+        check,
         level,
         per_function_touches,
         no_per_function_touches,
         skip_touch_metrics,
+        metrics_only,
+        use_mtime,
         hybrid_touches,
         all_functions,
         include_models,
         explain_patterns,
         source_url,
+        html_css,
+        html_title,
         jobs,
         callgraph_skip_above,
         skip_gate,
         cold_start,
+        why,
+        function,
+        file,
+        list_skipped,
+        max_file_size,
+        max_function_loc,
+        granularity,
+        include_globs,
+        exclude_globs,
+        include_langs,
+        exclude_langs,
+        exclude_only,
+        require_full_history,
+        require_functions,
+        progress,
+        with_authors,
+        author,
+        only_changed_functions,
+        coverage,
+        incremental,
+        ignore_version_skew,
+        baseline_branch,
+        paths_from_rev_range,
+        order,
+        sort,
+        baseline_diff,
+        redact,
+        quiet,
     } = args;
 
+    if quiet {
+        diagnostics::set_threshold(Level::Error);
+    }
+
     // Configure the global rayon thread pool before any parallel work begins.
     // Errors are ignored: build_global() fails if rayon was already initialized
     // (e.g. in tests), which is harmless.
@@ -174,15 +513,82 @@ pub(crate) fn handle_analyze(args: AnalyzeArgs) -> anyhow::Result<()> {
         anyhow::bail!("Path does not exist: {}", normalized_path.display());
     }
 
-    let project_root = find_repo_root(&normalized_path).unwrap_or_else(|_| normalized_path.clone());
-    let resolved_config =
+    // An archive stands alone: config comes from beside it, never from an
+    // enclosing repository
+    let is_archive = hotspots_core::archive::is_archive(&normalized_path);
+    let project_root = match normalized_path.parent().filter(|_| is_archive) {
+        Some(dir) => dir.to_path_buf(),
+        None => find_repo_root(&normalized_path).unwrap_or_else(|_| normalized_path.clone()),
+    };
+    let mut resolved_config =
         hotspots_core::config::load_and_resolve(&project_root, config_path.as_deref())
             .context("failed to load configuration")?;
+    if let Some(n) = max_file_size {
+        resolved_config.max_file_bytes = n;
+    }
+    if let Some(n) = max_function_loc {
+        if n == 0 {
+            anyhow::bail!("--max-function-loc must be at least 1");
+        }
+        resolved_config.set_max_function_loc(n);
+    }
+    if require_functions {
+        resolved_config.require_functions = true;
+    }
+    if let Some(g) = granularity {
+        resolved_config.granularity = match g {
+            Granularity::Function => hotspots_core::config::Granularity::Function,
+            Granularity::File => hotspots_core::config::Granularity::File,
+        };
+    }
+    if why.is_some() || function.is_some() {
+        // Both look up a single function by name
+        resolved_config.granularity = hotspots_core::config::Granularity::Function;
+    }
+    if exclude_only || !include_globs.is_empty() || !exclude_globs.is_empty() {
+        resolved_config
+            .apply_path_filter_overrides(&include_globs, &exclude_globs, exclude_only)
+            .context("invalid --include/--exclude pattern")?;
+    }
+    if !include_langs.is_empty() || !exclude_langs.is_empty() {
+        resolved_config.apply_language_filter_overrides(&include_langs, &exclude_langs)?;
+    }
 
     if let Some(ref p) = resolved_config.config_path {
-        eprintln!("Using config: {}", p.display());
+        if diagnostics::enabled(Level::Info) {
+            eprintln!("Using config: {}", p.display());
+        }
+    }
+
+    let redactor = if redact {
+        let salt = resolved_config.redact_salt.as_deref().context(
+            "--redact requires `redact_salt` in the config file so redacted runs are comparable",
+        )?;
+        Some(Redactor::new(salt, &project_root))
+    } else {
+        None
+    };
+
+    let coverage = coverage
+        .map(|path| hotspots_core::coverage::LcovReport::load(&path))
+        .transpose()?;
+    if let Some(report) = &coverage {
+        if diagnostics::enabled(Level::Info) {
+            eprintln!("info: loaded coverage for {} file(s)", report.file_count());
+        }
     }
 
+    let html_theme = hotspots_core::html::HtmlTheme {
+        title: html_title,
+        css: html_css
+            .map(|p| {
+                std::fs::read_to_string(&p)
+                    .with_context(|| format!("failed to read --html-css file: {}", p.display()))
+            })
+            .transpose()?,
+        band_colors: resolved_config.band_colors.clone(),
+    };
+
     let effective_min_lrs = min_lrs.or(resolved_config.min_lrs);
     let effective_top = top.or(resolved_config.top_n);
     let touch_args = TouchArgs {
@@ -198,12 +604,67 @@ pub(crate) fn handle_analyze(args: AnalyzeArgs) -> anyhow::Result<()> {
         resolved_config.per_function_touches,
     );
 
+    if list_skipped {
+        return handle_list_skipped(&normalized_path, &resolved_config, format);
+    }
+
+    if let Some(function_id) = why {
+        return handle_why(&normalized_path, &function_id, &resolved_config, progress);
+    }
+
+    if let Some(name) = function {
+        return handle_function(
+            &normalized_path,
+            &name,
+            file.as_deref(),
+            format,
+            &resolved_config,
+            progress,
+        );
+    }
+
+    if let Some(baseline_path) = baseline_diff {
+        let baseline_path =
+            baseline_path.unwrap_or_else(|| project_root.join(delta::BASELINE_FILE));
+        return handle_baseline_diff(
+            &normalized_path,
+            &project_root,
+            &baseline_path,
+            &resolved_config,
+            format,
+            progress,
+        );
+    }
+
+    if metrics_only {
+        if mode == Some(OutputMode::Snapshot) {
+            return handle_metrics_only_snapshot(
+                &normalized_path,
+                &project_root,
+                &resolved_config,
+                MetricsOnlyOptions {
+                    format,
+                    min_lrs: effective_min_lrs,
+                    top: effective_top,
+                    all_functions,
+                    use_mtime,
+                    output,
+                    progress,
+                    redactor,
+                },
+            );
+        }
+    } else if cold_start || mode.is_some() {
+        check_history_depth(&project_root, require_full_history)?;
+    }
+
     if cold_start {
         return handle_cold_start(
             &normalized_path,
             &resolved_config,
             effective_touch_mode,
             effective_top,
+            progress,
         );
     }
 
@@ -216,20 +677,34 @@ pub(crate) fn handle_analyze(args: AnalyzeArgs) -> anyhow::Result<()> {
                 format,
                 policy,
                 top: effective_top,
+                top_output,
                 min_lrs: effective_min_lrs,
                 output,
+                emit,
                 explain,
                 force,
                 no_persist,
+                check,
                 level,
                 touch_mode: effective_touch_mode,
                 all_functions,
                 include_models,
                 explain_patterns,
                 source_url,
+                html_theme,
                 callgraph_skip_above,
                 skip_touch_metrics: touch_args.skip,
                 skip_gate,
+                progress,
+                with_authors,
+                author,
+                only_changed_functions,
+                coverage,
+                incremental,
+                ignore_version_skew,
+                baseline_branch,
+                order,
+                redactor,
             },
         );
         return result;
@@ -241,7 +716,13 @@ pub(crate) fn handle_analyze(args: AnalyzeArgs) -> anyhow::Result<()> {
     let repo_root_for_ranker =
         find_repo_root(&normalized_path).unwrap_or_else(|_| normalized_path.clone());
     let ranker_path = snapshot::hotspots_dir(&repo_root_for_ranker).join("ranker.json");
-    if ranker_path.exists() {
+    if ranker_path.exists()
+        && redactor.is_none()
+        && !metrics_only
+        && !is_archive
+        && paths_from_rev_range.is_none()
+    {
+        check_history_depth(&repo_root_for_ranker, require_full_history)?;
         let result = handle_mode_output(
             &normalized_path,
             OutputMode::Snapshot,
@@ -250,33 +731,70 @@ pub(crate) fn handle_analyze(args: AnalyzeArgs) -> anyhow::Result<()> {
                 format,
                 policy: false,
                 top: effective_top,
+                top_output: None,
                 min_lrs: effective_min_lrs,
                 output,
-                explain: matches!(format, OutputFormat::Text),
+                emit: Vec::new(),
+                explain: explain || matches!(format, OutputFormat::Text),
                 force,
                 no_persist: true, // default analyze doesn't persist snapshots
+                check: false,
                 level,
                 touch_mode: effective_touch_mode,
                 all_functions: false,
                 include_models: false,
                 explain_patterns,
                 source_url,
+                html_theme,
                 callgraph_skip_above,
                 skip_touch_metrics: touch_args.skip,
                 skip_gate,
+                progress,
+                with_authors: false,
+                author: None,
+                only_changed_functions: false,
+                coverage: None,
+                incremental: false,
+                ignore_version_skew: false,
+                baseline_branch: None,
+                order,
+                redactor: None,
             },
         );
         return result;
     }
 
+    let files = paths_from_rev_range
+        .map(|range| {
+            let changed = git::changed_files_in_range(&project_root, &range)
+                .with_context(|| format!("failed to list files changed in {range}"))?;
+            let files = paths_under(&normalized_path, &project_root, &changed);
+            if diagnostics::enabled(Level::Info) {
+                eprintln!(
+                    "info: analyzing {} file(s) changed in {}",
+                    files.len(),
+                    range
+                );
+            }
+            anyhow::Ok(files)
+        })
+        .transpose()?;
+
     // Default behavior (no --mode): simple text/JSON output
     handle_default_output(
         &normalized_path,
-        format,
-        explain_patterns,
-        effective_min_lrs,
-        effective_top,
         &resolved_config,
+        DefaultOutputOptions {
+            files,
+            format,
+            order,
+            sort,
+            explain_patterns,
+            min_lrs: effective_min_lrs,
+            top: effective_top,
+            progress,
+            redactor,
+        },
     )
 }
 
@@ -299,9 +817,10 @@ fn handle_cold_start(
     resolved_config: &hotspots_core::ResolvedConfig,
     touch_mode: TouchMode,
     top: Option<usize>,
+    progress: bool,
 ) -> anyhow::Result<()> {
     let repo_root = find_repo_root(path)?;
-    let analysis_progress = make_analysis_progress();
+    let analysis_progress = make_analysis_progress(progress);
     let reports = analyze_with_progress(
         path,
         AnalysisOptions {
@@ -350,15 +869,247 @@ fn handle_cold_start(
     Ok(())
 }
 
-fn handle_default_output(
+struct MetricsOnlyOptions {
+    format: OutputFormat,
+    min_lrs: Option<f64>,
+    top: Option<usize>,
+    all_functions: bool,
+    /// Fill in recency from file mtimes (`--use-mtime`)
+    use_mtime: bool,
+    output: Option<PathBuf>,
+    progress: bool,
+    redactor: Option<Redactor>,
+}
+
+/// `hotspots analyze --mode snapshot --metrics-only`: a snapshot of structural
+/// metrics, LRS, and band alone. Makes no git calls, so it works outside a
+/// repository; the commit is the `working-tree` placeholder and nothing is persisted.
+/// With `--use-mtime`, file modification times (an archive's entry times) stand
+/// in for git recency.
+fn handle_metrics_only_snapshot(
+    path: &Path,
+    project_root: &Path,
+    resolved_config: &hotspots_core::ResolvedConfig,
+    opts: MetricsOnlyOptions,
+) -> anyhow::Result<()> {
+    let MetricsOnlyOptions {
+        format,
+        min_lrs,
+        top,
+        all_functions,
+        use_mtime,
+        output,
+        progress,
+        redactor,
+    } = opts;
+    let analysis_progress = make_analysis_progress(progress);
+    let reports = analyze_with_progress(
+        path,
+        AnalysisOptions {
+            min_lrs,
+            top_n: None,
+        },
+        Some(resolved_config),
+        Some(analysis_progress.as_ref()),
+    )?;
+    let mut snapshot = Snapshot::new(
+        git::GitContext::placeholder("working-tree", Vec::new()),
+        reports,
+    );
+    // Paths are relative to the repository, or outside one to the analyzed
+    // directory, as in the other snapshot outputs
+    let root = if project_root.is_dir() {
+        project_root
+    } else {
+        project_root.parent().unwrap_or(project_root)
+    };
+    snapshot.relativize_paths(root);
+    if use_mtime {
+        if hotspots_core::archive::is_archive(path) {
+            let mtimes = hotspots_core::archive::entry_mtimes(path, Some(resolved_config))?;
+            snapshot.populate_recency_from_mtimes(&mtimes);
+        } else {
+            snapshot.populate_mtime_recency(root)?;
+        }
+        snapshot.compute_activity_risk(Some(&resolved_config.scoring_weights));
+    }
+    snapshot.compute_quadrants(resolved_config.driver_threshold_percentile, false);
+    apply_top_n(&mut snapshot, format, false, None, top);
+    if let Some(redactor) = &redactor {
+        redactor.redact_snapshot(&mut snapshot);
+    }
+    match format {
+        OutputFormat::Jsonl => emit_jsonl_output(&mut snapshot, None, output),
+        _ if all_functions => write_json_snapshot(&snapshot, output),
+        _ => {
+            let aggregates = hotspots_core::aggregates::compute_structural_aggregates(
+                &snapshot,
+                root,
+                &resolved_config.file_risk_weights,
+                &resolved_config.module_zones,
+            );
+            let agent_output = hotspots_core::aggregates::compute_agent_snapshot_output(
+                &snapshot,
+                &aggregates,
+                root,
+            );
+            write_json_agent(&agent_output, output)
+        }
+    }
+}
+
+/// `hotspots analyze --why <id>`: print the full score derivation for one function.
+fn handle_why(
     path: &Path,
+    function_id: &str,
+    resolved_config: &hotspots_core::ResolvedConfig,
+    progress: bool,
+) -> anyhow::Result<()> {
+    let analysis_progress = make_analysis_progress(progress);
+    let reports = analyze_with_progress(
+        path,
+        AnalysisOptions {
+            min_lrs: None,
+            top_n: None,
+        },
+        Some(resolved_config),
+        Some(analysis_progress.as_ref()),
+    )?;
+    let report = why::find_function(&reports, function_id)?;
+    why::print_why(report, resolved_config);
+    Ok(())
+}
+
+/// `hotspots analyze --list-skipped`: print each path analysis would skip, with the reason.
+fn handle_list_skipped(
+    path: &Path,
+    resolved_config: &hotspots_core::ResolvedConfig,
     format: OutputFormat,
+) -> anyhow::Result<()> {
+    let skipped = hotspots_core::list_skipped_paths(path, Some(resolved_config))?;
+    if matches!(format, OutputFormat::Json) {
+        println!("{}", hotspots_core::render_skipped_json(&skipped));
+        return Ok(());
+    }
+    for entry in &skipped {
+        println!("{:<22} {}", entry.reason.as_str(), entry.path.display());
+    }
+    println!("{} path(s) skipped", skipped.len());
+    Ok(())
+}
+
+/// `hotspots analyze --function <name>`: print the breakdown for the named function(s).
+fn handle_function(
+    path: &Path,
+    name: &str,
+    file: Option<&str>,
+    format: OutputFormat,
+    resolved_config: &hotspots_core::ResolvedConfig,
+    progress: bool,
+) -> anyhow::Result<()> {
+    let analysis_progress = make_analysis_progress(progress);
+    let reports = analyze_with_progress(
+        path,
+        AnalysisOptions {
+            min_lrs: None,
+            top_n: None,
+        },
+        Some(resolved_config),
+        Some(analysis_progress.as_ref()),
+    )?;
+    let matches = why::find_named_functions(&reports, name, file)?;
+    if matches!(format, OutputFormat::Json) {
+        let selected: Vec<_> = matches.into_iter().cloned().collect();
+        println!("{}", hotspots_core::render_json(&selected));
+        return Ok(());
+    }
+    for (i, report) in matches.into_iter().enumerate() {
+        if i > 0 {
+            println!();
+        }
+        why::print_why(report, resolved_config);
+    }
+    Ok(())
+}
+
+struct DefaultOutputOptions {
+    /// Analyze only these files rather than walking `path` (`--paths-from-rev-range`)
+    files: Option<Vec<PathBuf>>,
+    format: OutputFormat,
+    order: Option<FunctionOrder>,
+    sort: Option<SortKey>,
     explain_patterns: bool,
     min_lrs: Option<f64>,
     top: Option<usize>,
+    progress: bool,
+    redactor: Option<Redactor>,
+}
+
+/// `hotspots analyze --baseline-diff`: diff the working tree against a committed
+/// baseline report instead of a parent snapshot, keeping only LRS regressions.
+fn handle_baseline_diff(
+    path: &Path,
+    project_root: &Path,
+    baseline_path: &Path,
+    resolved_config: &hotspots_core::ResolvedConfig,
+    format: OutputFormat,
+    progress: bool,
+) -> anyhow::Result<()> {
+    if !baseline_path.exists() {
+        anyhow::bail!(
+            "baseline file not found: {} (create it with `hotspots analyze . --format json > {}`)",
+            baseline_path.display(),
+            delta::BASELINE_FILE
+        );
+    }
+    let analysis_progress = make_analysis_progress(progress);
+    let reports = analyze_with_progress(
+        path,
+        AnalysisOptions {
+            min_lrs: None,
+            top_n: None,
+        },
+        Some(resolved_config),
+        Some(analysis_progress.as_ref()),
+    )?;
+    let mut delta_val = delta::compute_baseline_file_delta(
+        project_root,
+        baseline_path,
+        reports,
+        &resolved_config.lrs_weights(),
+    )?;
+    delta_val.retain_regressions();
+
+    match format {
+        OutputFormat::Json => println!("{}", delta_val.to_json()?),
+        _ => print!(
+            "{}",
+            crate::output::baseline::render_baseline_regressions(&delta_val)?
+        ),
+    }
+    if !delta_val.deltas.is_empty() {
+        std::process::exit(1);
+    }
+    Ok(())
+}
+
+fn handle_default_output(
+    path: &Path,
     resolved_config: &hotspots_core::ResolvedConfig,
+    opts: DefaultOutputOptions,
 ) -> anyhow::Result<()> {
-    let analysis_progress = make_analysis_progress();
+    let DefaultOutputOptions {
+        files,
+        format,
+        order,
+        sort,
+        explain_patterns,
+        min_lrs,
+        top,
+        progress,
+        redactor,
+    } = opts;
+    let analysis_progress = make_analysis_progress(progress);
     let explicit_top = top.or(resolved_config.top_n);
     // 0 is the sentinel for "show all"; otherwise default to 20 for text output
     let limit = match explicit_top {
@@ -366,23 +1117,47 @@ fn handle_default_output(
         Some(n) => n,
         None => 20,
     };
-    let mut reports = analyze_with_progress(
-        path,
-        AnalysisOptions {
-            min_lrs,
-            top_n: if matches!(format, OutputFormat::Text) {
-                Some(limit).filter(|&n| n != usize::MAX)
-            } else {
-                explicit_top.filter(|&n| n != 0)
-            },
-        },
-        Some(resolved_config),
-        Some(analysis_progress.as_ref()),
-    )?;
+    let top_n = if matches!(format, OutputFormat::Text) {
+        Some(limit).filter(|&n| n != usize::MAX)
+    } else {
+        explicit_top.filter(|&n| n != 0)
+    };
+    // Analysis keeps the top N by LRS, so any other ranking needs every function
+    let options = AnalysisOptions {
+        min_lrs,
+        top_n: top_n.filter(|_| sort.is_none()),
+    };
+    let mut reports = match files {
+        Some(files) => hotspots_core::analyze_files_with_progress(
+            path,
+            &files,
+            options,
+            Some(resolved_config),
+            Some(analysis_progress.as_ref()),
+        )?,
+        None => analyze_with_progress(
+            path,
+            options,
+            Some(resolved_config),
+            Some(analysis_progress.as_ref()),
+        )?,
+    };
+    if let Some(key) = sort {
+        rank_reports(&mut reports, key);
+        if let Some(n) = top_n {
+            reports.truncate(n);
+        }
+    }
 
     if explain_patterns {
         populate_pattern_details(&mut reports, resolved_config);
     }
+    if let Some(order) = order {
+        sort_reports(&mut reports, order);
+    }
+    if let Some(redactor) = &redactor {
+        redactor.redact_reports(&mut reports);
+    }
 
     match format {
         OutputFormat::Text => {
@@ -397,6 +1172,7 @@ fn handle_default_output(
             anyhow::bail!("HTML/JSONL format requires --mode snapshot or --mode delta");
         }
         OutputFormat::Sarif => anyhow::bail!("SARIF format requires --mode snapshot"),
+        OutputFormat::Canonical => anyhow::bail!("canonical format requires --mode snapshot"),
     }
     Ok(())
 }
@@ -412,6 +1188,12 @@ fn populate_pattern_details(
             fo: report.metrics.fo as usize,
             ns: report.metrics.ns as usize,
             loc: report.metrics.loc as usize,
+            sloc: report.metrics.sloc.map(|n| n as usize),
+            return_points: report.metrics.return_points.map(|n| n as usize),
+            unwrap_count: report.metrics.unwrap_count.map(|n| n as usize),
+            max_arm_loc: report.metrics.max_arm_loc.map(|n| n as usize),
+            max_data_nesting: report.metrics.max_data_nesting.map(|n| n as usize),
+            unreachable_code: report.metrics.unreachable_code.map(|n| n as usize),
         };
         let t2 = hotspots_core::patterns::Tier2Input {
             fan_in: None,
@@ -419,12 +1201,15 @@ fn populate_pattern_details(
             churn_lines: None,
             days_since_last_change: None,
             neighbor_churn: None,
+            coverage_pct: None,
+            lrs: Some(report.lrs),
             is_entrypoint: false,
         };
+        let kind = report.location.as_ref().map(|l| l.kind);
         report.pattern_details = Some(hotspots_core::patterns::classify_detailed(
             &t1,
             &t2,
-            &resolved_config.pattern_thresholds,
+            resolved_config.pattern_thresholds.for_kind(kind),
         ));
     }
 }
@@ -433,20 +1218,34 @@ pub(crate) struct ModeOutputOptions {
     pub format: OutputFormat,
     pub policy: bool,
     pub top: Option<usize>,
+    pub top_output: Option<usize>,
     pub min_lrs: Option<f64>,
     pub output: Option<PathBuf>,
+    pub emit: Vec<EmitTarget>,
     pub explain: bool,
     pub force: bool,
     pub no_persist: bool,
+    pub check: bool,
     pub level: Option<OutputLevel>,
     pub touch_mode: TouchMode,
     pub all_functions: bool,
     pub include_models: bool,
     pub explain_patterns: bool,
     pub source_url: Option<String>,
+    pub html_theme: hotspots_core::html::HtmlTheme,
     pub callgraph_skip_above: Option<usize>,
     pub skip_touch_metrics: bool,
     pub skip_gate: bool,
+    pub progress: bool,
+    pub with_authors: bool,
+    pub author: Option<String>,
+    pub only_changed_functions: bool,
+    pub coverage: Option<hotspots_core::coverage::LcovReport>,
+    pub incremental: bool,
+    pub ignore_version_skew: bool,
+    pub baseline_branch: Option<String>,
+    pub order: Option<FunctionOrder>,
+    pub redactor: Option<Redactor>,
 }
 
 pub(crate) fn handle_mode_output(
@@ -456,34 +1255,133 @@ pub(crate) fn handle_mode_output(
     opts: ModeOutputOptions,
 ) -> anyhow::Result<()> {
     let repo_root = find_repo_root(path)?;
-    let analysis_progress = make_analysis_progress();
-    let reports = analyze_with_progress(
-        path,
-        AnalysisOptions {
-            min_lrs: opts.min_lrs,
-            top_n: None,
-        },
-        Some(resolved_config),
-        Some(analysis_progress.as_ref()),
-    )?;
+    let options = AnalysisOptions {
+        min_lrs: opts.min_lrs,
+        top_n: None,
+    };
+    let incremental = if mode == OutputMode::Snapshot && opts.incremental {
+        load_incremental_base(path, &repo_root)?
+    } else {
+        None
+    };
+    // `max-new-cc` needs each function's CFG decision points, gathered in the same pass
+    let (reports, decisions) =
+        if mode == OutputMode::Delta && opts.policy && resolved_config.max_new_cc.is_some() {
+            let (reports, decisions) =
+                delta::analyze_with_decisions(path, options, Some(resolved_config))?;
+            (reports, Some(decisions))
+        } else {
+            let analysis_progress = make_analysis_progress(opts.progress);
+            let reports = match &incremental {
+                Some(base) => hotspots_core::analyze_files_with_progress(
+                    path,
+                    &base.changed_paths,
+                    options,
+                    Some(resolved_config),
+                    Some(analysis_progress.as_ref()),
+                )?,
+                None => analyze_with_progress(
+                    path,
+                    options,
+                    Some(resolved_config),
+                    Some(analysis_progress.as_ref()),
+                )?,
+            };
+            (reports, None)
+        };
     let pr_context = git::detect_pr_context();
 
     match mode {
-        OutputMode::Snapshot => {
-            handle_snapshot_mode(path, &repo_root, resolved_config, reports, pr_context, opts)
-        }
-        OutputMode::Delta => {
-            handle_delta_mode(&repo_root, resolved_config, reports, pr_context, opts)
-        }
+        OutputMode::Snapshot => handle_snapshot_mode(
+            path,
+            &repo_root,
+            resolved_config,
+            reports,
+            incremental,
+            pr_context,
+            opts,
+        ),
+        OutputMode::Delta => handle_delta_mode(
+            &repo_root,
+            resolved_config,
+            reports,
+            decisions.as_ref(),
+            pr_context,
+            opts,
+        ),
         OutputMode::Models => handle_models_mode(path, &repo_root, resolved_config, reports, opts),
     }
 }
 
+/// Repo-relative `changed` paths that lie under `path`, named as the directory
+/// walk of `path` would name them
+fn paths_under(path: &Path, repo_root: &Path, changed: &[String]) -> Vec<PathBuf> {
+    changed
+        .iter()
+        .filter_map(|rel| {
+            let rest = repo_root.join(rel).strip_prefix(path).ok()?.to_path_buf();
+            Some(if rest.as_os_str().is_empty() {
+                path.to_path_buf()
+            } else {
+                path.join(rest)
+            })
+        })
+        .collect()
+}
+
+/// The parent commit's snapshot and the files changed since, for `--incremental`
+struct IncrementalBase {
+    parent: Snapshot,
+    /// Changed files under the analyzed path, as the directory walk names them
+    changed_paths: Vec<PathBuf>,
+    /// `changed_paths` in `FunctionSnapshot::file` form
+    changed_files: std::collections::HashSet<String>,
+}
+
+/// Load the parent commit's snapshot and list the files changed since it, or
+/// `None` (with a notice) when there is no parent snapshot to build on
+fn load_incremental_base(path: &Path, repo_root: &Path) -> anyhow::Result<Option<IncrementalBase>> {
+    let git_context =
+        git::extract_git_context_at(repo_root).context("failed to extract git context")?;
+    let parent = match git_context.parent_shas.first() {
+        Some(sha) => snapshot::load_snapshot(repo_root, sha)
+            .with_context(|| format!("failed to load parent snapshot {sha}"))?,
+        None => None,
+    };
+    let Some(parent) = parent else {
+        if diagnostics::enabled(Level::Info) {
+            eprintln!("info: no snapshot of the parent commit; running a full analysis");
+        }
+        return Ok(None);
+    };
+
+    let changed = git::changed_files_since(repo_root, parent.commit_sha())
+        .context("failed to list files changed since the parent commit")?;
+    let changed_paths = paths_under(path, repo_root, &changed);
+    let changed_files = changed_paths
+        .iter()
+        .map(|p| p.to_string_lossy().replace('\\', "/"))
+        .collect();
+    if diagnostics::enabled(Level::Info) {
+        eprintln!(
+            "info: incremental snapshot: re-analyzing {} changed file(s) since {}",
+            changed_paths.len(),
+            &parent.commit_sha()[..parent.commit_sha().len().min(8)]
+        );
+    }
+    Ok(Some(IncrementalBase {
+        parent,
+        changed_paths,
+        changed_files,
+    }))
+}
+
 fn handle_snapshot_mode(
     path: &Path,
     repo_root: &Path,
     resolved_config: &hotspots_core::ResolvedConfig,
     reports: Vec<hotspots_core::FunctionRiskReport>,
+    incremental: Option<IncrementalBase>,
     pr_context: hotspots_core::git::PrContext,
     opts: ModeOutputOptions,
 ) -> anyhow::Result<()> {
@@ -492,17 +1390,27 @@ fn handle_snapshot_mode(
         explain,
         force,
         no_persist,
+        check,
         level,
         touch_mode,
         all_functions,
         include_models,
         explain_patterns,
         source_url,
+        html_theme,
         callgraph_skip_above,
         skip_touch_metrics,
         skip_gate,
         top,
+        top_output,
         output,
+        emit,
+        with_authors,
+        author,
+        only_changed_functions,
+        coverage,
+        order,
+        redactor,
         ..
     } = opts;
     let mut snapshot = build_snapshot_via_db(
@@ -515,7 +1423,43 @@ fn handle_snapshot_mode(
     )
     .context("failed to build enriched snapshot")?;
 
+    // Splice the re-analyzed files into the parent's snapshot, then redo the
+    // repo-relative scores (activity risk, percentiles, drivers) over the result
+    if let Some(IncrementalBase {
+        parent,
+        changed_files,
+        ..
+    }) = incremental
+    {
+        let spliced = Snapshot::splice(parent, snapshot, &changed_files)
+            .context("failed to splice incremental snapshot")?;
+        snapshot = snapshot::SnapshotEnricher::new(spliced)
+            .with_trivial_cutoff(resolved_config.trivial)
+            .enrich(
+                Some(&resolved_config.scoring_weights),
+                resolved_config.driver_threshold_percentile,
+            )
+            .build();
+    }
+
+    if let Some(coverage) = &coverage {
+        let matched = snapshot.populate_coverage(coverage, repo_root);
+        if matched == 0 && !snapshot.functions.is_empty() && diagnostics::enabled(Level::Warn) {
+            eprintln!(
+                "warning: the coverage report matched none of the {} analyzed function(s); \
+                 check that its SF: paths point at the analyzed files",
+                snapshot.functions.len()
+            );
+        }
+    }
     snapshot.populate_patterns(&resolved_config.pattern_thresholds);
+    if check {
+        // The snapshot counterpart of the blocking critical-introduction policy
+        if print_snapshot_check_summary(&snapshot) > 0 {
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
     if explain_patterns {
         snapshot.populate_pattern_details(&resolved_config.pattern_thresholds);
     }
@@ -552,7 +1496,9 @@ fn handle_snapshot_mode(
             hotspots_core::trainer::ModelClass::Ridge => "Ridge",
             hotspots_core::trainer::ModelClass::RandomForest => "RandomForest",
         };
-        eprintln!("hotspots: using trained ranker (model class: {label})");
+        if diagnostics::enabled(Level::Info) {
+            eprintln!("hotspots: using trained ranker (model class: {label})");
+        }
     }
 
     // Re-run quadrant assignment now that activity_risk reflects trained RF scores.
@@ -561,6 +1507,8 @@ fn handle_snapshot_mode(
     if ranker_applied {
         snapshot.compute_quadrants(resolved_config.driver_threshold_percentile, true);
     }
+    snapshot.populate_priority(&resolved_config.priority_weights);
+    snapshot.populate_refactor_leverage();
 
     // Populate explanation phrases for CRITICAL/HIGH functions when --explain is set
     // and a trained ranker was applied. Percentiles are computed over all functions
@@ -569,8 +1517,6 @@ fn handle_snapshot_mode(
         populate_explanations(&mut snapshot);
     }
 
-    let total_function_count = snapshot.functions.len();
-
     // Suppression gate: check if the activity ranker is working on this repo.
     // Run on the full sorted snapshot (before top-N truncation) so calibration
     // sees a representative top-50.
@@ -583,10 +1529,12 @@ fn handle_snapshot_mode(
         } = &gate_verdict
         {
             if ranker_applied {
-                eprintln!(
-                    "hotspots: activity ranker P@10={p_at_10:.2} (< {threshold:.2}); using trained ranker instead."
-                );
-            } else {
+                if diagnostics::enabled(Level::Info) {
+                    eprintln!(
+                        "hotspots: activity ranker P@10={p_at_10:.2} (< {threshold:.2}); using trained ranker instead."
+                    );
+                }
+            } else if diagnostics::enabled(Level::Warn) {
                 eprintln!(
                     "hotspots: warning: activity ranker P@10={p_at_10:.2} < {threshold:.2} — rankings may be misleading."
                 );
@@ -595,48 +1543,99 @@ fn handle_snapshot_mode(
         }
     }
 
-    apply_top_n(&mut snapshot, format, explain, level, top);
+    // After persisting and the gate, so both see the whole repo; percentiles,
+    // quadrants, and the summary were already computed over every function
+    if let Some(author) = &author {
+        let author_lines = hotspots_core::git::extract_file_author_lines(repo_root)
+            .context("failed to mine authorship for --author")?;
+        hotspots_core::aggregates::retain_functions_owned_by(
+            &mut snapshot.functions,
+            author,
+            &author_lines,
+            repo_root,
+        );
+    }
+    // The total is taken before --only-changed-functions, which narrows what
+    // is shown, not what the report covers
+    let total_function_count = snapshot.functions.len();
+    if only_changed_functions {
+        retain_changed_functions(&mut snapshot, repo_root)?;
+    }
+    let candidate_function_count = snapshot.functions.len();
+
+    // `--emit` never renders text, so its default top-N is that of the file formats
+    let top_n_format = emit.first().map_or(format, |target| target.format);
+    apply_top_n(&mut snapshot, top_n_format, explain, level, top);
+    if with_authors {
+        // Per-function `git log -L`; only pay for the functions being shown
+        snapshot.populate_function_age(repo_root);
+    }
+    if let Some(order) = order {
+        sort_snapshot_functions(&mut snapshot.functions, order);
+    }
 
-    emit_snapshot_output(
-        &mut snapshot,
-        SnapshotOutputOpts {
-            format,
-            explain,
-            level,
-            top,
-            total_function_count,
-            output,
-            co_change_window_days: resolved_config.co_change_window_days,
-            co_change_min_count: resolved_config.co_change_min_count,
-            all_functions,
-            include_models,
-            source_url: source_url.clone(),
-            risk_thresholds: hotspots_core::risk::RiskThresholds {
-                moderate: resolved_config.moderate_threshold,
-                high: resolved_config.high_threshold,
-                critical: resolved_config.critical_threshold,
-            },
+    let output_opts = SnapshotOutputOpts {
+        format,
+        explain,
+        level,
+        top,
+        top_output,
+        total_function_count,
+        candidate_function_count,
+        output,
+        co_change: resolved_config.co_change_options(),
+        file_risk_weights: resolved_config.file_risk_weights,
+        module_zones: resolved_config.module_zones,
+        with_authors,
+        all_functions,
+        include_models,
+        source_url: source_url.clone(),
+        html_theme,
+        risk_thresholds: hotspots_core::risk::RiskThresholds {
+            moderate: resolved_config.moderate_threshold,
+            high: resolved_config.high_threshold,
+            critical: resolved_config.critical_threshold,
         },
-        repo_root,
-        path,
-    )
+        custom_patterns: resolved_config.pattern_thresholds.custom.clone(),
+        redactor,
+    };
+    if emit.is_empty() {
+        return emit_snapshot_output(&mut snapshot, output_opts, repo_root, path);
+    }
+    // One snapshot, rendered once per artifact; each render gets its own copy
+    // because JSON and JSONL output may redact or truncate it in place
+    for target in emit {
+        let opts = SnapshotOutputOpts {
+            format: target.format,
+            output: Some(target.path),
+            ..output_opts.clone()
+        };
+        emit_snapshot_output(&mut snapshot.clone(), opts, repo_root, path)?;
+    }
+    Ok(())
 }
 
 fn handle_delta_mode(
     repo_root: &Path,
     resolved_config: &hotspots_core::ResolvedConfig,
     reports: Vec<hotspots_core::FunctionRiskReport>,
+    decisions: Option<&delta::DecisionMap>,
     pr_context: hotspots_core::git::PrContext,
     opts: ModeOutputOptions,
 ) -> anyhow::Result<()> {
     let ModeOutputOptions {
         format,
         policy,
+        check,
+        top,
         output,
         source_url,
+        html_theme,
         touch_mode,
         callgraph_skip_above,
         skip_touch_metrics,
+        ignore_version_skew,
+        baseline_branch,
         ..
     } = opts;
     let snapshot = build_enriched_snapshot(
@@ -649,30 +1648,182 @@ fn handle_delta_mode(
     )
     .context("failed to build enriched snapshot")?;
 
-    let delta_val = if pr_context.is_pr {
-        compute_pr_delta(repo_root, &snapshot)?
+    // With --baseline-branch the base is analyzed on the spot, so no
+    // persisted snapshot history is needed
+    let baseline_sha = baseline_branch
+        .map(|branch| git::merge_base_with(repo_root, &branch))
+        .transpose()?;
+    let parent = if let Some(sha) = &baseline_sha {
+        if diagnostics::enabled(Level::Info) {
+            eprintln!("info: analyzing merge-base {sha} as the delta baseline");
+        }
+        Some(analyze_at_ref(repo_root, sha, resolved_config)?)
+    } else if pr_context.is_pr {
+        load_pr_base(repo_root, &snapshot)?
     } else {
-        delta::compute_delta(repo_root, &snapshot)?
+        match snapshot.commit.parents.first() {
+            Some(sha) => delta::load_parent_snapshot(repo_root, sha)?,
+            None => None,
+        }
     };
+    if let Some(parent) = parent.as_ref().filter(|_| !ignore_version_skew) {
+        warn_version_skew("delta", [parent, &snapshot]);
+    }
+    let mut delta_val =
+        delta::Delta::new_with_weights(&snapshot, parent.as_ref(), &resolved_config.lrs_weights())?;
+    // The baseline is the merge-base, not HEAD's first parent
+    if let Some(sha) = &baseline_sha {
+        delta_val.commit.parent = sha.clone();
+    }
+    if let Some(decisions) = decisions {
+        let base = baseline_sha.or_else(|| {
+            pr_context
+                .is_pr
+                .then(git::resolve_merge_base_auto)
+                .flatten()
+        });
+        annotate_new_cc(repo_root, &mut delta_val, decisions, base);
+    }
+
+    if check {
+        // Skip co-change aggregates; only the policy verdict matters here, and
+        // of the aggregates only module instability feeds a policy
+        let policy_results = if policy {
+            let zones = &resolved_config.module_zones;
+            let current_modules = snapshot_modules(&snapshot, repo_root, zones);
+            let prev_modules = parent
+                .as_ref()
+                .map(|p| snapshot_modules(p, repo_root, zones))
+                .unwrap_or_default();
+            delta_val.aggregates = Some(hotspots_core::aggregates::compute_delta_aggregates(
+                &delta_val,
+                &[],
+                &[],
+                &current_modules,
+                &prev_modules,
+            ));
+            hotspots_core::policy::evaluate_policies(
+                &delta_val,
+                &snapshot,
+                repo_root,
+                resolved_config,
+            )
+            .context("failed to evaluate policies")?
+        } else {
+            None
+        };
+        print_delta_check_summary(&delta_val, policy_results.as_ref());
+        if policy_results.is_some_and(|p| p.has_blocking_failures()) {
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
 
-    let delta_with_extras = enrich_delta(repo_root, resolved_config, &snapshot, delta_val, policy)?;
+    let delta_with_extras = enrich_delta(
+        repo_root,
+        resolved_config,
+        &snapshot,
+        parent.as_ref(),
+        delta_val,
+        policy,
+    )?;
 
     if emit_delta_output(
         &delta_with_extras,
         format,
         policy,
+        top,
         output,
         source_url.as_deref(),
+        &html_theme,
     )? {
         std::process::exit(1);
     }
     Ok(())
 }
 
+/// Fill in `new_cc` from the lines the analyzed commit adds over the delta's
+/// base commit (the merge-base `base` when given, else the parent); skipped
+/// with a warning if git fails
+fn annotate_new_cc(
+    repo_root: &Path,
+    delta_val: &mut Delta,
+    decisions: &delta::DecisionMap,
+    base: Option<String>,
+) {
+    if delta_val.baseline {
+        return;
+    }
+    let base = base.unwrap_or_else(|| delta_val.commit.parent.clone());
+    match git::added_lines_between(repo_root, &base, &delta_val.commit.sha) {
+        Ok(added) => delta_val.annotate_new_cc(decisions, &added, repo_root),
+        Err(e) => {
+            if diagnostics::enabled(Level::Warn) {
+                eprintln!("warning: skipping max-new-cc, failed to diff against {base}: {e}");
+            }
+        }
+    }
+}
+
+/// One-line `--check` summary for snapshot mode; returns the number of
+/// unsuppressed critical functions, each a violation
+fn print_snapshot_check_summary(snapshot: &Snapshot) -> usize {
+    use hotspots_core::risk::RiskBand;
+
+    let count = |band: RiskBand| snapshot.functions.iter().filter(|f| f.band == band).count();
+    let violations = snapshot
+        .functions
+        .iter()
+        .filter(|f| f.band == RiskBand::Critical && f.suppression_reason.is_none())
+        .count();
+    let plural = |n: usize| if n == 1 { "" } else { "s" };
+    println!(
+        "check: {} function{} ({} critical, {} high); {} violation{}",
+        snapshot.functions.len(),
+        plural(snapshot.functions.len()),
+        count(RiskBand::Critical),
+        count(RiskBand::High),
+        violations,
+        plural(violations)
+    );
+    violations
+}
+
+/// One-line `--check` summary for delta mode, with policy counts when evaluated
+fn print_delta_check_summary(
+    delta_val: &Delta,
+    policy_results: Option<&hotspots_core::policy::PolicyResults>,
+) {
+    use hotspots_core::delta::FunctionStatus;
+
+    let count = |status: FunctionStatus| {
+        delta_val
+            .deltas
+            .iter()
+            .filter(|d| d.status == status)
+            .count()
+    };
+    let mut summary = format!(
+        "check: {} new, {} modified, {} deleted functions",
+        count(FunctionStatus::New),
+        count(FunctionStatus::Modified),
+        count(FunctionStatus::Deleted)
+    );
+    if let Some(results) = policy_results {
+        summary.push_str(&format!(
+            "; policy: {} blocking, {} warnings",
+            results.failed.len(),
+            results.warnings.len()
+        ));
+    }
+    println!("{summary}");
+}
+
 fn enrich_delta(
     repo_root: &Path,
     resolved_config: &hotspots_core::ResolvedConfig,
     snapshot: &Snapshot,
+    parent: Option<&Snapshot>,
     delta_val: delta::Delta,
     policy: bool,
 ) -> anyhow::Result<delta::Delta> {
@@ -686,34 +1837,42 @@ fn enrich_delta(
     unique_files.sort();
     let files_as_str: Vec<&str> = unique_files.iter().map(|s| s.as_str()).collect();
     let import_edges = hotspots_core::imports::resolve_file_deps(&files_as_str, repo_root);
-    let mut current_co_change = hotspots_core::git::extract_co_change_pairs(
-        repo_root,
-        resolved_config.co_change_window_days,
-        resolved_config.co_change_min_count,
-    )
-    .unwrap_or_default();
+    let mut current_co_change =
+        hotspots_core::git::extract_co_change_pairs(repo_root, resolved_config.co_change_options())
+            .unwrap_or_default();
     hotspots_core::aggregates::annotate_static_deps(
         &mut current_co_change,
         &import_edges,
         repo_root,
     );
 
-    let parent_sha = snapshot.commit.parents.first().cloned();
-    let prev_co_change: Vec<hotspots_core::git::CoChangePair> = parent_sha
-        .as_deref()
-        .and_then(|sha| {
-            hotspots_core::delta::load_parent_snapshot(repo_root, sha)
-                .ok()
-                .flatten()
-        })
-        .and_then(|s| s.aggregates)
-        .map(|a| a.co_change)
+    let current_modules = hotspots_core::aggregates::compute_module_instability_from_edges(
+        &snapshot.functions,
+        &import_edges,
+        repo_root,
+        &resolved_config.module_zones,
+    );
+
+    let prev_co_change: Vec<hotspots_core::git::CoChangePair> = parent
+        .and_then(|s| s.aggregates.as_ref())
+        .map(|a| a.co_change.clone())
+        .unwrap_or_default();
+    let prev_modules = parent
+        .map(|p| snapshot_modules(p, repo_root, &resolved_config.module_zones))
         .unwrap_or_default();
 
-    let mut enriched = delta_val.clone();
+    let mut enriched = delta_val;
+    enriched.aggregates = Some(hotspots_core::aggregates::compute_delta_aggregates(
+        &enriched,
+        &current_co_change,
+        &prev_co_change,
+        &current_modules,
+        &prev_modules,
+    ));
+    // After aggregates, which the instability-regression policy reads
     if policy {
         let policy_results = hotspots_core::policy::evaluate_policies(
-            &delta_val,
+            &enriched,
             snapshot,
             repo_root,
             resolved_config,
@@ -723,14 +1882,32 @@ fn enrich_delta(
             enriched.policy = Some(results);
         }
     }
-    enriched.aggregates = Some(hotspots_core::aggregates::compute_delta_aggregates(
-        &delta_val,
-        &current_co_change,
-        &prev_co_change,
-    ));
     Ok(enriched)
 }
 
+/// Module instability of `snapshot`'s files
+///
+/// Persisted snapshots rarely carry module aggregates; those fall back to the
+/// snapshot's files, with imports resolved from the working tree.
+pub(crate) fn snapshot_modules(
+    snapshot: &Snapshot,
+    repo_root: &Path,
+    zones: &hotspots_core::aggregates::ModuleZoneThresholds,
+) -> Vec<hotspots_core::aggregates::ModuleInstability> {
+    match snapshot
+        .aggregates
+        .as_ref()
+        .filter(|a| !a.modules.is_empty())
+    {
+        Some(a) => a.modules.clone(),
+        None => hotspots_core::aggregates::compute_module_instability(
+            &snapshot.functions,
+            repo_root,
+            zones,
+        ),
+    }
+}
+
 fn handle_models_mode(
     path: &Path,
     repo_root: &Path,
@@ -770,26 +1947,39 @@ fn handle_models_mode(
                 hotspots_core::models::render_model_risk_json(&model_map)?
             );
         }
-        OutputFormat::Html | OutputFormat::Jsonl | OutputFormat::Sarif => {
+        OutputFormat::Html
+        | OutputFormat::Jsonl
+        | OutputFormat::Sarif
+        | OutputFormat::Canonical => {
             unreachable!("validated by validate_analyze_flags")
         }
     }
     Ok(())
 }
 
+#[derive(Clone)]
 struct SnapshotOutputOpts {
     format: OutputFormat,
     explain: bool,
     level: Option<OutputLevel>,
     top: Option<usize>,
+    top_output: Option<usize>,
     total_function_count: usize,
+    /// Functions left to pick the top N from; fewer than the total with
+    /// `--only-changed-functions`
+    candidate_function_count: usize,
     output: Option<PathBuf>,
-    co_change_window_days: u64,
-    co_change_min_count: usize,
+    co_change: hotspots_core::git::CoChangeOptions,
+    file_risk_weights: hotspots_core::aggregates::FileRiskWeights,
+    module_zones: hotspots_core::aggregates::ModuleZoneThresholds,
+    with_authors: bool,
     all_functions: bool,
     include_models: bool,
     source_url: Option<String>,
+    html_theme: hotspots_core::html::HtmlTheme,
     risk_thresholds: hotspots_core::risk::RiskThresholds,
+    custom_patterns: Vec<hotspots_core::patterns::CustomPattern>,
+    redactor: Option<Redactor>,
 }
 
 fn emit_snapshot_output(
@@ -800,10 +1990,16 @@ fn emit_snapshot_output(
 ) -> anyhow::Result<()> {
     match opts.format {
         OutputFormat::Json => emit_json_output(snapshot, repo_root, analysis_path, opts),
-        OutputFormat::Jsonl => emit_jsonl_output(snapshot),
+        OutputFormat::Jsonl => {
+            if let Some(n) = opts.top_output {
+                truncate_output_functions(&mut snapshot.functions, n);
+            }
+            emit_jsonl_output(snapshot, opts.redactor.as_ref(), opts.output)
+        }
         OutputFormat::Text => emit_text_output(snapshot, repo_root, opts),
         OutputFormat::Html => emit_html_output(snapshot, repo_root, analysis_path, opts),
         OutputFormat::Sarif => emit_sarif_output(snapshot, repo_root, opts),
+        OutputFormat::Canonical => emit_canonical_output(snapshot, repo_root, opts.output),
     }
 }
 
@@ -814,22 +2010,45 @@ fn emit_json_output(
     opts: SnapshotOutputOpts,
 ) -> anyhow::Result<()> {
     let SnapshotOutputOpts {
+        explain,
+        total_function_count,
         all_functions,
         include_models,
-        co_change_window_days,
-        co_change_min_count,
+        co_change,
+        file_risk_weights,
+        module_zones,
+        with_authors,
         output,
+        redactor,
+        top_output,
         ..
     } = opts;
-    let aggregates = hotspots_core::aggregates::compute_snapshot_aggregates_with_models(
+    let mut aggregates = hotspots_core::aggregates::compute_snapshot_aggregates_with_models(
         snapshot,
         repo_root,
-        co_change_window_days,
-        co_change_min_count,
+        co_change,
+        &file_risk_weights,
+        &module_zones,
         include_models.then_some(analysis_path),
     );
-    if all_functions {
+    if with_authors {
+        annotate_authorship(&mut aggregates, repo_root);
+    }
+    if explain {
+        let explain_output = hotspots_core::aggregates::compute_explain_output(
+            snapshot,
+            total_function_count,
+            repo_root,
+        );
+        write_json_explain(&explain_output, output)
+    } else if all_functions {
         snapshot.aggregates = Some(aggregates);
+        if let Some(n) = top_output {
+            truncate_output_functions(&mut snapshot.functions, n);
+        }
+        if let Some(redactor) = &redactor {
+            redactor.redact_snapshot(snapshot);
+        }
         write_json_snapshot(snapshot, output)
     } else {
         let agent_output = hotspots_core::aggregates::compute_agent_snapshot_output(
@@ -841,7 +2060,40 @@ fn emit_json_output(
     }
 }
 
-fn emit_jsonl_output(snapshot: &mut Snapshot) -> anyhow::Result<()> {
+/// Add bus-factor / knowledge-risk fields to file risk (`--with-authors`).
+/// A git failure degrades to a warning; the rest of the report is still useful.
+fn annotate_authorship(
+    aggregates: &mut hotspots_core::aggregates::SnapshotAggregates,
+    repo_root: &Path,
+) {
+    if let Err(e) =
+        hotspots_core::aggregates::annotate_file_authorship(&mut aggregates.file_risk, repo_root)
+    {
+        if diagnostics::enabled(Level::Warn) {
+            eprintln!("warning: skipping --with-authors: {e:#}");
+        }
+    }
+}
+
+fn emit_jsonl_output(
+    snapshot: &mut Snapshot,
+    redactor: Option<&Redactor>,
+    output: Option<PathBuf>,
+) -> anyhow::Result<()> {
+    if let Some(redactor) = redactor {
+        redactor.redact_snapshot(snapshot);
+    }
+    if let Some(output_path) = output {
+        write_snapshot_json_file(&output_path, |out| {
+            snapshot
+                .write_jsonl_to(out)
+                .context("failed to write snapshot JSONL")
+        })?;
+        if diagnostics::enabled(Level::Info) {
+            eprintln!("JSONL report written to: {}", output_path.display());
+        }
+        return Ok(());
+    }
     let stdout = std::io::stdout();
     let mut out = std::io::BufWriter::new(stdout.lock());
     snapshot
@@ -859,23 +2111,35 @@ fn emit_text_output(
         explain,
         top,
         total_function_count,
-        co_change_window_days,
-        co_change_min_count,
+        candidate_function_count,
+        co_change,
+        file_risk_weights,
+        module_zones,
+        with_authors,
         ..
     } = opts;
-    let aggregates = hotspots_core::aggregates::compute_snapshot_aggregates(
+    let mut aggregates = hotspots_core::aggregates::compute_snapshot_aggregates(
         snapshot,
         repo_root,
-        co_change_window_days,
-        co_change_min_count,
+        co_change,
+        &file_risk_weights,
+        &module_zones,
     );
+    if with_authors {
+        annotate_authorship(&mut aggregates, repo_root);
+    }
     if level == Some(OutputLevel::File) {
         explain::print_file_risk_output(&aggregates.file_risk, top)?;
     } else if level == Some(OutputLevel::Module) {
         explain::print_module_output(&aggregates.modules, top)?;
     } else if explain {
         let color = std::io::stdout().is_terminal() && std::env::var_os("NO_COLOR").is_none();
-        explain::print_explain_output(snapshot, total_function_count, color)?;
+        explain::print_explain_output(
+            snapshot,
+            total_function_count,
+            candidate_function_count,
+            color,
+        )?;
     } else {
         anyhow::bail!(
             "text format without --explain is not supported for snapshot mode (use --format json or add --explain)"
@@ -891,36 +2155,65 @@ fn emit_html_output(
     opts: SnapshotOutputOpts,
 ) -> anyhow::Result<()> {
     let SnapshotOutputOpts {
-        co_change_window_days,
-        co_change_min_count,
+        co_change,
+        file_risk_weights,
+        module_zones,
+        with_authors,
         include_models,
         source_url,
+        html_theme,
         risk_thresholds,
+        custom_patterns,
         output,
         ..
     } = opts;
-    let aggregates = hotspots_core::aggregates::compute_snapshot_aggregates_with_models(
+    let mut aggregates = hotspots_core::aggregates::compute_snapshot_aggregates_with_models(
         snapshot,
         repo_root,
-        co_change_window_days,
-        co_change_min_count,
+        co_change,
+        &file_risk_weights,
+        &module_zones,
         include_models.then_some(analysis_path),
     );
+    if with_authors {
+        annotate_authorship(&mut aggregates, repo_root);
+    }
     snapshot.aggregates = Some(aggregates);
-    let history: Vec<_> = hotspots_core::trends::load_snapshot_window(repo_root, 30)
-        .unwrap_or_default()
-        .into_iter()
-        .filter_map(|s| s.summary.map(|sum| (s.commit, sum)))
-        .collect();
-    let html = hotspots_core::html::render_html_snapshot(
+    let history =
+        snapshot::load_history(repo_root, snapshot::DEFAULT_HISTORY_WINDOW).unwrap_or_default();
+    // Merge per-function deltas in when the parent commit has a persisted snapshot
+    let parent = snapshot
+        .commit
+        .parents
+        .first()
+        .and_then(|sha| delta::load_parent_snapshot(repo_root, sha).ok().flatten());
+    let html = hotspots_core::html::render_html_snapshot_with_delta(
         snapshot,
+        parent.as_ref(),
         &history,
         source_url.as_deref(),
         &risk_thresholds,
-    );
-    let output_path = output.unwrap_or_else(|| PathBuf::from(".hotspots/report.html"));
+        &custom_patterns,
+        &html_theme,
+    )
+    .unwrap_or_else(|e| {
+        if diagnostics::enabled(Level::Warn) {
+            eprintln!("Warning: skipping inline deltas in HTML report: {e}");
+        }
+        hotspots_core::html::render_html_snapshot(
+            snapshot,
+            &history,
+            source_url.as_deref(),
+            &risk_thresholds,
+            &custom_patterns,
+            &html_theme,
+        )
+    });
+    let output_path = output.unwrap_or_else(|| default_report_path("report.html"));
     write_html_report(&output_path, &html)?;
-    eprintln!("HTML report written to: {}", output_path.display());
+    if diagnostics::enabled(Level::Info) {
+        eprintln!("HTML report written to: {}", output_path.display());
+    }
     Ok(())
 }
 
@@ -937,13 +2230,152 @@ fn emit_sarif_output(
         }
         std::fs::write(&output_path, &sarif)
             .with_context(|| format!("failed to write SARIF to {}", output_path.display()))?;
-        eprintln!("SARIF report written to: {}", output_path.display());
+        if diagnostics::enabled(Level::Info) {
+            eprintln!("SARIF report written to: {}", output_path.display());
+        }
     } else {
         println!("{sarif}");
     }
     Ok(())
 }
 
+fn emit_canonical_output(
+    snapshot: &Snapshot,
+    repo_root: &Path,
+    output: Option<PathBuf>,
+) -> anyhow::Result<()> {
+    let text = snapshot.to_canonical_text(repo_root);
+    if let Some(output_path) = output {
+        if let Some(parent) = output_path.parent() {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("failed to create directory: {}", parent.display()))?;
+        }
+        std::fs::write(&output_path, &text).with_context(|| {
+            format!(
+                "failed to write canonical text to {}",
+                output_path.display()
+            )
+        })?;
+        if diagnostics::enabled(Level::Info) {
+            eprintln!("Canonical report written to: {}", output_path.display());
+        }
+    } else {
+        print!("{text}");
+    }
+    Ok(())
+}
+
+/// Keep only functions touched since the snapshot's parent commit
+/// (`--only-changed-functions`). A root commit has no parent, so every
+/// function counts as changed.
+fn retain_changed_functions(snapshot: &mut Snapshot, repo_root: &Path) -> anyhow::Result<()> {
+    let Some(parent) = snapshot.commit.parents.first() else {
+        return Ok(());
+    };
+    let changed = hotspots_core::git::changed_lines_since(repo_root, parent)
+        .context("failed to diff against the parent commit for --only-changed-functions")?;
+    hotspots_core::aggregates::retain_changed_functions(
+        &mut snapshot.functions,
+        &changed,
+        repo_root,
+    );
+    Ok(())
+}
+
+/// Reorder functions for presentation (`--order`), after top-N selection.
+fn sort_snapshot_functions(
+    functions: &mut [hotspots_core::snapshot::FunctionSnapshot],
+    order: FunctionOrder,
+) {
+    match order {
+        FunctionOrder::Source => functions.sort_by(|a, b| {
+            a.file
+                .cmp(&b.file)
+                .then(a.line.cmp(&b.line))
+                .then_with(|| a.function_id.cmp(&b.function_id))
+        }),
+        FunctionOrder::Id => functions.sort_by(|a, b| a.function_id.cmp(&b.function_id)),
+        FunctionOrder::CcDensity => functions.sort_by(|a, b| {
+            b.metrics
+                .cc_density()
+                .total_cmp(&a.metrics.cc_density())
+                .then_with(|| a.function_id.cmp(&b.function_id))
+        }),
+        FunctionOrder::Priority => functions.sort_by(|a, b| {
+            let priority =
+                |f: &hotspots_core::snapshot::FunctionSnapshot| f.priority_score.unwrap_or(f.lrs);
+            priority(b)
+                .total_cmp(&priority(a))
+                .then_with(|| a.function_id.cmp(&b.function_id))
+        }),
+        FunctionOrder::Leverage => functions.sort_by(|a, b| {
+            let leverage = |f: &hotspots_core::snapshot::FunctionSnapshot| {
+                f.refactor_leverage.unwrap_or(f.lrs)
+            };
+            leverage(b)
+                .total_cmp(&leverage(a))
+                .then_with(|| a.function_id.cmp(&b.function_id))
+        }),
+    }
+}
+
+/// Reorder default-mode reports for presentation (`--order`), after top-N selection.
+fn sort_reports(reports: &mut [hotspots_core::FunctionRiskReport], order: FunctionOrder) {
+    match order {
+        FunctionOrder::Source => reports.sort_by(|a, b| {
+            a.file
+                .cmp(&b.file)
+                .then(a.line.cmp(&b.line))
+                .then_with(|| a.function.cmp(&b.function))
+        }),
+        FunctionOrder::Id => reports.sort_by(|a, b| {
+            a.file
+                .cmp(&b.file)
+                .then_with(|| a.function.cmp(&b.function))
+        }),
+        FunctionOrder::CcDensity => reports.sort_by(|a, b| {
+            b.metrics
+                .cc_density()
+                .total_cmp(&a.metrics.cc_density())
+                .then_with(|| a.file.cmp(&b.file))
+                .then_with(|| a.function.cmp(&b.function))
+        }),
+        // Snapshot-only (see validate_analyze_flags); reports keep analysis order
+        FunctionOrder::Priority | FunctionOrder::Leverage => {}
+    }
+}
+
+/// Rank default-mode reports by `--sort`, before top-N truncation.
+///
+/// Metrics sort largest first; ties fall back to the LRS, file, line, function
+/// order that analysis uses, so the result stays deterministic.
+fn rank_reports(reports: &mut [hotspots_core::FunctionRiskReport], key: SortKey) {
+    let metric = |r: &hotspots_core::FunctionRiskReport| match key {
+        SortKey::Cc => r.metrics.cc,
+        SortKey::Nd => r.metrics.nd,
+        SortKey::Fo => r.metrics.fo,
+        SortKey::Ns => r.metrics.ns,
+        SortKey::Loc => r.metrics.loc,
+        SortKey::Lrs | SortKey::File => 0,
+    };
+    reports.sort_by(|a, b| {
+        let by_key = match key {
+            SortKey::File => a.file.cmp(&b.file),
+            _ => metric(b).cmp(&metric(a)),
+        };
+        let by_lrs = if key == SortKey::File {
+            std::cmp::Ordering::Equal
+        } else {
+            b.lrs.total_cmp(&a.lrs)
+        };
+        by_key
+            .then(by_lrs)
+            .then_with(|| a.file.cmp(&b.file))
+            .then(a.line.cmp(&b.line))
+            .then_with(|| a.function.cmp(&b.function))
+    });
+}
+
 fn apply_top_n(
     snapshot: &mut Snapshot,
     format: OutputFormat,
@@ -953,7 +2385,8 @@ fn apply_top_n(
 ) {
     let is_aggregate_level = level == Some(OutputLevel::File) || level == Some(OutputLevel::Module);
     let is_text = matches!(format, OutputFormat::Text);
-    if !is_aggregate_level && (top.is_some() || (is_text && explain)) {
+    let is_explain = explain && matches!(format, OutputFormat::Text | OutputFormat::Json);
+    if !is_aggregate_level && (top.is_some() || is_explain) {
         snapshot.functions.sort_by(|a, b| {
             let a_score = a.activity_risk.unwrap_or(a.lrs);
             let b_score = b.activity_risk.unwrap_or(b.lrs);
@@ -961,11 +2394,11 @@ fn apply_top_n(
                 .partial_cmp(&a_score)
                 .unwrap_or(std::cmp::Ordering::Equal)
         });
-        // 0 = show all; None in text+explain defaults to 20
+        // 0 = show all; None with --explain (text or JSON) defaults to 20
         let limit = match top {
             Some(0) => usize::MAX,
             Some(n) => n,
-            None if is_text => 20,
+            None if is_text || is_explain => 20,
             None => usize::MAX,
         };
         if limit != usize::MAX {
@@ -974,6 +2407,35 @@ fn apply_top_n(
     }
 }
 
+/// Keep only the `n` highest-risk functions for output (`--top-output`; 0 keeps
+/// all), preserving their current order. Callers compute aggregates first so
+/// they still reflect every function.
+fn truncate_output_functions(
+    functions: &mut Vec<hotspots_core::snapshot::FunctionSnapshot>,
+    n: usize,
+) {
+    if n == 0 || functions.len() <= n {
+        return;
+    }
+    let score = |f: &hotspots_core::snapshot::FunctionSnapshot| f.activity_risk.unwrap_or(f.lrs);
+    let mut scores: Vec<f64> = functions.iter().map(score).collect();
+    scores.sort_by(|a, b| b.partial_cmp(a).unwrap_or(std::cmp::Ordering::Equal));
+    let cutoff = scores[n - 1];
+    // Ties at the cutoff are kept in current order until N functions are selected
+    let mut ties = n - scores.iter().take_while(|&&s| s > cutoff).count();
+    functions.retain(|f| {
+        let s = score(f);
+        if s > cutoff {
+            true
+        } else if s == cutoff && ties > 0 {
+            ties -= 1;
+            true
+        } else {
+            false
+        }
+    });
+}
+
 /// If `.hotspots/ranker.json` exists, overwrite each function's `activity_risk`
 /// with the trained model's score. Returns the model class that was applied.
 /// Silent no-op (returns `None`) if the model is absent or fails to load.
@@ -988,7 +2450,9 @@ fn apply_trained_ranker(
     let model = match hotspots_core::trainer::RankerModel::load(&model_path) {
         Ok(m) => m,
         Err(e) => {
-            eprintln!("hotspots: warning: failed to load ranker.json: {e}");
+            if diagnostics::enabled(Level::Warn) {
+                eprintln!("hotspots: warning: failed to load ranker.json: {e}");
+            }
             return None;
         }
     };
@@ -1078,7 +2542,9 @@ fn write_json_snapshot(snapshot: &Snapshot, output: Option<PathBuf>) -> anyhow::
                 .write_json_to(out)
                 .context("failed to write snapshot JSON")
         })?;
-        eprintln!("JSON report written to: {}", output_path.display());
+        if diagnostics::enabled(Level::Info) {
+            eprintln!("JSON report written to: {}", output_path.display());
+        }
     } else {
         let stdout = std::io::stdout();
         let mut out = std::io::BufWriter::new(stdout.lock());
@@ -1099,7 +2565,9 @@ fn write_json_agent(
                 .write_json_to(out)
                 .context("failed to write agent snapshot JSON")
         })?;
-        eprintln!("JSON report written to: {}", output_path.display());
+        if diagnostics::enabled(Level::Info) {
+            eprintln!("JSON report written to: {}", output_path.display());
+        }
     } else {
         let stdout = std::io::stdout();
         let mut out = std::io::BufWriter::new(stdout.lock());
@@ -1110,13 +2578,38 @@ fn write_json_agent(
     Ok(())
 }
 
+fn write_json_explain(
+    explain_output: &hotspots_core::aggregates::ExplainOutput,
+    output: Option<PathBuf>,
+) -> anyhow::Result<()> {
+    if let Some(output_path) = output {
+        write_snapshot_json_file(&output_path, |out| {
+            explain_output
+                .write_json_to(out)
+                .context("failed to write explain JSON")
+        })?;
+        if diagnostics::enabled(Level::Info) {
+            eprintln!("JSON report written to: {}", output_path.display());
+        }
+    } else {
+        let stdout = std::io::stdout();
+        let mut out = std::io::BufWriter::new(stdout.lock());
+        explain_output
+            .write_json_to(&mut out)
+            .context("failed to write explain JSON")?;
+    }
+    Ok(())
+}
+
 /// Returns true if there are blocking policy failures (caller should exit non-zero).
 fn emit_delta_output(
     delta_val: &Delta,
     format: OutputFormat,
     with_policy: bool,
+    top: Option<usize>,
     output: Option<PathBuf>,
     source_url: Option<&str>,
+    html_theme: &hotspots_core::html::HtmlTheme,
 ) -> anyhow::Result<bool> {
     let has_blocking_failures = delta_val
         .policy
@@ -1132,31 +2625,38 @@ fn emit_delta_output(
             anyhow::bail!("JSONL format is not supported for delta mode (use --mode snapshot)");
         }
         OutputFormat::Text => {
-            emit_delta_text(delta_val, with_policy)?;
+            emit_delta_text(delta_val, with_policy, top)?;
         }
         OutputFormat::Html => {
-            emit_delta_html(delta_val, source_url, output)?;
+            emit_delta_html(delta_val, source_url, html_theme, output)?;
         }
         OutputFormat::Sarif => {
             anyhow::bail!("SARIF format is not supported for delta mode (use --mode snapshot)");
         }
+        OutputFormat::Canonical => {
+            anyhow::bail!("canonical format is not supported for delta mode (use --mode snapshot)");
+        }
     }
 
     Ok(has_blocking_failures)
 }
 
-fn emit_delta_text(delta_val: &Delta, with_policy: bool) -> anyhow::Result<()> {
+/// Policy sections with `--policy`; otherwise change counts and the top ΔLRS regressions
+fn emit_delta_text(delta_val: &Delta, with_policy: bool, top: Option<usize>) -> anyhow::Result<()> {
     if !with_policy {
-        anyhow::bail!(
-            "text format is not supported for delta mode without --policy (use --format json)"
+        print!(
+            "{}",
+            crate::output::delta::render_delta_text_output(delta_val, top)?
         );
+        return Ok(());
     }
     if let Some(ref policy_results) = delta_val.policy {
         policy::print_policy_text_output(delta_val, policy_results)?;
     } else {
         println!("Delta Analysis");
         println!("{}", "=".repeat(80));
-        println!("Baseline delta (no parent snapshot) - policy evaluation skipped.");
+        println!("{}", delta_val.status.describe());
+        println!("Policy evaluation skipped (no parent snapshot).");
         println!(
             "\nDelta contains {} function changes.",
             delta_val.deltas.len()
@@ -1168,21 +2668,24 @@ fn emit_delta_text(delta_val: &Delta, with_policy: bool) -> anyhow::Result<()> {
 fn emit_delta_html(
     delta_val: &Delta,
     source_url: Option<&str>,
+    html_theme: &hotspots_core::html::HtmlTheme,
     output: Option<PathBuf>,
 ) -> anyhow::Result<()> {
-    let html = hotspots_core::html::render_html_delta(delta_val, source_url);
-    let output_path = output.unwrap_or_else(|| PathBuf::from(".hotspots/report.html"));
+    let html = hotspots_core::html::render_html_delta(delta_val, source_url, html_theme);
+    let output_path = output.unwrap_or_else(|| default_report_path("report.html"));
     write_html_report(&output_path, &html)?;
-    eprintln!("HTML report written to: {}", output_path.display());
+    if diagnostics::enabled(Level::Info) {
+        eprintln!("HTML report written to: {}", output_path.display());
+    }
     Ok(())
 }
 
 /// Compute delta for PR mode (compares vs merge-base).
-fn compute_pr_delta(repo_root: &Path, snapshot: &Snapshot) -> anyhow::Result<delta::Delta> {
+/// Snapshot a PR delta compares against: the merge-base's, else the direct parent's
+fn load_pr_base(repo_root: &Path, snapshot: &Snapshot) -> anyhow::Result<Option<Snapshot>> {
     let merge_base_sha = git::resolve_merge_base_auto();
     let fallback_sha = snapshot.commit.parents.first().map(|s| s.as_str());
-    let parent = load_merge_base_or_fallback(repo_root, merge_base_sha.as_deref(), fallback_sha)?;
-    delta::Delta::new(snapshot, parent.as_ref())
+    load_merge_base_or_fallback(repo_root, merge_base_sha.as_deref(), fallback_sha)
 }
 
 fn load_merge_base_or_fallback(
@@ -1193,12 +2696,15 @@ fn load_merge_base_or_fallback(
     if let Some(sha) = merge_base_sha {
         match delta::load_parent_snapshot(repo_root, sha)? {
             Some(snap) => return Ok(Some(snap)),
-            None => {
+            None if diagnostics::enabled(Level::Warn) => {
                 eprintln!("Warning: merge-base snapshot not found, falling back to direct parent")
             }
+            None => {}
         }
     } else {
-        eprintln!("Warning: failed to resolve merge-base, falling back to direct parent");
+        if diagnostics::enabled(Level::Warn) {
+            eprintln!("Warning: failed to resolve merge-base, falling back to direct parent");
+        }
     }
     if let Some(sha) = fallback_sha {
         delta::load_parent_snapshot(repo_root, sha)
@@ -1240,7 +2746,7 @@ pub(crate) fn build_snapshot_via_db(
     drop(reports);
 
     // Phase 2: churn (needed before callgraph so neighbor_churn can read it).
-    if !git_context.parent_shas.is_empty() {
+    if !git_context.parent_shas.is_empty() && !is_ignored_commit(resolved_config, &git_context) {
         match git::extract_commit_churn_at(repo_root, &sha) {
             Ok(churns) => {
                 let churn_map: std::collections::HashMap<String, _> = churns
@@ -1254,7 +2760,10 @@ pub(crate) fn build_snapshot_via_db(
                 db.update_churn(&sha, &churn_map)
                     .context("failed to update churn in pipeline DB")?;
             }
-            Err(e) => eprintln!("Warning: failed to extract churn: {}", e),
+            Err(e) if diagnostics::enabled(Level::Warn) => {
+                eprintln!("Warning: failed to extract churn: {}", e)
+            }
+            Err(_) => {}
         }
     }
 
@@ -1265,8 +2774,14 @@ pub(crate) fn build_snapshot_via_db(
         .context("failed to count functions in pipeline DB")?;
 
     if function_count <= effective_skip_above {
-        let call_graph = hotspots_core::build_call_graph_from_db(&db, &sha, repo_root)
-            .context("failed to build call graph from DB")?;
+        let call_graph = hotspots_core::build_call_graph_from_db(
+            &db,
+            &sha,
+            repo_root,
+            resolved_config.callgraph_method_resolution,
+            resolved_config.callgraph_include_anonymous,
+        )
+        .context("failed to build call graph from DB")?;
         db.update_callgraph_metrics(
             &sha,
             &call_graph,
@@ -1276,10 +2791,12 @@ pub(crate) fn build_snapshot_via_db(
         .context("failed to update callgraph metrics in pipeline DB")?;
         // call_graph dropped here, freeing ~25 MB.
     } else {
-        eprintln!(
-            "info: call graph skipped ({} functions > --callgraph-skip-above {})",
-            function_count, effective_skip_above
-        );
+        if diagnostics::enabled(Level::Info) {
+            eprintln!(
+                "info: call graph skipped ({} functions > --callgraph-skip-above {})",
+                function_count, effective_skip_above
+            );
+        }
     }
 
     // Phase 4: load enriched functions from DB (churn + callgraph already set).
@@ -1304,7 +2821,8 @@ pub(crate) fn build_snapshot_via_db(
     // Phase 5: remaining enrichment (touch, activity risk, percentiles, driver, quadrant).
     let mut enricher = snapshot::SnapshotEnricher::new(snapshot)
         .with_subsystems(repo_root)
-        .with_burst_score(repo_root);
+        .with_burst_score(repo_root)
+        .with_trivial_cutoff(resolved_config.trivial);
     if !skip_touch_metrics {
         let needs_progress = matches!(
             touch_mode,
@@ -1315,7 +2833,9 @@ pub(crate) fn build_snapshot_via_db(
         } else {
             None
         };
-        enricher = enricher.with_touch_metrics(repo_root, touch_mode, progress);
+        enricher = enricher
+            .with_ignored_commits(repo_root, &resolved_config.ignore_commits)
+            .with_touch_metrics(repo_root, touch_mode, progress);
         enricher = enricher.with_branch_recency_adjustment(repo_root, merge_base.as_ref());
     }
 
@@ -1347,14 +2867,22 @@ pub(crate) fn build_enriched_snapshot(
 
     let effective_skip_above = callgraph_skip_above.unwrap_or(resolved_config.callgraph_skip_above);
     let call_graph = if reports.len() > effective_skip_above {
-        eprintln!(
-            "info: call graph skipped ({} functions > --callgraph-skip-above {})",
-            reports.len(),
-            effective_skip_above
-        );
+        if diagnostics::enabled(Level::Info) {
+            eprintln!(
+                "info: call graph skipped ({} functions > --callgraph-skip-above {})",
+                reports.len(),
+                effective_skip_above
+            );
+        }
         None
     } else {
-        hotspots_core::build_call_graph(&reports, repo_root).ok()
+        hotspots_core::build_call_graph(
+            &reports,
+            repo_root,
+            resolved_config.callgraph_method_resolution,
+            resolved_config.callgraph_include_anonymous,
+        )
+        .ok()
     };
 
     for r in &mut reports {
@@ -1365,9 +2893,10 @@ pub(crate) fn build_enriched_snapshot(
     let total_functions = reports.len();
     let mut enricher = snapshot::SnapshotEnricher::new(Snapshot::new(git_context.clone(), reports))
         .with_subsystems(repo_root)
-        .with_burst_score(repo_root);
+        .with_burst_score(repo_root)
+        .with_trivial_cutoff(resolved_config.trivial);
 
-    if !git_context.parent_shas.is_empty() {
+    if !git_context.parent_shas.is_empty() && !is_ignored_commit(resolved_config, &git_context) {
         match git::extract_commit_churn_at(repo_root, &git_context.head_sha) {
             Ok(churns) => {
                 let churn_map: std::collections::HashMap<String, _> = churns
@@ -1381,7 +2910,9 @@ pub(crate) fn build_enriched_snapshot(
                 enricher = enricher.with_churn(&churn_map);
             }
             Err(e) => {
-                eprintln!("Warning: failed to extract churn: {}", e);
+                if diagnostics::enabled(Level::Warn) {
+                    eprintln!("Warning: failed to extract churn: {}", e);
+                }
             }
         }
     }
@@ -1396,7 +2927,9 @@ pub(crate) fn build_enriched_snapshot(
         } else {
             None
         };
-        enricher = enricher.with_touch_metrics(repo_root, touch_mode, progress);
+        enricher = enricher
+            .with_ignored_commits(repo_root, &resolved_config.ignore_commits)
+            .with_touch_metrics(repo_root, touch_mode, progress);
         enricher = enricher.with_branch_recency_adjustment(repo_root, merge_base.as_ref());
     }
 
@@ -1416,9 +2949,20 @@ pub(crate) fn build_enriched_snapshot(
         .build())
 }
 
+/// Whether config `ignore_commits` leaves the analyzed commit's churn out
+fn is_ignored_commit(
+    resolved_config: &hotspots_core::ResolvedConfig,
+    git_context: &hotspots_core::GitContext,
+) -> bool {
+    resolved_config.ignore_commits.matches(
+        &git_context.head_sha,
+        git_context.message.as_deref().unwrap_or(""),
+    )
+}
+
 fn make_progress_reporter(total: usize) -> Box<dyn Fn(usize, usize)> {
     use std::io::IsTerminal;
-    if total == 0 {
+    if total == 0 || !diagnostics::enabled(Level::Info) {
         return Box::new(|_i: usize, _total: usize| {});
     }
     if std::io::stderr().is_terminal() {
@@ -1468,6 +3012,26 @@ pub(crate) fn analyze_and_persist_at_ref(
     repo_root: &Path,
     sha: &str,
     resolved_config: &hotspots_core::ResolvedConfig,
+) -> anyhow::Result<hotspots_core::snapshot::Snapshot> {
+    let snapshot = analyze_at_ref(repo_root, sha, resolved_config)?;
+
+    // Persist into the *real* repo's .hotspots/ directory, not the worktree.
+    hotspots_core::snapshot::persist_snapshot(repo_root, &snapshot, false)
+        .with_context(|| format!("failed to persist snapshot for {sha}"))?;
+    hotspots_core::snapshot::append_to_index(repo_root, &snapshot)
+        .with_context(|| format!("failed to update index for {sha}"))?;
+
+    Ok(snapshot)
+}
+
+/// Analyze and enrich the source tree at `sha` without persisting anything.
+///
+/// File paths are rewritten from the temporary worktree to `repo_root`, so
+/// function IDs line up with a working-tree analysis of the same repo.
+pub(crate) fn analyze_at_ref(
+    repo_root: &Path,
+    sha: &str,
+    resolved_config: &hotspots_core::ResolvedConfig,
 ) -> anyhow::Result<hotspots_core::snapshot::Snapshot> {
     let worktree = hotspots_core::git::create_worktree(repo_root, sha)
         .with_context(|| format!("failed to create worktree for {sha}"))?;
@@ -1476,7 +3040,7 @@ pub(crate) fn analyze_and_persist_at_ref(
         min_lrs: None,
         top_n: None,
     };
-    let progress = make_analysis_progress();
+    let progress = make_analysis_progress(false);
     let reports = analyze_with_progress(
         &worktree.path,
         options,
@@ -1505,12 +3069,6 @@ pub(crate) fn analyze_and_persist_at_ref(
 
     snapshot.populate_patterns(&resolved_config.pattern_thresholds);
 
-    // Persist into the *real* repo's .hotspots/ directory, not the worktree.
-    hotspots_core::snapshot::persist_snapshot(repo_root, &snapshot, false)
-        .with_context(|| format!("failed to persist snapshot for {sha}"))?;
-    hotspots_core::snapshot::append_to_index(repo_root, &snapshot)
-        .with_context(|| format!("failed to update index for {sha}"))?;
-
     // Drop of `worktree` runs `git worktree remove` here.
     Ok(snapshot)
 }
@@ -1521,6 +3079,9 @@ fn rewrite_worktree_paths(snapshot: &mut Snapshot, worktree_prefix: &str, repo_p
             let rel = f.file[worktree_prefix.len()..].to_string();
             let old_file = f.file.clone();
             f.file = format!("{}{}", repo_prefix, rel);
+            if let Some(location) = f.location.as_mut() {
+                location.file = f.file.clone();
+            }
             if let Some(symbol) = f.function_id.strip_prefix(&format!("{old_file}::")) {
                 f.function_id = format!("{}::{}", f.file, symbol);
             }
@@ -1548,8 +3109,31 @@ fn resolve_touch_mode(
     }
 }
 
-pub(crate) fn make_analysis_progress() -> Box<dyn Fn(usize, usize) + Send + Sync> {
+/// File-analysis progress reporter (stderr only, never stdout).
+///
+/// Shows a progress bar on a terminal and a line every 30s otherwise. With
+/// `lines` (`--progress`), always prints "analyzed N/M files" lines, throttled
+/// to one every two seconds. Silent under `--quiet`.
+pub(crate) fn make_analysis_progress(lines: bool) -> Box<dyn Fn(usize, usize) + Send + Sync> {
     use std::io::IsTerminal;
+    if !diagnostics::enabled(Level::Info) {
+        return Box::new(|_done: usize, _total: usize| {});
+    }
+    if lines {
+        let last_print = std::sync::Mutex::new(std::time::Instant::now());
+        return Box::new(move |done: usize, total: usize| {
+            if done == 0 || done >= total {
+                eprintln!("analyzed {done}/{total} files");
+                return;
+            }
+            if let Ok(mut last) = last_print.try_lock() {
+                if last.elapsed().as_secs() >= 2 {
+                    eprintln!("analyzed {done}/{total} files");
+                    *last = std::time::Instant::now();
+                }
+            }
+        });
+    }
     if !std::io::stderr().is_terminal() {
         let last_print = std::sync::Mutex::new(std::time::Instant::now());
         return Box::new(move |done: usize, total: usize| {
@@ -0,0 +1,51 @@
+use crate::util::find_repo_root;
+use crate::BadgeMetric;
+use anyhow::Context;
+use std::path::{Path, PathBuf};
+
+pub(crate) fn handle_badge(
+    path: PathBuf,
+    metric: BadgeMetric,
+    output: Option<PathBuf>,
+    config_path: Option<&Path>,
+) -> anyhow::Result<()> {
+    let normalized_path = if path.is_relative() {
+        std::env::current_dir()?.join(&path)
+    } else {
+        path
+    };
+
+    if !normalized_path.exists() {
+        anyhow::bail!("Path does not exist: {}", normalized_path.display());
+    }
+
+    let repo_root = find_repo_root(&normalized_path)?;
+    let resolved_config = hotspots_core::config::load_and_resolve(&repo_root, config_path)
+        .context("failed to load configuration")?;
+    let Some(snapshot) = hotspots_core::trends::load_snapshot_window(&repo_root, 1)
+        .context("failed to load snapshots")?
+        .pop()
+    else {
+        anyhow::bail!("no snapshots found; run `hotspots analyze --mode snapshot` first");
+    };
+
+    let metric = match metric {
+        BadgeMetric::CriticalCount => hotspots_core::report::BadgeMetric::CriticalCount,
+        BadgeMetric::ActivityRisk => hotspots_core::report::BadgeMetric::ActivityRisk,
+    };
+    let svg = hotspots_core::report::render_badge_svg(
+        &snapshot,
+        metric,
+        &resolved_config.risk_thresholds(),
+    );
+
+    match output {
+        Some(output_path) => {
+            std::fs::write(&output_path, svg)
+                .with_context(|| format!("failed to write {}", output_path.display()))?;
+            eprintln!("Badge written to: {}", output_path.display());
+        }
+        None => print!("{svg}"),
+    }
+    Ok(())
+}
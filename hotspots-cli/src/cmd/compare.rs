@@ -0,0 +1,78 @@
+use crate::cmd::analyze::make_analysis_progress;
+use crate::cmd::diff::emit_diff_output;
+use crate::OutputFormat;
+use anyhow::Context;
+use hotspots_core::delta::FunctionStatus;
+use hotspots_core::{analyze_with_progress, AnalysisOptions, FunctionRiskReport};
+use std::path::{Path, PathBuf};
+
+pub(crate) struct CompareArgs {
+    pub before: PathBuf,
+    pub after: PathBuf,
+    pub format: OutputFormat,
+    pub output: Option<PathBuf>,
+    pub config_path: Option<PathBuf>,
+}
+
+pub(crate) fn handle_compare(args: CompareArgs) -> anyhow::Result<()> {
+    let CompareArgs {
+        before,
+        after,
+        format,
+        output,
+        config_path,
+    } = args;
+
+    // Both trees are scored under one configuration so differences come from
+    // the code, not from each tree's own settings
+    let resolved_config =
+        hotspots_core::config::load_and_resolve(&std::env::current_dir()?, config_path.as_deref())
+            .context("failed to load configuration")?;
+
+    let before = canonical_dir(&before)?;
+    let after = canonical_dir(&after)?;
+    let before_reports = analyze_tree(&before, &resolved_config)?;
+    let after_reports = analyze_tree(&after, &resolved_config)?;
+
+    let mut delta_val = hotspots_core::delta::compute_tree_delta(
+        &before,
+        before_reports,
+        &after,
+        after_reports,
+        &resolved_config.lrs_weights(),
+    )
+    .context("failed to compute delta between directories")?;
+    delta_val
+        .deltas
+        .retain(|e| e.status != FunctionStatus::Unchanged);
+
+    emit_diff_output(&delta_val, format, false, output)?;
+    Ok(())
+}
+
+fn canonical_dir(path: &Path) -> anyhow::Result<PathBuf> {
+    let dir = path
+        .canonicalize()
+        .with_context(|| format!("failed to resolve {}", path.display()))?;
+    if !dir.is_dir() {
+        anyhow::bail!("{} is not a directory", path.display());
+    }
+    Ok(dir)
+}
+
+fn analyze_tree(
+    root: &Path,
+    resolved_config: &hotspots_core::ResolvedConfig,
+) -> anyhow::Result<Vec<FunctionRiskReport>> {
+    let analysis_progress = make_analysis_progress(false);
+    analyze_with_progress(
+        root,
+        AnalysisOptions {
+            min_lrs: None,
+            top_n: None,
+        },
+        Some(resolved_config),
+        Some(analysis_progress.as_ref()),
+    )
+    .with_context(|| format!("failed to analyze {}", root.display()))
+}
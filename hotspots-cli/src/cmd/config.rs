@@ -1,3 +1,4 @@
+use crate::OutputFormat;
 use anyhow::Context;
 use hotspots_core::config;
 use hotspots_core::config::PolicyMode;
@@ -10,6 +11,14 @@ fn policy_mode_str(mode: PolicyMode) -> &'static str {
     }
 }
 
+fn language_list(languages: &[hotspots_core::language::Language]) -> String {
+    languages
+        .iter()
+        .map(|l| l.name())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
 fn reason_suffix(reason: Option<&str>) -> String {
     match reason {
         Some(r) => format!(" ({r})"),
@@ -30,6 +39,10 @@ pub(crate) enum ConfigAction {
         /// Path to config file (default: auto-discover from current directory)
         #[arg(long)]
         path: Option<std::path::PathBuf>,
+
+        /// Output format (`json` emits the full resolved configuration)
+        #[arg(long, default_value = "text")]
+        format: OutputFormat,
     },
 }
 
@@ -52,11 +65,27 @@ pub(crate) fn handle_config(action: ConfigAction) -> anyhow::Result<()> {
                 }
             }
         }
-        ConfigAction::Show { path } => {
+        ConfigAction::Show { path, format } => {
             let project_root = std::env::current_dir()?;
             let resolved = config::load_and_resolve(&project_root, path.as_deref())
                 .context("failed to load configuration")?;
 
+            match format {
+                OutputFormat::Text => {}
+                OutputFormat::Json => {
+                    println!("{}", resolved.to_json()?);
+                    return Ok(());
+                }
+                OutputFormat::Html
+                | OutputFormat::Jsonl
+                | OutputFormat::Sarif
+                | OutputFormat::Canonical => {
+                    anyhow::bail!(
+                        "HTML/JSONL/SARIF/canonical format is not supported for config show"
+                    );
+                }
+            }
+
             println!("Configuration:");
             if let Some(ref p) = resolved.config_path {
                 println!("  Source: {}", p.display());
@@ -69,6 +98,7 @@ pub(crate) fn handle_config(action: ConfigAction) -> anyhow::Result<()> {
             println!("  nd: {}", resolved.weight_nd);
             println!("  fo: {}", resolved.weight_fo);
             println!("  ns: {}", resolved.weight_ns);
+            println!("  unwrap: {}", resolved.weight_unwrap);
             println!();
             println!("Thresholds:");
             println!("  moderate: {}", resolved.moderate_threshold);
@@ -90,6 +120,89 @@ pub(crate) fn handle_config(action: ConfigAction) -> anyhow::Result<()> {
                     .map(|v| v.to_string())
                     .unwrap_or_else(|| "none".to_string())
             );
+            println!(
+                "  callgraph_method_resolution: {}",
+                match resolved.callgraph_method_resolution {
+                    hotspots_core::callgraph::MethodResolution::Receiver => "receiver",
+                    hotspots_core::callgraph::MethodResolution::Name => "name",
+                }
+            );
+            println!(
+                "  callgraph_include_anonymous: {}",
+                resolved.callgraph_include_anonymous
+            );
+            println!(
+                "  max_file_bytes: {}",
+                if resolved.max_file_bytes == 0 {
+                    "no limit".to_string()
+                } else {
+                    resolved.max_file_bytes.to_string()
+                }
+            );
+            println!(
+                "  skip_anonymous_functions: {}",
+                resolved.skip_anonymous_functions
+            );
+            println!("  require_functions: {}", resolved.require_functions);
+            println!(
+                "  granularity: {}",
+                match resolved.granularity {
+                    hotspots_core::config::Granularity::Function => "function",
+                    hotspots_core::config::Granularity::File => "file",
+                }
+            );
+            println!(
+                "  rust_branching_macros: {}",
+                resolved
+                    .rust_branching_macros
+                    .as_ref()
+                    .map(|m| format!("[{}]", m.join(", ")))
+                    .unwrap_or_else(|| "default".to_string())
+            );
+            println!(
+                "  max_function_loc: {}",
+                resolved
+                    .max_function_loc
+                    .map(|v| v.to_string())
+                    .unwrap_or_else(|| "none".to_string())
+            );
+            println!(
+                "  count_trivial: {}",
+                match (resolved.trivial.min_loc, resolved.trivial.min_lrs) {
+                    (None, None) => "true".to_string(),
+                    (min_loc, min_lrs) => format!(
+                        "false (functions under{}{}{} left out of summary)",
+                        min_loc.map(|m| format!(" {m} lines")).unwrap_or_default(),
+                        if min_loc.is_some() && min_lrs.is_some() {
+                            " or"
+                        } else {
+                            ""
+                        },
+                        min_lrs.map(|m| format!(" LRS {m}")).unwrap_or_default()
+                    ),
+                }
+            );
+            println!(
+                "  max_new_cc: {}",
+                resolved
+                    .max_new_cc
+                    .map(|v| v.to_string())
+                    .unwrap_or_else(|| "none".to_string())
+            );
+            println!(
+                "  max_new_critical: {}",
+                resolved
+                    .max_new_critical
+                    .map(|v| v.to_string())
+                    .unwrap_or_else(|| "none".to_string())
+            );
+            println!(
+                "  max_new_high: {}",
+                resolved
+                    .max_new_high
+                    .map(|v| v.to_string())
+                    .unwrap_or_else(|| "none".to_string())
+            );
             println!(
                 "  include: {}",
                 if resolved.include.is_some() {
@@ -98,6 +211,18 @@ pub(crate) fn handle_config(action: ConfigAction) -> anyhow::Result<()> {
                     "all files"
                 }
             );
+            if !resolved.include_languages.is_empty() {
+                println!(
+                    "  include_languages: {}",
+                    language_list(&resolved.include_languages)
+                );
+            }
+            if !resolved.exclude_languages.is_empty() {
+                println!(
+                    "  exclude_languages: {}",
+                    language_list(&resolved.exclude_languages)
+                );
+            }
             println!(
                 "  exclude: active ({} patterns)",
                 if resolved.config_path.is_some() {
@@ -106,6 +231,12 @@ pub(crate) fn handle_config(action: ConfigAction) -> anyhow::Result<()> {
                     "default"
                 }
             );
+            if !resolved.exclude_functions.is_empty() {
+                println!(
+                    "  exclude_functions: {} function(s)",
+                    resolved.exclude_functions.len()
+                );
+            }
             println!();
             println!("Policy:");
             println!(
@@ -118,6 +249,46 @@ pub(crate) fn handle_config(action: ConfigAction) -> anyhow::Result<()> {
                 policy_mode_str(resolved.excessive_risk_regression_mode),
                 reason_suffix(resolved.excessive_risk_regression_reason.as_deref())
             );
+            println!(
+                "  function-too-long: {}",
+                if resolved.max_function_loc.is_some() {
+                    policy_mode_str(resolved.function_too_long_mode)
+                } else {
+                    "off (no max_function_loc)"
+                }
+            );
+            println!(
+                "  max-new-cc: {}",
+                if resolved.max_new_cc.is_some() {
+                    policy_mode_str(resolved.max_new_cc_mode)
+                } else {
+                    "off (no max_new_cc)"
+                }
+            );
+            println!(
+                "  max-new-critical: {}",
+                if resolved.max_new_critical.is_some() {
+                    policy_mode_str(resolved.max_new_critical_mode)
+                } else {
+                    "off (no max_new_critical)"
+                }
+            );
+            println!(
+                "  max-new-high: {}",
+                if resolved.max_new_high.is_some() {
+                    policy_mode_str(resolved.max_new_high_mode)
+                } else {
+                    "off (no max_new_high)"
+                }
+            );
+            println!(
+                "  instability-regression: {}",
+                if resolved.max_stable_afferent.is_some() {
+                    policy_mode_str(resolved.instability_regression_mode)
+                } else {
+                    "off (no max_stable_afferent)"
+                }
+            );
         }
     }
     Ok(())
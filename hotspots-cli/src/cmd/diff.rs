@@ -1,5 +1,5 @@
-use crate::cmd::analyze::analyze_and_persist_at_ref;
-use crate::util::{find_repo_root, write_html_report};
+use crate::cmd::analyze::{analyze_and_persist_at_ref, snapshot_modules};
+use crate::util::{default_report_path, find_repo_root, warn_version_skew, write_html_report};
 use crate::OutputFormat;
 use anyhow::Context;
 use hotspots_core::delta::Delta;
@@ -25,6 +25,7 @@ pub(crate) struct DiffArgs {
     pub top: Option<usize>,
     pub config_path: Option<PathBuf>,
     pub auto_analyze: bool,
+    pub ignore_version_skew: bool,
 }
 
 pub(crate) fn handle_diff(args: DiffArgs) -> anyhow::Result<()> {
@@ -37,6 +38,7 @@ pub(crate) fn handle_diff(args: DiffArgs) -> anyhow::Result<()> {
         top,
         config_path,
         auto_analyze,
+        ignore_version_skew,
     } = args;
 
     let repo_root = find_repo_root(&std::env::current_dir()?)?;
@@ -84,9 +86,17 @@ pub(crate) fn handle_diff(args: DiffArgs) -> anyhow::Result<()> {
         }
     };
 
+    if !ignore_version_skew {
+        warn_version_skew("diff", [&base_snapshot, &head_snapshot]);
+    }
+
     // Compute delta
-    let mut delta_val = Delta::new(&head_snapshot, Some(&base_snapshot))
-        .context("failed to compute delta between snapshots")?;
+    let mut delta_val = Delta::new_with_weights(
+        &head_snapshot,
+        Some(&base_snapshot),
+        &resolved_config.lrs_weights(),
+    )
+    .context("failed to compute delta between snapshots")?;
 
     // Attach delta aggregates (file-level summaries used by HTML renderer)
     let current_co_change = head_snapshot
@@ -99,10 +109,16 @@ pub(crate) fn handle_diff(args: DiffArgs) -> anyhow::Result<()> {
         .as_ref()
         .map(|a| a.co_change.as_slice())
         .unwrap_or(&[]);
+    // Module instability feeds the instability-regression policy
+    let zones = &resolved_config.module_zones;
+    let current_modules = snapshot_modules(&head_snapshot, &repo_root, zones);
+    let prev_modules = snapshot_modules(&base_snapshot, &repo_root, zones);
     delta_val.aggregates = Some(hotspots_core::aggregates::compute_delta_aggregates(
         &delta_val,
         current_co_change,
         prev_co_change,
+        &current_modules,
+        &prev_modules,
     ));
 
     // Filter out Unchanged, then optionally keep top N by risk magnitude
@@ -191,7 +207,7 @@ fn load_snapshot_or_report(
 }
 
 /// Render diff output. Returns true if there are blocking policy failures.
-fn emit_diff_output(
+pub(crate) fn emit_diff_output(
     delta_val: &Delta,
     format: OutputFormat,
     with_policy: bool,
@@ -217,9 +233,12 @@ fn emit_diff_output(
             write_or_print(output, &jsonl)?;
         }
         OutputFormat::Html => {
-            let html = hotspots_core::html::render_html_delta(delta_val, None);
-            let output_path =
-                output.unwrap_or_else(|| PathBuf::from(".hotspots/delta-report.html"));
+            let html = hotspots_core::html::render_html_delta(
+                delta_val,
+                None,
+                &hotspots_core::html::HtmlTheme::default(),
+            );
+            let output_path = output.unwrap_or_else(|| default_report_path("delta-report.html"));
             write_html_report(&output_path, &html)?;
             eprintln!("HTML report written to: {}", output_path.display());
         }
@@ -228,6 +247,11 @@ fn emit_diff_output(
                 "--format sarif is not supported for diff (use --format json or --format html)"
             );
         }
+        OutputFormat::Canonical => {
+            anyhow::bail!(
+                "--format canonical is not supported for diff (use --format json or --format html)"
+            );
+        }
     }
 
     Ok(has_blocking_failures)
@@ -251,7 +275,7 @@ fn write_or_print(output: Option<PathBuf>, content: &str) -> anyhow::Result<()>
 }
 
 fn render_diff_text(delta_val: &Delta, with_policy: bool) -> anyhow::Result<String> {
-    use hotspots_core::delta::FunctionStatus;
+    use hotspots_core::delta::{DeltaStatus, FunctionStatus};
     use std::fmt::Write;
 
     let mut out = String::new();
@@ -276,8 +300,18 @@ fn render_diff_text(delta_val: &Delta, with_policy: bool) -> anyhow::Result<Stri
         out,
         "{modified_count} modified, {new_count} new, {deleted_count} deleted"
     )?;
+    crate::output::delta::write_risk_removed(&mut out, delta_val)?;
     writeln!(out, "{}", "=".repeat(100))?;
 
+    match delta_val.status {
+        DeltaStatus::NoChange => {
+            writeln!(out, "{}", delta_val.status.describe())?;
+            return Ok(out);
+        }
+        DeltaStatus::Baseline => writeln!(out, "{}", delta_val.status.describe())?,
+        DeltaStatus::Changes => {}
+    }
+
     if delta_val.deltas.is_empty() {
         writeln!(out, "No changes.")?;
         return Ok(out);
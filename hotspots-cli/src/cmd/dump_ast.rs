@@ -0,0 +1,10 @@
+use std::path::Path;
+
+pub(crate) fn handle_dump_ast(file: &Path, function: &str) -> anyhow::Result<()> {
+    if !file.exists() {
+        anyhow::bail!("File does not exist: {}", file.display());
+    }
+    let dumps = hotspots_core::ast_dump::dump_function_ast(file, function)?;
+    print!("{}", hotspots_core::ast_dump::render_ast_dump(&dumps));
+    Ok(())
+}
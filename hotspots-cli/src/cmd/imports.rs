@@ -0,0 +1,36 @@
+use crate::util::find_repo_root;
+use crate::ImportsFormat;
+use anyhow::Context;
+use std::path::{Path, PathBuf};
+
+pub(crate) fn handle_imports(
+    path: PathBuf,
+    format: ImportsFormat,
+    config_path: Option<&Path>,
+) -> anyhow::Result<()> {
+    let normalized_path = if path.is_relative() {
+        std::env::current_dir()?.join(&path)
+    } else {
+        path
+    };
+
+    if !normalized_path.exists() {
+        anyhow::bail!("Path does not exist: {}", normalized_path.display());
+    }
+
+    let project_root = find_repo_root(&normalized_path).unwrap_or_else(|_| normalized_path.clone());
+    let resolved_config = hotspots_core::config::load_and_resolve(&project_root, config_path)
+        .context("failed to load configuration")?;
+    let edges = hotspots_core::imports::import_graph(
+        &normalized_path,
+        &project_root,
+        Some(&resolved_config),
+    )
+    .context("failed to resolve imports")?;
+
+    match format {
+        ImportsFormat::Dot => print!("{}", hotspots_core::imports::render_dot(&edges)),
+        ImportsFormat::Json => println!("{}", hotspots_core::imports::render_json(&edges)),
+    }
+    Ok(())
+}
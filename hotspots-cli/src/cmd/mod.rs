@@ -1,8 +1,14 @@
 pub(crate) mod analyze;
+pub(crate) mod badge;
 pub(crate) mod compact;
+pub(crate) mod compare;
 pub(crate) mod config;
 pub(crate) mod diff;
+pub(crate) mod dump_ast;
+pub(crate) mod imports;
 pub(crate) mod init;
 pub(crate) mod prune;
+pub(crate) mod report_index;
 pub(crate) mod train;
 pub(crate) mod trends;
+pub(crate) mod verify_determinism;
@@ -5,14 +5,21 @@ pub(crate) fn handle_prune(
     unreachable: bool,
     older_than: Option<u64>,
     dry_run: bool,
+    refs: Vec<String>,
 ) -> anyhow::Result<()> {
     if !unreachable {
         anyhow::bail!("--unreachable flag must be specified to prune snapshots");
     }
 
     let repo_root = find_repo_root(&std::env::current_dir()?)?;
+    let ref_patterns = if refs.is_empty() {
+        prune::default_ref_patterns(&repo_root)?
+    } else {
+        refs
+    };
+    println!("Tracked refs: {}", ref_patterns.join(", "));
     let options = prune::PruneOptions {
-        ref_patterns: vec!["refs/heads/*".to_string()],
+        ref_patterns,
         older_than_days: older_than,
         dry_run,
     };
@@ -0,0 +1,118 @@
+use crate::util::{find_repo_root, write_html_report};
+use anyhow::Context;
+use hotspots_core::html::IndexLink;
+use hotspots_core::snapshot::{self, Index};
+use std::path::{Path, PathBuf};
+
+const INDEX_FILE: &str = "index.html";
+
+pub(crate) fn handle_report_index(path: PathBuf) -> anyhow::Result<()> {
+    let normalized_path = if path.is_relative() {
+        std::env::current_dir()?.join(&path)
+    } else {
+        path
+    };
+
+    if !normalized_path.exists() {
+        anyhow::bail!("Path does not exist: {}", normalized_path.display());
+    }
+
+    let repo_root = find_repo_root(&normalized_path)?;
+    let hotspots_dir = snapshot::hotspots_dir(&repo_root);
+    if !hotspots_dir.is_dir() {
+        anyhow::bail!(
+            "{} does not exist; run `hotspots analyze --mode snapshot` or generate an HTML report first",
+            hotspots_dir.display()
+        );
+    }
+
+    let reports = collect_html_reports(&hotspots_dir)?;
+    let snapshots = collect_snapshots(&repo_root)?;
+    let history =
+        snapshot::load_history(&repo_root, snapshot::DEFAULT_HISTORY_WINDOW).unwrap_or_default();
+
+    let html = hotspots_core::html::render_html_report_index(&reports, &snapshots, &history);
+    let output_path = hotspots_dir.join(INDEX_FILE);
+    write_html_report(&output_path, &html)?;
+    eprintln!("Report index written to: {}", output_path.display());
+    Ok(())
+}
+
+/// HTML files directly under `.hotspots/` (other than the index itself), newest first
+fn collect_html_reports(hotspots_dir: &Path) -> anyhow::Result<Vec<IndexLink>> {
+    let entries = std::fs::read_dir(hotspots_dir)
+        .with_context(|| format!("failed to read {}", hotspots_dir.display()))?;
+    let mut reports = Vec::new();
+    for entry in entries {
+        let path = entry?.path();
+        let Some(name) = path.file_name().and_then(|n| n.to_str()) else {
+            continue;
+        };
+        if !name.ends_with(".html") || name == INDEX_FILE || !path.is_file() {
+            continue;
+        }
+        let timestamp = std::fs::metadata(&path)
+            .and_then(|m| m.modified())
+            .ok()
+            .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+            .map_or(0, |d| d.as_secs() as i64);
+        let label = match report_title(&path) {
+            Some(title) => format!("{name} — {title}"),
+            None => name.to_string(),
+        };
+        reports.push(IndexLink {
+            href: name.to_string(),
+            label,
+            timestamp,
+        });
+    }
+    reports.sort_by(|a, b| {
+        b.timestamp
+            .cmp(&a.timestamp)
+            .then_with(|| a.href.cmp(&b.href))
+    });
+    Ok(reports)
+}
+
+/// The `<title>` of a generated report, e.g. "Hotspots Report - 1a2b3c4d"
+fn report_title(path: &Path) -> Option<String> {
+    let html = std::fs::read_to_string(path).ok()?;
+    let start = html.find("<title>")? + "<title>".len();
+    let end = start + html[start..].find("</title>")?;
+    Some(html[start..end].trim().to_string())
+}
+
+/// Snapshot files for every commit in the snapshot index, newest first
+fn collect_snapshots(repo_root: &Path) -> anyhow::Result<Vec<IndexLink>> {
+    let index = Index::load_or_new(&snapshot::index_path(repo_root))
+        .context("failed to load snapshot index")?;
+    let mut commits = index.commits;
+    commits.sort_by(|a, b| {
+        b.timestamp
+            .cmp(&a.timestamp)
+            .then_with(|| a.sha.cmp(&b.sha))
+    });
+
+    let hotspots_dir = snapshot::hotspots_dir(repo_root);
+    let mut snapshots = Vec::new();
+    for commit in commits {
+        let short_sha = &commit.sha[..commit.sha.len().min(8)];
+        let candidates = [
+            (snapshot::snapshot_path(repo_root, &commit.sha), "snapshot"),
+            (
+                snapshot::delta_snapshot_path(repo_root, &commit.sha),
+                "delta",
+            ),
+        ];
+        let Some((file, kind)) = candidates.iter().find(|(p, _)| p.exists()) else {
+            continue;
+        };
+        let rel = file.strip_prefix(&hotspots_dir).unwrap_or(file);
+        snapshots.push(IndexLink {
+            href: rel.to_string_lossy().replace('\\', "/"),
+            label: format!("{short_sha} ({kind})"),
+            timestamp: commit.timestamp,
+        });
+    }
+    Ok(snapshots)
+}
@@ -1,7 +1,7 @@
 //! `hotspots train` — fit a local RandomForest ranker from git history.
 
 use anyhow::{bail, Context, Result};
-use hotspots_core::snapshot::{index_path, load_snapshot, Snapshot};
+use hotspots_core::snapshot::{hotspots_dir, index_path, load_snapshot, Snapshot};
 use hotspots_core::trainer::{
     collect_fix_files, precision_at_k, score, screen_repo, train, FunctionId, ModelClass,
     RankerModel, RegimeVerdict, ScoredFunction, ScreenerVerdict, TrainConfig,
@@ -12,7 +12,7 @@ use std::time::Instant;
 
 pub(crate) struct TrainArgs {
     pub path: PathBuf,
-    pub output: PathBuf,
+    pub output: Option<PathBuf>,
     pub label_window_days: u32,
     pub label_before: Option<String>,
     pub n_estimators: usize,
@@ -28,12 +28,11 @@ pub(crate) fn handle_train(args: TrainArgs) -> Result<()> {
     let repo_root = args.path.canonicalize().context("resolve repo path")?;
     let snapshot = load_latest_snapshot(&repo_root)?;
 
-    let output = if args.output.is_relative() {
-        repo_root.join(&args.output)
-    } else {
-        args.output.clone()
+    let output = match &args.output {
+        Some(path) if path.is_relative() => repo_root.join(path),
+        Some(path) => path.clone(),
+        None => hotspots_dir(&repo_root).join("ranker.json"),
     };
-    let args = TrainArgs { output, ..args };
 
     let cfg = TrainConfig {
         label_window_days: args.label_window_days,
@@ -142,8 +141,8 @@ pub(crate) fn handle_train(args: TrainArgs) -> Result<()> {
         Some(model) => {
             let elapsed = start.elapsed();
             report_model(&model, n_funcs, elapsed.as_secs());
-            model.save(&args.output)?;
-            eprintln!("Model saved → {}", args.output.display());
+            model.save(&output)?;
+            eprintln!("Model saved → {}", output.display());
             if args.eval {
                 run_eval(
                     &model,
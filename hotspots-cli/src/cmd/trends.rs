@@ -1,17 +1,34 @@
-use crate::util::truncate_string;
+use crate::util::{truncate_string, warn_version_skew};
 use crate::OutputFormat;
 use anyhow::Context;
 use hotspots_core::trends::TrendsAnalysis;
 use std::path::PathBuf;
 
-pub(crate) fn handle_trends(
-    path: PathBuf,
-    format: OutputFormat,
-    window: usize,
-    top: usize,
-) -> anyhow::Result<()> {
+pub(crate) struct TrendsArgs {
+    pub path: PathBuf,
+    pub format: OutputFormat,
+    pub window: usize,
+    pub top: usize,
+    pub min_snapshots: Option<usize>,
+    pub author: Option<String>,
+    pub ignore_version_skew: bool,
+    pub smoothing: Option<f64>,
+}
+
+pub(crate) fn handle_trends(args: TrendsArgs) -> anyhow::Result<()> {
     use crate::util::find_repo_root;
 
+    let TrendsArgs {
+        path,
+        format,
+        window,
+        top,
+        min_snapshots,
+        author,
+        ignore_version_skew,
+        smoothing,
+    } = args;
+
     let normalized_path = if path.is_relative() {
         std::env::current_dir()?.join(&path)
     } else {
@@ -22,9 +39,41 @@ pub(crate) fn handle_trends(
         anyhow::bail!("Path does not exist: {}", normalized_path.display());
     }
 
+    if min_snapshots == Some(0) {
+        anyhow::bail!("--min-snapshots must be at least 1");
+    }
+    if smoothing.is_some_and(|alpha| !(alpha > 0.0 && alpha <= 1.0)) {
+        anyhow::bail!("--smoothing must be in (0, 1]");
+    }
+
     let repo_root = find_repo_root(&normalized_path)?;
-    let trends = hotspots_core::trends::analyze_trends(&repo_root, window, top)
+    let min_snapshots = match min_snapshots {
+        Some(n) => n,
+        None => {
+            hotspots_core::config::load_and_resolve(&repo_root, None)
+                .context("failed to load configuration")?
+                .trends_min_snapshots
+        }
+    };
+    let mut snapshots = hotspots_core::trends::load_snapshot_window(&repo_root, window)
         .context("failed to analyze trends")?;
+    if !ignore_version_skew {
+        warn_version_skew("trends", &snapshots);
+    }
+    if let Some(author) = author {
+        let author_lines = hotspots_core::git::extract_file_author_lines(&repo_root)
+            .context("failed to mine authorship for --author")?;
+        for snapshot in &mut snapshots {
+            hotspots_core::aggregates::retain_functions_owned_by(
+                &mut snapshot.functions,
+                &author,
+                &author_lines,
+                &repo_root,
+            );
+        }
+    }
+    let trends =
+        hotspots_core::trends::analyze_snapshot_trends(&snapshots, top, min_snapshots, smoothing);
 
     match format {
         OutputFormat::Json => {
@@ -36,8 +85,11 @@ pub(crate) fn handle_trends(
         OutputFormat::Text => {
             print_trends_text_output(&trends)?;
         }
-        OutputFormat::Html | OutputFormat::Jsonl | OutputFormat::Sarif => {
-            anyhow::bail!("HTML/JSONL/SARIF format is not supported for trends analysis");
+        OutputFormat::Html
+        | OutputFormat::Jsonl
+        | OutputFormat::Sarif
+        | OutputFormat::Canonical => {
+            anyhow::bail!("HTML/JSONL/SARIF/canonical format is not supported for trends analysis");
         }
     }
 
@@ -49,20 +101,29 @@ fn print_trends_text_output(trends: &TrendsAnalysis) -> anyhow::Result<()> {
     println!("{}", "=".repeat(80));
 
     if !trends.velocities.is_empty() {
+        let smoothed = trends
+            .velocities
+            .iter()
+            .any(|v| v.smoothed_velocity.is_some());
         println!("\nRisk Velocities:");
-        println!(
+        print!(
             "{:<40} {:<12} {:<12} {:<12} {:<12}",
             "Function", "Velocity", "Direction", "First LRS", "Last LRS"
         );
-        println!("{}", "-".repeat(100));
+        if smoothed {
+            print!(" {:<12}", "Smoothed");
+        }
+        println!();
+        println!("{}", "-".repeat(if smoothed { 113 } else { 100 }));
 
         for velocity in &trends.velocities {
             let direction_str = match velocity.direction {
                 hotspots_core::trends::VelocityDirection::Positive => "positive",
                 hotspots_core::trends::VelocityDirection::Negative => "negative",
                 hotspots_core::trends::VelocityDirection::Flat => "flat",
+                hotspots_core::trends::VelocityDirection::InsufficientData => "insufficient",
             };
-            println!(
+            print!(
                 "{:<40} {:<12.2} {:<12} {:<12.2} {:<12.2}",
                 truncate_string(&velocity.function_id, 40),
                 velocity.velocity,
@@ -70,6 +131,10 @@ fn print_trends_text_output(trends: &TrendsAnalysis) -> anyhow::Result<()> {
                 velocity.first_lrs,
                 velocity.last_lrs
             );
+            if let Some(s) = velocity.smoothed_velocity {
+                print!(" {:<12.2}", s);
+            }
+            println!();
         }
     }
 
@@ -86,6 +151,7 @@ fn print_trends_text_output(trends: &TrendsAnalysis) -> anyhow::Result<()> {
                 hotspots_core::trends::HotspotStability::Stable => "stable",
                 hotspots_core::trends::HotspotStability::Emerging => "emerging",
                 hotspots_core::trends::HotspotStability::Volatile => "volatile",
+                hotspots_core::trends::HotspotStability::InsufficientData => "insufficient",
             };
             println!(
                 "{:<40} {:<12} {:<12.2} {:<12}/{}",
@@ -122,10 +188,32 @@ fn print_trends_text_output(trends: &TrendsAnalysis) -> anyhow::Result<()> {
         }
     }
 
+    if !trends.stale_hotspots.is_empty() {
+        println!("\nStale Hotspots (high/critical in every snapshot):");
+        println!(
+            "{:<40} {:<12} {:<12} {:<12} {:<12}",
+            "Function", "Band", "First LRS", "Last LRS", "Stuck"
+        );
+        println!("{}", "-".repeat(100));
+
+        for stale in &trends.stale_hotspots {
+            println!(
+                "{:<40} {:<12} {:<12.2} {:<12.2} {} snapshots / {}d",
+                truncate_string(&stale.function_id, 40),
+                stale.band.as_str(),
+                stale.first_lrs,
+                stale.last_lrs,
+                stale.stuck_snapshots,
+                stale.stuck_days
+            );
+        }
+    }
+
     println!("\nSummary:");
     println!("  Risk velocities: {}", trends.velocities.len());
     println!("  Hotspots analyzed: {}", trends.hotspots.len());
     println!("  Refactors detected: {}", trends.refactors.len());
+    println!("  Stale hotspots: {}", trends.stale_hotspots.len());
 
     Ok(())
 }
@@ -0,0 +1,54 @@
+use crate::util::find_repo_root;
+use anyhow::Context;
+use std::path::{Path, PathBuf};
+
+pub(crate) fn handle_verify_determinism(
+    path: PathBuf,
+    reverse_order: bool,
+    config_path: Option<&Path>,
+) -> anyhow::Result<()> {
+    let normalized_path = if path.is_relative() {
+        std::env::current_dir()?.join(&path)
+    } else {
+        path
+    };
+
+    if !normalized_path.exists() {
+        anyhow::bail!("Path does not exist: {}", normalized_path.display());
+    }
+
+    let project_root = find_repo_root(&normalized_path).unwrap_or_else(|_| normalized_path.clone());
+    let resolved_config = hotspots_core::config::load_and_resolve(&project_root, config_path)
+        .context("failed to load configuration")?;
+    let check = hotspots_core::determinism::verify_determinism(
+        &normalized_path,
+        Some(&resolved_config),
+        reverse_order,
+    )
+    .context("analysis failed")?;
+
+    let order = if reverse_order {
+        "second run in reverse file order"
+    } else {
+        "same file order"
+    };
+    match check.diff() {
+        None => {
+            println!(
+                "Deterministic: {} file(s), {} function(s), {} bytes of identical output ({})",
+                check.files,
+                check.functions,
+                check.first.len(),
+                order
+            );
+            Ok(())
+        }
+        Some(diff) => {
+            eprintln!(
+                "Output differs between runs ({}):\n--- first run\n+++ second run\n{}",
+                order, diff
+            );
+            std::process::exit(1);
+        }
+    }
+}
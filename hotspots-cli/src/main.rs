@@ -11,7 +11,10 @@ mod output;
 mod util;
 
 use clap::{Parser, Subcommand};
-use cmd::{analyze::AnalyzeArgs, config::ConfigAction, diff::DiffArgs};
+use cmd::{
+    analyze::AnalyzeArgs, compare::CompareArgs, config::ConfigAction, diff::DiffArgs,
+    trends::TrendsArgs,
+};
 use std::path::PathBuf;
 
 #[derive(Parser)]
@@ -21,6 +24,11 @@ use std::path::PathBuf;
 )]
 #[command(version = env!("HOTSPOTS_VERSION"))]
 struct Cli {
+    /// Directory for snapshots, the index, and caches (default: <repo>/.hotspots;
+    /// also settable with HOTSPOTS_STORE_DIR)
+    #[arg(long, global = true, value_name = "PATH")]
+    store_dir: Option<PathBuf>,
+
     #[command(subcommand)]
     command: Commands,
 }
@@ -28,116 +36,71 @@ struct Cli {
 #[derive(Subcommand)]
 enum Commands {
     /// Analyze source files (TypeScript, JavaScript, Go, Java, Python, Rust)
-    Analyze {
-        /// Path to source file or directory
-        path: PathBuf,
+    Analyze(Box<AnalyzeCommand>),
+    /// Debug: list the AST nodes feeding CC/ND/FO/NS for one function
+    #[command(name = "dump-ast")]
+    DumpAst {
+        /// Source file containing the function
+        file: PathBuf,
 
-        /// Output format
-        #[arg(long, default_value = "text")]
-        format: OutputFormat,
-
-        /// Output mode (snapshot, delta, or models)
+        /// Function name (bare method names match qualified ones)
         #[arg(long)]
-        mode: Option<OutputMode>,
-
-        /// Evaluate policies (only valid with --mode delta)
-        #[arg(long)]
-        policy: bool,
-
-        /// Show only top N results (overrides config file)
-        #[arg(long)]
-        top: Option<usize>,
+        function: String,
+    },
+    /// Print the resolved file-level import graph (importer -> imported)
+    Imports {
+        /// File or directory to scan
+        #[arg(default_value = ".")]
+        path: PathBuf,
 
-        /// Minimum LRS threshold (overrides config file)
-        #[arg(long)]
-        min_lrs: Option<f64>,
+        /// Output format
+        #[arg(long, value_enum, default_value = "json")]
+        format: ImportsFormat,
 
         /// Path to config file (default: auto-discover)
         #[arg(long)]
         config: Option<PathBuf>,
+    },
+    /// Write a static .hotspots/index.html linking HTML reports and snapshots,
+    /// with the risk history chart
+    #[command(name = "report-index")]
+    ReportIndex {
+        /// Path inside the repository
+        #[arg(default_value = ".")]
+        path: PathBuf,
+    },
+    /// Analyze a path twice and check the JSON output is byte-for-byte identical
+    #[command(name = "verify-determinism")]
+    VerifyDeterminism {
+        /// File or directory to analyze
+        #[arg(default_value = ".")]
+        path: PathBuf,
 
-        /// Output file path (for HTML format, default: .hotspots/report.html)
-        #[arg(long)]
-        output: Option<PathBuf>,
-
-        /// Show human-readable risk explanations (only valid with --mode snapshot)
-        #[arg(long)]
-        explain: bool,
-
-        /// Overwrite existing snapshot if it already exists
-        #[arg(short = 'f', long)]
-        force: bool,
-
-        /// Skip writing snapshot to disk (only valid with --mode snapshot or --mode delta)
-        #[arg(long)]
-        no_persist: bool,
-
-        /// Output level for text format: file shows a ranked file risk table
-        #[arg(long, value_name = "LEVEL")]
-        level: Option<OutputLevel>,
-
-        /// Use per-function git log -L for touch metrics (more accurate than file-level
-        /// batching). Results are cached in .hotspots/touch-cache.json.zst — the first
-        /// run on a new commit is slow (~9 ms per uncached function); subsequent runs
-        /// are fast. A warning is printed when 50+ functions need to be fetched.
-        #[arg(long)]
-        per_function_touches: bool,
-
-        /// Disable per-function touch metrics, use file-level batching instead.
-        /// Overrides config and --per-function-touches. Useful for large repos
-        /// where the cold-start per-function git log -L calls dominate CPU time.
-        #[arg(long, conflicts_with = "per_function_touches")]
-        no_per_function_touches: bool,
-
-        /// Skip all touch metrics entirely (no git log calls for churn/recency).
-        /// Overrides --per-function-touches and --no-per-function-touches.
-        /// Use for benchmarking pure analysis + call graph performance.
-        #[arg(long, conflicts_with = "per_function_touches")]
-        skip_touch_metrics: bool,
-
-        /// Output all functions as a flat array (only valid with --mode snapshot --format json)
-        #[arg(long)]
-        all_functions: bool,
-
-        /// Include model risk map data in snapshot JSON/HTML reports.
+        /// Process files in reverse order on the second run
         #[arg(long)]
-        include_models: bool,
+        reverse_order: bool,
 
-        /// Populate and emit pattern details for --explain-patterns
+        /// Path to config file (default: auto-discover)
         #[arg(long)]
-        explain_patterns: bool,
-
-        /// URL of the written analysis post to link from the HTML report (HTML format only)
-        #[arg(long, value_name = "URL")]
-        source_url: Option<String>,
+        config: Option<PathBuf>,
+    },
+    /// Write a README badge (SVG) for the latest snapshot
+    Badge {
+        /// Path inside the repository
+        #[arg(default_value = ".")]
+        path: PathBuf,
 
-        /// Number of parallel worker threads (default: number of logical CPUs)
-        #[arg(long, short = 'j', value_name = "N")]
-        jobs: Option<usize>,
+        /// Number shown on the badge
+        #[arg(long, value_enum, default_value = "critical-count")]
+        metric: BadgeMetric,
 
-        /// Skip all call graph algorithms when the repo exceeds N functions.
-        /// Omits PageRank, betweenness, fan-in/fan-out, SCC, and dependency depth.
-        /// Useful for very large repos where graph computation dominates CPU time.
-        #[arg(long, value_name = "N")]
-        callgraph_skip_above: Option<usize>,
+        /// Write the SVG to this file instead of stdout
+        #[arg(short = 'o', long)]
+        output: Option<PathBuf>,
 
-        /// Skip the suppression gate check (the P@10 calibration that warns when
-        /// the activity ranker may be producing misleading rankings).
-        #[arg(long)]
-        skip_gate: bool,
-
-        /// Hybrid touch mode: run file-level touch first, then per-function only for
-        /// files with touch_count_30d >= N. Balances accuracy and performance for
-        /// large repos. Conflicts with --per-function-touches and --no-per-function-touches.
-        #[arg(long, value_name = "N", conflicts_with_all = ["per_function_touches", "no_per_function_touches"])]
-        hybrid_touches: Option<usize>,
-
-        /// Rank using a Gini-gated cold-start strategy (formula / IsolationForest
-        /// anomaly / uniform prior) instead of a trained ranker. For repos with no
-        /// fix-commit label history yet. Reads no label data. Explicit opt-in only —
-        /// not an automatic fallback when `hotspots train` fails its label threshold.
+        /// Path to config file (default: auto-discover)
         #[arg(long)]
-        cold_start: bool,
+        config: Option<PathBuf>,
     },
     /// Prune unreachable snapshots
     Prune {
@@ -152,6 +115,11 @@ enum Commands {
         /// Dry-run mode (report what would be pruned without actually deleting)
         #[arg(long)]
         dry_run: bool,
+
+        /// Ref pattern whose commits are kept, e.g. `refs/remotes/origin/` (repeatable;
+        /// default: local branches, plus remote-tracking branches when there is at most one)
+        #[arg(long = "refs", value_name = "PATTERN")]
+        refs: Vec<String>,
     },
     /// Compact history to reduce storage
     Compact {
@@ -179,6 +147,26 @@ enum Commands {
         /// Top K functions for hotspot analysis
         #[arg(long, default_value = "5")]
         top: usize,
+
+        /// Snapshots required before velocity/stability are labelled; fewer report
+        /// `insufficient_data` (default: `trends_min_snapshots` config, else 5)
+        #[arg(long)]
+        min_snapshots: Option<usize>,
+
+        /// Only include functions in files whose primary author (most lines
+        /// added, mailmap-canonical name, case-insensitive) is NAME
+        #[arg(long, value_name = "NAME")]
+        author: Option<String>,
+
+        /// Don't warn when snapshots in the window were written by different
+        /// major/minor hotspots releases
+        #[arg(long)]
+        ignore_version_skew: bool,
+
+        /// Also report an EWMA-smoothed velocity with this alpha in (0, 1];
+        /// directions then follow the smoothed value
+        #[arg(long, value_name = "ALPHA")]
+        smoothing: Option<f64>,
     },
     /// Validate a configuration file
     #[command(name = "config")]
@@ -226,6 +214,31 @@ enum Commands {
         /// Analyze missing refs automatically using git worktrees
         #[arg(long)]
         auto_analyze: bool,
+
+        /// Don't warn when the two snapshots were written by different
+        /// major/minor hotspots releases
+        #[arg(long)]
+        ignore_version_skew: bool,
+    },
+    /// Compare risk between two directory trees, without git snapshots
+    Compare {
+        /// Directory holding the "before" version
+        before: PathBuf,
+
+        /// Directory holding the "after" version
+        after: PathBuf,
+
+        /// Output format
+        #[arg(long, default_value = "text")]
+        format: CompareFormat,
+
+        /// Write output to file instead of stdout (HTML default: .hotspots/delta-report.html)
+        #[arg(long)]
+        output: Option<PathBuf>,
+
+        /// Path to config file (default: auto-discover)
+        #[arg(long)]
+        config: Option<PathBuf>,
     },
     /// Train a local RandomForest ranker from fix-commit history
     Train {
@@ -233,9 +246,9 @@ enum Commands {
         #[arg(default_value = ".")]
         path: PathBuf,
 
-        /// Output path for the trained model (JSON)
-        #[arg(long, default_value = ".hotspots/ranker.json")]
-        output: PathBuf,
+        /// Output path for the trained model (JSON) (default: ranker.json in the storage directory)
+        #[arg(long)]
+        output: Option<PathBuf>,
 
         /// Days of git history to scan for fix-commit labels
         #[arg(long, default_value = "365")]
@@ -283,13 +296,392 @@ enum Commands {
     },
 }
 
+// Boxed in `Commands` so the other subcommands don't pay for its size
+#[derive(clap::Args)]
+struct AnalyzeCommand {
+    /// Path to source file or directory
+    path: PathBuf,
+
+    /// Output format
+    #[arg(long, default_value = "text")]
+    format: OutputFormat,
+
+    /// Output mode (snapshot, delta, or models)
+    #[arg(long)]
+    mode: Option<OutputMode>,
+
+    /// Evaluate policies (only valid with --mode delta)
+    #[arg(long)]
+    policy: bool,
+
+    /// Show only top N results (overrides config file)
+    #[arg(long)]
+    top: Option<usize>,
+
+    /// Emit only the top N functions in snapshot JSON/JSONL output, after
+    /// aggregates are computed over all functions (--mode snapshot with
+    /// --format jsonl or --all-functions; 0 = all)
+    #[arg(long, value_name = "N")]
+    top_output: Option<usize>,
+
+    /// Minimum LRS threshold (overrides config file)
+    #[arg(long)]
+    min_lrs: Option<f64>,
+
+    /// Path to config file (default: auto-discover)
+    #[arg(long)]
+    config: Option<PathBuf>,
+
+    /// Output file path (for HTML format, default: .hotspots/report.html)
+    #[arg(long)]
+    output: Option<PathBuf>,
+
+    /// Write FORMAT (json, jsonl, html, or sarif) to PATH. Repeat to render
+    /// several artifacts from one analysis, e.g. `--emit html:report.html
+    /// --emit sarif:results.sarif`. Replaces --format and --output (--mode
+    /// snapshot only).
+    #[arg(long, value_name = "FORMAT:PATH", conflicts_with = "output")]
+    emit: Vec<EmitTarget>,
+
+    /// Show risk explanations for top functions (only valid with --mode snapshot; text or JSON)
+    #[arg(long)]
+    explain: bool,
+
+    /// Overwrite existing snapshot if it already exists
+    #[arg(short = 'f', long)]
+    force: bool,
+
+    /// Skip writing snapshot to disk (only valid with --mode snapshot or --mode delta)
+    #[arg(long)]
+    no_persist: bool,
+
+    /// Compute and evaluate --policy without writing snapshots or emitting reports;
+    /// print a one-line summary and exit 1 on blocking failures (--mode snapshot or delta)
+    #[arg(long)]
+    check: bool,
+
+    /// Output level for text format: file shows a ranked file risk table
+    #[arg(long, value_name = "LEVEL")]
+    level: Option<OutputLevel>,
+
+    /// Use per-function git log -L for touch metrics (more accurate than file-level
+    /// batching). Results are cached in .hotspots/touch-cache.json.zst — the first
+    /// run on a new commit is slow (~9 ms per uncached function); subsequent runs
+    /// are fast. A warning is printed when 50+ functions need to be fetched.
+    #[arg(long)]
+    per_function_touches: bool,
+
+    /// Disable per-function touch metrics, use file-level batching instead.
+    /// Overrides config and --per-function-touches. Useful for large repos
+    /// where the cold-start per-function git log -L calls dominate CPU time.
+    #[arg(long, conflicts_with = "per_function_touches")]
+    no_per_function_touches: bool,
+
+    /// Skip all touch metrics entirely (no git log calls for churn/recency).
+    /// Overrides --per-function-touches and --no-per-function-touches.
+    /// Use for benchmarking pure analysis + call graph performance.
+    #[arg(long, conflicts_with = "per_function_touches")]
+    skip_touch_metrics: bool,
+
+    /// Structural metrics only: no git, touch, churn, call graph, or co-change
+    /// work, so it runs outside a git repository. Without --mode, skips the
+    /// trained ranker; with --mode snapshot, emits a snapshot holding only
+    /// metrics, LRS, and band (--format json or jsonl; never persisted).
+    #[arg(long)]
+    metrics_only: bool,
+
+    /// With --metrics-only --mode snapshot, approximate recency from file
+    /// modification times, counted back from the newest file, and fold it into
+    /// activity risk. Mtimes vary across clones and copies, so output is no
+    /// longer reproducible across machines.
+    #[arg(long)]
+    use_mtime: bool,
+
+    /// Output all functions as a flat array (only valid with --mode snapshot --format json)
+    #[arg(long)]
+    all_functions: bool,
+
+    /// Include model risk map data in snapshot JSON/HTML reports.
+    #[arg(long)]
+    include_models: bool,
+
+    /// Populate and emit pattern details for --explain-patterns
+    #[arg(long)]
+    explain_patterns: bool,
+
+    /// URL of the written analysis post to link from the HTML report (HTML format only)
+    #[arg(long, value_name = "URL")]
+    source_url: Option<String>,
+
+    /// Stylesheet appended after the built-in HTML report CSS (HTML format only)
+    #[arg(long, value_name = "FILE")]
+    html_css: Option<PathBuf>,
+
+    /// Title for the HTML report page and heading (HTML format only)
+    #[arg(long, value_name = "TITLE")]
+    html_title: Option<String>,
+
+    /// Number of parallel worker threads (default: number of logical CPUs)
+    #[arg(long, short = 'j', value_name = "N")]
+    jobs: Option<usize>,
+
+    /// Skip all call graph algorithms when the repo exceeds N functions.
+    /// Omits PageRank, betweenness, fan-in/fan-out, SCC, and dependency depth.
+    /// Useful for very large repos where graph computation dominates CPU time.
+    #[arg(long, value_name = "N")]
+    callgraph_skip_above: Option<usize>,
+
+    /// Skip the suppression gate check (the P@10 calibration that warns when
+    /// the activity ranker may be producing misleading rankings).
+    #[arg(long)]
+    skip_gate: bool,
+
+    /// Hybrid touch mode: run file-level touch first, then per-function only for
+    /// files with touch_count_30d >= N. Balances accuracy and performance for
+    /// large repos. Conflicts with --per-function-touches and --no-per-function-touches.
+    #[arg(long, value_name = "N", conflicts_with_all = ["per_function_touches", "no_per_function_touches"])]
+    hybrid_touches: Option<usize>,
+
+    /// Rank using a Gini-gated cold-start strategy (formula / IsolationForest
+    /// anomaly / uniform prior) instead of a trained ranker. For repos with no
+    /// fix-commit label history yet. Reads no label data. Explicit opt-in only —
+    /// not an automatic fallback when `hotspots train` fails its label threshold.
+    #[arg(long)]
+    cold_start: bool,
+
+    /// Explain how one function's score was derived: raw metrics, the weights
+    /// and thresholds applied (with the config file each came from), the LRS
+    /// breakdown, and the resulting band. Accepts a full function ID
+    /// (`path/to/file.ts::name`), a path-suffix ID, or a bare function name.
+    #[arg(long, value_name = "FUNCTION_ID", conflicts_with_all = ["mode", "cold_start"])]
+    why: Option<String>,
+
+    /// Print the full metric/risk breakdown (metrics, LRS, band, patterns) for
+    /// functions with this name only. Matching functions in several files are
+    /// an error unless `--file` picks one. Text or JSON output.
+    #[arg(
+        long,
+        value_name = "NAME",
+        conflicts_with_all = ["mode", "cold_start", "why", "baseline_diff"]
+    )]
+    function: Option<String>,
+
+    /// With `--function`, only consider functions in files whose path ends with PATH
+    #[arg(long, value_name = "PATH", requires = "function")]
+    file: Option<String>,
+
+    /// List every path that would not be analyzed, with the reason (symlink,
+    /// ignored directory, declaration file, unsupported extension, excluded,
+    /// too large), instead of analyzing. Text or JSON output.
+    #[arg(
+        long,
+        conflicts_with_all = ["mode", "cold_start", "why", "function", "baseline_diff"]
+    )]
+    list_skipped: bool,
+
+    /// Skip source files larger than this many bytes (overrides config
+    /// `max_file_bytes`; default 2 MiB, 0 = no limit).
+    #[arg(long, value_name = "BYTES")]
+    max_file_size: Option<u64>,
+
+    /// Flag functions longer than this many lines as `long_function` and, with
+    /// `--mode delta --policy`, run the `function-too-long` policy (overrides
+    /// config `max_function_loc`).
+    #[arg(long, value_name = "LINES")]
+    max_function_loc: Option<usize>,
+
+    /// Unit of analysis: `function` (default), or `file` for one entry per file
+    /// combining its top-level code and all its functions (overrides config
+    /// `granularity`). For scripts without clean function boundaries.
+    #[arg(long, value_enum)]
+    granularity: Option<Granularity>,
+
+    /// Only analyze files matching this glob (repeatable). Merged with the
+    /// config file's `include` list unless `--exclude-only` is set.
+    #[arg(long = "include", value_name = "GLOB")]
+    include_globs: Vec<String>,
+
+    /// Skip files matching this glob (repeatable). Merged with the config
+    /// file's `exclude` list unless `--exclude-only` is set.
+    #[arg(long = "exclude", value_name = "GLOB")]
+    exclude_globs: Vec<String>,
+
+    /// Only analyze files in this language (repeatable), e.g. `rust` or
+    /// `typescript`. Merged with the config file's `include_languages`.
+    #[arg(long = "include-lang", value_name = "LANG")]
+    include_langs: Vec<String>,
+
+    /// Skip files in this language (repeatable). Merged with the config file's
+    /// `exclude_languages`.
+    #[arg(long = "exclude-lang", value_name = "LANG")]
+    exclude_langs: Vec<String>,
+
+    /// Use only the `--include`/`--exclude` flags, replacing the config file's
+    /// include/exclude lists instead of merging with them. Built-in default
+    /// excludes (tests, node_modules, dist, vendored dirs) still apply.
+    #[arg(long)]
+    exclude_only: bool,
+
+    /// Fail instead of warning when the repository is a shallow clone, since
+    /// churn, touch, and co-change metrics would be computed from truncated history.
+    #[arg(long)]
+    require_full_history: bool,
+
+    /// Fail when analysis finds no functions at all, e.g. because the path is
+    /// wrong or an exclude matches everything (overrides config `require_functions`).
+    #[arg(long)]
+    require_functions: bool,
+
+    /// Print plain "analyzed N/M files" progress lines to stderr every couple of
+    /// seconds, even when stderr is not a terminal. Stdout is unaffected.
+    #[arg(long)]
+    progress: bool,
+
+    /// Add git-authorship bus factor and single-owner knowledge-risk flags to
+    /// file risk (snapshot mode only; mines the full history, so opt-in)
+    #[arg(long)]
+    with_authors: bool,
+
+    /// Only show functions in files whose primary author (most lines added,
+    /// mailmap-canonical name, case-insensitive) is NAME (snapshot mode only)
+    #[arg(long, value_name = "NAME")]
+    author: Option<String>,
+
+    /// Limit `--explain` output to functions whose lines changed since the
+    /// parent commit (`git diff`, including uncommitted changes). Percentiles
+    /// and the summary still cover the whole repo (snapshot mode only)
+    #[arg(long)]
+    only_changed_functions: bool,
+
+    /// Join an LCOV tracefile (e.g. `lcov.info`) onto the snapshot: each
+    /// function gets `coverage`, the covered share of its instrumented lines,
+    /// and high-LRS functions under the coverage threshold get the
+    /// `risky_untested` pattern (snapshot mode only)
+    #[arg(long, value_name = "LCOV")]
+    coverage: Option<PathBuf>,
+
+    /// Build the snapshot from the parent commit's persisted snapshot,
+    /// re-analyzing only files changed since the parent (snapshot mode only).
+    /// Falls back to a full analysis when the parent has no snapshot.
+    #[arg(long)]
+    incremental: bool,
+
+    /// Don't warn when the parent snapshot was written by a different
+    /// major/minor hotspots release (delta mode only)
+    #[arg(long)]
+    ignore_version_skew: bool,
+
+    /// Diff against the merge-base of HEAD and BRANCH, analyzed on the spot
+    /// from a temporary worktree, instead of a persisted parent snapshot
+    /// (delta mode only). Needs no `.hotspots` history; nothing is persisted.
+    #[arg(long, value_name = "BRANCH")]
+    baseline_branch: Option<String>,
+
+    /// Analyze only the files `git diff --name-only A..B` lists, instead of
+    /// walking the tree (without --mode only). Files are read from the working
+    /// tree; deleted files and config-excluded paths are skipped.
+    #[arg(long, value_name = "A..B")]
+    paths_from_rev_range: Option<String>,
+
+    /// Order functions in text/HTML output: `source` (by file, then line),
+    /// `id` (by function ID), `cc-density` (CC per source line, densest
+    /// first), `priority` (by `priority_score`, highest first), or `leverage`
+    /// (by `refactor_leverage`, highest first); the last two need `--mode
+    /// snapshot`. Default: by risk. Presentation only; snapshots on disk stay
+    /// ordered by function ID.
+    #[arg(long, value_enum)]
+    order: Option<FunctionOrder>,
+
+    /// Rank functions by a metric before `--top` truncation (default output
+    /// without --mode only): `cc`, `nd`, `fo`, `ns`, `loc`, or `lrs` (largest
+    /// first), or `file` (by path, then line). Default: `lrs`. Text output
+    /// keeps its risk bands and applies the ranking within each band.
+    #[arg(long, value_enum, conflicts_with = "order")]
+    sort: Option<SortKey>,
+
+    /// Diff the working tree against a committed baseline report and print only
+    /// functions whose LRS regressed. PATH defaults to `.hotspots/baseline.json`
+    /// under the project root; create it with `hotspots analyze . --format json`.
+    /// Needs no git history or snapshots. Exits 1 if anything regressed.
+    #[arg(
+        long,
+        value_name = "PATH",
+        num_args = 0..=1,
+        conflicts_with_all = ["mode", "cold_start", "why"]
+    )]
+    baseline_diff: Option<Option<PathBuf>>,
+
+    /// Replace file paths and function names with salted hashes (salt from the
+    /// config's `redact_salt`), keeping all metrics. For sharing results
+    /// externally; works with default text/JSON output, or `--mode snapshot`
+    /// with `--format jsonl` or `--all-functions`.
+    #[arg(long)]
+    redact: bool,
+
+    /// Suppress warnings, notices, and progress on stderr (errors are still reported)
+    #[arg(long, short = 'q')]
+    quiet: bool,
+}
+
 #[derive(Clone, Copy, clap::ValueEnum)]
 pub(crate) enum OutputFormat {
     Text,
     Json,
     Html,
+    /// One compact JSON object per line; also accepted as `ndjson`
+    #[value(alias = "ndjson")]
     Jsonl,
     Sarif,
+    Canonical,
+}
+
+/// Output formats `compare` can produce (no SARIF or canonical form for a delta)
+#[derive(Clone, Copy, clap::ValueEnum)]
+pub(crate) enum CompareFormat {
+    Text,
+    Json,
+    Html,
+    /// One compact JSON object per line; also accepted as `ndjson`
+    #[value(alias = "ndjson")]
+    Jsonl,
+}
+
+impl From<CompareFormat> for OutputFormat {
+    fn from(format: CompareFormat) -> Self {
+        match format {
+            CompareFormat::Text => OutputFormat::Text,
+            CompareFormat::Json => OutputFormat::Json,
+            CompareFormat::Html => OutputFormat::Html,
+            CompareFormat::Jsonl => OutputFormat::Jsonl,
+        }
+    }
+}
+
+/// One `--emit FORMAT:PATH` artifact
+#[derive(Clone)]
+pub(crate) struct EmitTarget {
+    pub format: OutputFormat,
+    pub path: PathBuf,
+}
+
+impl std::str::FromStr for EmitTarget {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (format, path) = s
+            .split_once(':')
+            .filter(|(_, path)| !path.is_empty())
+            .ok_or_else(|| format!("expected FORMAT:PATH, got '{s}'"))?;
+        let format = <OutputFormat as clap::ValueEnum>::from_str(format, true)
+            .map_err(|_| format!("unknown format '{format}'"))?;
+        if matches!(format, OutputFormat::Text) {
+            return Err("text output goes to stdout; use --format text instead".to_string());
+        }
+        Ok(EmitTarget {
+            format,
+            path: PathBuf::from(path),
+        })
+    }
 }
 
 #[derive(Clone, Copy, PartialEq, clap::ValueEnum)]
@@ -305,66 +697,205 @@ pub(crate) enum OutputLevel {
     Module,
 }
 
+#[derive(Clone, Copy, PartialEq, clap::ValueEnum)]
+pub(crate) enum ImportsFormat {
+    Dot,
+    Json,
+}
+
+#[derive(Clone, Copy, PartialEq, clap::ValueEnum)]
+pub(crate) enum BadgeMetric {
+    /// Functions in the critical or high band
+    CriticalCount,
+    /// Total activity risk
+    ActivityRisk,
+}
+
+#[derive(Clone, Copy, PartialEq, clap::ValueEnum)]
+pub(crate) enum Granularity {
+    Function,
+    File,
+}
+
+#[derive(Clone, Copy, PartialEq, clap::ValueEnum)]
+pub(crate) enum FunctionOrder {
+    Source,
+    Id,
+    CcDensity,
+    Priority,
+    Leverage,
+}
+
+#[derive(Clone, Copy, PartialEq, clap::ValueEnum)]
+pub(crate) enum SortKey {
+    Cc,
+    Nd,
+    Fo,
+    Ns,
+    Lrs,
+    Loc,
+    File,
+}
+
 fn main() -> anyhow::Result<()> {
     let cli = Cli::parse();
+    if let Some(store_dir) = cli.store_dir {
+        hotspots_core::snapshot::set_store_dir(store_dir);
+    }
 
     match cli.command {
-        Commands::Analyze {
+        Commands::Analyze(args) => {
+            let AnalyzeCommand {
+                path,
+                format,
+                mode,
+                policy,
+                top,
+                top_output,
+                min_lrs,
+                config: config_path,
+                output,
+                emit,
+                explain,
+                force,
+                no_persist,
+                check,
+                level,
+                per_function_touches,
+                no_per_function_touches,
+                skip_touch_metrics,
+                metrics_only,
+                use_mtime,
+                all_functions,
+                include_models,
+                explain_patterns,
+                source_url,
+                html_css,
+                html_title,
+                jobs,
+                callgraph_skip_above,
+                hybrid_touches,
+                skip_gate,
+                cold_start,
+                why,
+                function,
+                file,
+                list_skipped,
+                max_file_size,
+                max_function_loc,
+                granularity,
+                include_globs,
+                exclude_globs,
+                include_langs,
+                exclude_langs,
+                exclude_only,
+                require_full_history,
+                require_functions,
+                progress,
+                with_authors,
+                author,
+                only_changed_functions,
+                coverage,
+                incremental,
+                ignore_version_skew,
+                baseline_branch,
+                paths_from_rev_range,
+                order,
+                sort,
+                baseline_diff,
+                redact,
+                quiet,
+            } = *args;
+            cmd::analyze::handle_analyze(AnalyzeArgs {
+                path,
+                format,
+                mode,
+                policy,
+                top,
+                top_output,
+                min_lrs,
+                config_path,
+                output,
+                emit,
+                explain,
+                force,
+                no_persist,
+                check,
+                level,
+                per_function_touches,
+                no_per_function_touches,
+                skip_touch_metrics,
+                metrics_only,
+                use_mtime,
+                all_functions,
+                include_models,
+                explain_patterns,
+                source_url,
+                html_css,
+                html_title,
+                jobs,
+                callgraph_skip_above,
+                hybrid_touches,
+                skip_gate,
+                cold_start,
+                why,
+                function,
+                file,
+                list_skipped,
+                max_file_size,
+                max_function_loc,
+                granularity,
+                include_globs,
+                exclude_globs,
+                include_langs,
+                exclude_langs,
+                exclude_only,
+                require_full_history,
+                require_functions,
+                progress,
+                with_authors,
+                author,
+                only_changed_functions,
+                coverage,
+                incremental,
+                ignore_version_skew,
+                baseline_branch,
+                paths_from_rev_range,
+                order,
+                sort,
+                baseline_diff,
+                redact,
+                quiet,
+            })?
+        }
+        Commands::DumpAst { file, function } => cmd::dump_ast::handle_dump_ast(&file, &function)?,
+        Commands::Imports {
             path,
             format,
-            mode,
-            policy,
-            top,
-            min_lrs,
-            config: config_path,
-            output,
-            explain,
-            force,
-            no_persist,
-            level,
-            per_function_touches,
-            no_per_function_touches,
-            skip_touch_metrics,
-            all_functions,
-            include_models,
-            explain_patterns,
-            source_url,
-            jobs,
-            callgraph_skip_above,
-            hybrid_touches,
-            skip_gate,
-            cold_start,
-        } => cmd::analyze::handle_analyze(AnalyzeArgs {
+            config,
+        } => cmd::imports::handle_imports(path, format, config.as_deref())?,
+        Commands::ReportIndex { path } => cmd::report_index::handle_report_index(path)?,
+        Commands::VerifyDeterminism {
             path,
-            format,
-            mode,
-            policy,
-            top,
-            min_lrs,
-            config_path,
+            reverse_order,
+            config,
+        } => cmd::verify_determinism::handle_verify_determinism(
+            path,
+            reverse_order,
+            config.as_deref(),
+        )?,
+        Commands::Badge {
+            path,
+            metric,
             output,
-            explain,
-            force,
-            no_persist,
-            level,
-            per_function_touches,
-            no_per_function_touches,
-            skip_touch_metrics,
-            all_functions,
-            include_models,
-            explain_patterns,
-            source_url,
-            jobs,
-            callgraph_skip_above,
-            hybrid_touches,
-            skip_gate,
-            cold_start,
-        })?,
+            config,
+        } => cmd::badge::handle_badge(path, metric, output, config.as_deref())?,
         Commands::Prune {
             unreachable,
             older_than,
             dry_run,
-        } => cmd::prune::handle_prune(unreachable, older_than, dry_run)?,
+            refs,
+        } => cmd::prune::handle_prune(unreachable, older_than, dry_run, refs)?,
         Commands::Compact { level, dry_run } => cmd::compact::handle_compact(level, dry_run)?,
         Commands::Config { action } => cmd::config::handle_config(action)?,
         Commands::Trends {
@@ -372,7 +903,20 @@ fn main() -> anyhow::Result<()> {
             format,
             window,
             top,
-        } => cmd::trends::handle_trends(path, format, window, top)?,
+            min_snapshots,
+            author,
+            ignore_version_skew,
+            smoothing,
+        } => cmd::trends::handle_trends(TrendsArgs {
+            path,
+            format,
+            window,
+            top,
+            min_snapshots,
+            author,
+            ignore_version_skew,
+            smoothing,
+        })?,
         Commands::Init { hooks, ci } => cmd::init::handle_init(hooks, ci)?,
         Commands::Diff {
             base,
@@ -383,6 +927,7 @@ fn main() -> anyhow::Result<()> {
             top,
             config,
             auto_analyze,
+            ignore_version_skew,
         } => cmd::diff::handle_diff(DiffArgs {
             base,
             head,
@@ -392,6 +937,20 @@ fn main() -> anyhow::Result<()> {
             top,
             config_path: config,
             auto_analyze,
+            ignore_version_skew,
+        })?,
+        Commands::Compare {
+            before,
+            after,
+            format,
+            output,
+            config,
+        } => cmd::compare::handle_compare(CompareArgs {
+            before,
+            after,
+            format: format.into(),
+            output,
+            config_path: config,
         })?,
         Commands::Train {
             path,
@@ -422,3 +981,51 @@ fn main() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_emit_target_parses_format_and_path() {
+        let target: EmitTarget = "html:out/report.html".parse().unwrap();
+        assert!(matches!(target.format, OutputFormat::Html));
+        assert_eq!(target.path, PathBuf::from("out/report.html"));
+
+        // Case-insensitive format and aliases; the path keeps any later colon
+        let target: EmitTarget = "NDJSON:C:/out.jsonl".parse().unwrap();
+        assert!(matches!(target.format, OutputFormat::Jsonl));
+        assert_eq!(target.path, PathBuf::from("C:/out.jsonl"));
+    }
+
+    #[test]
+    fn test_emit_target_rejects_bad_targets() {
+        for bad in ["json", "json:", ":out.json", "xml:out.xml", "text:out.txt"] {
+            assert!(bad.parse::<EmitTarget>().is_err(), "{bad} should not parse");
+        }
+    }
+
+    #[test]
+    fn test_emit_is_repeatable() {
+        let cli = Cli::try_parse_from([
+            "hotspots",
+            "analyze",
+            ".",
+            "--emit",
+            "json:a.json",
+            "--emit",
+            "json:b.json",
+        ])
+        .unwrap();
+        let Commands::Analyze(args) = cli.command else {
+            panic!("expected analyze");
+        };
+        let paths: Vec<_> = args.emit.iter().map(|t| t.path.clone()).collect();
+        assert_eq!(
+            paths,
+            vec![PathBuf::from("a.json"), PathBuf::from("b.json")]
+        );
+
+        assert!(Cli::try_parse_from(["hotspots", "analyze", ".", "--emit", "bogus"]).is_err());
+    }
+}
@@ -0,0 +1,45 @@
+use crate::util::truncate_string;
+use hotspots_core::delta::Delta;
+use std::fmt::Write;
+
+/// Render the regressions left in a baseline-file delta as a text table.
+pub(crate) fn render_baseline_regressions(delta: &Delta) -> anyhow::Result<String> {
+    let mut out = String::new();
+    writeln!(out, "Baseline Regressions")?;
+    writeln!(out, "{}", "=".repeat(80))?;
+    if delta.deltas.is_empty() {
+        writeln!(out, "No function regressed past the baseline.")?;
+        return Ok(out);
+    }
+    writeln!(
+        out,
+        "{:<40} {:<16} {:<16} {:<10}",
+        "Function", "Before", "After", "ΔLRS"
+    )?;
+    writeln!(out, "{}", "-".repeat(85))?;
+    for entry in &delta.deltas {
+        let state = |s: Option<&hotspots_core::delta::FunctionState>| {
+            s.map(|s| format!("{:.2} {}", s.lrs, s.band.as_str()))
+                .unwrap_or_else(|| "N/A".to_string())
+        };
+        let delta_lrs = entry
+            .delta
+            .as_ref()
+            .map(|d| format!("+{:.2}", d.lrs))
+            .unwrap_or_else(|| "N/A".to_string());
+        writeln!(
+            out,
+            "{:<40} {:<16} {:<16} {:<10}",
+            truncate_string(&entry.function_id, 40),
+            state(entry.before.as_ref()),
+            state(entry.after.as_ref()),
+            delta_lrs
+        )?;
+    }
+    writeln!(
+        out,
+        "\n{} function(s) regressed past the baseline.",
+        delta.deltas.len()
+    )?;
+    Ok(out)
+}
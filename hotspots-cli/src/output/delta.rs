@@ -0,0 +1,166 @@
+use crate::util::truncate_string;
+use hotspots_core::delta::{Delta, DeltaStatus, FunctionStatus};
+use std::fmt::Write;
+
+/// Rows shown in the regressions table when `--top` is not given
+const DEFAULT_TOP_REGRESSIONS: usize = 10;
+
+/// Render a delta without policy evaluation: change counts plus the
+/// functions whose LRS rose the most, largest first.
+pub(crate) fn render_delta_text_output(
+    delta: &Delta,
+    top: Option<usize>,
+) -> anyhow::Result<String> {
+    let count = |status: FunctionStatus| delta.deltas.iter().filter(|e| e.status == status).count();
+
+    let mut out = String::new();
+    writeln!(out, "Delta Analysis")?;
+    writeln!(out, "{}", "=".repeat(80))?;
+    if delta.status != DeltaStatus::Changes {
+        writeln!(out, "{}", delta.status.describe())?;
+    }
+    writeln!(
+        out,
+        "{} new, {} modified, {} deleted functions",
+        count(FunctionStatus::New),
+        count(FunctionStatus::Modified),
+        count(FunctionStatus::Deleted)
+    )?;
+    write_risk_removed(&mut out, delta)?;
+
+    let mut regressions: Vec<_> = delta
+        .deltas
+        .iter()
+        .filter_map(|e| e.delta.as_ref().map(|d| (e, d.lrs)))
+        .filter(|(_, lrs)| *lrs > 0.0)
+        .collect();
+    if regressions.is_empty() {
+        writeln!(out, "\nNo function's LRS increased.")?;
+        return Ok(out);
+    }
+    regressions.sort_by(|(a, a_lrs), (b, b_lrs)| {
+        b_lrs
+            .total_cmp(a_lrs)
+            .then_with(|| a.function_id.cmp(&b.function_id))
+    });
+    // 0 = show all, as for the other `--top` tables
+    let shown = match top {
+        Some(0) => regressions.len(),
+        Some(n) => n.min(regressions.len()),
+        None => DEFAULT_TOP_REGRESSIONS.min(regressions.len()),
+    };
+
+    writeln!(out, "\nTop regressions by ΔLRS:")?;
+    writeln!(
+        out,
+        "{:<40} {:<16} {:<16} {:<10} Driven by",
+        "Function", "Before", "After", "ΔLRS"
+    )?;
+    writeln!(out, "{}", "-".repeat(100))?;
+    for (entry, delta_lrs) in &regressions[..shown] {
+        let state = |s: Option<&hotspots_core::delta::FunctionState>| {
+            s.map(|s| format!("{:.2} {}", s.lrs, s.band.as_str()))
+                .unwrap_or_else(|| "N/A".to_string())
+        };
+        writeln!(
+            out,
+            "{:<40} {:<16} {:<16} {:<10} {}",
+            truncate_string(&entry.function_id, 40),
+            state(entry.before.as_ref()),
+            state(entry.after.as_ref()),
+            format!("{:+.2}", delta_lrs),
+            entry.driver_summary().unwrap_or_else(|| "-".to_string())
+        )?;
+    }
+    if shown < regressions.len() {
+        writeln!(
+            out,
+            "... {} more regressed (use --top to show more)",
+            regressions.len() - shown
+        )?;
+    }
+    Ok(out)
+}
+
+/// One "Risk removed" line crediting deleted functions, when there are any
+pub(crate) fn write_risk_removed(out: &mut String, delta: &Delta) -> anyhow::Result<()> {
+    let Some(removed) = &delta.risk_removed else {
+        return Ok(());
+    };
+    let mut line = format!(
+        "Risk removed: {} deleted function{}",
+        removed.functions,
+        if removed.functions == 1 { "" } else { "s" }
+    );
+    if removed.critical > 0 || removed.high > 0 {
+        line.push_str(&format!(
+            " ({} critical, {} high)",
+            removed.critical, removed.high
+        ));
+    }
+    line.push_str(&format!(", -{:.2} LRS", removed.lrs));
+    if let Some(activity_risk) = removed.activity_risk {
+        line.push_str(&format!(", -{:.2} activity risk", activity_risk));
+    }
+    writeln!(out, "{line}")?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hotspots_core::delta::DeltaCommitInfo;
+    use hotspots_core::language::Language;
+
+    /// A delta in which `n` functions each gained one branch
+    fn regressed_delta(n: usize) -> Delta {
+        let before: String = (0..n)
+            .map(|i| format!("function f{i}(x: number) {{\n  return x;\n}}\n"))
+            .collect();
+        let after: String = (0..n)
+            .map(|i| {
+                format!("function f{i}(x: number) {{\n  if (x > {i}) {{\n    return x;\n  }}\n  return 0;\n}}\n")
+            })
+            .collect();
+        let deltas =
+            hotspots_core::delta::diff_sources(&before, &after, Language::TypeScript).unwrap();
+        Delta {
+            schema_version: 1,
+            commit: DeltaCommitInfo {
+                sha: "head".to_string(),
+                parent: "base".to_string(),
+            },
+            baseline: false,
+            status: DeltaStatus::Changes,
+            deltas,
+            policy: None,
+            aggregates: None,
+            risk_removed: None,
+        }
+    }
+
+    fn table_rows(out: &str) -> usize {
+        out.lines().filter(|l| l.starts_with("source.ts::")).count()
+    }
+
+    #[test]
+    fn test_regressions_table_honors_top() {
+        let delta = regressed_delta(12);
+
+        let out = render_delta_text_output(&delta, None).unwrap();
+        assert_eq!(table_rows(&out), DEFAULT_TOP_REGRESSIONS);
+        assert!(out.contains("... 2 more regressed"));
+
+        let out = render_delta_text_output(&delta, Some(3)).unwrap();
+        assert_eq!(table_rows(&out), 3);
+        assert!(out.contains("... 9 more regressed"));
+    }
+
+    #[test]
+    fn test_regressions_table_top_zero_shows_all() {
+        let delta = regressed_delta(12);
+        let out = render_delta_text_output(&delta, Some(0)).unwrap();
+        assert_eq!(table_rows(&out), 12);
+        assert!(!out.contains("more regressed"));
+    }
+}
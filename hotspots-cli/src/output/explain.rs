@@ -28,6 +28,10 @@ pub(crate) fn print_file_risk_output(
             "   Functions: {} | LOC: {} | Max CC: {} | Avg CC: {:.1}",
             view.function_count, view.loc, view.max_cc, view.avg_cc
         );
+        println!(
+            "   Avg function LOC: {:.1} | Functions/KLOC: {:.1}",
+            view.avg_function_loc, view.functions_per_kloc
+        );
         println!("   Risk Score: {:.2}", view.file_risk_score);
         if view.file_churn > 0 {
             println!("   Churn: {} lines changed (30 days)", view.file_churn);
@@ -35,6 +39,21 @@ pub(crate) fn print_file_risk_output(
         if view.critical_count > 0 {
             println!("   Critical functions: {}", view.critical_count);
         }
+        if let (Some(bus_factor), Some(author), Some(share)) =
+            (view.bus_factor, &view.top_author, view.top_author_share)
+        {
+            println!(
+                "   Bus factor: {} | Top author: {} ({:.0}%){}",
+                bus_factor,
+                author,
+                share * 100.0,
+                if view.knowledge_risk {
+                    " | KNOWLEDGE RISK"
+                } else {
+                    ""
+                }
+            );
+        }
         println!();
     }
 
@@ -66,27 +85,41 @@ pub(crate) fn print_module_output(
     println!("{}", "=".repeat(80));
     println!();
     println!(
-        "{:<3} {:<40} {:>5} {:>5} {:>7} {:>9} {:>9} {:>11} {:>5}",
-        "#", "module", "files", "fns", "avg_cc", "afferent", "efferent", "instability", "risk"
+        "{:<3} {:<40} {:>5} {:>5} {:>7} {:>9} {:>7} {:>9} {:>9} {:>11} {:>5} {:>10}",
+        "#",
+        "module",
+        "files",
+        "fns",
+        "fn_loc",
+        "fns/kloc",
+        "avg_cc",
+        "afferent",
+        "efferent",
+        "instability",
+        "risk",
+        "test_ratio"
     );
-    println!("{}", "-".repeat(98));
+    println!("{}", "-".repeat(127));
 
     for (i, m) in modules.iter().take(display_count).enumerate() {
         println!(
-            "{:<3} {:<40} {:>5} {:>5} {:>7.1} {:>9} {:>9} {:>11.3} {:>5}",
+            "{:<3} {:<40} {:>5} {:>5} {:>7.1} {:>9.1} {:>7.1} {:>9} {:>9} {:>11.3} {:>5} {:>10.2}",
             i + 1,
             truncate_string(&m.module, 40),
             m.file_count,
             m.function_count,
+            m.avg_function_loc,
+            m.functions_per_kloc,
             m.avg_complexity,
             m.afferent,
             m.efferent,
             m.instability,
             m.module_risk,
+            m.test_loc_ratio,
         );
     }
 
-    println!("{}", "-".repeat(98));
+    println!("{}", "-".repeat(127));
     println!("Showing {}/{} modules", display_count, total);
 
     let high_risk_count = modules
@@ -105,9 +138,13 @@ pub(crate) fn print_module_output(
 }
 
 /// Print human-readable risk explanations for top functions.
+///
+/// `candidate_count` functions were left for the top N to pick from, out of
+/// `total_count` in the report.
 pub(crate) fn print_explain_output(
     snapshot: &hotspots_core::snapshot::Snapshot,
     total_count: usize,
+    candidate_count: usize,
     color: bool,
 ) -> anyhow::Result<()> {
     use hotspots_core::risk::RiskBand;
@@ -119,7 +156,7 @@ pub(crate) fn print_explain_output(
         return Ok(());
     }
 
-    let show_all = funcs.len() >= total_count;
+    let show_all = funcs.len() >= candidate_count;
 
     let critical: Vec<_> = funcs
         .iter()
@@ -184,12 +221,22 @@ pub(crate) fn print_explain_output(
             } else {
                 format!("  [{}]", f.patterns.join(", "))
             };
+            let age_str = f
+                .function_age_days
+                .map(|d| format!("  (age {}d)", d))
+                .unwrap_or_default();
+            let coverage_str = f
+                .coverage
+                .map(|c| format!("  (cov {:.0}%)", c * 100.0))
+                .unwrap_or_default();
             println!(
-                "  {:.2}  {:<col_w$}  {}{}",
+                "  {:.2}  {:<col_w$}  {}{}{}{}",
                 score,
                 loc,
                 name,
                 patterns_str,
+                age_str,
+                coverage_str,
                 col_w = col_w
             );
             if let Some(exp) = &f.explanation {
@@ -206,7 +253,9 @@ pub(crate) fn print_explain_output(
     }
 
     println!("{}", "─".repeat(60));
-    if show_all {
+    if show_all && candidate_count < total_count {
+        println!("{} of {} functions shown", funcs.len(), total_count);
+    } else if show_all {
         println!("{} functions total", funcs.len());
     } else {
         let shown = critical.len() + high.len();
@@ -215,5 +264,44 @@ pub(crate) fn print_explain_output(
         println!("Use --top 0 to show all  ·  --top N for a different limit  ·  --format json for full output");
     }
 
+    // Only informative when the repo mixes languages
+    if let Some(summary) = snapshot
+        .summary
+        .as_ref()
+        .filter(|s| s.by_language.len() > 1)
+    {
+        let name_width = summary
+            .by_language
+            .keys()
+            .map(|n| n.len())
+            .max()
+            .unwrap_or(0);
+        println!("\nBy language:");
+        for (language, stats) in &summary.by_language {
+            let bands: Vec<String> = ["critical", "high", "moderate", "low"]
+                .iter()
+                .filter_map(|band| {
+                    stats
+                        .by_band
+                        .get(*band)
+                        .map(|count| format!("{count} {band}"))
+                })
+                .collect();
+            let noun = if stats.function_count == 1 {
+                "function"
+            } else {
+                "functions"
+            };
+            println!(
+                "  {:<name_width$}  {:>5} {:<9}  avg LRS {:.2}  ({})",
+                language,
+                stats.function_count,
+                noun,
+                stats.avg_lrs,
+                bands.join(", "),
+            );
+        }
+    }
+
     Ok(())
 }
@@ -1,2 +1,5 @@
+pub(crate) mod baseline;
+pub(crate) mod delta;
 pub(crate) mod explain;
 pub(crate) mod policy;
+pub(crate) mod why;
@@ -11,6 +11,10 @@ pub(crate) fn render_policy_text_output(
     let mut out = String::new();
     writeln!(out, "Policy Evaluation Results")?;
     writeln!(out, "{}", "=".repeat(80))?;
+    if delta.status != hotspots_core::delta::DeltaStatus::Changes {
+        writeln!(out, "{}", delta.status.describe())?;
+    }
+    super::delta::write_risk_removed(&mut out, delta)?;
     write_failing_functions_section(&mut out, delta, policy_results)?;
     write_threshold_warning_section(
         &mut out,
@@ -27,6 +31,30 @@ pub(crate) fn render_policy_text_output(
         &policy_results.warnings,
     )?;
     write_rapid_growth_section(&mut out, delta, &policy_results.warnings)?;
+    write_message_warnings_section(
+        &mut out,
+        "Over Length Limit (max_function_loc)",
+        "function-too-long",
+        &policy_results.warnings,
+    )?;
+    write_message_warnings_section(
+        &mut out,
+        "New Complexity Over Limit (max_new_cc)",
+        "max-new-cc",
+        &policy_results.warnings,
+    )?;
+    write_message_warnings_section(
+        &mut out,
+        "New Critical Functions Over Limit (max_new_critical)",
+        "max-new-critical",
+        &policy_results.warnings,
+    )?;
+    write_message_warnings_section(
+        &mut out,
+        "New High Functions Over Limit (max_new_high)",
+        "max-new-high",
+        &policy_results.warnings,
+    )?;
     write_repo_warnings_section(&mut out, &policy_results.warnings)?;
     write_co_change_delta_section(&mut out, delta)?;
     write_policy_summary(&mut out, policy_results)?;
@@ -53,8 +81,8 @@ fn write_co_change_delta_section(out: &mut String, delta: &Delta) -> anyhow::Res
         .iter()
         .filter_map(|e| {
             e.function_id
-                .rfind("::")
-                .map(|pos| e.function_id[..pos].to_string())
+                .split_once("::")
+                .map(|(file, _)| file.to_string())
         })
         .collect();
 
@@ -104,7 +132,7 @@ fn write_failing_functions_section(
         if let Some(ref function_id) = result.function_id {
             writeln!(out, "- {}: {}", result.id.as_str(), function_id)?;
         } else {
-            writeln!(out, "- {}", result.id.as_str())?;
+            writeln!(out, "- {}: {}", result.id.as_str(), result.message)?;
         }
     }
     writeln!(out, "\nViolating functions:")?;
@@ -256,6 +284,27 @@ fn write_rapid_growth_section(
     Ok(())
 }
 
+/// Warnings of one policy, listed by message under `title`
+fn write_message_warnings_section(
+    out: &mut String,
+    title: &str,
+    policy_id: &str,
+    warnings: &[PolicyResult],
+) -> anyhow::Result<()> {
+    let group: Vec<_> = warnings
+        .iter()
+        .filter(|r| r.id.as_str() == policy_id)
+        .collect();
+    if group.is_empty() {
+        return Ok(());
+    }
+    writeln!(out, "\n{title}:")?;
+    for warning in group {
+        writeln!(out, "- {}", warning.message)?;
+    }
+    Ok(())
+}
+
 fn write_repo_warnings_section(out: &mut String, warnings: &[PolicyResult]) -> anyhow::Result<()> {
     let group: Vec<_> = warnings
         .iter()
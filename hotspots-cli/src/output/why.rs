@@ -0,0 +1,213 @@
+use hotspots_core::risk::{unwrap_risk, LrsWeights, RiskThresholds};
+use hotspots_core::{FunctionRiskReport, ResolvedConfig};
+
+/// Resolve a `--why` argument to exactly one report.
+///
+/// Matching, in order: exact `file::function` ID, an ID whose file path ends
+/// with the given path, then a bare function name. Ambiguous matches are an
+/// error listing the candidates.
+pub(crate) fn find_function<'a>(
+    reports: &'a [FunctionRiskReport],
+    query: &str,
+) -> anyhow::Result<&'a FunctionRiskReport> {
+    let id = |r: &FunctionRiskReport| format!("{}::{}", r.file.replace('\\', "/"), r.function);
+    let query = query.replace('\\', "/");
+
+    let exact: Vec<_> = reports.iter().filter(|r| id(r) == query).collect();
+    let candidates = if !exact.is_empty() {
+        exact
+    } else if query.contains("::") {
+        let suffix = format!("/{}", query.trim_start_matches("./"));
+        reports
+            .iter()
+            .filter(|r| id(r).ends_with(&suffix))
+            .collect()
+    } else {
+        reports.iter().filter(|r| r.function == query).collect()
+    };
+
+    match candidates.as_slice() {
+        [] => anyhow::bail!("no function matches --why {}", query),
+        [only] => Ok(only),
+        many => {
+            let list: Vec<String> = many.iter().map(|r| format!("  {}", id(r))).collect();
+            anyhow::bail!(
+                "--why {} is ambiguous; {} functions match:\n{}",
+                query,
+                many.len(),
+                list.join("\n")
+            )
+        }
+    }
+}
+
+/// Select the functions named `name` for `--function`, optionally restricted to
+/// files whose path ends with `file`.
+///
+/// A name matches either the whole function name or its last `::` segment, so
+/// `handleRequest` also finds `Server::handleRequest`. Several matches in one
+/// file are all returned; matches spread across files are an error unless
+/// `file` narrows them down.
+pub(crate) fn find_named_functions<'a>(
+    reports: &'a [FunctionRiskReport],
+    name: &str,
+    file: Option<&str>,
+) -> anyhow::Result<Vec<&'a FunctionRiskReport>> {
+    let file = file.map(|f| f.replace('\\', "/").trim_start_matches("./").to_string());
+    let in_file = |r: &FunctionRiskReport| match &file {
+        Some(f) => {
+            let path = r.file.replace('\\', "/");
+            path == *f || path.ends_with(&format!("/{f}"))
+        }
+        None => true,
+    };
+    let matches: Vec<_> = reports
+        .iter()
+        .filter(|r| r.function == name || r.function.rsplit("::").next() == Some(name))
+        .filter(|r| in_file(r))
+        .collect();
+
+    let Some(first) = matches.first() else {
+        match &file {
+            Some(f) => anyhow::bail!("no function named {} in {}", name, f),
+            None => anyhow::bail!("no function named {}", name),
+        }
+    };
+    if matches.iter().any(|r| r.file != first.file) {
+        let list: Vec<String> = matches
+            .iter()
+            .map(|r| format!("  {}::{} (line {})", r.file, r.function, r.line))
+            .collect();
+        anyhow::bail!(
+            "--function {} is ambiguous across files; pass --file to pick one:\n{}",
+            name,
+            list.join("\n")
+        );
+    }
+    Ok(matches)
+}
+
+/// Print raw metrics, applied weights/thresholds with their sources, the LRS
+/// breakdown, and the resulting band for one function.
+pub(crate) fn print_why(report: &FunctionRiskReport, config: &ResolvedConfig) {
+    let weights = LrsWeights {
+        cc: config.weight_cc,
+        nd: config.weight_nd,
+        fo: config.weight_fo,
+        ns: config.weight_ns,
+        unwrap: config.weight_unwrap,
+    };
+    let thresholds = RiskThresholds {
+        moderate: config.moderate_threshold,
+        high: config.high_threshold,
+        critical: config.critical_threshold,
+    };
+
+    println!("Function: {}::{}", report.file, report.function);
+    println!("  line {}, {}", report.line, report.language.name());
+    if let Some(ref reason) = report.suppression_reason {
+        println!("  suppressed: {}", reason);
+    }
+    println!();
+
+    println!("Raw metrics:");
+    println!("  cc   {}", report.metrics.cc);
+    println!("  nd   {}", report.metrics.nd);
+    println!("  fo   {}", report.metrics.fo);
+    println!("  ns   {}", report.metrics.ns);
+    println!("  loc  {}", report.metrics.loc);
+    if let Some(unwrap_count) = report.metrics.unwrap_count {
+        println!("  unwrap {}", unwrap_count);
+    }
+    println!();
+
+    println!("LRS computation:");
+    println!(
+        "  {:<6} {:>5}  {:<20} {:>6}  {:>6}  {:>6}  weight source",
+        "metric", "raw", "transform", "risk", "weight", "contrib"
+    );
+    let mut rows = vec![
+        (
+            "cc",
+            report.metrics.cc,
+            "min(log2(cc+1), 6)",
+            report.risk.r_cc,
+            weights.cc,
+        ),
+        (
+            "nd",
+            report.metrics.nd,
+            "min(nd, 8)",
+            report.risk.r_nd,
+            weights.nd,
+        ),
+        (
+            "fo",
+            report.metrics.fo,
+            "min(log2(fo+1), 6)",
+            report.risk.r_fo,
+            weights.fo,
+        ),
+        (
+            "ns",
+            report.metrics.ns,
+            "min(ns, 6)",
+            report.risk.r_ns,
+            weights.ns,
+        ),
+    ];
+    // Rust only; the term is part of LRS whenever `weights.unwrap` is non-zero
+    if let Some(unwrap_count) = report.metrics.unwrap_count {
+        rows.push((
+            "unwrap",
+            unwrap_count,
+            "min(log2(n+1), 6)",
+            unwrap_risk(unwrap_count as usize),
+            weights.unwrap,
+        ));
+    }
+    for (name, raw, transform, risk, weight) in rows {
+        println!(
+            "  {:<6} {:>5}  {:<20} {:>6.2}  {:>6.2}  {:>6.2}  {}",
+            name,
+            raw,
+            transform,
+            risk,
+            weight,
+            risk * weight,
+            config.setting_source(&format!("weights.{name}"))
+        );
+    }
+    println!("  LRS = {:.2}", report.lrs);
+    println!();
+
+    println!("Band thresholds:");
+    for (name, value) in [
+        ("moderate", thresholds.moderate),
+        ("high", thresholds.high),
+        ("critical", thresholds.critical),
+    ] {
+        println!(
+            "  {:<9} {:>5.2}  {}",
+            name,
+            value,
+            config.setting_source(&format!("thresholds.{name}"))
+        );
+    }
+    println!();
+
+    let range = match report.band {
+        hotspots_core::risk::RiskBand::Low => format!("LRS < {:.2}", thresholds.moderate),
+        hotspots_core::risk::RiskBand::Moderate => {
+            format!("{:.2} <= LRS < {:.2}", thresholds.moderate, thresholds.high)
+        }
+        hotspots_core::risk::RiskBand::High => {
+            format!("{:.2} <= LRS < {:.2}", thresholds.high, thresholds.critical)
+        }
+        hotspots_core::risk::RiskBand::Critical => format!("LRS >= {:.2}", thresholds.critical),
+    };
+    println!("Band: {} ({})", report.band, range);
+    if !report.patterns.is_empty() {
+        println!("Patterns: {}", report.patterns.join(", "));
+    }
+}
@@ -29,6 +29,14 @@ pub(crate) fn write_html_report(path: &Path, html: &str) -> anyhow::Result<()> {
     Ok(())
 }
 
+/// Default location of an HTML report: `.hotspots/<file_name>` under the
+/// working directory, or in the storage directory when it has been relocated
+pub(crate) fn default_report_path(file_name: &str) -> PathBuf {
+    hotspots_core::snapshot::store_dir_override()
+        .unwrap_or_else(|| PathBuf::from(".hotspots"))
+        .join(file_name)
+}
+
 /// Find the git repository root by walking up from `start_path`.
 pub(crate) fn find_repo_root(start_path: &Path) -> anyhow::Result<PathBuf> {
     let mut current = if start_path.is_file() {
@@ -50,3 +58,24 @@ pub(crate) fn find_repo_root(start_path: &Path) -> anyhow::Result<PathBuf> {
         }
     }
 }
+
+/// Warn when `snapshots` came from different major/minor hotspots releases,
+/// whose metric counting may differ. `command` names what is comparing them.
+pub(crate) fn warn_version_skew<'a>(
+    command: &str,
+    snapshots: impl IntoIterator<Item = &'a hotspots_core::snapshot::Snapshot>,
+) {
+    use hotspots_core::diagnostics::{self, Level};
+
+    let Some(versions) = hotspots_core::snapshot::tool_version_skew(snapshots) else {
+        return;
+    };
+    if diagnostics::enabled(Level::Warn) {
+        eprintln!(
+            "Warning: {} compares snapshots from hotspots {}; metric changes between releases \
+             may skew the comparison (--ignore-version-skew to silence)",
+            command,
+            versions.join(", ")
+        );
+    }
+}
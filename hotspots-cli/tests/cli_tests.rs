@@ -0,0 +1,757 @@
+//! End-to-end tests that run the `hotspots` binary against temporary git repos
+
+use std::path::Path;
+use std::process::{Command, Output};
+use tempfile::TempDir;
+
+/// Run `git` in `repo`, panicking on failure
+fn git(repo: &Path, args: &[&str]) -> String {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(repo)
+        .output()
+        .expect("failed to run git");
+    assert!(
+        output.status.success(),
+        "git {args:?} failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    String::from_utf8_lossy(&output.stdout).trim().to_string()
+}
+
+/// Empty repo on `main` with a committer identity and `.hotspots/` ignored
+fn create_repo() -> TempDir {
+    let dir = TempDir::new().expect("failed to create temp dir");
+    let repo = dir.path();
+    git(repo, &["init", "--initial-branch=main"]);
+    git(repo, &["config", "user.name", "Test"]);
+    git(repo, &["config", "user.email", "test@example.com"]);
+    git(repo, &["config", "commit.gpgsign", "false"]);
+    write(repo, ".gitignore", ".hotspots/\n");
+    dir
+}
+
+fn write(repo: &Path, rel: &str, content: &str) {
+    let path = repo.join(rel);
+    std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+    std::fs::write(path, content).unwrap();
+}
+
+/// Stage everything and commit, returning the new SHA
+fn commit(repo: &Path, message: &str) -> String {
+    git(repo, &["add", "-A"]);
+    git(repo, &["commit", "-q", "-m", message]);
+    git(repo, &["rev-parse", "HEAD"])
+}
+
+fn hotspots(repo: &Path, args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_hotspots"))
+        .args(args)
+        .current_dir(repo)
+        .output()
+        .expect("failed to run hotspots")
+}
+
+fn stdout(output: &Output) -> String {
+    String::from_utf8_lossy(&output.stdout).into_owned()
+}
+
+/// A stable `src/core` module imported by `src/app`, with the zone-of-pain
+/// complexity cutoff low enough that any added branch crosses it; the only
+/// other blocking policy is turned off so each verdict is down to
+/// `instability-regression`
+fn create_stable_module_repo() -> TempDir {
+    let dir = create_repo();
+    let repo = dir.path();
+    write(
+        repo,
+        ".hotspotsrc.json",
+        r#"{
+            "max_stable_afferent": 5,
+            "module_zones": {"pain_complexity": 1.0},
+            "policy": {
+                "excessive_risk_regression": "off",
+                "excessive_risk_regression_reason": "only instability-regression may block"
+            }
+        }"#,
+    );
+    write(
+        repo,
+        "src/app/main.ts",
+        "import { util } from '../core/util';\n\nexport function main(x: number) {\n  return util(x);\n}\n",
+    );
+    write(
+        repo,
+        "src/core/util.ts",
+        "export function util(x: number) {\n  return x + 1;\n}\n",
+    );
+    commit(repo, "initial");
+    dir
+}
+
+/// Make `src/core/util.ts` more complex and commit it
+fn raise_core_complexity(repo: &Path) {
+    write(
+        repo,
+        "src/core/util.ts",
+        "export function util(x: number) {\n  if (x > 0) {\n    if (x > 10) {\n      return x;\n    }\n    return x + 1;\n  }\n  return 0;\n}\n",
+    );
+    commit(repo, "complicate util");
+}
+
+/// Analyze `src` at HEAD and persist the snapshot
+fn persist_snapshot(repo: &Path) {
+    let snap = hotspots(
+        repo,
+        &["analyze", "--mode", "snapshot", "--format", "json", "src"],
+    );
+    assert!(snap.status.success(), "{snap:?}");
+}
+
+#[test]
+fn test_diff_policy_flags_instability_regression() {
+    let dir = create_stable_module_repo();
+    let repo = dir.path();
+    persist_snapshot(repo);
+    raise_core_complexity(repo);
+    persist_snapshot(repo);
+
+    let diff = hotspots(
+        repo,
+        &["diff", "HEAD~1", "HEAD", "--policy", "--format", "json"],
+    );
+    assert_eq!(diff.status.code(), Some(1), "{diff:?}");
+    assert!(stdout(&diff).contains("instability-regression"), "{diff:?}");
+}
+
+#[test]
+fn test_delta_check_flags_instability_regression() {
+    let dir = create_stable_module_repo();
+    let repo = dir.path();
+    persist_snapshot(repo);
+    raise_core_complexity(repo);
+
+    let check = hotspots(
+        repo,
+        &["analyze", "--mode", "delta", "--policy", "--check", "src"],
+    );
+    assert_eq!(check.status.code(), Some(1), "{check:?}");
+    assert!(stdout(&check).contains("1 blocking"), "{check:?}");
+}
+
+#[test]
+fn test_only_changed_functions_keeps_the_repo_wide_total() {
+    let dir = create_repo();
+    let repo = dir.path();
+    write(
+        repo,
+        "src/a.ts",
+        "export function a(x: number) {\n  return x;\n}\n",
+    );
+    write(
+        repo,
+        "src/b.ts",
+        "export function b(x: number) {\n  return x;\n}\n",
+    );
+    commit(repo, "initial");
+    write(
+        repo,
+        "src/b.ts",
+        "export function b(x: number) {\n  return x * 2;\n}\n",
+    );
+    commit(repo, "change b");
+
+    let args = [
+        "analyze",
+        "--mode",
+        "snapshot",
+        "--explain",
+        "--only-changed-functions",
+        "src",
+    ];
+    let json = hotspots(repo, &[&args[..], &["--format", "json"]].concat());
+    assert!(json.status.success(), "{json:?}");
+    let explain: serde_json::Value = serde_json::from_slice(&json.stdout).unwrap();
+    assert_eq!(explain["total_functions"], 2);
+    let functions: Vec<&str> = explain["functions"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|f| f["function"].as_str().unwrap())
+        .collect();
+    assert_eq!(functions, vec!["b"]);
+
+    let text = hotspots(repo, &args);
+    assert!(text.status.success(), "{text:?}");
+    assert!(stdout(&text).contains("1 of 2 functions shown"), "{text:?}");
+}
+
+#[test]
+fn test_metrics_only_snapshot_paths_are_relative_to_the_analyzed_root() {
+    let dir = TempDir::new().unwrap();
+    let root = dir.path();
+    write(
+        root,
+        "src/a.ts",
+        "export function a(x: number) {\n  return x;\n}\n",
+    );
+
+    let snap = hotspots(
+        root,
+        &[
+            "analyze",
+            "--mode",
+            "snapshot",
+            "--metrics-only",
+            "--use-mtime",
+            "--all-functions",
+            "--format",
+            "json",
+            ".",
+        ],
+    );
+    assert!(snap.status.success(), "{snap:?}");
+    let snapshot: serde_json::Value = serde_json::from_slice(&snap.stdout).unwrap();
+    let function = &snapshot["functions"][0];
+    assert_eq!(function["file"], "src/a.ts");
+    assert_eq!(function["function_id"], "src/a.ts::a");
+    assert_eq!(function["days_since_last_change"], 0);
+}
+
+#[test]
+fn test_metrics_only_snapshot_json_is_the_agent_view_limited_by_top() {
+    let dir = TempDir::new().unwrap();
+    let root = dir.path();
+    write(
+        root,
+        "src/a.ts",
+        "export function a(x: number) {\n  if (x > 0) {\n    if (x > 1) {\n      if (x > 2) {\n        return 3;\n      }\n    }\n  }\n  return 0;\n}\n\nexport function b(x: number) {\n  return x;\n}\n",
+    );
+
+    let snap = hotspots(
+        root,
+        &[
+            "analyze",
+            "--mode",
+            "snapshot",
+            "--metrics-only",
+            "--top",
+            "1",
+            "--format",
+            "json",
+            ".",
+        ],
+    );
+    assert!(snap.status.success(), "{snap:?}");
+    let output: serde_json::Value = serde_json::from_slice(&snap.stdout).unwrap();
+    assert!(output["schema_version"].is_number(), "{output}");
+    assert!(output.get("functions").is_none(), "{output}");
+    let triage = &output["triage"];
+    let counted: u64 = ["fire", "debt", "watch", "ok"]
+        .iter()
+        .map(|q| triage[q]["count"].as_u64().unwrap())
+        .sum();
+    assert_eq!(counted, 1, "{output}");
+}
+
+#[test]
+fn test_snapshot_check_exits_non_zero_on_critical_functions() {
+    let dir = create_repo();
+    let repo = dir.path();
+    write(
+        repo,
+        "src/a.ts",
+        "export function a(x: number) {\n  return x;\n}\n",
+    );
+    commit(repo, "initial");
+
+    let args = ["analyze", "--mode", "snapshot", "--check", "."];
+    let clean = hotspots(repo, &args);
+    assert!(clean.status.success(), "{clean:?}");
+    assert_eq!(
+        stdout(&clean).trim(),
+        "check: 1 function (0 critical, 0 high); 0 violations"
+    );
+
+    write(
+        repo,
+        "src/z.ts",
+        "export function z(a: number, b: number) {\n  for (let i = 0; i < a; i++) {\n    if (i > b) {\n      while (b > 0) {\n        if (a && b || i) {\n          for (const k of [1, 2]) {\n            if (k > 1) { return k; }\n            if (k < 0) { throw new Error(\"x\"); }\n          }\n        }\n        b--;\n      }\n    }\n  }\n  return 0;\n}\n",
+    );
+    commit(repo, "add critical function");
+
+    let failing = hotspots(repo, &args);
+    assert_eq!(failing.status.code(), Some(1), "{failing:?}");
+    assert!(
+        stdout(&failing).contains("(1 critical, 0 high); 1 violation"),
+        "{failing:?}"
+    );
+}
+
+#[test]
+fn test_language_breakdown_pluralizes_function_counts() {
+    let dir = create_repo();
+    let repo = dir.path();
+    write(
+        repo,
+        "src/a.ts",
+        "export function a(x: number) {\n  return x;\n}\n\nexport function b(x: number) {\n  return x;\n}\n",
+    );
+    write(
+        repo,
+        "src/c.go",
+        "package src\n\nfunc C(x int) int {\n\treturn x\n}\n",
+    );
+    commit(repo, "initial");
+
+    let run = hotspots(
+        repo,
+        &[
+            "analyze",
+            "--mode",
+            "snapshot",
+            "--explain",
+            "--no-persist",
+            ".",
+        ],
+    );
+    assert!(run.status.success(), "{run:?}");
+    let text = stdout(&run);
+    assert!(text.contains("    1 function   avg LRS"), "{text}");
+    assert!(text.contains("    2 functions  avg LRS"), "{text}");
+}
+
+#[test]
+fn test_top_output_trims_functions_but_not_aggregates() {
+    let dir = create_repo();
+    let repo = dir.path();
+    write(
+        repo,
+        "src/a.ts",
+        "export function a(x: number) {\n  return x;\n}\n",
+    );
+    write(
+        repo,
+        "src/b.ts",
+        "export function b(x: number) {\n  if (x > 0) {\n    if (x > 1) {\n      return x;\n    }\n  }\n  return 0;\n}\n",
+    );
+    commit(repo, "initial");
+
+    let run = hotspots(
+        repo,
+        &[
+            "analyze",
+            "--mode",
+            "snapshot",
+            "--all-functions",
+            "--top-output",
+            "1",
+            "--no-persist",
+            "--format",
+            "json",
+            ".",
+        ],
+    );
+    assert!(run.status.success(), "{run:?}");
+    let snapshot: serde_json::Value = serde_json::from_slice(&run.stdout).unwrap();
+    let functions = snapshot["functions"].as_array().unwrap();
+    assert_eq!(functions.len(), 1, "{snapshot}");
+    let id = functions[0]["function_id"].as_str().unwrap();
+    assert!(id.ends_with("src/b.ts::b"), "{id}");
+    assert_eq!(
+        snapshot["aggregates"]["files"].as_array().unwrap().len(),
+        2,
+        "{snapshot}"
+    );
+
+    let text = hotspots(
+        repo,
+        &["analyze", "--mode", "snapshot", "--top-output", "1", "."],
+    );
+    assert!(!text.status.success(), "{text:?}");
+}
+
+#[test]
+fn test_progress_prints_line_based_file_counts() {
+    let dir = TempDir::new().unwrap();
+    let root = dir.path();
+    write(
+        root,
+        "src/a.ts",
+        "export function a(x: number) {\n  return x;\n}\n",
+    );
+    write(
+        root,
+        "src/b.ts",
+        "export function b(x: number) {\n  return x;\n}\n",
+    );
+
+    let run = hotspots(root, &["analyze", "src", "--progress"]);
+    assert!(run.status.success(), "{run:?}");
+    let stderr = String::from_utf8_lossy(&run.stderr);
+    assert!(stderr.contains("analyzed 0/2 files\n"), "{stderr}");
+    assert!(stderr.contains("analyzed 2/2 files\n"), "{stderr}");
+    assert!(!stderr.contains("Analyzing:"), "{stderr}");
+}
+
+#[test]
+fn test_order_source_and_id_reorder_text_output() {
+    let dir = TempDir::new().unwrap();
+    let root = dir.path();
+    write(
+        root,
+        "src/a.ts",
+        "export function mid(x: number) {\n  if (x) {\n    return 1;\n  }\n  return 0;\n}\n",
+    );
+    write(
+        root,
+        "src/b.ts",
+        "export function zeta(x: number) {\n  return x;\n}\n\nexport function alpha(x: number) {\n  if (x > 0) {\n    if (x > 1) {\n      return x;\n    }\n  }\n  return 0;\n}\n",
+    );
+
+    let names = |order: &str| -> Vec<String> {
+        let run = hotspots(root, &["analyze", "src", "--top", "0", "--order", order]);
+        assert!(run.status.success(), "{run:?}");
+        stdout(&run)
+            .lines()
+            .filter(|line| line.contains("src/"))
+            .filter_map(|line| line.split_whitespace().last().map(str::to_string))
+            .collect()
+    };
+    assert_eq!(names("source"), ["mid", "zeta", "alpha"]);
+    assert_eq!(names("id"), ["mid", "alpha", "zeta"]);
+}
+
+#[test]
+fn test_quiet_silences_warnings_and_progress_but_not_output() {
+    let dir = TempDir::new().unwrap();
+    let root = dir.path();
+    write(
+        root,
+        "src/a.ts",
+        "export function a(x: number) {\n  return x;\n}\n",
+    );
+    write(
+        root,
+        "src/big.ts",
+        &format!(
+            "export function big() {{\n{}  return 0;\n}}\n",
+            "  let x = 1;\n".repeat(50)
+        ),
+    );
+    let args = [
+        "analyze",
+        "src",
+        "--top",
+        "0",
+        "--progress",
+        "--max-file-size",
+        "200",
+    ];
+
+    let loud = hotspots(root, &args);
+    assert!(loud.status.success(), "{loud:?}");
+    let loud_stderr = String::from_utf8_lossy(&loud.stderr);
+    assert!(
+        loud_stderr.contains("warning: skipping file"),
+        "{loud_stderr}"
+    );
+    assert!(loud_stderr.contains("analyzed"), "{loud_stderr}");
+
+    let quiet = hotspots(root, &[&args[..], &["--quiet"]].concat());
+    assert!(quiet.status.success(), "{quiet:?}");
+    assert_eq!(String::from_utf8_lossy(&quiet.stderr), "");
+    assert_eq!(stdout(&quiet), stdout(&loud));
+}
+
+#[test]
+fn test_report_index_links_reports_and_snapshots() {
+    let dir = create_repo();
+    let repo = dir.path();
+    write(
+        repo,
+        "src/a.ts",
+        "export function a(x: number) {\n  return x;\n}\n",
+    );
+    let sha = commit(repo, "initial");
+
+    let missing = hotspots(repo, &["report-index"]);
+    assert!(!missing.status.success(), "{missing:?}");
+
+    let report = hotspots(
+        repo,
+        &["analyze", "--mode", "snapshot", "--format", "html", "."],
+    );
+    assert!(report.status.success(), "{report:?}");
+
+    let run = hotspots(repo, &["report-index"]);
+    assert!(run.status.success(), "{run:?}");
+    let index = std::fs::read_to_string(repo.join(".hotspots/index.html")).unwrap();
+    assert!(index.contains(r#"href="report.html""#), "{index}");
+    assert!(
+        index.contains(&format!("{} (snapshot)", &sha[..8])),
+        "{index}"
+    );
+    assert!(!index.contains(r#"href="index.html""#), "{index}");
+}
+
+#[test]
+fn test_function_and_file_select_breakdowns() {
+    let dir = TempDir::new().unwrap();
+    let root = dir.path();
+    write(
+        root,
+        "src/a.ts",
+        "export function handle(x: number) {\n  return x;\n}\n",
+    );
+    write(
+        root,
+        "src/b.ts",
+        "export function handle(x: number) {\n  if (x > 0) {\n    return x;\n  }\n  return 0;\n}\n",
+    );
+
+    let ambiguous = hotspots(root, &["analyze", "src", "--function", "handle"]);
+    assert!(!ambiguous.status.success(), "{ambiguous:?}");
+    let stderr = String::from_utf8_lossy(&ambiguous.stderr);
+    assert!(
+        stderr.contains("a.ts") && stderr.contains("b.ts"),
+        "{stderr}"
+    );
+
+    let run = hotspots(
+        root,
+        &["analyze", "src", "--function", "handle", "--file", "b.ts"],
+    );
+    assert!(run.status.success(), "{run:?}");
+    let text = stdout(&run);
+    assert!(text.contains("src/b.ts::handle"), "{text}");
+    assert!(!text.contains("src/a.ts"), "{text}");
+    assert!(text.contains("LRS computation:"), "{text}");
+
+    let json = hotspots(
+        root,
+        &[
+            "analyze",
+            "src",
+            "--function",
+            "handle",
+            "--file",
+            "a.ts",
+            "--format",
+            "json",
+        ],
+    );
+    assert!(json.status.success(), "{json:?}");
+    let reports: serde_json::Value = serde_json::from_slice(&json.stdout).unwrap();
+    let reports = reports.as_array().unwrap();
+    assert_eq!(reports.len(), 1);
+    assert_eq!(reports[0]["metrics"]["cc"], 1);
+
+    let missing = hotspots(root, &["analyze", "src", "--function", "nope"]);
+    assert!(!missing.status.success(), "{missing:?}");
+}
+
+#[test]
+fn test_emit_writes_every_target_from_one_run() {
+    let dir = create_repo();
+    let repo = dir.path();
+    write(
+        repo,
+        "src/a.ts",
+        "export function a(x: number) {\n  return x;\n}\n",
+    );
+    commit(repo, "initial");
+
+    let run = hotspots(
+        repo,
+        &[
+            "analyze",
+            "--mode",
+            "snapshot",
+            "--emit",
+            "json:out/snapshot.json",
+            "--emit",
+            "sarif:out/results.sarif",
+            "src",
+        ],
+    );
+    assert!(run.status.success(), "{run:?}");
+    let snapshot: serde_json::Value =
+        serde_json::from_slice(&std::fs::read(repo.join("out/snapshot.json")).unwrap()).unwrap();
+    assert_eq!(snapshot["summary"]["total_functions"], 1);
+    let sarif: serde_json::Value =
+        serde_json::from_slice(&std::fs::read(repo.join("out/results.sarif")).unwrap()).unwrap();
+    assert_eq!(sarif["version"], "2.1.0");
+
+    let duplicate = hotspots(
+        repo,
+        &[
+            "analyze",
+            "--mode",
+            "snapshot",
+            "--emit",
+            "json:out/x.json",
+            "--emit",
+            "html:out/x.json",
+            "src",
+        ],
+    );
+    assert!(!duplicate.status.success(), "{duplicate:?}");
+    assert!(String::from_utf8_lossy(&duplicate.stderr).contains("more than once"));
+}
+
+#[test]
+fn test_baseline_branch_delta_records_the_merge_base_as_parent() {
+    let dir = create_repo();
+    let repo = dir.path();
+    write(
+        repo,
+        "src/a.ts",
+        "export function a(x: number) {\n  return x;\n}\n",
+    );
+    let merge_base = commit(repo, "initial");
+    git(repo, &["checkout", "-q", "-b", "feature"]);
+    write(
+        repo,
+        "src/a.ts",
+        "export function a(x: number) {\n  if (x > 0) {\n    return x;\n  }\n  return 0;\n}\n",
+    );
+    commit(repo, "branch a");
+    write(
+        repo,
+        "src/b.ts",
+        "export function b(x: number) {\n  return x;\n}\n",
+    );
+    let head = commit(repo, "add b");
+
+    let run = hotspots(
+        repo,
+        &[
+            "analyze",
+            "--mode",
+            "delta",
+            "--baseline-branch",
+            "main",
+            "--format",
+            "json",
+            "src",
+        ],
+    );
+    assert!(run.status.success(), "{run:?}");
+    let delta: serde_json::Value = serde_json::from_slice(&run.stdout).unwrap();
+    assert_eq!(delta["commit"]["sha"], head.as_str());
+    assert_eq!(delta["commit"]["parent"], merge_base.as_str());
+}
+
+#[test]
+fn test_compare_html_report_labels_trees_by_path() {
+    let dir = TempDir::new().unwrap();
+    let root = dir.path();
+    write(
+        root,
+        "b/a.ts",
+        "export function a(x: number) {\n  return x;\n}\n",
+    );
+    write(
+        root,
+        "c/a.ts",
+        "export function a(x: number) {\n  if (x > 0) {\n    return x;\n  }\n  return 0;\n}\n",
+    );
+
+    let run = hotspots(
+        root,
+        &[
+            "compare", "b", "c", "--format", "html", "--output", "out.html",
+        ],
+    );
+    assert!(run.status.success(), "{run:?}");
+    let html = std::fs::read_to_string(root.join("out.html")).unwrap();
+    let after = root.join("c").canonicalize().unwrap();
+    assert!(html.contains(&after.display().to_string()), "{html}");
+
+    let sarif = hotspots(root, &["compare", "b", "c", "--format", "sarif"]);
+    assert!(!sarif.status.success(), "{sarif:?}");
+}
+
+#[test]
+fn test_html_theme_applies_title_band_colors_and_escaped_stylesheet() {
+    let dir = create_repo();
+    let repo = dir.path();
+    write(
+        repo,
+        "src/a.ts",
+        "export function a(x: number) {\n  return x;\n}\n",
+    );
+    write(
+        repo,
+        ".hotspotsrc.json",
+        r##"{ "band_colors": { "critical": "#123456" } }"##,
+    );
+    write(
+        repo,
+        "brand.css",
+        "body { color: #abcdef; }\n</STYLE><script>alert(1)</script>\n",
+    );
+    commit(repo, "initial");
+
+    let run = hotspots(
+        repo,
+        &[
+            "analyze",
+            "--mode",
+            "snapshot",
+            "--format",
+            "html",
+            "--html-title",
+            "Acme Risk",
+            "--html-css",
+            "brand.css",
+            "--output",
+            "out.html",
+            ".",
+        ],
+    );
+    assert!(run.status.success(), "{run:?}");
+    let html = std::fs::read_to_string(repo.join("out.html")).unwrap();
+    assert!(html.contains("<title>Acme Risk"), "{html}");
+    assert!(html.contains("--band-critical: #123456;"), "{html}");
+    assert!(html.contains("body { color: #abcdef; }"), "{html}");
+    assert!(!html.contains("</STYLE>"), "{html}");
+    assert!(!html.contains("<script>alert(1)"), "{html}");
+}
+
+#[test]
+fn test_sort_ranks_within_each_risk_band() {
+    let dir = TempDir::new().unwrap();
+    let root = dir.path();
+    write(
+        root,
+        "src/a.ts",
+        "export function a(x: number) {\n  return x;\n}\n",
+    );
+    write(
+        root,
+        "src/b.ts",
+        "export function b(x: number) {\n  if (x > 0) {\n    return x;\n  }\n  return 0;\n}\n",
+    );
+    write(
+        root,
+        "src/z.ts",
+        "export function z(a: number, b: number) {\n  for (let i = 0; i < a; i++) {\n    if (i > b) {\n      while (b > 0) {\n        if (a && b || i) {\n          for (const k of [1, 2]) {\n            if (k > 1) { return k; }\n            if (k < 0) { throw new Error(\"x\"); }\n          }\n        }\n        b--;\n      }\n    }\n  }\n  return 0;\n}\n",
+    );
+
+    let run = hotspots(root, &["analyze", "src", "--top", "0", "--sort", "file"]);
+    assert!(run.status.success(), "{run:?}");
+    let text = stdout(&run);
+    // `z` stays in the critical band ahead of the files sorting before it,
+    // and the lower band is in file order rather than LRS order
+    let position = |needle: &str| text.find(needle).unwrap_or_else(|| panic!("{text}"));
+    assert!(position("CRITICAL") < position("src/z.ts:1"));
+    assert!(position("src/z.ts:1") < position("MEDIUM / LOW"));
+    assert!(position("MEDIUM / LOW") < position("src/a.ts:1"));
+    assert!(position("src/a.ts:1") < position("src/b.ts:1"));
+
+    let conflict = hotspots(root, &["analyze", "src", "--sort", "cc", "--order", "id"]);
+    assert!(!conflict.status.success(), "{conflict:?}");
+    assert!(String::from_utf8_lossy(&conflict.stderr).contains("cannot be used with"));
+}
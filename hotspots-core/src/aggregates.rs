@@ -33,10 +33,90 @@ pub struct DirectoryAggregates {
     pub high_plus_count: usize,
 }
 
+/// Coefficients for the composite `file_risk_score` (configurable under `file_risk`).
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct FileRiskWeights {
+    pub max_cc: f64,
+    pub avg_cc: f64,
+    pub function_count: f64,
+    pub churn: f64,
+}
+
+impl Default for FileRiskWeights {
+    fn default() -> Self {
+        FileRiskWeights {
+            max_cc: 0.4,
+            avg_cc: 0.3,
+            function_count: 0.2,
+            churn: 0.1,
+        }
+    }
+}
+
+impl FileRiskWeights {
+    /// Human-readable formula, e.g. for the HTML report tooltip.
+    pub fn formula(&self) -> String {
+        format!(
+            "max_cc × {} + avg_cc × {} + log2(functions + 1) × {} + churn_factor × {}",
+            self.max_cc, self.avg_cc, self.function_count, self.churn
+        )
+    }
+}
+
+/// Cutoffs for placing modules in Martin's zones: the HTML zone label and
+/// the JSON `module_risk` (configurable under `module_zones`).
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct ModuleZoneThresholds {
+    /// Instability below this is stable (zone of pain when also complex)
+    pub stable_below: f64,
+    /// Instability above this is volatile
+    pub volatile_above: f64,
+    /// Average CC above which a stable module is in the zone of pain
+    pub pain_complexity: f64,
+}
+
+impl Default for ModuleZoneThresholds {
+    fn default() -> Self {
+        ModuleZoneThresholds {
+            stable_below: 0.3,
+            volatile_above: 0.7,
+            pain_complexity: 8.0,
+        }
+    }
+}
+
+impl ModuleZoneThresholds {
+    /// Whether a module is stable yet complex: Martin's zone of pain
+    pub fn in_zone_of_pain(&self, instability: f64, avg_complexity: f64) -> bool {
+        instability < self.stable_below && avg_complexity > self.pain_complexity
+    }
+
+    /// Zone label and CSS class for a module.
+    pub fn classify(&self, instability: f64, avg_complexity: f64) -> (&'static str, &'static str) {
+        let complex = avg_complexity > self.pain_complexity;
+        if instability < self.stable_below {
+            if self.in_zone_of_pain(instability, avg_complexity) {
+                ("zone of pain", "zone-pain")
+            } else {
+                ("stable", "zone-stable")
+            }
+        } else if instability > self.volatile_above {
+            if complex {
+                ("volatile", "zone-volatile-complex")
+            } else {
+                ("volatile", "zone-volatile")
+            }
+        } else {
+            ("balanced", "zone-balanced")
+        }
+    }
+}
+
 /// File-level risk view
 ///
-/// Richer than `FileAggregates` — includes CC, LOC, function density, and a composite
-/// file_risk_score derived from:
+/// Richer than `FileAggregates` — includes CC, LOC (summed SLOC when available, so
+/// brace-heavy languages aren't inflated), function density, and a composite
+/// file_risk_score derived from (default [`FileRiskWeights`]):
 ///   max_cc × 0.4 + avg_cc × 0.3 + log2(function_count + 1) × 0.2 + churn_factor × 0.1
 /// where churn_factor = (file_churn / 100).min(10.0)
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -45,11 +125,34 @@ pub struct FileRiskView {
     pub file: String,
     pub function_count: usize,
     pub loc: usize,
+    /// loc / function_count: high for "one giant function" files
+    #[serde(default)]
+    pub avg_function_loc: f64,
+    /// Functions per 1000 non-blank lines of the file: high for "many small
+    /// functions" files
+    #[serde(default)]
+    pub functions_per_kloc: f64,
     pub max_cc: usize,
     pub avg_cc: f64,
     pub critical_count: usize,
+    /// Functions in the high band
+    #[serde(default)]
+    pub high_count: usize,
     pub file_churn: u64,
     pub file_risk_score: f64,
+    /// Fewest authors who together wrote more than half of the file's lines
+    /// (`--with-authors` only)
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub bus_factor: Option<usize>,
+    /// Author with the most lines in the file (`--with-authors` only)
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub top_author: Option<String>,
+    /// Fraction of the file's lines written by `top_author` (`--with-authors` only)
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub top_author_share: Option<f64>,
+    /// A single author owns more than 80% of a file with high or critical functions
+    #[serde(skip_serializing_if = "std::ops::Not::not", default)]
+    pub knowledge_risk: bool,
 }
 
 /// Module (directory) instability metric (Robert Martin's Ca/Ce)
@@ -60,6 +163,12 @@ pub struct ModuleInstability {
     pub module: String,
     pub file_count: usize,
     pub function_count: usize,
+    /// Mean function LOC (SLOC when available), as in [`FileRiskView`]
+    #[serde(default)]
+    pub avg_function_loc: f64,
+    /// Functions per 1000 non-blank lines of the module's analyzed files
+    #[serde(default)]
+    pub functions_per_kloc: f64,
     pub avg_complexity: f64,
     /// Afferent coupling: external modules that depend on this one
     pub afferent: usize,
@@ -67,8 +176,18 @@ pub struct ModuleInstability {
     pub efferent: usize,
     /// instability = efferent / (afferent + efferent); 0.5 if both == 0 (undefined)
     pub instability: f64,
-    /// "high" if instability < 0.3 and avg_complexity > 10, else "low"
+    /// "high" if the module is in the zone of pain (see [`ModuleZoneThresholds`]), else "low"
     pub module_risk: String,
+    /// Test files in the module directory (or its `__tests__/`), by the
+    /// built-in test-file conventions
+    #[serde(default)]
+    pub test_file_count: usize,
+    /// test_file_count / file_count
+    #[serde(default)]
+    pub test_file_ratio: f64,
+    /// Non-blank test lines / non-blank source lines
+    #[serde(default)]
+    pub test_loc_ratio: f64,
 }
 
 /// Snapshot aggregates container
@@ -87,6 +206,12 @@ pub struct SnapshotAggregates {
     pub modules: Vec<ModuleInstability>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub models: Option<crate::models::ModelRiskMap>,
+    /// Coefficients `file_risk` scores were computed with (not persisted)
+    #[serde(skip)]
+    pub file_risk_weights: FileRiskWeights,
+    /// Zone cutoffs for the HTML module cards (not persisted)
+    #[serde(skip)]
+    pub module_zones: ModuleZoneThresholds,
 }
 
 /// Delta aggregates for a file
@@ -116,6 +241,17 @@ pub struct CoChangeDeltaEntry {
     pub has_static_dep: bool,
 }
 
+/// A module whose instability or complexity changed in the delta
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub struct ModuleDeltaEntry {
+    pub module: String,
+    /// None for a module that is new in this change
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub before: Option<ModuleInstability>,
+    pub after: ModuleInstability,
+}
+
 /// Delta aggregates container
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(rename_all = "snake_case")]
@@ -124,6 +260,8 @@ pub struct DeltaAggregates {
     pub files: Vec<FileDeltaAggregates>,
     #[serde(skip_serializing_if = "Vec::is_empty")]
     pub co_change_delta: Vec<CoChangeDeltaEntry>,
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub modules: Vec<ModuleDeltaEntry>,
 }
 
 /// Number of functions per quadrant in the agent triage view
@@ -160,8 +298,17 @@ pub struct AgentFunctionView {
     pub touches_30d: Option<usize>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub days_since_changed: Option<u32>,
+    /// Days since the function first appeared in history (`--with-authors`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub function_age_days: Option<u32>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub fan_in: Option<usize>,
+    /// Covered fraction of instrumented lines (`--coverage`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub coverage: Option<f64>,
+    /// Refactor payoff: activity risk scaled by fan-in and recent touches
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub refactor_leverage: Option<f64>,
     #[serde(skip_serializing_if = "Vec::is_empty", default)]
     pub patterns: Vec<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -285,7 +432,10 @@ fn to_agent_view(
                 },
                 touches_30d: func.touch_count_30d,
                 days_since_changed: func.days_since_last_change,
+                function_age_days: func.function_age_days,
                 fan_in: func.callgraph.as_ref().map(|cg| cg.fan_in),
+                coverage: func.coverage,
+                refactor_leverage: func.refactor_leverage,
                 patterns: func.patterns.clone(),
                 explanation: func.explanation.clone(),
                 shap: None,
@@ -296,7 +446,8 @@ fn to_agent_view(
 
 /// Build the agent-optimized v4 JSON output from a fully enriched snapshot and its aggregates.
 ///
-/// Groups functions by triage quadrant, sorts each group by `activity_risk` descending,
+/// Groups functions by triage quadrant, sorts each group by `activity_risk` descending
+/// (`risky_untested` functions first),
 /// and returns top-N per quadrant. Co-change is split into hidden pairs only, capped at
 /// top 20 by coupling_ratio. File risk is capped at top 10.
 pub fn compute_agent_snapshot_output(
@@ -319,15 +470,9 @@ pub fn compute_agent_snapshot_output(
         }
     }
 
-    // Sort each group by activity_risk descending
+    // Sort each group by activity_risk descending, untested risk first
     let sort_by_risk = |fns: &mut Vec<&FunctionSnapshot>| {
-        fns.sort_by(|a, b| {
-            let a_score = a.activity_risk.unwrap_or(a.lrs);
-            let b_score = b.activity_risk.unwrap_or(b.lrs);
-            b_score
-                .partial_cmp(&a_score)
-                .unwrap_or(std::cmp::Ordering::Equal)
-        });
+        fns.sort_by(|a, b| crate::snapshot::triage_order(a, b));
     };
     sort_by_risk(&mut fire_fns);
     sort_by_risk(&mut debt_fns);
@@ -426,6 +571,139 @@ pub fn compute_agent_snapshot_output(
     }
 }
 
+pub const EXPLAIN_OUTPUT_SCHEMA_VERSION: u32 = 1;
+
+/// Full risk breakdown for one function in `--explain --format json` output
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct ExplainFunctionView {
+    pub function_id: String,
+    pub function: String,
+    pub file: String,
+    pub line: u32,
+    pub band: String,
+    pub lrs: f64,
+    pub activity_risk: f64,
+    pub quadrant: String,
+    pub driver: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub driver_detail: Option<String>,
+    pub action: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub risk_factors: Option<crate::scoring::RiskFactors>,
+    pub metrics: crate::report::MetricsReport,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub touches_30d: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub days_since_changed: Option<u32>,
+    /// Days since the function first appeared in history (`--with-authors`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub function_age_days: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fan_in: Option<usize>,
+    /// Covered fraction of instrumented lines (`--coverage`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub coverage: Option<f64>,
+    /// Refactor payoff: activity risk scaled by fan-in and recent touches
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub refactor_leverage: Option<f64>,
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub patterns: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub explanation: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub suppression_reason: Option<String>,
+}
+
+/// Machine-readable counterpart of the `--explain` text report
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct ExplainOutput {
+    pub schema_version: u32,
+    pub commit: crate::snapshot::CommitInfo,
+    /// Number of functions analyzed before top-N truncation
+    pub total_functions: usize,
+    /// Per-language breakdown from the snapshot summary (all functions, not just the top N)
+    #[serde(skip_serializing_if = "std::collections::BTreeMap::is_empty")]
+    pub by_language: std::collections::BTreeMap<String, crate::snapshot::LanguageStats>,
+    pub functions: Vec<ExplainFunctionView>,
+}
+
+impl ExplainOutput {
+    /// Write pretty-printed JSON directly to `writer` without an intermediate String.
+    pub fn write_json_to<W: std::io::Write>(&self, writer: &mut W) -> anyhow::Result<()> {
+        serde_json::to_writer_pretty(writer as &mut dyn std::io::Write, self)
+            .map_err(|e| anyhow::anyhow!("{}", e))?;
+        writeln!(writer).map_err(|e| anyhow::anyhow!("{}", e))
+    }
+}
+
+/// Build the `--explain` JSON output from an enriched snapshot.
+///
+/// Functions are emitted in snapshot order, so callers sort and truncate
+/// (top-N) beforehand exactly as for the text report.
+pub fn compute_explain_output(
+    snapshot: &crate::snapshot::Snapshot,
+    total_functions: usize,
+    repo_root: &std::path::Path,
+) -> ExplainOutput {
+    let functions = snapshot
+        .functions
+        .iter()
+        .map(|func| {
+            let function_name = func
+                .function_id
+                .split("::")
+                .last()
+                .unwrap_or(&func.function_id)
+                .to_string();
+            let file = normalize_path_relative_to_repo(&func.file, repo_root)
+                .unwrap_or_else(|| func.file.clone());
+            let driver = func.driver.as_deref().unwrap_or("composite");
+            let quadrant = func.quadrant.as_deref().unwrap_or("ok");
+            ExplainFunctionView {
+                function_id: func.function_id.clone(),
+                function: function_name,
+                file,
+                line: func.line,
+                band: func.band.as_str().to_string(),
+                lrs: func.lrs,
+                activity_risk: func.activity_risk.unwrap_or(func.lrs),
+                quadrant: quadrant.to_string(),
+                driver: driver.to_string(),
+                driver_detail: func.driver_detail.clone(),
+                action: crate::snapshot::driver_action_for_quadrant(
+                    driver,
+                    func.quadrant.as_deref().unwrap_or(""),
+                ),
+                risk_factors: func.risk_factors.clone(),
+                metrics: func.metrics.clone(),
+                touches_30d: func.touch_count_30d,
+                days_since_changed: func.days_since_last_change,
+                function_age_days: func.function_age_days,
+                fan_in: func.callgraph.as_ref().map(|cg| cg.fan_in),
+                coverage: func.coverage,
+                refactor_leverage: func.refactor_leverage,
+                patterns: func.patterns.clone(),
+                explanation: func.explanation.clone(),
+                suppression_reason: func.suppression_reason.clone(),
+            }
+        })
+        .collect();
+
+    ExplainOutput {
+        schema_version: EXPLAIN_OUTPUT_SCHEMA_VERSION,
+        commit: snapshot.commit.clone(),
+        total_functions,
+        by_language: snapshot
+            .summary
+            .as_ref()
+            .map(|s| s.by_language.clone())
+            .unwrap_or_default(),
+        functions,
+    }
+}
+
 fn normalize_model_risk_map(
     map: &crate::models::ModelRiskMap,
     repo_root: &std::path::Path,
@@ -457,7 +735,7 @@ fn normalize_model_risk_map(
     }
 }
 
-fn relativize_function_id(function_id: &str, repo_root: &std::path::Path) -> String {
+pub(crate) fn relativize_function_id(function_id: &str, repo_root: &std::path::Path) -> String {
     let Some((file, function)) = function_id.split_once("::") else {
         return function_id.to_string();
     };
@@ -482,7 +760,10 @@ fn extract_directory(file_path: &str) -> String {
 
 /// Normalize file path relative to repo root
 /// Returns None if path is outside repo root
-fn normalize_path_relative_to_repo(file_path: &str, repo_root: &std::path::Path) -> Option<String> {
+pub(crate) fn normalize_path_relative_to_repo(
+    file_path: &str,
+    repo_root: &std::path::Path,
+) -> Option<String> {
     let file_path_buf = std::path::PathBuf::from(file_path);
 
     // Try to make path relative to repo root
@@ -607,24 +888,36 @@ pub fn compute_directory_aggregates(
     aggregates
 }
 
+/// Per-file accumulator: (sum_cc, max_cc, count, critical_count, loc, file_churn, high_count)
+type FileTotals = (usize, usize, usize, usize, usize, u64, usize);
+
 /// Compute file risk views from snapshot functions
 ///
 /// Ranked descending by `file_risk_score`. Score formula:
-///   max_cc × 0.4 + avg_cc × 0.3 + log2(function_count + 1) × 0.2 + churn_factor × 0.1
-pub fn compute_file_risk_views(functions: &[FunctionSnapshot]) -> Vec<FileRiskView> {
-    // Accumulate (sum_cc, max_cc, count, critical_count, loc, file_churn) per file
-    let mut file_data: HashMap<String, (usize, usize, usize, usize, usize, u64)> = HashMap::new();
+///   max_cc × w.max_cc + avg_cc × w.avg_cc + log2(function_count + 1) × w.function_count
+///     + churn_factor × w.churn
+///
+/// `repo_root` resolves relative file paths for `functions_per_kloc`, which
+/// counts the file's own non-blank lines.
+pub fn compute_file_risk_views(
+    functions: &[FunctionSnapshot],
+    repo_root: &std::path::Path,
+    weights: &FileRiskWeights,
+) -> Vec<FileRiskView> {
+    let mut file_data: HashMap<String, FileTotals> = HashMap::new();
     for func in functions {
         let e = file_data
             .entry(func.file.clone())
-            .or_insert((0, 0, 0, 0, 0, 0));
+            .or_insert((0, 0, 0, 0, 0, 0, 0));
         e.0 += func.metrics.cc as usize;
         e.1 = e.1.max(func.metrics.cc as usize);
         e.2 += 1;
-        if func.band == RiskBand::Critical {
-            e.3 += 1;
+        match func.band {
+            RiskBand::Critical => e.3 += 1,
+            RiskBand::High => e.6 += 1,
+            _ => {}
         }
-        e.4 += func.metrics.loc as usize;
+        e.4 += func.metrics.effective_loc() as usize;
         if let Some(churn) = &func.churn {
             let lines = (churn.lines_added + churn.lines_deleted) as u64;
             e.5 = e.5.max(lines);
@@ -634,26 +927,41 @@ pub fn compute_file_risk_views(functions: &[FunctionSnapshot]) -> Vec<FileRiskVi
     let mut views: Vec<FileRiskView> = file_data
         .into_iter()
         .map(
-            |(file, (sum_cc, max_cc, function_count, critical_count, loc, file_churn))| {
+            |(
+                file,
+                (sum_cc, max_cc, function_count, critical_count, loc, file_churn, high_count),
+            )| {
                 let avg_cc = if function_count > 0 {
                     sum_cc as f64 / function_count as f64
                 } else {
                     0.0
                 };
+                let avg_function_loc = avg_function_loc(function_count, loc);
+                let functions_per_kloc = functions_per_kloc(
+                    function_count,
+                    count_nonblank_lines(&repo_root.join(&file)),
+                );
                 let churn_factor = (file_churn as f64 / 100.0).min(10.0);
-                let score = max_cc as f64 * 0.4
-                    + avg_cc * 0.3
-                    + (function_count as f64 + 1.0).log2() * 0.2
-                    + churn_factor * 0.1;
+                let score = max_cc as f64 * weights.max_cc
+                    + avg_cc * weights.avg_cc
+                    + (function_count as f64 + 1.0).log2() * weights.function_count
+                    + churn_factor * weights.churn;
                 FileRiskView {
                     file,
                     function_count,
                     loc,
+                    avg_function_loc,
+                    functions_per_kloc,
                     max_cc,
                     avg_cc: (avg_cc * 100.0).round() / 100.0,
                     critical_count,
+                    high_count,
                     file_churn,
                     file_risk_score: (score * 100.0).round() / 100.0,
+                    bus_factor: None,
+                    top_author: None,
+                    top_author_share: None,
+                    knowledge_risk: false,
                 }
             },
         )
@@ -669,6 +977,133 @@ pub fn compute_file_risk_views(functions: &[FunctionSnapshot]) -> Vec<FileRiskVi
     views
 }
 
+/// Mean lines per function of `function_count` functions spanning `loc`
+/// lines, rounded to 2 decimals; zero when either count is
+fn avg_function_loc(function_count: usize, loc: usize) -> f64 {
+    if function_count == 0 || loc == 0 {
+        return 0.0;
+    }
+    (loc as f64 / function_count as f64 * 100.0).round() / 100.0
+}
+
+/// Functions per 1000 of the `source_lines` that contain them, rounded to 2
+/// decimals; zero when either count is
+fn functions_per_kloc(function_count: usize, source_lines: usize) -> f64 {
+    if function_count == 0 || source_lines == 0 {
+        return 0.0;
+    }
+    (function_count as f64 * 1000.0 / source_lines as f64 * 100.0).round() / 100.0
+}
+
+/// Share of a file's lines above which a single author is a knowledge risk
+const KNOWLEDGE_RISK_OWNER_SHARE: f64 = 0.8;
+
+/// Fill in the authorship fields of `views` from per-file author line counts
+/// (see [`crate::git::extract_file_author_lines`]).
+///
+/// `bus_factor` is the smallest number of authors whose lines together exceed
+/// half of the file; `knowledge_risk` flags files with high or critical
+/// functions where one author wrote more than 80% of the lines. Files without history are left
+/// untouched.
+pub fn apply_file_authorship(
+    views: &mut [FileRiskView],
+    author_lines: &crate::git::FileAuthorLines,
+    repo_root: &std::path::Path,
+) {
+    for view in views.iter_mut() {
+        let rel_file = normalize_path_relative_to_repo(&view.file, repo_root)
+            .unwrap_or_else(|| view.file.clone());
+        let Some(authors) = author_lines.get(&rel_file) else {
+            continue;
+        };
+        let total: u64 = authors.values().sum();
+        if total == 0 {
+            continue;
+        }
+        let counts = ranked_authors(authors);
+
+        let mut covered = 0u64;
+        let mut bus_factor = 0usize;
+        for (_, lines) in &counts {
+            covered += lines;
+            bus_factor += 1;
+            if covered * 2 > total {
+                break;
+            }
+        }
+
+        let share = counts[0].1 as f64 / total as f64;
+        view.bus_factor = Some(bus_factor);
+        view.top_author = Some(counts[0].0.clone());
+        view.top_author_share = Some((share * 100.0).round() / 100.0);
+        view.knowledge_risk =
+            view.critical_count + view.high_count > 0 && share > KNOWLEDGE_RISK_OWNER_SHARE;
+    }
+}
+
+/// A file's authors by lines added, most first (ties broken by name)
+fn ranked_authors(authors: &HashMap<String, u64>) -> Vec<(&String, u64)> {
+    let mut counts: Vec<(&String, u64)> = authors.iter().map(|(a, n)| (a, *n)).collect();
+    counts.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+    counts
+}
+
+/// Keep only functions whose file is primarily owned by `author` (`--author`).
+///
+/// A file's primary author is its `top_author`: the one who added the most
+/// lines. `author` is matched case-insensitively against the mailmap-canonical
+/// names of [`crate::git::extract_file_author_lines`]. Functions in files
+/// without history are dropped.
+pub fn retain_functions_owned_by(
+    functions: &mut Vec<FunctionSnapshot>,
+    author: &str,
+    author_lines: &crate::git::FileAuthorLines,
+    repo_root: &std::path::Path,
+) {
+    let author = author.to_lowercase();
+    let owned_by_author = |file: &str| {
+        normalize_path_relative_to_repo(file, repo_root)
+            .and_then(|rel_file| author_lines.get(&rel_file))
+            .and_then(|authors| ranked_authors(authors).first().map(|(name, _)| *name))
+            .is_some_and(|owner| owner.to_lowercase() == author)
+    };
+    functions.retain(|f| owned_by_author(&f.file));
+}
+
+/// Keep only functions whose line range overlaps a changed line
+/// (`--only-changed-functions`).
+///
+/// `changed` is keyed by repo-relative path, as from
+/// [`crate::git::changed_lines_since`].
+pub fn retain_changed_functions(
+    functions: &mut Vec<FunctionSnapshot>,
+    changed: &crate::git::AddedLines,
+    repo_root: &std::path::Path,
+) {
+    functions.retain(|f| {
+        normalize_path_relative_to_repo(&f.file, repo_root)
+            .and_then(|rel_file| changed.get(&rel_file))
+            .is_some_and(|ranges| {
+                ranges
+                    .iter()
+                    .any(|&(start, end)| start <= f.end_line() && f.line <= end)
+            })
+    });
+}
+
+/// Mine git authorship for `repo_root` and apply it to `views`.
+///
+/// Runs one `git log --numstat` over the full history, so it is opt-in
+/// (`--with-authors`).
+pub fn annotate_file_authorship(
+    views: &mut [FileRiskView],
+    repo_root: &std::path::Path,
+) -> anyhow::Result<()> {
+    let author_lines = crate::git::extract_file_author_lines(repo_root)?;
+    apply_file_authorship(views, &author_lines, repo_root);
+    Ok(())
+}
+
 /// Annotate co-change pairs with `has_static_dep` from the import edge set.
 ///
 /// For each pair, checks whether a direct import exists in either direction.
@@ -698,10 +1133,11 @@ pub fn annotate_static_deps(
 }
 
 /// Compute module (directory) instability from a pre-computed import edge list.
-fn compute_module_instability_from_edges(
+pub fn compute_module_instability_from_edges(
     functions: &[FunctionSnapshot],
     edges: &[(String, String)],
     repo_root: &std::path::Path,
+    zones: &ModuleZoneThresholds,
 ) -> Vec<ModuleInstability> {
     // Helper: extract directory from a file path (relative to repo_root)
     let file_dir = |file: &str| -> Option<String> {
@@ -733,6 +1169,7 @@ fn compute_module_instability_from_edges(
         files: std::collections::HashSet<String>,
         function_count: usize,
         sum_cc: usize,
+        loc: usize,
     }
     let mut dir_stats: HashMap<String, DirStats> = HashMap::new();
 
@@ -745,10 +1182,12 @@ fn compute_module_instability_from_edges(
             files: std::collections::HashSet::new(),
             function_count: 0,
             sum_cc: 0,
+            loc: 0,
         });
         stats.files.insert(func.file.clone());
         stats.function_count += 1;
         stats.sum_cc += func.metrics.cc as usize;
+        stats.loc += func.metrics.effective_loc() as usize;
     }
 
     // Collect all directory names seen in any of the three maps
@@ -775,20 +1214,40 @@ fn compute_module_instability_from_edges(
             } else {
                 0.0
             };
-            let module_risk = if instability < 0.3 && avg_complexity > 10.0 {
+            let avg_function_loc = avg_function_loc(stats.function_count, stats.loc);
+            let module_risk = if zones.in_zone_of_pain(instability, avg_complexity) {
                 "high".to_string()
             } else {
                 "low".to_string()
             };
+            let source_loc: usize = stats
+                .files
+                .iter()
+                .map(|f| count_nonblank_lines(&repo_root.join(f)))
+                .sum();
+            let test_files = module_test_files(&repo_root.join(&dir));
+            let test_loc: usize = test_files.iter().map(|f| count_nonblank_lines(f)).sum();
+            let test_file_ratio = test_files.len() as f64 / stats.files.len().max(1) as f64;
+            let functions_per_kloc = functions_per_kloc(stats.function_count, source_loc);
+            let test_loc_ratio = if source_loc > 0 {
+                test_loc as f64 / source_loc as f64
+            } else {
+                0.0
+            };
             Some(ModuleInstability {
                 module: dir,
                 file_count: stats.files.len(),
                 function_count: stats.function_count,
+                avg_function_loc,
+                functions_per_kloc,
                 avg_complexity: (avg_complexity * 100.0).round() / 100.0,
                 afferent: aff,
                 efferent: eff,
                 instability: (instability * 1000.0).round() / 1000.0,
                 module_risk,
+                test_file_count: test_files.len(),
+                test_file_ratio: (test_file_ratio * 100.0).round() / 100.0,
+                test_loc_ratio: (test_loc_ratio * 100.0).round() / 100.0,
             })
         })
         .collect();
@@ -808,12 +1267,43 @@ fn compute_module_instability_from_edges(
     modules
 }
 
+/// Test files that accompany a module: those directly in `dir` plus anything
+/// under `dir/__tests__/`. Sorted for determinism.
+fn module_test_files(dir: &std::path::Path) -> Vec<std::path::PathBuf> {
+    let list = |d: &std::path::Path| -> Vec<std::path::PathBuf> {
+        std::fs::read_dir(d)
+            .map(|entries| {
+                entries
+                    .filter_map(|e| e.ok())
+                    .map(|e| e.path())
+                    .filter(|p| p.is_file())
+                    .collect()
+            })
+            .unwrap_or_default()
+    };
+    let mut files: Vec<std::path::PathBuf> = list(dir)
+        .into_iter()
+        .chain(list(&dir.join("__tests__")))
+        .filter(|p| crate::config::is_test_file(p))
+        .collect();
+    files.sort();
+    files
+}
+
+/// Non-blank line count of a file; 0 if it can't be read.
+fn count_nonblank_lines(path: &std::path::Path) -> usize {
+    std::fs::read_to_string(path)
+        .map(|content| content.lines().filter(|l| !l.trim().is_empty()).count())
+        .unwrap_or(0)
+}
+
 /// Compute module instability from snapshot functions (computes import edges internally).
 ///
 /// Exposed as a public API for callers that don't have pre-computed edges.
 pub fn compute_module_instability(
     functions: &[FunctionSnapshot],
     repo_root: &std::path::Path,
+    zones: &ModuleZoneThresholds,
 ) -> Vec<ModuleInstability> {
     let mut unique_files: Vec<String> = functions
         .iter()
@@ -824,7 +1314,7 @@ pub fn compute_module_instability(
     unique_files.sort();
     let files_as_str: Vec<&str> = unique_files.iter().map(|s| s.as_str()).collect();
     let edges = crate::imports::resolve_file_deps(&files_as_str, repo_root);
-    compute_module_instability_from_edges(functions, &edges, repo_root)
+    compute_module_instability_from_edges(functions, &edges, repo_root, zones)
 }
 
 /// Compute snapshot aggregates
@@ -833,17 +1323,22 @@ pub fn compute_module_instability(
 ///
 /// * `snapshot` - Snapshot to compute aggregates for
 /// * `repo_root` - Repository root path for normalizing directory paths
+/// * `co_change` - Co-change mining parameters (see [`crate::git::extract_co_change_pairs`])
+/// * `file_risk_weights` - Coefficients for the `file_risk` score
+/// * `module_zones` - Cutoffs behind each module's `module_risk` and zone label
 pub fn compute_snapshot_aggregates(
     snapshot: &Snapshot,
     repo_root: &std::path::Path,
-    co_change_window_days: u64,
-    co_change_min_count: usize,
+    co_change: crate::git::CoChangeOptions,
+    file_risk_weights: &FileRiskWeights,
+    module_zones: &ModuleZoneThresholds,
 ) -> SnapshotAggregates {
     compute_snapshot_aggregates_with_models(
         snapshot,
         repo_root,
-        co_change_window_days,
-        co_change_min_count,
+        co_change,
+        file_risk_weights,
+        module_zones,
         None,
     )
 }
@@ -852,13 +1347,52 @@ pub fn compute_snapshot_aggregates(
 pub fn compute_snapshot_aggregates_with_models(
     snapshot: &Snapshot,
     repo_root: &std::path::Path,
-    co_change_window_days: u64,
-    co_change_min_count: usize,
+    co_change: crate::git::CoChangeOptions,
+    file_risk_weights: &FileRiskWeights,
+    module_zones: &ModuleZoneThresholds,
+    model_source_root: Option<&std::path::Path>,
+) -> SnapshotAggregates {
+    aggregate_snapshot(
+        snapshot,
+        repo_root,
+        Some(co_change),
+        file_risk_weights,
+        module_zones,
+        model_source_root,
+    )
+}
+
+/// Compute snapshot aggregates from source alone, for `--metrics-only`.
+///
+/// Imports still feed module instability, but co-change needs git history and
+/// is left empty.
+pub fn compute_structural_aggregates(
+    snapshot: &Snapshot,
+    repo_root: &std::path::Path,
+    file_risk_weights: &FileRiskWeights,
+    module_zones: &ModuleZoneThresholds,
+) -> SnapshotAggregates {
+    aggregate_snapshot(
+        snapshot,
+        repo_root,
+        None,
+        file_risk_weights,
+        module_zones,
+        None,
+    )
+}
+
+fn aggregate_snapshot(
+    snapshot: &Snapshot,
+    repo_root: &std::path::Path,
+    co_change: Option<crate::git::CoChangeOptions>,
+    file_risk_weights: &FileRiskWeights,
+    module_zones: &ModuleZoneThresholds,
     model_source_root: Option<&std::path::Path>,
 ) -> SnapshotAggregates {
     let files = compute_file_aggregates(&snapshot.functions);
     let directories = compute_directory_aggregates(&files, repo_root);
-    let file_risk = compute_file_risk_views(&snapshot.functions);
+    let file_risk = compute_file_risk_views(&snapshot.functions, repo_root, file_risk_weights);
 
     // Compute import edges once — shared by module instability and co-change annotation
     let mut unique_files: Vec<String> = snapshot
@@ -876,12 +1410,17 @@ pub fn compute_snapshot_aggregates_with_models(
         &files_as_str,
     ));
 
-    let mut co_change =
-        crate::git::extract_co_change_pairs(repo_root, co_change_window_days, co_change_min_count)
-            .unwrap_or_default();
+    let mut co_change = co_change
+        .and_then(|options| crate::git::extract_co_change_pairs(repo_root, options).ok())
+        .unwrap_or_default();
     annotate_static_deps(&mut co_change, &all_edges, repo_root);
 
-    let modules = compute_module_instability_from_edges(&snapshot.functions, &all_edges, repo_root);
+    let modules = compute_module_instability_from_edges(
+        &snapshot.functions,
+        &all_edges,
+        repo_root,
+        module_zones,
+    );
     let models = model_source_root.and_then(|source_root| {
         crate::models::compute_model_risk_map(source_root, repo_root, snapshot, Some(10)).ok()
     });
@@ -893,6 +1432,8 @@ pub fn compute_snapshot_aggregates_with_models(
         co_change,
         modules,
         models,
+        file_risk_weights: *file_risk_weights,
+        module_zones: *module_zones,
     }
 }
 
@@ -999,22 +1540,56 @@ pub fn diff_co_change_pairs(
     result
 }
 
+/// Pair each current module with its parent-side state, keeping only modules
+/// that are new or whose complexity or coupling changed. Sorted by module path.
+pub fn diff_module_instability(
+    prev: &[ModuleInstability],
+    current: &[ModuleInstability],
+) -> Vec<ModuleDeltaEntry> {
+    let prev_by_module: HashMap<&str, &ModuleInstability> =
+        prev.iter().map(|m| (m.module.as_str(), m)).collect();
+
+    let mut entries: Vec<ModuleDeltaEntry> = current
+        .iter()
+        .filter_map(|after| {
+            let before = prev_by_module.get(after.module.as_str()).copied();
+            let changed = before.map_or(true, |b| {
+                b.avg_complexity != after.avg_complexity
+                    || b.afferent != after.afferent
+                    || b.efferent != after.efferent
+            });
+            changed.then(|| ModuleDeltaEntry {
+                module: after.module.clone(),
+                before: before.cloned(),
+                after: after.clone(),
+            })
+        })
+        .collect();
+    entries.sort_by(|a, b| a.module.cmp(&b.module));
+    entries
+}
+
 /// Compute delta aggregates from delta entries
 ///
 /// Sorted by `net_lrs_delta` descending (worst regressions first).
-/// Ties broken by file path for determinism.
+/// Ties broken by file path for determinism. Module entries come from
+/// [`diff_module_instability`] over the parent and current module views.
 pub fn compute_delta_aggregates(
     delta: &Delta,
     current_co_change: &[crate::git::CoChangePair],
     prev_co_change: &[crate::git::CoChangePair],
+    current_modules: &[ModuleInstability],
+    prev_modules: &[ModuleInstability],
 ) -> DeltaAggregates {
     // (net_lrs_delta, regression_count, improvement_count)
     let mut file_data: HashMap<String, (f64, usize, usize)> = HashMap::new();
 
     for entry in &delta.deltas {
-        // Extract file path from function_id (format: "path/to/file.ts::function")
-        let file = if let Some(sep_pos) = entry.function_id.rfind("::") {
-            entry.function_id[..sep_pos].to_string()
+        // Extract file path from function_id (format: "path/to/file.ts::function");
+        // the function part may itself contain `::` (`Type::method`,
+        // `outer::<anonymous@4>`), so split at the first separator
+        let file = if let Some((file, _)) = entry.function_id.split_once("::") {
+            file.to_string()
         } else {
             continue; // Skip malformed function_id
         };
@@ -1071,6 +1646,7 @@ pub fn compute_delta_aggregates(
     DeltaAggregates {
         files: aggregates,
         co_change_delta,
+        modules: diff_module_instability(prev_modules, current_modules),
     }
 }
 
@@ -1092,6 +1668,12 @@ mod tests {
                 fo: 0,
                 ns: 0,
                 loc: 10,
+                sloc: None,
+                return_points: None,
+                unwrap_count: None,
+                max_arm_loc: None,
+                max_data_nesting: None,
+                unreachable_code: None,
             },
             lrs,
             band: crate::risk::RiskBand::parse(band).unwrap_or(crate::risk::RiskBand::Low),
@@ -1120,7 +1702,12 @@ mod tests {
             isolation_rate: None,
             age_days: None,
             last_touch_days: None,
+            function_age_days: None,
+            coverage: None,
+            priority_score: None,
+            refactor_leverage: None,
             explanation: None,
+            location: None,
         }
     }
 
@@ -1146,6 +1733,69 @@ mod tests {
         assert_eq!(bar_agg.high_plus_count, 0);
     }
 
+    #[test]
+    fn test_file_risk_views_use_configured_weights() {
+        let mut functions = vec![
+            create_test_function("src/foo.ts", "func1", 5.0, "moderate"),
+            create_test_function("src/foo.ts", "func2", 8.0, "high"),
+        ];
+        functions[1].metrics.cc = 5;
+
+        // Defaults: 5×0.4 + 3×0.3 + log2(3)×0.2 + 0×0.1
+        let views = compute_file_risk_views(
+            &functions,
+            std::path::Path::new("/repo"),
+            &FileRiskWeights::default(),
+        );
+        assert_eq!(views[0].file_risk_score, 3.22);
+
+        let size_only = FileRiskWeights {
+            max_cc: 0.0,
+            avg_cc: 0.0,
+            function_count: 1.0,
+            churn: 0.0,
+        };
+        let views = compute_file_risk_views(&functions, std::path::Path::new("/repo"), &size_only);
+        assert_eq!(views[0].file_risk_score, 1.58);
+    }
+
+    #[test]
+    fn test_file_and_module_function_density() {
+        let mut functions = vec![
+            create_test_function("src/big.ts", "giant", 9.0, "high"),
+            create_test_function("src/small.ts", "a", 1.0, "low"),
+            create_test_function("src/small.ts", "b", 1.0, "low"),
+            create_test_function("src/small.ts", "c", 1.0, "low"),
+        ];
+        functions[0].metrics.loc = 400;
+        functions[1].metrics.sloc = Some(5);
+
+        // Density counts each file's own non-blank lines, not the functions' LOC
+        let tmp = tempfile::tempdir().unwrap();
+        std::fs::create_dir(tmp.path().join("src")).unwrap();
+        std::fs::write(tmp.path().join("src/big.ts"), "x\n\n".repeat(500)).unwrap();
+        std::fs::write(tmp.path().join("src/small.ts"), "x\n".repeat(30)).unwrap();
+
+        let views = compute_file_risk_views(&functions, tmp.path(), &FileRiskWeights::default());
+        let big = views.iter().find(|v| v.file == "src/big.ts").unwrap();
+        assert_eq!((big.avg_function_loc, big.functions_per_kloc), (400.0, 2.0));
+        // SLOC stands in for LOC where known: 5 + 10 + 10 lines
+        let small = views.iter().find(|v| v.file == "src/small.ts").unwrap();
+        assert_eq!(
+            (small.avg_function_loc, small.functions_per_kloc),
+            (8.33, 100.0)
+        );
+
+        let modules = compute_module_instability_from_edges(
+            &functions,
+            &[],
+            tmp.path(),
+            &ModuleZoneThresholds::default(),
+        );
+        assert_eq!(modules[0].avg_function_loc, 106.25);
+        assert_eq!(modules[0].functions_per_kloc, 7.55);
+    }
+
     #[test]
     fn test_directory_aggregates() {
         let file_aggregates = vec![
@@ -1198,4 +1848,261 @@ mod tests {
         assert!(!is_high_plus(crate::risk::RiskBand::Moderate));
         assert!(!is_high_plus(crate::risk::RiskBand::Low));
     }
+
+    #[test]
+    fn test_apply_file_authorship_flags_single_owner() {
+        let mut functions = vec![
+            create_test_function("/repo/src/owned.ts", "a", 9.5, "critical"),
+            create_test_function("/repo/src/shared.ts", "b", 9.5, "critical"),
+            create_test_function("/repo/src/high.ts", "c", 7.0, "high"),
+            create_test_function("/repo/src/calm.ts", "d", 4.0, "moderate"),
+        ];
+        functions[0].metrics.cc = 3;
+        let mut views = compute_file_risk_views(
+            &functions,
+            std::path::Path::new("/repo"),
+            &FileRiskWeights::default(),
+        );
+
+        let mut author_lines = crate::git::FileAuthorLines::new();
+        author_lines.insert(
+            "src/owned.ts".to_string(),
+            [("alice".to_string(), 90), ("bob".to_string(), 10)].into(),
+        );
+        author_lines.insert(
+            "src/shared.ts".to_string(),
+            [
+                ("alice".to_string(), 40),
+                ("bob".to_string(), 35),
+                ("carol".to_string(), 25),
+            ]
+            .into(),
+        );
+        for file in ["src/high.ts", "src/calm.ts"] {
+            author_lines.insert(file.to_string(), [("alice".to_string(), 100)].into());
+        }
+        apply_file_authorship(&mut views, &author_lines, std::path::Path::new("/repo"));
+
+        let owned = views.iter().find(|v| v.file.ends_with("owned.ts")).unwrap();
+        assert_eq!(owned.bus_factor, Some(1));
+        assert_eq!(owned.top_author.as_deref(), Some("alice"));
+        assert_eq!(owned.top_author_share, Some(0.9));
+        assert!(owned.knowledge_risk);
+
+        let shared = views
+            .iter()
+            .find(|v| v.file.ends_with("shared.ts"))
+            .unwrap();
+        assert_eq!(shared.bus_factor, Some(2));
+        assert!(!shared.knowledge_risk);
+
+        // A high-band function is risky enough; a moderate one is not
+        let flagged = |suffix: &str| {
+            views
+                .iter()
+                .find(|v| v.file.ends_with(suffix))
+                .unwrap()
+                .knowledge_risk
+        };
+        assert!(flagged("high.ts"));
+        assert!(!flagged("calm.ts"));
+    }
+
+    #[test]
+    fn test_retain_functions_owned_by_primary_author() {
+        let mut functions = vec![
+            create_test_function("/repo/src/owned.ts", "a", 9.5, "critical"),
+            create_test_function("/repo/src/shared.ts", "b", 5.0, "moderate"),
+            create_test_function("/repo/src/new.ts", "c", 1.0, "low"),
+        ];
+        let mut author_lines = crate::git::FileAuthorLines::new();
+        author_lines.insert(
+            "src/owned.ts".to_string(),
+            [("Alice".to_string(), 90), ("Bob".to_string(), 10)].into(),
+        );
+        // Tied: the name that sorts first owns the file
+        author_lines.insert(
+            "src/shared.ts".to_string(),
+            [("Bob".to_string(), 50), ("Alice".to_string(), 50)].into(),
+        );
+        retain_functions_owned_by(
+            &mut functions,
+            "alice",
+            &author_lines,
+            std::path::Path::new("/repo"),
+        );
+
+        let files: Vec<&str> = functions.iter().map(|f| f.file.as_str()).collect();
+        assert_eq!(files, vec!["/repo/src/owned.ts", "/repo/src/shared.ts"]);
+    }
+
+    #[test]
+    fn test_retain_changed_functions_intersects_line_ranges() {
+        let mut functions = vec![
+            create_test_function("/repo/src/a.ts", "top", 9.5, "critical"),
+            create_test_function("/repo/src/a.ts", "below", 5.0, "moderate"),
+            create_test_function("/repo/src/b.ts", "untouched", 8.0, "high"),
+        ];
+        // Each test function spans 10 lines from `line`
+        functions[1].line = 20;
+        let mut changed = crate::git::AddedLines::new();
+        changed.insert("src/a.ts".to_string(), vec![(10, 10), (30, 31)]);
+        changed.insert("src/c.ts".to_string(), vec![(1, 5)]);
+        retain_changed_functions(&mut functions, &changed, std::path::Path::new("/repo"));
+
+        let ids: Vec<&str> = functions.iter().map(|f| f.function_id.as_str()).collect();
+        assert_eq!(ids, vec!["/repo/src/a.ts::top"]);
+    }
+
+    #[test]
+    fn test_module_test_ratios_pair_source_with_test_files() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path();
+        std::fs::create_dir_all(root.join("src/__tests__")).unwrap();
+        std::fs::write(root.join("src/a.ts"), "a\nb\n\nc\nd\n").unwrap();
+        std::fs::write(root.join("src/b.ts"), "a\nb\nc\nd\n").unwrap();
+        std::fs::write(root.join("src/a.test.ts"), "t\nt\n").unwrap();
+        std::fs::write(root.join("src/__tests__/b.js"), "t\nt\n\n").unwrap();
+
+        let functions = vec![
+            create_test_function(root.join("src/a.ts").to_str().unwrap(), "f", 1.0, "low"),
+            create_test_function(root.join("src/b.ts").to_str().unwrap(), "g", 1.0, "low"),
+        ];
+        let modules = compute_module_instability_from_edges(
+            &functions,
+            &[],
+            root,
+            &ModuleZoneThresholds::default(),
+        );
+        let src = modules.iter().find(|m| m.module == "src").unwrap();
+        assert_eq!(src.test_file_count, 2);
+        assert_eq!(src.test_file_ratio, 1.0);
+        assert_eq!(src.test_loc_ratio, 0.5);
+    }
+
+    #[test]
+    fn test_explain_output_carries_risk_breakdown() {
+        let git_context = crate::git::GitContext {
+            head_sha: "abc123".to_string(),
+            parent_shas: vec![],
+            timestamp: 1705600000,
+            branch: Some("main".to_string()),
+            is_detached: false,
+            message: None,
+            author: None,
+            is_fix_commit: None,
+            is_revert_commit: None,
+            ticket_ids: vec![],
+        };
+        let mut snapshot = crate::snapshot::Snapshot::new(git_context, vec![]);
+        let mut func = create_test_function("/repo/src/foo.ts", "handler", 9.5, "critical");
+        func.driver = Some("high_complexity".to_string());
+        func.quadrant = Some("fire".to_string());
+        func.risk_factors = Some(crate::scoring::RiskFactors {
+            complexity: 9.5,
+            churn: 1.0,
+            activity: 2.0,
+            recency: 0.5,
+            fan_in: 0.0,
+            cyclic_dependency: 0.0,
+            depth: 0.0,
+            neighbor_churn: 0.0,
+            burst: 0.0,
+        });
+        snapshot.functions = vec![func];
+
+        let output = compute_explain_output(&snapshot, 12, std::path::Path::new("/repo"));
+        assert_eq!(output.total_functions, 12);
+        let view = &output.functions[0];
+        assert_eq!(view.function, "handler");
+        assert_eq!(view.file, "src/foo.ts");
+        assert_eq!(view.quadrant, "fire");
+        assert_eq!(
+            view.action,
+            crate::snapshot::driver_action_for_quadrant("high_complexity", "fire")
+        );
+        assert_eq!(view.risk_factors.as_ref().map(|r| r.activity), Some(2.0));
+    }
+
+    #[test]
+    fn test_module_zone_classification_uses_cutoffs() {
+        let defaults = ModuleZoneThresholds::default();
+        assert_eq!(defaults.classify(0.1, 9.0).0, "zone of pain");
+        assert_eq!(defaults.classify(0.5, 20.0).0, "balanced");
+
+        let tuned = ModuleZoneThresholds {
+            pain_complexity: 12.0,
+            ..defaults
+        };
+        assert_eq!(tuned.classify(0.1, 9.0).0, "stable");
+        assert_eq!(tuned.classify(0.9, 13.0).1, "zone-volatile-complex");
+    }
+
+    #[test]
+    fn test_module_risk_follows_zone_cutoffs() {
+        let mut functions = vec![
+            create_test_function("/repo/app/main.ts", "main", 1.0, "low"),
+            create_test_function("/repo/lib/util.ts", "util", 5.0, "moderate"),
+        ];
+        functions[1].metrics.cc = 9;
+        let edges = vec![(
+            "/repo/app/main.ts".to_string(),
+            "/repo/lib/util.ts".to_string(),
+        )];
+        let root = std::path::Path::new("/repo");
+        let risk = |zones: &ModuleZoneThresholds| {
+            compute_module_instability_from_edges(&functions, &edges, root, zones)
+                .into_iter()
+                .find(|m| m.module == "lib")
+                .unwrap()
+                .module_risk
+        };
+
+        // Stable (instability 0) with avg CC 9: in the zone of pain by default
+        let defaults = ModuleZoneThresholds::default();
+        assert_eq!(risk(&defaults), "high");
+        let tuned = ModuleZoneThresholds {
+            pain_complexity: 12.0,
+            ..defaults
+        };
+        assert_eq!(risk(&tuned), "low");
+    }
+
+    fn module(name: &str, avg_complexity: f64, afferent: usize) -> ModuleInstability {
+        ModuleInstability {
+            module: name.to_string(),
+            file_count: 1,
+            function_count: 1,
+            avg_function_loc: 0.0,
+            functions_per_kloc: 0.0,
+            avg_complexity,
+            afferent,
+            efferent: 1,
+            instability: 1.0 / (afferent + 1) as f64,
+            module_risk: "low".to_string(),
+            test_file_count: 0,
+            test_file_ratio: 0.0,
+            test_loc_ratio: 0.0,
+        }
+    }
+
+    #[test]
+    fn test_diff_module_instability_keeps_new_and_changed_modules() {
+        let prev = vec![module("src/core", 12.0, 3), module("src/ui", 4.0, 0)];
+        let current = vec![
+            module("src/ui", 4.0, 0),
+            module("src/core", 12.5, 3),
+            module("src/api", 2.0, 1),
+        ];
+
+        let entries = diff_module_instability(&prev, &current);
+        let names: Vec<&str> = entries.iter().map(|e| e.module.as_str()).collect();
+        assert_eq!(names, vec!["src/api", "src/core"]);
+        assert!(entries[0].before.is_none());
+        assert_eq!(
+            entries[1].before.as_ref().map(|b| b.avg_complexity),
+            Some(12.0)
+        );
+        assert_eq!(entries[1].after.avg_complexity, 12.5);
+    }
 }
@@ -1,11 +1,14 @@
 //! Analysis orchestration - ties together parsing, discovery, CFG, metrics, and reporting
 
 use crate::ast::FunctionNode;
+use crate::cfg::Cfg;
+use crate::diagnostics::{self, Level};
 use crate::language::{self, Language, LanguageParser};
 use crate::metrics;
 use crate::report;
 use crate::risk;
 use anyhow::{Context, Result};
+use std::collections::HashMap;
 use std::path::Path;
 use swc_common::{sync::Lrc, SourceMap};
 
@@ -29,54 +32,382 @@ pub fn analyze_file_with_config(
     thresholds: Option<&risk::RiskThresholds>,
     pattern_thresholds: Option<&crate::patterns::Thresholds>,
 ) -> Result<Vec<report::FunctionRiskReport>> {
+    let settings = AnalysisSettings {
+        weights,
+        thresholds,
+        pattern_thresholds,
+        rust_branching_macros: None,
+    };
+    let analyzed = analyze_file_with_cfgs(path, source_map, file_index, options, settings)?;
+    Ok(analyzed.into_iter().map(|(report, _)| report).collect())
+}
+
+/// Scoring and measurement settings of a file analysis; `None` falls back to
+/// the defaults
+#[derive(Clone, Copy, Default)]
+pub(crate) struct AnalysisSettings<'a> {
+    pub weights: Option<&'a risk::LrsWeights>,
+    pub thresholds: Option<&'a risk::RiskThresholds>,
+    pub pattern_thresholds: Option<&'a crate::patterns::Thresholds>,
+    /// Rust macros counted as decision points, in place of
+    /// [`language::rust::DEFAULT_BRANCHING_MACROS`]
+    pub rust_branching_macros: Option<&'a [String]>,
+}
+
+/// Like [`analyze_file_with_config`] but keeps each function's CFG alongside its report
+pub(crate) fn analyze_file_with_cfgs(
+    path: &Path,
+    source_map: &Lrc<SourceMap>,
+    file_index: usize,
+    options: &crate::AnalysisOptions,
+    settings: AnalysisSettings<'_>,
+) -> Result<Vec<(report::FunctionRiskReport, Cfg)>> {
     let default_weights = risk::LrsWeights::default();
     let default_thresholds = risk::RiskThresholds::default();
     let default_pattern_thresholds = crate::patterns::Thresholds::default();
-    let w = weights.unwrap_or(&default_weights);
-    let t = thresholds.unwrap_or(&default_thresholds);
-    let pt = pattern_thresholds.unwrap_or(&default_pattern_thresholds);
-
-    let src = std::fs::read_to_string(path)
-        .with_context(|| format!("Failed to read file: {}", path.display()))?;
-
-    let (max_line, long_line_count) = long_line_stats(&src, 1000);
-    if long_line_count >= 3 {
-        eprintln!(
-            "warning: skipping {} — looks minified or machine-generated \
-             ({} lines exceed 1000 chars, max: {})",
-            path.display(),
-            long_line_count,
-            max_line
-        );
+    let Some(parsed) = parse_file(path, source_map)? else {
         return Ok(vec![]);
-    } else if looks_vendored(path) {
-        eprintln!(
-            "warning: skipping {} — path suggests vendored or generated third-party code",
-            path.display()
-        );
+    };
+
+    let source_lines: Vec<&str> = parsed.src.lines().collect();
+    let func_cfg = FunctionAnalysisConfig {
+        options,
+        weights: settings.weights.unwrap_or(&default_weights),
+        thresholds: settings.thresholds.unwrap_or(&default_thresholds),
+        pattern_thresholds: settings
+            .pattern_thresholds
+            .unwrap_or(&default_pattern_thresholds),
+        rust_branching_macros: settings.rust_branching_macros,
+        source_map,
+        source_lines: &source_lines,
+    };
+    Ok(analyze_parsed(&parsed, path, file_index, &func_cfg))
+}
+
+/// Analyze in-memory source with default weights and thresholds
+///
+/// `filename` only picks the language (by extension) and names the file in
+/// the reports; nothing is read from disk. Unlike file analysis, minified or
+/// vendored-looking sources are analyzed as given. This is the entry point of
+/// builds without the `native` feature (e.g. `wasm32-unknown-unknown`).
+///
+/// # Errors
+///
+/// Returns error if `filename` has an unsupported extension or the source
+/// fails to parse.
+pub fn analyze_source(
+    source: &str,
+    filename: &str,
+    options: &crate::AnalysisOptions,
+) -> Result<Vec<report::FunctionRiskReport>> {
+    analyze_source_with_settings(source, filename, 0, options, AnalysisSettings::default())
+}
+
+/// Like [`analyze_source`] with a configured run's settings
+pub(crate) fn analyze_source_with_settings(
+    source: &str,
+    filename: &str,
+    file_index: usize,
+    options: &crate::AnalysisOptions,
+    settings: AnalysisSettings<'_>,
+) -> Result<Vec<report::FunctionRiskReport>> {
+    let source_map: Lrc<SourceMap> = Default::default();
+    let path = Path::new(filename);
+    let parsed = parse_source(source.to_string(), path, &source_map)?;
+
+    let default_weights = risk::LrsWeights::default();
+    let default_thresholds = risk::RiskThresholds::default();
+    let default_pattern_thresholds = crate::patterns::Thresholds::default();
+    let source_lines: Vec<&str> = parsed.src.lines().collect();
+    let func_cfg = FunctionAnalysisConfig {
+        options,
+        weights: settings.weights.unwrap_or(&default_weights),
+        thresholds: settings.thresholds.unwrap_or(&default_thresholds),
+        pattern_thresholds: settings
+            .pattern_thresholds
+            .unwrap_or(&default_pattern_thresholds),
+        rust_branching_macros: settings.rust_branching_macros,
+        source_map: &source_map,
+        source_lines: &source_lines,
+    };
+    Ok(analyze_parsed(&parsed, path, file_index, &func_cfg)
+        .into_iter()
+        .map(|(report, _)| report)
+        .collect())
+}
+
+/// Like [`analyze_source_with_settings`] for the raw contents of a file read
+/// from somewhere other than disk (an archive member)
+///
+/// As in file analysis, notebooks are converted to their code cells and
+/// minified or vendored-looking sources are skipped. `filename` is relative
+/// to the root the files came from, so a top-level `vendor/` counts.
+#[cfg(feature = "native")]
+pub(crate) fn analyze_contents_with_settings(
+    contents: String,
+    filename: &str,
+    file_index: usize,
+    options: &crate::AnalysisOptions,
+    settings: AnalysisSettings<'_>,
+) -> Result<Vec<report::FunctionRiskReport>> {
+    let path = Path::new(filename);
+    let source = language::source_text(path, contents)?;
+    if looks_unauthored(Path::new(&format!("/{filename}")), &source) {
         return Ok(vec![]);
     }
+    analyze_source_with_settings(&source, filename, file_index, options, settings)
+}
 
-    let language = Language::from_path(path)
-        .ok_or_else(|| anyhow::anyhow!("Unsupported file type: {}", path.display()))?;
-    let parser = create_parser(language, source_map)?;
-    let module = parser.parse(&src, &path.to_string_lossy())?;
-    let functions = module.discover_functions(file_index, &src);
+/// Report and CFG for each function of a parsed file, in discovery order
+fn analyze_parsed(
+    parsed: &ParsedFile,
+    path: &Path,
+    file_index: usize,
+    func_cfg: &FunctionAnalysisConfig<'_>,
+) -> Vec<(report::FunctionRiskReport, Cfg)> {
+    let functions = parsed.module.discover_functions(file_index, &parsed.src);
+    let mut reports = Vec::new();
+    let anonymous = anonymous_symbols(&functions);
+    for (function, symbol) in functions.iter().zip(anonymous) {
+        if let Some((mut report, cfg)) = analyze_function(function, path, parsed.language, func_cfg)
+        {
+            if let Some(symbol) = symbol {
+                report.set_function(symbol);
+            }
+            reports.push((report, cfg));
+        }
+    }
+    reports
+}
+
+/// Like [`analyze_file_with_config`] at file granularity: a single report,
+/// named [`Granularity::FILE_ENTRY_NAME`](crate::config::Granularity::FILE_ENTRY_NAME),
+/// for the whole file
+///
+/// The file's top-level code and each of its functions are measured as units
+/// and combined: CC is one plus every unit's decision points, ND the deepest
+/// nesting, FO the distinct callees, NS and return points the totals, and
+/// LOC/SLOC the file's. Returns `None` for files with no code to measure, or
+/// when the combined score falls below `min_lrs`.
+#[cfg(feature = "native")]
+pub(crate) fn analyze_whole_file(
+    path: &Path,
+    source_map: &Lrc<SourceMap>,
+    file_index: usize,
+    options: &crate::AnalysisOptions,
+    settings: AnalysisSettings<'_>,
+) -> Result<Option<report::FunctionRiskReport>> {
+    let default_weights = risk::LrsWeights::default();
+    let default_thresholds = risk::RiskThresholds::default();
+    let default_pattern_thresholds = crate::patterns::Thresholds::default();
+    let Some(ParsedFile {
+        src,
+        language,
+        module,
+    }) = parse_file(path, source_map)?
+    else {
+        return Ok(None);
+    };
+    let units: Vec<FunctionNode> = module
+        .top_level_unit(file_index, &src)
+        .into_iter()
+        .chain(module.discover_functions(file_index, &src))
+        .collect();
 
+    let source_lines: Vec<&str> = src.lines().collect();
     let func_cfg = FunctionAnalysisConfig {
         options,
-        weights: w,
-        thresholds: t,
-        pattern_thresholds: pt,
+        weights: settings.weights.unwrap_or(&default_weights),
+        thresholds: settings.thresholds.unwrap_or(&default_thresholds),
+        pattern_thresholds: settings
+            .pattern_thresholds
+            .unwrap_or(&default_pattern_thresholds),
+        rust_branching_macros: settings.rust_branching_macros,
         source_map,
+        source_lines: &source_lines,
     };
-    let mut reports = Vec::new();
-    for function in &functions {
-        if let Some(report) = analyze_function(function, path, language, &func_cfg) {
-            reports.push(report);
+
+    let mut measured = units
+        .iter()
+        .filter_map(|unit| measure_function(unit, path, language, &func_cfg))
+        .peekable();
+    if measured.peek().is_none() {
+        return Ok(None);
+    }
+    let mut combined = Measurements {
+        metrics: metrics::RawMetrics {
+            cc: 1,
+            nd: 0,
+            fo: 0,
+            ns: 0,
+            loc: source_lines.len(),
+            callee_names: vec![],
+        },
+        sloc: metrics::calculate_sloc(&source_lines, language),
+        return_points: 0,
+        unwrap_count: None,
+        max_arm_loc: None,
+        max_data_nesting: None,
+        unreachable_code: None,
+    };
+    let mut callees = std::collections::BTreeSet::new();
+    for (m, _) in measured {
+        combined.metrics.cc += m.metrics.cc.saturating_sub(1);
+        combined.metrics.nd = combined.metrics.nd.max(m.metrics.nd);
+        combined.metrics.ns += m.metrics.ns;
+        combined.return_points += m.return_points;
+        if let Some(n) = m.unwrap_count {
+            *combined.unwrap_count.get_or_insert(0) += n;
+        }
+        combined.max_arm_loc = combined.max_arm_loc.max(m.max_arm_loc);
+        combined.max_data_nesting = combined.max_data_nesting.max(m.max_data_nesting);
+        if let Some(n) = m.unreachable_code {
+            *combined.unreachable_code.get_or_insert(0) += n;
         }
+        callees.extend(m.metrics.callee_names);
     }
-    Ok(reports)
+    combined.metrics.callee_names = callees.into_iter().collect();
+    combined.metrics.fo = crate::callgraph::fan_out(language, &combined.metrics.callee_names);
+
+    let Some(analysis) = score(combined, crate::ast::FunctionKind::Function, &func_cfg) else {
+        return Ok(None);
+    };
+    let file_node = FunctionNode {
+        name: Some(crate::config::Granularity::FILE_ENTRY_NAME.to_string()),
+        kind: crate::ast::FunctionKind::Function,
+        span: language::SourceSpan::whole_file(&src),
+        suppression_reason: None,
+        ..units[0].clone()
+    };
+    let source_lines: Vec<&str> = src.lines().collect();
+    Ok(Some(report::FunctionRiskReport::new(
+        &file_node,
+        report_path(path),
+        language,
+        analysis,
+        source_map,
+        &source_lines,
+    )))
+}
+
+/// A source file read and parsed for analysis
+struct ParsedFile {
+    src: String,
+    language: Language,
+    module: Box<dyn language::ParsedModule>,
+}
+
+/// Read and parse `path`, or `None` when it looks minified or vendored
+fn parse_file(path: &Path, source_map: &Lrc<SourceMap>) -> Result<Option<ParsedFile>> {
+    let src = language::read_source(path)?;
+    if looks_unauthored(path, &src) {
+        return Ok(None);
+    }
+    parse_source(src, path, source_map).map(Some)
+}
+
+/// Why a source looks machine-written or third-party rather than authored
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Unauthored {
+    /// At least three lines over 1000 characters
+    Minified { long_lines: usize, max_line: usize },
+    /// Under a vendored or generated-asset directory (see [`looks_vendored`])
+    Vendored,
+}
+
+/// Why `src` at `path` would be skipped as unauthored, or `None` to analyze it
+pub(crate) fn unauthored(path: &Path, src: &str) -> Option<Unauthored> {
+    let (max_line, long_lines) = long_line_stats(src, 1000);
+    if long_lines >= 3 {
+        Some(Unauthored::Minified {
+            long_lines,
+            max_line,
+        })
+    } else if looks_vendored(path) {
+        Some(Unauthored::Vendored)
+    } else {
+        None
+    }
+}
+
+/// Whether `src` looks minified or `path` vendored, warning when skipping it
+fn looks_unauthored(path: &Path, src: &str) -> bool {
+    let Some(reason) = unauthored(path, src) else {
+        return false;
+    };
+    if diagnostics::enabled(Level::Warn) {
+        match reason {
+            Unauthored::Minified {
+                long_lines,
+                max_line,
+            } => eprintln!(
+                "warning: skipping {} — looks minified or machine-generated \
+                 ({} lines exceed 1000 chars, max: {})",
+                path.display(),
+                long_lines,
+                max_line
+            ),
+            Unauthored::Vendored => eprintln!(
+                "warning: skipping {} — path suggests vendored or generated third-party code",
+                path.display()
+            ),
+        }
+    }
+    true
+}
+
+/// Parse `src` in the language `path`'s extension names
+fn parse_source(src: String, path: &Path, source_map: &Lrc<SourceMap>) -> Result<ParsedFile> {
+    let language = Language::from_path(path)
+        .ok_or_else(|| anyhow::anyhow!("Unsupported file type: {}", path.display()))?;
+    let parser = create_parser(language, source_map)?;
+    let module = parser.parse(&src, &path.to_string_lossy())?;
+    Ok(ParsedFile {
+        src,
+        language,
+        module,
+    })
+}
+
+/// Symbols for the anonymous functions in one file, indexed like `functions`
+/// (`None` for named functions)
+///
+/// An anonymous function is `<anonymous@LINE>`, qualified by the innermost
+/// named function that encloses it (`outer::<anonymous@LINE>`). When several
+/// anonymous functions end up with the same symbol (same line, same parent),
+/// later ones in source order get `#2`, `#3`, ... so function IDs within a file
+/// never collide.
+fn anonymous_symbols(functions: &[FunctionNode]) -> Vec<Option<String>> {
+    let mut seen: HashMap<String, usize> = HashMap::new();
+    functions
+        .iter()
+        .map(|function| {
+            if function.name.is_some() {
+                return None;
+            }
+            let enclosing = functions
+                .iter()
+                .filter(|outer| {
+                    outer.name.is_some()
+                        && outer.span.start <= function.span.start
+                        && function.span.end <= outer.span.end
+                })
+                .max_by_key(|outer| outer.span.start)
+                .and_then(|outer| outer.name.as_deref());
+            let line = function.span.start_line;
+            let symbol = match enclosing {
+                Some(outer) => format!("{outer}::<anonymous@{line}>"),
+                None => format!("<anonymous@{line}>"),
+            };
+            let count = seen.entry(symbol.clone()).or_insert(0);
+            *count += 1;
+            Some(if *count == 1 {
+                symbol
+            } else {
+                format!("{symbol}#{count}")
+            })
+        })
+        .collect()
 }
 
 /// Returns the length of the longest line and the count of lines exceeding `threshold` chars.
@@ -120,7 +451,7 @@ fn looks_vendored(path: &Path) -> bool {
 }
 
 /// Instantiates the correct parser for the given language.
-fn create_parser(
+pub(crate) fn create_parser(
     language: Language,
     source_map: &Lrc<SourceMap>,
 ) -> Result<Box<dyn LanguageParser>> {
@@ -140,12 +471,14 @@ fn create_parser(
         }
         Language::Rust => Box::new(language::RustParser),
         Language::Vue => Box::new(language::VueParser::new(source_map.clone())),
+        Language::Svelte => Box::new(language::SvelteParser::new(source_map.clone())),
         Language::CSharp => {
             Box::new(language::CSharpParser::new().context("Failed to create C# parser")?)
         }
         Language::C | Language::CHeader => {
             Box::new(language::CParser::new().context("Failed to create C parser")?)
         }
+        Language::Hcl => Box::new(language::HclParser),
     };
     Ok(parser)
 }
@@ -155,37 +488,125 @@ struct FunctionAnalysisConfig<'a> {
     weights: &'a risk::LrsWeights,
     thresholds: &'a risk::RiskThresholds,
     pattern_thresholds: &'a crate::patterns::Thresholds,
+    rust_branching_macros: Option<&'a [String]>,
     source_map: &'a Lrc<SourceMap>,
+    source_lines: &'a [&'a str],
+}
+
+/// A function's raw measurements, before risk scoring
+struct Measurements {
+    metrics: metrics::RawMetrics,
+    sloc: usize,
+    return_points: usize,
+    unwrap_count: Option<usize>,
+    max_arm_loc: Option<usize>,
+    max_data_nesting: Option<usize>,
+    unreachable_code: Option<usize>,
 }
 
 /// Builds CFG, extracts metrics, computes risk and patterns for one function.
-/// Returns None if the CFG is invalid or the function is filtered by min_lrs.
+/// Returns the report with the CFG it was computed from, or None if the CFG is
+/// invalid or the function is filtered by min_lrs.
 fn analyze_function(
     function: &FunctionNode,
     path: &Path,
     language: Language,
     config: &FunctionAnalysisConfig<'_>,
-) -> Option<report::FunctionRiskReport> {
-    let w = config.weights;
-    let t = config.thresholds;
-    let pt = config.pattern_thresholds;
-    let options = config.options;
-    let source_map = config.source_map;
-    let cfg = language::get_builder_for_function(function).build(function);
+) -> Option<(report::FunctionRiskReport, Cfg)> {
+    let (measurements, cfg) = measure_function(function, path, language, config)?;
+    let analysis = score(measurements, function.kind, config)?;
+    let report = report::FunctionRiskReport::new(
+        function,
+        report_path(path),
+        language,
+        analysis,
+        config.source_map,
+        config.source_lines,
+    );
+    Some((report, cfg))
+}
+
+/// Report form of a file path: forward slashes on every host so output is
+/// byte-identical across OSes
+fn report_path(path: &Path) -> String {
+    path.to_string_lossy().replace('\\', "/")
+}
+
+/// Builds the CFG and extracts raw metrics for one function, or None (with a
+/// warning) if the CFG is invalid.
+fn measure_function(
+    function: &FunctionNode,
+    path: &Path,
+    language: Language,
+    config: &FunctionAnalysisConfig<'_>,
+) -> Option<(Measurements, Cfg)> {
+    let mut cfg = match (&function.body, config.rust_branching_macros) {
+        (language::FunctionBody::Rust { .. }, Some(macros)) => {
+            language::RustCfgBuilder.build_with_macros(function, macros)
+        }
+        _ => language::get_builder_for_function(function)
+            .build_with_source_map(function, config.source_map),
+    };
     if let Err(e) = cfg.validate() {
-        eprintln!(
-            "warning: skipping function '{}' in {}: invalid CFG: {}",
-            function.name.as_deref().unwrap_or("<anonymous>"),
-            path.display(),
-            e
-        );
+        if diagnostics::enabled(Level::Warn) {
+            eprintln!(
+                "warning: skipping function '{}' in {}: invalid CFG: {}",
+                function.name.as_deref().unwrap_or("<anonymous>"),
+                path.display(),
+                e
+            );
+        }
         return None;
     }
 
-    let raw_metrics = metrics::extract_metrics(function, &cfg);
-    let (risk_components, lrs, band) = risk::analyze_risk_with_config(&raw_metrics, w, t);
+    let (raw_metrics, extra_decisions) =
+        metrics::extract_metrics_with_decisions(function, &cfg, Some(config.source_map));
+    cfg.extra_decisions = extra_decisions;
+    let sloc = {
+        let lines = config.source_lines;
+        let start = (function.span.start_line as usize)
+            .saturating_sub(1)
+            .min(lines.len());
+        let end = (function.span.end_line as usize).clamp(start, lines.len());
+        metrics::calculate_sloc(&lines[start..end], language)
+    };
+    let measurements = Measurements {
+        return_points: metrics::return_points(function, &cfg),
+        unwrap_count: metrics::unwrap_count(function),
+        max_arm_loc: cfg.max_arm_loc,
+        max_data_nesting: metrics::max_data_nesting(function),
+        unreachable_code: (cfg.unreachable_stmts > 0).then_some(cfg.unreachable_stmts),
+        metrics: raw_metrics,
+        sloc,
+    };
+    Some((measurements, cfg))
+}
+
+/// Computes risk, band, and patterns (with `kind`'s pattern thresholds) from
+/// raw measurements, or None if the result is filtered by min_lrs.
+fn score(
+    measurements: Measurements,
+    kind: crate::ast::FunctionKind,
+    config: &FunctionAnalysisConfig<'_>,
+) -> Option<report::FunctionAnalysis> {
+    let w = config.weights;
+    let t = config.thresholds;
+    let Measurements {
+        metrics: raw_metrics,
+        sloc,
+        return_points,
+        unwrap_count,
+        max_arm_loc,
+        max_data_nesting,
+        unreachable_code,
+    } = measurements;
+    let (risk_components, mut lrs, mut band) = risk::analyze_risk_with_config(&raw_metrics, w, t);
+    if let Some(n) = unwrap_count.filter(|_| w.unwrap > 0.0) {
+        lrs += w.unwrap * risk::unwrap_risk(n);
+        band = risk::assign_risk_band_with_thresholds(lrs, t);
+    }
 
-    if options.min_lrs.is_some_and(|min| lrs < min) {
+    if config.options.min_lrs.is_some_and(|min| lrs < min) {
         return None;
     }
 
@@ -195,6 +616,12 @@ fn analyze_function(
         fo: raw_metrics.fo,
         ns: raw_metrics.ns,
         loc: raw_metrics.loc,
+        sloc: Some(sloc),
+        return_points: Some(return_points),
+        unwrap_count,
+        max_arm_loc,
+        max_data_nesting,
+        unreachable_code,
     };
     let t2 = crate::patterns::Tier2Input {
         fan_in: None,
@@ -202,21 +629,24 @@ fn analyze_function(
         churn_lines: None,
         days_since_last_change: None,
         neighbor_churn: None,
+        coverage_pct: None,
+        lrs: None,
         is_entrypoint: false,
     };
-    let patterns = crate::patterns::classify(&t1, &t2, pt);
+    let patterns =
+        crate::patterns::classify(&t1, &t2, config.pattern_thresholds.for_kind(Some(kind)));
 
-    Some(report::FunctionRiskReport::new(
-        function,
-        path.to_string_lossy().to_string(),
-        language,
-        report::FunctionAnalysis {
-            metrics: raw_metrics,
-            risk: risk_components,
-            lrs,
-            band,
-            patterns,
-        },
-        source_map,
-    ))
+    Some(report::FunctionAnalysis {
+        metrics: raw_metrics,
+        sloc,
+        return_points,
+        unwrap_count,
+        max_arm_loc,
+        max_data_nesting,
+        unreachable_code,
+        risk: risk_components,
+        lrs,
+        band,
+        patterns,
+    })
 }
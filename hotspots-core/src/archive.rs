@@ -0,0 +1,511 @@
+//! Source archives (`.zip`, `.tar`, `.tar.gz`/`.tgz`) analyzed without extracting
+//!
+//! Entries are streamed and filtered like a directory walk (ignored
+//! directories, unsupported extensions, `include`/`exclude`, `max_file_bytes`),
+//! then each source is analyzed in memory, with the notebook conversion and
+//! minified/vendored skips of file analysis. Reports name files by their path
+//! inside the archive. There is no repository behind an archive, so nothing
+//! git-based applies.
+
+use crate::analysis::{self, AnalysisSettings};
+use crate::config::{Granularity, ResolvedConfig};
+use crate::diagnostics::{self, Level};
+use crate::{AnalysisOptions, FunctionRiskReport};
+use anyhow::{Context, Result};
+use std::collections::HashMap;
+use std::io::Read;
+use std::path::Path;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ArchiveKind {
+    Zip,
+    Tar,
+    TarGz,
+}
+
+fn archive_kind(path: &Path) -> Option<ArchiveKind> {
+    let name = path.file_name()?.to_str()?.to_ascii_lowercase();
+    if name.ends_with(".zip") {
+        Some(ArchiveKind::Zip)
+    } else if name.ends_with(".tar.gz") || name.ends_with(".tgz") {
+        Some(ArchiveKind::TarGz)
+    } else if name.ends_with(".tar") {
+        Some(ArchiveKind::Tar)
+    } else {
+        None
+    }
+}
+
+/// Whether `path` is a file with a supported archive extension
+pub fn is_archive(path: &Path) -> bool {
+    path.is_file() && archive_kind(path).is_some()
+}
+
+/// A source file read from an archive
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct ArchiveEntry {
+    /// `/`-separated path inside the archive, without a leading `./` or `/`
+    path: String,
+    source: String,
+    /// Modification time in seconds since the Unix epoch
+    mtime: i64,
+}
+
+/// Which archive members are analyzed
+struct EntryFilter<'a> {
+    resolved_config: Option<&'a ResolvedConfig>,
+    max_file_bytes: u64,
+}
+
+impl<'a> EntryFilter<'a> {
+    fn new(resolved_config: Option<&'a ResolvedConfig>) -> Self {
+        EntryFilter {
+            resolved_config,
+            max_file_bytes: resolved_config
+                .map_or(crate::config::DEFAULT_MAX_FILE_BYTES, |c| c.max_file_bytes),
+        }
+    }
+
+    fn accepts(&self, path: &str, size: u64) -> bool {
+        let mut components: Vec<&str> = path.split('/').collect();
+        let Some(filename) = components.pop() else {
+            return false;
+        };
+        !components.iter().any(|dir| crate::is_skipped_dir(dir))
+            && crate::unsupported_file_reason(filename).is_none()
+            && (self.max_file_bytes == 0 || size <= self.max_file_bytes)
+            && self
+                .resolved_config
+                .map_or(true, |c| c.should_include(Path::new(path)))
+    }
+}
+
+/// Archive-relative form of a member name
+fn entry_path(name: &str) -> String {
+    let name = name.replace('\\', "/");
+    let mut rest = name.as_str();
+    loop {
+        let trimmed = rest.trim_start_matches('/');
+        let trimmed = trimmed.strip_prefix("./").unwrap_or(trimmed);
+        if trimmed == rest {
+            return rest.to_string();
+        }
+        rest = trimmed;
+    }
+}
+
+/// Read `reader` as UTF-8 source, or `None` (with a warning) for binary or
+/// non-UTF-8 content
+fn read_source(mut reader: impl Read, archive: &Path, path: &str) -> Result<Option<String>> {
+    let mut bytes = Vec::new();
+    reader
+        .read_to_end(&mut bytes)
+        .with_context(|| format!("failed to read {} from {}", path, archive.display()))?;
+    match String::from_utf8(bytes) {
+        Ok(source) => Ok(Some(source)),
+        Err(_) => {
+            if diagnostics::enabled(Level::Warn) {
+                eprintln!(
+                    "warning: skipping {} in {}: not UTF-8",
+                    path,
+                    archive.display()
+                );
+            }
+            Ok(None)
+        }
+    }
+}
+
+fn read_zip_entries(archive: &Path, filter: &EntryFilter<'_>) -> Result<Vec<ArchiveEntry>> {
+    let file = std::fs::File::open(archive)
+        .with_context(|| format!("failed to open archive: {}", archive.display()))?;
+    let mut zip = zip::ZipArchive::new(std::io::BufReader::new(file))
+        .with_context(|| format!("failed to read zip archive: {}", archive.display()))?;
+    let mut entries = Vec::new();
+    for index in 0..zip.len() {
+        let member = zip
+            .by_index(index)
+            .with_context(|| format!("failed to read zip archive: {}", archive.display()))?;
+        // Symlinks are never followed, as in a directory walk
+        let is_symlink = member
+            .unix_mode()
+            .is_some_and(|mode| mode & 0o170000 == 0o120000);
+        if member.is_dir() || is_symlink {
+            continue;
+        }
+        let path = entry_path(member.name());
+        if !filter.accepts(&path, member.size()) {
+            continue;
+        }
+        let mtime = zip_mtime(member.last_modified());
+        if let Some(source) = read_source(member, archive, &path)? {
+            entries.push(ArchiveEntry {
+                path,
+                source,
+                mtime,
+            });
+        }
+    }
+    Ok(entries)
+}
+
+/// Seconds since the Unix epoch of a zip timestamp. Zip stores local time with
+/// no zone, so it is read as UTC: every entry shifts alike, which keeps the
+/// differences between them intact.
+fn zip_mtime(time: zip::DateTime) -> i64 {
+    // Days from 1970-01-01 to the civil date (Howard Hinnant's algorithm)
+    let (month, day) = (i64::from(time.month()), i64::from(time.day()));
+    let year = i64::from(time.year()) - i64::from(month <= 2);
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    let days = era * 146_097 + day_of_era - 719_468;
+    days * 86_400
+        + i64::from(time.hour()) * 3_600
+        + i64::from(time.minute()) * 60
+        + i64::from(time.second())
+}
+
+fn read_tar_entries(
+    reader: impl Read,
+    archive: &Path,
+    filter: &EntryFilter<'_>,
+) -> Result<Vec<ArchiveEntry>> {
+    let mut tar = tar::Archive::new(reader);
+    let mut entries = Vec::new();
+    let members = tar
+        .entries()
+        .with_context(|| format!("failed to read tar archive: {}", archive.display()))?;
+    for member in members {
+        let member =
+            member.with_context(|| format!("failed to read tar archive: {}", archive.display()))?;
+        // Regular files only: directories, links, and devices are skipped
+        if !member.header().entry_type().is_file() {
+            continue;
+        }
+        let path = entry_path(&member.path()?.to_string_lossy());
+        if !filter.accepts(&path, member.size()) {
+            continue;
+        }
+        let mtime = member.header().mtime().map_or(0, |secs| secs as i64);
+        if let Some(source) = read_source(member, archive, &path)? {
+            entries.push(ArchiveEntry {
+                path,
+                source,
+                mtime,
+            });
+        }
+    }
+    Ok(entries)
+}
+
+/// Source files in `archive` that analysis would read, sorted by path
+fn read_entries(archive: &Path, filter: &EntryFilter<'_>) -> Result<Vec<ArchiveEntry>> {
+    let kind = archive_kind(archive)
+        .with_context(|| format!("not a supported archive: {}", archive.display()))?;
+    let open = || {
+        std::fs::File::open(archive)
+            .map(std::io::BufReader::new)
+            .with_context(|| format!("failed to open archive: {}", archive.display()))
+    };
+    let mut entries = match kind {
+        ArchiveKind::Zip => read_zip_entries(archive, filter)?,
+        ArchiveKind::Tar => read_tar_entries(open()?, archive, filter)?,
+        ArchiveKind::TarGz => {
+            read_tar_entries(flate2::read::GzDecoder::new(open()?), archive, filter)?
+        }
+    };
+    // An archive may repeat a path; the last copy wins, as on extraction
+    entries.reverse();
+    entries.sort_by(|a, b| a.path.cmp(&b.path));
+    entries.dedup_by(|later, earlier| later.path == earlier.path);
+    Ok(entries)
+}
+
+/// Modification time, in seconds since the Unix epoch, of each source file in
+/// `archive` that [`analyze_archive`] would read, keyed by its path inside the
+/// archive (`--use-mtime`)
+///
+/// # Errors
+///
+/// Returns error if the archive cannot be read.
+pub fn entry_mtimes(
+    archive: &Path,
+    resolved_config: Option<&ResolvedConfig>,
+) -> Result<HashMap<String, i64>> {
+    Ok(read_entries(archive, &EntryFilter::new(resolved_config))?
+        .into_iter()
+        .map(|entry| (entry.path, entry.mtime))
+        .collect())
+}
+
+/// Analyze every supported source file inside `archive`
+///
+/// Config weights, thresholds, patterns, filters, `suppress` rules, and
+/// `require_functions` apply as for a directory. Analysis is always per
+/// function: `granularity: "file"` is rejected.
+///
+/// # Errors
+///
+/// Returns error if the archive cannot be read, file granularity is
+/// requested, or `require_functions` is set and no function is found.
+pub fn analyze_archive(
+    archive: &Path,
+    options: AnalysisOptions,
+    resolved_config: Option<&ResolvedConfig>,
+    progress: Option<&(dyn Fn(usize, usize) + Send + Sync)>,
+) -> Result<Vec<FunctionRiskReport>> {
+    use rayon::prelude::*;
+
+    if resolved_config.is_some_and(|c| c.granularity == Granularity::File) {
+        anyhow::bail!("granularity \"file\" is not supported for archives");
+    }
+    let entries = read_entries(archive, &EntryFilter::new(resolved_config))?;
+
+    let weights = resolved_config.map(ResolvedConfig::lrs_weights);
+    let thresholds = resolved_config.map(ResolvedConfig::risk_thresholds);
+    let settings = AnalysisSettings {
+        weights: weights.as_ref(),
+        thresholds: thresholds.as_ref(),
+        pattern_thresholds: resolved_config.map(|c| &c.pattern_thresholds),
+        rust_branching_macros: resolved_config.and_then(|c| c.rust_branching_macros.as_deref()),
+    };
+    let file_options = AnalysisOptions {
+        min_lrs: None,
+        top_n: None,
+    };
+    let skip_anonymous = resolved_config.is_some_and(|c| c.skip_anonymous_functions);
+
+    let total = entries.len();
+    if let Some(f) = progress.filter(|_| total > 0) {
+        f(0, total);
+    }
+    let done = std::sync::atomic::AtomicUsize::new(0);
+    let results: Vec<(&str, Result<Vec<FunctionRiskReport>>)> = entries
+        .par_iter()
+        .enumerate()
+        .map(|(index, entry)| {
+            let result = analysis::analyze_contents_with_settings(
+                entry.source.clone(),
+                &entry.path,
+                index,
+                &file_options,
+                settings,
+            );
+            let n = done.fetch_add(1, std::sync::atomic::Ordering::Relaxed) + 1;
+            if let Some(f) = progress {
+                f(n, total);
+            }
+            (entry.path.as_str(), result)
+        })
+        .collect();
+
+    let mut found_functions = 0;
+    let mut skipped_files = 0;
+    let mut reports = Vec::new();
+    for (path, result) in results {
+        match result {
+            Ok(file_reports) => {
+                let file_reports = file_reports
+                    .into_iter()
+                    .filter(|r| !(skip_anonymous && r.is_anonymous()));
+                for report in file_reports {
+                    found_functions += 1;
+                    let excluded = resolved_config
+                        .is_some_and(|c| c.is_function_excluded(&report.file, &report.function));
+                    if !excluded && options.min_lrs.map_or(true, |min| report.lrs >= min) {
+                        reports.push(report);
+                    }
+                }
+            }
+            Err(e) => {
+                if diagnostics::enabled(Level::Warn) {
+                    eprintln!("warning: skipping {} in {}: {}", path, archive.display(), e);
+                }
+                skipped_files += 1;
+            }
+        }
+    }
+
+    let mut reports = crate::sort_reports(reports);
+    if let Some(n) = options.top_n {
+        reports.truncate(n);
+    }
+    if let Some(c) = resolved_config {
+        crate::suppression::apply(&mut reports, &c.suppress_rules);
+    }
+    if skipped_files > 0 && diagnostics::enabled(Level::Warn) {
+        eprintln!("Skipped {} file(s) due to analysis errors", skipped_files);
+    }
+    if resolved_config.is_some_and(|c| c.require_functions) && found_functions == 0 {
+        anyhow::bail!(
+            "no functions found in {} source file(s) of {} (require_functions is set)",
+            total,
+            archive.display()
+        );
+    }
+    Ok(reports)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    fn tar_gz(members: &[(&str, &str)]) -> Vec<u8> {
+        let encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+        let mut builder = tar::Builder::new(encoder);
+        for (path, content) in members {
+            let mut header = tar::Header::new_gnu();
+            header.set_size(content.len() as u64);
+            header.set_mode(0o644);
+            header.set_cksum();
+            builder
+                .append_data(&mut header, path, content.as_bytes())
+                .unwrap();
+        }
+        builder.into_inner().unwrap().finish().unwrap()
+    }
+
+    fn zip(members: &[(&str, &str)]) -> Vec<u8> {
+        let mut writer = zip::ZipWriter::new(std::io::Cursor::new(Vec::new()));
+        let options = zip::write::FileOptions::default();
+        for (path, content) in members {
+            writer.start_file(*path, options).unwrap();
+            writer.write_all(content.as_bytes()).unwrap();
+        }
+        writer.finish().unwrap().into_inner()
+    }
+
+    const PICK: &str = "export function pick(a: number, b: number) {\n  if (a > b) {\n    return a;\n  }\n  return b;\n}\n";
+
+    #[test]
+    fn test_archive_entries_are_filtered_like_a_walk() {
+        let tmp = tempfile::tempdir().unwrap();
+        let archive = tmp.path().join("release-1.0.tar.gz");
+        std::fs::write(
+            &archive,
+            tar_gz(&[
+                ("./pkg/src/pick.ts", PICK),
+                ("pkg/node_modules/dep/index.js", "function dep() {}\n"),
+                ("pkg/src/types.d.ts", "declare function x(): void;\n"),
+                ("pkg/README.md", "# pkg\n"),
+            ]),
+        )
+        .unwrap();
+        assert!(is_archive(&archive));
+
+        let reports = analyze_archive(
+            &archive,
+            AnalysisOptions {
+                min_lrs: None,
+                top_n: None,
+            },
+            None,
+            None,
+        )
+        .unwrap();
+        let names: Vec<(&str, &str)> = reports
+            .iter()
+            .map(|r| (r.file.as_str(), r.function.as_str()))
+            .collect();
+        assert_eq!(names, vec![("pkg/src/pick.ts", "pick")]);
+    }
+
+    #[test]
+    fn test_archive_entries_are_read_like_files() {
+        let tmp = tempfile::tempdir().unwrap();
+        let archive = tmp.path().join("notebooks.tar.gz");
+        let notebook =
+            r#"{"cells": [{"cell_type": "code", "source": ["def f(x):\n", "    return x\n"]}]}"#;
+        let minified = format!("function m() {{}}\n{}", "x;".repeat(600) + "\n").repeat(3);
+        std::fs::write(
+            &archive,
+            tar_gz(&[
+                ("nb/explore.ipynb", notebook),
+                ("vendor/lib.ts", PICK),
+                ("src/bundle.js", &minified),
+            ]),
+        )
+        .unwrap();
+
+        let reports = analyze_archive(
+            &archive,
+            AnalysisOptions {
+                min_lrs: None,
+                top_n: None,
+            },
+            None,
+            None,
+        )
+        .unwrap();
+        let names: Vec<(&str, &str)> = reports
+            .iter()
+            .map(|r| (r.file.as_str(), r.function.as_str()))
+            .collect();
+        assert_eq!(names, vec![("nb/explore.ipynb", "f")]);
+    }
+
+    #[test]
+    fn test_zip_entries_are_analyzed_in_memory() {
+        let tmp = tempfile::tempdir().unwrap();
+        let archive = tmp.path().join("release.ZIP");
+        std::fs::write(
+            &archive,
+            zip(&[
+                ("lib/b.py", "def b(x):\n    return x\n"),
+                ("lib/a.ts", PICK),
+                ("lib/", ""),
+            ]),
+        )
+        .unwrap();
+
+        let filter = EntryFilter {
+            resolved_config: None,
+            max_file_bytes: 0,
+        };
+        let entries = read_entries(&archive, &filter).unwrap();
+        let paths: Vec<&str> = entries.iter().map(|e| e.path.as_str()).collect();
+        assert_eq!(paths, vec!["lib/a.ts", "lib/b.py"]);
+
+        let small_only = EntryFilter {
+            resolved_config: None,
+            max_file_bytes: 40,
+        };
+        let entries = read_entries(&archive, &small_only).unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].path, "lib/b.py");
+    }
+
+    #[test]
+    fn test_entry_mtimes_come_from_member_headers() {
+        let tmp = tempfile::tempdir().unwrap();
+        let tarball = tmp.path().join("src.tar");
+        let mut builder = tar::Builder::new(Vec::new());
+        for (path, mtime) in [("a.ts", 1_700_000_000), ("b.ts", 1_700_086_400)] {
+            let mut header = tar::Header::new_gnu();
+            header.set_size(PICK.len() as u64);
+            header.set_mode(0o644);
+            header.set_mtime(mtime);
+            header.set_cksum();
+            builder
+                .append_data(&mut header, path, PICK.as_bytes())
+                .unwrap();
+        }
+        std::fs::write(&tarball, builder.into_inner().unwrap()).unwrap();
+        let mtimes = entry_mtimes(&tarball, None).unwrap();
+        assert_eq!(mtimes["a.ts"], 1_700_000_000);
+        assert_eq!(mtimes["b.ts"], 1_700_086_400);
+
+        let archive = tmp.path().join("src.zip");
+        let mut writer = zip::ZipWriter::new(std::io::Cursor::new(Vec::new()));
+        let time = zip::DateTime::from_date_and_time(2024, 1, 2, 3, 4, 6).unwrap();
+        let options = zip::write::FileOptions::default().last_modified_time(time);
+        writer.start_file("a.ts", options).unwrap();
+        writer.write_all(PICK.as_bytes()).unwrap();
+        std::fs::write(&archive, writer.finish().unwrap().into_inner()).unwrap();
+        // 2024-01-02T03:04:06Z
+        assert_eq!(entry_mtimes(&archive, None).unwrap()["a.ts"], 1_704_164_646);
+    }
+}
@@ -5,6 +5,7 @@
 //! - Formatting, comments, and whitespace must not affect results
 
 use crate::language::{FunctionBody, SourceSpan};
+use serde::{Deserialize, Serialize};
 
 /// Function identifier: (file_index, local_index)
 ///
@@ -16,11 +17,45 @@ pub struct FunctionId {
     pub local_index: usize,
 }
 
+/// Name of the synthetic function holding a file's top-level code (see
+/// [`crate::language::ParsedModule::top_level_unit`]). No supported language
+/// allows `<` in an identifier, so it cannot collide with a real function.
+pub const TOP_LEVEL_NAME: &str = "<top-level>";
+
+/// What kind of function a [`FunctionNode`] is, as captured at discovery
+///
+/// Synthetic units (top-level code, file-granularity entries) are `Function`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FunctionKind {
+    /// Free-standing named function or procedure
+    Function,
+    /// Function declared on a class, struct, impl block, or object literal
+    Method,
+    /// Function expression, arrow function, lambda, or closure
+    Closure,
+    /// Class constructor
+    Constructor,
+}
+
+impl FunctionKind {
+    /// Name as it appears in output and config
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            FunctionKind::Function => "function",
+            FunctionKind::Method => "method",
+            FunctionKind::Closure => "closure",
+            FunctionKind::Constructor => "constructor",
+        }
+    }
+}
+
 /// Stable abstraction for a function node in the AST
 #[derive(Debug, Clone)]
 pub struct FunctionNode {
     pub id: FunctionId,
     pub name: Option<String>,
+    pub kind: FunctionKind,
     pub span: SourceSpan,
     pub body: FunctionBody,
     pub suppression_reason: Option<String>,
@@ -39,4 +74,9 @@ impl FunctionNode {
     pub fn line(&self) -> u32 {
         self.span.start_line
     }
+
+    /// Whether this is the synthetic function for a file's top-level code
+    pub fn is_top_level(&self) -> bool {
+        self.name.as_deref() == Some(TOP_LEVEL_NAME)
+    }
 }
@@ -0,0 +1,584 @@
+//! Per-function AST dump for debugging metric counts
+//!
+//! Lists the syntax nodes that feed CC, ND, FO, and NS for a named function,
+//! as each language's metric extractor sees them: tree-sitter node kinds for
+//! Go/Java/Python/C#/C, SWC node types for ECMAScript, `syn` node types for
+//! Rust, and block facts for Terraform/HCL. Entries are sorted by position, so output is deterministic.
+
+use crate::analysis;
+use crate::ast::FunctionNode;
+use crate::language::{self, FunctionBody, Language};
+use crate::metrics::{self, RawMetrics};
+use anyhow::Result;
+use std::path::Path;
+use swc_common::{sync::Lrc, SourceMap, Spanned};
+
+/// One syntax node that contributes to at least one metric
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AstNodeEntry {
+    /// 1-indexed line in the source file
+    pub line: usize,
+    /// 1-indexed column
+    pub column: usize,
+    /// Node kind as named by the language's parser
+    pub kind: String,
+    /// Metrics the node feeds (`cc`, `nd`, `fo`, `ns`)
+    pub metrics: Vec<&'static str>,
+}
+
+/// AST dump for a single function
+#[derive(Debug, Clone)]
+pub struct FunctionAstDump {
+    pub name: String,
+    pub line: u32,
+    /// Metrics as computed by the normal analysis pipeline, for comparison
+    pub metrics: RawMetrics,
+    pub nodes: Vec<AstNodeEntry>,
+}
+
+/// Dump the metric-relevant AST nodes of every function named `function_name`
+/// in `path` (in source order).
+///
+/// Methods match on their bare name as well as their qualified name.
+pub fn dump_function_ast(path: &Path, function_name: &str) -> Result<Vec<FunctionAstDump>> {
+    let src = crate::language::read_source(path)?;
+    let language = Language::from_path(path)
+        .ok_or_else(|| anyhow::anyhow!("Unsupported file type: {}", path.display()))?;
+    let source_map: Lrc<SourceMap> = Default::default();
+    let parser = analysis::create_parser(language, &source_map)?;
+    let module = parser.parse(&src, &path.to_string_lossy())?;
+
+    let dumps: Vec<FunctionAstDump> = module
+        .discover_functions(0, &src)
+        .iter()
+        .filter(|f| {
+            f.name
+                .as_deref()
+                .is_some_and(|n| name_matches(n, function_name))
+        })
+        .map(|f| {
+            let cfg = language::get_builder_for_function(f).build(f);
+            let mut nodes = collect_nodes(f, &source_map);
+            nodes.sort_by(|a, b| (a.line, a.column, &a.kind).cmp(&(b.line, b.column, &b.kind)));
+            FunctionAstDump {
+                name: f.name.clone().unwrap_or_default(),
+                line: f.line(),
+                metrics: metrics::extract_metrics(f, &cfg),
+                nodes,
+            }
+        })
+        .collect();
+
+    if dumps.is_empty() {
+        anyhow::bail!(
+            "no function named '{}' found in {}",
+            function_name,
+            path.display()
+        );
+    }
+    Ok(dumps)
+}
+
+/// Render dumps as plain text, one block per function.
+pub fn render_ast_dump(dumps: &[FunctionAstDump]) -> String {
+    let mut out = String::new();
+    for (i, dump) in dumps.iter().enumerate() {
+        if i > 0 {
+            out.push('\n');
+        }
+        out.push_str(&format!(
+            "{} (line {}): CC={} ND={} FO={} NS={}\n",
+            dump.name,
+            dump.line,
+            dump.metrics.cc,
+            dump.metrics.nd,
+            dump.metrics.fo,
+            dump.metrics.ns
+        ));
+        if dump.nodes.is_empty() {
+            out.push_str("  (no contributing nodes)\n");
+        }
+        for node in &dump.nodes {
+            let position = format!("{}:{}", node.line, node.column);
+            out.push_str(&format!(
+                "  {:<8} {:<32} {}\n",
+                position,
+                node.kind,
+                node.metrics.join(",")
+            ));
+        }
+    }
+    out
+}
+
+fn name_matches(name: &str, wanted: &str) -> bool {
+    name == wanted
+        || name
+            .rsplit(['.', ':'])
+            .next()
+            .is_some_and(|bare| bare == wanted)
+}
+
+fn collect_nodes(function: &FunctionNode, source_map: &Lrc<SourceMap>) -> Vec<AstNodeEntry> {
+    match &function.body {
+        FunctionBody::ECMAScript(block) => {
+            let mut visitor = EcmaNodeVisitor {
+                source_map,
+                nodes: Vec::new(),
+            };
+            swc_ecma_visit::VisitWith::visit_with(block, &mut visitor);
+            visitor.nodes
+        }
+        FunctionBody::Rust { source } => rust_nodes(source, function.span.start_line as usize),
+        FunctionBody::Go { source, .. } => ts_nodes(function, source, &GO_TABLE),
+        FunctionBody::Java { source, .. } => ts_nodes(function, source, &JAVA_TABLE),
+        FunctionBody::Python { source, .. } => ts_nodes(function, source, &PYTHON_TABLE),
+        FunctionBody::CSharp { source, .. } => ts_nodes(function, source, &CSHARP_TABLE),
+        FunctionBody::C { source, .. } => ts_nodes(function, source, &C_TABLE),
+        FunctionBody::Hcl { source } => hcl_nodes(function, source),
+    }
+}
+
+// ============================================================================
+// Tree-sitter languages
+// ============================================================================
+
+/// Node kinds that feed each metric for one tree-sitter language
+struct TsKindTable {
+    language: fn() -> tree_sitter::Language,
+    function_kinds: &'static [&'static str],
+    body_kinds: &'static [&'static str],
+    /// Decision points (branches, cases, handlers, ternaries)
+    branch_kinds: &'static [&'static str],
+    /// Operators that make a `binary_expression` a decision point
+    logical_ops: &'static [&'static str],
+    nesting_kinds: &'static [&'static str],
+    exit_kinds: &'static [&'static str],
+    call_kinds: &'static [&'static str],
+}
+
+const GO_TABLE: TsKindTable = TsKindTable {
+    language: || tree_sitter_go::LANGUAGE.into(),
+    function_kinds: language::go::FUNCTION_KINDS,
+    body_kinds: language::go::BODY_KINDS,
+    branch_kinds: &[
+        "if_statement",
+        "for_statement",
+        "expression_case",
+        "default_case",
+        "communication_case",
+        "type_case",
+    ],
+    logical_ops: &["&&", "||"],
+    nesting_kinds: language::go::NESTING_KINDS,
+    exit_kinds: &["return_statement", "defer_statement"],
+    call_kinds: &["call_expression", "go_statement"],
+};
+
+const JAVA_TABLE: TsKindTable = TsKindTable {
+    language: || tree_sitter_java::LANGUAGE.into(),
+    function_kinds: language::java::FUNCTION_KINDS,
+    body_kinds: language::java::BODY_KINDS,
+    branch_kinds: &[
+        "if_statement",
+        "while_statement",
+        "do_statement",
+        "for_statement",
+        "enhanced_for_statement",
+        "switch_block_statement_group",
+        "switch_rule",
+        "catch_clause",
+        "ternary_expression",
+    ],
+    logical_ops: &["&&", "||"],
+    nesting_kinds: language::java::NESTING_KINDS,
+    exit_kinds: language::java::EXIT_KINDS,
+    call_kinds: &["method_invocation"],
+};
+
+const PYTHON_TABLE: TsKindTable = TsKindTable {
+    language: || tree_sitter_python::LANGUAGE.into(),
+    function_kinds: language::python::FUNCTION_KINDS,
+    body_kinds: language::python::BODY_KINDS,
+    branch_kinds: &[
+        "if_statement",
+        "elif_clause",
+        "while_statement",
+        "for_statement",
+        "except_clause",
+        "case_clause",
+        "boolean_operator",
+        "conditional_expression",
+        "if_clause",
+    ],
+    logical_ops: &[],
+    nesting_kinds: language::python::NESTING_KINDS,
+    exit_kinds: language::python::EXIT_KINDS,
+    call_kinds: &["call"],
+};
+
+const CSHARP_TABLE: TsKindTable = TsKindTable {
+    language: || tree_sitter_c_sharp::LANGUAGE.into(),
+    function_kinds: language::csharp::FUNCTION_KINDS,
+    body_kinds: language::csharp::BODY_KINDS,
+    branch_kinds: &[
+        "if_statement",
+        "while_statement",
+        "do_statement",
+        "for_statement",
+        "foreach_statement",
+        "switch_section",
+        "catch_clause",
+        "conditional_expression",
+    ],
+    logical_ops: &["&&", "||", "??"],
+    nesting_kinds: language::csharp::NESTING_KINDS,
+    exit_kinds: language::csharp::EXIT_KINDS,
+    call_kinds: &["invocation_expression"],
+};
+
+const C_TABLE: TsKindTable = TsKindTable {
+    language: || tree_sitter_c::LANGUAGE.into(),
+    function_kinds: language::c::FUNCTION_KINDS,
+    body_kinds: language::c::BODY_KINDS,
+    branch_kinds: &[
+        "if_statement",
+        "while_statement",
+        "do_statement",
+        "for_statement",
+        "case_statement",
+        "conditional_expression",
+    ],
+    logical_ops: &["&&", "||"],
+    nesting_kinds: language::c::NESTING_KINDS,
+    exit_kinds: language::c::EXIT_KINDS,
+    call_kinds: &["call_expression"],
+};
+
+fn ts_nodes(function: &FunctionNode, source: &str, table: &TsKindTable) -> Vec<AstNodeEntry> {
+    fn walk(node: tree_sitter::Node, table: &TsKindTable, out: &mut Vec<AstNodeEntry>) {
+        let kind = node.kind();
+        let mut tags = Vec::new();
+        let mut label = kind.to_string();
+        if table.branch_kinds.contains(&kind) {
+            tags.push("cc");
+        } else if kind == "binary_expression" {
+            let mut cursor = node.walk();
+            let op = node
+                .children(&mut cursor)
+                .map(|c| c.kind())
+                .find(|k| table.logical_ops.contains(k));
+            if let Some(op) = op {
+                tags.push("cc");
+                label = format!("{kind}({op})");
+            }
+        }
+        if table.nesting_kinds.contains(&kind) {
+            tags.push("nd");
+        }
+        if table.call_kinds.contains(&kind) {
+            tags.push("fo");
+        }
+        if table.exit_kinds.contains(&kind) {
+            tags.push("ns");
+        }
+        if !tags.is_empty() {
+            let pos = node.start_position();
+            out.push(AstNodeEntry {
+                line: pos.row + 1,
+                column: pos.column + 1,
+                kind: label,
+                metrics: tags,
+            });
+        }
+        let mut cursor = node.walk();
+        for child in node.children(&mut cursor) {
+            walk(child, table, out);
+        }
+    }
+
+    metrics::ts_with_function_body(
+        source,
+        (table.language)(),
+        function.span.start,
+        table.function_kinds,
+        table.body_kinds,
+        |_func_node, body_node| {
+            let mut out = Vec::new();
+            walk(body_node, table, &mut out);
+            out
+        },
+    )
+    .unwrap_or_default()
+}
+
+// ============================================================================
+// ECMAScript (SWC)
+// ============================================================================
+
+struct EcmaNodeVisitor<'a> {
+    source_map: &'a Lrc<SourceMap>,
+    nodes: Vec<AstNodeEntry>,
+}
+
+impl EcmaNodeVisitor<'_> {
+    fn record(&mut self, span: swc_common::Span, kind: &str, metrics: &[&'static str]) {
+        let loc = self.source_map.lookup_char_pos(span.lo);
+        self.nodes.push(AstNodeEntry {
+            line: loc.line,
+            column: loc.col_display + 1,
+            kind: kind.to_string(),
+            metrics: metrics.to_vec(),
+        });
+    }
+}
+
+macro_rules! ecma_visit {
+    ($($method:ident, $ty:ident, $metrics:expr);* $(;)?) => {
+        $(
+            fn $method(&mut self, node: &swc_ecma_ast::$ty) {
+                self.record(node.span(), stringify!($ty), $metrics);
+                swc_ecma_visit::VisitWith::visit_children_with(node, self);
+            }
+        )*
+    };
+}
+
+impl swc_ecma_visit::Visit for EcmaNodeVisitor<'_> {
+    ecma_visit!(
+        visit_if_stmt, IfStmt, &["cc", "nd"];
+        visit_while_stmt, WhileStmt, &["cc", "nd"];
+        visit_do_while_stmt, DoWhileStmt, &["cc", "nd"];
+        visit_for_stmt, ForStmt, &["cc", "nd"];
+        visit_for_in_stmt, ForInStmt, &["cc", "nd"];
+        visit_for_of_stmt, ForOfStmt, &["cc", "nd"];
+        visit_switch_stmt, SwitchStmt, &["nd"];
+        visit_switch_case, SwitchCase, &["cc"];
+        visit_try_stmt, TryStmt, &["nd"];
+        visit_catch_clause, CatchClause, &["cc"];
+        visit_cond_expr, CondExpr, &["cc"];
+        visit_call_expr, CallExpr, &["fo"];
+        visit_return_stmt, ReturnStmt, &["ns"];
+        visit_throw_stmt, ThrowStmt, &["ns"];
+        visit_break_stmt, BreakStmt, &["ns"];
+        visit_continue_stmt, ContinueStmt, &["ns"];
+    );
+
+    fn visit_bin_expr(&mut self, node: &swc_ecma_ast::BinExpr) {
+        use swc_ecma_ast::BinaryOp;
+        match node.op {
+            BinaryOp::LogicalAnd => self.record(node.span, "BinExpr(&&)", &["cc"]),
+            BinaryOp::LogicalOr => self.record(node.span, "BinExpr(||)", &["cc"]),
+            _ => {}
+        }
+        swc_ecma_visit::VisitWith::visit_children_with(node, self);
+    }
+}
+
+// ============================================================================
+// Rust (syn)
+// ============================================================================
+
+fn rust_nodes(source: &str, start_line: usize) -> Vec<AstNodeEntry> {
+    use syn::visit::Visit;
+
+    let Ok(item_fn) = syn::parse_str::<syn::ItemFn>(source) else {
+        return Vec::new();
+    };
+    let mut visitor = RustNodeVisitor {
+        line_offset: start_line.saturating_sub(1),
+        nodes: Vec::new(),
+    };
+    visitor.visit_block(&item_fn.block);
+    visitor.nodes
+}
+
+struct RustNodeVisitor {
+    line_offset: usize,
+    nodes: Vec<AstNodeEntry>,
+}
+
+impl RustNodeVisitor {
+    fn record(&mut self, span: proc_macro2::Span, kind: &str, metrics: &[&'static str]) {
+        let start = span.start();
+        self.nodes.push(AstNodeEntry {
+            line: start.line + self.line_offset,
+            column: start.column + 1,
+            kind: kind.to_string(),
+            metrics: metrics.to_vec(),
+        });
+    }
+}
+
+macro_rules! rust_visit {
+    ($($method:ident, $visit_fn:ident, $ty:ident, $metrics:expr);* $(;)?) => {
+        $(
+            fn $method(&mut self, node: &'ast syn::$ty) {
+                self.record(syn::spanned::Spanned::span(node), stringify!($ty), $metrics);
+                syn::visit::$visit_fn(self, node);
+            }
+        )*
+    };
+}
+
+impl<'ast> syn::visit::Visit<'ast> for RustNodeVisitor {
+    rust_visit!(
+        visit_expr_if, visit_expr_if, ExprIf, &["cc", "nd"];
+        visit_expr_while, visit_expr_while, ExprWhile, &["cc", "nd"];
+        visit_expr_for_loop, visit_expr_for_loop, ExprForLoop, &["cc", "nd"];
+        visit_expr_loop, visit_expr_loop, ExprLoop, &["nd"];
+        visit_expr_match, visit_expr_match, ExprMatch, &["nd"];
+        visit_arm, visit_arm, Arm, &["cc"];
+        visit_expr_call, visit_expr_call, ExprCall, &["fo"];
+        visit_expr_method_call, visit_expr_method_call, ExprMethodCall, &["fo"];
+        visit_macro, visit_macro, Macro, &["fo"];
+        visit_expr_return, visit_expr_return, ExprReturn, &["ns"];
+        visit_expr_break, visit_expr_break, ExprBreak, &["ns"];
+        visit_expr_continue, visit_expr_continue, ExprContinue, &["ns"];
+        visit_expr_try, visit_expr_try, ExprTry, &["ns"];
+    );
+
+    fn visit_expr_binary(&mut self, node: &'ast syn::ExprBinary) {
+        let span = syn::spanned::Spanned::span(node);
+        match node.op {
+            syn::BinOp::And(_) => self.record(span, "ExprBinary(&&)", &["cc"]),
+            syn::BinOp::Or(_) => self.record(span, "ExprBinary(||)", &["cc"]),
+            _ => {}
+        }
+        syn::visit::visit_expr_binary(self, node);
+    }
+
+    // Closures and nested items are analyzed as their own functions
+    fn visit_expr_closure(&mut self, _node: &'ast syn::ExprClosure) {}
+    fn visit_item(&mut self, _node: &'ast syn::Item) {}
+}
+
+// ============================================================================
+// Terraform/HCL (hcl-edit)
+// ============================================================================
+
+fn hcl_nodes(function: &FunctionNode, source: &str) -> Vec<AstNodeEntry> {
+    use crate::language::hcl::block::{block_facts, parse_block, Site};
+
+    let Some(block) = parse_block(source) else {
+        return Vec::new();
+    };
+    let facts = block_facts(&block);
+    let entry = |site: &Site, kind: &str, metrics: &[&'static str]| {
+        let line_start = source[..site.offset].rfind('\n').map_or(0, |i| i + 1);
+        let line = source[..line_start].matches('\n').count();
+        let column = site.offset - line_start
+            + if line == 0 {
+                function.span.start_col as usize
+            } else {
+                0
+            };
+        AstNodeEntry {
+            line: function.span.start_line as usize + line,
+            column: column + 1,
+            kind: kind.to_string(),
+            metrics: metrics.to_vec(),
+        }
+    };
+
+    let mut nodes: Vec<AstNodeEntry> = Vec::new();
+    nodes.extend(facts.decisions.iter().map(|d| entry(d, &d.kind, &["cc"])));
+    nodes.extend(
+        facts
+            .blocks
+            .iter()
+            .map(|b| entry(b, &b.kind, &["nd", "ns"])),
+    );
+    nodes.extend(
+        facts
+            .references
+            .iter()
+            .map(|r| entry(r, &format!("reference({})", r.kind), &["fo"])),
+    );
+    nodes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dump(file_name: &str, source: &str, function: &str) -> Vec<FunctionAstDump> {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(file_name);
+        std::fs::write(&path, source).unwrap();
+        dump_function_ast(&path, function).unwrap()
+    }
+
+    #[test]
+    fn test_dump_typescript_function() {
+        let src = "function f(a: number) {\n  if (a > 0 && a < 10) {\n    return g(a);\n  }\n  return 0;\n}\n";
+        let dumps = dump("t.ts", src, "f");
+        assert_eq!(dumps.len(), 1);
+        let kinds: Vec<&str> = dumps[0].nodes.iter().map(|n| n.kind.as_str()).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                "IfStmt",
+                "BinExpr(&&)",
+                "ReturnStmt",
+                "CallExpr",
+                "ReturnStmt"
+            ]
+        );
+        assert_eq!((dumps[0].nodes[0].line, dumps[0].nodes[0].column), (2, 3));
+        // Deterministic across runs
+        assert_eq!(
+            render_ast_dump(&dumps),
+            render_ast_dump(&dump("t.ts", src, "f"))
+        );
+    }
+
+    #[test]
+    fn test_dump_python_function() {
+        let src = "def f(x):\n    if x and x > 1:\n        return g(x)\n    return 0\n";
+        let dumps = dump("t.py", src, "f");
+        let rendered = render_ast_dump(&dumps);
+        assert!(rendered.contains("if_statement"), "{rendered}");
+        assert!(rendered.contains("boolean_operator"), "{rendered}");
+        assert!(rendered.contains("return_statement"), "{rendered}");
+    }
+
+    #[test]
+    fn test_dump_rust_uses_file_lines() {
+        let src = "// header\nfn f(x: u32) -> Option<u32> {\n    let y = g(x)?;\n    Some(y)\n}\n";
+        let dumps = dump("t.rs", src, "f");
+        let try_node = dumps[0]
+            .nodes
+            .iter()
+            .find(|n| n.kind == "ExprTry")
+            .expect("? operator listed");
+        assert_eq!(try_node.line, 3);
+    }
+
+    #[test]
+    fn test_dump_hcl_block() {
+        let src = "# header\nresource \"aws_instance\" \"web\" {\n  count = var.on ? 1 : 0\n  subnet_id = module.vpc.subnet_id\n}\n";
+        let dumps = dump("main.tf", src, "aws_instance.web");
+        let kinds: Vec<(usize, &str)> = dumps[0]
+            .nodes
+            .iter()
+            .map(|n| (n.line, n.kind.as_str()))
+            .collect();
+        assert_eq!(
+            kinds,
+            vec![
+                (3, "count"),
+                (3, "conditional"),
+                (4, "reference(module.vpc)")
+            ]
+        );
+    }
+
+    #[test]
+    fn test_dump_unknown_function_errors() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("t.ts");
+        std::fs::write(&path, "function f() {}\n").unwrap();
+        assert!(dump_function_ast(&path, "missing").is_err());
+    }
+}
@@ -18,7 +18,8 @@
 //! architecture. Advanced call tracking (including external dependencies and runtime
 //! analysis) is reserved for future cloud/pro versions.
 
-use std::collections::{HashMap, VecDeque};
+use crate::language::Language;
+use std::collections::{HashMap, HashSet, VecDeque};
 
 /// Call graph for a codebase.
 ///
@@ -35,6 +36,12 @@ pub struct CallGraph {
     pub total_callee_names: usize,
     /// Callee names that resolved to a known internal function ID
     pub resolved_callee_names: usize,
+    /// Explicit roots from `.hotspots/entrypoints.txt`; when non-empty they
+    /// replace the name heuristics in `is_entry_point`
+    entry_points: Vec<u32>,
+    /// (caller, callee) edges resolved only by name match, not by same-file,
+    /// import, or receiver evidence
+    heuristic_edges: HashSet<(u32, u32)>,
 }
 
 /// Graph metrics for a single function
@@ -59,6 +66,8 @@ impl CallGraph {
             adj: Vec::new(),
             total_callee_names: 0,
             resolved_callee_names: 0,
+            entry_points: Vec::new(),
+            heuristic_edges: HashSet::new(),
         }
     }
 
@@ -103,11 +112,32 @@ impl CallGraph {
         self.adj[caller_idx as usize].push(callee_idx);
     }
 
+    /// Add an edge like [`CallGraph::add_adj`], marking it as resolved by name
+    /// alone so it counts against both endpoints' edge confidence.
+    pub fn add_heuristic_adj(&mut self, caller_idx: u32, callee_idx: u32) {
+        self.add_adj(caller_idx, callee_idx);
+        self.heuristic_edges.insert((caller_idx, callee_idx));
+    }
+
     /// Iterate over all interned function IDs in the graph.
     pub fn all_ids(&self) -> impl Iterator<Item = &str> {
         self.ids.iter().map(|s| s.as_str())
     }
 
+    /// Use these functions as the only entry points for reachability and depth.
+    ///
+    /// IDs that are not nodes in the graph are ignored; see `entrypoints::apply`
+    /// for how listed roots are resolved and reported.
+    pub fn set_entry_points<'a>(&mut self, function_ids: impl IntoIterator<Item = &'a str>) {
+        let mut entries: Vec<u32> = function_ids
+            .into_iter()
+            .filter_map(|id| self.id_to_idx.get(id).copied())
+            .collect();
+        entries.sort_unstable();
+        entries.dedup();
+        self.entry_points = entries;
+    }
+
     /// Add a function to the graph (interning its ID).
     pub fn add_node(&mut self, function_id: String) {
         self.intern(function_id);
@@ -420,15 +450,20 @@ impl CallGraph {
     /// Compute dependency depth for all functions.
     ///
     /// Returns a map from function ID to depth (0 = entry point, None = unreachable).
+    /// Explicit entry points, when set, are the only roots.
     pub fn compute_dependency_depth(&self) -> HashMap<String, Option<usize>> {
         let n = self.ids.len();
         let mut depths: Vec<Option<usize>> = vec![None; n];
         let mut queue: VecDeque<(u32, usize)> = VecDeque::new();
 
         // Identify entry points
-        let mut entry_indices: Vec<u32> = (0..n as u32)
-            .filter(|&i| self.is_entry_point(&self.ids[i as usize]))
-            .collect();
+        let mut entry_indices: Vec<u32> = if self.entry_points.is_empty() {
+            (0..n as u32)
+                .filter(|&i| self.is_entry_point(&self.ids[i as usize]))
+                .collect()
+        } else {
+            self.entry_points.clone()
+        };
 
         if entry_indices.is_empty() {
             let mut fan_in = vec![0usize; n];
@@ -463,6 +498,34 @@ impl CallGraph {
             .collect()
     }
 
+    /// Build a map from function ID to the share of its edges (incoming and
+    /// outgoing) that were resolved with evidence rather than by name alone.
+    ///
+    /// Only functions with at least one heuristic edge are present; a missing
+    /// entry means every edge is trusted (or the function has none).
+    pub fn build_edge_confidence_map(&self) -> HashMap<String, f64> {
+        let n = self.ids.len();
+        let mut total = vec![0usize; n];
+        let mut heuristic = vec![0usize; n];
+        for (caller, callees) in self.adj.iter().enumerate() {
+            for &callee in callees {
+                total[caller] += 1;
+                total[callee as usize] += 1;
+                if self.heuristic_edges.contains(&(caller as u32, callee)) {
+                    heuristic[caller] += 1;
+                    heuristic[callee as usize] += 1;
+                }
+            }
+        }
+        (0..n)
+            .filter(|&i| heuristic[i] > 0)
+            .map(|i| {
+                let confidence = 1.0 - heuristic[i] as f64 / total[i] as f64;
+                (self.ids[i].clone(), confidence)
+            })
+            .collect()
+    }
+
     /// Build a map from function ID to its fan-in count in O(N + E).
     pub fn build_fan_in_map(&self) -> HashMap<String, usize> {
         let n = self.ids.len();
@@ -480,7 +543,16 @@ impl CallGraph {
     }
 
     /// Check if a function is likely an entry point.
+    ///
+    /// With explicit entry points set, only those functions qualify.
     pub fn is_entry_point(&self, function_id: &str) -> bool {
+        if !self.entry_points.is_empty() {
+            return self
+                .id_to_idx
+                .get(function_id)
+                .is_some_and(|idx| self.entry_points.binary_search(idx).is_ok());
+        }
+
         let function_name = function_id.split("::").last().unwrap_or("").to_lowercase();
 
         let entry_point_names = [
@@ -597,6 +669,202 @@ impl Default for CallGraph {
     }
 }
 
+/// How call sites with a receiver (`obj.method()`, `this.helper()`) are matched
+/// to analyzed functions
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, serde::Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MethodResolution {
+    /// Match on the method name and use the receiver as a hint (default):
+    /// `this`/`self` calls prefer the caller's own class, `Type.method` calls
+    /// prefer functions on `Type`, and calls on other receivers only resolve
+    /// within the caller's file or the files it imports
+    #[default]
+    Receiver,
+    /// Match the bare callee name exactly against function names, as call
+    /// graphs did before receivers were recorded (see [`receiverless_callee`])
+    Name,
+}
+
+impl MethodResolution {
+    /// Parse a `callgraph_method_resolution` value
+    pub fn parse(s: &str) -> anyhow::Result<Self> {
+        match s {
+            "receiver" => Ok(MethodResolution::Receiver),
+            "name" => Ok(MethodResolution::Name),
+            other => anyhow::bail!(
+                "callgraph_method_resolution must be \"receiver\" or \"name\" (got \"{}\")",
+                other
+            ),
+        }
+    }
+}
+
+/// Name a call site was recorded under before receivers were kept, or `None`
+/// for a call that was not recorded at all
+///
+/// ECMAScript and Rust call sites keep their receiver (`this.helper`,
+/// `self.helper`, `Type::new`) for receiver resolution. Fan-out and `Name`
+/// resolution use the bare names instead, so keeping receivers changes
+/// neither: Rust drops the `Type::` or `self.` qualifier, and ECMAScript calls
+/// on `this` are left out. Other languages record the same text as before.
+pub fn receiverless_callee(language: Language, callee: &str) -> Option<&str> {
+    match language {
+        Language::Rust => {
+            let name = callee.rsplit("::").next().unwrap_or(callee);
+            Some(name.strip_prefix("self.").unwrap_or(name))
+        }
+        Language::TypeScript
+        | Language::TypeScriptReact
+        | Language::JavaScript
+        | Language::JavaScriptReact
+        | Language::Vue
+        | Language::Svelte => (!callee.starts_with("this.")).then_some(callee),
+        _ => Some(callee),
+    }
+}
+
+/// Fan-out (FO) of a function's recorded call sites: the number of distinct
+/// [`receiverless_callee`] names
+pub fn fan_out(language: Language, callees: &[String]) -> usize {
+    callees
+        .iter()
+        .filter_map(|callee| receiverless_callee(language, callee))
+        .collect::<HashSet<_>>()
+        .len()
+}
+
+/// A call site split into its receiver and the called name
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CallSite<'a> {
+    /// Expression the method is called on (`this`, `obj`, `Type`), if any
+    pub receiver: Option<&'a str>,
+    /// Called function or method name without receiver, arguments, or generics
+    pub name: &'a str,
+}
+
+impl<'a> CallSite<'a> {
+    /// Split a recorded callee into receiver and name.
+    ///
+    /// Accepts every form the extractors record: `helper`, `this.helper`,
+    /// `self.helper`, `Type::new`, `ptr->fn`, and whole invocations such as
+    /// `this.helper(x)` or `List.<T>of(a, b)` (Java, C#).
+    pub fn parse(callee: &'a str) -> Self {
+        let mut text = callee.trim();
+        if text.ends_with(')') {
+            if let Some(open) = matching_open(text, b'(', b')') {
+                text = text[..open].trim_end();
+            }
+        }
+        if text.ends_with('>') && !text.ends_with("->") {
+            if let Some(open) = matching_open(text, b'<', b'>') {
+                text = text[..open].trim_end().trim_end_matches("::");
+            }
+        }
+
+        let bytes = text.as_bytes();
+        let mut depth = 0usize;
+        for i in (0..bytes.len()).rev() {
+            match bytes[i] {
+                b')' | b']' => depth += 1,
+                b'(' | b'[' => depth = depth.saturating_sub(1),
+                b'.' | b':' | b'>' if depth == 0 => {
+                    let sep_start = match bytes[i] {
+                        b':' if i > 0 && bytes[i - 1] == b':' => i - 1,
+                        b'>' if i > 0 && bytes[i - 1] == b'-' => i - 1,
+                        b'.' => i,
+                        _ => continue,
+                    };
+                    let receiver = text[..sep_start].trim().trim_end_matches('?').trim_end();
+                    let name = strip_generics(text[i + 1..].trim());
+                    return CallSite {
+                        receiver: (!receiver.is_empty()).then_some(receiver),
+                        name,
+                    };
+                }
+                _ => {}
+            }
+        }
+        CallSite {
+            receiver: None,
+            name: strip_generics(text),
+        }
+    }
+
+    /// Whether the receiver refers to the caller's own instance or type
+    pub fn is_self_call(&self) -> bool {
+        matches!(self.receiver, Some("this" | "self" | "Self"))
+    }
+
+    /// Final segment of the receiver (`Util` for `com.acme.Util`), when it
+    /// could name a type rather than a call result or index expression
+    pub fn receiver_type(&self) -> Option<&'a str> {
+        let receiver = self.receiver?;
+        if receiver.ends_with(')') || receiver.ends_with(']') {
+            return None;
+        }
+        receiver
+            .rsplit(['.', ':'])
+            .next()
+            .filter(|segment| !segment.is_empty())
+    }
+}
+
+/// Name of the type a function belongs to, for languages that report methods
+/// qualified by their owner (`Type::method` in Rust)
+///
+/// An anonymous function belongs to the type of the function it is defined in
+/// (`Type` for `Type::method::<anonymous@4>`).
+pub fn owner_of(function: &str) -> Option<&str> {
+    let named = enclosing_function(function).unwrap_or(function);
+    let (owner, _) = named.rsplit_once("::")?;
+    Some(owner.rsplit("::").next().unwrap_or(owner))
+}
+
+/// Unqualified name of a function (`method` for `Type::method`)
+pub fn method_name_of(function: &str) -> &str {
+    function
+        .rsplit_once("::")
+        .map_or(function, |(_, name)| name)
+}
+
+/// Whether a function name is an anonymous function's symbol
+/// (`<anonymous@LINE>`, possibly qualified by its enclosing function)
+pub fn is_anonymous(function: &str) -> bool {
+    method_name_of(function).starts_with("<anonymous@")
+}
+
+/// The named function an anonymous function is defined in
+/// (`outer` for `outer::<anonymous@4>`), or None for a named or top-level one
+pub fn enclosing_function(function: &str) -> Option<&str> {
+    function
+        .split_once("::<anonymous@")
+        .map(|(enclosing, _)| enclosing)
+}
+
+/// Drop leading generic arguments from a called name (`<T>of` → `of`)
+fn strip_generics(name: &str) -> &str {
+    match name.strip_prefix('<') {
+        Some(rest) => rest.split_once('>').map_or(name, |(_, n)| n.trim()),
+        None => name,
+    }
+}
+
+/// Index of the bracket opening the group that closes at the end of `text`
+fn matching_open(text: &str, open: u8, close: u8) -> Option<usize> {
+    let mut depth = 0usize;
+    for (i, &b) in text.as_bytes().iter().enumerate().rev() {
+        if b == close {
+            depth += 1;
+        } else if b == open {
+            depth = depth.checked_sub(1)?;
+            if depth == 0 {
+                return Some(i);
+            }
+        }
+    }
+    None
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -659,6 +927,25 @@ mod tests {
         assert_eq!(fan_in.get("C").copied().unwrap_or(0), 2); // A and B call C
     }
 
+    #[test]
+    fn test_build_edge_confidence_map() {
+        let mut graph = CallGraph::new();
+        // A -> B (resolved), A -> C (name match only), B -> C (resolved)
+        let (a, b, c) = (
+            graph.intern("A".to_string()),
+            graph.intern("B".to_string()),
+            graph.intern("C".to_string()),
+        );
+        graph.add_adj(a, b);
+        graph.add_heuristic_adj(a, c);
+        graph.add_adj(b, c);
+
+        let confidence = graph.build_edge_confidence_map();
+        assert_eq!(confidence.get("A"), Some(&0.5));
+        assert_eq!(confidence.get("B"), None); // only trusted edges
+        assert_eq!(confidence.get("C"), Some(&0.5));
+    }
+
     #[test]
     fn test_betweenness_linear_chain() {
         // a -> b -> c: b is the only intermediary on the a→c shortest path.
@@ -834,4 +1121,69 @@ mod tests {
             "hub_c missing from top-3: {top3:?}"
         );
     }
+
+    #[test]
+    fn test_explicit_entry_points_replace_name_heuristics() {
+        let mut graph = CallGraph::new();
+        graph.add_edge("a.ts::main".to_string(), "a.ts::helper".to_string());
+        graph.add_edge("a.ts::dispatch".to_string(), "a.ts::route".to_string());
+        graph.add_node("a.ts::orphan".to_string());
+
+        graph.set_entry_points(["a.ts::dispatch", "a.ts::missing"]);
+
+        assert!(graph.is_entry_point("a.ts::dispatch"));
+        assert!(!graph.is_entry_point("a.ts::main"));
+        let depths = graph.compute_dependency_depth();
+        assert_eq!(depths["a.ts::dispatch"], Some(0));
+        assert_eq!(depths["a.ts::route"], Some(1));
+        assert_eq!(depths["a.ts::main"], None);
+        assert_eq!(depths["a.ts::helper"], None);
+        assert_eq!(depths["a.ts::orphan"], None);
+    }
+
+    #[test]
+    fn test_call_site_parse_splits_receiver_and_name() {
+        let parse = |callee| {
+            let site = CallSite::parse(callee);
+            (site.receiver, site.name)
+        };
+        assert_eq!(parse("helper"), (None, "helper"));
+        assert_eq!(parse("this.helper"), (Some("this"), "helper"));
+        assert_eq!(parse("self.helper"), (Some("self"), "helper"));
+        assert_eq!(parse("Type::new"), (Some("Type"), "new"));
+        assert_eq!(parse("ops->run"), (Some("ops"), "run"));
+        assert_eq!(parse("this.helper(a.b, c())"), (Some("this"), "helper"));
+        assert_eq!(parse("a.b(x).c(y)"), (Some("a.b(x)"), "c"));
+        assert_eq!(parse("List.<String>of(x)"), (Some("List"), "of"));
+        assert_eq!(parse("Parse<int>(s)"), (None, "Parse"));
+        assert_eq!(parse("obj?.load"), (Some("obj"), "load"));
+    }
+
+    #[test]
+    fn test_call_site_receiver_hints() {
+        assert!(CallSite::parse("this.helper").is_self_call());
+        assert!(CallSite::parse("Self::new").is_self_call());
+        assert!(!CallSite::parse("other.helper").is_self_call());
+        assert_eq!(
+            CallSite::parse("com.acme.Util.helper(1)").receiver_type(),
+            Some("Util")
+        );
+        assert_eq!(CallSite::parse("make().helper").receiver_type(), None);
+        assert_eq!(owner_of("Shape::area"), Some("Shape"));
+        assert_eq!(owner_of("area"), None);
+        assert_eq!(method_name_of("Shape::area"), "area");
+    }
+
+    #[test]
+    fn test_anonymous_callbacks_are_not_methods_of_their_enclosing_function() {
+        assert_eq!(owner_of("main::<anonymous@4>"), None);
+        assert_eq!(owner_of("<anonymous@4>"), None);
+        assert_eq!(owner_of("Shape::area::<anonymous@4>"), Some("Shape"));
+        assert_eq!(
+            owner_of("Shape::area::<anonymous@4>::<anonymous@5>"),
+            Some("Shape")
+        );
+        assert_eq!(method_name_of("main::<anonymous@4>"), "<anonymous@4>");
+        assert!(is_anonymous("main::<anonymous@4>"));
+    }
 }
@@ -8,7 +8,7 @@
 
 pub mod builder;
 
-use std::collections::BTreeSet;
+use std::collections::{BTreeMap, BTreeSet};
 
 /// CFG node identifier
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -36,6 +36,9 @@ pub enum NodeKind {
 pub struct CfgNode {
     pub id: NodeId,
     pub kind: NodeKind,
+    /// Source line (1-indexed) of the construct behind a branch node, when the
+    /// builder records it
+    pub line: Option<u32>,
 }
 
 /// An edge in the control flow graph
@@ -58,6 +61,20 @@ pub struct Cfg {
     pub edges: Vec<CfgEdge>,
     pub entry: NodeId,
     pub exit: NodeId,
+    /// Lines spanned by the longest switch case / match arm; `None` when the
+    /// function has none or the builder does not track arm sizes
+    pub max_arm_loc: Option<usize>,
+    /// Statements the builder found after an unconditional return, throw,
+    /// break, or continue; always 0 for builders that don't track dead code
+    pub unreachable_stmts: usize,
+    /// Switch nodes with an extra edge to their join for a `switch` with no
+    /// `default`; the edge keeps the code after the switch reachable but is
+    /// left out of CC, which counts each case separately
+    pub no_match_edges: Vec<NodeId>,
+    /// Source lines of the CC increments counted outside the graph (boolean
+    /// operators, ternaries, case arms, catch clauses, ...), one entry per
+    /// increment; filled in by metric extraction
+    pub extra_decisions: Vec<u32>,
 }
 
 impl Cfg {
@@ -66,10 +83,12 @@ impl Cfg {
         let entry_node = CfgNode {
             id: NodeId(0),
             kind: NodeKind::Entry,
+            line: None,
         };
         let exit_node = CfgNode {
             id: NodeId(1),
             kind: NodeKind::Exit,
+            line: None,
         };
 
         Cfg {
@@ -77,6 +96,10 @@ impl Cfg {
             edges: Vec::new(),
             entry: entry_node.id,
             exit: exit_node.id,
+            max_arm_loc: None,
+            unreachable_stmts: 0,
+            no_match_edges: Vec::new(),
+            extra_decisions: Vec::new(),
         }
     }
 
@@ -85,10 +108,37 @@ impl Cfg {
     /// Returns the NodeId of the added node
     pub fn add_node(&mut self, kind: NodeKind) -> NodeId {
         let id = NodeId(self.nodes.len());
-        self.nodes.push(CfgNode { id, kind });
+        self.nodes.push(CfgNode {
+            id,
+            kind,
+            line: None,
+        });
         id
     }
 
+    /// Add a node for a construct starting on source line `line` (1-indexed)
+    pub fn add_node_at(&mut self, kind: NodeKind, line: u32) -> NodeId {
+        let id = self.add_node(kind);
+        self.nodes[id.0].line = Some(line);
+        id
+    }
+
+    /// Record a switch case or match arm spanning `loc` source lines
+    pub fn record_arm_loc(&mut self, loc: usize) {
+        self.max_arm_loc = Some(self.max_arm_loc.map_or(loc, |max| max.max(loc)));
+    }
+
+    /// Record a statement that no path from entry can reach
+    pub fn record_unreachable(&mut self) {
+        self.unreachable_stmts += 1;
+    }
+
+    /// Add the edge taken when a `switch` discriminant matches no case
+    pub fn add_no_match_edge(&mut self, switch: NodeId, join: NodeId) {
+        self.add_edge(switch, join);
+        self.no_match_edges.push(switch);
+    }
+
     /// Add an edge to the CFG
     pub fn add_edge(&mut self, from: NodeId, to: NodeId) {
         self.edges.push(CfgEdge { from, to });
@@ -221,6 +271,55 @@ impl Cfg {
     pub fn node_count(&self) -> usize {
         self.nodes.len()
     }
+
+    /// Number of distinct nodes with an edge into the exit node
+    ///
+    /// Each is a point where control leaves the function: a `return`,
+    /// `throw`/`raise`/`panic`, or the fall-through end of the body.
+    pub fn exit_edge_count(&self) -> usize {
+        self.edges
+            .iter()
+            .filter(|e| e.to == self.exit)
+            .map(|e| e.from)
+            .collect::<BTreeSet<_>>()
+            .len()
+    }
+
+    /// Decision points with a known source line, as `(line, paths)`
+    ///
+    /// A branch node with `k` successors adds `k - 1` independent paths, its
+    /// share of the cyclomatic complexity. Condition nodes count at least one
+    /// path even when a builder models them with a single edge (catch clauses,
+    /// `goto`, boolean expressions); a loop header only counts when the loop
+    /// has no separate condition node. Each of [`Cfg::extra_decisions`] adds
+    /// one path on its line, so the points cover every CC increment. One entry
+    /// per line, sorted by line.
+    pub fn decision_points(&self) -> Vec<(u32, usize)> {
+        let mut out_degree = vec![0usize; self.nodes.len()];
+        for edge in &self.edges {
+            out_degree[edge.from.0] += 1;
+        }
+        for switch in &self.no_match_edges {
+            out_degree[switch.0] -= 1;
+        }
+        let mut points: BTreeMap<u32, usize> = BTreeMap::new();
+        for node in &self.nodes {
+            let Some(line) = node.line else { continue };
+            let extra = out_degree[node.id.0].saturating_sub(1);
+            let paths = match node.kind {
+                NodeKind::Condition => extra.max(1),
+                NodeKind::LoopHeader => extra,
+                _ => 0,
+            };
+            if paths > 0 {
+                *points.entry(line).or_default() += paths;
+            }
+        }
+        for &line in &self.extra_decisions {
+            *points.entry(line).or_default() += 1;
+        }
+        points.into_iter().collect()
+    }
 }
 
 impl Default for Cfg {
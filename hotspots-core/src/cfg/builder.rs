@@ -7,11 +7,12 @@
 
 use crate::ast::FunctionNode;
 use crate::cfg::{Cfg, NodeId, NodeKind};
+use swc_common::{BytePos, SourceMap, Spanned};
 use swc_ecma_ast::*;
 
 /// Build a CFG from a function's AST body
 pub fn build_cfg(function: &FunctionNode) -> Cfg {
-    let mut builder = CfgBuilder::new();
+    let mut builder = CfgBuilder::new(None);
     // Extract ECMAScript body - this will panic if the function is not ECMAScript
     // This is intentional: only ECMAScript is currently supported
     let block_stmt = function.body.as_ecmascript();
@@ -19,6 +20,38 @@ pub fn build_cfg(function: &FunctionNode) -> Cfg {
     builder.cfg
 }
 
+/// Like [`build_cfg`], but records source lines on branch nodes
+///
+/// `source_map` must be the map the function was parsed with.
+pub fn build_cfg_with_lines(function: &FunctionNode, source_map: &SourceMap) -> Cfg {
+    let mut builder = CfgBuilder::new(Some(LineResolver::new(function, source_map)));
+    builder.build_from_body(function.body.as_ecmascript());
+    builder.cfg
+}
+
+/// Maps swc byte positions to file line numbers
+pub(crate) struct LineResolver<'a> {
+    source_map: &'a SourceMap,
+    offset: i64,
+}
+
+impl<'a> LineResolver<'a> {
+    /// Resolver for positions inside `function`, parsed with `source_map`
+    pub(crate) fn new(function: &FunctionNode, source_map: &'a SourceMap) -> Self {
+        let function_lo = BytePos(function.span.start as u32);
+        LineResolver {
+            source_map,
+            // Vue and Svelte script blocks are parsed on their own, then shifted to file lines
+            offset: i64::from(function.span.start_line)
+                - source_map.lookup_char_pos(function_lo).line as i64,
+        }
+    }
+
+    pub(crate) fn line(&self, pos: BytePos) -> u32 {
+        (self.source_map.lookup_char_pos(pos).line as i64 + self.offset).max(1) as u32
+    }
+}
+
 /// Context for break/continue target resolution
 struct BreakableContext {
     label: Option<String>,
@@ -31,8 +64,10 @@ struct BreakableContext {
 }
 
 /// Builder for constructing CFG from AST
-struct CfgBuilder {
+struct CfgBuilder<'a> {
     cfg: Cfg,
+    /// Set when branch nodes should carry source lines
+    lines: Option<LineResolver<'a>>,
     current_node: Option<NodeId>,
     /// Stack of enclosing loop/switch contexts for break/continue routing
     breakable_stack: Vec<BreakableContext>,
@@ -40,19 +75,31 @@ struct CfgBuilder {
     pending_label: Option<String>,
 }
 
-impl CfgBuilder {
-    fn new() -> Self {
+impl<'a> CfgBuilder<'a> {
+    fn new(lines: Option<LineResolver<'a>>) -> Self {
         let cfg = Cfg::new();
         let entry = cfg.entry;
 
         CfgBuilder {
             cfg,
+            lines,
             current_node: Some(entry),
             breakable_stack: Vec::new(),
             pending_label: None,
         }
     }
 
+    /// Add a branch node for the construct at `span`, with its line when known
+    fn add_branch_node(&mut self, kind: NodeKind, span: swc_common::Span) -> NodeId {
+        match &self.lines {
+            Some(lines) => {
+                let line = lines.line(span.lo);
+                self.cfg.add_node_at(kind, line)
+            }
+            None => self.cfg.add_node(kind),
+        }
+    }
+
     /// Take the pending label (if any) for the next loop/switch context
     fn take_label(&mut self) -> Option<String> {
         self.pending_label.take()
@@ -94,6 +141,13 @@ impl CfgBuilder {
 
     /// Visit a statement and add CFG nodes/edges
     fn visit_stmt(&mut self, stmt: &Stmt) {
+        if self.current_node.is_none() {
+            // Dead code after a terminator — count it, but build nothing
+            if !is_inert_when_dead(stmt) {
+                self.cfg.record_unreachable();
+            }
+            return;
+        }
         match stmt {
             Stmt::Labeled(labeled) => {
                 // Store label for the next loop/switch to consume
@@ -138,7 +192,7 @@ impl CfgBuilder {
         };
 
         // Condition node
-        let condition_node = self.cfg.add_node(NodeKind::Condition);
+        let condition_node = self.add_branch_node(NodeKind::Condition, if_stmt.span);
         self.cfg.add_edge(from_node, condition_node);
 
         // Then branch
@@ -185,11 +239,11 @@ impl CfgBuilder {
         let label = self.take_label();
 
         // Loop header node
-        let header_node = self.cfg.add_node(NodeKind::LoopHeader);
+        let header_node = self.add_branch_node(NodeKind::LoopHeader, while_stmt.span);
         self.cfg.add_edge(from_node, header_node);
 
         // Condition node
-        let condition_node = self.cfg.add_node(NodeKind::Condition);
+        let condition_node = self.add_branch_node(NodeKind::Condition, while_stmt.span);
         self.cfg.add_edge(header_node, condition_node);
 
         // The condition always provides a false-branch edge to join, so eager
@@ -229,7 +283,7 @@ impl CfgBuilder {
         let label = self.take_label();
 
         // Loop header node
-        let header_node = self.cfg.add_node(NodeKind::LoopHeader);
+        let header_node = self.add_branch_node(NodeKind::LoopHeader, do_while_stmt.span);
         self.cfg.add_edge(from_node, header_node);
 
         // Unlike while/for, do-while has no pre-body condition that could provide
@@ -259,7 +313,8 @@ impl CfgBuilder {
             // Body completed normally — emit the post-body condition, back-edge
             // and false-exit edge to the join node.
             let join_node = lazy_join.unwrap_or_else(|| self.cfg.add_node(NodeKind::Join));
-            let condition_node = self.cfg.add_node(NodeKind::Condition);
+            let condition_node =
+                self.add_branch_node(NodeKind::Condition, do_while_stmt.test.span());
             self.cfg.add_edge(body_end, condition_node);
             self.cfg.add_edge(condition_node, header_node);
             self.cfg.add_edge(condition_node, join_node);
@@ -290,14 +345,14 @@ impl CfgBuilder {
         };
 
         // Loop header node
-        let header_node = self.cfg.add_node(NodeKind::LoopHeader);
+        let header_node = self.add_branch_node(NodeKind::LoopHeader, for_stmt.span);
         self.cfg.add_edge(init_end, header_node);
 
         // When a condition is present it always provides a false-branch edge to
         // join → eager creation.  Without a condition (infinite loop) the join
         // is only reachable via break → lazy creation.
         let (condition_node, initial_break_target) = if for_stmt.test.is_some() {
-            let cnode = self.cfg.add_node(NodeKind::Condition);
+            let cnode = self.add_branch_node(NodeKind::Condition, for_stmt.span);
             self.cfg.add_edge(header_node, cnode);
             let join = self.cfg.add_node(NodeKind::Join);
             self.cfg.add_edge(cnode, join);
@@ -351,10 +406,10 @@ impl CfgBuilder {
         };
         let label = self.take_label();
 
-        let header_node = self.cfg.add_node(NodeKind::LoopHeader);
+        let header_node = self.add_branch_node(NodeKind::LoopHeader, for_in_stmt.span);
         self.cfg.add_edge(from_node, header_node);
 
-        let condition_node = self.cfg.add_node(NodeKind::Condition);
+        let condition_node = self.add_branch_node(NodeKind::Condition, for_in_stmt.span);
         self.cfg.add_edge(header_node, condition_node);
 
         // Create join node BEFORE body so break can target it
@@ -391,10 +446,10 @@ impl CfgBuilder {
         };
         let label = self.take_label();
 
-        let header_node = self.cfg.add_node(NodeKind::LoopHeader);
+        let header_node = self.add_branch_node(NodeKind::LoopHeader, for_of_stmt.span);
         self.cfg.add_edge(from_node, header_node);
 
-        let condition_node = self.cfg.add_node(NodeKind::Condition);
+        let condition_node = self.add_branch_node(NodeKind::Condition, for_of_stmt.span);
         self.cfg.add_edge(header_node, condition_node);
 
         // Create join node BEFORE body so break can target it
@@ -432,7 +487,7 @@ impl CfgBuilder {
         let label = self.take_label();
 
         // Switch expression evaluation (implied)
-        let switch_node = self.cfg.add_node(NodeKind::Condition);
+        let switch_node = self.add_branch_node(NodeKind::Condition, switch_stmt.span);
         self.cfg.add_edge(from_node, switch_node);
 
         // The join node is created lazily: it comes into existence on the first
@@ -448,6 +503,10 @@ impl CfgBuilder {
         // Process cases
         let mut prev_case_end: Option<NodeId> = None;
         for case in &switch_stmt.cases {
+            if let Some(lines) = &self.lines {
+                let loc = lines.line(case.span.hi) - lines.line(case.span.lo) + 1;
+                self.cfg.record_arm_loc(loc as usize);
+            }
             let case_node = self.cfg.add_node(NodeKind::Statement);
 
             // Each case gets an edge from switch (for case matching)
@@ -462,9 +521,6 @@ impl CfgBuilder {
             self.current_node = Some(case_node);
             for stmt in &case.cons {
                 self.visit_stmt(stmt);
-                if self.current_node.is_none() {
-                    break;
-                }
             }
 
             // Track end of case for fallthrough to next case
@@ -473,7 +529,14 @@ impl CfgBuilder {
         }
 
         let ctx = self.breakable_stack.pop().unwrap();
-        let lazy_join = ctx.break_target;
+        let mut lazy_join = ctx.break_target;
+
+        // Without a `default`, a discriminant matching no case skips the
+        // switch entirely
+        if !switch_stmt.cases.iter().any(|case| case.test.is_none()) {
+            let join_node = *lazy_join.get_or_insert_with(|| self.cfg.add_node(NodeKind::Join));
+            self.cfg.add_no_match_edge(switch_node, join_node);
+        }
 
         if let Some(last_end) = prev_case_end {
             // Last case fell through — wire it to the (possibly new) join node.
@@ -674,9 +737,23 @@ impl CfgBuilder {
     }
 }
 
-impl Default for CfgBuilder {
+/// Statements that do nothing at their position even when control can't reach
+/// them: hoisted function declarations, bare `var` declarations, type-only
+/// declarations, and empty statements
+fn is_inert_when_dead(stmt: &Stmt) -> bool {
+    match stmt {
+        Stmt::Empty(_) => true,
+        Stmt::Decl(Decl::Fn(_) | Decl::TsInterface(_) | Decl::TsTypeAlias(_)) => true,
+        Stmt::Decl(Decl::Var(var)) => {
+            var.kind == VarDeclKind::Var && var.decls.iter().all(|d| d.init.is_none())
+        }
+        _ => false,
+    }
+}
+
+impl Default for CfgBuilder<'_> {
     fn default() -> Self {
-        Self::new()
+        Self::new(None)
     }
 }
 
@@ -936,5 +1013,60 @@ mod tests {
         "#,
         );
         cfg.validate().expect("CFG should be valid");
+        assert_eq!(cfg.unreachable_stmts, 1);
+    }
+
+    #[test]
+    fn test_unreachable_statements_are_counted() {
+        let cfg = build_cfg_for(
+            r#"
+            function f(x: number): number {
+                switch (x) {
+                    case 1:
+                        return 1;
+                        x++;
+                    default:
+                        break;
+                }
+                return x;
+                { x++; x--; }
+                function hoisted() {}
+                var later;
+                type T = number;
+            }
+        "#,
+        );
+        cfg.validate().expect("CFG should be valid");
+        // `x++` after the case's return, and the whole block after the final return
+        assert_eq!(cfg.unreachable_stmts, 2);
+    }
+
+    /// A switch with no `default` can match no case, so the code after it
+    /// stays reachable even when every case returns
+    #[test]
+    fn test_switch_without_default_falls_past_returning_cases() {
+        let cfg = build_cfg_for(
+            r#"
+            function f(x: number): number {
+                switch (x) {
+                    case 1:
+                        return 10;
+                }
+                return 0;
+            }
+        "#,
+        );
+        cfg.validate().expect("CFG should be valid");
+        assert_eq!(cfg.unreachable_stmts, 0);
+    }
+
+    #[test]
+    fn test_decision_points_carry_source_lines() {
+        let source = "function f(x) {\n  if (x) {\n    x++;\n  }\n  while (x) { x--; }\n}\n";
+        let sm = Lrc::new(SourceMap::default());
+        let module = parse_source(source, &sm, "test.ts").unwrap();
+        let functions = discover_functions(&module, 0, source, &sm);
+        let cfg = build_cfg_with_lines(&functions[0], &sm);
+        assert_eq!(cfg.decision_points(), vec![(2, 1), (5, 1)]);
     }
 }
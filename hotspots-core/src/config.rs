@@ -1,23 +1,26 @@
 //! Configuration file support for Hotspots
 //!
-//! Loads project-specific configuration from JSON files.
+//! Loads project-specific configuration from JSON or YAML files.
 //!
 //! Search order:
 //! 1. Explicit path (--config CLI flag)
-//! 2. `.hotspotsrc.json` in project root
-//! 3. `hotspots.config.json` in project root
-//! 4. `"hotspots"` key in `package.json`
+//! 2. `HOTSPOTS_CONFIG` environment variable: a config file path, or inline JSON/YAML
+//! 3. `.hotspotsrc.json` in project root
+//! 4. `hotspots.config.json` in project root
+//! 5. `.hotspots.yaml` / `.hotspots.yml` in project root
+//! 6. `"hotspots"` key in `package.json`
 //!
 //! All fields are optional. CLI flags take precedence over config file values.
 
+use crate::language::Language;
 use anyhow::{Context, Result};
 use globset::{Glob, GlobSet, GlobSetBuilder};
 use serde::{Deserialize, Serialize};
 use std::path::{Path, PathBuf};
 
-/// Default exclude patterns always applied (merged with any user-specified excludes).
-const DEFAULT_EXCLUDES: &[&str] = &[
-    // Test files
+/// Test-file conventions. Always excluded from analysis (see [`DEFAULT_EXCLUDES`])
+/// and used by [`is_test_file`] to pair modules with their tests.
+const TEST_FILE_PATTERNS: &[&str] = &[
     "**/*.test.ts",
     "**/*.test.tsx",
     "**/*.test.js",
@@ -27,13 +30,20 @@ const DEFAULT_EXCLUDES: &[&str] = &[
     "**/*.spec.js",
     "**/*.spec.jsx",
     "**/__tests__/**",
-    "**/__mocks__/**",
-    "**/__snapshots__/**",
     // Python test conventions
     "**/test_*.py",
     "**/*_test.py",
-    // Go test and generated conventions
+    // Go test convention
     "**/*_test.go",
+];
+
+/// Default exclude patterns always applied (merged with any user-specified excludes),
+/// in addition to [`TEST_FILE_PATTERNS`].
+const DEFAULT_EXCLUDES: &[&str] = &[
+    // Test fixtures
+    "**/__mocks__/**",
+    "**/__snapshots__/**",
+    // Go vendored and generated code
     "**/vendor/**",
     "**/*.pb.go",
     "**/zz_generated*.go",
@@ -75,6 +85,9 @@ const DEFAULT_EXCLUDES: &[&str] = &[
     "**/contrib/**",
 ];
 
+/// Default size limit for source files (2 MiB). Larger files are skipped.
+pub const DEFAULT_MAX_FILE_BYTES: u64 = 2 * 1024 * 1024;
+
 /// Hotspots configuration loaded from a JSON config file
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 #[serde(deny_unknown_fields)]
@@ -87,6 +100,15 @@ pub struct HotspotsConfig {
     #[serde(default)]
     pub exclude: Vec<String>,
 
+    /// Only analyze files in these languages, by detected `Language` (e.g.
+    /// `"rust"`, `"typescript"`; default: all)
+    #[serde(default)]
+    pub include_languages: Vec<String>,
+
+    /// Skip files in these languages, by detected `Language`
+    #[serde(default)]
+    pub exclude_languages: Vec<String>,
+
     /// Custom risk band thresholds
     #[serde(default)]
     pub thresholds: Option<ThresholdConfig>,
@@ -111,6 +133,22 @@ pub struct HotspotsConfig {
     #[serde(default)]
     pub scoring: Option<ScoringWeightsConfig>,
 
+    /// Coefficients for the file-level risk score
+    #[serde(default)]
+    pub file_risk: Option<FileRiskConfig>,
+
+    /// Module zone cutoffs for the HTML report and JSON `module_risk`
+    #[serde(default)]
+    pub module_zones: Option<ModuleZonesConfig>,
+
+    /// Risk band colours in the HTML report
+    #[serde(default)]
+    pub band_colors: Option<BandColorsConfig>,
+
+    /// Weights for the `priority_score` triage ranking
+    #[serde(default)]
+    pub priority: Option<PriorityConfig>,
+
     /// Number of days back to look for co-change pairs (default: 90)
     #[serde(default)]
     pub co_change_window_days: Option<u64>,
@@ -119,6 +157,21 @@ pub struct HotspotsConfig {
     #[serde(default)]
     pub co_change_min_count: Option<usize>,
 
+    /// Weight each commit's co-change contribution by 1/(files - 1) so
+    /// sweeping commits count for less (default: true)
+    #[serde(default)]
+    pub co_change_weighted: Option<bool>,
+
+    /// Denominator of the co-change coupling ratio: "min" (default) divides by
+    /// the smaller file's change count, "jaccard" by the commits touching either
+    #[serde(default)]
+    pub co_change_metric: Option<String>,
+
+    /// Snapshots required before `trends` labels velocity direction and hotspot
+    /// stability; fewer yield `insufficient_data` (default: 5)
+    #[serde(default)]
+    pub trends_min_snapshots: Option<usize>,
+
     /// Use per-function git log -L for touch metrics (default: false).
     /// Warm runs use the on-disk cache and are as fast as file-level.
     /// Set to true for full precision; the default is hybrid touch mode (see hybrid_touch_threshold).
@@ -131,6 +184,12 @@ pub struct HotspotsConfig {
     #[serde(default)]
     pub hybrid_touch_threshold: Option<usize>,
 
+    /// Commits left out of churn and touch metrics, like `.git-blame-ignore-revs`:
+    /// e.g. a repo-wide `cargo fmt` that would otherwise count as a touch on
+    /// every function.
+    #[serde(default)]
+    pub ignore_commits: Option<IgnoreCommitsConfig>,
+
     /// Percentile threshold for driving dimension detection (1–99, default: 75).
     /// A function must exceed this percentile in a metric to trigger that driver label.
     /// Lower values = more functions get specific labels; higher = only extreme outliers.
@@ -156,13 +215,119 @@ pub struct HotspotsConfig {
     #[serde(default)]
     pub callgraph_skip_above: Option<usize>,
 
+    /// How member calls like `obj.method()` and `this.helper()` resolve in the
+    /// call graph: "receiver" (default) matches the method name and prefers the
+    /// receiver's type, "name" matches the recorded callee text exactly.
+    #[serde(default)]
+    pub callgraph_method_resolution: Option<String>,
+
+    /// Keep anonymous functions (closures, callbacks) in the call graph, each
+    /// linked from the named function that defines it (default: true). Set to
+    /// false to leave them out of the graph and its metrics.
+    #[serde(default)]
+    pub callgraph_include_anonymous: Option<bool>,
+
+    /// Skip source files larger than this many bytes (default: 2 MiB).
+    /// Guards against huge minified or generated files hanging the parser.
+    /// Set to 0 to disable the limit.
+    #[serde(default)]
+    pub max_file_bytes: Option<u64>,
+
+    /// Leave anonymous functions (closures, callbacks, unassigned arrows) out of
+    /// the analysis entirely (default: false). Named functions are unaffected.
+    #[serde(default)]
+    pub skip_anonymous_functions: Option<bool>,
+
+    /// Fail analysis when it finds no functions at all (default: false), so a
+    /// wrong path or an exclude that matches everything can't pass CI silently.
+    #[serde(default)]
+    pub require_functions: Option<bool>,
+
+    /// Unit of analysis: "function" (default) reports each function, "file"
+    /// reports one entry per file with whole-file CC/ND/FO/NS/LOC.
+    #[serde(default)]
+    pub granularity: Option<String>,
+
+    /// Rust macros counted as decision points in CC, replacing the built-in
+    /// list (`assert`, `assert_eq`, `assert_ne`, their `debug_` forms,
+    /// `ensure`, `matches`). Names without `!`; `[]` counts none. Macros are
+    /// not expanded, so this is how custom assertion macros are made visible.
+    #[serde(default)]
+    pub rust_branching_macros: Option<Vec<String>>,
+
+    /// Hard limit on function length in lines. Longer functions are flagged
+    /// `long_function` (in place of `patterns.long_function_loc`) and, in delta
+    /// mode, trip the `function-too-long` policy when they cross it.
+    #[serde(default)]
+    pub max_function_loc: Option<usize>,
+
+    /// Limit on the cyclomatic complexity a change may add to one function,
+    /// counting only decision points on added lines. Enables the `max-new-cc`
+    /// delta policy.
+    #[serde(default)]
+    pub max_new_cc: Option<u32>,
+
+    /// Limit on the number of new functions landing in the critical band in one
+    /// change. Enables the `max-new-critical` delta policy.
+    #[serde(default)]
+    pub max_new_critical: Option<usize>,
+
+    /// Limit on the number of new functions landing in the high band in one
+    /// change. Enables the `max-new-high` delta policy.
+    #[serde(default)]
+    pub max_new_high: Option<usize>,
+
+    /// Limit on afferent coupling (modules depending on it) for a stable
+    /// module. Enables the `instability-regression` delta policy.
+    #[serde(default)]
+    pub max_stable_afferent: Option<usize>,
+
+    /// Functions shorter than this many lines count as trivial (generated
+    /// accessors, one-liners). Only used when `count_trivial` is false.
+    #[serde(default)]
+    pub min_loc: Option<usize>,
+
+    /// Include trivial functions in the snapshot summary and percentile flags
+    /// (default: true). Set to false to leave functions under `min_loc` or
+    /// `min_lrs` out of the band counts and top-k shares; they still appear in
+    /// every report.
+    #[serde(default)]
+    pub count_trivial: Option<bool>,
+
     /// Pattern detection thresholds. Overrides defaults from `docs/patterns.md`.
     #[serde(default)]
     pub patterns: Option<PatternThresholdsConfig>,
 
+    /// Pattern thresholds for one kind of function (`function`, `method`,
+    /// `closure`, `constructor`), layered over `patterns`: e.g. a higher
+    /// `complex_branching_cc` for constructors.
+    #[serde(default, skip_serializing_if = "std::collections::BTreeMap::is_empty")]
+    pub kind_thresholds:
+        std::collections::BTreeMap<crate::ast::FunctionKind, PatternThresholdsConfig>,
+
+    /// User-defined patterns, evaluated against each function's metrics after
+    /// the built-in set.
+    #[serde(default)]
+    pub custom_patterns: Vec<CustomPatternConfig>,
+
     /// Per-repo severity overrides for blocking policies.
     #[serde(default)]
     pub policy: Option<PolicyConfig>,
+
+    /// Glob-based suppressions with a required reason. Matching functions are
+    /// still reported but excluded from policy failures.
+    #[serde(default)]
+    pub suppress: Vec<SuppressRuleConfig>,
+
+    /// Function IDs (`file::symbol`) to drop from reports and policy evaluation.
+    /// Finer-grained than `exclude`, which drops the whole file.
+    #[serde(default)]
+    pub exclude_functions: Vec<String>,
+
+    /// Salt for `--redact` identifier hashes; keep it private and stable so
+    /// redacted runs stay comparable with each other
+    #[serde(default)]
+    pub redact_salt: Option<String>,
 }
 
 /// Severity for a blocking policy, as configured per-repo.
@@ -171,7 +336,8 @@ pub struct HotspotsConfig {
 /// dense, one-shot scripts) can downgrade a policy from `block` to `warn`, or
 /// disable it with `off`, without changing what counts as Critical (see
 /// `thresholds.critical`) or which findings are reported.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
 pub enum PolicyMode {
     Block,
     Warn,
@@ -193,6 +359,34 @@ impl PolicyMode {
     }
 }
 
+/// Unit each report entry describes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Granularity {
+    /// One entry per function (default)
+    Function,
+    /// One entry per file, named [`Granularity::FILE_ENTRY_NAME`], combining the
+    /// file's top-level code and all its functions
+    File,
+}
+
+impl Granularity {
+    /// Function name of a file-granularity entry
+    pub const FILE_ENTRY_NAME: &'static str = "<file>";
+
+    /// Parse a `granularity` value
+    pub fn parse(s: &str) -> Result<Self> {
+        match s {
+            "function" => Ok(Granularity::Function),
+            "file" => Ok(Granularity::File),
+            other => anyhow::bail!(
+                "granularity must be \"function\" or \"file\" (got \"{}\")",
+                other
+            ),
+        }
+    }
+}
+
 /// Per-repo severity overrides for blocking policies.
 ///
 /// Both `critical-introduction` (a function becomes Critical) and
@@ -206,6 +400,13 @@ impl PolicyMode {
 /// (mirroring the `// hotspots-ignore: <reason>` convention for per-function
 /// suppression), so a reviewer of the `.hotspotsrc.json` diff sees *why* a
 /// blocking gate was weakened, not just that it was.
+///
+/// `function-too-long` is opt-in (it needs `max_function_loc`) and defaults to
+/// `warn`; setting it to `block` makes the length limit a CI gate, so it needs
+/// no reason. `max-new-cc` is opt-in too (it needs `max_new_cc`) but defaults
+/// to `block`, since configuring the limit is asking for the gate; the same
+/// goes for `instability-regression` and `max_stable_afferent`, and for
+/// `max-new-critical`/`max-new-high` and their count limits.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(deny_unknown_fields)]
 pub struct PolicyConfig {
@@ -217,6 +418,21 @@ pub struct PolicyConfig {
     pub excessive_risk_regression: Option<String>,
     /// Required when `excessive_risk_regression` is not "block"
     pub excessive_risk_regression_reason: Option<String>,
+    /// Severity for `function-too-long`, which only runs when `max_function_loc`
+    /// is set: "block" | "warn" | "off" (default: "warn")
+    pub function_too_long: Option<String>,
+    /// Severity for `max-new-cc`, which only runs when `max_new_cc` is set:
+    /// "block" | "warn" | "off" (default: "block")
+    pub max_new_cc: Option<String>,
+    /// Severity for `max-new-critical`, which only runs when `max_new_critical`
+    /// is set: "block" | "warn" | "off" (default: "block")
+    pub max_new_critical: Option<String>,
+    /// Severity for `max-new-high`, which only runs when `max_new_high` is set:
+    /// "block" | "warn" | "off" (default: "block")
+    pub max_new_high: Option<String>,
+    /// Severity for `instability-regression`, which only runs when
+    /// `max_stable_afferent` is set: "block" | "warn" | "off" (default: "block")
+    pub instability_regression: Option<String>,
 }
 
 /// Custom risk band thresholds
@@ -243,6 +459,8 @@ pub struct WeightConfig {
     pub fo: Option<f64>,
     /// Weight for non-structured exits (default: 0.7)
     pub ns: Option<f64>,
+    /// Weight for Rust unwrap/expect/panic sites (default: 0.0, informational)
+    pub unwrap: Option<f64>,
 }
 
 /// Weights for activity-weighted risk scoring
@@ -265,6 +483,63 @@ pub struct ScoringWeightsConfig {
     pub neighbor_churn: Option<f64>,
     /// Weight for commit-timing burstiness factor (default: 0.3)
     pub burst: Option<f64>,
+    /// Fraction (0.0-1.0) of the call graph factors removed for functions
+    /// whose edges were resolved by name alone (default: 0.0, off)
+    pub heuristic_discount: Option<f64>,
+}
+
+/// Coefficients for the composite file risk score
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct FileRiskConfig {
+    /// Weight for the file's maximum CC (default: 0.4)
+    pub max_cc: Option<f64>,
+    /// Weight for the file's average CC (default: 0.3)
+    pub avg_cc: Option<f64>,
+    /// Weight for log2(function count + 1) (default: 0.2)
+    pub function_count: Option<f64>,
+    /// Weight for the churn factor (default: 0.1)
+    pub churn: Option<f64>,
+}
+
+/// Weights for the `priority_score` triage ranking
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PriorityConfig {
+    /// Share of the score a fully covered function gives up, 0.0–1.0 (default: 1.0)
+    pub coverage: Option<f64>,
+    /// Boost for a function changed today (default: 1.0)
+    pub recency: Option<f64>,
+    /// Boost for 10+ commits in the last 30 days (default: 1.0)
+    pub churn: Option<f64>,
+    /// Days for the recency boost to halve (default: 30)
+    pub recency_half_life_days: Option<f64>,
+}
+
+/// Instability/complexity cutoffs for the module zones and `module_risk`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ModuleZonesConfig {
+    /// Instability below which a module is stable (default: 0.3)
+    pub stable_below: Option<f64>,
+    /// Instability above which a module is volatile (default: 0.7)
+    pub volatile_above: Option<f64>,
+    /// Average CC above which a stable module is in the zone of pain (default: 8.0)
+    pub pain_complexity: Option<f64>,
+}
+
+/// Hex colours (`#rgb` or `#rrggbb`) for the HTML risk bands
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct BandColorsConfig {
+    /// Low band (default: #22c55e)
+    pub low: Option<String>,
+    /// Moderate band (default: #eab308)
+    pub moderate: Option<String>,
+    /// High band (default: #f97316)
+    pub high: Option<String>,
+    /// Critical band (default: #ef4444)
+    pub critical: Option<String>,
 }
 
 /// Pattern detection thresholds — override defaults from `docs/patterns.md`
@@ -275,9 +550,15 @@ pub struct PatternThresholdsConfig {
     pub complex_branching_nd: Option<usize>,
     pub deeply_nested_nd: Option<usize>,
     pub exit_heavy_ns: Option<usize>,
+    pub exit_heavy_points: Option<usize>,
+    pub exit_heavy_ratio_pct: Option<usize>,
+    pub fat_case_arm_loc: Option<usize>,
+    pub fat_case_ratio_pct: Option<usize>,
     pub god_function_loc: Option<usize>,
     pub god_function_fo: Option<usize>,
     pub long_function_loc: Option<usize>,
+    pub nested_literal_depth: Option<usize>,
+    pub panic_prone_unwraps: Option<usize>,
     pub churn_magnet_churn: Option<usize>,
     pub churn_magnet_cc: Option<usize>,
     pub cyclic_hub_scc: Option<usize>,
@@ -294,6 +575,48 @@ pub struct PatternThresholdsConfig {
     pub stale_complex_cc: Option<usize>,
     pub stale_complex_loc: Option<usize>,
     pub stale_complex_days: Option<u32>,
+    pub risky_untested_lrs: Option<usize>,
+    pub risky_untested_coverage_pct: Option<usize>,
+}
+
+/// Commits to leave out of churn and touch metrics
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct IgnoreCommitsConfig {
+    /// Commit SHA prefixes, at least 4 hex digits each
+    #[serde(default)]
+    pub revs: Vec<String>,
+    /// Regular expressions; a commit is ignored when any line of its message
+    /// matches one (e.g. `"^chore: format"`)
+    #[serde(default)]
+    pub messages: Vec<String>,
+}
+
+/// A user-defined pattern: `name` fires when every comparison in `condition` holds.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CustomPatternConfig {
+    /// Pattern ID shown in output (lowercase letters, digits, and `_`)
+    pub name: String,
+    /// `&&`-joined comparisons, e.g. `"cc > 20 && nd >= 4"`
+    pub condition: String,
+    /// Short description shown in the HTML pattern breakdown
+    #[serde(default)]
+    pub description: Option<String>,
+}
+
+/// A suppression rule: functions in files matching `pattern` (and, if set,
+/// named like `function`) are suppressed with `reason`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SuppressRuleConfig {
+    /// File glob, e.g. `"src/legacy/**"`; relative globs match at any depth
+    pub pattern: String,
+    /// Optional function-name glob, e.g. `"handle*"`
+    #[serde(default)]
+    pub function: Option<String>,
+    /// Why these functions are suppressed (required, non-empty)
+    pub reason: String,
 }
 
 /// Warning thresholds for proactive alerts
@@ -313,12 +636,25 @@ pub struct WarningThresholdConfig {
 }
 
 /// Resolved configuration with compiled glob patterns
-#[derive(Debug)]
+///
+/// Serializes (for `config show --format json`) with the source glob lists in
+/// place of the compiled matchers.
+#[derive(Debug, Serialize)]
 pub struct ResolvedConfig {
     /// Compiled include patterns (empty means include all)
+    #[serde(skip)]
     pub include: Option<GlobSet>,
     /// Compiled exclude patterns
+    #[serde(skip)]
     pub exclude: GlobSet,
+    /// Source include globs behind `include` (after any command-line overrides)
+    pub include_patterns: Vec<String>,
+    /// Source user exclude globs behind `exclude`, excluding built-in defaults
+    pub exclude_patterns: Vec<String>,
+    /// Languages to analyze (empty means all)
+    pub include_languages: Vec<Language>,
+    /// Languages to skip
+    pub exclude_languages: Vec<Language>,
     /// Risk band thresholds
     pub moderate_threshold: f64,
     pub high_threshold: f64,
@@ -328,6 +664,7 @@ pub struct ResolvedConfig {
     pub weight_nd: f64,
     pub weight_fo: f64,
     pub weight_ns: f64,
+    pub weight_unwrap: f64,
     /// Warning thresholds
     pub watch_min: f64,
     pub watch_max: f64,
@@ -340,10 +677,16 @@ pub struct ResolvedConfig {
     /// Co-change mining parameters
     pub co_change_window_days: u64,
     pub co_change_min_count: usize,
+    pub co_change_weighted: bool,
+    pub co_change_metric: crate::git::CoChangeMetric,
+    /// Minimum history for trend classification
+    pub trends_min_snapshots: usize,
     /// Whether to use per-function git log -L for touch metrics
     pub per_function_touches: bool,
     /// Hybrid touch threshold: Some(n) = file-level first, per-function for files with ≥n touches
     pub hybrid_touch_threshold: Option<usize>,
+    /// Commits left out of churn and touch metrics
+    pub ignore_commits: crate::git::IgnoredCommits,
     /// Percentile threshold for driving dimension detection (1–99)
     pub driver_threshold_percentile: u8,
     /// Node count above which betweenness switches to approximate algorithm
@@ -352,10 +695,44 @@ pub struct ResolvedConfig {
     pub betweenness_approx_k: usize,
     /// Skip all call graph computation above this function count (usize::MAX = never skip)
     pub callgraph_skip_above: usize,
+    /// How member calls resolve to functions in the call graph
+    pub callgraph_method_resolution: crate::callgraph::MethodResolution,
+    /// Whether anonymous functions are call graph nodes
+    pub callgraph_include_anonymous: bool,
+    /// Skip source files larger than this many bytes (0 = no limit)
+    pub max_file_bytes: u64,
+    /// Drop anonymous functions from analysis results
+    pub skip_anonymous_functions: bool,
+    /// Treat an analysis that finds no functions as an error
+    pub require_functions: bool,
+    /// Report per function or per file
+    pub granularity: Granularity,
+    /// Rust macros counted as decision points (None = built-in list)
+    pub rust_branching_macros: Option<Vec<String>>,
+    /// Hard function length limit in lines (None = no limit); see [`ResolvedConfig::set_max_function_loc`]
+    pub max_function_loc: Option<usize>,
+    /// Functions left out of summary statistics and percentile flags
+    /// (`count_trivial: false`; the default counts every function)
+    pub trivial: crate::snapshot::TrivialCutoff,
     /// Activity risk scoring weights
     pub scoring_weights: crate::scoring::ScoringWeights,
+    /// File risk score coefficients
+    pub file_risk_weights: crate::aggregates::FileRiskWeights,
+    /// Weights for `priority_score`
+    pub priority_weights: crate::scoring::PriorityWeights,
+    /// Module zone cutoffs for the HTML report and JSON `module_risk`
+    pub module_zones: crate::aggregates::ModuleZoneThresholds,
+    /// Risk band colours for the HTML report
+    pub band_colors: crate::html::BandColors,
     /// Pattern detection thresholds
     pub pattern_thresholds: crate::patterns::Thresholds,
+    /// Compiled `suppress` rules
+    pub suppress_rules: Vec<crate::suppression::SuppressRule>,
+    /// Function IDs removed from analysis results
+    pub exclude_functions: Vec<String>,
+    /// Salt for `--redact` hashes (never serialized)
+    #[serde(skip)]
+    pub redact_salt: Option<String>,
     /// Severity for the `critical-introduction` policy (default: Block)
     pub critical_introduction_mode: PolicyMode,
     /// Reason given for downgrading `critical_introduction_mode` below Block (None if Block)
@@ -364,7 +741,29 @@ pub struct ResolvedConfig {
     pub excessive_risk_regression_mode: PolicyMode,
     /// Reason given for downgrading `excessive_risk_regression_mode` below Block (None if Block)
     pub excessive_risk_regression_reason: Option<String>,
-    /// Path the config was loaded from (None if defaults)
+    /// Severity for the `function-too-long` policy (default: Warn)
+    pub function_too_long_mode: PolicyMode,
+    /// Cyclomatic complexity a change may add to one function (None = no limit)
+    pub max_new_cc: Option<u32>,
+    /// Severity for the `max-new-cc` policy (default: Block)
+    pub max_new_cc_mode: PolicyMode,
+    /// New critical-band functions one change may add (None = no limit)
+    pub max_new_critical: Option<usize>,
+    /// Severity for the `max-new-critical` policy (default: Block)
+    pub max_new_critical_mode: PolicyMode,
+    /// New high-band functions one change may add (None = no limit)
+    pub max_new_high: Option<usize>,
+    /// Severity for the `max-new-high` policy (default: Block)
+    pub max_new_high_mode: PolicyMode,
+    /// Afferent coupling a stable module may reach (None = policy off)
+    pub max_stable_afferent: Option<usize>,
+    /// Severity for the `instability-regression` policy (default: Block)
+    pub instability_regression_mode: PolicyMode,
+    /// Dotted keys (e.g. `"weights.cc"`) that the config file set explicitly.
+    /// Everything else came from built-in defaults. See [`ResolvedConfig::setting_source`].
+    pub explicit_settings: std::collections::BTreeSet<String>,
+    /// Path the config was loaded from (None if defaults), or
+    /// `$HOTSPOTS_CONFIG` for inline config from that variable
     pub config_path: Option<PathBuf>,
 }
 
@@ -383,18 +782,68 @@ impl HotspotsConfig {
         if let Some(ref s) = self.scoring {
             validate_scoring(s)?;
         }
+        if let Some(ref f) = self.file_risk {
+            validate_file_risk(f)?;
+        }
+        if let Some(ref z) = self.module_zones {
+            validate_module_zones(z)?;
+        }
+        if let Some(ref b) = self.band_colors {
+            validate_band_colors(b)?;
+        }
+        if let Some(ref p) = self.priority {
+            validate_priority(p)?;
+        }
         if let Some(ref p) = self.patterns {
-            validate_pattern_thresholds(p)?;
+            validate_pattern_thresholds("patterns", p)?;
+            if p.long_function_loc.is_some() && self.max_function_loc.is_some() {
+                anyhow::bail!(
+                    "max_function_loc and patterns.long_function_loc both set the long_function threshold; keep only one"
+                );
+            }
+        }
+        for (kind, p) in &self.kind_thresholds {
+            validate_pattern_thresholds(&format!("kind_thresholds.{}", kind.as_str()), p)?;
+        }
+        validate_custom_patterns(&self.custom_patterns)?;
+        for rule in &self.suppress {
+            crate::suppression::SuppressRule::parse(
+                &rule.pattern,
+                rule.function.as_deref(),
+                &rule.reason,
+            )?;
+        }
+        for id in &self.exclude_functions {
+            let valid = id
+                .split_once("::")
+                .is_some_and(|(file, symbol)| !file.is_empty() && !symbol.is_empty());
+            if !valid {
+                anyhow::bail!(
+                    "exclude_functions entry \"{}\" must be a function ID like src/file.ts::name",
+                    id
+                );
+            }
         }
         if let Some(ref p) = self.policy {
             validate_policy_config(p)?;
         }
+        if let Some(ref i) = self.ignore_commits {
+            crate::git::IgnoredCommits::new(&i.revs, &i.messages)?;
+        }
         validate_scalar_fields(self)?;
+        parse_language_filter("include_languages", &self.include_languages)?;
+        parse_language_filter("exclude_languages", &self.exclude_languages)?;
         validate_glob_patterns(&self.include, &self.exclude)
     }
 }
 
 fn validate_scalar_fields(c: &HotspotsConfig) -> Result<()> {
+    if c.redact_salt
+        .as_deref()
+        .is_some_and(|s| s.trim().is_empty())
+    {
+        anyhow::bail!("redact_salt must not be empty");
+    }
     if let Some(min) = c.min_lrs {
         if min < 0.0 {
             anyhow::bail!("min_lrs must be non-negative (got {})", min);
@@ -410,6 +859,9 @@ fn validate_scalar_fields(c: &HotspotsConfig) -> Result<()> {
             anyhow::bail!("co_change_min_count must be at least 1");
         }
     }
+    if c.trends_min_snapshots == Some(0) {
+        anyhow::bail!("trends_min_snapshots must be at least 1");
+    }
     if let Some(p) = c.driver_threshold_percentile {
         if p == 0 || p >= 100 {
             anyhow::bail!(
@@ -423,9 +875,75 @@ fn validate_scalar_fields(c: &HotspotsConfig) -> Result<()> {
             anyhow::bail!("betweenness_approx_k must be at least 1");
         }
     }
+    if c.max_function_loc == Some(0) {
+        anyhow::bail!("max_function_loc must be at least 1");
+    }
+    if let Some(ref g) = c.granularity {
+        Granularity::parse(g)?;
+    }
+    if let Some(ref m) = c.rust_branching_macros {
+        branching_macro_names(m)?;
+    }
+    if let Some(ref m) = c.callgraph_method_resolution {
+        crate::callgraph::MethodResolution::parse(m)?;
+    }
+    if let Some(ref m) = c.co_change_metric {
+        crate::git::CoChangeMetric::parse(m)?;
+    }
+    if c.min_loc == Some(0) {
+        anyhow::bail!("min_loc must be at least 1");
+    }
+    if c.count_trivial == Some(false) && c.min_loc.is_none() && c.min_lrs.is_none() {
+        anyhow::bail!(
+            "count_trivial: false requires min_loc or min_lrs to define trivial functions"
+        );
+    }
     Ok(())
 }
 
+/// `rust_branching_macros` entries as bare macro names, with any trailing `!`
+/// dropped
+fn branching_macro_names(names: &[String]) -> Result<Vec<String>> {
+    names
+        .iter()
+        .map(|name| {
+            let bare = name.trim().trim_end_matches('!');
+            let valid = bare
+                .chars()
+                .next()
+                .is_some_and(|c| c.is_alphabetic() || c == '_')
+                && bare.chars().all(|c| c.is_alphanumeric() || c == '_');
+            if !valid {
+                anyhow::bail!(
+                    "rust_branching_macros entry \"{}\" must be a macro name like ensure",
+                    name
+                );
+            }
+            Ok(bare.to_string())
+        })
+        .collect()
+}
+
+/// Resolve language filter names (see [`Language::from_filter_name`]),
+/// deduplicated in first-seen order
+fn parse_language_filter(field: &str, names: &[String]) -> Result<Vec<Language>> {
+    let mut languages = Vec::new();
+    for name in names {
+        let family = Language::from_filter_name(name).with_context(|| {
+            format!(
+                "{field}: unknown language '{name}' (expected typescript, javascript, go, \
+                 java, python, rust, vue, svelte, csharp, c, or hcl)"
+            )
+        })?;
+        for language in family {
+            if !languages.contains(language) {
+                languages.push(*language);
+            }
+        }
+    }
+    Ok(languages)
+}
+
 fn validate_glob_patterns(include: &[String], exclude: &[String]) -> Result<()> {
     for pattern in include {
         Glob::new(pattern).with_context(|| format!("invalid include pattern: {}", pattern))?;
@@ -440,6 +958,16 @@ fn validate_thresholds(t: &ThresholdConfig) -> Result<()> {
     let moderate = t.moderate.unwrap_or(3.0);
     let high = t.high.unwrap_or(6.0);
     let critical = t.critical.unwrap_or(9.0);
+    // YAML accepts `.nan` and `.inf`, which would slip past the comparisons below
+    for (name, v) in [
+        ("moderate", moderate),
+        ("high", high),
+        ("critical", critical),
+    ] {
+        if !v.is_finite() {
+            anyhow::bail!("thresholds.{} must be a finite number (got {})", name, v);
+        }
+    }
     if moderate <= 0.0 {
         anyhow::bail!("thresholds.moderate must be positive (got {})", moderate);
     }
@@ -483,6 +1011,21 @@ fn validate_policy_config(p: &PolicyConfig) -> Result<()> {
             p.excessive_risk_regression_reason.as_deref(),
         )?;
     }
+    if let Some(ref s) = p.function_too_long {
+        PolicyMode::parse("function_too_long", s)?;
+    }
+    if let Some(ref s) = p.max_new_cc {
+        PolicyMode::parse("max_new_cc", s)?;
+    }
+    if let Some(ref s) = p.max_new_critical {
+        PolicyMode::parse("max_new_critical", s)?;
+    }
+    if let Some(ref s) = p.max_new_high {
+        PolicyMode::parse("max_new_high", s)?;
+    }
+    if let Some(ref s) = p.instability_regression {
+        PolicyMode::parse("instability_regression", s)?;
+    }
     Ok(())
 }
 
@@ -503,8 +1046,17 @@ fn require_reason_if_downgraded(field: &str, mode: PolicyMode, reason: Option<&s
 }
 
 fn validate_weights(w: &WeightConfig) -> Result<()> {
-    for (name, val) in [("cc", w.cc), ("nd", w.nd), ("fo", w.fo), ("ns", w.ns)] {
+    for (name, val) in [
+        ("cc", w.cc),
+        ("nd", w.nd),
+        ("fo", w.fo),
+        ("ns", w.ns),
+        ("unwrap", w.unwrap),
+    ] {
         if let Some(v) = val {
+            if !v.is_finite() {
+                anyhow::bail!("weights.{} must be a finite number (got {})", name, v);
+            }
             if v < 0.0 {
                 anyhow::bail!("weights.{} must be non-negative (got {})", name, v);
             }
@@ -513,6 +1065,14 @@ fn validate_weights(w: &WeightConfig) -> Result<()> {
             }
         }
     }
+    // `unwrap` only scores Rust, so the four structural weights must carry the score
+    let structural =
+        w.cc.unwrap_or(1.0) + w.nd.unwrap_or(0.8) + w.fo.unwrap_or(0.6) + w.ns.unwrap_or(0.7);
+    if structural < 1e-9 {
+        anyhow::bail!(
+            "weights.cc, weights.nd, weights.fo, and weights.ns are all 0; every function would score LRS 0 and land in the low band"
+        );
+    }
     Ok(())
 }
 
@@ -589,19 +1149,181 @@ fn validate_scoring(s: &ScoringWeightsConfig) -> Result<()> {
             }
         }
     }
+    if let Some(d) = s.heuristic_discount {
+        if !(0.0..=1.0).contains(&d) {
+            anyhow::bail!(
+                "scoring.heuristic_discount must be between 0.0 and 1.0 (got {})",
+                d
+            );
+        }
+    }
+    Ok(())
+}
+
+fn validate_file_risk(f: &FileRiskConfig) -> Result<()> {
+    for (name, val) in [
+        ("max_cc", f.max_cc),
+        ("avg_cc", f.avg_cc),
+        ("function_count", f.function_count),
+        ("churn", f.churn),
+    ] {
+        if let Some(v) = val {
+            if !v.is_finite() || v < 0.0 {
+                anyhow::bail!(
+                    "file_risk.{} must be a non-negative number (got {})",
+                    name,
+                    v
+                );
+            }
+        }
+    }
+    Ok(())
+}
+
+fn validate_priority(p: &PriorityConfig) -> Result<()> {
+    for (name, val) in [("recency", p.recency), ("churn", p.churn)] {
+        if let Some(v) = val {
+            if !v.is_finite() || v < 0.0 {
+                anyhow::bail!(
+                    "priority.{} must be a non-negative number (got {})",
+                    name,
+                    v
+                );
+            }
+        }
+    }
+    if let Some(c) = p.coverage {
+        if !(0.0..=1.0).contains(&c) {
+            anyhow::bail!("priority.coverage must be between 0.0 and 1.0 (got {})", c);
+        }
+    }
+    if let Some(h) = p.recency_half_life_days {
+        if !h.is_finite() || h <= 0.0 {
+            anyhow::bail!(
+                "priority.recency_half_life_days must be a positive number (got {})",
+                h
+            );
+        }
+    }
+    Ok(())
+}
+
+fn validate_band_colors(b: &BandColorsConfig) -> Result<()> {
+    let bands = [
+        ("low", &b.low),
+        ("moderate", &b.moderate),
+        ("high", &b.high),
+        ("critical", &b.critical),
+    ];
+    for (band, color) in bands {
+        let Some(color) = color else { continue };
+        let valid = color.strip_prefix('#').is_some_and(|hex| {
+            matches!(hex.len(), 3 | 6) && hex.chars().all(|c| c.is_ascii_hexdigit())
+        });
+        if !valid {
+            anyhow::bail!(
+                "band_colors.{} must be a hex colour like #ef4444 (got '{}')",
+                band,
+                color
+            );
+        }
+    }
     Ok(())
 }
 
-fn validate_pattern_thresholds(p: &PatternThresholdsConfig) -> Result<()> {
+fn validate_module_zones(z: &ModuleZonesConfig) -> Result<()> {
+    let defaults = crate::aggregates::ModuleZoneThresholds::default();
+    let stable = z.stable_below.unwrap_or(defaults.stable_below);
+    let volatile = z.volatile_above.unwrap_or(defaults.volatile_above);
+    if !(0.0..=1.0).contains(&stable) || !(0.0..=1.0).contains(&volatile) {
+        anyhow::bail!(
+            "module_zones.stable_below and module_zones.volatile_above must be between 0.0 and 1.0"
+        );
+    }
+    if stable > volatile {
+        anyhow::bail!(
+            "module_zones.stable_below ({}) must not exceed module_zones.volatile_above ({})",
+            stable,
+            volatile
+        );
+    }
+    if let Some(v) = z.pain_complexity {
+        if !v.is_finite() || v < 0.0 {
+            anyhow::bail!(
+                "module_zones.pain_complexity must be a non-negative number (got {})",
+                v
+            );
+        }
+    }
+    Ok(())
+}
+
+/// `base` with every threshold set in `p` replaced
+fn apply_pattern_thresholds(
+    base: &crate::patterns::Thresholds,
+    p: &PatternThresholdsConfig,
+) -> crate::patterns::Thresholds {
+    crate::patterns::Thresholds {
+        complex_branching_cc: p.complex_branching_cc.unwrap_or(base.complex_branching_cc),
+        complex_branching_nd: p.complex_branching_nd.unwrap_or(base.complex_branching_nd),
+        deeply_nested_nd: p.deeply_nested_nd.unwrap_or(base.deeply_nested_nd),
+        exit_heavy_ns: p.exit_heavy_ns.unwrap_or(base.exit_heavy_ns),
+        exit_heavy_points: p.exit_heavy_points.unwrap_or(base.exit_heavy_points),
+        exit_heavy_ratio_pct: p.exit_heavy_ratio_pct.unwrap_or(base.exit_heavy_ratio_pct),
+        fat_case_arm_loc: p.fat_case_arm_loc.unwrap_or(base.fat_case_arm_loc),
+        fat_case_ratio_pct: p.fat_case_ratio_pct.unwrap_or(base.fat_case_ratio_pct),
+        god_function_loc: p.god_function_loc.unwrap_or(base.god_function_loc),
+        god_function_fo: p.god_function_fo.unwrap_or(base.god_function_fo),
+        long_function_loc: p.long_function_loc.unwrap_or(base.long_function_loc),
+        // An explicit `long_function_loc` replaces an inherited hard limit
+        max_function_loc: base
+            .max_function_loc
+            .filter(|_| p.long_function_loc.is_none()),
+        nested_literal_depth: p.nested_literal_depth.unwrap_or(base.nested_literal_depth),
+        panic_prone_unwraps: p.panic_prone_unwraps.unwrap_or(base.panic_prone_unwraps),
+        churn_magnet_churn: p.churn_magnet_churn.unwrap_or(base.churn_magnet_churn),
+        churn_magnet_cc: p.churn_magnet_cc.unwrap_or(base.churn_magnet_cc),
+        cyclic_hub_scc: p.cyclic_hub_scc.unwrap_or(base.cyclic_hub_scc),
+        cyclic_hub_fan_in: p.cyclic_hub_fan_in.unwrap_or(base.cyclic_hub_fan_in),
+        hub_function_fan_in: p.hub_function_fan_in.unwrap_or(base.hub_function_fan_in),
+        hub_function_cc: p.hub_function_cc.unwrap_or(base.hub_function_cc),
+        middle_man_fan_in: p.middle_man_fan_in.unwrap_or(base.middle_man_fan_in),
+        middle_man_fo: p.middle_man_fo.unwrap_or(base.middle_man_fo),
+        middle_man_cc_max: p.middle_man_cc_max.unwrap_or(base.middle_man_cc_max),
+        neighbor_risk_churn: p.neighbor_risk_churn.unwrap_or(base.neighbor_risk_churn),
+        neighbor_risk_fo: p.neighbor_risk_fo.unwrap_or(base.neighbor_risk_fo),
+        shotgun_target_fan_in: p
+            .shotgun_target_fan_in
+            .unwrap_or(base.shotgun_target_fan_in),
+        shotgun_target_churn: p.shotgun_target_churn.unwrap_or(base.shotgun_target_churn),
+        stale_complex_cc: p.stale_complex_cc.unwrap_or(base.stale_complex_cc),
+        stale_complex_loc: p.stale_complex_loc.unwrap_or(base.stale_complex_loc),
+        stale_complex_days: p.stale_complex_days.unwrap_or(base.stale_complex_days),
+        risky_untested_lrs: p.risky_untested_lrs.unwrap_or(base.risky_untested_lrs),
+        risky_untested_coverage_pct: p
+            .risky_untested_coverage_pct
+            .unwrap_or(base.risky_untested_coverage_pct),
+        custom: base.custom.clone(),
+        by_kind: std::collections::BTreeMap::new(),
+    }
+}
+
+fn validate_pattern_thresholds(section: &str, p: &PatternThresholdsConfig) -> Result<()> {
     // All thresholds must be at least 1 when specified
     let usize_fields: &[(&str, Option<usize>)] = &[
         ("complex_branching_cc", p.complex_branching_cc),
         ("complex_branching_nd", p.complex_branching_nd),
         ("deeply_nested_nd", p.deeply_nested_nd),
         ("exit_heavy_ns", p.exit_heavy_ns),
+        ("exit_heavy_points", p.exit_heavy_points),
+        ("exit_heavy_ratio_pct", p.exit_heavy_ratio_pct),
+        ("fat_case_arm_loc", p.fat_case_arm_loc),
+        ("fat_case_ratio_pct", p.fat_case_ratio_pct),
         ("god_function_loc", p.god_function_loc),
         ("god_function_fo", p.god_function_fo),
         ("long_function_loc", p.long_function_loc),
+        ("nested_literal_depth", p.nested_literal_depth),
+        ("panic_prone_unwraps", p.panic_prone_unwraps),
         ("churn_magnet_churn", p.churn_magnet_churn),
         ("churn_magnet_cc", p.churn_magnet_cc),
         ("cyclic_hub_scc", p.cyclic_hub_scc),
@@ -617,49 +1339,107 @@ fn validate_pattern_thresholds(p: &PatternThresholdsConfig) -> Result<()> {
         ("shotgun_target_churn", p.shotgun_target_churn),
         ("stale_complex_cc", p.stale_complex_cc),
         ("stale_complex_loc", p.stale_complex_loc),
+        ("risky_untested_lrs", p.risky_untested_lrs),
+        ("risky_untested_coverage_pct", p.risky_untested_coverage_pct),
     ];
     for (name, val) in usize_fields {
         if let Some(v) = val {
             if *v == 0 {
-                anyhow::bail!("patterns.{} must be at least 1 (got 0)", name);
+                anyhow::bail!("{}.{} must be at least 1 (got 0)", section, name);
             }
         }
     }
     if let Some(v) = p.stale_complex_days {
         if v == 0 {
-            anyhow::bail!("patterns.stale_complex_days must be at least 1 (got 0)");
+            anyhow::bail!("{section}.stale_complex_days must be at least 1 (got 0)");
         }
     }
+    if let Some(v) = p.risky_untested_coverage_pct {
+        if v > 100 {
+            anyhow::bail!("{section}.risky_untested_coverage_pct must be at most 100 (got {v})");
+        }
+    }
+    Ok(())
+}
+
+fn validate_custom_patterns(patterns: &[CustomPatternConfig]) -> Result<()> {
+    let mut seen = std::collections::HashSet::new();
+    for p in patterns {
+        let valid_name = !p.name.is_empty()
+            && p.name
+                .chars()
+                .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_');
+        if !valid_name {
+            anyhow::bail!(
+                "custom_patterns name \"{}\" must be non-empty and use only lowercase letters, digits, and '_'",
+                p.name
+            );
+        }
+        if crate::patterns::BUILTIN_PATTERNS.contains(&p.name.as_str()) {
+            anyhow::bail!(
+                "custom_patterns name \"{}\" collides with a built-in pattern",
+                p.name
+            );
+        }
+        if !seen.insert(p.name.as_str()) {
+            anyhow::bail!("custom_patterns name \"{}\" is defined twice", p.name);
+        }
+        crate::patterns::parse_conditions(&p.condition)
+            .map_err(|e| anyhow::anyhow!("custom_patterns.{}: {}", p.name, e))?;
+    }
     Ok(())
 }
 
+/// Whether `path` follows one of the built-in test-file conventions
+/// (`*.test.ts`, `*.spec.js`, `__tests__/`, `test_*.py`, `*_test.go`, ...).
+pub fn is_test_file(path: &Path) -> bool {
+    static TEST_FILES: std::sync::OnceLock<GlobSet> = std::sync::OnceLock::new();
+    TEST_FILES
+        .get_or_init(|| {
+            let mut builder = GlobSetBuilder::new();
+            for pattern in TEST_FILE_PATTERNS {
+                builder.add(Glob::new(pattern).expect("built-in test pattern is valid"));
+            }
+            builder.build().expect("built-in test patterns compile")
+        })
+        .is_match(path)
+}
+
+/// Compile include/exclude globs into matchers.
+///
+/// Empty `include` means include all. [`TEST_FILE_PATTERNS`] and
+/// [`DEFAULT_EXCLUDES`] always apply; user
+/// exclude patterns are additive.
+fn compile_path_filters(
+    include: &[String],
+    exclude: &[String],
+) -> Result<(Option<GlobSet>, GlobSet)> {
+    let include_set = if include.is_empty() {
+        None
+    } else {
+        let mut builder = GlobSetBuilder::new();
+        for pattern in include {
+            builder.add(Glob::new(pattern)?);
+        }
+        Some(builder.build()?)
+    };
+
+    let mut builder = GlobSetBuilder::new();
+    for pattern in TEST_FILE_PATTERNS.iter().chain(DEFAULT_EXCLUDES) {
+        builder.add(Glob::new(pattern)?);
+    }
+    for pattern in exclude {
+        builder.add(Glob::new(pattern)?);
+    }
+    Ok((include_set, builder.build()?))
+}
+
 impl HotspotsConfig {
     /// Resolve config into compiled form ready for use
     pub fn resolve(&self) -> Result<ResolvedConfig> {
         self.validate()?;
 
-        // Compile include patterns
-        let include = if self.include.is_empty() {
-            None
-        } else {
-            let mut builder = GlobSetBuilder::new();
-            for pattern in &self.include {
-                builder.add(Glob::new(pattern)?);
-            }
-            Some(builder.build()?)
-        };
-
-        // Compile exclude patterns: defaults always apply; user patterns are additive.
-        let exclude = {
-            let mut builder = GlobSetBuilder::new();
-            for pattern in DEFAULT_EXCLUDES {
-                builder.add(Glob::new(pattern)?);
-            }
-            for pattern in &self.exclude {
-                builder.add(Glob::new(pattern)?);
-            }
-            builder.build()?
-        };
+        let (include, exclude) = compile_path_filters(&self.include, &self.exclude)?;
 
         let (moderate, high, critical) = match &self.thresholds {
             Some(t) => (
@@ -670,14 +1450,15 @@ impl HotspotsConfig {
             None => (3.0, 6.0, 9.0),
         };
 
-        let (w_cc, w_nd, w_fo, w_ns) = match &self.weights {
+        let (w_cc, w_nd, w_fo, w_ns, w_unwrap) = match &self.weights {
             Some(w) => (
                 w.cc.unwrap_or(1.0),
                 w.nd.unwrap_or(0.8),
                 w.fo.unwrap_or(0.6),
                 w.ns.unwrap_or(0.7),
+                w.unwrap.unwrap_or(0.0),
             ),
-            None => (1.0, 0.8, 0.6, 0.7),
+            None => (1.0, 0.8, 0.6, 0.7, 0.0),
         };
 
         let (watch_min, watch_max, attention_min, attention_max, rapid_growth_percent) =
@@ -704,51 +1485,104 @@ impl HotspotsConfig {
                     depth: s.depth.unwrap_or(defaults.depth),
                     neighbor_churn: s.neighbor_churn.unwrap_or(defaults.neighbor_churn),
                     burst: s.burst.unwrap_or(defaults.burst),
+                    heuristic_discount: s.heuristic_discount.unwrap_or(defaults.heuristic_discount),
                 }
             }
             None => crate::scoring::ScoringWeights::default(),
         };
 
-        let pattern_thresholds = match &self.patterns {
+        let file_risk_weights = match &self.file_risk {
+            Some(f) => {
+                let defaults = crate::aggregates::FileRiskWeights::default();
+                crate::aggregates::FileRiskWeights {
+                    max_cc: f.max_cc.unwrap_or(defaults.max_cc),
+                    avg_cc: f.avg_cc.unwrap_or(defaults.avg_cc),
+                    function_count: f.function_count.unwrap_or(defaults.function_count),
+                    churn: f.churn.unwrap_or(defaults.churn),
+                }
+            }
+            None => crate::aggregates::FileRiskWeights::default(),
+        };
+
+        let priority_weights = match &self.priority {
             Some(p) => {
-                let d = crate::patterns::Thresholds::default();
-                crate::patterns::Thresholds {
-                    complex_branching_cc: p.complex_branching_cc.unwrap_or(d.complex_branching_cc),
-                    complex_branching_nd: p.complex_branching_nd.unwrap_or(d.complex_branching_nd),
-                    deeply_nested_nd: p.deeply_nested_nd.unwrap_or(d.deeply_nested_nd),
-                    exit_heavy_ns: p.exit_heavy_ns.unwrap_or(d.exit_heavy_ns),
-                    god_function_loc: p.god_function_loc.unwrap_or(d.god_function_loc),
-                    god_function_fo: p.god_function_fo.unwrap_or(d.god_function_fo),
-                    long_function_loc: p.long_function_loc.unwrap_or(d.long_function_loc),
-                    churn_magnet_churn: p.churn_magnet_churn.unwrap_or(d.churn_magnet_churn),
-                    churn_magnet_cc: p.churn_magnet_cc.unwrap_or(d.churn_magnet_cc),
-                    cyclic_hub_scc: p.cyclic_hub_scc.unwrap_or(d.cyclic_hub_scc),
-                    cyclic_hub_fan_in: p.cyclic_hub_fan_in.unwrap_or(d.cyclic_hub_fan_in),
-                    hub_function_fan_in: p.hub_function_fan_in.unwrap_or(d.hub_function_fan_in),
-                    hub_function_cc: p.hub_function_cc.unwrap_or(d.hub_function_cc),
-                    middle_man_fan_in: p.middle_man_fan_in.unwrap_or(d.middle_man_fan_in),
-                    middle_man_fo: p.middle_man_fo.unwrap_or(d.middle_man_fo),
-                    middle_man_cc_max: p.middle_man_cc_max.unwrap_or(d.middle_man_cc_max),
-                    neighbor_risk_churn: p.neighbor_risk_churn.unwrap_or(d.neighbor_risk_churn),
-                    neighbor_risk_fo: p.neighbor_risk_fo.unwrap_or(d.neighbor_risk_fo),
-                    shotgun_target_fan_in: p
-                        .shotgun_target_fan_in
-                        .unwrap_or(d.shotgun_target_fan_in),
-                    shotgun_target_churn: p.shotgun_target_churn.unwrap_or(d.shotgun_target_churn),
-                    stale_complex_cc: p.stale_complex_cc.unwrap_or(d.stale_complex_cc),
-                    stale_complex_loc: p.stale_complex_loc.unwrap_or(d.stale_complex_loc),
-                    stale_complex_days: p.stale_complex_days.unwrap_or(d.stale_complex_days),
+                let defaults = crate::scoring::PriorityWeights::default();
+                crate::scoring::PriorityWeights {
+                    coverage: p.coverage.unwrap_or(defaults.coverage),
+                    recency: p.recency.unwrap_or(defaults.recency),
+                    churn: p.churn.unwrap_or(defaults.churn),
+                    recency_half_life_days: p
+                        .recency_half_life_days
+                        .unwrap_or(defaults.recency_half_life_days),
                 }
             }
-            None => crate::patterns::Thresholds::default(),
+            None => crate::scoring::PriorityWeights::default(),
         };
 
-        let (
-            critical_introduction_mode,
-            critical_introduction_reason,
-            excessive_risk_regression_mode,
-            excessive_risk_regression_reason,
-        ) = match &self.policy {
+        let module_zones = match &self.module_zones {
+            Some(z) => {
+                let defaults = crate::aggregates::ModuleZoneThresholds::default();
+                crate::aggregates::ModuleZoneThresholds {
+                    stable_below: z.stable_below.unwrap_or(defaults.stable_below),
+                    volatile_above: z.volatile_above.unwrap_or(defaults.volatile_above),
+                    pain_complexity: z.pain_complexity.unwrap_or(defaults.pain_complexity),
+                }
+            }
+            None => crate::aggregates::ModuleZoneThresholds::default(),
+        };
+
+        let band_colors = {
+            let defaults = crate::html::BandColors::default();
+            match &self.band_colors {
+                Some(b) => crate::html::BandColors {
+                    low: b.low.clone().unwrap_or(defaults.low),
+                    moderate: b.moderate.clone().unwrap_or(defaults.moderate),
+                    high: b.high.clone().unwrap_or(defaults.high),
+                    critical: b.critical.clone().unwrap_or(defaults.critical),
+                },
+                None => defaults,
+            }
+        };
+
+        let custom_patterns = self
+            .custom_patterns
+            .iter()
+            .map(|p| {
+                crate::patterns::CustomPattern::parse(&p.name, &p.condition, p.description.clone())
+                    .map_err(|e| anyhow::anyhow!("custom_patterns.{}: {}", p.name, e))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let suppress_rules = self
+            .suppress
+            .iter()
+            .map(|r| {
+                crate::suppression::SuppressRule::parse(
+                    &r.pattern,
+                    r.function.as_deref(),
+                    &r.reason,
+                )
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let mut pattern_thresholds = match &self.patterns {
+            Some(p) => apply_pattern_thresholds(&crate::patterns::Thresholds::default(), p),
+            None => crate::patterns::Thresholds::default(),
+        };
+        pattern_thresholds.custom = custom_patterns;
+        // Each kind starts from the resolved base, so it only lists what differs
+        pattern_thresholds.by_kind = self
+            .kind_thresholds
+            .iter()
+            .map(|(kind, p)| (*kind, apply_pattern_thresholds(&pattern_thresholds, p)))
+            .collect();
+
+        let (
+            critical_introduction_mode,
+            critical_introduction_reason,
+            excessive_risk_regression_mode,
+            excessive_risk_regression_reason,
+        ) = match &self.policy {
             Some(p) => (
                 match &p.critical_introduction {
                     Some(s) => PolicyMode::parse("critical_introduction", s)?,
@@ -763,10 +1597,72 @@ impl HotspotsConfig {
             ),
             None => (PolicyMode::Block, None, PolicyMode::Block, None),
         };
+        let function_too_long_mode = match self
+            .policy
+            .as_ref()
+            .and_then(|p| p.function_too_long.as_deref())
+        {
+            Some(s) => PolicyMode::parse("function_too_long", s)?,
+            None => PolicyMode::Warn,
+        };
+        let max_new_cc_mode = match self.policy.as_ref().and_then(|p| p.max_new_cc.as_deref()) {
+            Some(s) => PolicyMode::parse("max_new_cc", s)?,
+            None => PolicyMode::Block,
+        };
+        let max_new_critical_mode = match self
+            .policy
+            .as_ref()
+            .and_then(|p| p.max_new_critical.as_deref())
+        {
+            Some(s) => PolicyMode::parse("max_new_critical", s)?,
+            None => PolicyMode::Block,
+        };
+        let max_new_high_mode = match self.policy.as_ref().and_then(|p| p.max_new_high.as_deref()) {
+            Some(s) => PolicyMode::parse("max_new_high", s)?,
+            None => PolicyMode::Block,
+        };
+        let instability_regression_mode = match self
+            .policy
+            .as_ref()
+            .and_then(|p| p.instability_regression.as_deref())
+        {
+            Some(s) => PolicyMode::parse("instability_regression", s)?,
+            None => PolicyMode::Block,
+        };
+
+        let mut explicit_settings = std::collections::BTreeSet::new();
+        if let Some(t) = &self.thresholds {
+            for (key, val) in [
+                ("moderate", t.moderate),
+                ("high", t.high),
+                ("critical", t.critical),
+            ] {
+                if val.is_some() {
+                    explicit_settings.insert(format!("thresholds.{key}"));
+                }
+            }
+        }
+        if let Some(w) = &self.weights {
+            for (key, val) in [
+                ("cc", w.cc),
+                ("nd", w.nd),
+                ("fo", w.fo),
+                ("ns", w.ns),
+                ("unwrap", w.unwrap),
+            ] {
+                if val.is_some() {
+                    explicit_settings.insert(format!("weights.{key}"));
+                }
+            }
+        }
 
-        Ok(ResolvedConfig {
+        let mut resolved = ResolvedConfig {
             include,
             exclude,
+            include_patterns: self.include.clone(),
+            exclude_patterns: self.exclude.clone(),
+            include_languages: parse_language_filter("include_languages", &self.include_languages)?,
+            exclude_languages: parse_language_filter("exclude_languages", &self.exclude_languages)?,
             moderate_threshold: moderate,
             high_threshold: high,
             critical_threshold: critical,
@@ -774,34 +1670,203 @@ impl HotspotsConfig {
             weight_nd: w_nd,
             weight_fo: w_fo,
             weight_ns: w_ns,
+            weight_unwrap: w_unwrap,
             watch_min,
             watch_max,
             attention_min,
             attention_max,
             rapid_growth_percent,
-            min_lrs: self.min_lrs,
+            // Under `count_trivial: false`, `min_lrs` marks functions trivial
+            // instead of dropping them from the results
+            min_lrs: self.min_lrs.filter(|_| self.count_trivial != Some(false)),
             top_n: self.top,
             scoring_weights,
+            file_risk_weights,
+            priority_weights,
+            module_zones,
+            band_colors,
             pattern_thresholds,
+            suppress_rules,
+            exclude_functions: self.exclude_functions.clone(),
+            redact_salt: self.redact_salt.clone(),
             critical_introduction_mode,
             critical_introduction_reason,
             excessive_risk_regression_mode,
             excessive_risk_regression_reason,
+            function_too_long_mode,
+            max_new_cc: self.max_new_cc,
+            max_new_cc_mode,
+            max_new_critical: self.max_new_critical,
+            max_new_critical_mode,
+            max_new_high: self.max_new_high,
+            max_new_high_mode,
+            max_stable_afferent: self.max_stable_afferent,
+            instability_regression_mode,
             co_change_window_days: self.co_change_window_days.unwrap_or(90),
             co_change_min_count: self.co_change_min_count.unwrap_or(3),
+            co_change_weighted: self.co_change_weighted.unwrap_or(true),
+            co_change_metric: match self.co_change_metric.as_deref() {
+                Some(m) => crate::git::CoChangeMetric::parse(m)?,
+                None => crate::git::CoChangeMetric::Min,
+            },
+            trends_min_snapshots: self
+                .trends_min_snapshots
+                .unwrap_or(crate::trends::DEFAULT_MIN_SNAPSHOTS),
             per_function_touches: self.per_function_touches.unwrap_or(false),
             hybrid_touch_threshold: self.hybrid_touch_threshold,
+            ignore_commits: match &self.ignore_commits {
+                Some(i) => crate::git::IgnoredCommits::new(&i.revs, &i.messages)?,
+                None => crate::git::IgnoredCommits::default(),
+            },
             driver_threshold_percentile: self.driver_threshold_percentile.unwrap_or(75),
             betweenness_exact_threshold: self.betweenness_exact_threshold.unwrap_or(2000),
             betweenness_approx_k: self.betweenness_approx_k.unwrap_or(256),
             callgraph_skip_above: self.callgraph_skip_above.unwrap_or(usize::MAX),
+            callgraph_method_resolution: match self.callgraph_method_resolution.as_deref() {
+                Some(m) => crate::callgraph::MethodResolution::parse(m)?,
+                None => crate::callgraph::MethodResolution::Receiver,
+            },
+            callgraph_include_anonymous: self.callgraph_include_anonymous.unwrap_or(true),
+            max_file_bytes: self.max_file_bytes.unwrap_or(DEFAULT_MAX_FILE_BYTES),
+            skip_anonymous_functions: self.skip_anonymous_functions.unwrap_or(false),
+            require_functions: self.require_functions.unwrap_or(false),
+            granularity: match self.granularity.as_deref() {
+                Some(g) => Granularity::parse(g)?,
+                None => Granularity::Function,
+            },
+            rust_branching_macros: self
+                .rust_branching_macros
+                .as_deref()
+                .map(branching_macro_names)
+                .transpose()?,
+            max_function_loc: None,
+            trivial: if self.count_trivial == Some(false) {
+                crate::snapshot::TrivialCutoff {
+                    min_loc: self.min_loc,
+                    min_lrs: self.min_lrs,
+                }
+            } else {
+                crate::snapshot::TrivialCutoff::default()
+            },
+            explicit_settings,
             config_path: None,
-        })
+        };
+        if let Some(max_loc) = self.max_function_loc {
+            resolved.set_max_function_loc(max_loc);
+        }
+        Ok(resolved)
     }
 }
 
 impl ResolvedConfig {
-    /// Check if a file path should be included based on include/exclude patterns
+    /// Serialize the resolved configuration to pretty-printed JSON
+    pub fn to_json(&self) -> Result<String> {
+        serde_json::to_string_pretty(self).context("failed to serialize configuration to JSON")
+    }
+
+    /// LRS weights from the `weights` section
+    pub fn lrs_weights(&self) -> crate::risk::LrsWeights {
+        crate::risk::LrsWeights {
+            cc: self.weight_cc,
+            nd: self.weight_nd,
+            fo: self.weight_fo,
+            ns: self.weight_ns,
+            unwrap: self.weight_unwrap,
+        }
+    }
+
+    /// Band cutoffs from the `thresholds` section
+    pub fn risk_thresholds(&self) -> crate::risk::RiskThresholds {
+        crate::risk::RiskThresholds {
+            moderate: self.moderate_threshold,
+            high: self.high_threshold,
+            critical: self.critical_threshold,
+        }
+    }
+
+    /// Co-change mining parameters from the `co_change_*` keys
+    pub fn co_change_options(&self) -> crate::git::CoChangeOptions {
+        crate::git::CoChangeOptions {
+            window_days: self.co_change_window_days,
+            min_count: self.co_change_min_count,
+            weighted: self.co_change_weighted,
+            metric: self.co_change_metric,
+        }
+    }
+
+    /// Set the hard function length limit (config `max_function_loc` or
+    /// `--max-function-loc`).
+    ///
+    /// Functions with more than `max_loc` lines are flagged `long_function`,
+    /// whatever `patterns.long_function_loc` says. A `kind_thresholds` entry
+    /// with a `long_function_loc` of its own keeps it as that kind's limit.
+    pub fn set_max_function_loc(&mut self, max_loc: usize) {
+        self.max_function_loc = Some(max_loc);
+        let inherited = self.pattern_thresholds.long_function_loc;
+        self.pattern_thresholds.max_function_loc = Some(max_loc);
+        for thresholds in self.pattern_thresholds.by_kind.values_mut() {
+            if thresholds.long_function_loc == inherited {
+                thresholds.max_function_loc = Some(max_loc);
+            }
+        }
+    }
+
+    /// Length limit the `function-too-long` policy applies to a function of
+    /// `kind`, the same one the `long_function` pattern uses (see
+    /// [`Thresholds::function_loc_limit`](crate::patterns::Thresholds::function_loc_limit)).
+    /// `None` when `max_function_loc` is unset.
+    pub fn function_loc_limit(&self, kind: Option<crate::ast::FunctionKind>) -> Option<usize> {
+        self.max_function_loc?;
+        Some(self.pattern_thresholds.for_kind(kind).function_loc_limit())
+    }
+
+    /// Apply command-line `--include`/`--exclude` globs on top of the config file's filters.
+    ///
+    /// By default the patterns are appended to the configured lists; with `replace`
+    /// they replace them instead. Built-in default excludes apply either way.
+    pub fn apply_path_filter_overrides(
+        &mut self,
+        include: &[String],
+        exclude: &[String],
+        replace: bool,
+    ) -> Result<()> {
+        validate_glob_patterns(include, exclude)?;
+        if replace {
+            self.include_patterns = include.to_vec();
+            self.exclude_patterns = exclude.to_vec();
+        } else {
+            self.include_patterns.extend_from_slice(include);
+            self.exclude_patterns.extend_from_slice(exclude);
+        }
+        let (include_set, exclude_set) =
+            compile_path_filters(&self.include_patterns, &self.exclude_patterns)?;
+        self.include = include_set;
+        self.exclude = exclude_set;
+        Ok(())
+    }
+
+    /// Apply command-line `--include-lang`/`--exclude-lang` names on top of the
+    /// config file's language lists, which they are appended to.
+    pub fn apply_language_filter_overrides(
+        &mut self,
+        include: &[String],
+        exclude: &[String],
+    ) -> Result<()> {
+        for language in parse_language_filter("--include-lang", include)? {
+            if !self.include_languages.contains(&language) {
+                self.include_languages.push(language);
+            }
+        }
+        for language in parse_language_filter("--exclude-lang", exclude)? {
+            if !self.exclude_languages.contains(&language) {
+                self.exclude_languages.push(language);
+            }
+        }
+        Ok(())
+    }
+
+    /// Check if a file path should be included based on include/exclude
+    /// patterns and the language filters
     pub fn should_include(&self, path: &Path) -> bool {
         let path_str = path.to_string_lossy();
 
@@ -810,6 +1875,18 @@ impl ResolvedConfig {
             return false;
         }
 
+        if !self.include_languages.is_empty() || !self.exclude_languages.is_empty() {
+            let Some(language) = Language::from_path(path) else {
+                return false;
+            };
+            if self.exclude_languages.contains(&language)
+                || (!self.include_languages.is_empty()
+                    && !self.include_languages.contains(&language))
+            {
+                return false;
+            }
+        }
+
         // If include patterns exist, file must match at least one
         if let Some(ref include) = self.include {
             return include.is_match(path_str.as_ref());
@@ -818,6 +1895,28 @@ impl ResolvedConfig {
         true
     }
 
+    /// Check if a function is listed in `exclude_functions`.
+    ///
+    /// Entries are `file::symbol` with `file` relative to the project root. The
+    /// symbol must match exactly; the file matches as a whole-component path
+    /// suffix, since analyzed paths may be absolute or `./`-prefixed.
+    pub fn is_function_excluded(&self, file: &str, function: &str) -> bool {
+        let path = Path::new(file);
+        self.exclude_functions.iter().any(|id| {
+            id.split_once("::")
+                .is_some_and(|(f, symbol)| symbol == function && path.ends_with(f))
+        })
+    }
+
+    /// Describe where a setting's value came from: the config file path when
+    /// the file set `key` explicitly, otherwise `"default"`.
+    pub fn setting_source(&self, key: &str) -> String {
+        match &self.config_path {
+            Some(path) if self.explicit_settings.contains(key) => path.display().to_string(),
+            _ => "default".to_string(),
+        }
+    }
+
     /// Build a ResolvedConfig with all defaults (no config file)
     pub fn defaults() -> Result<Self> {
         HotspotsConfig::default().resolve()
@@ -829,7 +1928,8 @@ impl ResolvedConfig {
 /// Search order:
 /// 1. `.hotspotsrc.json`
 /// 2. `hotspots.config.json`
-/// 3. `"hotspots"` key in `package.json`
+/// 3. `.hotspots.yaml`, then `.hotspots.yml`
+/// 4. `"hotspots"` key in `package.json`
 ///
 /// Returns `None` if no config file is found (use defaults).
 pub fn discover_config(project_root: &Path) -> Result<Option<(HotspotsConfig, PathBuf)>> {
@@ -847,7 +1947,16 @@ pub fn discover_config(project_root: &Path) -> Result<Option<(HotspotsConfig, Pa
         return Ok(Some((config, config_path)));
     }
 
-    // 3. package.json "hotspots" key
+    // 3. .hotspots.yaml / .hotspots.yml
+    for name in [".hotspots.yaml", ".hotspots.yml"] {
+        let yaml_path = project_root.join(name);
+        if yaml_path.exists() {
+            let config = load_config_file(&yaml_path)?;
+            return Ok(Some((config, yaml_path)));
+        }
+    }
+
+    // 4. package.json "hotspots" key
     let pkg_path = project_root.join("package.json");
     if pkg_path.exists() {
         if let Some(config) = load_from_package_json(&pkg_path)? {
@@ -859,12 +1968,23 @@ pub fn discover_config(project_root: &Path) -> Result<Option<(HotspotsConfig, Pa
 }
 
 /// Load config from an explicit file path
+///
+/// Files ending in `.yaml` or `.yml` are parsed as YAML; anything else as JSON.
 pub fn load_config_file(path: &Path) -> Result<HotspotsConfig> {
     let content = std::fs::read_to_string(path)
         .with_context(|| format!("failed to read config file: {}", path.display()))?;
 
-    let config: HotspotsConfig = serde_json::from_str(&content)
-        .with_context(|| format!("failed to parse config file: {}", path.display()))?;
+    let is_yaml = matches!(
+        path.extension().and_then(|e| e.to_str()),
+        Some("yaml" | "yml")
+    );
+    let config: HotspotsConfig = if is_yaml {
+        serde_norway::from_str(&content)
+            .with_context(|| format!("failed to parse config file: {}", path.display()))?
+    } else {
+        serde_json::from_str(&content)
+            .with_context(|| format!("failed to parse config file: {}", path.display()))?
+    };
 
     config
         .validate()
@@ -894,17 +2014,47 @@ fn load_from_package_json(path: &Path) -> Result<Option<HotspotsConfig>> {
     }
 }
 
+/// Environment variable holding a config file path or inline config
+pub const CONFIG_ENV_VAR: &str = "HOTSPOTS_CONFIG";
+
+/// Load config from the value of [`CONFIG_ENV_VAR`]
+///
+/// A value naming an existing file loads that file like `--config`; anything
+/// else is parsed as inline JSON or YAML (YAML being a superset of JSON), so CI
+/// can inject config without writing a file. An empty value counts as unset.
+pub fn load_env_config(value: Option<&str>) -> Result<Option<(HotspotsConfig, PathBuf)>> {
+    let Some(value) = value.filter(|v| !v.trim().is_empty()) else {
+        return Ok(None);
+    };
+    let path = Path::new(value);
+    if path.is_file() {
+        return Ok(Some((load_config_file(path)?, path.to_path_buf())));
+    }
+    let config: HotspotsConfig = serde_norway::from_str(value).with_context(|| {
+        format!("{CONFIG_ENV_VAR} is neither an existing file nor inline JSON/YAML config")
+    })?;
+    config
+        .validate()
+        .with_context(|| format!("invalid config in {CONFIG_ENV_VAR}"))?;
+    Ok(Some((config, PathBuf::from(format!("${CONFIG_ENV_VAR}")))))
+}
+
 /// Load and resolve config for a project
 ///
-/// If `config_path` is provided, loads from that file.
-/// Otherwise, discovers config from the project root.
-/// Returns default config if nothing is found.
+/// If `config_path` is provided, loads from that file. Otherwise, uses
+/// [`CONFIG_ENV_VAR`] when set (see [`load_env_config`]), then discovers
+/// config from the project root. Returns default config if nothing is found.
 pub fn load_and_resolve(project_root: &Path, config_path: Option<&Path>) -> Result<ResolvedConfig> {
     let (config, source_path) = if let Some(path) = config_path {
         let config = load_config_file(path)?;
         (config, Some(path.to_path_buf()))
     } else {
-        match discover_config(project_root)? {
+        let env_value = std::env::var(CONFIG_ENV_VAR).ok();
+        let found = match load_env_config(env_value.as_deref())? {
+            Some(found) => Some(found),
+            None => discover_config(project_root)?,
+        };
+        match found {
             Some((config, path)) => (config, Some(path)),
             None => (HotspotsConfig::default(), None),
         }
@@ -973,6 +2123,16 @@ mod tests {
         assert_eq!(resolved.top_n, Some(20));
     }
 
+    #[test]
+    fn test_is_test_file() {
+        assert!(is_test_file(Path::new("src/api/users.test.ts")));
+        assert!(is_test_file(Path::new("/repo/pkg/server_test.go")));
+        assert!(is_test_file(Path::new("app/__tests__/render.js")));
+        assert!(is_test_file(Path::new("lib/test_parser.py")));
+        assert!(!is_test_file(Path::new("src/api/users.ts")));
+        assert!(!is_test_file(Path::new("src/testing.py")));
+    }
+
     #[test]
     fn test_reject_unknown_fields() {
         let json = r#"{"unknown_field": true}"#;
@@ -980,6 +2140,16 @@ mod tests {
         assert!(result.is_err(), "unknown fields should be rejected");
     }
 
+    #[test]
+    fn test_reject_unknown_nested_field_names_key() {
+        let json = r#"{"weights": {"weigth_cc": 1.0}}"#;
+        let err = serde_json::from_str::<HotspotsConfig>(json).unwrap_err();
+        assert!(
+            err.to_string().contains("weigth_cc"),
+            "error should name the misspelled key: {err}"
+        );
+    }
+
     #[test]
     fn test_reject_negative_weight() {
         let json = r#"{"weights": {"cc": -1.0}}"#;
@@ -994,6 +2164,27 @@ mod tests {
         assert!(config.validate().is_err());
     }
 
+    #[test]
+    fn test_reject_all_zero_structural_weights() {
+        let json = r#"{"weights": {"cc": 0.0, "nd": 0.0, "fo": 0.0, "ns": 0.0, "unwrap": 1.0}}"#;
+        let config: HotspotsConfig = serde_json::from_str(json).unwrap();
+        let err = config.validate().unwrap_err();
+        assert!(err.to_string().contains("all 0"), "{err}");
+
+        let json = r#"{"weights": {"cc": 0.0, "nd": 0.0, "fo": 0.0}}"#;
+        let config: HotspotsConfig = serde_json::from_str(json).unwrap();
+        assert!(config.validate().is_ok(), "ns keeps its default weight");
+    }
+
+    #[test]
+    fn test_reject_non_finite_yaml_values() {
+        let config: HotspotsConfig = serde_norway::from_str("weights:\n  cc: .nan\n").unwrap();
+        assert!(config.validate().is_err());
+        let config: HotspotsConfig =
+            serde_norway::from_str("thresholds:\n  critical: .inf\n").unwrap();
+        assert!(config.validate().is_err());
+    }
+
     #[test]
     fn test_reject_negative_threshold() {
         let json = r#"{"thresholds": {"moderate": -1.0}}"#;
@@ -1015,6 +2206,320 @@ mod tests {
         assert!(config.validate().is_err());
     }
 
+    #[test]
+    fn test_custom_patterns_resolve() {
+        let json = r#"{"custom_patterns": [
+            {"name": "tangled", "condition": "cc > 20 && nd >= 4", "description": "Knotted control flow"}
+        ]}"#;
+        let config: HotspotsConfig = serde_json::from_str(json).unwrap();
+        let resolved = config.resolve().unwrap();
+        let custom = &resolved.pattern_thresholds.custom;
+        assert_eq!(custom.len(), 1);
+        assert_eq!(custom[0].name, "tangled");
+        assert_eq!(custom[0].conditions.len(), 2);
+        assert_eq!(
+            custom[0].description.as_deref(),
+            Some("Knotted control flow")
+        );
+    }
+
+    #[test]
+    fn test_suppress_rules_resolve_and_require_reason() {
+        let json = r#"{"suppress": [
+            {"pattern": "src/legacy/**", "reason": "scheduled for deletion"},
+            {"pattern": "src/api/*.ts", "function": "handle*", "reason": "generated"}
+        ]}"#;
+        let config: HotspotsConfig = serde_json::from_str(json).unwrap();
+        config.validate().unwrap();
+        let resolved = config.resolve().unwrap();
+        assert_eq!(resolved.suppress_rules.len(), 2);
+        assert!(resolved.suppress_rules[1].matches("/repo/src/api/users.ts", "handleGet"));
+
+        let missing_reason = r#"{"suppress": [{"pattern": "src/**", "reason": ""}]}"#;
+        let config: HotspotsConfig = serde_json::from_str(missing_reason).unwrap();
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_exclude_functions_match_exact_ids() {
+        let json = r#"{"exclude_functions": [
+            "src/legacy.ts::parseEverything",
+            "src/app.ts::main::<anonymous@12>",
+            "src/server.rs::Server::start"
+        ]}"#;
+        let config: HotspotsConfig = serde_json::from_str(json).unwrap();
+        config.validate().unwrap();
+        let resolved = config.resolve().unwrap();
+
+        assert!(resolved.is_function_excluded("src/legacy.ts", "parseEverything"));
+        assert!(resolved.is_function_excluded("/repo/src/legacy.ts", "parseEverything"));
+        assert!(resolved.is_function_excluded("./src/app.ts", "main::<anonymous@12>"));
+        assert!(resolved.is_function_excluded("src/server.rs", "Server::start"));
+        assert!(!resolved.is_function_excluded("src/legacy.ts", "parse"));
+        assert!(!resolved.is_function_excluded("src/app.ts", "main::<anonymous@12>#2"));
+        assert!(!resolved.is_function_excluded("src/app.ts", "main"));
+        assert!(!resolved.is_function_excluded("lib/src-legacy.ts", "parseEverything"));
+
+        let missing_symbol = r#"{"exclude_functions": ["src/legacy.ts"]}"#;
+        let config: HotspotsConfig = serde_json::from_str(missing_symbol).unwrap();
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_reject_invalid_custom_patterns() {
+        for json in [
+            r#"{"custom_patterns": [{"name": "god_function", "condition": "cc > 1"}]}"#,
+            r#"{"custom_patterns": [{"name": "Bad Name", "condition": "cc > 1"}]}"#,
+            r#"{"custom_patterns": [{"name": "x", "condition": "cc > 1"}, {"name": "x", "condition": "nd > 1"}]}"#,
+            r#"{"custom_patterns": [{"name": "x", "condition": "mystery > 1"}]}"#,
+        ] {
+            let config: HotspotsConfig = serde_json::from_str(json).unwrap();
+            assert!(config.validate().is_err(), "should reject: {json}");
+        }
+    }
+
+    #[test]
+    fn test_max_function_loc_sets_long_function_threshold() {
+        let json = r#"{"max_function_loc": 50, "policy": {"function_too_long": "block"}}"#;
+        let config: HotspotsConfig = serde_json::from_str(json).unwrap();
+        config.validate().unwrap();
+        let resolved = config.resolve().unwrap();
+        assert_eq!(resolved.max_function_loc, Some(50));
+        assert_eq!(resolved.pattern_thresholds.max_function_loc, Some(50));
+        assert_eq!(resolved.pattern_thresholds.function_loc_limit(), 50);
+        assert_eq!(resolved.function_too_long_mode, PolicyMode::Block);
+
+        for json in [
+            r#"{"max_function_loc": 0}"#,
+            r#"{"max_function_loc": 50, "patterns": {"long_function_loc": 80}}"#,
+        ] {
+            let config: HotspotsConfig = serde_json::from_str(json).unwrap();
+            assert!(config.validate().is_err(), "should reject: {json}");
+        }
+    }
+
+    #[test]
+    fn test_kind_thresholds_layer_over_patterns() {
+        use crate::ast::FunctionKind;
+
+        let json = r#"{
+            "patterns": {"complex_branching_cc": 12, "god_function_loc": 70},
+            "kind_thresholds": {"constructor": {"complex_branching_cc": 20}},
+            "max_function_loc": 100
+        }"#;
+        let config: HotspotsConfig = serde_json::from_str(json).unwrap();
+        config.validate().unwrap();
+        let resolved = config.resolve().unwrap();
+        let thresholds = &resolved.pattern_thresholds;
+
+        let ctor = thresholds.for_kind(Some(FunctionKind::Constructor));
+        assert_eq!(ctor.complex_branching_cc, 20);
+        assert_eq!(ctor.god_function_loc, 70);
+        assert_eq!(ctor.function_loc_limit(), 100);
+        let method = thresholds.for_kind(Some(FunctionKind::Method));
+        assert_eq!(method.complex_branching_cc, 12);
+        assert_eq!(thresholds.for_kind(None).complex_branching_cc, 12);
+
+        // A kind's own length threshold outlives max_function_loc
+        let json = r#"{
+            "max_function_loc": 50,
+            "kind_thresholds": {"constructor": {"long_function_loc": 81}}
+        }"#;
+        let config: HotspotsConfig = serde_json::from_str(json).unwrap();
+        config.validate().unwrap();
+        let mut resolved = config.resolve().unwrap();
+        assert_eq!(resolved.function_loc_limit(None), Some(50));
+        assert_eq!(
+            resolved.function_loc_limit(Some(FunctionKind::Constructor)),
+            Some(80)
+        );
+        resolved.set_max_function_loc(40);
+        assert_eq!(
+            resolved.function_loc_limit(Some(FunctionKind::Method)),
+            Some(40)
+        );
+        assert_eq!(
+            resolved.function_loc_limit(Some(FunctionKind::Constructor)),
+            Some(80)
+        );
+
+        assert!(
+            serde_json::from_str::<HotspotsConfig>(r#"{"kind_thresholds": {"getter": {}}}"#)
+                .is_err()
+        );
+        let config: HotspotsConfig =
+            serde_json::from_str(r#"{"kind_thresholds": {"method": {"complex_branching_cc": 0}}}"#)
+                .unwrap();
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_granularity_parses_function_or_file() {
+        let config: HotspotsConfig = serde_json::from_str("{}").unwrap();
+        assert_eq!(config.resolve().unwrap().granularity, Granularity::Function);
+
+        let config: HotspotsConfig = serde_json::from_str(r#"{"granularity": "file"}"#).unwrap();
+        assert_eq!(config.resolve().unwrap().granularity, Granularity::File);
+
+        let config: HotspotsConfig = serde_json::from_str(r#"{"granularity": "module"}"#).unwrap();
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_rust_branching_macros_are_bare_names() {
+        let config: HotspotsConfig = serde_json::from_str("{}").unwrap();
+        assert_eq!(config.resolve().unwrap().rust_branching_macros, None);
+
+        let config: HotspotsConfig =
+            serde_json::from_str(r#"{"rust_branching_macros": ["ensure!", "check_arg"]}"#).unwrap();
+        assert_eq!(
+            config.resolve().unwrap().rust_branching_macros,
+            Some(vec!["ensure".to_string(), "check_arg".to_string()])
+        );
+
+        let config: HotspotsConfig =
+            serde_json::from_str(r#"{"rust_branching_macros": ["anyhow::ensure"]}"#).unwrap();
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_callgraph_method_resolution_parses_receiver_or_name() {
+        use crate::callgraph::MethodResolution;
+
+        let config: HotspotsConfig = serde_json::from_str("{}").unwrap();
+        assert_eq!(
+            config.resolve().unwrap().callgraph_method_resolution,
+            MethodResolution::Receiver
+        );
+
+        let json = r#"{"callgraph_method_resolution": "name"}"#;
+        let config: HotspotsConfig = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            config.resolve().unwrap().callgraph_method_resolution,
+            MethodResolution::Name
+        );
+
+        let json = r#"{"callgraph_method_resolution": "type"}"#;
+        let config: HotspotsConfig = serde_json::from_str(json).unwrap();
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_co_change_metric_defaults_to_min() {
+        use crate::git::CoChangeMetric;
+
+        let config: HotspotsConfig = serde_json::from_str("{}").unwrap();
+        let resolved = config.resolve().unwrap();
+        assert_eq!(resolved.co_change_metric, CoChangeMetric::Min);
+        assert_eq!(resolved.co_change_options().metric, CoChangeMetric::Min);
+
+        let json = r#"{"co_change_metric": "jaccard"}"#;
+        let config: HotspotsConfig = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            config.resolve().unwrap().co_change_metric,
+            CoChangeMetric::Jaccard
+        );
+
+        let json = r#"{"co_change_metric": "union"}"#;
+        let config: HotspotsConfig = serde_json::from_str(json).unwrap();
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_count_trivial_uses_min_loc_and_min_lrs() {
+        let config: HotspotsConfig =
+            serde_json::from_str(r#"{"min_loc": 3, "min_lrs": 1.5}"#).unwrap();
+        let resolved = config.resolve().unwrap();
+        assert_eq!(resolved.trivial, crate::snapshot::TrivialCutoff::default());
+        assert_eq!(resolved.min_lrs, Some(1.5));
+
+        let json = r#"{"min_loc": 3, "min_lrs": 1.5, "count_trivial": false}"#;
+        let config: HotspotsConfig = serde_json::from_str(json).unwrap();
+        let resolved = config.resolve().unwrap();
+        assert_eq!(resolved.trivial.min_loc, Some(3));
+        assert_eq!(resolved.trivial.min_lrs, Some(1.5));
+        // Trivial functions stay in the results
+        assert_eq!(resolved.min_lrs, None);
+
+        let json = r#"{"min_lrs": 1.5, "count_trivial": false}"#;
+        let config: HotspotsConfig = serde_json::from_str(json).unwrap();
+        assert!(config.validate().is_ok());
+
+        for bad in [r#"{"count_trivial": false}"#, r#"{"min_loc": 0}"#] {
+            let config: HotspotsConfig = serde_json::from_str(bad).unwrap();
+            assert!(config.validate().is_err(), "{bad} should be rejected");
+        }
+    }
+
+    #[test]
+    fn test_max_new_cc_policy_defaults_to_block() {
+        let config: HotspotsConfig = serde_json::from_str(r#"{"max_new_cc": 2}"#).unwrap();
+        let resolved = config.resolve().unwrap();
+        assert_eq!(resolved.max_new_cc, Some(2));
+        assert_eq!(resolved.max_new_cc_mode, PolicyMode::Block);
+
+        let json = r#"{"max_new_cc": 2, "policy": {"max_new_cc": "warn"}}"#;
+        let config: HotspotsConfig = serde_json::from_str(json).unwrap();
+        assert_eq!(config.resolve().unwrap().max_new_cc_mode, PolicyMode::Warn);
+
+        let config: HotspotsConfig =
+            serde_json::from_str(r#"{"policy": {"max_new_cc": "loud"}}"#).unwrap();
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_max_new_critical_and_high_policies_default_to_block() {
+        let json =
+            r#"{"max_new_critical": 0, "max_new_high": 3, "policy": {"max_new_high": "warn"}}"#;
+        let config: HotspotsConfig = serde_json::from_str(json).unwrap();
+        let resolved = config.resolve().unwrap();
+        assert_eq!(resolved.max_new_critical, Some(0));
+        assert_eq!(resolved.max_new_critical_mode, PolicyMode::Block);
+        assert_eq!(resolved.max_new_high, Some(3));
+        assert_eq!(resolved.max_new_high_mode, PolicyMode::Warn);
+
+        let config: HotspotsConfig =
+            serde_json::from_str(r#"{"policy": {"max_new_critical": "loud"}}"#).unwrap();
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_instability_regression_policy_needs_afferent_limit() {
+        let resolved = HotspotsConfig::default().resolve().unwrap();
+        assert_eq!(resolved.max_stable_afferent, None);
+
+        let json = r#"{"max_stable_afferent": 5, "policy": {"instability_regression": "warn"}}"#;
+        let config: HotspotsConfig = serde_json::from_str(json).unwrap();
+        let resolved = config.resolve().unwrap();
+        assert_eq!(resolved.max_stable_afferent, Some(5));
+        assert_eq!(resolved.instability_regression_mode, PolicyMode::Warn);
+    }
+
+    #[test]
+    fn test_should_include_filters_by_language() {
+        let json = r#"{"include_languages": ["rust", "TypeScript"], "exclude_languages": ["ts"]}"#;
+        let config: HotspotsConfig = serde_json::from_str(json).unwrap();
+        let mut resolved = config.resolve().unwrap();
+        assert!(resolved.should_include(Path::new("src/lib.rs")));
+        // Exclusion wins over inclusion, and covers TSX as well
+        assert!(!resolved.should_include(Path::new("src/api.ts")));
+        assert!(!resolved.should_include(Path::new("src/App.tsx")));
+        assert!(!resolved.should_include(Path::new("main.go")));
+
+        resolved
+            .apply_language_filter_overrides(&["go".to_string()], &["rs".to_string()])
+            .unwrap();
+        assert!(resolved.should_include(Path::new("main.go")));
+        assert!(!resolved.should_include(Path::new("src/lib.rs")));
+        assert!(resolved
+            .apply_language_filter_overrides(&["cobol".to_string()], &[])
+            .is_err());
+
+        let config: HotspotsConfig =
+            serde_json::from_str(r#"{"exclude_languages": ["kotlin"]}"#).unwrap();
+        assert!(config.validate().is_err());
+    }
+
     #[test]
     fn test_should_include_default_excludes() {
         let resolved = ResolvedConfig::defaults().unwrap();
@@ -1035,6 +2540,41 @@ mod tests {
         assert!(resolved.should_include(Path::new("src/networking.c")));
     }
 
+    #[test]
+    fn test_path_filter_overrides_merge_and_replace() {
+        let json = r#"{
+            "include": ["src/**/*.ts"],
+            "exclude": ["src/generated/**"]
+        }"#;
+        let config: HotspotsConfig = serde_json::from_str(json).unwrap();
+
+        let mut merged = config.resolve().unwrap();
+        merged
+            .apply_path_filter_overrides(
+                &["lib/**/*.ts".to_string()],
+                &["src/legacy/**".to_string()],
+                false,
+            )
+            .unwrap();
+        assert!(merged.should_include(Path::new("src/api.ts")));
+        assert!(merged.should_include(Path::new("lib/util.ts")));
+        assert!(!merged.should_include(Path::new("src/generated/types.ts")));
+        assert!(!merged.should_include(Path::new("src/legacy/old.ts")));
+
+        let mut replaced = config.resolve().unwrap();
+        replaced
+            .apply_path_filter_overrides(&["lib/**/*.ts".to_string()], &[], true)
+            .unwrap();
+        assert!(!replaced.should_include(Path::new("src/api.ts")));
+        assert!(replaced.should_include(Path::new("lib/util.ts")));
+        // Built-in default excludes still apply
+        assert!(!replaced.should_include(Path::new("lib/util.test.ts")));
+
+        assert!(replaced
+            .apply_path_filter_overrides(&["[invalid".to_string()], &[], false)
+            .is_err());
+    }
+
     #[test]
     fn test_should_include_custom_patterns() {
         let config: HotspotsConfig = serde_json::from_str(
@@ -1075,6 +2615,33 @@ mod tests {
         assert_eq!(config.top, Some(10));
     }
 
+    #[test]
+    fn test_discover_hotspots_yaml() {
+        let dir = tempfile::tempdir().unwrap();
+        let config_path = dir.path().join(".hotspots.yml");
+        fs::write(
+            &config_path,
+            "min_lrs: 4.5\nexclude:\n  - \"vendor/**\"\nweights:\n  cc: 2.0\n",
+        )
+        .unwrap();
+
+        let (config, path) = discover_config(dir.path()).unwrap().unwrap();
+        assert_eq!(config.min_lrs, Some(4.5));
+        assert_eq!(config.exclude, vec!["vendor/**"]);
+        assert_eq!(path, config_path);
+
+        // Explicit paths pick the parser by extension, and unknown keys are still rejected
+        let bad_path = dir.path().join("custom.yaml");
+        fs::write(&bad_path, "min_lrs: 1.0\nbogus: true\n").unwrap();
+        assert!(load_and_resolve(dir.path(), Some(&bad_path)).is_err());
+        assert_eq!(
+            load_and_resolve(dir.path(), Some(&config_path))
+                .unwrap()
+                .config_path,
+            Some(config_path)
+        );
+    }
+
     #[test]
     fn test_discover_package_json() {
         let dir = tempfile::tempdir().unwrap();
@@ -1137,6 +2704,64 @@ mod tests {
         assert!(result.is_none());
     }
 
+    #[test]
+    fn test_resolved_config_to_json() {
+        let json = r#"{"weights": {"cc": 2.0}, "exclude": ["src/generated/**"]}"#;
+        let config: HotspotsConfig = serde_json::from_str(json).unwrap();
+        let resolved = config.resolve().unwrap();
+        let value: serde_json::Value = serde_json::from_str(&resolved.to_json().unwrap()).unwrap();
+        assert_eq!(value["weight_cc"], 2.0);
+        assert_eq!(value["exclude_patterns"][0], "src/generated/**");
+        assert_eq!(value["critical_introduction_mode"], "block");
+        assert!(value.get("include").is_none());
+    }
+
+    #[test]
+    fn test_setting_source_tracks_explicit_keys() {
+        let dir = tempfile::tempdir().unwrap();
+        let config_path = dir.path().join(".hotspotsrc.json");
+        fs::write(
+            &config_path,
+            r#"{"weights": {"cc": 2.0}, "thresholds": {"high": 7.0}}"#,
+        )
+        .unwrap();
+        let resolved = load_and_resolve(dir.path(), None).unwrap();
+        let path = config_path.display().to_string();
+        assert_eq!(resolved.setting_source("weights.cc"), path);
+        assert_eq!(resolved.setting_source("thresholds.high"), path);
+        assert_eq!(resolved.setting_source("weights.nd"), "default");
+        assert_eq!(resolved.setting_source("thresholds.critical"), "default");
+    }
+
+    #[test]
+    fn test_env_config_is_a_path_or_inline_config() {
+        assert!(load_env_config(None).unwrap().is_none());
+        assert!(load_env_config(Some("  ")).unwrap().is_none());
+
+        let (config, source) = load_env_config(Some("min_lrs: 4.5\ntop: 7\n"))
+            .unwrap()
+            .unwrap();
+        assert_eq!(config.min_lrs, Some(4.5));
+        assert_eq!(config.top, Some(7));
+        assert_eq!(source, PathBuf::from("$HOTSPOTS_CONFIG"));
+
+        let (config, _) = load_env_config(Some(r#"{"weights": {"cc": 2.0}}"#))
+            .unwrap()
+            .unwrap();
+        assert_eq!(config.resolve().unwrap().weight_cc, 2.0);
+
+        let dir = tempfile::tempdir().unwrap();
+        let config_path = dir.path().join("ci.yml");
+        fs::write(&config_path, "top: 3\n").unwrap();
+        let (config, source) = load_env_config(config_path.to_str()).unwrap().unwrap();
+        assert_eq!(config.top, Some(3));
+        assert_eq!(source, config_path);
+
+        // A missing file reads as an inline string, not a config mapping
+        assert!(load_env_config(Some("ci/missing.yml")).is_err());
+        assert!(load_env_config(Some(r#"{"weights": {"cc": -1.0}}"#)).is_err());
+    }
+
     #[test]
     fn test_load_and_resolve_defaults() {
         let dir = tempfile::tempdir().unwrap();
@@ -1292,6 +2917,72 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_priority_weights_from_config() {
+        let config: HotspotsConfig =
+            serde_json::from_str(r#"{"priority": {"coverage": 0.5}}"#).unwrap();
+        let resolved = config.resolve().unwrap();
+        let defaults = crate::scoring::PriorityWeights::default();
+        assert_eq!(resolved.priority_weights.coverage, 0.5);
+        assert_eq!(resolved.priority_weights.recency, defaults.recency);
+
+        for json in [
+            r#"{"priority": {"coverage": 1.5}}"#,
+            r#"{"priority": {"churn": -1.0}}"#,
+            r#"{"priority": {"recency_half_life_days": 0}}"#,
+        ] {
+            let config: HotspotsConfig = serde_json::from_str(json).unwrap();
+            assert!(config.validate().is_err(), "{json}");
+        }
+    }
+
+    #[test]
+    fn test_file_risk_weights_from_config() {
+        let config: HotspotsConfig =
+            serde_json::from_str(r#"{"file_risk": {"churn": 0.5}}"#).unwrap();
+        config.validate().unwrap();
+        let resolved = config.resolve().unwrap();
+        let defaults = crate::aggregates::FileRiskWeights::default();
+        assert_eq!(resolved.file_risk_weights.churn, 0.5);
+        assert_eq!(resolved.file_risk_weights.max_cc, defaults.max_cc);
+
+        let config: HotspotsConfig =
+            serde_json::from_str(r#"{"file_risk": {"max_cc": -1.0}}"#).unwrap();
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_module_zones_from_config() {
+        let config: HotspotsConfig =
+            serde_json::from_str(r#"{"module_zones": {"pain_complexity": 12.0}}"#).unwrap();
+        config.validate().unwrap();
+        let resolved = config.resolve().unwrap();
+        let defaults = crate::aggregates::ModuleZoneThresholds::default();
+        assert_eq!(resolved.module_zones.pain_complexity, 12.0);
+        assert_eq!(resolved.module_zones.stable_below, defaults.stable_below);
+
+        let config: HotspotsConfig =
+            serde_json::from_str(r#"{"module_zones": {"stable_below": 0.8}}"#).unwrap();
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_band_colors_from_config() {
+        let config: HotspotsConfig =
+            serde_json::from_str(r##"{"band_colors": {"critical": "#b91c1c"}}"##).unwrap();
+        config.validate().unwrap();
+        let resolved = config.resolve().unwrap();
+        let defaults = crate::html::BandColors::default();
+        assert_eq!(resolved.band_colors.critical, "#b91c1c");
+        assert_eq!(resolved.band_colors.low, defaults.low);
+
+        for bad in ["red", "#12345", "#ggg", "#fff; color: red"] {
+            let json = format!(r#"{{"band_colors": {{"high": "{bad}"}}}}"#);
+            let config: HotspotsConfig = serde_json::from_str(&json).unwrap();
+            assert!(config.validate().is_err(), "{bad} should be rejected");
+        }
+    }
+
     #[test]
     fn test_scoring_weights_from_config() {
         let json = r#"{
@@ -11,6 +11,7 @@
 //!   Jaccard < DC_JACCARD_THRESHOLD → dc_365d (architecturally volatile repo)
 //!   Jaccard ≥ DC_JACCARD_THRESHOLD → dc_full (stable repo, full history is an asset)
 
+use crate::diagnostics::{self, Level};
 use std::collections::HashMap;
 use std::path::Path;
 use std::process::Command;
@@ -284,7 +285,7 @@ pub fn compute_directed_coupling_for_repo(
 
     // On large repos the git log traversal can take several seconds.
     // Surface this so users aren't surprised by latency in hotspots analyze.
-    if commits.len() > 50_000 {
+    if commits.len() > 50_000 && diagnostics::enabled(Level::Info) {
         eprintln!(
             "hotspots: directed coupling loading {} commits (large repo — may be slow)",
             commits.len()
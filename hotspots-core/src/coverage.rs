@@ -0,0 +1,204 @@
+//! LCOV line coverage, mapped onto function line ranges
+//!
+//! Only `SF:` (source file) and `DA:` (line hits) records are read; function
+//! and branch records are ignored. A function's coverage is the share of the
+//! report's instrumented lines inside its range that ran at least once.
+
+use anyhow::{Context, Result};
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Component, Path};
+
+/// Line hit counts per source file from one LCOV report
+#[derive(Debug, Clone, Default)]
+pub struct LcovReport {
+    /// Normalized path → line → hit count. Records repeated for one file
+    /// (e.g. from merged test runs) are summed.
+    files: HashMap<String, BTreeMap<u32, u64>>,
+}
+
+impl LcovReport {
+    /// Parse LCOV tracefile content
+    ///
+    /// # Errors
+    ///
+    /// Returns error on a `DA:` record outside an `SF:` section or with a
+    /// non-numeric line or hit count.
+    pub fn parse(content: &str) -> Result<Self> {
+        let mut files: HashMap<String, BTreeMap<u32, u64>> = HashMap::new();
+        let mut current: Option<String> = None;
+        for (index, line) in content.lines().enumerate() {
+            let line = line.trim();
+            if let Some(path) = line.strip_prefix("SF:") {
+                current = Some(normalize(path.trim(), None));
+            } else if let Some(record) = line.strip_prefix("DA:") {
+                let file = current.as_ref().with_context(|| {
+                    format!("line {}: DA record outside an SF section", index + 1)
+                })?;
+                let (line_no, hits) = parse_da(record)
+                    .with_context(|| format!("line {}: malformed DA record", index + 1))?;
+                *files
+                    .entry(file.clone())
+                    .or_default()
+                    .entry(line_no)
+                    .or_default() += hits;
+            } else if line == "end_of_record" {
+                current = None;
+            }
+        }
+        Ok(LcovReport { files })
+    }
+
+    /// Read and parse an LCOV tracefile (e.g. `lcov.info`)
+    ///
+    /// # Errors
+    ///
+    /// Returns error if the file cannot be read or fails to parse.
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("failed to read coverage file: {}", path.display()))?;
+        Self::parse(&content)
+            .with_context(|| format!("failed to parse LCOV file: {}", path.display()))
+    }
+
+    /// Number of source files in the report
+    pub fn file_count(&self) -> usize {
+        self.files.len()
+    }
+
+    /// Fraction of instrumented lines in `start_line..=end_line` of `file` that
+    /// were hit, or `None` when the report has no such file or instruments no
+    /// line in the range
+    ///
+    /// `file` is made relative to `repo_root` when it lies under it. Report
+    /// paths match exactly (after the same normalization), or failing that, a
+    /// single report path that is a whole-component suffix of `file` or has
+    /// `file` as one. Reports written from a package subdirectory, and the
+    /// absolute `SF:` paths most coverage tools write, both line up.
+    pub fn function_coverage(
+        &self,
+        file: &str,
+        start_line: u32,
+        end_line: u32,
+        repo_root: &Path,
+    ) -> Option<f64> {
+        let lines = self.lines_for(&normalize(file, Some(repo_root)))?;
+        let (hit, total) = lines
+            .range(start_line..=end_line.max(start_line))
+            .fold((0usize, 0usize), |(hit, total), (_, &hits)| {
+                (hit + usize::from(hits > 0), total + 1)
+            });
+        (total > 0).then(|| hit as f64 / total as f64)
+    }
+
+    fn lines_for(&self, key: &str) -> Option<&BTreeMap<u32, u64>> {
+        if let Some(lines) = self.files.get(key) {
+            return Some(lines);
+        }
+        let mut suffixes = self.files.iter().filter(|(path, _)| {
+            key.ends_with(&format!("/{path}")) || path.ends_with(&format!("/{key}"))
+        });
+        match (suffixes.next(), suffixes.next()) {
+            (Some((_, lines)), None) => Some(lines),
+            _ => None,
+        }
+    }
+}
+
+/// `DA:<line>,<hits>[,<checksum>]` without the `DA:` prefix
+fn parse_da(record: &str) -> Option<(u32, u64)> {
+    let mut fields = record.split(',');
+    let line = fields.next()?.trim().parse().ok()?;
+    // Some generators emit negative or fractional counts; any positive value is a hit
+    let hits = fields.next()?.trim().parse::<f64>().ok()?;
+    Some((line, if hits > 0.0 { hits.ceil() as u64 } else { 0 }))
+}
+
+/// `/`-separated path with `.` segments dropped, relative to `repo_root` when
+/// it lies under it
+fn normalize(path: &str, repo_root: Option<&Path>) -> String {
+    let path = Path::new(path);
+    let path = repo_root
+        .and_then(|root| path.strip_prefix(root).ok())
+        .unwrap_or(path);
+    path.components()
+        .filter(|c| !matches!(c, Component::CurDir))
+        .map(|c| c.as_os_str().to_string_lossy().into_owned())
+        .collect::<Vec<_>>()
+        .join("/")
+        .replace("//", "/")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LCOV: &str = "TN:\n\
+SF:src/lib.ts\n\
+FN:1,covered\n\
+DA:1,4\n\
+DA:2,4\n\
+DA:3,0\n\
+DA:4,1\n\
+DA:10,0\n\
+DA:11,0\n\
+end_of_record\n\
+SF:./src/lib.ts\n\
+DA:3,2\n\
+end_of_record\n";
+
+    #[test]
+    fn test_function_coverage_counts_hit_lines_in_range() {
+        let report = LcovReport::parse(LCOV).unwrap();
+        let root = Path::new("/repo");
+        assert_eq!(report.file_count(), 1);
+        // The second record for the same file marks line 3 as hit
+        assert_eq!(
+            report.function_coverage("/repo/./src/lib.ts", 1, 4, root),
+            Some(1.0)
+        );
+        assert_eq!(
+            report.function_coverage("src/lib.ts", 4, 11, root),
+            Some(1.0 / 3.0)
+        );
+        // No instrumented lines in range, or a file the report doesn't cover
+        assert_eq!(report.function_coverage("src/lib.ts", 5, 9, root), None);
+        assert_eq!(report.function_coverage("src/other.ts", 1, 4, root), None);
+    }
+
+    #[test]
+    fn test_report_paths_match_as_component_suffix() {
+        let report = LcovReport::parse("SF:lib.ts\nDA:1,0\nend_of_record\n").unwrap();
+        let root = Path::new("/repo");
+        assert_eq!(
+            report.function_coverage("/repo/packages/web/lib.ts", 1, 1, root),
+            Some(0.0)
+        );
+        assert_eq!(
+            report.function_coverage("/repo/packages/web/mylib.ts", 1, 1, root),
+            None
+        );
+    }
+
+    #[test]
+    fn test_absolute_report_paths_match_repo_relative_files() {
+        let report = LcovReport::parse(
+            "SF:/home/ci/repo/src/a.ts\nDA:1,0\nend_of_record\n\
+             SF:/home/ci/repo/lib/a.ts\nDA:1,1\nend_of_record\n",
+        )
+        .unwrap();
+        let root = Path::new("/checkout");
+        assert_eq!(report.function_coverage("src/a.ts", 1, 1, root), Some(0.0));
+        assert_eq!(
+            report.function_coverage("/checkout/lib/a.ts", 1, 1, root),
+            Some(1.0)
+        );
+        // Both report paths end in `a.ts`, so a bare `a.ts` is ambiguous
+        assert_eq!(report.function_coverage("a.ts", 1, 1, root), None);
+    }
+
+    #[test]
+    fn test_malformed_da_record_is_an_error() {
+        assert!(LcovReport::parse("DA:1,1\n").is_err());
+        assert!(LcovReport::parse("SF:a.ts\nDA:x,1\n").is_err());
+    }
+}
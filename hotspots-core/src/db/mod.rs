@@ -65,6 +65,7 @@ CREATE TABLE IF NOT EXISTS functions (
     is_entrypoint           INTEGER,
     dependency_depth        INTEGER,
     neighbor_churn          INTEGER,
+    edge_confidence         REAL,
     activity_risk           REAL,
     risk_factors            TEXT,
     is_top_10_pct           INTEGER,
@@ -74,6 +75,13 @@ CREATE TABLE IF NOT EXISTS functions (
     driver_detail           TEXT,
     quadrant                TEXT,
     patterns                TEXT,
+    sloc                    INTEGER,
+    return_points           INTEGER,
+    unwrap_count            INTEGER,
+    location                TEXT,
+    max_arm_loc             INTEGER,
+    max_data_nesting        INTEGER,
+    unreachable_code        INTEGER,
     FOREIGN KEY (commit_sha) REFERENCES commits(sha),
     UNIQUE (commit_sha, function_id)
 );
@@ -89,7 +97,27 @@ CREATE INDEX IF NOT EXISTS idx_functions_lrs    ON functions(commit_sha, lrs DES
 
 /// Apply the schema DDL to an open connection.
 fn apply_schema(conn: &Connection) -> Result<()> {
-    conn.execute_batch(SCHEMA).context("failed to apply schema")
+    conn.execute_batch(SCHEMA)
+        .context("failed to apply schema")?;
+    // Databases created before these columns existed lack them; add them in place.
+    for (column, ty) in [
+        ("sloc", "INTEGER"),
+        ("return_points", "INTEGER"),
+        ("unwrap_count", "INTEGER"),
+        ("location", "TEXT"),
+        ("max_arm_loc", "INTEGER"),
+        ("max_data_nesting", "INTEGER"),
+        ("unreachable_code", "INTEGER"),
+    ] {
+        if conn
+            .prepare(&format!("SELECT {column} FROM functions LIMIT 0"))
+            .is_err()
+        {
+            conn.execute_batch(&format!("ALTER TABLE functions ADD COLUMN {column} {ty}"))
+                .with_context(|| format!("failed to add {column} column"))?;
+        }
+    }
+    Ok(())
 }
 
 /// Insert a commit row, ignoring conflicts (idempotent).
@@ -131,7 +159,9 @@ fn insert_functions(conn: &Connection, snapshot: &Snapshot) -> Result<()> {
             scc_id, scc_size, is_entrypoint, dependency_depth, neighbor_churn,
             activity_risk, risk_factors,
             is_top_10_pct, is_top_5_pct, is_top_1_pct,
-            driver, driver_detail, quadrant, patterns
+            driver, driver_detail, quadrant, patterns, sloc, return_points,
+            unwrap_count, location, max_arm_loc, max_data_nesting, unreachable_code,
+            edge_confidence
         ) VALUES (
             ?1,?2,?3,?4,?5,
             ?6,?7,?8,?9,?10,?11,?12,?13,
@@ -141,7 +171,9 @@ fn insert_functions(conn: &Connection, snapshot: &Snapshot) -> Result<()> {
             ?22,?23,?24,?25,?26,
             ?27,?28,
             ?29,?30,?31,
-            ?32,?33,?34,?35
+            ?32,?33,?34,?35,?36,?37,
+            ?38,?39,?40,?41,?42,
+            ?43
         )",
     )?;
 
@@ -151,6 +183,10 @@ fn insert_functions(conn: &Connection, snapshot: &Snapshot) -> Result<()> {
             .as_ref()
             .and_then(|rf| serde_json::to_string(rf).ok());
         let patterns_json = serde_json::to_string(&func.patterns).unwrap_or_default();
+        let location_json = func
+            .location
+            .as_ref()
+            .and_then(|l| serde_json::to_string(l).ok());
 
         let (churn_added, churn_deleted) = func
             .churn
@@ -234,6 +270,14 @@ fn insert_functions(conn: &Connection, snapshot: &Snapshot) -> Result<()> {
             func.driver_detail,
             func.quadrant,
             patterns_json,
+            func.metrics.sloc.map(|n| n as i64),
+            func.metrics.return_points.map(|n| n as i64),
+            func.metrics.unwrap_count.map(|n| n as i64),
+            location_json,
+            func.metrics.max_arm_loc.map(|n| n as i64),
+            func.metrics.max_data_nesting.map(|n| n as i64),
+            func.metrics.unreachable_code.map(|n| n as i64),
+            func.callgraph.as_ref().and_then(|cg| cg.edge_confidence),
         ])
         .context("failed to insert function row")?;
     }
@@ -254,7 +298,9 @@ fn load_functions(conn: &Connection, sha: &str) -> Result<Vec<FunctionSnapshot>>
                 scc_id, scc_size, is_entrypoint, dependency_depth, neighbor_churn,
                 activity_risk, risk_factors,
                 is_top_10_pct, is_top_5_pct, is_top_1_pct,
-                driver, driver_detail, quadrant, patterns
+                driver, driver_detail, quadrant, patterns, sloc, return_points,
+                unwrap_count, location, max_arm_loc, max_data_nesting, unreachable_code,
+                edge_confidence
          FROM functions
          WHERE commit_sha = ?1
          ORDER BY function_id",
@@ -297,6 +343,7 @@ fn load_functions(conn: &Connection, sha: &str) -> Result<Vec<FunctionSnapshot>>
         let is_entrypoint: Option<i64> = row.get(22)?;
         let dep_depth: Option<i64> = row.get(23)?;
         let nbr_churn: Option<i64> = row.get(24)?;
+        let edge_confidence: Option<f64> = row.get(41)?;
         let callgraph = fan_in
             .zip(fan_out)
             .zip(pagerank)
@@ -314,6 +361,7 @@ fn load_functions(conn: &Connection, sha: &str) -> Result<Vec<FunctionSnapshot>>
                 is_entrypoint: ep != 0,
                 dependency_depth: dep_depth.map(|d| d as usize),
                 neighbor_churn: nbr_churn.map(|n| n as usize),
+                edge_confidence,
             });
 
         let activity_risk: Option<f64> = row.get(25)?;
@@ -335,6 +383,13 @@ fn load_functions(conn: &Connection, sha: &str) -> Result<Vec<FunctionSnapshot>>
         let driver_detail: Option<String> = row.get(31)?;
         let quadrant: Option<String> = row.get(32)?;
         let patterns_json: Option<String> = row.get(33)?;
+        let sloc: Option<i64> = row.get(34)?;
+        let return_points: Option<i64> = row.get(35)?;
+        let unwrap_count: Option<i64> = row.get(36)?;
+        let location_json: Option<String> = row.get(37)?;
+        let max_arm_loc: Option<i64> = row.get(38)?;
+        let max_data_nesting: Option<i64> = row.get(39)?;
+        let unreachable_code: Option<i64> = row.get(40)?;
 
         Ok((
             function_id,
@@ -346,6 +401,12 @@ fn load_functions(conn: &Connection, sha: &str) -> Result<Vec<FunctionSnapshot>>
             fo,
             ns,
             loc,
+            sloc,
+            return_points,
+            unwrap_count,
+            max_arm_loc,
+            max_data_nesting,
+            unreachable_code,
             lrs,
             band,
             suppression_reason,
@@ -360,6 +421,7 @@ fn load_functions(conn: &Connection, sha: &str) -> Result<Vec<FunctionSnapshot>>
             driver_detail,
             quadrant,
             patterns_json,
+            location_json,
         ))
     })?;
 
@@ -375,6 +437,12 @@ fn load_functions(conn: &Connection, sha: &str) -> Result<Vec<FunctionSnapshot>>
             fo,
             ns,
             loc,
+            sloc,
+            return_points,
+            unwrap_count,
+            max_arm_loc,
+            max_data_nesting,
+            unreachable_code,
             lrs,
             band,
             suppression_reason,
@@ -389,6 +457,7 @@ fn load_functions(conn: &Connection, sha: &str) -> Result<Vec<FunctionSnapshot>>
             driver_detail,
             quadrant,
             patterns_json,
+            location_json,
         ) = row.context("failed to read function row")?;
 
         let risk_factors = risk_factors_json
@@ -400,6 +469,10 @@ fn load_functions(conn: &Connection, sha: &str) -> Result<Vec<FunctionSnapshot>>
             .and_then(|s| serde_json::from_str(s).ok())
             .unwrap_or_default();
 
+        let location = location_json
+            .as_deref()
+            .and_then(|s| serde_json::from_str(s).ok());
+
         let language = crate::language::Language::from_name(&language)
             .unwrap_or(crate::language::Language::TypeScript);
         let band = crate::risk::RiskBand::parse(&band).unwrap_or(crate::risk::RiskBand::Low);
@@ -414,6 +487,12 @@ fn load_functions(conn: &Connection, sha: &str) -> Result<Vec<FunctionSnapshot>>
                 fo: fo as u32,
                 ns: ns as u32,
                 loc: loc as u32,
+                sloc: sloc.map(|n| n as u32),
+                return_points: return_points.map(|n| n as u32),
+                unwrap_count: unwrap_count.map(|n| n as u32),
+                max_arm_loc: max_arm_loc.map(|n| n as u32),
+                max_data_nesting: max_data_nesting.map(|n| n as u32),
+                unreachable_code: unreachable_code.map(|n| n as u32),
             },
             lrs,
             band,
@@ -442,7 +521,12 @@ fn load_functions(conn: &Connection, sha: &str) -> Result<Vec<FunctionSnapshot>>
             isolation_rate: None,
             age_days: None,
             last_touch_days: None,
+            function_age_days: None,
+            coverage: None,
+            priority_score: None,
+            refactor_leverage: None,
             explanation: None,
+            location,
         });
     }
 
@@ -486,20 +570,21 @@ impl TempDb {
         let mut stmt = self.conn.prepare(
             "INSERT OR REPLACE INTO functions (
                 commit_sha, function_id, file, line, language,
-                cc, nd, fo, ns, loc, lrs, band, suppression_reason, callees
-            ) VALUES (?1,?2,?3,?4,?5,?6,?7,?8,?9,?10,?11,?12,?13,?14)",
+                cc, nd, fo, ns, loc, lrs, band, suppression_reason, callees, sloc,
+                return_points, unwrap_count, location, max_arm_loc, max_data_nesting,
+                unreachable_code
+            ) VALUES (?1,?2,?3,?4,?5,?6,?7,?8,?9,?10,?11,?12,?13,?14,?15,?16,?17,?18,?19,?20,?21)",
         )?;
 
         for report in reports {
             let normalized_file = report.file.replace('\\', "/");
-            let function_symbol = if report.function.starts_with("<anonymous>") {
-                "<anonymous>"
-            } else {
-                &report.function
-            };
-            let function_id = format!("{}::{}", normalized_file, function_symbol);
+            let function_id = format!("{}::{}", normalized_file, report.function);
             let callees_json =
                 serde_json::to_string(&report.callees).unwrap_or_else(|_| "[]".to_string());
+            let location_json = report
+                .location
+                .as_ref()
+                .and_then(|l| serde_json::to_string(l).ok());
             stmt.execute(params![
                 sha,
                 function_id,
@@ -515,6 +600,13 @@ impl TempDb {
                 report.band.as_str(),
                 report.suppression_reason,
                 callees_json,
+                report.metrics.sloc.map(|n| n as i64),
+                report.metrics.return_points.map(|n| n as i64),
+                report.metrics.unwrap_count.map(|n| n as i64),
+                location_json,
+                report.metrics.max_arm_loc.map(|n| n as i64),
+                report.metrics.max_data_nesting.map(|n| n as i64),
+                report.metrics.unreachable_code.map(|n| n as i64),
             ])
             .context("failed to insert report row")?;
         }
@@ -606,6 +698,7 @@ impl TempDb {
         let scc_info = graph.find_strongly_connected_components();
         let depths = graph.compute_dependency_depth();
         let fan_in_map = graph.build_fan_in_map();
+        let edge_confidence = graph.build_edge_confidence_map();
 
         // Load churn for neighbor_churn computation.
         let churn_map: std::collections::HashMap<String, usize> = {
@@ -628,7 +721,7 @@ impl TempDb {
             "UPDATE functions
              SET fan_in = ?1, fan_out = ?2, pagerank = ?3, betweenness = ?4,
                  scc_id = ?5, scc_size = ?6, is_entrypoint = ?7,
-                 dependency_depth = ?8, neighbor_churn = ?9
+                 dependency_depth = ?8, neighbor_churn = ?9, edge_confidence = ?12
              WHERE commit_sha = ?10 AND function_id = ?11",
         )?;
 
@@ -654,6 +747,7 @@ impl TempDb {
                 neighbor_churn.map(|n| n as i64),
                 sha,
                 function_id,
+                edge_confidence.get(function_id).copied(),
             ])
             .context("failed to update callgraph metrics row")?;
         }
@@ -923,6 +1017,12 @@ mod tests {
                 fo: 2,
                 ns: 0,
                 loc: 20,
+                sloc: None,
+                return_points: None,
+                unwrap_count: None,
+                max_arm_loc: None,
+                max_data_nesting: None,
+                unreachable_code: None,
             },
             risk: RiskReport {
                 r_cc: 1.0,
@@ -937,6 +1037,7 @@ mod tests {
             patterns: vec![],
             pattern_details: None,
             explanation: None,
+            location: None,
         }];
         Snapshot::new(ctx, reports)
     }
@@ -1057,6 +1158,12 @@ mod tests {
                 fo: 5,
                 ns: 2,
                 loc: 100,
+                sloc: None,
+                return_points: None,
+                unwrap_count: None,
+                max_arm_loc: None,
+                max_data_nesting: None,
+                unreachable_code: None,
             },
             risk: RiskReport {
                 r_cc: 2.0,
@@ -1071,6 +1178,15 @@ mod tests {
             patterns: vec!["complex_branching".to_string()],
             pattern_details: None,
             explanation: None,
+            location: Some(crate::report::Location {
+                file: "src/svc.ts".to_string(),
+                start_line: 42,
+                end_line: 141,
+                start_col: Some(5),
+                end_col: Some(6),
+                symbol: "processRequest".to_string(),
+                kind: crate::ast::FunctionKind::Method,
+            }),
         };
         let mut snapshot = Snapshot::new(ctx, vec![report]);
 
@@ -1093,6 +1209,7 @@ mod tests {
             is_entrypoint: true,
             dependency_depth: Some(2),
             neighbor_churn: Some(12),
+            edge_confidence: Some(0.75),
         });
         f.activity_risk = Some(9.5);
         f.risk_factors = Some(RiskFactors {
@@ -1136,6 +1253,7 @@ mod tests {
         assert!(cg.is_entrypoint);
         assert_eq!(cg.dependency_depth, Some(2));
         assert_eq!(cg.neighbor_churn, Some(12));
+        assert_eq!(cg.edge_confidence, Some(0.75));
 
         assert!((lf.activity_risk.unwrap() - 9.5).abs() < 1e-9);
 
@@ -1157,6 +1275,7 @@ mod tests {
         assert_eq!(lf.driver_detail.as_deref(), Some("cc (P92)"));
         assert_eq!(lf.quadrant.as_deref(), Some("fire"));
         assert_eq!(lf.patterns, vec!["complex_branching"]);
+        assert_eq!(lf.location, snapshot.functions[0].location);
 
         // Commit fields
         assert_eq!(loaded.commit.ticket_ids, vec!["PROJ-123"]);
@@ -1194,6 +1313,12 @@ mod tests {
                     fo: 0,
                     ns: 0,
                     loc: 10,
+                    sloc: None,
+                    return_points: None,
+                    unwrap_count: None,
+                    max_arm_loc: None,
+                    max_data_nesting: None,
+                    unreachable_code: None,
                 },
                 risk: RiskReport {
                     r_cc: i as f64,
@@ -1208,6 +1333,7 @@ mod tests {
                 patterns: vec![],
                 pattern_details: None,
                 explanation: None,
+                location: None,
             })
             .collect();
 
@@ -9,8 +9,8 @@
 //! - Status based on metrics/LRS/band changes, not file/line movements
 
 use crate::policy::PolicyResults;
-use crate::report::MetricsReport;
-use crate::risk::RiskBand;
+use crate::report::{FunctionRiskReport, MetricsReport};
+use crate::risk::{LrsWeights, RiskBand};
 use crate::snapshot::{FunctionSnapshot, Snapshot};
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
@@ -20,6 +20,10 @@ use std::path::Path;
 /// Schema version for deltas
 const DELTA_SCHEMA_VERSION: u32 = 1;
 
+/// LRS increase below which a function is not counted as regressed; absorbs
+/// float noise from round-tripping scores through JSON baseline files
+const REGRESSION_LRS_TOLERANCE: f64 = 1e-9;
+
 /// Function change status
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "lowercase")]
@@ -30,6 +34,54 @@ pub enum FunctionStatus {
     Unchanged,
 }
 
+/// Top-level outcome of a delta, so consumers can tell "nothing changed" apart
+/// from "no parent to compare against" without inspecting individual entries
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum DeltaStatus {
+    /// At least one function was added, modified, or deleted
+    #[default]
+    Changes,
+    /// Parent snapshot exists but no analyzed function changed
+    NoChange,
+    /// No parent snapshot; every function is reported as new
+    Baseline,
+}
+
+impl DeltaStatus {
+    /// Derive the status from the baseline flag and the (unfiltered) entries
+    pub fn from_entries(baseline: bool, deltas: &[FunctionDeltaEntry]) -> Self {
+        if baseline {
+            DeltaStatus::Baseline
+        } else if deltas.iter().all(|e| e.status == FunctionStatus::Unchanged) {
+            DeltaStatus::NoChange
+        } else {
+            DeltaStatus::Changes
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DeltaStatus::Changes => "changes",
+            DeltaStatus::NoChange => "no-change",
+            DeltaStatus::Baseline => "baseline",
+        }
+    }
+
+    /// One-line human-readable summary for text renderers
+    pub fn describe(&self) -> &'static str {
+        match self {
+            DeltaStatus::Changes => "Changes: functions were added, modified, or deleted.",
+            DeltaStatus::NoChange => {
+                "No changes: no analyzed functions were added, modified, or deleted."
+            }
+            DeltaStatus::Baseline => {
+                "Baseline: no parent snapshot; all functions are reported as new."
+            }
+        }
+    }
+}
+
 /// Function state in delta (before or after)
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(rename_all = "snake_case")]
@@ -48,6 +100,11 @@ pub struct FunctionDelta {
     pub fo: i64,
     pub ns: i64,
     pub lrs: f64,
+    /// Metric (`"cc"`, `"nd"`, `"fo"`, `"ns"`, or `"unwrap"`) whose risk term moved LRS
+    /// furthest in the direction it changed, scored with the run's LRS weights;
+    /// absent when LRS did not change or for deleted functions
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub driver: Option<String>,
 }
 
 /// Band transition information
@@ -78,6 +135,68 @@ pub struct FunctionDeltaEntry {
     /// Set by second-pass heuristic; absent when exact match was found or no match possible.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub rename_hint: Option<String>,
+    /// Cyclomatic complexity from decision points on lines added since the
+    /// base commit; set by [`Delta::annotate_new_cc`] when `max_new_cc` is configured
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub new_cc: Option<u32>,
+    /// Kind of the function (current side, else parent side), from the
+    /// snapshot's `location`; selects its `kind_thresholds` in policies
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub kind: Option<crate::ast::FunctionKind>,
+}
+
+impl FunctionDeltaEntry {
+    /// The metric that drove the LRS change with its before and after values,
+    /// e.g. `CC 8→14`, or `None` without a [`FunctionDelta::driver`]
+    pub fn driver_summary(&self) -> Option<String> {
+        let driver = self.delta.as_ref()?.driver.as_deref()?;
+        let (before, after) = (
+            &self.before.as_ref()?.metrics,
+            &self.after.as_ref()?.metrics,
+        );
+        let value = |m: &MetricsReport| match driver {
+            "cc" => m.cc,
+            "nd" => m.nd,
+            "fo" => m.fo,
+            "unwrap" => m.unwrap_count.unwrap_or(0),
+            _ => m.ns,
+        };
+        Some(format!(
+            "{} {}→{}",
+            driver.to_uppercase(),
+            value(before),
+            value(after)
+        ))
+    }
+
+    /// Every metric that changed, the driver first as in [`driver_summary`]
+    /// and the rest as signed differences, e.g. `CC 8→14 · ND +1`; `None` for
+    /// new and deleted functions or when no metric changed
+    ///
+    /// [`driver_summary`]: FunctionDeltaEntry::driver_summary
+    pub fn metric_breakdown(&self) -> Option<String> {
+        if self.status != FunctionStatus::Modified {
+            return None;
+        }
+        let delta = self.delta.as_ref()?;
+        let driver = delta.driver.as_deref();
+        let parts: Vec<String> = self
+            .driver_summary()
+            .into_iter()
+            .chain(
+                [
+                    ("cc", delta.cc),
+                    ("nd", delta.nd),
+                    ("fo", delta.fo),
+                    ("ns", delta.ns),
+                ]
+                .into_iter()
+                .filter(|&(name, change)| change != 0 && Some(name) != driver)
+                .map(|(name, change)| format!("{} {:+}", name.to_uppercase(), change)),
+            )
+            .collect();
+        (!parts.is_empty()).then(|| parts.join(" · "))
+    }
 }
 
 /// Commit info in delta
@@ -96,11 +215,37 @@ pub struct Delta {
     pub schema_version: u32,
     pub commit: DeltaCommitInfo,
     pub baseline: bool,
+    /// `"changes"`, `"no-change"`, or `"baseline"`; computed before any entry filtering
+    #[serde(default)]
+    pub status: DeltaStatus,
     pub deltas: Vec<FunctionDeltaEntry>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub policy: Option<PolicyResults>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub aggregates: Option<crate::aggregates::DeltaAggregates>,
+    /// Risk eliminated by deleted functions; absent when nothing was deleted
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub risk_removed: Option<RiskRemoved>,
+}
+
+/// Risk eliminated by deleting functions — the cleanup counterpart to regressions
+///
+/// Deleted entries carrying a `rename_hint` are treated as moves, not removals,
+/// and are left out.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub struct RiskRemoved {
+    /// Deleted functions counted
+    pub functions: usize,
+    /// Sum of their LRS in the parent snapshot
+    pub lrs: f64,
+    /// Sum of their activity risk; absent when the parent snapshot had none
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub activity_risk: Option<f64>,
+    /// How many of them were in the critical band
+    pub critical: usize,
+    /// How many of them were in the high band
+    pub high: usize,
 }
 
 impl Delta {
@@ -116,6 +261,16 @@ impl Delta {
     /// If `parent` is None, all functions in `current` are marked as `new`
     /// and `baseline` is set to `true`.
     pub fn new(current: &Snapshot, parent: Option<&Snapshot>) -> Result<Self> {
+        Self::new_with_weights(current, parent, &LrsWeights::default())
+    }
+
+    /// Like [`Delta::new`], attributing each LRS change to a metric
+    /// ([`FunctionDelta::driver`]) under the weights the snapshots were scored with
+    pub fn new_with_weights(
+        current: &Snapshot,
+        parent: Option<&Snapshot>,
+        weights: &LrsWeights,
+    ) -> Result<Self> {
         validate_snapshot_versions(current, parent)?;
         // Get parent SHA (use parents[0] only for delta computation)
         let parent_sha = current.commit.parents.first().cloned().unwrap_or_default();
@@ -142,8 +297,9 @@ impl Delta {
             .into_iter()
             .collect();
         all_ids.sort();
-        let mut deltas = compute_function_deltas(&all_ids, &parent_funcs, &current_funcs);
+        let mut deltas = compute_function_deltas(&all_ids, &parent_funcs, &current_funcs, weights);
         apply_rename_hints(&mut deltas, &parent_funcs, &current_funcs);
+        let risk_removed = compute_risk_removed(&deltas, &parent_funcs);
         Ok(Delta {
             schema_version: DELTA_SCHEMA_VERSION,
             commit: DeltaCommitInfo {
@@ -151,9 +307,11 @@ impl Delta {
                 parent: parent_sha,
             },
             baseline: false,
+            status: DeltaStatus::from_entries(false, &deltas),
             deltas,
             policy: None,
             aggregates: None,
+            risk_removed,
         })
     }
 
@@ -163,14 +321,66 @@ impl Delta {
     }
 
     /// Serialize delta entries as newline-delimited JSON (one entry per line).
+    ///
+    /// Each entry is compact and newline-terminated, so the output has exactly
+    /// one line per entry and none when there are no entries.
     pub fn to_jsonl(&self) -> Result<String> {
-        let mut lines = Vec::with_capacity(self.deltas.len());
+        let mut out = String::new();
         for entry in &self.deltas {
-            lines.push(
-                serde_json::to_string(entry).context("failed to serialize delta entry to JSON")?,
+            out.push_str(
+                &serde_json::to_string(entry).context("failed to serialize delta entry to JSON")?,
             );
+            out.push('\n');
+        }
+        Ok(out)
+    }
+
+    /// Keep only functions that existed before and whose LRS increased
+    ///
+    /// New and deleted functions are dropped: a regression is measured against
+    /// a prior value. `status` is left as computed on the full entry set.
+    pub fn retain_regressions(&mut self) {
+        self.deltas.retain(|entry| {
+            entry.status == FunctionStatus::Modified
+                && entry
+                    .delta
+                    .as_ref()
+                    .is_some_and(|d| d.lrs > REGRESSION_LRS_TOLERANCE)
+        });
+    }
+
+    /// Set `new_cc` on new and modified entries
+    ///
+    /// `new_cc` sums the paths of the function's decision points that sit on
+    /// lines in `added` (see [`crate::git::added_lines_between`]), so branches a
+    /// change merely moves past or reindents around don't count, while every
+    /// branch it writes does. Functions missing from `decisions` are left unset.
+    pub fn annotate_new_cc(
+        &mut self,
+        decisions: &DecisionMap,
+        added: &crate::git::AddedLines,
+        repo_root: &Path,
+    ) {
+        for entry in &mut self.deltas {
+            if !matches!(entry.status, FunctionStatus::New | FunctionStatus::Modified) {
+                continue;
+            }
+            let Some(function) = decisions.get(&entry.function_id) else {
+                continue;
+            };
+            let rel = Path::new(&function.file)
+                .strip_prefix(repo_root)
+                .map(|p| p.to_string_lossy().replace('\\', "/"))
+                .unwrap_or_else(|_| function.file.clone());
+            let ranges = added.get(&rel).map_or(&[][..], Vec::as_slice);
+            let new_cc: usize = function
+                .points
+                .iter()
+                .filter(|(line, _)| ranges.iter().any(|&(s, e)| s <= *line && *line <= e))
+                .map(|(_, paths)| paths)
+                .sum();
+            entry.new_cc = Some(new_cc as u32);
         }
-        Ok(lines.join("\n"))
     }
 
     /// Deserialize delta from JSON string
@@ -191,6 +401,40 @@ impl Delta {
     }
 }
 
+/// CFG decision points of one analyzed function
+#[derive(Debug, Clone)]
+pub struct FunctionDecisions {
+    /// File as reported by analysis (`/` separators)
+    pub file: String,
+    /// `(line, paths)` pairs from [`crate::cfg::Cfg::decision_points`]
+    pub points: Vec<(u32, usize)>,
+}
+
+/// Decision points keyed by function_id (`<file>::<symbol>`, as in snapshots)
+pub type DecisionMap = HashMap<String, FunctionDecisions>;
+
+/// Analyze `path` and record each function's decision points for
+/// [`Delta::annotate_new_cc`], in the same pass that produces the reports
+pub fn analyze_with_decisions(
+    path: &Path,
+    options: crate::AnalysisOptions,
+    resolved_config: Option<&crate::ResolvedConfig>,
+) -> Result<(Vec<FunctionRiskReport>, DecisionMap)> {
+    let mut decisions = DecisionMap::new();
+    let reports =
+        crate::analyze_with_visitor(path, options, resolved_config, &mut |report, cfg| {
+            let file = report.file.replace('\\', "/");
+            decisions.insert(
+                format!("{}::{}", file, report.function),
+                FunctionDecisions {
+                    file,
+                    points: cfg.decision_points(),
+                },
+            );
+        })?;
+    Ok((reports, decisions))
+}
+
 fn validate_snapshot_versions(current: &Snapshot, parent: Option<&Snapshot>) -> Result<()> {
     if current.schema_version != crate::snapshot::SNAPSHOT_SCHEMA_VERSION {
         anyhow::bail!(
@@ -228,6 +472,8 @@ fn build_baseline_delta(current: &Snapshot, parent_sha: String) -> Delta {
             band_transition: None,
             suppression_reason: func.suppression_reason.clone(),
             rename_hint: None,
+            new_cc: None,
+            kind: func.kind(),
         })
         .collect();
     Delta {
@@ -237,16 +483,43 @@ fn build_baseline_delta(current: &Snapshot, parent_sha: String) -> Delta {
             parent: parent_sha,
         },
         baseline: true,
+        status: DeltaStatus::Baseline,
         deltas,
         policy: None,
         aggregates: None,
+        risk_removed: None,
     }
 }
 
+/// Tally the deleted (not renamed) functions' parent LRS, activity risk, and bands
+fn compute_risk_removed(
+    deltas: &[FunctionDeltaEntry],
+    parent_funcs: &HashMap<&str, &FunctionSnapshot>,
+) -> Option<RiskRemoved> {
+    let removed: Vec<&FunctionSnapshot> = deltas
+        .iter()
+        .filter(|e| e.status == FunctionStatus::Deleted && e.rename_hint.is_none())
+        .filter_map(|e| parent_funcs.get(e.function_id.as_str()).copied())
+        .collect();
+    if removed.is_empty() {
+        return None;
+    }
+    let activity: Vec<f64> = removed.iter().filter_map(|f| f.activity_risk).collect();
+    let in_band = |band: RiskBand| removed.iter().filter(|f| f.band == band).count();
+    Some(RiskRemoved {
+        functions: removed.len(),
+        lrs: removed.iter().map(|f| f.lrs).sum(),
+        activity_risk: (!activity.is_empty()).then(|| activity.iter().sum()),
+        critical: in_band(RiskBand::Critical),
+        high: in_band(RiskBand::High),
+    })
+}
+
 fn compute_function_deltas(
     all_ids: &[&str],
     parent_funcs: &HashMap<&str, &FunctionSnapshot>,
     current_funcs: &HashMap<&str, &FunctionSnapshot>,
+    weights: &LrsWeights,
 ) -> Vec<FunctionDeltaEntry> {
     let mut deltas = Vec::new();
     for function_id in all_ids {
@@ -260,7 +533,7 @@ fn compute_function_deltas(
                     FunctionStatus::Unchanged
                 };
                 let delta = if status == FunctionStatus::Modified {
-                    Some(compute_function_delta(parent, current))
+                    Some(compute_function_delta(parent, current, weights))
                 } else {
                     None
                 };
@@ -289,6 +562,8 @@ fn compute_function_deltas(
                     band_transition,
                     suppression_reason: current.suppression_reason.clone(),
                     rename_hint: None,
+                    new_cc: None,
+                    kind: current.kind().or_else(|| parent.kind()),
                 });
             }
             (Some(parent), None) => {
@@ -305,6 +580,8 @@ fn compute_function_deltas(
                     band_transition: None,
                     suppression_reason: parent.suppression_reason.clone(),
                     rename_hint: None,
+                    new_cc: None,
+                    kind: parent.kind(),
                 });
             }
             (None, Some(current)) => {
@@ -321,6 +598,8 @@ fn compute_function_deltas(
                     band_transition: None,
                     suppression_reason: current.suppression_reason.clone(),
                     rename_hint: None,
+                    new_cc: None,
+                    kind: current.kind(),
                 });
             }
             (None, None) => {
@@ -432,14 +711,74 @@ fn functions_differ(parent: &FunctionSnapshot, current: &FunctionSnapshot) -> bo
 ///
 /// Returns deltas for metrics and LRS. Negative deltas are allowed
 /// (valid for reverts, refactors).
-fn compute_function_delta(parent: &FunctionSnapshot, current: &FunctionSnapshot) -> FunctionDelta {
+fn compute_function_delta(
+    parent: &FunctionSnapshot,
+    current: &FunctionSnapshot,
+    weights: &LrsWeights,
+) -> FunctionDelta {
     FunctionDelta {
         cc: current.metrics.cc as i64 - parent.metrics.cc as i64,
         nd: current.metrics.nd as i64 - parent.metrics.nd as i64,
         fo: current.metrics.fo as i64 - parent.metrics.fo as i64,
         ns: current.metrics.ns as i64 - parent.metrics.ns as i64,
         lrs: current.lrs - parent.lrs,
+        driver: lrs_driver(
+            &parent.metrics,
+            &current.metrics,
+            current.lrs - parent.lrs,
+            weights,
+        ),
+    }
+}
+
+/// The metric whose weighted risk term changed most in the direction of
+/// `lrs_change`, or `None` when LRS did not change or no term moved with it
+fn lrs_driver(
+    before: &MetricsReport,
+    after: &MetricsReport,
+    lrs_change: f64,
+    w: &LrsWeights,
+) -> Option<String> {
+    if lrs_change == 0.0 {
+        return None;
     }
+    let components = |m: &MetricsReport| {
+        crate::risk::calculate_risk_components(&crate::metrics::RawMetrics {
+            cc: m.cc as usize,
+            nd: m.nd as usize,
+            fo: m.fo as usize,
+            ns: m.ns as usize,
+            loc: m.loc as usize,
+            callee_names: vec![],
+        })
+    };
+    let (b, a) = (components(before), components(after));
+    let unwrap_term =
+        |m: &MetricsReport| crate::risk::unwrap_risk(m.unwrap_count.unwrap_or(0) as usize);
+    let terms = [
+        ("cc", w.cc * (a.r_cc - b.r_cc)),
+        ("nd", w.nd * (a.r_nd - b.r_nd)),
+        ("fo", w.fo * (a.r_fo - b.r_fo)),
+        ("ns", w.ns * (a.r_ns - b.r_ns)),
+        (
+            "unwrap",
+            w.unwrap * (unwrap_term(after) - unwrap_term(before)),
+        ),
+    ];
+    // First of equal contributions wins, so ties resolve in CC, ND, FO, NS,
+    // unwrap order
+    terms
+        .iter()
+        .map(|&(name, change)| (name, change * lrs_change.signum()))
+        .filter(|&(_, moved)| moved > 0.0)
+        .fold(
+            None,
+            |best: Option<(&str, f64)>, (name, moved)| match best {
+                Some((_, top)) if top >= moved => best,
+                _ => Some((name, moved)),
+            },
+        )
+        .map(|(name, _)| name.to_string())
 }
 
 /// Compute delta for a deleted function (all values negative)
@@ -450,6 +789,7 @@ fn compute_delete_delta(parent: &FunctionSnapshot) -> FunctionDelta {
         fo: -(parent.metrics.fo as i64),
         ns: -(parent.metrics.ns as i64),
         lrs: -parent.lrs,
+        driver: None,
     }
 }
 
@@ -499,6 +839,118 @@ pub fn compute_delta(repo_root: &Path, current: &Snapshot) -> Result<Delta> {
     Delta::new(current, parent.as_ref())
 }
 
+/// Conventional location of a committed baseline report, relative to the project root
+pub const BASELINE_FILE: &str = ".hotspots/baseline.json";
+
+/// Compute a delta for the working tree against a committed baseline report
+///
+/// The baseline is the JSON report array written by `hotspots analyze --format json`.
+/// Unlike [`compute_delta`], this needs no git history or persisted snapshots: the
+/// "before" side comes entirely from `baseline_path`. File paths on both sides are
+/// made relative to `project_root` so absolute paths from different checkouts match.
+/// `weights` are the LRS weights both sides were scored with.
+///
+/// # Errors
+///
+/// Returns error if the baseline file cannot be read or is not a report array.
+pub fn compute_baseline_file_delta(
+    project_root: &Path,
+    baseline_path: &Path,
+    current: Vec<FunctionRiskReport>,
+    weights: &LrsWeights,
+) -> Result<Delta> {
+    let content = std::fs::read_to_string(baseline_path)
+        .with_context(|| format!("failed to read baseline: {}", baseline_path.display()))?;
+    let baseline: Vec<FunctionRiskReport> = serde_json::from_str(&content).with_context(|| {
+        format!(
+            "failed to parse baseline {} (expected `hotspots analyze --format json` output)",
+            baseline_path.display()
+        )
+    })?;
+    let before = Snapshot::new(
+        crate::git::GitContext::placeholder("baseline", Vec::new()),
+        relativize_reports(baseline, project_root),
+    );
+    let after = Snapshot::new(
+        crate::git::GitContext::placeholder("working-tree", vec!["baseline".to_string()]),
+        relativize_reports(current, project_root),
+    );
+    Delta::new_with_weights(&after, Some(&before), weights)
+}
+
+/// Compute a delta between two directory trees that aren't tied to commits
+///
+/// Each side's reports are made relative to its own root, so the same file in
+/// `before_root` and `after_root` yields the same `function_id`. The commit
+/// fields of the result carry the two roots as labels. `weights` are the LRS
+/// weights both sides were scored with.
+pub fn compute_tree_delta(
+    before_root: &Path,
+    before: Vec<FunctionRiskReport>,
+    after_root: &Path,
+    after: Vec<FunctionRiskReport>,
+    weights: &LrsWeights,
+) -> Result<Delta> {
+    let before_label = before_root.display().to_string();
+    let before = Snapshot::new(
+        crate::git::GitContext::placeholder(&before_label, Vec::new()),
+        relativize_reports(before, before_root),
+    );
+    let after = Snapshot::new(
+        crate::git::GitContext::placeholder(&after_root.display().to_string(), vec![before_label]),
+        relativize_reports(after, after_root),
+    );
+    Delta::new_with_weights(&after, Some(&before), weights)
+}
+
+/// Compute per-function deltas between two versions of one source file
+///
+/// Both strings are analyzed in memory with default weights and thresholds
+/// (see [`crate::analyze_source`]), so there is no git history, snapshot, or
+/// file to read, e.g. for a pre-commit hook holding both blobs from
+/// `git show`. Function IDs name the file `source.<ext>` after the
+/// language's first extension, such as `source.rs::parse`.
+///
+/// # Errors
+///
+/// Returns error if either side fails to parse.
+pub fn diff_sources(
+    before: &str,
+    after: &str,
+    language: crate::language::Language,
+) -> Result<Vec<FunctionDeltaEntry>> {
+    let filename = format!("source.{}", language.extensions()[0]);
+    let options = crate::AnalysisOptions {
+        min_lrs: None,
+        top_n: None,
+    };
+    let analyze = |source: &str, side: &str| {
+        crate::analyze_source(source, &filename, &options)
+            .with_context(|| format!("failed to analyze {side} source"))
+    };
+    let before = Snapshot::new(
+        crate::git::GitContext::placeholder("before", Vec::new()),
+        analyze(before, "before")?,
+    );
+    let after = Snapshot::new(
+        crate::git::GitContext::placeholder("after", vec!["before".to_string()]),
+        analyze(after, "after")?,
+    );
+    Ok(Delta::new(&after, Some(&before))?.deltas)
+}
+
+fn relativize_reports(
+    mut reports: Vec<FunctionRiskReport>,
+    project_root: &Path,
+) -> Vec<FunctionRiskReport> {
+    for report in &mut reports {
+        if let Ok(rel) = Path::new(&report.file).strip_prefix(project_root) {
+            report.set_file(rel.to_string_lossy().into_owned());
+        }
+    }
+    reports
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -539,6 +991,12 @@ mod tests {
                 fo: 3,
                 ns: 1,
                 loc: 10,
+                sloc: None,
+                return_points: None,
+                unwrap_count: None,
+                max_arm_loc: None,
+                max_data_nesting: None,
+                unreachable_code: None,
             },
             risk: crate::report::RiskReport {
                 r_cc: 2.0,
@@ -553,6 +1011,7 @@ mod tests {
             pattern_details: None,
             callees: vec![],
             explanation: None,
+            location: None,
         };
 
         Snapshot::new(git_context, vec![report])
@@ -566,6 +1025,7 @@ mod tests {
         let delta = Delta::new(&current, None).expect("should create baseline delta");
 
         assert!(delta.baseline);
+        assert_eq!(delta.status, DeltaStatus::Baseline);
         assert_eq!(delta.deltas.len(), 1);
         assert_eq!(delta.deltas[0].status, FunctionStatus::New);
     }
@@ -578,6 +1038,7 @@ mod tests {
         let delta = Delta::new(&current, Some(&parent)).expect("should create delta");
 
         assert!(!delta.baseline);
+        assert_eq!(delta.status, DeltaStatus::Changes);
         assert_eq!(delta.deltas.len(), 1);
         assert_eq!(delta.deltas[0].status, FunctionStatus::Modified);
 
@@ -602,6 +1063,10 @@ mod tests {
         assert_eq!(delta.deltas[0].status, FunctionStatus::Unchanged);
         assert!(delta.deltas[0].delta.is_none());
         assert!(delta.deltas[0].band_transition.is_none());
+
+        assert_eq!(delta.status, DeltaStatus::NoChange);
+        let json = delta.to_json().expect("should serialize");
+        assert!(json.contains("\"status\": \"no-change\""));
     }
 
     #[test]
@@ -616,6 +1081,53 @@ mod tests {
         assert!(delta_values.lrs < 0.0); // Negative LRS delta allowed
     }
 
+    #[test]
+    fn test_lrs_driver_follows_configured_weights() {
+        let parent = create_test_snapshot("parent123", "grandparent", 4, 3.9, "moderate");
+        let mut current = create_test_snapshot("current123", "parent123", 6, 6.2, "high");
+        current.functions[0].metrics.nd = 5;
+        let weighted = |cc: f64, nd: f64| LrsWeights {
+            cc,
+            nd,
+            ..LrsWeights::default()
+        };
+
+        let delta = Delta::new_with_weights(&current, Some(&parent), &weighted(1.0, 0.0))
+            .expect("should create delta");
+        let entry = &delta.deltas[0];
+        assert_eq!(entry.delta.as_ref().unwrap().driver.as_deref(), Some("cc"));
+        assert_eq!(entry.driver_summary().as_deref(), Some("CC 4→6"));
+        assert_eq!(entry.metric_breakdown().as_deref(), Some("CC 4→6 · ND +3"));
+
+        let delta = Delta::new_with_weights(&current, Some(&parent), &weighted(0.0, 1.0))
+            .expect("should create delta");
+        assert_eq!(
+            delta.deltas[0].metric_breakdown().as_deref(),
+            Some("ND 2→5 · CC +2")
+        );
+
+        // The driver is the metric that pulled LRS down
+        let delta = Delta::new_with_weights(&parent, Some(&current), &weighted(1.0, 0.0))
+            .expect("should create delta");
+        assert_eq!(
+            delta.deltas[0].metric_breakdown().as_deref(),
+            Some("CC 6→4 · ND -3")
+        );
+
+        // Rust unwrap sites compete once `weights.unwrap` scores them
+        current.functions[0].metrics.unwrap_count = Some(7);
+        let unwrap_heavy = LrsWeights {
+            unwrap: 5.0,
+            ..weighted(0.1, 0.1)
+        };
+        let delta = Delta::new_with_weights(&current, Some(&parent), &unwrap_heavy)
+            .expect("should create delta");
+        assert_eq!(
+            delta.deltas[0].metric_breakdown().as_deref(),
+            Some("UNWRAP 0→7 · CC +2 · ND +3")
+        );
+    }
+
     #[test]
     fn test_deleted_function() {
         let parent = create_test_snapshot("parent123", "grandparent", 5, 4.8, "moderate");
@@ -642,4 +1154,151 @@ mod tests {
         assert!(delta.deltas[0].before.is_some());
         assert!(delta.deltas[0].after.is_none());
     }
+
+    #[test]
+    fn test_deleted_functions_tally_risk_removed() {
+        let mut parent = create_test_snapshot("parent123", "grandparent", 12, 9.5, "critical");
+        parent.functions[0].activity_risk = Some(7.25);
+        let mut current = create_test_snapshot("current123", "parent123", 12, 9.5, "critical");
+        current.functions.clear();
+
+        let delta = Delta::new(&current, Some(&parent)).expect("should create delta");
+        let removed = delta.risk_removed.expect("deletion should be credited");
+        assert_eq!(removed.functions, 1);
+        assert!((removed.lrs - 9.5).abs() < 1e-9);
+        assert_eq!(removed.activity_risk, Some(7.25));
+        assert_eq!((removed.critical, removed.high), (1, 0));
+
+        // Nothing deleted: nothing to credit
+        let current = create_test_snapshot("current123", "parent123", 6, 6.2, "high");
+        let delta = Delta::new(&current, Some(&parent)).expect("should create delta");
+        assert!(delta.risk_removed.is_none());
+    }
+
+    fn report_in(root: &Path, function: &str, cc: u32, lrs: f64) -> FunctionRiskReport {
+        FunctionRiskReport {
+            file: root.join("src/foo.ts").to_string_lossy().into_owned(),
+            function: function.to_string(),
+            line: 1,
+            language: Language::TypeScript,
+            metrics: MetricsReport {
+                cc,
+                nd: 0,
+                fo: 0,
+                ns: 0,
+                loc: 5,
+                sloc: None,
+                return_points: None,
+                unwrap_count: None,
+                max_arm_loc: None,
+                max_data_nesting: None,
+                unreachable_code: None,
+            },
+            risk: crate::report::RiskReport {
+                r_cc: 0.0,
+                r_nd: 0.0,
+                r_fo: 0.0,
+                r_ns: 0.0,
+            },
+            lrs,
+            band: RiskBand::Low,
+            suppression_reason: None,
+            patterns: vec![],
+            pattern_details: None,
+            callees: vec![],
+            explanation: None,
+            location: None,
+        }
+    }
+
+    #[test]
+    fn test_baseline_file_delta_keeps_only_regressions() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        let report = |function: &str, cc: u32, lrs: f64| report_in(root, function, cc, lrs);
+        let baseline = vec![report("worse", 2, 1.0), report("better", 4, 3.0)];
+        let baseline_path = root.join("baseline.json");
+        std::fs::write(&baseline_path, serde_json::to_string(&baseline).unwrap()).unwrap();
+
+        let current = vec![
+            report("worse", 5, 2.5),
+            report("better", 2, 1.0),
+            report("added", 9, 6.0),
+        ];
+        let mut delta =
+            compute_baseline_file_delta(root, &baseline_path, current, &LrsWeights::default())
+                .unwrap();
+        assert!(!delta.baseline);
+        assert_eq!(delta.deltas.len(), 3);
+
+        delta.retain_regressions();
+        assert_eq!(delta.deltas.len(), 1);
+        assert_eq!(delta.deltas[0].function_id, "src/foo.ts::worse");
+        assert_eq!(delta.status, DeltaStatus::Changes);
+    }
+
+    #[test]
+    fn test_tree_delta_keys_functions_relative_to_each_root() {
+        let (before_root, after_root) = (Path::new("/work/main"), Path::new("/work/refactor"));
+        let before = vec![
+            report_in(before_root, "parse", 8, 5.0),
+            report_in(before_root, "same", 2, 1.0),
+        ];
+        let after = vec![
+            report_in(after_root, "parse", 3, 2.0),
+            report_in(after_root, "same", 2, 1.0),
+            report_in(after_root, "helper", 2, 1.0),
+        ];
+
+        let delta = compute_tree_delta(
+            before_root,
+            before,
+            after_root,
+            after,
+            &LrsWeights::default(),
+        )
+        .unwrap();
+        let status_of = |id: &str| {
+            delta
+                .deltas
+                .iter()
+                .find(|e| e.function_id == id)
+                .map(|e| e.status.clone())
+        };
+        assert_eq!(
+            status_of("src/foo.ts::parse"),
+            Some(FunctionStatus::Modified)
+        );
+        assert_eq!(
+            status_of("src/foo.ts::same"),
+            Some(FunctionStatus::Unchanged)
+        );
+        assert_eq!(status_of("src/foo.ts::helper"), Some(FunctionStatus::New));
+        assert_eq!(delta.deltas.len(), 3);
+        assert_eq!(delta.commit.parent, "/work/main");
+    }
+
+    #[test]
+    fn test_diff_sources_compares_two_strings() {
+        let before = "fn parse(x: i32) -> i32 {\n    x\n}\n\nfn gone() {}\n";
+        let after = "fn parse(x: i32) -> i32 {\n    if x > 0 {\n        return x;\n    }\n    0\n}\n\nfn added() {}\n";
+        let deltas = diff_sources(before, after, Language::Rust).unwrap();
+        let status_of = |id: &str| {
+            deltas
+                .iter()
+                .find(|e| e.function_id == id)
+                .map(|e| e.status.clone())
+        };
+        assert_eq!(deltas.len(), 3);
+        assert_eq!(
+            status_of("source.rs::parse"),
+            Some(FunctionStatus::Modified)
+        );
+        assert_eq!(status_of("source.rs::gone"), Some(FunctionStatus::Deleted));
+        assert_eq!(status_of("source.rs::added"), Some(FunctionStatus::New));
+        let parse = deltas.iter().find(|e| e.function_id == "source.rs::parse");
+        assert!(parse.and_then(|e| e.delta.as_ref()).unwrap().cc > 0);
+
+        assert!(diff_sources("fn broken( {", after, Language::Rust).is_err());
+    }
 }
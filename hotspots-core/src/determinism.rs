@@ -0,0 +1,185 @@
+//! Determinism self-test: analyze the same tree twice and compare the output
+//!
+//! Backs `hotspots verify-determinism`. Both runs render their reports with
+//! [`render_json`], so any difference breaks the crate-wide guarantee that
+//! identical input yields byte-for-byte identical output. The second run can
+//! be fed its files in reverse order, which catches results that depend on
+//! traversal order rather than on content.
+
+use crate::config::ResolvedConfig;
+use crate::report::render_json;
+use crate::AnalysisOptions;
+use anyhow::Result;
+use std::path::Path;
+
+/// Diff lines shown before the rest of a mismatch is elided
+const MAX_DIFF_LINES: usize = 200;
+
+/// Unchanged lines shown around a differing region
+const CONTEXT_LINES: usize = 3;
+
+/// Rendered output of two analysis runs over the same path
+#[derive(Debug, Clone)]
+pub struct DeterminismCheck {
+    /// Source files analyzed in each run, after include/exclude filters
+    pub files: usize,
+    /// Functions reported by the first run
+    pub functions: usize,
+    /// JSON output of the first run
+    pub first: String,
+    /// JSON output of the second run
+    pub second: String,
+}
+
+impl DeterminismCheck {
+    /// Whether both runs produced byte-for-byte identical output
+    pub fn is_identical(&self) -> bool {
+        self.first == self.second
+    }
+
+    /// Line diff from the first run's output to the second's, or `None` when
+    /// they are identical
+    pub fn diff(&self) -> Option<String> {
+        line_diff(&self.first, &self.second)
+    }
+}
+
+/// Analyze `path` twice and keep both rendered outputs for comparison
+///
+/// Files are selected as `analyze` selects them. With `reverse_order`, the
+/// second run processes them last to first.
+///
+/// # Errors
+///
+/// Returns error if no source file under `path` passes the config's filters,
+/// or if either run fails.
+pub fn verify_determinism(
+    path: &Path,
+    resolved_config: Option<&ResolvedConfig>,
+    reverse_order: bool,
+) -> Result<DeterminismCheck> {
+    let source_files: Vec<_> = crate::collect_source_files(path)?
+        .into_iter()
+        .filter(|f| resolved_config.map_or(true, |c| c.should_include(f)))
+        .collect();
+    if source_files.is_empty() {
+        anyhow::bail!(
+            "no source files to analyze in {} (check include/exclude patterns)",
+            path.display()
+        );
+    }
+    let require_functions = resolved_config.is_some_and(|c| c.require_functions);
+    let run = |files: Vec<std::path::PathBuf>| {
+        let options = AnalysisOptions {
+            min_lrs: None,
+            top_n: None,
+        };
+        crate::run_analysis(
+            files,
+            options,
+            resolved_config,
+            None,
+            None,
+            require_functions,
+        )
+    };
+
+    let first = run(source_files.clone())?;
+    let mut second_files = source_files.clone();
+    if reverse_order {
+        second_files.reverse();
+    }
+    let second = run(second_files)?;
+
+    Ok(DeterminismCheck {
+        files: source_files.len(),
+        functions: first.len(),
+        first: render_json(&first),
+        second: render_json(&second),
+    })
+}
+
+/// Unified-style diff of the region where `first` and `second` differ, or
+/// `None` when they are identical
+///
+/// Lines shared at the start and end are trimmed, leaving one hunk with a few
+/// lines of context. Output past [`MAX_DIFF_LINES`] is elided.
+pub fn line_diff(first: &str, second: &str) -> Option<String> {
+    if first == second {
+        return None;
+    }
+    let old: Vec<&str> = first.lines().collect();
+    let new: Vec<&str> = second.lines().collect();
+    let prefix = old.iter().zip(&new).take_while(|(a, b)| a == b).count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let old_changed = &old[prefix..old.len() - suffix];
+    let new_changed = &new[prefix..new.len() - suffix];
+    let before = &old[prefix.saturating_sub(CONTEXT_LINES)..prefix];
+    let after = &old[old.len() - suffix..][..suffix.min(CONTEXT_LINES)];
+
+    let start = prefix - before.len();
+    let mut lines: Vec<String> = before.iter().map(|l| format!(" {l}")).collect();
+    lines.extend(old_changed.iter().map(|l| format!("-{l}")));
+    lines.extend(new_changed.iter().map(|l| format!("+{l}")));
+    lines.extend(after.iter().map(|l| format!(" {l}")));
+    if old_changed.is_empty() && new_changed.is_empty() {
+        lines.push("\\ outputs differ only in line endings".to_string());
+    }
+
+    let context = before.len() + after.len();
+    let mut out = format!(
+        "@@ -{},{} +{},{} @@\n",
+        start + 1,
+        old_changed.len() + context,
+        start + 1,
+        new_changed.len() + context
+    );
+    for line in lines.iter().take(MAX_DIFF_LINES) {
+        out.push_str(line);
+        out.push('\n');
+    }
+    if lines.len() > MAX_DIFF_LINES {
+        out.push_str(&format!(
+            "... {} more diff lines\n",
+            lines.len() - MAX_DIFF_LINES
+        ));
+    }
+    Some(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_line_diff_trims_shared_lines_to_one_hunk() {
+        assert_eq!(line_diff("a\nb\n", "a\nb\n"), None);
+
+        let first = "1\n2\n3\n4\n5\nold\n6\n7\n";
+        let second = "1\n2\n3\n4\n5\nnew\nnewer\n6\n7\n";
+        assert_eq!(
+            line_diff(first, second).unwrap(),
+            "@@ -3,6 +3,7 @@\n 3\n 4\n 5\n-old\n+new\n+newer\n 6\n 7\n"
+        );
+
+        // A difference only in the trailing newline still counts
+        assert_eq!(
+            line_diff("a\n", "a").unwrap(),
+            "@@ -1,1 +1,1 @@\n a\n\\ outputs differ only in line endings\n"
+        );
+    }
+
+    #[test]
+    fn test_line_diff_elides_long_hunks() {
+        let first: String = (0..300).map(|i| format!("a{i}\n")).collect();
+        let second: String = (0..300).map(|i| format!("b{i}\n")).collect();
+        let diff = line_diff(&first, &second).unwrap();
+        assert_eq!(diff.lines().count(), MAX_DIFF_LINES + 2);
+        assert!(diff.ends_with("... 400 more diff lines\n"));
+    }
+}
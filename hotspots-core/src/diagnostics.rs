@@ -0,0 +1,44 @@
+//! Stderr diagnostic level
+//!
+//! Warnings and informational notices (skipped files, cache status, config
+//! discovery) are printed to stderr only when their level is at or below the
+//! process-wide threshold. `--quiet` lowers the threshold to `Error`; errors
+//! themselves are returned as `anyhow::Error` and are never gated here.
+//!
+//! The threshold only controls what is written to stderr and never affects
+//! analysis results or stdout.
+
+use std::sync::atomic::{AtomicU8, Ordering};
+
+/// Severity of a stderr diagnostic, from most to least important
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Level {
+    Error = 0,
+    Warn = 1,
+    Info = 2,
+}
+
+static THRESHOLD: AtomicU8 = AtomicU8::new(Level::Info as u8);
+
+/// Set the most verbose level that is still printed
+pub fn set_threshold(level: Level) {
+    THRESHOLD.store(level as u8, Ordering::Relaxed);
+}
+
+/// Whether diagnostics at `level` should be printed
+pub fn enabled(level: Level) -> bool {
+    level as u8 <= THRESHOLD.load(Ordering::Relaxed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_levels_are_ordered_by_importance() {
+        assert!(Level::Error < Level::Warn);
+        assert!(Level::Warn < Level::Info);
+        // Default threshold prints everything
+        assert!(enabled(Level::Info));
+    }
+}
@@ -9,6 +9,7 @@
 //! - Function expressions (`FnExpr`)
 //! - Arrow functions (`ArrowExpr`)
 //! - Class methods (`ClassMethod`)
+//! - Class constructors (`Constructor`)
 //! - Object literal methods (`MethodProp`)
 //!
 //! Ignored constructs (automatically excluded as they have no function bodies):
@@ -16,9 +17,16 @@
 //! - Type aliases
 //! - Overload signatures without bodies (filtered by `if let Some(body)`)
 //! - Ambient declarations
+//!
+//! Decorators (`@Component(...)`, `@Get()`, parameter decorators) are not part
+//! of the function they annotate: decorated method spans start at the method
+//! key, and functions nested inside decorator arguments are not discovered.
+//! A suppression comment may still sit above the decorator stack.
 
-use crate::ast::{FunctionId, FunctionNode};
-use crate::language::{span::span_with_location, FunctionBody};
+use crate::ast::{FunctionId, FunctionKind, FunctionNode};
+use crate::language::{span::span_with_location, FunctionBody, SourceSpan};
+use std::collections::HashMap;
+use swc_common::Spanned;
 use swc_ecma_ast::*;
 use swc_ecma_visit::{Visit, VisitWith};
 
@@ -37,6 +45,7 @@ pub fn discover_functions(
         local_index: 0,
         source_map,
         pending_name: None,
+        decorated_spans: HashMap::new(),
     };
 
     module.visit_with(&mut collector);
@@ -45,6 +54,7 @@ pub fn discover_functions(
     collector.functions.sort_by_key(|f| f.span.start);
 
     // Assign IDs and extract suppressions based on sorted order
+    let decorated_spans = collector.decorated_spans;
     collector
         .functions
         .into_iter()
@@ -54,9 +64,14 @@ pub fn discover_functions(
                 file_index,
                 local_index: idx,
             };
-            // Extract suppression comment for this function
+            // Extract suppression comment for this function; for decorated
+            // methods the comment sits above the first decorator
+            let leading_span = decorated_spans
+                .get(&func.span.start)
+                .copied()
+                .unwrap_or(func.span);
             func.suppression_reason =
-                crate::suppression::extract_suppression(source, func.span, source_map);
+                crate::suppression::extract_suppression(source, leading_span, source_map);
             func
         })
         .collect()
@@ -72,9 +87,17 @@ struct FunctionCollector<'a> {
     /// (e.g. `const Foo = () => {...}`), set while visiting the declarator's
     /// init expression so the function picks it up instead of `<anonymous>`.
     pending_name: Option<String>,
+    /// Full spans (including decorators) of decorated methods, keyed by the
+    /// start of the decorator-free span recorded on the `FunctionNode`.
+    decorated_spans: HashMap<usize, SourceSpan>,
 }
 
 impl<'a> Visit for FunctionCollector<'a> {
+    fn visit_decorator(&mut self, _decorator: &Decorator) {
+        // Decorator arguments (e.g. `@UseGuards(() => ...)`) are metadata, not
+        // functions of the annotated class or method
+    }
+
     fn visit_var_declarator(&mut self, decl: &VarDeclarator) {
         if let (Pat::Ident(ident), Some(init)) = (&decl.name, &decl.init) {
             if matches!(&**init, Expr::Fn(_) | Expr::Arrow(_)) {
@@ -99,6 +122,7 @@ impl<'a> Visit for FunctionCollector<'a> {
                     local_index: self.local_index,
                 },
                 name,
+                kind: FunctionKind::Function,
                 span: span_with_location(decl.function.span, self.source_map),
                 body: FunctionBody::ecmascript(body),
                 suppression_reason: None,
@@ -129,6 +153,7 @@ impl<'a> Visit for FunctionCollector<'a> {
                     local_index: self.local_index,
                 },
                 name,
+                kind: FunctionKind::Closure,
                 span: span_with_location(expr.function.span, self.source_map),
                 body: FunctionBody::ecmascript(body),
                 suppression_reason: None,
@@ -153,6 +178,7 @@ impl<'a> Visit for FunctionCollector<'a> {
                         local_index: self.local_index,
                     },
                     name,
+                    kind: FunctionKind::Closure,
                     span: span_with_location(arrow.span, self.source_map),
                     body: FunctionBody::ecmascript(body.clone()),
                     suppression_reason: None,
@@ -178,6 +204,7 @@ impl<'a> Visit for FunctionCollector<'a> {
                         local_index: self.local_index,
                     },
                     name,
+                    kind: FunctionKind::Closure,
                     span: span_with_location(arrow.span, self.source_map),
                     body: FunctionBody::ecmascript(body),
                     suppression_reason: None,
@@ -204,13 +231,25 @@ impl<'a> Visit for FunctionCollector<'a> {
         let body = method.function.body.clone();
 
         if let Some(body) = body {
+            let full_span = span_with_location(method.span, self.source_map);
+            let span = if method.function.decorators.is_empty() {
+                full_span
+            } else {
+                // Start at the method key so decorator lines don't count
+                // towards the method's location or size
+                let trimmed = method.span.with_lo(method.key.span().lo);
+                let span = span_with_location(trimmed, self.source_map);
+                self.decorated_spans.insert(span.start, full_span);
+                span
+            };
             self.functions.push(FunctionNode {
                 id: FunctionId {
                     file_index: self.file_index,
                     local_index: self.local_index,
                 },
                 name,
-                span: span_with_location(method.span, self.source_map),
+                kind: FunctionKind::Method,
+                span,
                 body: FunctionBody::ecmascript(body),
                 suppression_reason: None,
             });
@@ -221,6 +260,26 @@ impl<'a> Visit for FunctionCollector<'a> {
         method.visit_children_with(self);
     }
 
+    fn visit_constructor(&mut self, ctor: &Constructor) {
+        if let Some(body) = ctor.body.clone() {
+            self.functions.push(FunctionNode {
+                id: FunctionId {
+                    file_index: self.file_index,
+                    local_index: self.local_index,
+                },
+                name: Some("constructor".to_string()),
+                kind: FunctionKind::Constructor,
+                span: span_with_location(ctor.span, self.source_map),
+                body: FunctionBody::ecmascript(body),
+                suppression_reason: None,
+            });
+            self.local_index += 1;
+        }
+
+        // Continue visiting children
+        ctor.visit_children_with(self);
+    }
+
     fn visit_method_prop(&mut self, method: &MethodProp) {
         let name = match &method.key {
             PropName::Ident(ident) => Some(ident.sym.to_string()),
@@ -241,6 +300,7 @@ impl<'a> Visit for FunctionCollector<'a> {
                     local_index: self.local_index,
                 },
                 name,
+                kind: FunctionKind::Method,
                 span: span_with_location(method.function.span, self.source_map),
                 body: FunctionBody::ecmascript(body),
                 suppression_reason: None,
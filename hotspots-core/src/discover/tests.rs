@@ -136,4 +136,69 @@ mod discover_tests {
             "Should have one statement (return)"
         );
     }
+
+    #[test]
+    fn test_discover_skips_overload_signatures() {
+        let src = r#"
+            function parse(input: string): number;
+            function parse(input: number): string;
+            function parse(input: any): any { return input; }
+        "#;
+        let functions = parse_and_discover(src, 0);
+        assert_eq!(functions.len(), 1, "Only the implementation has a body");
+        assert_eq!(functions[0].span.start_line, 4);
+    }
+
+    #[test]
+    fn test_discover_decorated_method_starts_at_key() {
+        let src = r#"class C {
+    // hotspots-ignore: framework entry point
+    @Get(":id")
+    @UseGuards(() => true)
+    findOne(id: string) {
+        return id;
+    }
+}"#;
+        let functions = parse_and_discover(src, 0);
+        assert_eq!(
+            functions.len(),
+            1,
+            "Functions inside decorator arguments should not be discovered"
+        );
+        assert_eq!(functions[0].name, Some("findOne".to_string()));
+        assert_eq!(functions[0].span.start_line, 5);
+        assert_eq!(
+            functions[0].suppression_reason,
+            Some("framework entry point".to_string()),
+            "Suppression above the decorator stack should apply"
+        );
+    }
+
+    #[test]
+    fn test_discover_records_function_kind() {
+        use crate::ast::FunctionKind;
+
+        let src = r#"
+            function top() { return 1; }
+            const expr = function() { return 2; };
+            const arrow = () => 3;
+            class C { constructor() { this.x = 0; } run() { return 4; } }
+            const obj = { go() { return 5; } };
+        "#;
+        let kinds: Vec<_> = parse_and_discover(src, 0)
+            .into_iter()
+            .map(|f| (f.name.unwrap(), f.kind))
+            .collect();
+        assert_eq!(
+            kinds,
+            vec![
+                ("top".to_string(), FunctionKind::Function),
+                ("expr".to_string(), FunctionKind::Closure),
+                ("arrow".to_string(), FunctionKind::Closure),
+                ("constructor".to_string(), FunctionKind::Constructor),
+                ("run".to_string(), FunctionKind::Method),
+                ("go".to_string(), FunctionKind::Method),
+            ]
+        );
+    }
 }
@@ -0,0 +1,150 @@
+//! Explicit call-graph roots from `.hotspots/entrypoints.txt`
+//!
+//! Some entry points can't be recognised by name: handlers registered through
+//! dynamic dispatch, framework callbacks, CLI subcommands wired up by macros.
+//! Listing them in `.hotspots/entrypoints.txt` makes them the roots for
+//! `is_entrypoint` and `dependency_depth` instead of the name heuristics.
+//!
+//! Format: one `file::function` per line, with `file` relative to the repo root
+//! and `function` exactly as it appears in function IDs (e.g. `Server::start`
+//! for a Rust method). Blank lines and lines starting with `#` are ignored.
+//!
+//! Entries that don't name a known function are reported and skipped; if none
+//! resolve, the name heuristics stay in effect.
+
+use crate::callgraph::CallGraph;
+use crate::diagnostics::{self, Level};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// File name of the entry point list inside `.hotspots/`
+pub const ENTRYPOINTS_FILE: &str = "entrypoints.txt";
+
+/// Path to the entry point list for a repository
+///
+/// The list is checked in with the code, so it stays under the repository's
+/// own `.hotspots/` even when `--store-dir` relocates snapshot storage.
+pub fn entrypoints_path(repo_root: &Path) -> PathBuf {
+    repo_root.join(".hotspots").join(ENTRYPOINTS_FILE)
+}
+
+/// Parse the entry point list, skipping blank lines and `#` comments
+pub fn parse_entry_points(text: &str) -> Vec<String> {
+    text.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(str::to_string)
+        .collect()
+}
+
+/// Listed entry points split by whether they name a function in the graph
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ResolvedEntryPoints {
+    /// Graph function IDs, in listing order
+    pub resolved: Vec<String>,
+    /// Listed lines that matched no function
+    pub unresolved: Vec<String>,
+}
+
+/// Match listed `file::function` entries against the graph's function IDs.
+///
+/// Graph IDs may carry absolute or `./`-prefixed paths depending on how
+/// analysis was invoked, so both sides are compared repo-relative.
+pub fn resolve_entry_points(
+    graph: &CallGraph,
+    repo_root: &Path,
+    listed: &[String],
+) -> ResolvedEntryPoints {
+    let by_relative_id: HashMap<String, &str> = graph
+        .all_ids()
+        .filter_map(|id| relative_id(id, repo_root).map(|rel| (rel, id)))
+        .collect();
+
+    let mut result = ResolvedEntryPoints::default();
+    for entry in listed {
+        match relative_id(entry, repo_root).and_then(|rel| by_relative_id.get(&rel)) {
+            Some(id) => result.resolved.push(id.to_string()),
+            None => result.unresolved.push(entry.clone()),
+        }
+    }
+    result
+}
+
+/// Load `.hotspots/entrypoints.txt` and install it as the graph's roots.
+///
+/// A missing file is a no-op. An unreadable file and entries that don't
+/// resolve are warned about and skipped rather than failing the analysis.
+pub fn apply(graph: &mut CallGraph, repo_root: &Path) {
+    let path = entrypoints_path(repo_root);
+    let text = match std::fs::read_to_string(&path) {
+        Ok(text) => text,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return,
+        Err(e) => {
+            if diagnostics::enabled(Level::Warn) {
+                eprintln!("Warning: failed to read {}: {}", path.display(), e);
+            }
+            return;
+        }
+    };
+
+    let entries = resolve_entry_points(graph, repo_root, &parse_entry_points(&text));
+    if diagnostics::enabled(Level::Warn) {
+        for entry in &entries.unresolved {
+            eprintln!(
+                "Warning: {}: entry point '{}' does not match any analyzed function, skipping",
+                path.display(),
+                entry
+            );
+        }
+    }
+    graph.set_entry_points(entries.resolved.iter().map(String::as_str));
+}
+
+/// `file::function` with the file made repo-relative and `/`-separated
+fn relative_id(id: &str, repo_root: &Path) -> Option<String> {
+    let (file, function) = id.split_once("::")?;
+    let path = Path::new(file);
+    let rel = path.strip_prefix(repo_root).unwrap_or(path);
+    let rel = rel.strip_prefix(".").unwrap_or(rel);
+    Some(format!(
+        "{}::{}",
+        rel.to_string_lossy().replace('\\', "/"),
+        function
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_skips_blank_lines_and_comments() {
+        let text = "# roots\nsrc/server.ts::handle\n\n  src/jobs.rs::Worker::run  \n";
+        assert_eq!(
+            parse_entry_points(text),
+            vec!["src/server.ts::handle", "src/jobs.rs::Worker::run"]
+        );
+    }
+
+    #[test]
+    fn test_resolve_matches_repo_relative_entries() {
+        let root = Path::new("/repo");
+        let mut graph = CallGraph::new();
+        graph.add_node("/repo/src/server.ts::handle".to_string());
+        graph.add_node("/repo/src/jobs.rs::Worker::run".to_string());
+
+        let listed = parse_entry_points(
+            "./src/server.ts::handle\nsrc/jobs.rs::Worker::run\nsrc/gone.ts::old\n",
+        );
+        let entries = resolve_entry_points(&graph, root, &listed);
+
+        assert_eq!(
+            entries.resolved,
+            vec![
+                "/repo/src/server.ts::handle",
+                "/repo/src/jobs.rs::Worker::run"
+            ]
+        );
+        assert_eq!(entries.unresolved, vec!["src/gone.ts::old"]);
+    }
+}
@@ -9,6 +9,7 @@
 //!
 //! Uses git CLI directly (no libgit2) for portability.
 
+use crate::diagnostics::{self, Level};
 use anyhow::{Context, Result};
 use regex::Regex;
 use std::path::Path;
@@ -41,6 +42,25 @@ pub struct GitContext {
     pub ticket_ids: Vec<String>,
 }
 
+impl GitContext {
+    /// Context for a snapshot that isn't tied to a commit (a baseline file, a
+    /// `--metrics-only` run), with `sha` as a label and no commit metadata
+    pub fn placeholder(sha: &str, parent_shas: Vec<String>) -> Self {
+        GitContext {
+            head_sha: sha.to_string(),
+            parent_shas,
+            timestamp: 0,
+            branch: None,
+            is_detached: false,
+            message: None,
+            author: None,
+            is_fix_commit: None,
+            is_revert_commit: None,
+            ticket_ids: Vec::new(),
+        }
+    }
+}
+
 /// File churn metrics (lines added/deleted)
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct FileChurn {
@@ -61,6 +81,70 @@ pub struct BatchedTouchMetrics {
     pub days_since_last_change: std::collections::HashMap<String, u32>,
 }
 
+/// Commits left out of churn and touch metrics, such as repo-wide reformatting
+///
+/// The `.git-blame-ignore-revs` idea applied to activity: a commit is ignored
+/// when its SHA starts with one of `revs` or a line of its message matches
+/// one of `messages`.
+#[derive(Debug, Clone, Default, serde::Serialize)]
+pub struct IgnoredCommits {
+    /// Lowercase SHA prefixes
+    pub revs: Vec<String>,
+    /// Message regular expressions, as configured
+    pub messages: Vec<String>,
+    #[serde(skip)]
+    patterns: Vec<Regex>,
+}
+
+impl IgnoredCommits {
+    /// Validate SHA prefixes (4–40 hex digits) and compile message patterns
+    ///
+    /// # Errors
+    ///
+    /// Returns error on a malformed SHA prefix or an invalid regular expression.
+    pub fn new(revs: &[String], messages: &[String]) -> Result<Self> {
+        let revs = revs
+            .iter()
+            .map(|rev| {
+                let rev = rev.trim().to_ascii_lowercase();
+                if !(4..=40).contains(&rev.len()) || !rev.chars().all(|c| c.is_ascii_hexdigit()) {
+                    anyhow::bail!(
+                        "ignore_commits.revs entry \"{}\" must be a commit SHA prefix of 4 to 40 hex digits",
+                        rev
+                    );
+                }
+                Ok(rev)
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let patterns = messages
+            .iter()
+            .map(|m| {
+                regex::RegexBuilder::new(m)
+                    .multi_line(true)
+                    .build()
+                    .with_context(|| format!("invalid ignore_commits.messages pattern: {}", m))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Ok(IgnoredCommits {
+            revs,
+            messages: messages.to_vec(),
+            patterns,
+        })
+    }
+
+    /// Whether nothing is ignored
+    pub fn is_empty(&self) -> bool {
+        self.revs.is_empty() && self.patterns.is_empty()
+    }
+
+    /// Whether the commit `sha` with `message` is ignored
+    pub fn matches(&self, sha: &str, message: &str) -> bool {
+        let sha = sha.to_ascii_lowercase();
+        self.revs.iter().any(|rev| sha.starts_with(rev.as_str()))
+            || self.patterns.iter().any(|re| re.is_match(message))
+    }
+}
+
 /// Environment variables git uses to locate a repository, bypassing normal
 /// cwd-based discovery entirely when set. If the calling process inherits
 /// these (e.g. hotspots-core is invoked from within a git hook, where git
@@ -318,6 +402,18 @@ pub fn resolve_merge_base(target_branch: &str) -> Result<Option<String>> {
     }
 }
 
+/// Merge-base of HEAD and `branch` in the repository at `repo_path`
+///
+/// Unlike [`resolve_merge_base`], failure is an error rather than a fallback:
+/// callers that name the branch explicitly need a real base to compare to.
+pub fn merge_base_with(repo_path: &Path, branch: &str) -> Result<String> {
+    if branch.starts_with('-') {
+        anyhow::bail!("invalid branch name '{branch}'");
+    }
+    git_at(repo_path, &["merge-base", "HEAD", branch])
+        .with_context(|| format!("failed to resolve merge-base of HEAD and '{branch}'"))
+}
+
 /// Resolve merge-base with common target branches
 ///
 /// Tries local and remote-tracking names (main, origin/main, master, …) so
@@ -377,10 +473,12 @@ impl Drop for TempWorktree {
             ],
         );
         if let Err(e) = result {
-            eprintln!(
-                "warning: failed to remove temporary worktree {}: {e}",
-                self.path.display()
-            );
+            if diagnostics::enabled(Level::Warn) {
+                eprintln!(
+                    "warning: failed to remove temporary worktree {}: {e}",
+                    self.path.display()
+                );
+            }
         }
     }
 }
@@ -404,10 +502,12 @@ pub fn create_worktree(repo_root: &Path, sha: &str) -> Result<TempWorktree> {
         &["worktree", "remove", "--force", &dir.to_string_lossy()],
     ) {
         // Not fatal — the entry simply may not exist yet.
-        eprintln!(
-            "warning: failed to remove existing git worktree at {}: {e}",
-            dir.display()
-        );
+        if dir.exists() && diagnostics::enabled(Level::Warn) {
+            eprintln!(
+                "warning: failed to remove existing git worktree at {}: {e}",
+                dir.display()
+            );
+        }
     }
     // Remove the directory itself if it is still present.
     if dir.exists() {
@@ -431,6 +531,15 @@ pub fn create_worktree(repo_root: &Path, sha: &str) -> Result<TempWorktree> {
     })
 }
 
+/// Whether `repo_path` is a shallow clone (e.g. a CI checkout with `--depth 1`).
+///
+/// Churn, touch, and co-change metrics read history that a shallow clone has
+/// truncated, so callers should warn before trusting them. Returns false when
+/// git can't answer (not a repo, or git too old for `--is-shallow-repository`).
+pub fn is_shallow_repository(repo_path: &Path) -> bool {
+    git_at(repo_path, &["rev-parse", "--is-shallow-repository"]).is_ok_and(|out| out == "true")
+}
+
 /// Find the merge-base SHA and Unix timestamp between HEAD and main/master.
 /// Returns None when already on main (merge-base == HEAD) or no divergence found.
 pub fn find_merge_base(repo_root: &Path) -> Option<(String, i64)> {
@@ -600,6 +709,60 @@ pub fn extract_commit_churn_at(repo_path: &Path, sha: &str) -> Result<Vec<FileCh
     Ok(churns)
 }
 
+/// Full SHAs of the commits reachable from HEAD that `ignored` matches
+///
+/// Empty, without running git, when nothing is ignored. With no message
+/// patterns, each SHA prefix is resolved on its own rather than by scanning
+/// the whole history, which may also name commits off HEAD's history; a
+/// prefix that is unknown or ambiguous falls back to the scan.
+///
+/// # Errors
+///
+/// Returns error if `git log` fails.
+pub fn resolve_ignored_commits(
+    repo_root: &Path,
+    ignored: &IgnoredCommits,
+) -> Result<std::collections::HashSet<String>> {
+    if ignored.is_empty() {
+        return Ok(std::collections::HashSet::new());
+    }
+    if ignored.messages.is_empty() {
+        let resolved: Option<std::collections::HashSet<String>> = ignored
+            .revs
+            .iter()
+            .map(|rev| {
+                git_at(
+                    repo_root,
+                    &[
+                        "rev-parse",
+                        "--verify",
+                        "--quiet",
+                        &format!("{rev}^{{commit}}"),
+                    ],
+                )
+                .ok()
+            })
+            .collect();
+        if let Some(shas) = resolved {
+            return Ok(shas);
+        }
+    }
+    let output = git_at(repo_root, &["log", "--format=%H%x00%B%x1e", "HEAD"])
+        .context("failed to list commits for ignore_commits")?;
+    Ok(output
+        .split('\x1e')
+        .filter_map(|record| record.trim_start().split_once('\0'))
+        .filter(|(sha, message)| ignored.matches(sha, message))
+        .map(|(sha, _)| sha.to_string())
+        .collect())
+}
+
+/// Parse a `COMMIT <timestamp> <sha>` marker line from a touch-metric `git log`
+fn parse_commit_marker(line: &str) -> Option<(i64, &str)> {
+    let (ts, sha) = line.strip_prefix("COMMIT ")?.trim().split_once(' ')?;
+    Some((ts.parse().ok()?, sha))
+}
+
 /// Compute touch metrics for all files in a repository using two git log calls.
 ///
 /// Replaces the previous O(files) approach (one subprocess per file) with two
@@ -612,9 +775,12 @@ pub fn extract_commit_churn_at(repo_path: &Path, sha: &str) -> Result<Vec<FileCh
 ///
 /// Call 2 (fallback): for any file not seen in call 1, a single `git log -1 --format=%ct`
 ///   call per file (typically very few files; most active files appear in the window).
+///
+/// Commits in `ignored` (see [`resolve_ignored_commits`]) are not counted.
 pub fn batch_touch_metrics_at(
     repo_root: &Path,
     as_of_timestamp: i64,
+    ignored: &std::collections::HashSet<String>,
 ) -> Result<BatchedTouchMetrics> {
     use std::collections::HashMap;
 
@@ -627,7 +793,7 @@ pub fn batch_touch_metrics_at(
         repo_root,
         &[
             "log",
-            "--format=COMMIT %ct",
+            "--format=COMMIT %ct %H",
             "--name-only",
             &since_arg,
             &until_arg,
@@ -638,11 +804,14 @@ pub fn batch_touch_metrics_at(
     let mut touch_count: HashMap<String, usize> = HashMap::new();
     let mut last_touch_ts: HashMap<String, i64> = HashMap::new();
     let mut current_ts: i64 = 0;
+    let mut skipping = false;
 
     for line in window_output.lines() {
-        if let Some(ts_str) = line.strip_prefix("COMMIT ") {
-            current_ts = ts_str.trim().parse().unwrap_or(0);
-        } else if !line.trim().is_empty() {
+        if line.starts_with("COMMIT ") {
+            let marker = parse_commit_marker(line);
+            current_ts = marker.map_or(0, |(ts, _)| ts);
+            skipping = marker.is_some_and(|(_, sha)| ignored.contains(sha));
+        } else if !skipping && !line.trim().is_empty() {
             let file = line.trim().to_string();
             *touch_count.entry(file.clone()).or_insert(0) += 1;
             // First occurrence = most recent (git log is newest-first)
@@ -668,10 +837,12 @@ pub fn batch_touch_metrics_at(
 /// For files absent from the 30-day window, find the most recent commit timestamp
 /// for each using batched `git log` calls (up to 500 files per invocation).
 /// This replaces O(N) individual `git log -1 -- file` calls with O(N/500) calls.
+/// Commits in `ignored` are passed over.
 pub fn batch_last_touch_for_files(
     repo_path: &Path,
     stale_files: &std::collections::HashSet<&str>,
     as_of_timestamp: i64,
+    ignored: &std::collections::HashSet<String>,
 ) -> std::collections::HashMap<String, u32> {
     use std::collections::HashMap;
     if stale_files.is_empty() {
@@ -685,7 +856,7 @@ pub fn batch_last_touch_for_files(
     for chunk in files.chunks(500) {
         let mut args: Vec<&str> = vec![
             "log",
-            "--format=COMMIT %ct",
+            "--format=COMMIT %ct %H",
             "--name-only",
             &until_arg,
             "--",
@@ -699,8 +870,10 @@ pub fn batch_last_touch_for_files(
 
         let mut current_ts: i64 = 0;
         for line in output.lines() {
-            if let Some(ts_str) = line.strip_prefix("COMMIT ") {
-                current_ts = ts_str.trim().parse().unwrap_or(0);
+            if line.starts_with("COMMIT ") {
+                current_ts = parse_commit_marker(line)
+                    .filter(|(_, sha)| !ignored.contains(*sha))
+                    .map_or(0, |(ts, _)| ts);
             } else if current_ts > 0 && !line.trim().is_empty() {
                 let file = line.trim();
                 if stale_files.contains(file) {
@@ -728,12 +901,14 @@ pub fn batch_last_touch_for_files(
 /// * `start_line` - First line of function (1-based)
 /// * `end_line` - Last line of function (1-based)
 /// * `as_of_timestamp` - Unix timestamp to use as "now"
+/// * `ignored` - Full SHAs of commits to pass over (see [`resolve_ignored_commits`])
 pub fn function_touch_metrics_at(
     repo_path: &Path,
     file: &str,
     start_line: u32,
     end_line: u32,
     as_of_timestamp: i64,
+    ignored: &std::collections::HashSet<String>,
 ) -> Result<(usize, Option<u32>)> {
     let thirty_days_ago = as_of_timestamp - (30 * 24 * 60 * 60);
     let since_arg = format!("--since={}", thirty_days_ago);
@@ -746,7 +921,7 @@ pub fn function_touch_metrics_at(
         &[
             "log",
             &range_arg,
-            "--format=COMMIT %ct",
+            "--format=COMMIT %ct %H",
             &since_arg,
             &until_arg,
         ],
@@ -755,38 +930,87 @@ pub fn function_touch_metrics_at(
 
     let window_timestamps: Vec<i64> = window_output
         .lines()
-        .filter_map(|l| l.strip_prefix("COMMIT "))
-        .filter_map(|ts| ts.trim().parse::<i64>().ok())
+        .filter_map(parse_commit_marker)
+        .filter(|(_, sha)| !ignored.contains(*sha))
+        .map(|(ts, _)| ts)
         .collect();
 
     let touch_count = window_timestamps.len();
 
-    let days_since = if let Some(&ts) = window_timestamps.first() {
-        Some(((as_of_timestamp - ts).max(0) / (24 * 60 * 60)) as u32)
-    } else {
-        // Not in 30-day window: find most recent commit touching this range
-        let recent_until = format!("--until={}", as_of_timestamp);
-        let recent_output = git_at(
+    let days_since = match window_timestamps.first() {
+        Some(&ts) => Some(ts),
+        None => last_range_touch(repo_path, &range_arg, as_of_timestamp, ignored),
+    }
+    .map(|ts| ((as_of_timestamp - ts).max(0) / (24 * 60 * 60)) as u32);
+
+    Ok((touch_count, days_since))
+}
+
+/// Timestamp of the most recent commit outside `ignored` touching `range_arg`
+/// (a `-L` argument) up to `as_of_timestamp`
+///
+/// Asks for one commit, then for four times as many each time every commit
+/// returned was ignored, so an ignore list costs nothing for ranges whose
+/// last change it doesn't cover.
+fn last_range_touch(
+    repo_path: &Path,
+    range_arg: &str,
+    as_of_timestamp: i64,
+    ignored: &std::collections::HashSet<String>,
+) -> Option<i64> {
+    let until_arg = format!("--until={}", as_of_timestamp);
+    let mut limit = 1usize;
+    loop {
+        let limit_arg = format!("-{}", limit);
+        let output = git_at(
             repo_path,
             &[
                 "log",
-                &range_arg,
-                "--format=COMMIT %ct",
-                "-1",
-                &recent_until,
+                range_arg,
+                "--format=COMMIT %ct %H",
+                &limit_arg,
+                &until_arg,
             ],
         )
         .unwrap_or_default();
+        let markers: Vec<(i64, &str)> = output.lines().filter_map(parse_commit_marker).collect();
+        if let Some(&(ts, _)) = markers.iter().find(|(_, sha)| !ignored.contains(*sha)) {
+            return Some(ts);
+        }
+        if markers.len() < limit {
+            return None;
+        }
+        limit *= 4;
+    }
+}
 
-        recent_output
-            .lines()
-            .filter_map(|l| l.strip_prefix("COMMIT "))
-            .filter_map(|ts| ts.trim().parse::<i64>().ok())
-            .next()
-            .map(|ts| ((as_of_timestamp - ts).max(0) / (24 * 60 * 60)) as u32)
-    };
+/// Unix timestamp of the oldest commit touching a function's line range.
+///
+/// Follows the range back through history with `git log -L start,end:file`, so
+/// this is when the function's lines first appeared rather than when the file
+/// was created. Returns `None` when the range has no history (e.g. uncommitted code).
+///
+/// # Arguments
+///
+/// * `repo_root` - Path to git repository
+/// * `file` - Relative path to file from repository root
+/// * `start_line` - First line of function (1-based)
+/// * `end_line` - Last line of function (1-based)
+pub fn function_first_seen(
+    repo_root: &Path,
+    file: &str,
+    start_line: u32,
+    end_line: u32,
+) -> Result<Option<i64>> {
+    let range_arg = format!("-L{},{}:{}", start_line, end_line, file);
+    let output = git_at(repo_root, &["log", &range_arg, "--format=COMMIT %ct"])?;
 
-    Ok((touch_count, days_since))
+    // Newest first, so the last marker is the introducing commit
+    Ok(output
+        .lines()
+        .rev()
+        .filter_map(|l| l.strip_prefix("COMMIT "))
+        .find_map(|ts| ts.trim().parse::<i64>().ok()))
 }
 
 /// Count how many commits touched a file in the last 30 days
@@ -989,7 +1213,10 @@ pub struct CoChangePair {
     pub file_b: String,
     /// Number of commits where both files changed
     pub co_change_count: usize,
-    /// co_change_count / min(total_changes_a, total_changes_b)
+    /// Co-change weight over min(total_changes_a, total_changes_b), or over
+    /// the commits touching either file with [`CoChangeMetric::Jaccard`]. Each
+    /// shared commit weighs 1, or 1/(N-1) for a commit touching N files when
+    /// co-change weighting is on
     pub coupling_ratio: f64,
     /// "high" if ratio > 0.5 and no static dep, "moderate" if > 0.25 and no static dep,
     /// "expected" if a direct import exists between the two files, else "low"
@@ -998,6 +1225,57 @@ pub struct CoChangePair {
     pub has_static_dep: bool,
 }
 
+/// Denominator of a co-change pair's `coupling_ratio`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, serde::Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CoChangeMetric {
+    /// The smaller of the two files' change counts (default), so a file that
+    /// changes in every commit of its partner is fully coupled to it however
+    /// often it changes on its own
+    #[default]
+    Min,
+    /// The number of commits touching either file (Jaccard index), which is
+    /// symmetric and discounts files that change a lot independently
+    Jaccard,
+}
+
+impl CoChangeMetric {
+    /// Parse a `co_change_metric` value
+    pub fn parse(s: &str) -> anyhow::Result<Self> {
+        match s {
+            "min" => Ok(CoChangeMetric::Min),
+            "jaccard" => Ok(CoChangeMetric::Jaccard),
+            other => anyhow::bail!(
+                "co_change_metric must be \"min\" or \"jaccard\" (got \"{}\")",
+                other
+            ),
+        }
+    }
+
+    /// Coupling ratio of a pair with co-change `weight` across `co_changes`
+    /// shared commits, between files changed in `count_a` and `count_b` commits
+    fn ratio(self, weight: f64, co_changes: usize, count_a: usize, count_b: usize) -> f64 {
+        let denominator = match self {
+            CoChangeMetric::Min => count_a.min(count_b),
+            CoChangeMetric::Jaccard => (count_a + count_b).saturating_sub(co_changes),
+        };
+        weight / denominator.max(1) as f64
+    }
+}
+
+/// Co-change mining parameters (see [`extract_co_change_pairs`])
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CoChangeOptions {
+    /// Days of history to mine
+    pub window_days: u64,
+    /// Shared commits required to report a pair
+    pub min_count: usize,
+    /// Shrink each commit's contribution with its size (see [`count_co_changes`])
+    pub weighted: bool,
+    /// Denominator of `coupling_ratio`
+    pub metric: CoChangeMetric,
+}
+
 /// Returns true for pairs that are trivially expected to co-change (test+source,
 /// mod.rs+sibling) and should be excluded from coupling analysis.
 fn is_trivial_pair(file_a: &str, file_b: &str) -> bool {
@@ -1033,15 +1311,72 @@ fn is_trivial_pair(file_a: &str, file_b: &str) -> bool {
     false
 }
 
+/// Skip commits that touch more than this many files — they are mass-change
+/// commits (version bumps, renames, reformats) that produce O(n²) pairs and
+/// dominate memory without adding meaningful co-change signal.
+const MAX_FILES_PER_COMMIT: usize = 200;
+
+/// Per-pair (commit count, weight), keyed by the sorted file pair
+type PairCounts = std::collections::HashMap<(String, String), (usize, f64)>;
+
+/// Per-file change counts and per-pair (commit count, weight) over a list of
+/// per-commit file sets.
+///
+/// With `weighted`, a commit touching N distinct files adds 1/(N-1) to each of
+/// its pairs' weight instead of 1, so focused commits count for more than
+/// sweeping ones.
+fn count_co_changes(
+    commit_files: &[Vec<String>],
+    weighted: bool,
+) -> (std::collections::HashMap<String, usize>, PairCounts) {
+    let mut file_counts: std::collections::HashMap<String, usize> =
+        std::collections::HashMap::new();
+    let mut pair_counts = PairCounts::new();
+
+    for files in commit_files {
+        for f in files {
+            *file_counts.entry(f.clone()).or_insert(0) += 1;
+        }
+        if files.len() > MAX_FILES_PER_COMMIT {
+            continue;
+        }
+        // All unique pairs in this commit
+        let mut sorted = files.clone();
+        sorted.sort();
+        sorted.dedup();
+        let weight = if weighted && sorted.len() > 1 {
+            1.0 / (sorted.len() - 1) as f64
+        } else {
+            1.0
+        };
+        for i in 0..sorted.len() {
+            for j in (i + 1)..sorted.len() {
+                let key = (sorted[i].clone(), sorted[j].clone());
+                let entry = pair_counts.entry(key).or_insert((0, 0.0));
+                entry.0 += 1;
+                entry.1 += weight;
+            }
+        }
+    }
+    (file_counts, pair_counts)
+}
+
 /// Mine co-change pairs from git log over the last `window_days` days.
 ///
-/// Returns pairs that co-changed at least `min_count` times, ranked by
+/// Returns pairs that co-changed in at least `min_count` commits, ranked by
 /// coupling_ratio descending. Pairs where both files are the same are excluded.
+/// With `weighted`, each commit's contribution to the ratio shrinks with its
+/// size (see [`count_co_changes`]); `metric` picks the ratio's denominator.
 pub fn extract_co_change_pairs(
     repo_root: &Path,
-    window_days: u64,
-    min_count: usize,
+    options: CoChangeOptions,
 ) -> Result<Vec<CoChangePair>> {
+    let CoChangeOptions {
+        window_days,
+        min_count,
+        weighted,
+        metric,
+    } = options;
     let since = format!("{} days ago", window_days);
     let output = git_at(
         repo_root,
@@ -1072,47 +1407,20 @@ pub fn extract_co_change_pairs(
     }
 
     // Count per-file total appearances and per-pair co-occurrences
-    let mut file_counts: std::collections::HashMap<String, usize> =
-        std::collections::HashMap::new();
-    let mut pair_counts: std::collections::HashMap<(String, String), usize> =
-        std::collections::HashMap::new();
-
-    // Skip commits that touch more than this many files — they are mass-change
-    // commits (version bumps, renames, reformats) that produce O(n²) pairs and
-    // dominate memory without adding meaningful co-change signal.
-    const MAX_FILES_PER_COMMIT: usize = 200;
-
-    for files in &commit_files {
-        for f in files {
-            *file_counts.entry(f.clone()).or_insert(0) += 1;
-        }
-        if files.len() > MAX_FILES_PER_COMMIT {
-            continue;
-        }
-        // All unique pairs in this commit
-        let mut sorted = files.clone();
-        sorted.sort();
-        sorted.dedup();
-        for i in 0..sorted.len() {
-            for j in (i + 1)..sorted.len() {
-                let key = (sorted[i].clone(), sorted[j].clone());
-                *pair_counts.entry(key).or_insert(0) += 1;
-            }
-        }
-    }
+    let (file_counts, pair_counts) = count_co_changes(&commit_files, weighted);
 
     let mut pairs: Vec<CoChangePair> = pair_counts
         .into_iter()
-        .filter(|(_, count)| *count >= min_count)
+        .filter(|(_, (count, _))| *count >= min_count)
         .filter(|((file_a, file_b), _)| {
             // Skip pairs where either file no longer exists (e.g. renamed/deleted files)
             repo_root.join(file_a).exists() && repo_root.join(file_b).exists()
         })
         .filter(|((file_a, file_b), _)| !is_trivial_pair(file_a, file_b))
-        .map(|((file_a, file_b), co_change_count)| {
+        .map(|((file_a, file_b), (co_change_count, weight))| {
             let count_a = file_counts.get(&file_a).copied().unwrap_or(1);
             let count_b = file_counts.get(&file_b).copied().unwrap_or(1);
-            let coupling_ratio = co_change_count as f64 / count_a.min(count_b) as f64;
+            let coupling_ratio = metric.ratio(weight, co_change_count, count_a, count_b);
             let risk = if coupling_ratio > 0.5 {
                 "high".to_string()
             } else if coupling_ratio > 0.25 {
@@ -1142,6 +1450,200 @@ pub fn extract_co_change_pairs(
     Ok(pairs)
 }
 
+/// Per-file author line counts: file path (repo-relative) -> author -> lines added.
+pub type FileAuthorLines =
+    std::collections::HashMap<String, std::collections::HashMap<String, u64>>;
+
+/// Mine `git shortlog`-style authorship over the full history: lines added per
+/// author per file, from `git log --numstat`. Merge commits and binary files
+/// are skipped; renames are not followed, so paths are as of each commit.
+pub fn extract_file_author_lines(repo_root: &Path) -> Result<FileAuthorLines> {
+    let output = git_at(
+        repo_root,
+        &[
+            "log",
+            "--numstat",
+            "--no-merges",
+            "--no-renames",
+            "--format=AUTHOR:%aN",
+        ],
+    )?;
+    Ok(parse_author_numstat(&output))
+}
+
+/// Parse `git log --numstat --format=AUTHOR:%aN` output into per-file author line counts.
+fn parse_author_numstat(output: &str) -> FileAuthorLines {
+    let mut lines_by_file = FileAuthorLines::new();
+    let mut author: Option<&str> = None;
+    for line in output.lines() {
+        if let Some(name) = line.strip_prefix("AUTHOR:") {
+            author = Some(name.trim());
+            continue;
+        }
+        let Some(author) = author else { continue };
+        let mut parts = line.splitn(3, '\t');
+        let (Some(added), Some(_deleted), Some(file)) = (parts.next(), parts.next(), parts.next())
+        else {
+            continue;
+        };
+        // Binary files report "-" for both counts
+        let Ok(added) = added.parse::<u64>() else {
+            continue;
+        };
+        *lines_by_file
+            .entry(file.to_string())
+            .or_default()
+            .entry(author.to_string())
+            .or_insert(0) += added;
+    }
+    lines_by_file
+}
+
+/// Added line ranges per file: repo-relative path -> inclusive `(start, end)` lines
+pub type AddedLines = std::collections::HashMap<String, Vec<(u32, u32)>>;
+
+/// Lines added in the working tree since `base_sha`, from `git diff -U0`
+///
+/// Deleted files are omitted; renamed files are keyed by their new path and
+/// only carry the lines changed by the rename commit.
+pub fn added_lines_since(repo_root: &Path, base_sha: &str) -> Result<AddedLines> {
+    let output = git_at(
+        repo_root,
+        &["diff", "-U0", "--no-color", "--no-ext-diff", base_sha, "--"],
+    )?;
+    Ok(parse_diff_lines(&output, false))
+}
+
+/// Lines added between commits `base_sha` and `head_sha`, from `git diff -U0`
+///
+/// Like [`added_lines_since`] but limited to the committed change, so edits
+/// left uncommitted in the working tree are not attributed to `head_sha`.
+pub fn added_lines_between(repo_root: &Path, base_sha: &str, head_sha: &str) -> Result<AddedLines> {
+    let output = git_at(
+        repo_root,
+        &[
+            "diff",
+            "-U0",
+            "--no-color",
+            "--no-ext-diff",
+            base_sha,
+            head_sha,
+            "--",
+        ],
+    )?;
+    Ok(parse_diff_lines(&output, false))
+}
+
+/// Lines changed in the working tree since `base_sha`, from `git diff -U0`
+///
+/// Like [`added_lines_since`], plus each pure deletion as the two lines on
+/// either side of it, so a function that only lost lines still counts as changed.
+pub fn changed_lines_since(repo_root: &Path, base_sha: &str) -> Result<AddedLines> {
+    let output = git_at(
+        repo_root,
+        &["diff", "-U0", "--no-color", "--no-ext-diff", base_sha, "--"],
+    )?;
+    Ok(parse_diff_lines(&output, true))
+}
+
+/// Repo-relative paths that differ between `base_sha` and the working tree
+///
+/// Covers committed and uncommitted changes to tracked files (`git diff
+/// --name-only`) plus untracked, non-ignored files. A rename is listed as both
+/// its old and new path. Sorted and deduplicated.
+pub fn changed_files_since(repo_root: &Path, base_sha: &str) -> Result<Vec<String>> {
+    let diff = git_at(
+        repo_root,
+        &["diff", "--name-only", "--no-renames", "-z", base_sha, "--"],
+    )?;
+    let untracked = git_at(
+        repo_root,
+        &["ls-files", "--others", "--exclude-standard", "-z"],
+    )?;
+    let mut files: Vec<String> = diff
+        .split('\0')
+        .chain(untracked.split('\0'))
+        .filter(|path| !path.is_empty())
+        .map(str::to_string)
+        .collect();
+    files.sort();
+    files.dedup();
+    Ok(files)
+}
+
+/// Repo-relative paths that differ between the two ends of `range` (`A..B` or
+/// `A...B`), from `git diff --name-only`
+///
+/// Working-tree changes are not included. A rename is listed as both its old
+/// and new path. Sorted and deduplicated.
+pub fn changed_files_in_range(repo_root: &Path, range: &str) -> Result<Vec<String>> {
+    if !range.contains("..") || range.starts_with('-') {
+        anyhow::bail!("invalid revision range '{range}': expected A..B");
+    }
+    let diff = git_at(
+        repo_root,
+        &["diff", "--name-only", "--no-renames", "-z", range, "--"],
+    )?;
+    let mut files: Vec<String> = diff
+        .split('\0')
+        .filter(|path| !path.is_empty())
+        .map(str::to_string)
+        .collect();
+    files.sort();
+    files.dedup();
+    Ok(files)
+}
+
+/// Parse `git diff -U0` output into added line ranges per file, and with
+/// `with_deletions` the lines around each pure deletion.
+fn parse_diff_lines(diff: &str, with_deletions: bool) -> AddedLines {
+    let mut added = AddedLines::new();
+    let mut file: Option<String> = None;
+    // File headers only appear between `diff --git` and the first hunk, so an
+    // added line that itself starts with "++ " is never mistaken for one
+    let mut in_header = false;
+    for line in diff.lines() {
+        if line.starts_with("diff --git ") {
+            in_header = true;
+            file = None;
+            continue;
+        }
+        if in_header {
+            if let Some(path) = line.strip_prefix("+++ ") {
+                file = path.strip_prefix("b/").map(str::to_string);
+                continue;
+            }
+        }
+        let Some(hunk) = line.strip_prefix("@@ ") else {
+            continue;
+        };
+        in_header = false;
+        let Some(file) = file.as_ref() else { continue };
+        // "-old[,n] +new[,m] @@ ..."; a missing count means one line
+        let Some(new_range) = hunk.split_whitespace().find_map(|p| p.strip_prefix('+')) else {
+            continue;
+        };
+        let mut parts = new_range.splitn(2, ',');
+        let Some(Ok(start)) = parts.next().map(str::parse::<u32>) else {
+            continue;
+        };
+        let count = parts.next().map_or(Ok(1), str::parse::<u32>).unwrap_or(0);
+        if count > 0 {
+            added
+                .entry(file.clone())
+                .or_default()
+                .push((start, start + count - 1));
+        } else if with_deletions {
+            // A zero-length new side names the line the deletion follows
+            added
+                .entry(file.clone())
+                .or_default()
+                .push((start.max(1), start + 1));
+        }
+    }
+    added
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1311,4 +1813,76 @@ mod tests {
             "should return None when not in a git repo"
         );
     }
+
+    #[test]
+    fn test_parse_author_numstat_sums_lines_per_author() {
+        let output = "AUTHOR:Alice\n\n10\t2\tsrc/a.rs\n3\t0\tsrc/b.rs\n\
+AUTHOR:Bob\n\n5\t1\tsrc/a.rs\n-\t-\tlogo.png\n\
+AUTHOR:Alice\n\n7\t0\tsrc/a.rs\n";
+        let lines = parse_author_numstat(output);
+        assert_eq!(lines["src/a.rs"]["Alice"], 17);
+        assert_eq!(lines["src/a.rs"]["Bob"], 5);
+        assert_eq!(lines["src/b.rs"]["Alice"], 3);
+        assert!(!lines.contains_key("logo.png"), "binary files are skipped");
+    }
+
+    #[test]
+    fn test_parse_diff_lines_reads_new_side_of_hunks() {
+        let diff = "diff --git a/src/a.ts b/src/a.ts\n\
+--- a/src/a.ts\n\
++++ b/src/a.ts\n\
+@@ -3,0 +4,2 @@ function f() {\n\
++  if (x) {\n\
++++ y;\n\
+@@ -10 +12 @@\n\
+-  return 1;\n\
++  return 2;\n\
+@@ -20,3 +21,0 @@\n\
+diff --git a/old.ts b/old.ts\n\
+--- a/old.ts\n\
++++ /dev/null\n\
+@@ -1,2 +0,0 @@\n";
+        let added = parse_diff_lines(diff, false);
+        assert_eq!(added["src/a.ts"], vec![(4, 5), (12, 12)]);
+        assert!(
+            !added.contains_key("old.ts"),
+            "deleted files have no added lines"
+        );
+
+        let changed = parse_diff_lines(diff, true);
+        assert_eq!(changed["src/a.ts"], vec![(4, 5), (12, 12), (21, 22)]);
+        assert!(!changed.contains_key("old.ts"));
+    }
+
+    #[test]
+    fn test_co_change_weight_shrinks_with_commit_size() {
+        let commit = |files: &[&str]| files.iter().map(|f| f.to_string()).collect::<Vec<_>>();
+        let commits = vec![
+            commit(&["a.rs", "b.rs"]),
+            commit(&["a.rs", "b.rs", "c.rs", "d.rs", "e.rs"]),
+        ];
+        let key = ("a.rs".to_string(), "b.rs".to_string());
+
+        let (file_counts, weighted) = count_co_changes(&commits, true);
+        assert_eq!(file_counts["a.rs"], 2);
+        // 1 from the focused commit + 1/4 from the five-file sweep
+        assert_eq!(weighted[&key], (2, 1.25));
+
+        let (_, unweighted) = count_co_changes(&commits, false);
+        assert_eq!(unweighted[&key], (2, 2.0));
+    }
+
+    #[test]
+    fn test_jaccard_coupling_discounts_independent_changes() {
+        // a.rs changed in 10 commits, b.rs in 2, both of them together
+        assert_eq!(CoChangeMetric::Min.ratio(2.0, 2, 10, 2), 1.0);
+        assert_eq!(CoChangeMetric::Jaccard.ratio(2.0, 2, 10, 2), 0.2);
+        // Jaccard is symmetric and reaches 1.0 only when the files always change together
+        assert_eq!(CoChangeMetric::Jaccard.ratio(3.0, 3, 3, 3), 1.0);
+        assert_eq!(
+            CoChangeMetric::Jaccard.ratio(1.5, 2, 4, 3),
+            CoChangeMetric::Jaccard.ratio(1.5, 2, 3, 4)
+        );
+        assert!(CoChangeMetric::parse("max").is_err());
+    }
 }
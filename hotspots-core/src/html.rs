@@ -5,21 +5,147 @@
 
 use crate::aggregates::SnapshotAggregates;
 use crate::delta::{Delta, FunctionDeltaEntry, FunctionStatus};
+use crate::patterns::{CustomPattern, BUILTIN_PATTERNS};
 use crate::policy::{PolicyId, PolicyResults};
 use crate::risk::{RiskBand, RiskThresholds};
 use crate::snapshot::{CommitInfo, FunctionSnapshot, Snapshot, SnapshotSummary};
+use serde::Serialize;
+use std::collections::HashMap;
+
+/// White-labeling for generated reports
+///
+/// Every field is optional; the defaults reproduce the stock report.
+#[derive(Debug, Clone, Default)]
+pub struct HtmlTheme {
+    /// Replaces "Hotspots Report" / "Hotspots Delta Report" in the page title
+    /// and heading
+    pub title: Option<String>,
+    /// Stylesheet appended after the built-in CSS, so its rules take precedence
+    pub css: Option<String>,
+    /// Risk band colours (configurable under `band_colors`)
+    pub band_colors: BandColors,
+}
+
+/// CSS colour of each risk band, used for band labels, bars, and chart points
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct BandColors {
+    pub low: String,
+    pub moderate: String,
+    pub high: String,
+    pub critical: String,
+}
+
+impl Default for BandColors {
+    fn default() -> Self {
+        BandColors {
+            low: "#22c55e".to_string(),
+            moderate: "#eab308".to_string(),
+            high: "#f97316".to_string(),
+            critical: "#ef4444".to_string(),
+        }
+    }
+}
+
+impl BandColors {
+    /// `:root` rule overriding the built-in band colours, or `None` for the defaults
+    fn css(&self) -> Option<String> {
+        (*self != BandColors::default()).then(|| {
+            format!(
+                ":root {{ --band-low: {}; --band-moderate: {}; --band-high: {}; --band-critical: {}; }}",
+                self.low, self.moderate, self.high, self.critical
+            )
+        })
+    }
+}
+
+impl HtmlTheme {
+    fn title_or<'a>(&'a self, default: &'a str) -> &'a str {
+        self.title.as_deref().unwrap_or(default)
+    }
+
+    /// Built-in CSS followed by any band colour overrides and the custom
+    /// stylesheet, still inlined so the report stays self-contained
+    fn css(&self) -> String {
+        let mut css = inline_css().to_string();
+        if let Some(bands) = self.band_colors.css() {
+            css.push_str("\n/* Band colours */\n");
+            css.push_str(&bands);
+        }
+        if let Some(extra) = &self.css {
+            // `</style>` in any letter case would end the style element early,
+            // so every `<` becomes its CSS escape
+            css.push_str("\n/* Custom stylesheet */\n");
+            css.push_str(&extra.replace('<', "\\3c "));
+        }
+        css
+    }
+}
 
 /// Render a snapshot as an HTML report.
 ///
 /// `source_url` — optional URL of the corresponding written analysis post (e.g. a
 /// hotspots.dev blog post). When set, a banner linking to that post is shown below
 /// the header. Pass `None` for local CLI and CI use where no post exists.
+///
+/// `custom_patterns` — user-defined patterns from config; used to label their
+/// chips in the pattern breakdown.
+///
+/// `theme` — custom title and stylesheet; `HtmlTheme::default()` for the stock look.
 pub fn render_html_snapshot(
     snapshot: &Snapshot,
     history: &[(CommitInfo, SnapshotSummary)],
     source_url: Option<&str>,
     _thresholds: &RiskThresholds,
+    custom_patterns: &[CustomPattern],
+    theme: &HtmlTheme,
 ) -> String {
+    render_snapshot_page(snapshot, history, source_url, custom_patterns, None, theme)
+}
+
+/// Render a snapshot as an HTML report with per-function changes against `parent`.
+///
+/// When `parent` is `Some`, the functions table gains ΔLRS and band-transition
+/// columns merged in from the delta, so a single report shows both absolute risk
+/// and what changed. With `None` this is identical to [`render_html_snapshot`].
+pub fn render_html_snapshot_with_delta(
+    snapshot: &Snapshot,
+    parent: Option<&Snapshot>,
+    history: &[(CommitInfo, SnapshotSummary)],
+    source_url: Option<&str>,
+    thresholds: &RiskThresholds,
+    custom_patterns: &[CustomPattern],
+    theme: &HtmlTheme,
+) -> anyhow::Result<String> {
+    let Some(parent) = parent else {
+        return Ok(render_html_snapshot(
+            snapshot,
+            history,
+            source_url,
+            thresholds,
+            custom_patterns,
+            theme,
+        ));
+    };
+    let delta = Delta::new(snapshot, Some(parent))?;
+    Ok(render_snapshot_page(
+        snapshot,
+        history,
+        source_url,
+        custom_patterns,
+        Some(&delta),
+        theme,
+    ))
+}
+
+fn render_snapshot_page(
+    snapshot: &Snapshot,
+    history: &[(CommitInfo, SnapshotSummary)],
+    source_url: Option<&str>,
+    custom_patterns: &[CustomPattern],
+    delta: Option<&Delta>,
+    theme: &HtmlTheme,
+) -> String {
+    let title = html_escape(theme.title_or("Hotspots Report"));
     let aggregates = snapshot.aggregates.as_ref();
     let history_json = render_history_json(history);
     let trends = if history_json == "[]" {
@@ -27,7 +153,7 @@ pub fn render_html_snapshot(
     } else {
         render_trends_section(&history_json)
     };
-    let patterns_breakdown = render_pattern_breakdown(&snapshot.functions);
+    let patterns_breakdown = render_pattern_breakdown(&snapshot.functions, custom_patterns);
     let source_banner = render_source_banner(source_url);
     let scatter_json = render_scatter_json(&snapshot.functions);
     let scatter = render_scatter_section(&scatter_json);
@@ -38,7 +164,7 @@ pub fn render_html_snapshot(
 <head>
     <meta charset="UTF-8">
     <meta name="viewport" content="width=device-width, initial-scale=1.0">
-    <title>Hotspots Report - {sha}</title>
+    <title>{title} - {sha}</title>
     <style>{css}</style>
 </head>
 <body>
@@ -58,10 +184,11 @@ pub fn render_html_snapshot(
     <script>{js}</script>
 </body>
 </html>"#,
-        sha = &snapshot.commit.sha[..8],
-        css = inline_css(),
+        sha = short_sha(&snapshot.commit.sha),
+        title = title,
+        css = theme.css(),
         js = inline_javascript(),
-        header = render_header(&snapshot.commit),
+        header = render_header(&snapshot.commit, &title),
         source_banner = source_banner,
         summary = render_summary(snapshot),
         next_actions = render_next_actions(&snapshot.functions),
@@ -69,7 +196,7 @@ pub fn render_html_snapshot(
         trends = trends,
         triage = render_triage_panel(&snapshot.functions),
         patterns_breakdown = patterns_breakdown,
-        functions_table = render_functions_table(&snapshot.functions),
+        functions_table = render_functions_table(&snapshot.functions, delta),
         aggregates_section = aggregates.map(render_aggregates).unwrap_or_default(),
         footer = render_footer(),
     )
@@ -91,7 +218,7 @@ fn render_history_json(history: &[(CommitInfo, SnapshotSummary)]) -> String {
             format!(
                 r#"{{"ts":{},"sha":"{}","critical":{},"high":{},"moderate":{},"low":{},"risk":{:.2},"share":{:.4}}}"#,
                 commit.timestamp,
-                &commit.sha[..commit.sha.len().min(8)],
+                short_sha(&commit.sha),
                 critical,
                 high,
                 moderate,
@@ -214,8 +341,11 @@ fn render_scatter_section(json: &str) -> String {
 /// `source_url` — optional URL of the corresponding written analysis post. When set,
 /// a banner linking to that post is shown below the header. Pass `None` for local
 /// CLI and CI use where no post exists.
-pub fn render_html_delta(delta: &Delta, source_url: Option<&str>) -> String {
-    let commit_sha = &delta.commit.sha[..8];
+///
+/// `theme` — custom title and stylesheet; `HtmlTheme::default()` for the stock look.
+pub fn render_html_delta(delta: &Delta, source_url: Option<&str>, theme: &HtmlTheme) -> String {
+    let commit_sha = html_escape(short_sha(&delta.commit.sha));
+    let title = html_escape(theme.title_or("Hotspots Delta Report"));
     let source_banner = render_source_banner(source_url);
 
     format!(
@@ -224,7 +354,7 @@ pub fn render_html_delta(delta: &Delta, source_url: Option<&str>) -> String {
 <head>
     <meta charset="UTF-8">
     <meta name="viewport" content="width=device-width, initial-scale=1.0">
-    <title>Hotspots Delta Report - {sha}</title>
+    <title>{title} - {sha}</title>
     <style>{css}</style>
 </head>
 <body>
@@ -240,9 +370,10 @@ pub fn render_html_delta(delta: &Delta, source_url: Option<&str>) -> String {
 </body>
 </html>"#,
         sha = commit_sha,
-        css = inline_css(),
+        title = title,
+        css = theme.css(),
         js = inline_javascript(),
-        header = render_delta_header(&delta.commit),
+        header = render_delta_header(&delta.commit, &title),
         source_banner = source_banner,
         summary = render_delta_summary(delta),
         policy_section = delta
@@ -255,6 +386,112 @@ pub fn render_html_delta(delta: &Delta, source_url: Option<&str>) -> String {
     )
 }
 
+/// A link on the report index page (`hotspots report-index`)
+#[derive(Debug, Clone)]
+pub struct IndexLink {
+    /// Target path, relative to the index page
+    pub href: String,
+    /// Link text
+    pub label: String,
+    /// Unix timestamp shown next to the link (report mtime or commit time)
+    pub timestamp: i64,
+}
+
+/// Render the static landing page that links generated reports and persisted
+/// snapshots, with the trends chart over `history`.
+///
+/// Links are emitted in the order given; callers sort newest first.
+pub fn render_html_report_index(
+    reports: &[IndexLink],
+    snapshots: &[IndexLink],
+    history: &[(CommitInfo, SnapshotSummary)],
+) -> String {
+    let history_json = render_history_json(history);
+    let trends = if history_json == "[]" {
+        String::new()
+    } else {
+        render_trends_section(&history_json)
+    };
+
+    format!(
+        r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+    <meta charset="UTF-8">
+    <meta name="viewport" content="width=device-width, initial-scale=1.0">
+    <title>Hotspots Reports</title>
+    <style>{css}</style>
+</head>
+<body>
+    <div class="container">
+        <header>
+            <h1>Hotspots Reports</h1>
+            <div class="meta">
+                <span>{report_count} reports</span> •
+                <span>{snapshot_count} snapshots</span>
+            </div>
+        </header>
+        {trends}
+        {reports_section}
+        {snapshots_section}
+        {footer}
+    </div>
+    <script>{js}</script>
+</body>
+</html>"#,
+        css = inline_css(),
+        js = inline_javascript(),
+        report_count = reports.len(),
+        snapshot_count = snapshots.len(),
+        trends = trends,
+        reports_section = render_index_links(
+            "Reports",
+            "HTML reports generated by hotspots analyze and hotspots diff.",
+            reports,
+        ),
+        snapshots_section = render_index_links(
+            "Snapshots",
+            "Persisted snapshots from the snapshot index (zstd-compressed JSON).",
+            snapshots,
+        ),
+        footer = render_footer(),
+    )
+}
+
+/// Render one titled list of links for the report index page
+fn render_index_links(title: &str, note: &str, links: &[IndexLink]) -> String {
+    let rows: String = if links.is_empty() {
+        r#"<div class="visual-note">None yet.</div>"#.to_string()
+    } else {
+        links
+            .iter()
+            .map(|link| {
+                format!(
+                    r#"<div class="visual-card">
+    <div class="visual-card-title monospace"><a href="{href}">{label}</a></div>
+    <div class="visual-card-subtitle">{timestamp}</div>
+</div>"#,
+                    href = html_escape(&link.href),
+                    label = html_escape(&link.label),
+                    timestamp = format_timestamp(link.timestamp),
+                )
+            })
+            .collect()
+    };
+
+    format!(
+        r#"<section class="section">
+    <h2>{title} ({count})</h2>
+    <div class="visual-note">{note}</div>
+    <div class="visual-grid">{rows}</div>
+</section>"#,
+        title = title,
+        count = links.len(),
+        note = note,
+        rows = rows,
+    )
+}
+
 /// Inline CSS styles
 fn inline_css() -> &'static str {
     r#"
@@ -489,10 +726,10 @@ tbody tr:hover {
     background: #2563eb;
 }
 
-.overview-mini-fill.band-critical { background: #ef4444; }
-.overview-mini-fill.band-high { background: #f97316; }
-.overview-mini-fill.band-moderate { background: #eab308; }
-.overview-mini-fill.band-low { background: #22c55e; }
+.overview-mini-fill.band-critical { background: var(--band-critical); }
+.overview-mini-fill.band-high { background: var(--band-high); }
+.overview-mini-fill.band-moderate { background: var(--band-moderate); }
+.overview-mini-fill.band-low { background: var(--band-low); }
 
 .overview-kicker {
     color: #6b7280;
@@ -550,6 +787,10 @@ tbody tr:hover {
     margin-top: 0.65rem;
 }
 
+.visual-metrics.with-leverage {
+    grid-template-columns: repeat(5, 1fr);
+}
+
 .visual-metric {
     border-radius: 0.375rem;
     background: #f9fafb;
@@ -567,6 +808,10 @@ tbody tr:hover {
     font-size: 0.9rem;
 }
 
+.visual-metric.wide {
+    grid-column: 1 / -1;
+}
+
 .visual-bar {
     height: 0.55rem;
     border-radius: 999px;
@@ -581,10 +826,10 @@ tbody tr:hover {
     background: #2563eb;
 }
 
-.visual-bar-fill.band-critical { background: #ef4444; }
-.visual-bar-fill.band-high { background: #f97316; }
-.visual-bar-fill.band-moderate { background: #eab308; }
-.visual-bar-fill.band-low { background: #22c55e; }
+.visual-bar-fill.band-critical { background: var(--band-critical); }
+.visual-bar-fill.band-high { background: var(--band-high); }
+.visual-bar-fill.band-moderate { background: var(--band-moderate); }
+.visual-bar-fill.band-low { background: var(--band-low); }
 
 .visual-note {
     color: #6b7280;
@@ -801,27 +1046,54 @@ tbody tr:hover {
     font-size: 0.78rem;
 }
 
-/* Risk Bands */
+/* Risk Bands (colours overridable through config `band_colors`) */
+:root {
+    --band-low: #22c55e;
+    --band-moderate: #eab308;
+    --band-high: #f97316;
+    --band-critical: #ef4444;
+}
+
 .band-low {
-    color: #22c55e;
+    color: var(--band-low);
     font-weight: 600;
 }
 
 .band-moderate {
-    color: #eab308;
+    color: var(--band-moderate);
     font-weight: 600;
 }
 
 .band-high {
-    color: #f97316;
+    color: var(--band-high);
     font-weight: 600;
 }
 
 .band-critical {
+    color: var(--band-critical);
+    font-weight: 600;
+}
+
+/* Inline deltas against the parent snapshot */
+.delta-up {
     color: #ef4444;
     font-weight: 600;
 }
 
+.delta-down {
+    color: #22c55e;
+}
+
+.delta-new {
+    color: #6366f1;
+    font-weight: 600;
+}
+
+/* Functions suppressed inline or by config `suppress` rules */
+tr.suppressed {
+    opacity: 0.5;
+}
+
 /* Code/Monospace */
 .monospace {
     font-family: 'Monaco', 'Courier New', monospace;
@@ -1151,18 +1423,25 @@ footer a {
 .pattern-complex_branching { background: #fffbeb; color: #b45309; border-color: #fde68a; }
 .pattern-deeply_nested     { background: #fff7ed; color: #c2410c; border-color: #fed7aa; }
 .pattern-exit_heavy        { background: #f5f3ff; color: #7c3aed; border-color: #ddd6fe; }
+.pattern-fat_case          { background: #fef9c3; color: #a16207; border-color: #fde047; }
 .pattern-god_function      { background: #fef2f2; color: #dc2626; border-color: #fecaca; }
 .pattern-long_function     { background: #fff1f2; color: #be123c; border-color: #fecdd3; }
+.pattern-nested_literal    { background: #fef3c7; color: #78350f; border-color: #fcd34d; }
+.pattern-panic_prone       { background: #ffedd5; color: #9a3412; border-color: #fdba74; }
+.pattern-unreachable_code  { background: #f5f5f4; color: #57534e; border-color: #d6d3d1; }
 /* Tier 2 — behavioral (cool palette) */
 .pattern-churn_magnet      { background: #eff6ff; color: #1d4ed8; border-color: #bfdbfe; }
 .pattern-cyclic_hub        { background: #fdf4ff; color: #a21caf; border-color: #f0abfc; }
 .pattern-hub_function      { background: #eef2ff; color: #4338ca; border-color: #c7d2fe; }
 .pattern-middle_man        { background: #f1f5f9; color: #475569; border-color: #cbd5e1; }
 .pattern-neighbor_risk     { background: #f0fdfa; color: #0f766e; border-color: #99f6e4; }
+.pattern-risky_untested    { background: #f0f9ff; color: #0369a1; border-color: #bae6fd; }
 .pattern-shotgun_target    { background: #fdf2f8; color: #be185d; border-color: #fbcfe8; }
 .pattern-stale_complex     { background: #fefce8; color: #854d0e; border-color: #fef08a; }
 /* volatile_god — derived, most severe: inverted dark badge */
 .pattern-volatile_god      { background: #7f1d1d; color: #fef2f2; border-color: #991b1b; }
+/* User-defined patterns from config (neutral palette) */
+.pattern-custom            { background: #f3f4f6; color: #374151; border-color: #d1d5db; }
 
 /* Pattern breakdown widget */
 .pattern-breakdown {
@@ -1193,10 +1472,18 @@ footer a {
 .pattern-chip-deeply_nested     .pattern-chip-count { color: #c2410c; }
 .pattern-chip-exit_heavy        { border-left-color: #7c3aed; background: #f5f3ff; }
 .pattern-chip-exit_heavy        .pattern-chip-count { color: #7c3aed; }
+.pattern-chip-fat_case          { border-left-color: #a16207; background: #fef9c3; }
+.pattern-chip-fat_case          .pattern-chip-count { color: #a16207; }
 .pattern-chip-god_function      { border-left-color: #dc2626; background: #fef2f2; }
 .pattern-chip-god_function      .pattern-chip-count { color: #dc2626; }
 .pattern-chip-long_function     { border-left-color: #be123c; background: #fff1f2; }
 .pattern-chip-long_function     .pattern-chip-count { color: #be123c; }
+.pattern-chip-nested_literal    { border-left-color: #78350f; background: #fef3c7; }
+.pattern-chip-nested_literal    .pattern-chip-count { color: #78350f; }
+.pattern-chip-panic_prone       { border-left-color: #9a3412; background: #ffedd5; }
+.pattern-chip-panic_prone       .pattern-chip-count { color: #9a3412; }
+.pattern-chip-unreachable_code  { border-left-color: #57534e; background: #f5f5f4; }
+.pattern-chip-unreachable_code  .pattern-chip-count { color: #57534e; }
 .pattern-chip-churn_magnet      { border-left-color: #1d4ed8; background: #eff6ff; }
 .pattern-chip-churn_magnet      .pattern-chip-count { color: #1d4ed8; }
 .pattern-chip-cyclic_hub        { border-left-color: #a21caf; background: #fdf4ff; }
@@ -1207,12 +1494,16 @@ footer a {
 .pattern-chip-middle_man        .pattern-chip-count { color: #475569; }
 .pattern-chip-neighbor_risk     { border-left-color: #0f766e; background: #f0fdfa; }
 .pattern-chip-neighbor_risk     .pattern-chip-count { color: #0f766e; }
+.pattern-chip-risky_untested    { border-left-color: #0369a1; background: #f0f9ff; }
+.pattern-chip-risky_untested    .pattern-chip-count { color: #0369a1; }
 .pattern-chip-shotgun_target    { border-left-color: #be185d; background: #fdf2f8; }
 .pattern-chip-shotgun_target    .pattern-chip-count { color: #be185d; }
 .pattern-chip-stale_complex     { border-left-color: #854d0e; background: #fefce8; }
 .pattern-chip-stale_complex     .pattern-chip-count { color: #854d0e; }
 .pattern-chip-volatile_god      { border-left-color: #7f1d1d; background: #fef2f2; }
 .pattern-chip-volatile_god      .pattern-chip-count { color: #7f1d1d; }
+.pattern-chip-custom            { border-left-color: #6b7280; background: #f3f4f6; }
+.pattern-chip-custom            .pattern-chip-count { color: #374151; }
 
 /* Driver badges */
 .driver-badge { font-size: 0.75rem; padding: 0.15rem 0.4rem; border-radius: 0.25rem; margin-left: 0.4rem; }
@@ -1567,16 +1858,22 @@ th.sortable.desc::after {
     .pattern-complex_branching { background: #2d1b00; color: #fbbf24; border-color: #92400e; }
     .pattern-deeply_nested     { background: #3a1500; color: #fb923c; border-color: #c2410c; }
     .pattern-exit_heavy        { background: #1e0050; color: #c4b5fd; border-color: #6d28d9; }
+    .pattern-fat_case          { background: #2a2000; color: #facc15; border-color: #a16207; }
     .pattern-god_function      { background: #3a0000; color: #fca5a5; border-color: #991b1b; }
     .pattern-long_function     { background: #3b0018; color: #fda4af; border-color: #9f1239; }
+    .pattern-nested_literal    { background: #261400; color: #fcd34d; border-color: #78350f; }
+    .pattern-panic_prone       { background: #2c1005; color: #fdba74; border-color: #9a3412; }
+    .pattern-unreachable_code  { background: #1c1917; color: #d6d3d1; border-color: #57534e; }
     .pattern-churn_magnet      { background: #001a3d; color: #93c5fd; border-color: #1e40af; }
     .pattern-cyclic_hub        { background: #2a0035; color: #e879f9; border-color: #86198f; }
     .pattern-hub_function      { background: #13104a; color: #a5b4fc; border-color: #3730a3; }
     .pattern-middle_man        { background: #1a2030; color: #94a3b8; border-color: #334155; }
     .pattern-neighbor_risk     { background: #002020; color: #5eead4; border-color: #0f766e; }
+    .pattern-risky_untested    { background: #00203a; color: #7dd3fc; border-color: #0369a1; }
     .pattern-shotgun_target    { background: #3b0020; color: #f9a8d4; border-color: #9d174d; }
     .pattern-stale_complex     { background: #1a1200; color: #fde047; border-color: #854d0e; }
     .pattern-volatile_god      { background: #450a0a; color: #fef2f2; border-color: #7f1d1d; }
+    .pattern-custom            { background: #1f2937; color: #d1d5db; border-color: #4b5563; }
 
     /* Pattern breakdown widget — dark mode */
     .pattern-breakdown         { border-color: #374151; background: #1f2937; }
@@ -1589,10 +1886,18 @@ th.sortable.desc::after {
     .pattern-chip-deeply_nested     .pattern-chip-count { color: #fb923c; }
     .pattern-chip-exit_heavy        { background: #1e0050; }
     .pattern-chip-exit_heavy        .pattern-chip-count { color: #c4b5fd; }
+    .pattern-chip-fat_case          { background: #2a2000; }
+    .pattern-chip-fat_case          .pattern-chip-count { color: #facc15; }
     .pattern-chip-god_function      { background: #3a0000; }
     .pattern-chip-god_function      .pattern-chip-count { color: #fca5a5; }
     .pattern-chip-long_function     { background: #3b0018; }
     .pattern-chip-long_function     .pattern-chip-count { color: #fda4af; }
+    .pattern-chip-nested_literal    { background: #261400; }
+    .pattern-chip-nested_literal    .pattern-chip-count { color: #fcd34d; }
+    .pattern-chip-panic_prone       { background: #2c1005; }
+    .pattern-chip-panic_prone       .pattern-chip-count { color: #fdba74; }
+    .pattern-chip-unreachable_code  { background: #1c1917; }
+    .pattern-chip-unreachable_code  .pattern-chip-count { color: #d6d3d1; }
     .pattern-chip-churn_magnet      { background: #001a3d; }
     .pattern-chip-churn_magnet      .pattern-chip-count { color: #93c5fd; }
     .pattern-chip-cyclic_hub        { background: #2a0035; }
@@ -1603,12 +1908,16 @@ th.sortable.desc::after {
     .pattern-chip-middle_man        .pattern-chip-count { color: #94a3b8; }
     .pattern-chip-neighbor_risk     { background: #002020; }
     .pattern-chip-neighbor_risk     .pattern-chip-count { color: #5eead4; }
+    .pattern-chip-risky_untested    { background: #00203a; }
+    .pattern-chip-risky_untested    .pattern-chip-count { color: #7dd3fc; }
     .pattern-chip-shotgun_target    { background: #3b0020; }
     .pattern-chip-shotgun_target    .pattern-chip-count { color: #f9a8d4; }
     .pattern-chip-stale_complex     { background: #1a1200; }
     .pattern-chip-stale_complex     .pattern-chip-count { color: #fde047; }
     .pattern-chip-volatile_god      { background: #450a0a; }
     .pattern-chip-volatile_god      .pattern-chip-count { color: #fef2f2; }
+    .pattern-chip-custom            { background: #1f2937; }
+    .pattern-chip-custom            .pattern-chip-count { color: #d1d5db; }
 }
 "#
 }
@@ -1616,6 +1925,12 @@ th.sortable.desc::after {
 /// Inline JavaScript for interactivity
 fn inline_javascript() -> &'static str {
     r#"
+// Band colours come from the stylesheet's custom properties, so charts follow
+// config `band_colors` like the rest of the page
+function hsBandColor(band) {
+    return getComputedStyle(document.documentElement).getPropertyValue('--band-' + band).trim();
+}
+
 (function() {
     let sortColumn = 'lrs';
     let sortDirection = 'desc';
@@ -1628,6 +1943,7 @@ fn inline_javascript() -> &'static str {
 
     function sortTable(column) {
         const tbody = document.querySelector('#functions-table tbody');
+        if (!tbody) return;
         const rows = Array.from(tbody.querySelectorAll('tr'));
 
         if (sortColumn === column) {
@@ -1774,7 +2090,7 @@ fn inline_javascript() -> &'static str {
                 if (s > mx) mx = s;
             }
             var bW = cW / N, gap = Math.max(1, bW * 0.12);
-            var cols = ['#22c55e', '#eab308', '#f97316', '#ef4444'];
+            var cols = ['low', 'moderate', 'high', 'critical'].map(hsBandColor);
             ctx.clearRect(0, 0, W, H);
             ctx.font = '10px system-ui,sans-serif';
             for (t = 0; t <= 4; t++) {
@@ -1896,7 +2212,7 @@ fn inline_javascript() -> &'static str {
         var pts = window.__hsScatter;
         if (!pts || pts.length === 0) return;
         var hoveredIdx = -1, scatterRaf = null;
-        var bandColor = { c: '#ef4444', h: '#f97316', m: '#eab308', l: '#22c55e' };
+        var bandColor = { c: hsBandColor('critical'), h: hsBandColor('high'), m: hsBandColor('moderate'), l: hsBandColor('low') };
 
         function isDarkSc() { return !!(window.matchMedia && window.matchMedia('(prefers-color-scheme: dark)').matches); }
 
@@ -2100,7 +2416,7 @@ fn inline_javascript() -> &'static str {
         var lastW = 0;
         var lastH = 0;
         var dragging = null;
-        var colors = { critical: '#ef4444', high: '#f97316', moderate: '#eab308', low: '#22c55e' };
+        var colors = { critical: hsBandColor('critical'), high: hsBandColor('high'), moderate: hsBandColor('moderate'), low: hsBandColor('low') };
         var bandWeights = { critical: 1.5, high: 1.25, moderate: 1.0, low: 0.5 };
 
         function isDarkModel() { return !!(window.matchMedia && window.matchMedia('(prefers-color-scheme: dark)').matches); }
@@ -2423,19 +2739,20 @@ fn inline_javascript() -> &'static str {
 }
 
 /// Render header section
-fn render_header(commit: &CommitInfo) -> String {
+fn render_header(commit: &CommitInfo, title: &str) -> String {
     let branch = commit.branch.as_deref().unwrap_or("detached");
 
     format!(
         r#"<header>
-    <h1>Hotspots Report</h1>
+    <h1>{title}</h1>
     <div class="meta">
         <span>Commit: <code class="monospace">{sha}</code></span> •
         <span>Branch: <strong>{branch}</strong></span> •
         <span>Timestamp: {timestamp}</span>
     </div>
 </header>"#,
-        sha = &commit.sha[..8],
+        title = title,
+        sha = short_sha(&commit.sha),
         branch = branch,
         timestamp = format_timestamp(commit.timestamp),
     )
@@ -2494,8 +2811,7 @@ fn render_summary(snapshot: &Snapshot) -> String {
 
 /// Render pattern breakdown widget — shows per-pattern counts sorted by frequency.
 /// Returns empty string when no functions have patterns.
-fn render_pattern_breakdown(functions: &[FunctionSnapshot]) -> String {
-    use std::collections::HashMap;
+fn render_pattern_breakdown(functions: &[FunctionSnapshot], custom: &[CustomPattern]) -> String {
     let mut counts: HashMap<&str, usize> = HashMap::new();
     for f in functions {
         for p in &f.patterns {
@@ -2511,12 +2827,17 @@ fn render_pattern_breakdown(functions: &[FunctionSnapshot]) -> String {
     let chips: String = sorted
         .iter()
         .map(|(id, count)| {
-            let desc = pattern_description(id);
+            let desc = custom
+                .iter()
+                .find(|p| p.name == *id)
+                .and_then(|p| p.description.as_deref())
+                .unwrap_or_else(|| pattern_description(id));
             format!(
-                r#"<div class="pattern-chip pattern-chip-{id}"><div class="pattern-chip-count">{count}</div><div class="pattern-chip-name">{id}</div><div class="pattern-chip-desc">{desc}</div></div>"#,
+                r#"<div class="pattern-chip pattern-chip-{class}"><div class="pattern-chip-count">{count}</div><div class="pattern-chip-name">{id}</div><div class="pattern-chip-desc">{desc}</div></div>"#,
+                class = pattern_css_class(id),
                 id = html_escape(id),
                 count = count,
-                desc = desc,
+                desc = html_escape(desc),
             )
         })
         .collect();
@@ -2533,18 +2854,33 @@ fn render_pattern_breakdown(functions: &[FunctionSnapshot]) -> String {
     )
 }
 
+/// CSS modifier for a pattern badge/chip. Built-in patterns have their own
+/// palette; user-defined patterns share the neutral `custom` style.
+fn pattern_css_class(id: &str) -> String {
+    if BUILTIN_PATTERNS.contains(&id) {
+        html_escape(id)
+    } else {
+        "custom".to_string()
+    }
+}
+
 fn pattern_description(id: &str) -> &'static str {
     match id {
         "complex_branching" => "High cyclomatic complexity and nesting",
         "deeply_nested" => "Nesting depth \u{2265} 5 levels",
         "exit_heavy" => "Many early returns",
+        "fat_case" => "One switch/match arm dominates",
         "god_function" => "Too many responsibilities",
         "long_function" => "Exceeds recommended length",
+        "nested_literal" => "Deeply nested object/array literal",
+        "panic_prone" => "Many unwrap/expect/panic sites",
+        "unreachable_code" => "Statements no path reaches",
         "churn_magnet" => "Complex and frequently changed",
         "cyclic_hub" => "Node in a dependency cycle",
         "hub_function" => "High fan-in and complex",
         "middle_man" => "High fan-out, trivial logic",
         "neighbor_risk" => "Called from high-churn functions",
+        "risky_untested" => "High risk, low test coverage",
         "shotgun_target" => "Many callers and high churn",
         "stale_complex" => "Complex but rarely touched",
         "volatile_god" => "God function under heavy churn",
@@ -2617,15 +2953,26 @@ fn render_function_risk_gallery(functions: &[FunctionSnapshot]) -> String {
     )
 }
 
-fn render_functions_table(functions: &[FunctionSnapshot]) -> String {
+fn render_functions_table(functions: &[FunctionSnapshot], delta: Option<&Delta>) -> String {
+    let delta_entries: Option<HashMap<&str, &FunctionDeltaEntry>> = delta.map(|d| {
+        d.deltas
+            .iter()
+            .map(|e| (e.function_id.as_str(), e))
+            .collect()
+    });
     // Only show churn/fanin columns when enough functions actually have data
     let sparse_min = 10usize;
     let has_activity = functions.iter().any(|f| f.activity_risk.is_some());
+    let has_priority = functions.iter().any(|f| f.priority_score.is_some());
+    let has_leverage = functions.iter().any(|f| f.refactor_leverage.is_some());
     let has_churn = functions.iter().filter(|f| f.churn.is_some()).count() >= sparse_min;
     let has_touches = functions.iter().any(|f| f.touch_count_30d.is_some());
     let has_recency = functions.iter().any(|f| f.days_since_last_change.is_some());
     let has_fanin = functions.iter().filter(|f| f.callgraph.is_some()).count() >= sparse_min;
     let has_patterns = functions.iter().any(|f| !f.patterns.is_empty());
+    let has_data_nesting = functions
+        .iter()
+        .any(|f| f.metrics.max_data_nesting.is_some());
 
     let rows: String = functions
         .iter()
@@ -2658,6 +3005,22 @@ fn render_functions_table(functions: &[FunctionSnapshot]) -> String {
             } else {
                 String::new()
             };
+            let priority_cell = if has_priority {
+                match f.priority_score {
+                    Some(p) => format!("<td>{:.2}</td>", p),
+                    None => "<td>—</td>".to_string(),
+                }
+            } else {
+                String::new()
+            };
+            let leverage_cell = if has_leverage {
+                match f.refactor_leverage {
+                    Some(l) => format!("<td>{:.2}</td>", l),
+                    None => "<td>—</td>".to_string(),
+                }
+            } else {
+                String::new()
+            };
             let churn_cell = if has_churn {
                 match churn_val {
                     Some(c) => format!("<td>{}</td>", c),
@@ -2674,6 +3037,14 @@ fn render_functions_table(functions: &[FunctionSnapshot]) -> String {
             } else {
                 String::new()
             };
+            let data_nesting_cell = if has_data_nesting {
+                match f.metrics.max_data_nesting {
+                    Some(d) => format!("<td>{}</td>", d),
+                    None => "<td>—</td>".to_string(),
+                }
+            } else {
+                String::new()
+            };
             let fanin_cell = if has_fanin {
                 match f.callgraph.as_ref().map(|cg| cg.fan_in) {
                     Some(fi) => format!("<td>{}</td>", fi),
@@ -2731,7 +3102,7 @@ fn render_functions_table(functions: &[FunctionSnapshot]) -> String {
                                 .unwrap_or_default();
                             format!(
                                 r#"<span class="pattern pattern-{}"{title}>{}</span>"#,
-                                html_escape(id),
+                                pattern_css_class(id),
                                 html_escape(id),
                                 title = title,
                             )
@@ -2743,22 +3114,46 @@ fn render_functions_table(functions: &[FunctionSnapshot]) -> String {
                 String::new()
             };
 
+            // Suppressed functions stay visible but greyed out, with the reason on hover
+            let suppressed = f
+                .suppression_reason
+                .as_deref()
+                .map(|reason| {
+                    format!(
+                        " class=\"suppressed\" title=\"Suppressed: {}\"",
+                        html_escape(reason)
+                    )
+                })
+                .unwrap_or_default();
+
+            let (delta_value, delta_cells) = match &delta_entries {
+                Some(entries) => {
+                    render_delta_cells(f, entries.get(f.function_id.as_str()).copied())
+                }
+                None => (String::new(), String::new()),
+            };
+
             format!(
                 "<tr data-file=\"{file}\" data-function=\"{function}\" data-band=\"{band}\" \
-                 data-lrs=\"{lrs}\" data-line=\"{line}\" data-cc=\"{cc}\" data-nd=\"{nd}\" \
-                 data-driver=\"{driver}\" data-activity=\"{activity}\" data-churn=\"{churn}\" \
+                 data-lrs=\"{lrs}\" data-line=\"{line}\" data-cc=\"{cc}\" \
+                 data-density=\"{density:.4}\" data-nd=\"{nd}\" \
+                 data-driver=\"{driver}\" data-activity=\"{activity}\" data-priority=\"{priority}\" \
+                 data-leverage=\"{leverage}\" data-churn=\"{churn}\" \
                  data-touches=\"{touches}\" data-fanin=\"{fanin}\" \
-                 data-recency=\"{recency}\">\n\
+                 data-datanesting=\"{data_nesting}\" \
+                 data-recency=\"{recency}\" data-delta=\"{delta_value}\"{suppressed}>\n\
                  <td class=\"monospace\">{file_display}</td>\n\
                  <td>{function_display}{driver_badge}</td>\n\
                  <td>{line}</td>\n\
                  <td>{lrs:.2}</td>\n\
                  <td><span class=\"band-{band}\">{band}</span></td>\n\
+                 {delta_cells}\
                  <td>{cc}</td>\n\
+                 <td>{density:.2}</td>\n\
                  <td>{nd}</td>\n\
                  <td>{fo}</td>\n\
                  <td>{ns}</td>\n\
-                 {activity_cell}{churn_cell}{touches_cell}{recency_cell}{fanin_cell}{patterns_cell}\
+                 {data_nesting_cell}{activity_cell}{priority_cell}{leverage_cell}{churn_cell}{touches_cell}{recency_cell}{fanin_cell}{patterns_cell}\
                  </tr>",
                 file = html_escape(&f.file),
                 file_display = source_link(&f.file, f.line, &compact_source_label(&f.file)),
@@ -2769,6 +3164,14 @@ fn render_functions_table(functions: &[FunctionSnapshot]) -> String {
                     .activity_risk
                     .map(|ar| format!("{:.4}", ar))
                     .unwrap_or_default(),
+                priority = f
+                    .priority_score
+                    .map(|p| format!("{:.4}", p))
+                    .unwrap_or_default(),
+                leverage = f
+                    .refactor_leverage
+                    .map(|l| format!("{:.4}", l))
+                    .unwrap_or_default(),
                 churn = churn_val.map(|c| c.to_string()).unwrap_or_default(),
                 touches = f.touch_count_30d.map(|t| t.to_string()).unwrap_or_default(),
                 fanin = f
@@ -2784,25 +3187,52 @@ fn render_functions_table(functions: &[FunctionSnapshot]) -> String {
                 lrs = f.lrs,
                 band = f.band.as_str(),
                 cc = f.metrics.cc,
+                density = f.metrics.cc_density(),
                 nd = f.metrics.nd,
                 fo = f.metrics.fo,
                 ns = f.metrics.ns,
+                data_nesting = f
+                    .metrics
+                    .max_data_nesting
+                    .map(|d| d.to_string())
+                    .unwrap_or_default(),
+                data_nesting_cell = data_nesting_cell,
                 driver_badge = driver_badge,
+                delta_value = delta_value,
+                delta_cells = delta_cells,
                 activity_cell = activity_cell,
+                priority_cell = priority_cell,
+                leverage_cell = leverage_cell,
                 churn_cell = churn_cell,
                 touches_cell = touches_cell,
                 recency_cell = recency_cell,
                 fanin_cell = fanin_cell,
                 patterns_cell = patterns_cell,
+                suppressed = suppressed,
             )
         })
         .collect();
 
+    let data_nesting_header = if has_data_nesting {
+        "<th class=\"sortable\" data-column=\"datanesting\" title=\"Deepest nesting of object/array literals (JS/TS) — data shape, not control flow\">Data Nesting</th>"
+    } else {
+        ""
+    };
     let activity_header = if has_activity {
         "<th class=\"sortable\" data-column=\"activity\" title=\"Combined risk score weighting complexity, recent churn, and call graph centrality\">Activity Risk</th>"
     } else {
         ""
     };
+    let priority_header = if has_priority {
+        "<th class=\"sortable\" data-column=\"priority\" title=\"Fix-this-first score: activity risk, discounted by test coverage and boosted by recent change\">Priority</th>"
+    } else {
+        ""
+    };
+    let leverage_header = if has_leverage {
+        "<th class=\"sortable\" data-column=\"leverage\" title=\"Refactor payoff: activity risk scaled up by fan-in and recent touches\">Leverage</th>"
+    } else {
+        ""
+    };
     let churn_header = if has_churn {
         "<th class=\"sortable\" data-column=\"churn\" title=\"Lines added + deleted in recent git history\">Churn</th>"
     } else {
@@ -2828,6 +3258,12 @@ fn render_functions_table(functions: &[FunctionSnapshot]) -> String {
     } else {
         ""
     };
+    let delta_headers = if delta_entries.is_some() {
+        "<th class=\"sortable\" data-column=\"delta\" title=\"Change in LRS since the parent snapshot\">ΔLRS</th>\n\
+                <th title=\"Risk band before → after, when it changed\">Band Change</th>"
+    } else {
+        ""
+    };
     let gallery = render_function_risk_gallery(functions);
 
     format!(
@@ -2877,11 +3313,16 @@ fn render_functions_table(functions: &[FunctionSnapshot]) -> String {
                 <th class="sortable" data-column="line">Line</th>
                 <th class="sortable" data-column="lrs" title="Local Risk Score — composite metric combining complexity, nesting depth, and other factors">LRS</th>
                 <th class="sortable" data-column="band" title="Risk band based on LRS: low / moderate / high / critical">Band</th>
+                {delta_headers}
                 <th class="sortable" data-column="cc" title="Cyclomatic Complexity — number of independent paths through the function (lower is better)">CC</th>
+                <th class="sortable" data-column="density" title="Complexity density — CC per source line; high values flag tightly packed logic rather than merely long functions">CC/SLOC</th>
                 <th class="sortable" data-column="nd" title="Nesting Depth — maximum level of nested control structures">ND</th>
                 <th title="Fan-out — number of distinct functions called by this function">FO</th>
                 <th title="Number of Statements">NS</th>
+                {data_nesting_header}
                 {activity_header}
+                {priority_header}
+                {leverage_header}
                 {churn_header}
                 {touches_header}
                 {recency_header}
@@ -2898,7 +3339,11 @@ fn render_functions_table(functions: &[FunctionSnapshot]) -> String {
         count = functions.len(),
         gallery = gallery,
         rows = rows,
+        delta_headers = delta_headers,
+        data_nesting_header = data_nesting_header,
         activity_header = activity_header,
+        priority_header = priority_header,
+        leverage_header = leverage_header,
         churn_header = churn_header,
         touches_header = touches_header,
         recency_header = recency_header,
@@ -2907,6 +3352,45 @@ fn render_functions_table(functions: &[FunctionSnapshot]) -> String {
     )
 }
 
+/// Render the ΔLRS and band-change cells for one function row.
+///
+/// Returns `(sort value, cells)`. Functions absent from the parent are marked
+/// `new` and sort by their full LRS.
+fn render_delta_cells(
+    f: &FunctionSnapshot,
+    entry: Option<&FunctionDeltaEntry>,
+) -> (String, String) {
+    let (sort_value, delta_cell) = match entry {
+        Some(e) if e.status == FunctionStatus::New => (
+            format!("{:.4}", f.lrs),
+            "<td><span class=\"delta-new\">new</span></td>".to_string(),
+        ),
+        Some(FunctionDeltaEntry { delta: Some(d), .. }) => {
+            let class = if d.lrs > 0.0 {
+                " class=\"delta-up\""
+            } else if d.lrs < 0.0 {
+                " class=\"delta-down\""
+            } else {
+                ""
+            };
+            (
+                format!("{:.4}", d.lrs),
+                format!("<td><span{class}>{:+.2}</span></td>", d.lrs),
+            )
+        }
+        _ => ("0".to_string(), "<td>—</td>".to_string()),
+    };
+    let band_cell = match entry.and_then(|e| e.band_transition.as_ref()) {
+        Some(t) => format!(
+            "<td><span class=\"band-{from}\">{from}</span> → <span class=\"band-{to}\">{to}</span></td>",
+            from = html_escape(&t.from),
+            to = html_escape(&t.to),
+        ),
+        None => "<td>—</td>".to_string(),
+    };
+    (sort_value, format!("{delta_cell}\n{band_cell}\n"))
+}
+
 /// Map (driver, quadrant) to a one-line recommended action for the triage table.
 fn triage_action(driver: Option<&str>, quadrant: Option<&str>) -> &'static str {
     crate::snapshot::driver_action_for_quadrant(driver.unwrap_or(""), quadrant.unwrap_or(""))
@@ -3083,34 +3567,26 @@ fn render_triage_panel(functions: &[FunctionSnapshot]) -> String {
         .filter(|f| f.quadrant.as_deref() == Some("ok"))
         .count();
 
-    // Top risks: fire (active high/critical) first, then debt (stable high/critical)
+    // Top risks: fire (active high/critical) first, then debt (stable high/critical);
+    // untested risky functions lead each group
     let mut active_risks: Vec<&FunctionSnapshot> = functions
         .iter()
         .filter(|f| f.quadrant.as_deref() == Some("fire"))
         .collect();
-    active_risks.sort_by(|a, b| {
-        b.activity_risk
-            .unwrap_or(b.lrs)
-            .partial_cmp(&a.activity_risk.unwrap_or(a.lrs))
-            .unwrap_or(std::cmp::Ordering::Equal)
-    });
+    active_risks.sort_by(|a, b| crate::snapshot::triage_order(a, b));
 
     let mut inactive_risks: Vec<&FunctionSnapshot> = functions
         .iter()
         .filter(|f| f.quadrant.as_deref() == Some("debt"))
         .collect();
-    inactive_risks.sort_by(|a, b| {
-        b.activity_risk
-            .unwrap_or(b.lrs)
-            .partial_cmp(&a.activity_risk.unwrap_or(a.lrs))
-            .unwrap_or(std::cmp::Ordering::Equal)
-    });
+    inactive_risks.sort_by(|a, b| crate::snapshot::triage_order(a, b));
 
     let mut top_risks = active_risks;
     top_risks.extend(inactive_risks);
     top_risks.truncate(15);
 
     let show_touches = top_risks.iter().any(|f| f.touch_count_30d.is_some());
+    let show_leverage = top_risks.iter().any(|f| f.refactor_leverage.is_some());
     let show_last_change = top_risks.iter().any(|f| f.days_since_last_change.is_some());
     let count = top_risks.len();
 
@@ -3193,6 +3669,18 @@ fn render_triage_panel(functions: &[FunctionSnapshot]) -> String {
                 .as_ref()
                 .map(|cg| cg.fan_in.to_string())
                 .unwrap_or_else(|| "—".to_string());
+            let leverage_metric = if show_leverage {
+                let value = f
+                    .refactor_leverage
+                    .map(|l| format!("{l:.2}"))
+                    .unwrap_or_else(|| "—".to_string());
+                format!(
+                    r#"
+        <div class="visual-metric" title="Refactor payoff: activity risk scaled up by fan-in and recent touches"><span>Leverage</span><strong>{value}</strong></div>"#
+                )
+            } else {
+                String::new()
+            };
             let risk_width = (risk_val * 8.0).clamp(4.0, 100.0);
 
             let row_class = if f.quadrant.as_deref() == Some("fire") {
@@ -3206,11 +3694,11 @@ fn render_triage_panel(functions: &[FunctionSnapshot]) -> String {
     <div class="visual-card-title">{func}</div>
     <div class="visual-card-subtitle monospace">{source}</div>
     <div class="visual-bar"><div class="visual-bar-fill band-{band}" style="width:{risk_width:.0}%"></div></div>
-    <div class="visual-metrics">
+    <div class="visual-metrics{metrics_class}">
         <div class="visual-metric"><span>Band</span><strong class="band-{band}">{band}</strong></div>
         <div class="visual-metric"><span>Risk</span><strong>{risk:.2}</strong></div>
         <div class="visual-metric"><span>Touches</span><strong>{touches}</strong></div>
-        <div class="visual-metric"><span>Fan-in</span><strong>{fanin}</strong></div>
+        <div class="visual-metric"><span>Fan-in</span><strong>{fanin}</strong></div>{leverage_metric}
     </div>
     <div class="visual-note">{driver} · last change {last_change} · {action}</div>
 </div>"#,
@@ -3224,6 +3712,8 @@ fn render_triage_panel(functions: &[FunctionSnapshot]) -> String {
                 touches = touches_value,
                 last_change = last_change_value,
                 fanin = fanin_value,
+                leverage_metric = leverage_metric,
+                metrics_class = if show_leverage { " with-leverage" } else { "" },
                 action = triage_action(f.driver.as_deref(), f.quadrant.as_deref()),
             )
         })
@@ -3307,6 +3797,7 @@ fn render_aggregates(aggregates: &SnapshotAggregates) -> String {
                 .iter()
                 .map(|fa| (fa.file.as_str(), fa))
                 .collect();
+        let formula = html_escape(&aggregates.file_risk_weights.formula());
 
         let rows: String = aggregates
             .file_risk
@@ -3317,13 +3808,26 @@ fn render_aggregates(aggregates: &SnapshotAggregates) -> String {
                 let max_lrs = lrs.map(|l| l.max_lrs).unwrap_or(0.0);
                 let high_plus = lrs.map(|l| l.high_plus_count).unwrap_or(0);
                 let score_width = (f.file_risk_score * 10.0).clamp(4.0, 100.0);
+                let ownership = match (f.bus_factor, &f.top_author, f.top_author_share) {
+                    (Some(bus_factor), Some(author), Some(share)) => format!(
+                        " · bus factor {bus_factor} · {author} {pct:.0}%{flag}",
+                        author = html_escape(author),
+                        pct = share * 100.0,
+                        flag = if f.knowledge_risk {
+                            r#" · <span class="band-critical">knowledge risk</span>"#
+                        } else {
+                            ""
+                        },
+                    ),
+                    _ => String::new(),
+                };
                 format!(
                     r#"<div class="visual-card">
     <div class="visual-card-title monospace">{file}</div>
-    <div class="visual-card-subtitle">{fns} functions · {loc} LOC · {high_plus} high+</div>
+    <div class="visual-card-subtitle">{fns} functions · {loc} LOC · {avg_fn_loc:.0} LOC/function · {per_kloc:.1} functions/KLOC · {high_plus} high+{ownership}</div>
     <div class="visual-bar"><div class="visual-bar-fill band-high" style="width:{score_width:.0}%"></div></div>
     <div class="visual-metrics">
-        <div class="visual-metric"><span>Risk</span><strong>{score:.2}</strong></div>
+        <div class="visual-metric" title="{formula}"><span>Risk</span><strong>{score:.2}</strong></div>
         <div class="visual-metric"><span>Max LRS</span><strong>{max_lrs:.2}</strong></div>
         <div class="visual-metric"><span>Max CC</span><strong>{max_cc}</strong></div>
         <div class="visual-metric"><span>Critical</span><strong>{critical}</strong></div>
@@ -3332,12 +3836,16 @@ fn render_aggregates(aggregates: &SnapshotAggregates) -> String {
                     file = source_link(&f.file, 0, &f.file),
                     fns = f.function_count,
                     loc = f.loc,
+                    avg_fn_loc = f.avg_function_loc,
+                    per_kloc = f.functions_per_kloc,
                     max_cc = f.max_cc,
                     critical = f.critical_count,
                     high_plus = high_plus,
                     max_lrs = max_lrs,
                     score = f.file_risk_score,
                     score_width = score_width,
+                    formula = formula,
+                    ownership = ownership,
                 )
             })
             .collect();
@@ -3359,35 +3867,29 @@ fn render_aggregates(aggregates: &SnapshotAggregates) -> String {
             .iter()
             .map(|m| {
                 // Classify into Martin's zones using instability + complexity
-                let (zone_label, _zone_class) = if m.instability < 0.3 {
-                    if m.avg_complexity > 8.0 {
-                        ("zone of pain", "zone-pain")
-                    } else {
-                        ("stable", "zone-stable")
-                    }
-                } else if m.instability > 0.7 {
-                    if m.avg_complexity > 8.0 {
-                        ("volatile", "zone-volatile-complex")
-                    } else {
-                        ("volatile", "zone-volatile")
-                    }
-                } else {
-                    ("balanced", "zone-balanced")
-                };
+                let (zone_label, _zone_class) = aggregates
+                    .module_zones
+                    .classify(m.instability, m.avg_complexity);
                 let instability_width = (m.instability * 100.0).clamp(4.0, 100.0);
                 format!(
                     r#"<div class="visual-card">
     <div class="visual-card-title monospace">{module}</div>
-    <div class="visual-card-subtitle">{zone_label}</div>
+    <div class="visual-card-subtitle">{zone_label} · {fns} functions · {avg_fn_loc:.0} LOC/function · {per_kloc:.1} functions/KLOC</div>
     <div class="visual-bar"><div class="visual-bar-fill" style="width:{instability_width:.0}%"></div></div>
     <div class="visual-metrics">
         <div class="visual-metric"><span>Instability</span><strong>{instability:.2}</strong></div>
         <div class="visual-metric"><span>Avg CC</span><strong>{avg_cc:.1}</strong></div>
         <div class="visual-metric"><span>Afferent</span><strong>{afferent}</strong></div>
         <div class="visual-metric"><span>Efferent</span><strong>{efferent}</strong></div>
+        <div class="visual-metric" title="{test_files} test file(s); non-blank test lines per source line"><span>Test LOC</span><strong>{test_loc_ratio:.2}</strong></div>
     </div>
 </div>"#,
                     module = html_escape(&m.module),
+                    fns = m.function_count,
+                    avg_fn_loc = m.avg_function_loc,
+                    per_kloc = m.functions_per_kloc,
+                    test_files = m.test_file_count,
+                    test_loc_ratio = m.test_loc_ratio,
                     avg_cc = m.avg_complexity,
                     afferent = m.afferent,
                     efferent = m.efferent,
@@ -3652,20 +4154,21 @@ fn render_model_function_row(function: &crate::models::ModelFunction) -> String
 }
 
 /// Render delta header
-fn render_delta_header(commit: &crate::delta::DeltaCommitInfo) -> String {
+fn render_delta_header(commit: &crate::delta::DeltaCommitInfo, title: &str) -> String {
     format!(
         r#"<header>
-    <h1>Hotspots Delta Report</h1>
+    <h1>{title}</h1>
     <div class="meta">
         <span>Commit: <code class="monospace">{sha}</code></span> •
         <span>Parent: <code class="monospace">{parent}</code></span>
     </div>
 </header>"#,
-        sha = &commit.sha[..8],
+        title = title,
+        sha = html_escape(short_sha(&commit.sha)),
         parent = if commit.parent.is_empty() {
-            "none"
+            "none".to_string()
         } else {
-            &commit.parent[..8]
+            html_escape(short_sha(&commit.parent))
         },
     )
 }
@@ -3711,11 +4214,44 @@ fn render_delta_summary(delta: &Delta) -> String {
         <h3>Regressions</h3>
         <div class="value band-high">{regressions}</div>
     </div>
+    {risk_removed}
 </div>"#,
         new = new_count,
         modified = modified_count,
         deleted = deleted_count,
         regressions = regressions,
+        risk_removed = delta
+            .risk_removed
+            .as_ref()
+            .map(render_risk_removed_card)
+            .unwrap_or_default(),
+    )
+}
+
+/// Summary card crediting the risk eliminated by deleted functions
+fn render_risk_removed_card(removed: &crate::delta::RiskRemoved) -> String {
+    let mut detail = format!(
+        "{} deleted function{}",
+        removed.functions,
+        if removed.functions == 1 { "" } else { "s" }
+    );
+    if removed.critical > 0 || removed.high > 0 {
+        detail.push_str(&format!(
+            " · {} critical, {} high",
+            removed.critical, removed.high
+        ));
+    }
+    if let Some(activity_risk) = removed.activity_risk {
+        detail.push_str(&format!(" · -{:.2} activity risk", activity_risk));
+    }
+    format!(
+        r#"<div class="summary-card">
+        <h3>Risk Removed</h3>
+        <div class="value band-low">-{lrs:.2} LRS</div>
+        <div class="visual-note">{detail}</div>
+    </div>"#,
+        lrs = removed.lrs,
+        detail = detail,
     )
 }
 
@@ -3919,6 +4455,7 @@ fn render_delta_table(deltas: &[FunctionDeltaEntry]) -> String {
         <div class="visual-metric"><span>After</span><strong>{after_lrs}</strong></div>
         <div class="visual-metric"><span>Delta</span><strong>{delta_lrs}</strong></div>
         <div class="visual-metric"><span>Status</span><strong>{status_display}</strong></div>
+        <div class="visual-metric wide"><span>Breakdown</span><strong>{breakdown}</strong></div>
     </div>
     <div class="visual-note"><span class="band-{before_band}">{before_band}</span> {transition} <span class="band-{after_band}">{after_band}</span></div>
 </div>"#,
@@ -3934,6 +4471,10 @@ fn render_delta_table(deltas: &[FunctionDeltaEntry]) -> String {
                 delta_lrs = delta_lrs,
                 transition = transition,
                 status_display = status_debug,
+                breakdown = entry
+                    .metric_breakdown()
+                    .map(|b| html_escape(&b))
+                    .unwrap_or_else(|| "-".to_string()),
                 width = width,
             )
         })
@@ -4065,6 +4606,16 @@ fn looks_like_source_file(path: &str) -> bool {
     !NON_SOURCE_NAMES.contains(&basename)
 }
 
+/// First 8 characters of a commit SHA; labels that are not SHAs (e.g. the
+/// directory paths of a `compare` delta) are returned whole
+fn short_sha(sha: &str) -> &str {
+    if sha.bytes().all(|b| b.is_ascii_hexdigit()) {
+        sha.get(..8).unwrap_or(sha)
+    } else {
+        sha
+    }
+}
+
 /// Escape HTML special characters
 fn html_escape(s: &str) -> String {
     s.replace('&', "&amp;")
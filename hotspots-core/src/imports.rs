@@ -1,7 +1,9 @@
 //! Import extraction and file-level dependency resolution
 //!
 //! Parses `use`/`import` statements from source files and resolves them to
-//! in-project file paths. Used by `aggregates.rs` to compute module instability.
+//! in-project file paths. Used by `aggregates.rs` to compute module instability
+//! and annotate co-change pairs, and exposed directly as [`import_graph`] for
+//! `hotspots imports`.
 //!
 //! Global invariants:
 //! - Resolution is best-effort: unresolved imports produce no edge (never wrong)
@@ -25,9 +27,11 @@ pub fn extract_raw_imports(source: &str, language: Language) -> Vec<String> {
         | Language::TypeScriptReact
         | Language::JavaScript
         | Language::JavaScriptReact
-        | Language::Vue => extract_ecmascript_imports(source),
+        | Language::Vue
+        | Language::Svelte => extract_ecmascript_imports(source),
         Language::CSharp => extract_csharp_imports(source),
         Language::C | Language::CHeader => vec![], // #include resolution not implemented
+        Language::Hcl => vec![],                   // module sources are not resolved
     }
 }
 
@@ -284,13 +288,15 @@ fn resolve_import(
         | Language::TypeScriptReact
         | Language::JavaScript
         | Language::JavaScriptReact
-        | Language::Vue => resolve_ecmascript(raw, importing_file, all_files_set, repo_root),
+        | Language::Vue
+        | Language::Svelte => resolve_ecmascript(raw, importing_file, all_files_set, repo_root),
         Language::Rust => resolve_rust(raw, importing_file, all_files_set, repo_root, crate_map),
         Language::Go => resolve_go(raw, all_files_set),
         Language::Python => resolve_python(raw, importing_file, all_files_set, repo_root),
         Language::Java => resolve_java(raw, all_files_set),
         Language::CSharp => resolve_java(raw, all_files_set), // namespace-style, same strategy
         Language::C | Language::CHeader => None,              // #include resolution not implemented
+        Language::Hcl => None,
     }
 }
 
@@ -599,8 +605,9 @@ fn dedup(mut v: Vec<String>) -> Vec<String> {
 
 /// Resolve file-level import edges for a set of project source files.
 ///
-/// Returns `(from_file, to_file)` pairs where both files are in the project.
-/// External / unresolvable imports produce no edge.
+/// Returns deduplicated `(importer, imported)` pairs where both files are in the
+/// project, sorted by importer then imported. Paths are returned in the same form
+/// as `source_files`. External / unresolvable imports produce no edge.
 pub fn resolve_file_deps(source_files: &[&str], repo_root: &Path) -> Vec<(String, String)> {
     let all_files_set: HashSet<String> = source_files.iter().map(|s| s.to_string()).collect();
     let crate_map = build_crate_map(source_files, repo_root);
@@ -616,9 +623,76 @@ pub fn resolve_file_deps(source_files: &[&str], repo_root: &Path) -> Vec<(String
         }
     }
 
+    edges.sort();
     edges
 }
 
+/// Resolve the import graph for every analyzable source file under `path`.
+///
+/// Files are discovered and filtered exactly as `analyze` does (built-in
+/// excludes plus the config's include/exclude). Returns `(importer, imported)`
+/// pairs with paths relative to `repo_root`, sorted for deterministic output.
+pub fn import_graph(
+    path: &Path,
+    repo_root: &Path,
+    resolved_config: Option<&crate::ResolvedConfig>,
+) -> anyhow::Result<Vec<(String, String)>> {
+    let files: Vec<String> = crate::collect_source_files(path)?
+        .into_iter()
+        .filter(|f| resolved_config.map_or(true, |c| c.should_include(f)))
+        .map(|f| f.to_string_lossy().into_owned())
+        .collect();
+    let file_refs: Vec<&str> = files.iter().map(|s| s.as_str()).collect();
+    let rel = |file: String| -> String {
+        Path::new(&file)
+            .strip_prefix(repo_root)
+            .map(|p| p.to_string_lossy().replace('\\', "/"))
+            .unwrap_or(file)
+    };
+    let mut edges: Vec<(String, String)> = resolve_file_deps(&file_refs, repo_root)
+        .into_iter()
+        .map(|(from, to)| (rel(from), rel(to)))
+        .collect();
+    edges.sort();
+    Ok(edges)
+}
+
+/// Render import edges as a Graphviz `digraph`.
+pub fn render_dot(edges: &[(String, String)]) -> String {
+    let mut out = String::from("digraph imports {\n");
+    for (from, to) in edges {
+        out.push_str(&format!("  {} -> {};\n", dot_quote(from), dot_quote(to)));
+    }
+    out.push_str("}\n");
+    out
+}
+
+/// `id` as a quoted DOT ID. Quotes and backslashes are escaped and line breaks
+/// become `\n`, so the default node label shows the path as written.
+fn dot_quote(id: &str) -> String {
+    let mut quoted = String::with_capacity(id.len() + 2);
+    quoted.push('"');
+    for c in id.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            _ => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+/// Render import edges as a JSON array of `{"from", "to"}` objects.
+pub fn render_json(edges: &[(String, String)]) -> String {
+    let entries: Vec<serde_json::Value> = edges
+        .iter()
+        .map(|(from, to)| serde_json::json!({ "from": from, "to": to }))
+        .collect();
+    serde_json::to_string_pretty(&entries).unwrap_or_else(|_| "[]".to_string())
+}
+
 /// Collect all resolved import edges originating from a single source file.
 fn extract_file_import_edges(
     file: &str,
@@ -637,7 +711,7 @@ fn extract_file_import_edges(
         repo_root.join(file)
     };
 
-    let source = match std::fs::read_to_string(&abs_path) {
+    let source = match crate::language::read_source(&abs_path) {
         Ok(s) => s,
         Err(_) => return vec![],
     };
@@ -922,4 +996,41 @@ use std::collections::HashMap;
         let normalized = normalize_path_lexically(p);
         assert_eq!(normalized, Path::new("/foo/baz/qux"));
     }
+
+    #[test]
+    fn test_import_graph_is_relative_and_sorted() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        std::fs::write(root.join("b.ts"), "import { a } from './a';\n").unwrap();
+        std::fs::write(
+            root.join("c.ts"),
+            "import { b } from './b';\nimport { a } from './a';\nimport x from 'lodash';\n",
+        )
+        .unwrap();
+        std::fs::write(root.join("a.ts"), "export const a = 1;\n").unwrap();
+
+        let edges = import_graph(root, root, None).unwrap();
+        let pair = |f: &str, t: &str| (f.to_string(), t.to_string());
+        assert_eq!(
+            edges,
+            vec![
+                pair("b.ts", "a.ts"),
+                pair("c.ts", "a.ts"),
+                pair("c.ts", "b.ts")
+            ]
+        );
+        assert_eq!(
+            render_dot(&edges[..1]),
+            "digraph imports {\n  \"b.ts\" -> \"a.ts\";\n}\n"
+        );
+    }
+
+    #[test]
+    fn test_render_dot_escapes_ids() {
+        let edges = vec![("src/say \"hi\".ts".to_string(), "src\\a\nb.ts".to_string())];
+        assert_eq!(
+            render_dot(&edges),
+            "digraph imports {\n  \"src/say \\\"hi\\\".ts\" -> \"src\\\\a\\nb.ts\";\n}\n"
+        );
+    }
 }
@@ -2,9 +2,11 @@
 
 use crate::ast::FunctionNode;
 use crate::cfg::{Cfg, NodeId, NodeKind};
+use crate::language::c::{BODY_KINDS, FUNCTION_KINDS};
 use crate::language::cfg_builder::CfgBuilder;
 use crate::language::tree_sitter_utils::{
-    find_child_by_kind, find_function_by_start, with_cached_c_tree,
+    find_child_by_kind, find_child_by_kinds, find_function_by_start, node_line, node_loc,
+    with_cached_c_tree,
 };
 use tree_sitter::Node;
 
@@ -16,9 +18,8 @@ impl CfgBuilder for CCfgBuilder {
         let (_body_node_id, source) = function.body.as_c();
 
         let result = with_cached_c_tree(source, |root| {
-            let func_node =
-                find_function_by_start(root, function.span.start, &["function_definition"])?;
-            let body_node = find_child_by_kind(func_node, "compound_statement")?;
+            let func_node = find_function_by_start(root, function.span.start, FUNCTION_KINDS)?;
+            let body_node = find_child_by_kinds(func_node, BODY_KINDS)?;
             let mut builder = CCfgBuilderState::new();
             builder.build_from_block(&body_node, source);
             if let Some(last) = builder.current_node {
@@ -69,6 +70,12 @@ impl CCfgBuilderState {
     }
 
     fn visit_node(&mut self, node: &Node, source: &str) {
+        // Dead code after return/goto/break/continue is counted, not built. A
+        // label is exempt: `goto` can make it reachable again.
+        if self.current_node.is_none() && !matches!(node.kind(), "labeled_statement" | "comment") {
+            self.cfg.record_unreachable();
+            return;
+        }
         match node.kind() {
             "if_statement" => self.visit_if(node, source),
             "while_statement" => self.visit_while(node, source),
@@ -78,7 +85,7 @@ impl CCfgBuilderState {
             "return_statement" => self.visit_return(),
             "break_statement" => self.visit_break(),
             "continue_statement" => self.visit_continue(),
-            "goto_statement" => self.visit_goto(),
+            "goto_statement" => self.visit_goto(node),
             "labeled_statement" => self.visit_labeled(node, source),
             "compound_statement" => self.build_from_block(node, source),
             _ => self.visit_simple_statement(),
@@ -128,7 +135,7 @@ impl CCfgBuilderState {
             return;
         };
 
-        let condition_node = self.cfg.add_node(NodeKind::Condition);
+        let condition_node = self.cfg.add_node_at(NodeKind::Condition, node_line(node));
         self.cfg.add_edge(from_node, condition_node);
 
         // join_node is created lazily — only if at least one branch falls through.
@@ -189,7 +196,7 @@ impl CCfgBuilderState {
             return;
         };
 
-        let loop_header = self.cfg.add_node(NodeKind::LoopHeader);
+        let loop_header = self.cfg.add_node_at(NodeKind::LoopHeader, node_line(node));
         self.cfg.add_edge(from_node, loop_header);
 
         let body_start = self.cfg.add_node(NodeKind::Statement);
@@ -226,7 +233,7 @@ impl CCfgBuilderState {
             return;
         };
 
-        let loop_header = self.cfg.add_node(NodeKind::LoopHeader);
+        let loop_header = self.cfg.add_node_at(NodeKind::LoopHeader, node_line(node));
         self.cfg.add_edge(from_node, loop_header);
 
         let body_start = self.cfg.add_node(NodeKind::Statement);
@@ -266,7 +273,7 @@ impl CCfgBuilderState {
         let body_start = self.cfg.add_node(NodeKind::Statement);
         self.cfg.add_edge(from_node, body_start);
 
-        let loop_header = self.cfg.add_node(NodeKind::LoopHeader);
+        let loop_header = self.cfg.add_node_at(NodeKind::LoopHeader, node_line(node));
 
         self.loop_stack.push(LoopContext {
             break_target: None,
@@ -298,7 +305,7 @@ impl CCfgBuilderState {
             return;
         };
 
-        let switch_node = self.cfg.add_node(NodeKind::Condition);
+        let switch_node = self.cfg.add_node_at(NodeKind::Condition, node_line(node));
         self.cfg.add_edge(from_node, switch_node);
 
         self.loop_stack.push(LoopContext {
@@ -313,6 +320,7 @@ impl CCfgBuilderState {
             let mut cursor = body.walk();
             for child in body.children(&mut cursor) {
                 if child.kind() == "case_statement" || child.kind() == "default_statement" {
+                    self.cfg.record_arm_loc(node_loc(&child));
                     let case_start = self.cfg.add_node(NodeKind::Statement);
                     self.cfg.add_edge(switch_node, case_start);
                     self.current_node = Some(case_start);
@@ -346,10 +354,10 @@ impl CCfgBuilderState {
         }
     }
 
-    fn visit_goto(&mut self) {
+    fn visit_goto(&mut self, node: &Node) {
         if let Some(from_node) = self.current_node {
             // goto is a branch: +1 CC, then control leaves this path
-            let goto_node = self.cfg.add_node(NodeKind::Condition);
+            let goto_node = self.cfg.add_node_at(NodeKind::Condition, node_line(node));
             self.cfg.add_edge(from_node, goto_node);
             self.cfg.add_edge(goto_node, self.cfg.exit);
             self.current_node = None;
@@ -429,7 +437,7 @@ mod tests {
         let mut cursor = root.walk();
         let func_node = root
             .children(&mut cursor)
-            .find(|n| n.kind() == "function_definition")
+            .find(|n| FUNCTION_KINDS.contains(&n.kind()))
             .expect("No function found in test source");
 
         FunctionNode {
@@ -438,12 +446,14 @@ mod tests {
                 local_index: 0,
             },
             name: Some("test_func".to_string()),
+            kind: crate::ast::FunctionKind::Function,
             span: SourceSpan::new(
                 func_node.start_byte(),
                 func_node.end_byte(),
                 func_node.start_position().row as u32 + 1,
                 func_node.end_position().row as u32 + 1,
                 func_node.start_position().column as u32,
+                func_node.end_position().column as u32,
             ),
             body: FunctionBody::C {
                 body_node: 0,
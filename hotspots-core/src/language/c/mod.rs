@@ -8,3 +8,26 @@ pub mod parser;
 
 pub use cfg_builder::CCfgBuilder;
 pub use parser::CParser;
+
+/// Function definitions; prototypes have no body and are skipped
+pub(crate) const FUNCTION_KINDS: &[&str] = &["function_definition"];
+
+/// The `compound_statement` after the declarator
+pub(crate) const BODY_KINDS: &[&str] = &["compound_statement"];
+
+/// Statements that open a nesting level for ND
+pub(crate) const NESTING_KINDS: &[&str] = &[
+    "if_statement",
+    "while_statement",
+    "do_statement",
+    "for_statement",
+    "switch_statement",
+];
+
+/// Jump statements counted for NS, `goto` included
+pub(crate) const EXIT_KINDS: &[&str] = &[
+    "return_statement",
+    "break_statement",
+    "continue_statement",
+    "goto_statement",
+];
@@ -1,8 +1,9 @@
 //! C language parser using tree-sitter
 
 use crate::ast::FunctionNode;
+use crate::language::c::{BODY_KINDS, FUNCTION_KINDS};
 use crate::language::parser::{LanguageParser, ParsedModule};
-use crate::language::tree_sitter_utils::find_child_by_kind;
+use crate::language::tree_sitter_utils::find_child_by_kinds;
 use anyhow::{Context, Result};
 use tree_sitter::{Node, Parser, Tree};
 
@@ -66,7 +67,7 @@ fn discover_functions_recursive(
     file_index: usize,
     functions: &mut Vec<FunctionNode>,
 ) {
-    if node.kind() == "function_definition" {
+    if FUNCTION_KINDS.contains(&node.kind()) {
         if let Some(function_node) = extract_function(node, source, file_index, functions.len()) {
             functions.push(function_node);
         }
@@ -84,14 +85,13 @@ fn extract_function(
     file_index: usize,
     local_index: usize,
 ) -> Option<FunctionNode> {
-    use crate::ast::FunctionId;
+    use crate::ast::{FunctionId, FunctionKind};
     use crate::language::{FunctionBody, SourceSpan};
 
     // C function_definition has a declarator child containing the function name
     let name = extract_function_name(node, source);
 
-    // Body is a compound_statement
-    let body_node = find_child_by_kind(node, "compound_statement")?;
+    let body_node = find_child_by_kinds(node, BODY_KINDS)?;
 
     let span = SourceSpan::new(
         node.start_byte(),
@@ -99,6 +99,7 @@ fn extract_function(
         node.start_position().row as u32 + 1,
         node.end_position().row as u32 + 1,
         node.start_position().column as u32,
+        node.end_position().column as u32,
     );
 
     let body = FunctionBody::C {
@@ -112,6 +113,7 @@ fn extract_function(
             local_index,
         },
         name,
+        kind: FunctionKind::Function,
         span,
         body,
         suppression_reason: None,
@@ -2,6 +2,7 @@
 
 use crate::ast::FunctionNode;
 use crate::cfg::Cfg;
+use swc_common::SourceMap;
 
 /// Language-agnostic CFG builder interface
 ///
@@ -18,6 +19,14 @@ pub trait CfgBuilder {
     ///
     /// A CFG representing the control flow of the function
     fn build(&self, function: &FunctionNode) -> Cfg;
+
+    /// Build a CFG whose branch nodes carry source lines
+    ///
+    /// Tree-sitter and syn builders record lines in [`CfgBuilder::build`]
+    /// already; ECMAScript needs the file's source map to resolve them.
+    fn build_with_source_map(&self, function: &FunctionNode, _source_map: &SourceMap) -> Cfg {
+        self.build(function)
+    }
 }
 
 /// Get the appropriate CFG builder for a function based on its language
@@ -34,6 +43,7 @@ pub fn get_builder_for_function(function: &FunctionNode) -> Box<dyn CfgBuilder>
         FunctionBody::Rust { .. } => Box::new(super::rust::RustCfgBuilder),
         FunctionBody::CSharp { .. } => Box::new(super::csharp::CSharpCfgBuilder),
         FunctionBody::C { .. } => Box::new(super::c::CCfgBuilder),
+        FunctionBody::Hcl { .. } => Box::new(super::hcl::HclCfgBuilder),
     }
 }
 
@@ -50,7 +60,8 @@ mod tests {
                 local_index: 0,
             },
             name: Some("test".to_string()),
-            span: SourceSpan::new(0, 10, 1, 1, 0),
+            kind: crate::ast::FunctionKind::Function,
+            span: SourceSpan::new(0, 10, 1, 1, 0, 0),
             body: FunctionBody::ecmascript(swc_ecma_ast::BlockStmt {
                 span: swc_common::DUMMY_SP,
                 ctxt: Default::default(),
@@ -3,8 +3,10 @@
 use crate::ast::FunctionNode;
 use crate::cfg::{Cfg, NodeId, NodeKind};
 use crate::language::cfg_builder::CfgBuilder;
+use crate::language::csharp::{BODY_KINDS, FUNCTION_KINDS};
 use crate::language::tree_sitter_utils::{
-    find_child_by_kind, find_function_by_start, with_cached_csharp_tree,
+    find_child_by_kind, find_child_by_kinds, find_function_by_start, node_line, node_loc,
+    with_cached_csharp_tree,
 };
 use tree_sitter::Node;
 
@@ -15,18 +17,8 @@ impl CfgBuilder for CSharpCfgBuilder {
         let (_body_node_id, source) = function.body.as_csharp();
 
         let result = with_cached_csharp_tree(source, |root| {
-            let func_node = find_function_by_start(
-                root,
-                function.span.start,
-                &[
-                    "method_declaration",
-                    "constructor_declaration",
-                    "local_function_statement",
-                    "operator_declaration",
-                    "conversion_operator_declaration",
-                ],
-            )?;
-            let body_node = find_child_by_kind(func_node, "block")?;
+            let func_node = find_function_by_start(root, function.span.start, FUNCTION_KINDS)?;
+            let body_node = find_child_by_kinds(func_node, BODY_KINDS)?;
             let mut builder = CSharpCfgBuilderState::new();
             builder.build_from_block(&body_node, source);
             Some(builder.cfg)
@@ -85,6 +77,18 @@ impl CSharpCfgBuilderState {
     }
 
     fn visit_node(&mut self, node: &Node, source: &str) {
+        if self.current_node.is_none() {
+            // Dead code after return/throw/break/continue — count it, build
+            // nothing. Labels may be `goto` targets, and local functions are
+            // callable from anywhere in the method, so neither counts.
+            if !matches!(
+                node.kind(),
+                "comment" | "labeled_statement" | "local_function_statement"
+            ) {
+                self.cfg.record_unreachable();
+            }
+            return;
+        }
         match node.kind() {
             "if_statement" => self.visit_if(node, source),
             "while_statement" => self.visit_while(node, source),
@@ -109,7 +113,7 @@ impl CSharpCfgBuilderState {
             return;
         };
 
-        let condition = self.cfg.add_node(NodeKind::Condition);
+        let condition = self.cfg.add_node_at(NodeKind::Condition, node_line(node));
         self.cfg.add_edge(current, condition);
 
         let join = self.cfg.add_node(NodeKind::Statement);
@@ -159,7 +163,7 @@ impl CSharpCfgBuilderState {
             return;
         };
 
-        let condition = self.cfg.add_node(NodeKind::Condition);
+        let condition = self.cfg.add_node_at(NodeKind::Condition, node_line(node));
         self.cfg.add_edge(current, condition);
 
         let after_loop = self.cfg.add_node(NodeKind::Statement);
@@ -192,7 +196,7 @@ impl CSharpCfgBuilderState {
         let body_entry = self.cfg.add_node(NodeKind::Statement);
         self.cfg.add_edge(current, body_entry);
 
-        let condition = self.cfg.add_node(NodeKind::Condition);
+        let condition = self.cfg.add_node_at(NodeKind::Condition, node_line(node));
         let after_loop = self.cfg.add_node(NodeKind::Statement);
 
         self.loop_stack.push(LoopContext {
@@ -223,7 +227,7 @@ impl CSharpCfgBuilderState {
             return;
         };
 
-        let condition = self.cfg.add_node(NodeKind::Condition);
+        let condition = self.cfg.add_node_at(NodeKind::Condition, node_line(node));
         self.cfg.add_edge(current, condition);
 
         let after_loop = self.cfg.add_node(NodeKind::Statement);
@@ -258,7 +262,7 @@ impl CSharpCfgBuilderState {
             return;
         };
 
-        let switch_node = self.cfg.add_node(NodeKind::Condition);
+        let switch_node = self.cfg.add_node_at(NodeKind::Condition, node_line(node));
         self.cfg.add_edge(current, switch_node);
 
         let join = self.cfg.add_node(NodeKind::Statement);
@@ -275,6 +279,7 @@ impl CSharpCfgBuilderState {
 
             for child in body.children(&mut cursor) {
                 if child.kind() == "switch_section" {
+                    self.cfg.record_arm_loc(node_loc(&child));
                     let case_node = self.cfg.add_node(NodeKind::Statement);
                     self.cfg.add_edge(switch_node, case_node);
 
@@ -340,7 +345,7 @@ impl CSharpCfgBuilderState {
         let mut cursor = node.walk();
         for child in node.children(&mut cursor) {
             if child.kind() == "catch_clause" {
-                let catch_node = self.cfg.add_node(NodeKind::Condition);
+                let catch_node = self.cfg.add_node_at(NodeKind::Condition, node_line(&child));
                 self.cfg.add_edge(try_entry, catch_node);
 
                 if let Some(catch_body) = find_child_by_kind(child, "block") {
@@ -438,7 +443,8 @@ mod tests {
                 local_index: 0,
             },
             name: Some("test".to_string()),
-            span: SourceSpan::new(start_byte, end_byte, 1, 1, 0),
+            kind: crate::ast::FunctionKind::Function,
+            span: SourceSpan::new(start_byte, end_byte, 1, 1, 0, 0),
             body: FunctionBody::CSharp {
                 body_node: 0,
                 source: source.to_string(),
@@ -5,3 +5,34 @@ mod parser;
 
 pub use cfg_builder::CSharpCfgBuilder;
 pub use parser::CSharpParser;
+
+/// Declarations analyzed as functions, local functions and operators included
+pub(crate) const FUNCTION_KINDS: &[&str] = &[
+    "method_declaration",
+    "constructor_declaration",
+    "local_function_statement",
+    "operator_declaration",
+    "conversion_operator_declaration",
+];
+
+/// The `block` body; expression-bodied members have none and are skipped
+pub(crate) const BODY_KINDS: &[&str] = &["block"];
+
+/// Statements that open a nesting level for ND
+pub(crate) const NESTING_KINDS: &[&str] = &[
+    "if_statement",
+    "while_statement",
+    "do_statement",
+    "for_statement",
+    "foreach_statement",
+    "switch_statement",
+    "try_statement",
+];
+
+/// Jump statements counted for NS
+pub(crate) const EXIT_KINDS: &[&str] = &[
+    "return_statement",
+    "throw_statement",
+    "break_statement",
+    "continue_statement",
+];
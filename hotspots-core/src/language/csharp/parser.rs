@@ -1,8 +1,9 @@
 //! C# language parser using tree-sitter
 
 use crate::ast::FunctionNode;
+use crate::language::csharp::{BODY_KINDS, FUNCTION_KINDS};
 use crate::language::parser::{LanguageParser, ParsedModule};
-use crate::language::tree_sitter_utils::find_child_by_kind;
+use crate::language::tree_sitter_utils::{find_child_by_kind, find_child_by_kinds};
 use anyhow::{Context, Result};
 use tree_sitter::{Node, Parser, Tree};
 
@@ -65,18 +66,10 @@ fn discover_functions_recursive(
     file_index: usize,
     functions: &mut Vec<FunctionNode>,
 ) {
-    match node.kind() {
-        "method_declaration"
-        | "constructor_declaration"
-        | "local_function_statement"
-        | "operator_declaration"
-        | "conversion_operator_declaration" => {
-            if let Some(function_node) = extract_function(node, source, file_index, functions.len())
-            {
-                functions.push(function_node);
-            }
+    if FUNCTION_KINDS.contains(&node.kind()) {
+        if let Some(function_node) = extract_function(node, source, file_index, functions.len()) {
+            functions.push(function_node);
         }
-        _ => {}
     }
 
     let mut cursor = node.walk();
@@ -91,12 +84,17 @@ fn extract_function(
     file_index: usize,
     local_index: usize,
 ) -> Option<FunctionNode> {
-    use crate::ast::FunctionId;
+    use crate::ast::{FunctionId, FunctionKind};
     use crate::language::{FunctionBody, SourceSpan};
 
     let name = extract_function_name(node, source);
+    let kind = match node.kind() {
+        "constructor_declaration" => FunctionKind::Constructor,
+        "local_function_statement" => FunctionKind::Function,
+        _ => FunctionKind::Method,
+    };
 
-    let body_node = find_child_by_kind(node, "block")?;
+    let body_node = find_child_by_kinds(node, BODY_KINDS)?;
 
     let span = SourceSpan::new(
         node.start_byte(),
@@ -104,6 +102,7 @@ fn extract_function(
         node.start_position().row as u32 + 1,
         node.end_position().row as u32 + 1,
         node.start_position().column as u32,
+        node.end_position().column as u32,
     );
 
     let body = FunctionBody::CSharp {
@@ -117,6 +116,7 @@ fn extract_function(
             local_index,
         },
         name,
+        kind,
         span,
         body,
         suppression_reason: None,
@@ -4,10 +4,11 @@ use super::cfg_builder::CfgBuilder;
 use super::parser::{LanguageParser, ParsedModule};
 use crate::ast::FunctionNode;
 use crate::cfg::Cfg;
-use anyhow::Result;
+use anyhow::{Context, Result};
 use regex::Regex;
-use swc_common::{sync::Lrc, SourceMap};
-use swc_ecma_ast::Module;
+use swc_common::{sync::Lrc, SourceMap, Spanned};
+use swc_ecma_ast::{Decl, EmptyStmt, Expr, Invalid, KeyValueProp, Module, Prop, Stmt};
+use swc_ecma_visit::{VisitMut, VisitMutWith};
 
 /// ECMAScript parser using SWC
 ///
@@ -44,6 +45,95 @@ impl ParsedModule for ECMAScriptModule {
     fn discover_functions(&self, file_index: usize, source: &str) -> Vec<FunctionNode> {
         crate::discover::discover_functions(&self.module, file_index, source, &self.source_map)
     }
+
+    fn top_level_unit(&self, file_index: usize, _source: &str) -> Option<FunctionNode> {
+        use swc_ecma_ast::{BlockStmt, ExprStmt, ModuleDecl, ModuleItem};
+
+        // Function and class declarations are analyzed as their own functions;
+        // everything else at module level (including `export const` and
+        // `export default <expr>`) is top-level code. Imports carry no logic.
+        let stmts: Vec<Stmt> = self
+            .module
+            .body
+            .iter()
+            .filter_map(|item| match item {
+                ModuleItem::Stmt(Stmt::Decl(Decl::Fn(_) | Decl::Class(_))) => None,
+                ModuleItem::Stmt(stmt) => Some(stmt.clone()),
+                ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(export)) => match &export.decl {
+                    Decl::Fn(_) | Decl::Class(_) => None,
+                    decl => Some(Stmt::Decl(decl.clone())),
+                },
+                ModuleItem::ModuleDecl(ModuleDecl::ExportDefaultExpr(export)) => {
+                    Some(Stmt::Expr(ExprStmt {
+                        span: export.span,
+                        expr: export.expr.clone(),
+                    }))
+                }
+                ModuleItem::ModuleDecl(_) => None,
+            })
+            .collect();
+        if stmts.is_empty() {
+            return None;
+        }
+        // Function bodies nested in top-level code (`const f = () => {…}`,
+        // callbacks, object methods) are discovered as functions of their own
+        let mut stmts = stmts;
+        stmts.visit_mut_with(&mut StripDiscoveredFunctions);
+        Some(FunctionNode {
+            id: crate::ast::FunctionId {
+                file_index,
+                // Sorts after every discovered function
+                local_index: usize::MAX,
+            },
+            name: Some(crate::ast::TOP_LEVEL_NAME.to_string()),
+            kind: crate::ast::FunctionKind::Function,
+            span: super::span::span_with_location(self.module.span, &self.source_map),
+            body: super::FunctionBody::ecmascript(BlockStmt {
+                span: swc_common::DUMMY_SP,
+                ctxt: Default::default(),
+                stmts,
+            }),
+            suppression_reason: None,
+        })
+    }
+}
+
+/// Replaces every function [`crate::discover`] reports on its own with a
+/// placeholder, so top-level code does not count their bodies a second time
+struct StripDiscoveredFunctions;
+
+impl VisitMut for StripDiscoveredFunctions {
+    fn visit_mut_expr(&mut self, expr: &mut Expr) {
+        match expr {
+            Expr::Fn(_) | Expr::Arrow(_) | Expr::Class(_) => {
+                *expr = Expr::Invalid(Invalid { span: expr.span() });
+            }
+            _ => expr.visit_mut_children_with(self),
+        }
+    }
+
+    fn visit_mut_stmt(&mut self, stmt: &mut Stmt) {
+        match stmt {
+            Stmt::Decl(Decl::Fn(_) | Decl::Class(_)) => {
+                *stmt = Stmt::Empty(EmptyStmt { span: stmt.span() });
+            }
+            _ => stmt.visit_mut_children_with(self),
+        }
+    }
+
+    fn visit_mut_prop(&mut self, prop: &mut Prop) {
+        match prop {
+            Prop::Method(method) => {
+                *prop = Prop::KeyValue(KeyValueProp {
+                    key: method.key.clone(),
+                    value: Box::new(Expr::Invalid(Invalid {
+                        span: method.function.span,
+                    })),
+                });
+            }
+            _ => prop.visit_mut_children_with(self),
+        }
+    }
 }
 
 /// ECMAScript CFG builder
@@ -57,9 +147,13 @@ impl CfgBuilder for ECMAScriptCfgBuilder {
         // which already knows how to build CFGs from ECMAScript functions
         crate::cfg::builder::build_cfg(function)
     }
+
+    fn build_with_source_map(&self, function: &FunctionNode, source_map: &SourceMap) -> Cfg {
+        crate::cfg::builder::build_cfg_with_lines(function, source_map)
+    }
 }
 
-/// Extracted script block from a Vue SFC
+/// Extracted script block from a Vue SFC or Svelte component
 struct ScriptBlock {
     /// The raw content between `<script ...>` and `</script>`
     content: String,
@@ -67,44 +161,54 @@ struct ScriptBlock {
     start_line: u32,
     /// Whether `lang="ts"` (or `lang='ts'`) was detected on the script tag
     is_typescript: bool,
+    /// Whether this is a Svelte module script (`context="module"`, or the bare
+    /// `module` attribute of Svelte 5)
+    is_module: bool,
+}
+
+/// Extract every `<script>` block from an SFC source, in source order
+fn extract_script_blocks(source: &str) -> Vec<ScriptBlock> {
+    let open_re = Regex::new(r"(?i)<script(\s[^>]*)?>").unwrap();
+    let lang_re = Regex::new(r#"lang\s*=\s*['"]ts['"]"#).unwrap();
+    let module_re = Regex::new(r#"context\s*=\s*['"]module['"]|(?:^|\s)module(?:\s|/|$)"#).unwrap();
+    let close_tag = "</script>";
+
+    let mut blocks = Vec::new();
+    let mut search_from = 0;
+    while let Some(caps) = open_re.captures(&source[search_from..]) {
+        let open_m = caps.get(0).unwrap();
+        let attrs = caps.get(1).map(|m| m.as_str()).unwrap_or("");
+
+        let content_start = search_from + open_m.end();
+        let Some(close_pos) = source[content_start..].find(close_tag) else {
+            break;
+        };
+        let content = source[content_start..content_start + close_pos].to_string();
+
+        // Count lines before content_start to get the 1-indexed line of the first
+        // content line (the newline after the opening tag puts content on the next line).
+        let start_line = source[..content_start]
+            .chars()
+            .filter(|&c| c == '\n')
+            .count() as u32
+            + 1;
+
+        blocks.push(ScriptBlock {
+            content,
+            start_line,
+            is_typescript: lang_re.is_match(attrs),
+            is_module: module_re.is_match(attrs),
+        });
+        search_from = content_start + close_pos + close_tag.len();
+    }
+    blocks
 }
 
 /// Extract the first `<script>` or `<script setup>` block from a Vue SFC source.
 ///
 /// Returns `None` if no script block is found.
 fn extract_script_block(source: &str) -> Option<ScriptBlock> {
-    let open_re = Regex::new(r"(?i)<script(\s[^>]*)?>").unwrap();
-    let close_tag = "</script>";
-
-    let open_m = open_re.find(source)?;
-    let attrs = open_re
-        .captures(source)
-        .and_then(|c| c.get(1))
-        .map(|m| m.as_str())
-        .unwrap_or("");
-
-    let is_typescript = {
-        let lang_re = Regex::new(r#"lang\s*=\s*['"]ts['"]"#).unwrap();
-        lang_re.is_match(attrs)
-    };
-
-    let content_start = open_m.end();
-    let close_pos = source[content_start..].find(close_tag)?;
-    let content = source[content_start..content_start + close_pos].to_string();
-
-    // Count lines before content_start to get the 1-indexed line of the first
-    // content line (the newline after the opening tag puts content on the next line).
-    let start_line = source[..content_start]
-        .chars()
-        .filter(|&c| c == '\n')
-        .count() as u32
-        + 1;
-
-    Some(ScriptBlock {
-        content,
-        start_line,
-        is_typescript,
-    })
+    extract_script_blocks(source).into_iter().next()
 }
 
 /// Vue SFC parser — extracts the `<script>` block and delegates to ECMAScriptParser.
@@ -158,6 +262,121 @@ impl ParsedModule for VueParsedModule {
         }
         functions
     }
+
+    fn top_level_unit(&self, file_index: usize, source: &str) -> Option<FunctionNode> {
+        let mut unit = self.inner.top_level_unit(file_index, source)?;
+        unit.span.start_line += self.line_offset;
+        unit.span.end_line += self.line_offset;
+        Some(unit)
+    }
+}
+
+/// Svelte component parser — extracts the module (`<script context="module">`)
+/// and instance `<script>` blocks and delegates each to ECMAScriptParser.
+///
+/// Markup and styles are ignored. A component without a script block has no
+/// functions. Reactive `$:` statements are labeled statements to the
+/// ECMAScript parser, so they are analyzed as ordinary code.
+pub struct SvelteParser {
+    inner: ECMAScriptParser,
+}
+
+impl SvelteParser {
+    pub fn new(source_map: Lrc<SourceMap>) -> Self {
+        SvelteParser {
+            inner: ECMAScriptParser::new(source_map),
+        }
+    }
+}
+
+impl LanguageParser for SvelteParser {
+    fn parse(&self, source: &str, filename: &str) -> Result<Box<dyn ParsedModule>> {
+        // A component has at most one script of each kind; later `<script>`
+        // tags belong to markup such as `<svelte:head>`
+        let blocks = extract_script_blocks(source);
+        let module_block = blocks.iter().find(|b| b.is_module);
+        let instance_block = blocks.iter().find(|b| !b.is_module);
+        let mut selected: Vec<&ScriptBlock> =
+            module_block.into_iter().chain(instance_block).collect();
+        selected.sort_by_key(|b| b.start_line);
+
+        let mut scripts = Vec::with_capacity(selected.len());
+        for block in selected {
+            // Pick a synthetic filename so SWC uses the right syntax
+            let synthetic = if block.is_typescript {
+                "__svelte_script__.ts"
+            } else {
+                "__svelte_script__.js"
+            };
+            let module = self
+                .inner
+                .parse(&block.content, synthetic)
+                .with_context(|| {
+                    format!(
+                        "failed to parse <script> block at line {} of {}",
+                        block.start_line, filename
+                    )
+                })?;
+            scripts.push(SvelteScript {
+                module,
+                content: block.content.clone(),
+                line_offset: block.start_line.saturating_sub(1),
+            });
+        }
+        Ok(Box::new(SvelteParsedModule { scripts }))
+    }
+}
+
+/// One parsed script block of a Svelte component
+struct SvelteScript {
+    module: Box<dyn ParsedModule>,
+    /// The block's own source, which its spans (before shifting) refer to
+    content: String,
+    /// Lines to add to each reported start_line / end_line
+    line_offset: u32,
+}
+
+/// The script blocks of a Svelte component, in source order
+struct SvelteParsedModule {
+    scripts: Vec<SvelteScript>,
+}
+
+impl ParsedModule for SvelteParsedModule {
+    fn discover_functions(&self, file_index: usize, _source: &str) -> Vec<FunctionNode> {
+        let mut functions = Vec::new();
+        for script in &self.scripts {
+            // Each block numbers its functions from zero; continue the count
+            let first_index = functions.len();
+            for mut f in script
+                .module
+                .discover_functions(file_index, &script.content)
+            {
+                f.id.local_index += first_index;
+                f.span.start_line += script.line_offset;
+                f.span.end_line += script.line_offset;
+                functions.push(f);
+            }
+        }
+        functions
+    }
+
+    fn top_level_unit(&self, file_index: usize, _source: &str) -> Option<FunctionNode> {
+        // Both blocks' top-level code forms the component's single top-level unit
+        let mut units = self.scripts.iter().filter_map(|script| {
+            let mut unit = script.module.top_level_unit(file_index, &script.content)?;
+            unit.span.start_line += script.line_offset;
+            unit.span.end_line += script.line_offset;
+            Some(unit)
+        });
+        let mut unit = units.next()?;
+        for other in units {
+            let stmts = other.body.as_ecmascript().stmts.clone();
+            unit.body.as_ecmascript_mut().stmts.extend(stmts);
+            unit.span.end = other.span.end;
+            unit.span.end_line = other.span.end_line;
+        }
+        Some(unit)
+    }
 }
 
 #[cfg(test)]
@@ -301,4 +520,27 @@ mod tests {
         assert_eq!(functions1[1].name, Some("aaa".to_string()));
         assert_eq!(functions1[2].name, Some("mmm".to_string()));
     }
+
+    #[test]
+    fn test_svelte_parser_numbers_functions_across_blocks() {
+        let source = "<script>\n  function instance() {}\n</script>\n\n\
+                      <script context=\"module\">\n  export function shared() {}\n</script>\n\n\
+                      <svelte:head><script>function head() {}</script></svelte:head>\n";
+        let parser = SvelteParser::new(Default::default());
+        let module = parser.parse(source, "App.svelte").unwrap();
+        let functions = module.discover_functions(0, source);
+
+        let found: Vec<(Option<&str>, u32, usize)> = functions
+            .iter()
+            .map(|f| (f.name.as_deref(), f.span.start_line, f.id.local_index))
+            .collect();
+        assert_eq!(
+            found,
+            vec![(Some("instance"), 2, 0), (Some("shared"), 6, 1)]
+        );
+
+        let markup_only = "<h1>Hello</h1>\n";
+        let module = parser.parse(markup_only, "Title.svelte").unwrap();
+        assert!(module.discover_functions(0, markup_only).is_empty());
+    }
 }
@@ -75,6 +75,15 @@ pub enum FunctionBody {
         /// The source code (needed to reconstruct the tree)
         source: String,
     },
+
+    /// Terraform/HCL block body
+    ///
+    /// Contains the source code for the whole block (header + body).
+    /// We re-parse on demand using hcl-edit when building CFG or extracting metrics.
+    Hcl {
+        /// The source code for the entire block
+        source: String,
+    },
 }
 
 impl FunctionBody {
@@ -118,6 +127,11 @@ impl FunctionBody {
         matches!(self, FunctionBody::C { .. })
     }
 
+    /// Check if this is a Terraform/HCL block body
+    pub fn is_hcl(&self) -> bool {
+        matches!(self, FunctionBody::Hcl { .. })
+    }
+
     /// Get the ECMAScript body, if this is one
     ///
     /// # Panics
@@ -214,6 +228,18 @@ impl FunctionBody {
             _ => panic!("FunctionBody is not C"),
         }
     }
+
+    /// Get the HCL block source, if this is a Terraform/HCL block
+    ///
+    /// # Panics
+    ///
+    /// Panics if this is not an HCL body. Use `is_hcl()` to check first.
+    pub fn as_hcl(&self) -> &str {
+        match self {
+            FunctionBody::Hcl { source } => source.as_str(),
+            _ => panic!("FunctionBody is not HCL"),
+        }
+    }
 }
 
 // Implement From for easy conversion
@@ -3,8 +3,10 @@
 use crate::ast::FunctionNode;
 use crate::cfg::{Cfg, NodeId, NodeKind};
 use crate::language::cfg_builder::CfgBuilder;
+use crate::language::go::{BODY_KINDS, FUNCTION_KINDS};
 use crate::language::tree_sitter_utils::{
-    find_child_by_kind, find_function_by_start, with_cached_go_tree,
+    find_child_by_kind, find_child_by_kinds, find_function_by_start, node_line, node_loc,
+    with_cached_go_tree,
 };
 use tree_sitter::Node;
 
@@ -18,14 +20,11 @@ impl CfgBuilder for GoCfgBuilder {
         let (_body_node_id, source) = function.body.as_go();
 
         let result = with_cached_go_tree(source, |root| {
-            let func_node = find_function_by_start(
-                root,
-                function.span.start,
-                &["function_declaration", "method_declaration"],
-            )?;
-            let body_node = find_child_by_kind(func_node, "block")?;
+            let func_node = find_function_by_start(root, function.span.start, FUNCTION_KINDS)?;
+            let body_node = find_child_by_kinds(func_node, BODY_KINDS)?;
             let mut builder = GoCfgBuilderState::new();
             builder.build_from_block(&body_node, source);
+            record_case_locs(&body_node, &mut builder.cfg);
             Some(builder.cfg)
         });
 
@@ -37,6 +36,23 @@ impl CfgBuilder for GoCfgBuilder {
     }
 }
 
+/// Record the size of every switch/select case in a body, excluding function
+/// literals. Walks the tree directly because the builder does not descend into
+/// a body's `statement_list`.
+fn record_case_locs(node: &Node, cfg: &mut Cfg) {
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        match child.kind() {
+            "func_literal" => continue,
+            "expression_case" | "type_case" | "default_case" | "communication_case" => {
+                cfg.record_arm_loc(node_loc(&child));
+            }
+            _ => {}
+        }
+        record_case_locs(&child, cfg);
+    }
+}
+
 /// Internal builder state for constructing the CFG
 struct GoCfgBuilderState {
     cfg: Cfg,
@@ -130,7 +146,7 @@ impl GoCfgBuilderState {
         let from_node = self.current_node.expect("Current node should exist");
 
         // Condition node
-        let condition_node = self.cfg.add_node(NodeKind::Condition);
+        let condition_node = self.cfg.add_node_at(NodeKind::Condition, node_line(node));
         self.cfg.add_edge(from_node, condition_node);
 
         // Then branch (consequence block)
@@ -175,7 +191,7 @@ impl GoCfgBuilderState {
         let from_node = self.current_node.expect("Current node should exist");
 
         // Loop header
-        let loop_header = self.cfg.add_node(NodeKind::LoopHeader);
+        let loop_header = self.cfg.add_node_at(NodeKind::LoopHeader, node_line(node));
         self.cfg.add_edge(from_node, loop_header);
 
         // Loop body
@@ -217,7 +233,7 @@ impl GoCfgBuilderState {
         let from_node = self.current_node.expect("Current node should exist");
 
         // Switch condition
-        let condition_node = self.cfg.add_node(NodeKind::Condition);
+        let condition_node = self.cfg.add_node_at(NodeKind::Condition, node_line(node));
         self.cfg.add_edge(from_node, condition_node);
 
         // Join node after switch
@@ -264,7 +280,7 @@ impl GoCfgBuilderState {
         let from_node = self.current_node.expect("Current node should exist");
 
         // Select condition (non-deterministic choice)
-        let condition_node = self.cfg.add_node(NodeKind::Condition);
+        let condition_node = self.cfg.add_node_at(NodeKind::Condition, node_line(node));
         self.cfg.add_edge(from_node, condition_node);
 
         // Join node after select
@@ -382,7 +398,8 @@ mod tests {
                 local_index: 0,
             },
             name: Some("test".to_string()),
-            span: SourceSpan::new(0, source.len(), 1, 1, 0),
+            kind: crate::ast::FunctionKind::Function,
+            span: SourceSpan::new(0, source.len(), 1, 1, 0, 0),
             body: FunctionBody::Go {
                 body_node: 0,
                 source: source.to_string(),
@@ -8,3 +8,19 @@ pub mod parser;
 
 pub use cfg_builder::GoCfgBuilder;
 pub use parser::GoParser;
+
+/// Function and method declarations
+pub(crate) const FUNCTION_KINDS: &[&str] = &["function_declaration", "method_declaration"];
+
+/// The `block` holding a declaration's statements
+pub(crate) const BODY_KINDS: &[&str] = &["block"];
+
+/// Statements that open a nesting level for ND, including all three switch forms and `select`
+pub(crate) const NESTING_KINDS: &[&str] = &[
+    "if_statement",
+    "for_statement",
+    "switch_statement",
+    "expression_switch_statement",
+    "type_switch_statement",
+    "select_statement",
+];
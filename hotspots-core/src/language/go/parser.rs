@@ -1,8 +1,9 @@
 //! Go language parser using tree-sitter
 
 use crate::ast::FunctionNode;
+use crate::language::go::{BODY_KINDS, FUNCTION_KINDS};
 use crate::language::parser::{LanguageParser, ParsedModule};
-use crate::language::tree_sitter_utils::find_child_by_kind;
+use crate::language::tree_sitter_utils::{find_child_by_kind, find_child_by_kinds};
 use anyhow::{Context, Result};
 use tree_sitter::{Node, Parser, Tree};
 
@@ -78,7 +79,7 @@ fn discover_functions_recursive(
     functions: &mut Vec<FunctionNode>,
 ) {
     // Check if this node is a function declaration
-    if node.kind() == "function_declaration" || node.kind() == "method_declaration" {
+    if FUNCTION_KINDS.contains(&node.kind()) {
         if let Some(function_node) = extract_function(node, source, file_index, functions.len()) {
             functions.push(function_node);
         }
@@ -98,14 +99,19 @@ fn extract_function(
     file_index: usize,
     local_index: usize,
 ) -> Option<FunctionNode> {
-    use crate::ast::FunctionId;
+    use crate::ast::{FunctionId, FunctionKind};
     use crate::language::{FunctionBody, SourceSpan};
 
     // Get function name
     let name = extract_function_name(node, source);
+    let kind = if node.kind() == "method_declaration" {
+        FunctionKind::Method
+    } else {
+        FunctionKind::Function
+    };
 
     // Get function body (block node)
-    let body_node = find_child_by_kind(node, "block")?;
+    let body_node = find_child_by_kinds(node, BODY_KINDS)?;
 
     // Create SourceSpan from tree-sitter node
     let span = SourceSpan::new(
@@ -114,6 +120,7 @@ fn extract_function(
         node.start_position().row as u32 + 1, // tree-sitter uses 0-indexed rows
         node.end_position().row as u32 + 1,   // tree-sitter uses 0-indexed rows
         node.start_position().column as u32,
+        node.end_position().column as u32,
     );
 
     // Create FunctionBody::Go variant (placeholder for now)
@@ -128,6 +135,7 @@ fn extract_function(
             local_index,
         },
         name,
+        kind,
         span,
         body,
         suppression_reason: None, // Will be extracted separately
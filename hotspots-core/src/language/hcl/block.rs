@@ -0,0 +1,253 @@
+//! Metric-relevant facts of one Terraform/HCL block
+//!
+//! A block plays the part of a function: its decisions are the meta-arguments
+//! that multiply or drop instances (`count`, `for_each`), `dynamic` blocks, and
+//! `? :` conditionals; its nesting is block nesting; its calls are references
+//! to modules, resources, and data sources. The CFG builder, metric
+//! extraction, and AST dump all read these facts so they stay in agreement.
+
+use hcl_edit::expr::{Conditional, Expression, ForExpr, Traversal, TraversalOperator};
+use hcl_edit::structure::{Attribute, Block, Body};
+use hcl_edit::visit::{self, Visit};
+use hcl_edit::Span;
+use std::collections::{BTreeSet, HashSet};
+
+/// A syntax site inside a block, located by byte offset into the block source
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Site {
+    pub offset: usize,
+    /// Node kind, or the referenced address for references
+    pub kind: String,
+}
+
+/// What a block contributes to CC, ND, FO, and NS
+#[derive(Debug, Clone, Default)]
+pub(crate) struct BlockFacts {
+    /// `count`/`for_each` meta-arguments, `dynamic` blocks, and conditionals
+    pub decisions: Vec<Site>,
+    /// Blocks nested anywhere inside the block
+    pub blocks: Vec<Site>,
+    /// Deepest block nesting below the block itself
+    pub max_depth: usize,
+    /// Module, resource, and data source references, in source order
+    pub references: Vec<Site>,
+}
+
+impl BlockFacts {
+    /// Distinct referenced addresses, sorted
+    pub fn callee_names(&self) -> Vec<String> {
+        let names: BTreeSet<&str> = self.references.iter().map(|r| r.kind.as_str()).collect();
+        names.into_iter().map(str::to_string).collect()
+    }
+}
+
+/// Parse the source of a single block, as stored in [`crate::language::FunctionBody::Hcl`]
+pub(crate) fn parse_block(source: &str) -> Option<Block> {
+    let body = hcl_edit::parser::parse_body(source).ok()?;
+    body.into_blocks().next()
+}
+
+/// Collect the metric-relevant facts of `block`
+pub(crate) fn block_facts(block: &Block) -> BlockFacts {
+    let mut bound = HashSet::new();
+    collect_bound_names(&block.body, &mut bound);
+
+    let mut collector = FactCollector {
+        facts: BlockFacts::default(),
+        bound,
+        depth: 0,
+        in_dynamic: false,
+    };
+    collector.visit_body(&block.body);
+
+    let mut facts = collector.facts;
+    facts.decisions.sort_by_key(|s| s.offset);
+    facts.blocks.sort_by_key(|s| s.offset);
+    facts.references.sort_by_key(|s| s.offset);
+    facts
+}
+
+/// Iterator names introduced by `dynamic` blocks, which read like resource
+/// references (`ingress.value`) but are local to the block
+fn collect_bound_names(body: &Body, bound: &mut HashSet<String>) {
+    for block in body.blocks() {
+        if block.ident.as_str() == "dynamic" {
+            let iterator =
+                block
+                    .body
+                    .get_attribute("iterator")
+                    .and_then(|attr| match &attr.value {
+                        Expression::Variable(var) => Some(var.as_str().to_string()),
+                        _ => None,
+                    });
+            if let Some(name) =
+                iterator.or_else(|| block.labels.first().map(|label| label.as_str().to_string()))
+            {
+                bound.insert(name);
+            }
+        }
+        collect_bound_names(&block.body, bound);
+    }
+}
+
+struct FactCollector {
+    facts: BlockFacts,
+    /// Names bound by `dynamic` iterators and `for` expressions
+    bound: HashSet<String>,
+    depth: usize,
+    /// Whether the body being visited is a `dynamic` block's own body, whose
+    /// `for_each` belongs to the `dynamic` decision already counted
+    in_dynamic: bool,
+}
+
+impl FactCollector {
+    fn site(offset: Option<std::ops::Range<usize>>, kind: impl Into<String>) -> Site {
+        Site {
+            offset: offset.map_or(0, |range| range.start),
+            kind: kind.into(),
+        }
+    }
+}
+
+impl Visit for FactCollector {
+    fn visit_attr(&mut self, attr: &Attribute) {
+        let key = attr.key.as_str();
+        if matches!(key, "count" | "for_each") && !self.in_dynamic {
+            self.facts.decisions.push(Self::site(attr.span(), key));
+        }
+        visit::visit_attr(self, attr);
+    }
+
+    fn visit_block(&mut self, block: &Block) {
+        let ident = block.ident.as_str();
+        let is_dynamic = ident == "dynamic";
+        if is_dynamic {
+            self.facts.decisions.push(Self::site(block.span(), ident));
+        }
+        self.facts.blocks.push(Self::site(block.span(), ident));
+
+        self.depth += 1;
+        self.facts.max_depth = self.facts.max_depth.max(self.depth);
+        let in_dynamic = std::mem::replace(&mut self.in_dynamic, is_dynamic);
+        visit::visit_block(self, block);
+        self.in_dynamic = in_dynamic;
+        self.depth -= 1;
+    }
+
+    fn visit_conditional(&mut self, conditional: &Conditional) {
+        self.facts
+            .decisions
+            .push(Self::site(conditional.span(), "conditional"));
+        visit::visit_conditional(self, conditional);
+    }
+
+    fn visit_for_expr(&mut self, for_expr: &ForExpr) {
+        let intro = &for_expr.intro;
+        self.bound.insert(intro.value_var.as_str().to_string());
+        if let Some(key_var) = &intro.key_var {
+            self.bound.insert(key_var.as_str().to_string());
+        }
+        visit::visit_for_expr(self, for_expr);
+    }
+
+    fn visit_traversal(&mut self, traversal: &Traversal) {
+        if let Some(address) = reference_address(traversal, &self.bound) {
+            self.facts
+                .references
+                .push(Self::site(traversal.span(), address));
+        }
+        visit::visit_traversal(self, traversal);
+    }
+}
+
+/// The address a traversal refers to: `module.NAME`, `data.TYPE.NAME`, or
+/// `TYPE.NAME` for a managed resource, or `None` for anything else
+fn reference_address(traversal: &Traversal, bound: &HashSet<String>) -> Option<String> {
+    let Expression::Variable(root) = &traversal.expr else {
+        return None;
+    };
+    let root = root.as_str();
+    let attrs: Vec<&str> = traversal
+        .operators
+        .iter()
+        .map_while(|op| match op.value() {
+            TraversalOperator::GetAttr(ident) => Some(ident.as_str()),
+            _ => None,
+        })
+        .collect();
+
+    match root {
+        "module" => attrs.first().map(|name| format!("module.{}", name)),
+        "data" => match attrs.as_slice() {
+            [kind, name, ..] => Some(format!("data.{}.{}", kind, name)),
+            _ => None,
+        },
+        // Resource types are always `<provider>_<type>`, which also rules
+        // out `var`, `local`, `each`, `count`, `path`, and `self`
+        _ if root.contains('_') && !bound.contains(root) => {
+            attrs.first().map(|name| format!("{}.{}", root, name))
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn facts(source: &str) -> BlockFacts {
+        block_facts(&parse_block(source).expect("block should parse"))
+    }
+
+    fn kinds(sites: &[Site]) -> Vec<&str> {
+        sites.iter().map(|s| s.kind.as_str()).collect()
+    }
+
+    #[test]
+    fn test_block_facts_count_decisions_nesting_and_references() {
+        let source = r#"resource "aws_security_group" "web" {
+  count  = var.enabled ? 1 : 0
+  vpc_id = module.vpc.vpc_id
+  name   = "${aws_instance.web.id}-sg"
+
+  dynamic "ingress" {
+    for_each = var.ports
+    content {
+      from_port = ingress.value
+      cidr      = data.aws_vpc.main.cidr_block
+    }
+  }
+
+  tags = { for k, v in var.tags : k => v if v != "" }
+}
+"#;
+        let facts = facts(source);
+        assert_eq!(
+            kinds(&facts.decisions),
+            vec!["count", "conditional", "dynamic"]
+        );
+        assert_eq!(kinds(&facts.blocks), vec!["dynamic", "content"]);
+        assert_eq!(facts.max_depth, 2);
+        assert_eq!(
+            facts.callee_names(),
+            vec!["aws_instance.web", "data.aws_vpc.main", "module.vpc"]
+        );
+    }
+
+    #[test]
+    fn test_dynamic_iterator_is_not_a_reference() {
+        let source = r#"resource "aws_lb" "main" {
+  dynamic "access_logs" {
+    for_each = var.logs
+    iterator = log_cfg
+    content {
+      bucket = log_cfg.value.bucket
+    }
+  }
+}
+"#;
+        let facts = facts(source);
+        assert!(facts.references.is_empty());
+        assert_eq!(kinds(&facts.decisions), vec!["dynamic"]);
+    }
+}
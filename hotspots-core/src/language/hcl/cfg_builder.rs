@@ -0,0 +1,81 @@
+//! Terraform/HCL CFG builder implementation
+//!
+//! Configuration has no statements to sequence, so the graph is a chain of
+//! decisions in source order: each `count`/`for_each`, `dynamic` block, and
+//! conditional is a condition node whose taken branch rejoins the chain.
+
+use crate::ast::FunctionNode;
+use crate::cfg::{Cfg, NodeKind};
+use crate::language::cfg_builder::CfgBuilder;
+use crate::language::hcl::block::{block_facts, parse_block};
+use crate::language::hcl::parser::line_at;
+
+/// CFG builder for Terraform/HCL blocks
+pub struct HclCfgBuilder;
+
+impl CfgBuilder for HclCfgBuilder {
+    fn build(&self, function: &FunctionNode) -> Cfg {
+        let source = function.body.as_hcl();
+        let mut cfg = Cfg::new();
+        let Some(block) = parse_block(source) else {
+            // On error, return a minimal CFG (entry -> exit)
+            return cfg;
+        };
+
+        // Block lines are relative to the block source; shift them to file lines
+        let offset = function.span.start_line.saturating_sub(1);
+        let mut current = cfg.entry;
+        for decision in block_facts(&block).decisions {
+            let condition = cfg.add_node_at(
+                NodeKind::Condition,
+                line_at(source, decision.offset) + offset,
+            );
+            cfg.add_edge(current, condition);
+
+            let branch = cfg.add_node(NodeKind::Statement);
+            cfg.add_edge(condition, branch);
+
+            let join = cfg.add_node(NodeKind::Join);
+            cfg.add_edge(branch, join);
+            cfg.add_edge(condition, join);
+            current = join;
+        }
+        cfg.add_edge(current, cfg.exit);
+        cfg
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::language::{HclParser, LanguageParser};
+
+    fn build(source: &str) -> Cfg {
+        let module = HclParser.parse(source, "main.tf").unwrap();
+        let function = module.discover_functions(0, source).remove(0);
+        HclCfgBuilder.build(&function)
+    }
+
+    #[test]
+    fn test_decisions_become_condition_nodes_on_file_lines() {
+        let cfg = build(
+            "\nresource \"aws_eip\" \"nat\" {\n  count = var.ha ? 2 : 1\n  vpc   = true\n}\n",
+        );
+        assert!(cfg.validate().is_ok());
+        let lines: Vec<u32> = cfg
+            .nodes
+            .iter()
+            .filter(|n| matches!(n.kind, NodeKind::Condition))
+            .filter_map(|n| n.line)
+            .collect();
+        assert_eq!(lines, vec![3, 3]);
+    }
+
+    #[test]
+    fn test_block_without_decisions_is_straight_line() {
+        let cfg = build("locals {\n  name = \"web\"\n}\n");
+        assert!(cfg.validate().is_ok());
+        assert_eq!(cfg.node_count(), 2);
+        assert_eq!(cfg.edge_count(), 1);
+    }
+}
@@ -0,0 +1,11 @@
+//! Terraform/HCL language support
+//!
+//! This module provides parsing and CFG building for Terraform/HCL
+//! configuration using the `hcl-edit` crate.
+
+pub(crate) mod block;
+pub mod cfg_builder;
+pub mod parser;
+
+pub use cfg_builder::HclCfgBuilder;
+pub use parser::HclParser;
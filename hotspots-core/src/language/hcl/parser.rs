@@ -0,0 +1,160 @@
+//! Terraform/HCL parser implementation using hcl-edit
+//!
+//! The "functions" of a configuration are its top-level `resource`, `module`,
+//! and `locals` blocks, named by their Terraform address: `aws_instance.web`,
+//! `module.vpc`, and `locals`.
+
+use crate::ast::{FunctionId, FunctionKind, FunctionNode};
+use crate::language::function_body::FunctionBody;
+use crate::language::parser::{LanguageParser, ParsedModule};
+use crate::language::span::SourceSpan;
+use anyhow::Result;
+use hcl_edit::structure::{Block, Body};
+use hcl_edit::Span;
+
+/// Terraform/HCL parser using hcl-edit
+pub struct HclParser;
+
+impl LanguageParser for HclParser {
+    fn parse(&self, source: &str, filename: &str) -> Result<Box<dyn ParsedModule>> {
+        let body = hcl_edit::parser::parse_body(source)
+            .map_err(|e| anyhow::anyhow!("Failed to parse HCL file: {}: {}", filename, e))?;
+
+        Ok(Box::new(HclModule {
+            body,
+            source: source.to_string(),
+        }))
+    }
+}
+
+/// Parsed HCL file
+struct HclModule {
+    body: Body,
+    source: String,
+}
+
+impl ParsedModule for HclModule {
+    fn discover_functions(&self, file_index: usize, _source: &str) -> Vec<FunctionNode> {
+        let mut functions: Vec<FunctionNode> = self
+            .body
+            .blocks()
+            .filter_map(|block| {
+                let name = block_address(block)?;
+                let range = block.span()?;
+                Some((name, range))
+            })
+            .enumerate()
+            .map(|(local_index, (name, range))| {
+                // The block's range may run on past its closing brace
+                let end = range.start + self.source[range.clone()].trim_end().len();
+                let span = SourceSpan::new(
+                    range.start,
+                    range.end,
+                    line_at(&self.source, range.start),
+                    line_at(&self.source, range.end.saturating_sub(1)),
+                    column_at(&self.source, range.start),
+                    column_at(&self.source, end),
+                );
+                FunctionNode {
+                    id: FunctionId {
+                        file_index,
+                        local_index,
+                    },
+                    name: Some(name),
+                    kind: FunctionKind::Function,
+                    span,
+                    body: FunctionBody::Hcl {
+                        source: self.source[range].to_string(),
+                    },
+                    suppression_reason: None,
+                }
+            })
+            .collect();
+
+        functions.sort_by_key(|f| f.span.start);
+        functions
+    }
+}
+
+/// Terraform address of a block analyzed as a function, or `None` for blocks
+/// that only declare (`variable`, `output`, `provider`, `terraform`, `data`)
+fn block_address(block: &Block) -> Option<String> {
+    let labels: Vec<&str> = block.labels.iter().map(|label| label.as_str()).collect();
+    match (block.ident.as_str(), labels.as_slice()) {
+        ("resource", [kind, name]) => Some(format!("{}.{}", kind, name)),
+        ("module", [name]) => Some(format!("module.{}", name)),
+        ("locals", []) => Some("locals".to_string()),
+        _ => None,
+    }
+}
+
+/// 1-indexed line of a byte offset
+pub(crate) fn line_at(source: &str, offset: usize) -> u32 {
+    let offset = offset.min(source.len());
+    source.as_bytes()[..offset]
+        .iter()
+        .filter(|&&b| b == b'\n')
+        .count() as u32
+        + 1
+}
+
+/// 0-indexed column of a byte offset
+fn column_at(source: &str, offset: usize) -> u32 {
+    let line_start = source[..offset].rfind('\n').map_or(0, |i| i + 1);
+    (offset - line_start) as u32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_discovers_resource_module_and_locals_blocks() {
+        let source = r#"variable "region" {
+  type = string
+}
+
+resource "aws_instance" "web" {
+  ami = "ami-123"
+}
+
+module "vpc" {
+  source = "./modules/vpc"
+}
+
+locals {
+  name = "web"
+}
+
+data "aws_ami" "ubuntu" {
+  most_recent = true
+}
+"#;
+        let module = HclParser.parse(source, "main.tf").unwrap();
+        let functions = module.discover_functions(0, source);
+        let found: Vec<(&str, u32, u32)> = functions
+            .iter()
+            .map(|f| {
+                (
+                    f.name.as_deref().unwrap(),
+                    f.span.start_line,
+                    f.span.end_line,
+                )
+            })
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                ("aws_instance.web", 5, 7),
+                ("module.vpc", 9, 11),
+                ("locals", 13, 15),
+            ]
+        );
+        assert!(functions[1].body.as_hcl().starts_with("module \"vpc\" {"));
+    }
+
+    #[test]
+    fn test_invalid_hcl_is_an_error() {
+        assert!(HclParser.parse("resource \"a\" {", "bad.tf").is_err());
+    }
+}
@@ -3,8 +3,10 @@
 use crate::ast::FunctionNode;
 use crate::cfg::{Cfg, NodeId, NodeKind};
 use crate::language::cfg_builder::CfgBuilder;
+use crate::language::java::{BODY_KINDS, FUNCTION_KINDS};
 use crate::language::tree_sitter_utils::{
-    find_child_by_kind, find_function_by_start, with_cached_java_tree,
+    find_child_by_kind, find_child_by_kinds, find_function_by_start, node_line, node_loc,
+    with_cached_java_tree,
 };
 use tree_sitter::Node;
 
@@ -18,13 +20,8 @@ impl CfgBuilder for JavaCfgBuilder {
         let (_body_node_id, source) = function.body.as_java();
 
         let result = with_cached_java_tree(source, |root| {
-            let func_node = find_function_by_start(
-                root,
-                function.span.start,
-                &["method_declaration", "constructor_declaration"],
-            )?;
-            let body_node = find_child_by_kind(func_node, "block")
-                .or_else(|| find_child_by_kind(func_node, "constructor_body"))?;
+            let func_node = find_function_by_start(root, function.span.start, FUNCTION_KINDS)?;
+            let body_node = find_child_by_kinds(func_node, BODY_KINDS)?;
             let mut builder = JavaCfgBuilderState::new();
             builder.build_from_block(&body_node, source);
             Some(builder.cfg)
@@ -92,6 +89,13 @@ impl JavaCfgBuilderState {
 
     /// Visit a tree-sitter node and build CFG
     fn visit_node(&mut self, node: &Node, source: &str) {
+        if self.current_node.is_none() {
+            // Dead code after return/throw/break/continue — count it, build nothing
+            if !matches!(node.kind(), "line_comment" | "block_comment") {
+                self.cfg.record_unreachable();
+            }
+            return;
+        }
         match node.kind() {
             "if_statement" => self.visit_if(node, source),
             "while_statement" => self.visit_while(node, source),
@@ -121,7 +125,7 @@ impl JavaCfgBuilderState {
         };
 
         // Create condition node
-        let condition = self.cfg.add_node(NodeKind::Condition);
+        let condition = self.cfg.add_node_at(NodeKind::Condition, node_line(node));
         self.cfg.add_edge(current, condition);
 
         // Get the consequence block
@@ -184,7 +188,7 @@ impl JavaCfgBuilderState {
         };
 
         // Create condition node
-        let condition = self.cfg.add_node(NodeKind::Condition);
+        let condition = self.cfg.add_node_at(NodeKind::Condition, node_line(node));
         self.cfg.add_edge(current, condition);
 
         // After loop join point
@@ -227,7 +231,7 @@ impl JavaCfgBuilderState {
         self.cfg.add_edge(current, body_entry);
 
         // Create condition node
-        let condition = self.cfg.add_node(NodeKind::Condition);
+        let condition = self.cfg.add_node_at(NodeKind::Condition, node_line(node));
 
         // After loop join point
         let after_loop = self.cfg.add_node(NodeKind::Statement);
@@ -270,7 +274,7 @@ impl JavaCfgBuilderState {
         };
 
         // Create condition node (header)
-        let condition = self.cfg.add_node(NodeKind::Condition);
+        let condition = self.cfg.add_node_at(NodeKind::Condition, node_line(node));
         self.cfg.add_edge(current, condition);
 
         // After loop join point
@@ -309,7 +313,7 @@ impl JavaCfgBuilderState {
         };
 
         // Create switch node (decision point)
-        let switch_node = self.cfg.add_node(NodeKind::Condition);
+        let switch_node = self.cfg.add_node_at(NodeKind::Condition, node_line(node));
         self.cfg.add_edge(current, switch_node);
 
         // Join point after switch
@@ -327,6 +331,9 @@ impl JavaCfgBuilderState {
             let mut has_default = false;
 
             for child in switch_body.children(&mut cursor) {
+                if matches!(child.kind(), "switch_block_statement_group" | "switch_rule") {
+                    self.cfg.record_arm_loc(node_loc(&child));
+                }
                 if child.kind() == "switch_label" {
                     // Create a node for this case
                     let case_node = self.cfg.add_node(NodeKind::Statement);
@@ -398,7 +405,7 @@ impl JavaCfgBuilderState {
         for child in node.children(&mut cursor) {
             if child.kind() == "catch_clause" {
                 // Each catch is a separate branch from try entry
-                let catch_node = self.cfg.add_node(NodeKind::Condition);
+                let catch_node = self.cfg.add_node_at(NodeKind::Condition, node_line(&child));
                 self.cfg.add_edge(try_entry, catch_node);
 
                 if let Some(catch_body) = find_child_by_kind(child, "block") {
@@ -444,7 +451,7 @@ impl JavaCfgBuilderState {
         };
 
         // Create synchronized node (decision point - acquiring lock)
-        let sync_node = self.cfg.add_node(NodeKind::Condition);
+        let sync_node = self.cfg.add_node_at(NodeKind::Condition, node_line(node));
         self.cfg.add_edge(current, sync_node);
 
         // Process body
@@ -542,7 +549,8 @@ mod tests {
                 local_index: 0,
             },
             name: Some("test".to_string()),
-            span: SourceSpan::new(start_byte, end_byte, 1, 1, 0),
+            kind: crate::ast::FunctionKind::Function,
+            span: SourceSpan::new(start_byte, end_byte, 1, 1, 0, 0),
             body: FunctionBody::Java {
                 body_node: 0,
                 source: source.to_string(),
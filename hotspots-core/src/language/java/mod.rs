@@ -5,3 +5,30 @@ pub mod parser;
 
 pub use cfg_builder::JavaCfgBuilder;
 pub use parser::JavaParser;
+
+/// Method and constructor declarations
+pub(crate) const FUNCTION_KINDS: &[&str] = &["method_declaration", "constructor_declaration"];
+
+/// Body kinds, in lookup order: methods use `block`, constructors `constructor_body`
+pub(crate) const BODY_KINDS: &[&str] = &["block", "constructor_body"];
+
+/// Statements that open a nesting level for ND
+pub(crate) const NESTING_KINDS: &[&str] = &[
+    "if_statement",
+    "while_statement",
+    "do_statement",
+    "for_statement",
+    "enhanced_for_statement",
+    "switch_statement",
+    "switch_expression",
+    "try_statement",
+    "synchronized_statement",
+];
+
+/// Statements that leave the normal flow early, counted for NS
+pub(crate) const EXIT_KINDS: &[&str] = &[
+    "return_statement",
+    "throw_statement",
+    "break_statement",
+    "continue_statement",
+];
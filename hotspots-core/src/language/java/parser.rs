@@ -1,8 +1,9 @@
 //! Java language parser using tree-sitter
 
 use crate::ast::FunctionNode;
+use crate::language::java::{BODY_KINDS, FUNCTION_KINDS};
 use crate::language::parser::{LanguageParser, ParsedModule};
-use crate::language::tree_sitter_utils::find_child_by_kind;
+use crate::language::tree_sitter_utils::{find_child_by_kind, find_child_by_kinds};
 use anyhow::{Context, Result};
 use tree_sitter::{Node, Parser, Tree};
 
@@ -81,7 +82,7 @@ fn discover_functions_recursive(
     // Java has:
     // - "method_declaration" for regular and static methods
     // - "constructor_declaration" for constructors
-    if node.kind() == "method_declaration" || node.kind() == "constructor_declaration" {
+    if FUNCTION_KINDS.contains(&node.kind()) {
         if let Some(function_node) = extract_function(node, source, file_index, functions.len()) {
             functions.push(function_node);
         }
@@ -101,16 +102,20 @@ fn extract_function(
     file_index: usize,
     local_index: usize,
 ) -> Option<FunctionNode> {
-    use crate::ast::FunctionId;
+    use crate::ast::{FunctionId, FunctionKind};
     use crate::language::{FunctionBody, SourceSpan};
 
     // Get function/constructor name
     let name = extract_function_name(node, source);
+    let kind = if node.kind() == "constructor_declaration" {
+        FunctionKind::Constructor
+    } else {
+        FunctionKind::Method
+    };
 
     // Get function body (block node or constructor_body)
     // Constructors use "constructor_body", methods use "block"
-    let body_node = find_child_by_kind(node, "block")
-        .or_else(|| find_child_by_kind(node, "constructor_body"))?;
+    let body_node = find_child_by_kinds(node, BODY_KINDS)?;
 
     // Create SourceSpan from tree-sitter node
     let span = SourceSpan::new(
@@ -119,6 +124,7 @@ fn extract_function(
         node.start_position().row as u32 + 1, // tree-sitter uses 0-indexed rows
         node.end_position().row as u32 + 1,   // tree-sitter uses 0-indexed rows
         node.start_position().column as u32,
+        node.end_position().column as u32,
     );
 
     // Create FunctionBody::Java variant
@@ -133,6 +139,7 @@ fn extract_function(
             local_index,
         },
         name,
+        kind,
         span,
         body,
         suppression_reason: None, // Will be extracted separately
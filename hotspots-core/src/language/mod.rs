@@ -9,6 +9,7 @@ pub mod csharp;
 pub mod ecmascript;
 pub mod function_body;
 pub mod go;
+pub mod hcl;
 pub mod java;
 pub mod parser;
 pub mod python;
@@ -18,14 +19,16 @@ pub mod tree_sitter_utils;
 
 use std::path::Path;
 
+use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
 
 pub use c::{CCfgBuilder, CParser};
 pub use cfg_builder::{get_builder_for_function, CfgBuilder};
 pub use csharp::{CSharpCfgBuilder, CSharpParser};
-pub use ecmascript::{ECMAScriptCfgBuilder, ECMAScriptParser, VueParser};
+pub use ecmascript::{ECMAScriptCfgBuilder, ECMAScriptParser, SvelteParser, VueParser};
 pub use function_body::FunctionBody;
 pub use go::{GoCfgBuilder, GoParser};
+pub use hcl::{HclCfgBuilder, HclParser};
 pub use java::{JavaCfgBuilder, JavaParser};
 pub use parser::{LanguageParser, ParsedModule};
 pub use python::{PythonCfgBuilder, PythonParser};
@@ -53,12 +56,16 @@ pub enum Language {
     Rust,
     /// Vue Single File Component (.vue)
     Vue,
+    /// Svelte component (.svelte)
+    Svelte,
     /// C# (.cs)
     CSharp,
     /// C (.c)
     C,
     /// C header (.h)
     CHeader,
+    /// Terraform/HCL (.tf, .hcl)
+    Hcl,
 }
 
 impl Language {
@@ -90,15 +97,21 @@ impl Language {
             "java" => Some(Language::Java),
             // Python
             "py" | "pyw" => Some(Language::Python),
+            // Jupyter notebook, analyzed through its code cells
+            "ipynb" => Some(Language::Python),
             // Rust
             "rs" => Some(Language::Rust),
             // Vue Single File Component
             "vue" => Some(Language::Vue),
+            // Svelte component
+            "svelte" => Some(Language::Svelte),
             // C#
             "cs" => Some(Language::CSharp),
             // C
             "c" => Some(Language::C),
             "h" => Some(Language::CHeader),
+            // Terraform/HCL
+            "tf" | "hcl" => Some(Language::Hcl),
             // Unknown
             _ => None,
         }
@@ -156,9 +169,11 @@ impl Language {
             Language::Python => "Python",
             Language::Rust => "Rust",
             Language::Vue => "Vue",
+            Language::Svelte => "Svelte",
             Language::CSharp => "C#",
             Language::C => "C",
             Language::CHeader => "C Header",
+            Language::Hcl => "HCL",
         }
     }
 
@@ -188,15 +203,39 @@ impl Language {
             Language::JavaScriptReact => &["jsx", "mjsx", "cjsx"],
             Language::Go => &["go"],
             Language::Java => &["java"],
-            Language::Python => &["py", "pyw"],
+            Language::Python => &["py", "pyw", "ipynb"],
             Language::Rust => &["rs"],
             Language::Vue => &["vue"],
+            Language::Svelte => &["svelte"],
             Language::CSharp => &["cs"],
             Language::C => &["c"],
             Language::CHeader => &["h"],
+            Language::Hcl => &["tf", "hcl"],
         }
     }
 
+    /// Languages selected by a `--include-lang`/`--exclude-lang` name
+    ///
+    /// Names are case-insensitive and cover a family: `typescript` includes
+    /// TSX, `javascript` includes JSX, and `c` includes headers.
+    pub fn from_filter_name(s: &str) -> Option<&'static [Language]> {
+        let languages: &'static [Language] = match s.to_ascii_lowercase().as_str() {
+            "typescript" | "ts" => &[Language::TypeScript, Language::TypeScriptReact],
+            "javascript" | "js" => &[Language::JavaScript, Language::JavaScriptReact],
+            "go" => &[Language::Go],
+            "java" => &[Language::Java],
+            "python" | "py" => &[Language::Python],
+            "rust" | "rs" => &[Language::Rust],
+            "vue" => &[Language::Vue],
+            "svelte" => &[Language::Svelte],
+            "csharp" | "c#" | "cs" => &[Language::CSharp],
+            "c" => &[Language::C, Language::CHeader],
+            "hcl" | "terraform" | "tf" => &[Language::Hcl],
+            _ => return None,
+        };
+        Some(languages)
+    }
+
     /// Parse from canonical name string (as returned by `name()`).
     pub fn from_name(s: &str) -> Option<Self> {
         match s {
@@ -209,14 +248,36 @@ impl Language {
             "Python" => Some(Language::Python),
             "Rust" => Some(Language::Rust),
             "Vue" => Some(Language::Vue),
+            "Svelte" => Some(Language::Svelte),
             "C#" => Some(Language::CSharp),
             "C" => Some(Language::C),
             "C Header" => Some(Language::CHeader),
+            "HCL" => Some(Language::Hcl),
             _ => None,
         }
     }
 }
 
+/// Read a source file as its parser sees it
+///
+/// The file's contents, except for Jupyter notebooks, which become the
+/// Python source of their code cells (see [`python::notebook`]).
+pub fn read_source(path: &Path) -> Result<String> {
+    let src = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read file: {}", path.display()))?;
+    source_text(path, src)
+}
+
+/// The contents of `path`, already read from disk or elsewhere (an archive
+/// member), as its parser sees them; see [`read_source`]
+pub fn source_text(path: &Path, contents: String) -> Result<String> {
+    if python::notebook::is_notebook(path) {
+        return python::notebook::notebook_source(&contents)
+            .with_context(|| format!("Failed to read notebook: {}", path.display()));
+    }
+    Ok(contents)
+}
+
 impl Serialize for Language {
     fn serialize<S: serde::Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
         s.serialize_str(self.name())
@@ -272,6 +333,12 @@ mod tests {
         assert_eq!(Language::from_extension("java"), Some(Language::Java));
     }
 
+    #[test]
+    fn test_from_extension_python() {
+        assert_eq!(Language::from_extension("py"), Some(Language::Python));
+        assert_eq!(Language::from_extension("ipynb"), Some(Language::Python));
+    }
+
     #[test]
     fn test_from_extension_unknown() {
         assert_eq!(Language::from_extension("cpp"), None);
@@ -284,6 +351,26 @@ mod tests {
         assert_eq!(Language::from_extension("h"), Some(Language::CHeader));
     }
 
+    #[test]
+    fn test_from_extension_hcl() {
+        assert_eq!(Language::from_extension("tf"), Some(Language::Hcl));
+        assert_eq!(Language::from_extension("hcl"), Some(Language::Hcl));
+        assert_eq!(Language::from_name("HCL"), Some(Language::Hcl));
+    }
+
+    #[test]
+    fn test_from_filter_name_covers_language_families() {
+        assert_eq!(
+            Language::from_filter_name("TypeScript"),
+            Some(&[Language::TypeScript, Language::TypeScriptReact][..])
+        );
+        assert_eq!(
+            Language::from_filter_name("c"),
+            Some(&[Language::C, Language::CHeader][..])
+        );
+        assert_eq!(Language::from_filter_name("cobol"), None);
+    }
+
     #[test]
     fn test_from_path() {
         assert_eq!(
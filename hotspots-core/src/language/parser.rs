@@ -39,6 +39,16 @@ pub trait ParsedModule {
     ///
     /// Vector of function nodes sorted by source position
     fn discover_functions(&self, file_index: usize, source: &str) -> Vec<FunctionNode>;
+
+    /// The file's top-level code (statements outside any function or class) as
+    /// a synthetic function named [`crate::ast::TOP_LEVEL_NAME`], for
+    /// file-granularity analysis
+    ///
+    /// Returns `None` when there is no top-level code. Languages whose top
+    /// level only holds declarations keep this default.
+    fn top_level_unit(&self, _file_index: usize, _source: &str) -> Option<FunctionNode> {
+        None
+    }
 }
 
 #[cfg(test)]
@@ -62,7 +72,15 @@ mod tests {
                         local_index: i,
                     },
                     name: Some(format!("test_fn_{}", i)),
-                    span: SourceSpan::new(i * 10, (i + 1) * 10, (i + 1) as u32, (i + 1) as u32, 0),
+                    kind: crate::ast::FunctionKind::Function,
+                    span: SourceSpan::new(
+                        i * 10,
+                        (i + 1) * 10,
+                        (i + 1) as u32,
+                        (i + 1) as u32,
+                        0,
+                        0,
+                    ),
                     body: FunctionBody::ecmascript(swc_ecma_ast::BlockStmt {
                         span: swc_common::DUMMY_SP,
                         ctxt: Default::default(),
@@ -3,8 +3,11 @@
 use crate::ast::FunctionNode;
 use crate::cfg::{Cfg, NodeId, NodeKind};
 use crate::language::cfg_builder::CfgBuilder;
+use crate::language::python::parser::PYTHON_NON_CODE_KINDS;
+use crate::language::python::{BODY_KINDS, FUNCTION_KINDS};
 use crate::language::tree_sitter_utils::{
-    find_child_by_kind, find_function_by_start, with_cached_python_tree,
+    find_child_by_kind, find_child_by_kinds, find_function_by_start, node_line, node_loc,
+    with_cached_python_tree,
 };
 use tree_sitter::Node;
 
@@ -18,12 +21,13 @@ impl CfgBuilder for PythonCfgBuilder {
         let (_body_node_id, source) = function.body.as_python();
 
         let result = with_cached_python_tree(source, |root| {
-            let func_node = find_function_by_start(
-                root,
-                function.span.start,
-                &["function_definition", "async_function_definition"],
-            )?;
-            let body_node = find_child_by_kind(func_node, "block")?;
+            if function.is_top_level() {
+                let mut builder = PythonCfgBuilderState::new();
+                builder.build_from_top_level(&root, source);
+                return Some(builder.cfg);
+            }
+            let func_node = find_function_by_start(root, function.span.start, FUNCTION_KINDS)?;
+            let body_node = find_child_by_kinds(func_node, BODY_KINDS)?;
             let mut builder = PythonCfgBuilderState::new();
             builder.build_from_block(&body_node, source);
             Some(builder.cfg)
@@ -72,8 +76,23 @@ impl PythonCfgBuilderState {
                 self.visit_node(&child, source);
             }
         }
+        self.connect_to_exit();
+    }
 
-        // Connect last node to exit
+    /// Build CFG from a module's top-level statements, leaving out definitions
+    /// and imports (see [`PYTHON_NON_CODE_KINDS`])
+    fn build_from_top_level(&mut self, module: &Node, source: &str) {
+        let mut cursor = module.walk();
+        for child in module.named_children(&mut cursor) {
+            if !PYTHON_NON_CODE_KINDS.contains(&child.kind()) {
+                self.visit_node(&child, source);
+            }
+        }
+        self.connect_to_exit();
+    }
+
+    /// Connect the last node to exit, unless it already is or already does
+    fn connect_to_exit(&mut self) {
         if let Some(last_node) = self.current_node {
             if last_node != self.cfg.exit {
                 let has_exit_edge = self
@@ -126,7 +145,7 @@ impl PythonCfgBuilderState {
         let from_node = self.current_node.expect("Current node should exist");
 
         // Condition node
-        let condition_node = self.cfg.add_node(NodeKind::Condition);
+        let condition_node = self.cfg.add_node_at(NodeKind::Condition, node_line(node));
         self.cfg.add_edge(from_node, condition_node);
 
         // Then branch (consequence)
@@ -149,7 +168,7 @@ impl PythonCfgBuilderState {
         for child in node.children(&mut cursor) {
             if child.kind() == "elif_clause" {
                 // Create condition node for elif
-                let elif_condition = self.cfg.add_node(NodeKind::Condition);
+                let elif_condition = self.cfg.add_node_at(NodeKind::Condition, node_line(&child));
                 self.cfg.add_edge(last_condition, elif_condition);
 
                 // elif body
@@ -191,7 +210,7 @@ impl PythonCfgBuilderState {
         let from_node = self.current_node.expect("Current node should exist");
 
         // Loop header (condition)
-        let loop_header = self.cfg.add_node(NodeKind::LoopHeader);
+        let loop_header = self.cfg.add_node_at(NodeKind::LoopHeader, node_line(node));
         self.cfg.add_edge(from_node, loop_header);
 
         // Loop body
@@ -233,7 +252,7 @@ impl PythonCfgBuilderState {
         let from_node = self.current_node.expect("Current node should exist");
 
         // Loop header
-        let loop_header = self.cfg.add_node(NodeKind::LoopHeader);
+        let loop_header = self.cfg.add_node_at(NodeKind::LoopHeader, node_line(node));
         self.cfg.add_edge(from_node, loop_header);
 
         // Loop body
@@ -288,7 +307,7 @@ impl PythonCfgBuilderState {
     }
 
     fn process_except_clause(&mut self, child: Node, from_node: NodeId, source: &str) -> NodeId {
-        let except_condition = self.cfg.add_node(NodeKind::Condition);
+        let except_condition = self.cfg.add_node_at(NodeKind::Condition, node_line(&child));
         self.cfg.add_edge(from_node, except_condition);
         let except_start = self.cfg.add_node(NodeKind::Statement);
         self.cfg.add_edge(except_condition, except_start);
@@ -391,11 +410,20 @@ impl PythonCfgBuilderState {
         }
     }
 
-    fn visit_match(&mut self, _node: &Node, _source: &str) {
+    fn visit_match(&mut self, node: &Node, _source: &str) {
         // For now, simplify match statements - just treat as a single conditional
         // The CC contribution comes from metrics.rs counting case clauses
         // TODO: Model match statement CFG more precisely
 
+        if let Some(body) = node.child_by_field_name("body") {
+            let mut cursor = body.walk();
+            for clause in body.children(&mut cursor) {
+                if clause.kind() == "case_clause" {
+                    self.cfg.record_arm_loc(node_loc(&clause));
+                }
+            }
+        }
+
         let from_node = self.current_node.expect("Current node should exist");
 
         let stmt_node = self.cfg.add_node(NodeKind::Statement);
@@ -447,7 +475,7 @@ impl PythonCfgBuilderState {
         if has_control_flow_in_expression(node, source) {
             // Expression has decision points - add condition node
             if let Some(from_node) = self.current_node {
-                let condition_node = self.cfg.add_node(NodeKind::Condition);
+                let condition_node = self.cfg.add_node_at(NodeKind::Condition, node_line(node));
                 self.cfg.add_edge(from_node, condition_node);
                 self.current_node = Some(condition_node);
             }
@@ -520,7 +548,7 @@ mod tests {
         let mut cursor = root.walk();
         let func_node = root
             .children(&mut cursor)
-            .find(|n| n.kind() == "function_definition" || n.kind() == "async_function_definition")
+            .find(|n| FUNCTION_KINDS.contains(&n.kind()))
             .expect("No function found in test source");
 
         let start_byte = func_node.start_byte();
@@ -531,12 +559,14 @@ mod tests {
                 local_index: 0,
             },
             name: Some("test_func".to_string()),
+            kind: crate::ast::FunctionKind::Function,
             span: SourceSpan::new(
                 start_byte,
                 func_node.end_byte(),
                 func_node.start_position().row as u32 + 1,
                 func_node.end_position().row as u32 + 1,
                 func_node.start_position().column as u32,
+                func_node.end_position().column as u32,
             ),
             body: FunctionBody::Python {
                 body_node: 0,
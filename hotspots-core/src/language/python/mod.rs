@@ -1,10 +1,36 @@
 //! Python language support
 //!
 //! This module provides Python language parsing, function discovery, and CFG building
-//! using the tree-sitter-python parser.
+//! using the tree-sitter-python parser. Jupyter notebooks are analyzed through
+//! their code cells (see [`notebook`]).
 
 pub mod cfg_builder;
+pub mod notebook;
 pub mod parser;
 
 pub use cfg_builder::PythonCfgBuilder;
 pub use parser::PythonParser;
+
+/// `def` and `async def` definitions
+pub(crate) const FUNCTION_KINDS: &[&str] = &["function_definition", "async_function_definition"];
+
+/// The indented `block` under a definition
+pub(crate) const BODY_KINDS: &[&str] = &["block"];
+
+/// Compound statements that open a nesting level for ND, `with` and `match` included
+pub(crate) const NESTING_KINDS: &[&str] = &[
+    "if_statement",
+    "while_statement",
+    "for_statement",
+    "try_statement",
+    "with_statement",
+    "match_statement",
+];
+
+/// `return`, `raise`, `break`, and `continue`, counted for NS
+pub(crate) const EXIT_KINDS: &[&str] = &[
+    "return_statement",
+    "raise_statement",
+    "break_statement",
+    "continue_statement",
+];
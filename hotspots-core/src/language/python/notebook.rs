@@ -0,0 +1,113 @@
+//! Jupyter notebook (`.ipynb`) support
+//!
+//! A notebook is analyzed as the Python source formed by its code cells,
+//! concatenated in order. Markdown and raw cells, and all cell outputs, are
+//! ignored. Line numbers count the code cells' lines from the top of the
+//! notebook, so the first line of the second code cell follows the last line
+//! of the first.
+//!
+//! IPython magics (`%matplotlib inline`) and shell escapes (`!pip install`)
+//! aren't Python; those lines are commented out so they parse cleanly while
+//! keeping every later line number in place.
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::path::Path;
+
+/// File extension of Jupyter notebooks
+pub const NOTEBOOK_EXTENSION: &str = "ipynb";
+
+/// Whether `path` names a Jupyter notebook
+pub fn is_notebook(path: &Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| ext == NOTEBOOK_EXTENSION)
+}
+
+#[derive(Deserialize)]
+struct Notebook {
+    cells: Vec<Cell>,
+}
+
+#[derive(Deserialize)]
+struct Cell {
+    cell_type: String,
+    #[serde(default)]
+    source: CellSource,
+}
+
+/// nbformat stores cell source as either one string or a list of lines
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum CellSource {
+    Lines(Vec<String>),
+    Text(String),
+}
+
+impl Default for CellSource {
+    fn default() -> Self {
+        CellSource::Text(String::new())
+    }
+}
+
+impl CellSource {
+    fn text(&self) -> String {
+        match self {
+            CellSource::Lines(lines) => lines.concat(),
+            CellSource::Text(text) => text.clone(),
+        }
+    }
+}
+
+/// Assemble a notebook's code cells into one Python source string.
+///
+/// Each cell ends on its own line, and empty code cells contribute nothing.
+/// Fails if the JSON isn't an nbformat 4 notebook (a top-level `cells` array).
+pub fn notebook_source(json: &str) -> Result<String> {
+    let notebook: Notebook =
+        serde_json::from_str(json).context("not a Jupyter notebook (nbformat 4 expected)")?;
+
+    let mut source = String::new();
+    for cell in notebook.cells.iter().filter(|c| c.cell_type == "code") {
+        for line in cell.source.text().lines() {
+            let trimmed = line.trim_start();
+            if trimmed.starts_with('%') || trimmed.starts_with('!') {
+                source.push('#');
+            }
+            source.push_str(line);
+            source.push('\n');
+        }
+    }
+    Ok(source)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_notebook_source_joins_code_cells_in_order() {
+        let json = r##"{
+            "cells": [
+                {"cell_type": "markdown", "source": ["# Title\n", "prose"]},
+                {"cell_type": "code", "source": ["%matplotlib inline\n", "import os"],
+                 "outputs": [{"output_type": "stream", "text": ["ignored\n"]}]},
+                {"cell_type": "code", "source": []},
+                {"cell_type": "code", "source": "def f(x):\n    return x\n"}
+            ],
+            "metadata": {},
+            "nbformat": 4,
+            "nbformat_minor": 5
+        }"##;
+        assert_eq!(
+            notebook_source(json).unwrap(),
+            "#%matplotlib inline\nimport os\ndef f(x):\n    return x\n"
+        );
+    }
+
+    #[test]
+    fn test_notebook_source_rejects_non_notebook_json() {
+        assert!(notebook_source(r#"{"worksheets": []}"#).is_err());
+        assert!(notebook_source("not json").is_err());
+    }
+}
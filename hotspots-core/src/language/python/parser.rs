@@ -2,7 +2,8 @@
 
 use crate::ast::FunctionNode;
 use crate::language::parser::{LanguageParser, ParsedModule};
-use crate::language::tree_sitter_utils::find_child_by_kind;
+use crate::language::python::{BODY_KINDS, FUNCTION_KINDS};
+use crate::language::tree_sitter_utils::{find_child_by_kind, find_child_by_kinds};
 use anyhow::{Context, Result};
 use tree_sitter::{Node, Parser, Tree};
 
@@ -68,8 +69,49 @@ impl ParsedModule for PythonModule {
 
         functions
     }
+
+    fn top_level_unit(&self, file_index: usize, source: &str) -> Option<FunctionNode> {
+        use crate::ast::{FunctionId, FunctionKind, TOP_LEVEL_NAME};
+        use crate::language::{FunctionBody, SourceSpan};
+
+        let root = self.tree.root_node();
+        let mut cursor = root.walk();
+        let has_code = root
+            .named_children(&mut cursor)
+            .any(|child| !PYTHON_NON_CODE_KINDS.contains(&child.kind()));
+        if !has_code {
+            return None;
+        }
+        Some(FunctionNode {
+            id: FunctionId {
+                file_index,
+                // Sorts after every discovered function
+                local_index: usize::MAX,
+            },
+            name: Some(TOP_LEVEL_NAME.to_string()),
+            kind: FunctionKind::Function,
+            span: SourceSpan::whole_file(source),
+            body: FunctionBody::Python {
+                body_node: root.id(),
+                source: self.source.clone(),
+            },
+            suppression_reason: None,
+        })
+    }
 }
 
+/// Module-level statements that are not top-level code: definitions are
+/// analyzed as their own functions, and imports and comments carry no logic
+pub(crate) const PYTHON_NON_CODE_KINDS: &[&str] = &[
+    "function_definition",
+    "class_definition",
+    "decorated_definition",
+    "import_statement",
+    "import_from_statement",
+    "future_import_statement",
+    "comment",
+];
+
 /// Recursively discover function declarations in the Python AST
 fn discover_functions_recursive(
     node: Node,
@@ -78,8 +120,7 @@ fn discover_functions_recursive(
     functions: &mut Vec<FunctionNode>,
 ) {
     // Check if this node is a function declaration
-    // Python has "function_definition" for regular functions and "async_function_definition" for async functions
-    if node.kind() == "function_definition" || node.kind() == "async_function_definition" {
+    if FUNCTION_KINDS.contains(&node.kind()) {
         if let Some(function_node) = extract_function(node, source, file_index, functions.len()) {
             functions.push(function_node);
         }
@@ -104,9 +145,10 @@ fn extract_function(
 
     // Get function name
     let name = extract_function_name(node, source);
+    let kind = function_kind(node, name.as_deref());
 
     // Get function body (block node)
-    let body_node = find_child_by_kind(node, "block")?;
+    let body_node = find_child_by_kinds(node, BODY_KINDS)?;
 
     // Create SourceSpan from tree-sitter node
     let span = SourceSpan::new(
@@ -115,6 +157,7 @@ fn extract_function(
         node.start_position().row as u32 + 1, // tree-sitter uses 0-indexed rows
         node.end_position().row as u32 + 1,   // tree-sitter uses 0-indexed rows
         node.start_position().column as u32,
+        node.end_position().column as u32,
     );
 
     // Create FunctionBody::Python variant
@@ -129,12 +172,33 @@ fn extract_function(
             local_index,
         },
         name,
+        kind,
         span,
         body,
         suppression_reason: None, // Will be extracted separately
     })
 }
 
+/// Classify a function definition: defs directly in a class body (decorated
+/// or not) are methods, with `__init__` as the constructor
+fn function_kind(node: Node, name: Option<&str>) -> crate::ast::FunctionKind {
+    use crate::ast::FunctionKind;
+
+    let mut parent = node.parent();
+    if parent.is_some_and(|p| p.kind() == "decorated_definition") {
+        parent = parent.and_then(|p| p.parent());
+    }
+    let in_class = parent
+        .filter(|p| p.kind() == "block")
+        .and_then(|p| p.parent())
+        .is_some_and(|p| p.kind() == "class_definition");
+    match (in_class, name) {
+        (true, Some("__init__")) => FunctionKind::Constructor,
+        (true, _) => FunctionKind::Method,
+        (false, _) => FunctionKind::Function,
+    }
+}
+
 /// Extract function name from a function_definition or async_function_definition node
 fn extract_function_name(node: Node, source: &str) -> Option<String> {
     // Python function definitions have an "identifier" child for the function name
@@ -247,6 +311,38 @@ def third():
         assert_eq!(functions[2].name, Some("third".to_string()));
     }
 
+    #[test]
+    fn test_function_kinds() {
+        use crate::ast::FunctionKind;
+
+        let parser = PythonParser::new().unwrap();
+        let source = r#"
+def helper():
+    return 1
+
+class Widget:
+    def __init__(self):
+        self.x = 1
+
+    @property
+    def size(self):
+        return self.x
+"#;
+        let functions = parser
+            .parse(source, "test.py")
+            .unwrap()
+            .discover_functions(0, source);
+        let kinds: Vec<_> = functions.iter().map(|f| f.kind).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                FunctionKind::Function,
+                FunctionKind::Constructor,
+                FunctionKind::Method
+            ]
+        );
+    }
+
     #[test]
     fn test_parse_empty_file() {
         let parser = PythonParser::new().unwrap();
@@ -1,26 +1,135 @@
 //! Rust CFG builder implementation
+//!
+//! Macros are not expanded. Invocations of known branching macros
+//! ([`DEFAULT_BRANCHING_MACROS`], or a configured list) each become a decision
+//! point, so `assert!`/`ensure!`/`matches!` raise CC like the `if` they expand
+//! to; every other macro is an opaque statement. Macros nested in another
+//! macro's arguments are not seen, since those stay unparsed tokens.
 
 use crate::ast::FunctionNode;
 use crate::cfg::{Cfg, NodeId, NodeKind};
 use crate::language::cfg_builder::CfgBuilder;
 use anyhow::{Context, Result};
+use syn::spanned::Spanned;
+use syn::visit::Visit;
 use syn::{Block, Expr, ExprBlock, ExprForLoop, ExprIf, ExprLoop, ExprMatch, ExprWhile, Stmt};
 
+/// Macros counted as decision points unless configured otherwise: assertions
+/// and `ensure!` branch to a panic or early return, `matches!` is a pattern test
+pub const DEFAULT_BRANCHING_MACROS: &[&str] = &[
+    "assert",
+    "assert_eq",
+    "assert_ne",
+    "debug_assert",
+    "debug_assert_eq",
+    "debug_assert_ne",
+    "ensure",
+    "matches",
+];
+
 /// CFG builder for Rust functions
 pub struct RustCfgBuilder;
 
-impl CfgBuilder for RustCfgBuilder {
-    fn build(&self, function: &FunctionNode) -> Cfg {
+impl RustCfgBuilder {
+    /// Build the CFG, counting invocations of `branching_macros` (names
+    /// without `!`, matched against the last path segment) as decision points
+    /// in place of [`DEFAULT_BRANCHING_MACROS`]
+    pub fn build_with_macros(&self, function: &FunctionNode, branching_macros: &[String]) -> Cfg {
         let source = function.body.as_rust();
 
         // Parse the function source
         // On error, return a minimal CFG (entry -> exit)
-        build_cfg_from_source(source).unwrap_or_default()
+        let mut cfg = build_cfg_from_source(source, branching_macros).unwrap_or_default();
+
+        // syn lines are relative to the function source; shift them to file lines
+        let offset = function.span.start_line.saturating_sub(1);
+        for node in &mut cfg.nodes {
+            if let Some(line) = node.line.as_mut() {
+                *line += offset;
+            }
+        }
+        cfg
+    }
+}
+
+impl CfgBuilder for RustCfgBuilder {
+    fn build(&self, function: &FunctionNode) -> Cfg {
+        let macros: Vec<String> = DEFAULT_BRANCHING_MACROS
+            .iter()
+            .map(|name| name.to_string())
+            .collect();
+        self.build_with_macros(function, &macros)
+    }
+}
+
+/// Source line (within the parsed function source) a syntax node starts on
+fn line_of(node: &impl Spanned) -> u32 {
+    node.span().start().line as u32
+}
+
+/// Whether `mac` invokes one of `macros`
+fn is_branching_macro(mac: &syn::Macro, macros: &[String]) -> bool {
+    mac.path
+        .segments
+        .last()
+        .is_some_and(|segment| macros.iter().any(|name| segment.ident == name))
+}
+
+/// Collects the lines of branching macro invocations, skipping closures and
+/// nested items, which are measured on their own
+struct MacroDecisions<'m> {
+    macros: &'m [String],
+    lines: Vec<u32>,
+}
+
+impl<'ast> Visit<'ast> for MacroDecisions<'_> {
+    fn visit_macro(&mut self, mac: &'ast syn::Macro) {
+        if is_branching_macro(mac, self.macros) {
+            self.lines.push(line_of(mac));
+        }
+    }
+
+    fn visit_expr_closure(&mut self, _closure: &'ast syn::ExprClosure) {}
+
+    fn visit_item(&mut self, _item: &'ast syn::Item) {}
+}
+
+/// Lines of the branching macro invocations in `exprs`
+fn macro_decision_lines<'a>(
+    exprs: impl IntoIterator<Item = &'a Expr>,
+    macros: &[String],
+) -> Vec<u32> {
+    let mut collector = MacroDecisions {
+        macros,
+        lines: Vec::new(),
+    };
+    for expr in exprs {
+        collector.visit_expr(expr);
+    }
+    collector.lines
+}
+
+/// Chain a decision per line from `entry`: a condition whose taken branch
+/// rejoins the chain. Returns the last join (or `entry` when there are none).
+fn add_decisions(cfg: &mut Cfg, lines: Vec<u32>, entry: NodeId) -> NodeId {
+    let mut current = entry;
+    for line in lines {
+        let condition = cfg.add_node_at(NodeKind::Condition, line);
+        cfg.add_edge(current, condition);
+
+        let branch = cfg.add_node(NodeKind::Statement);
+        cfg.add_edge(condition, branch);
+
+        let join = cfg.add_node(NodeKind::Join);
+        cfg.add_edge(branch, join);
+        cfg.add_edge(condition, join);
+        current = join;
     }
+    current
 }
 
 /// Build CFG from Rust source
-fn build_cfg_from_source(source: &str) -> Result<Cfg> {
+fn build_cfg_from_source(source: &str, macros: &[String]) -> Result<Cfg> {
     // Parse the function source
     let item_fn: syn::ItemFn =
         syn::parse_str(source).context("Failed to parse Rust function for CFG building")?;
@@ -30,7 +139,7 @@ fn build_cfg_from_source(source: &str) -> Result<Cfg> {
     let exit = cfg.exit;
 
     // Build CFG from function block
-    let last_node = build_block_cfg(&mut cfg, &item_fn.block, entry, exit)?;
+    let last_node = build_block_cfg(&mut cfg, &item_fn.block, entry, exit, macros)?;
 
     // Connect last node to exit
     cfg.add_edge(last_node, exit);
@@ -39,22 +148,39 @@ fn build_cfg_from_source(source: &str) -> Result<Cfg> {
 }
 
 /// Build CFG for a block
-fn build_block_cfg(cfg: &mut Cfg, block: &Block, entry: NodeId, exit: NodeId) -> Result<NodeId> {
+fn build_block_cfg(
+    cfg: &mut Cfg,
+    block: &Block,
+    entry: NodeId,
+    exit: NodeId,
+    macros: &[String],
+) -> Result<NodeId> {
     let mut current = entry;
 
     for stmt in &block.stmts {
-        current = build_stmt_cfg(cfg, stmt, current, exit)?;
+        current = build_stmt_cfg(cfg, stmt, current, exit, macros)?;
     }
 
     Ok(current)
 }
 
 /// Build CFG for a statement
-fn build_stmt_cfg(cfg: &mut Cfg, stmt: &Stmt, entry: NodeId, exit: NodeId) -> Result<NodeId> {
+fn build_stmt_cfg(
+    cfg: &mut Cfg,
+    stmt: &Stmt,
+    entry: NodeId,
+    exit: NodeId,
+    macros: &[String],
+) -> Result<NodeId> {
     match stmt {
-        Stmt::Expr(expr, _) => build_expr_cfg(cfg, expr, entry, exit),
-        Stmt::Local(_) => {
+        Stmt::Expr(expr, _) => build_expr_cfg(cfg, expr, entry, exit, macros),
+        Stmt::Local(local) => {
             // Variable declaration
+            let exprs = local.init.iter().flat_map(|init| {
+                std::iter::once(init.expr.as_ref())
+                    .chain(init.diverge.iter().map(|(_, e)| e.as_ref()))
+            });
+            let entry = add_decisions(cfg, macro_decision_lines(exprs, macros), entry);
             let node = cfg.add_node(NodeKind::Statement);
             cfg.add_edge(entry, node);
             Ok(node)
@@ -65,8 +191,13 @@ fn build_stmt_cfg(cfg: &mut Cfg, stmt: &Stmt, entry: NodeId, exit: NodeId) -> Re
             cfg.add_edge(entry, node);
             Ok(node)
         }
-        Stmt::Macro(_) => {
-            // Macro invocation - treat as statement
+        Stmt::Macro(stmt_macro) => {
+            // Macro invocation - a decision if it branches, then a statement
+            let mut lines = Vec::new();
+            if is_branching_macro(&stmt_macro.mac, macros) {
+                lines.push(line_of(&stmt_macro.mac));
+            }
+            let entry = add_decisions(cfg, lines, entry);
             let node = cfg.add_node(NodeKind::Statement);
             cfg.add_edge(entry, node);
             Ok(node)
@@ -75,16 +206,23 @@ fn build_stmt_cfg(cfg: &mut Cfg, stmt: &Stmt, entry: NodeId, exit: NodeId) -> Re
 }
 
 /// Build CFG for an expression
-fn build_expr_cfg(cfg: &mut Cfg, expr: &Expr, entry: NodeId, exit: NodeId) -> Result<NodeId> {
+fn build_expr_cfg(
+    cfg: &mut Cfg,
+    expr: &Expr,
+    entry: NodeId,
+    exit: NodeId,
+    macros: &[String],
+) -> Result<NodeId> {
     match expr {
-        Expr::If(expr_if) => build_if_cfg(cfg, expr_if, entry, exit),
-        Expr::Match(expr_match) => build_match_cfg(cfg, expr_match, entry, exit),
-        Expr::Loop(expr_loop) => build_loop_cfg(cfg, expr_loop, entry, exit),
-        Expr::While(expr_while) => build_while_cfg(cfg, expr_while, entry, exit),
-        Expr::ForLoop(expr_for) => build_for_cfg(cfg, expr_for, entry, exit),
-        Expr::Block(expr_block) => build_expr_block_cfg(cfg, expr_block, entry, exit),
+        Expr::If(expr_if) => build_if_cfg(cfg, expr_if, entry, exit, macros),
+        Expr::Match(expr_match) => build_match_cfg(cfg, expr_match, entry, exit, macros),
+        Expr::Loop(expr_loop) => build_loop_cfg(cfg, expr_loop, entry, exit, macros),
+        Expr::While(expr_while) => build_while_cfg(cfg, expr_while, entry, exit, macros),
+        Expr::ForLoop(expr_for) => build_for_cfg(cfg, expr_for, entry, exit, macros),
+        Expr::Block(expr_block) => build_expr_block_cfg(cfg, expr_block, entry, exit, macros),
         Expr::Return(_) => {
             // Return statement - connects to exit
+            let entry = add_decisions(cfg, macro_decision_lines([expr], macros), entry);
             let node = cfg.add_node(NodeKind::Statement);
             cfg.add_edge(entry, node);
             cfg.add_edge(node, exit);
@@ -93,6 +231,7 @@ fn build_expr_cfg(cfg: &mut Cfg, expr: &Expr, entry: NodeId, exit: NodeId) -> Re
         Expr::Break(_) => {
             // Break statement
             // Note: In a full implementation, we'd route break to loop exit
+            let entry = add_decisions(cfg, macro_decision_lines([expr], macros), entry);
             let node = cfg.add_node(NodeKind::Statement);
             cfg.add_edge(entry, node);
             Ok(node)
@@ -106,6 +245,7 @@ fn build_expr_cfg(cfg: &mut Cfg, expr: &Expr, entry: NodeId, exit: NodeId) -> Re
         }
         _ => {
             // Other expressions (calls, literals, etc.)
+            let entry = add_decisions(cfg, macro_decision_lines([expr], macros), entry);
             let node = cfg.add_node(NodeKind::Statement);
             cfg.add_edge(entry, node);
             Ok(node)
@@ -114,14 +254,21 @@ fn build_expr_cfg(cfg: &mut Cfg, expr: &Expr, entry: NodeId, exit: NodeId) -> Re
 }
 
 /// Build CFG for if expression
-fn build_if_cfg(cfg: &mut Cfg, expr_if: &ExprIf, entry: NodeId, exit: NodeId) -> Result<NodeId> {
-    let condition = cfg.add_node(NodeKind::Condition);
+fn build_if_cfg(
+    cfg: &mut Cfg,
+    expr_if: &ExprIf,
+    entry: NodeId,
+    exit: NodeId,
+    macros: &[String],
+) -> Result<NodeId> {
+    let entry = add_decisions(cfg, macro_decision_lines([&*expr_if.cond], macros), entry);
+    let condition = cfg.add_node_at(NodeKind::Condition, line_of(expr_if));
     cfg.add_edge(entry, condition);
 
     // Then branch
     let then_entry = cfg.add_node(NodeKind::Statement);
     cfg.add_edge(condition, then_entry);
-    let then_exit = build_block_cfg(cfg, &expr_if.then_branch, then_entry, exit)?;
+    let then_exit = build_block_cfg(cfg, &expr_if.then_branch, then_entry, exit, macros)?;
 
     // Join node
     let join = cfg.add_node(NodeKind::Join);
@@ -131,7 +278,7 @@ fn build_if_cfg(cfg: &mut Cfg, expr_if: &ExprIf, entry: NodeId, exit: NodeId) ->
     if let Some((_, else_expr)) = &expr_if.else_branch {
         let else_entry = cfg.add_node(NodeKind::Statement);
         cfg.add_edge(condition, else_entry);
-        let else_exit = build_expr_cfg(cfg, else_expr, else_entry, exit)?;
+        let else_exit = build_expr_cfg(cfg, else_expr, else_entry, exit, macros)?;
         cfg.add_edge(else_exit, join);
     } else {
         // No else branch - condition can go directly to join
@@ -147,17 +294,27 @@ fn build_match_cfg(
     expr_match: &ExprMatch,
     entry: NodeId,
     exit: NodeId,
+    macros: &[String],
 ) -> Result<NodeId> {
-    let condition = cfg.add_node(NodeKind::Condition);
+    let entry = add_decisions(
+        cfg,
+        macro_decision_lines([&*expr_match.expr], macros),
+        entry,
+    );
+    let condition = cfg.add_node_at(NodeKind::Condition, line_of(expr_match));
     cfg.add_edge(entry, condition);
 
     let join = cfg.add_node(NodeKind::Join);
 
     // Each match arm is a separate path
     for arm in &expr_match.arms {
+        let span = arm.span();
+        cfg.record_arm_loc(span.end().line.saturating_sub(span.start().line) + 1);
         let arm_entry = cfg.add_node(NodeKind::Statement);
         cfg.add_edge(condition, arm_entry);
-        let arm_exit = build_expr_cfg(cfg, &arm.body, arm_entry, exit)?;
+        let guard = arm.guard.iter().map(|(_, guard)| guard.as_ref());
+        let arm_entry = add_decisions(cfg, macro_decision_lines(guard, macros), arm_entry);
+        let arm_exit = build_expr_cfg(cfg, &arm.body, arm_entry, exit, macros)?;
         cfg.add_edge(arm_exit, join);
     }
 
@@ -170,11 +327,12 @@ fn build_loop_cfg(
     expr_loop: &ExprLoop,
     entry: NodeId,
     _exit: NodeId,
+    macros: &[String],
 ) -> Result<NodeId> {
-    let header = cfg.add_node(NodeKind::LoopHeader);
+    let header = cfg.add_node_at(NodeKind::LoopHeader, line_of(expr_loop));
     cfg.add_edge(entry, header);
 
-    let body_exit = build_block_cfg(cfg, &expr_loop.body, header, header)?;
+    let body_exit = build_block_cfg(cfg, &expr_loop.body, header, header, macros)?;
 
     // Back edge to header
     cfg.add_edge(body_exit, header);
@@ -192,14 +350,20 @@ fn build_while_cfg(
     expr_while: &ExprWhile,
     entry: NodeId,
     _exit: NodeId,
+    macros: &[String],
 ) -> Result<NodeId> {
-    let condition = cfg.add_node(NodeKind::Condition);
+    let entry = add_decisions(
+        cfg,
+        macro_decision_lines([&*expr_while.cond], macros),
+        entry,
+    );
+    let condition = cfg.add_node_at(NodeKind::Condition, line_of(expr_while));
     cfg.add_edge(entry, condition);
 
     let body_entry = cfg.add_node(NodeKind::Statement);
     cfg.add_edge(condition, body_entry);
 
-    let body_exit = build_block_cfg(cfg, &expr_while.body, body_entry, condition)?;
+    let body_exit = build_block_cfg(cfg, &expr_while.body, body_entry, condition, macros)?;
 
     // Back edge to condition
     cfg.add_edge(body_exit, condition);
@@ -217,14 +381,16 @@ fn build_for_cfg(
     expr_for: &ExprForLoop,
     entry: NodeId,
     _exit: NodeId,
+    macros: &[String],
 ) -> Result<NodeId> {
-    let condition = cfg.add_node(NodeKind::Condition);
+    let entry = add_decisions(cfg, macro_decision_lines([&*expr_for.expr], macros), entry);
+    let condition = cfg.add_node_at(NodeKind::Condition, line_of(expr_for));
     cfg.add_edge(entry, condition);
 
     let body_entry = cfg.add_node(NodeKind::Statement);
     cfg.add_edge(condition, body_entry);
 
-    let body_exit = build_block_cfg(cfg, &expr_for.body, body_entry, condition)?;
+    let body_exit = build_block_cfg(cfg, &expr_for.body, body_entry, condition, macros)?;
 
     // Back edge to condition
     cfg.add_edge(body_exit, condition);
@@ -242,8 +408,9 @@ fn build_expr_block_cfg(
     expr_block: &ExprBlock,
     entry: NodeId,
     exit: NodeId,
+    macros: &[String],
 ) -> Result<NodeId> {
-    build_block_cfg(cfg, &expr_block.block, entry, exit)
+    build_block_cfg(cfg, &expr_block.block, entry, exit, macros)
 }
 
 #[cfg(test)]
@@ -259,7 +426,8 @@ mod tests {
                 local_index: 0,
             },
             name: Some("test".to_string()),
-            span: SourceSpan::new(0, source.len(), 1, 1, 0),
+            kind: crate::ast::FunctionKind::Function,
+            span: SourceSpan::new(0, source.len(), 1, 1, 0, 0),
             body: FunctionBody::Rust {
                 source: source.to_string(),
             },
@@ -322,4 +490,42 @@ fn with_match(x: i32) {
         // Should have entry, exit, condition, and match arms
         assert!(cfg.node_count() >= 4);
     }
+
+    fn condition_lines(cfg: &Cfg) -> Vec<u32> {
+        cfg.nodes
+            .iter()
+            .filter(|n| matches!(n.kind, NodeKind::Condition))
+            .filter_map(|n| n.line)
+            .collect()
+    }
+
+    #[test]
+    fn test_branching_macros_become_condition_nodes() {
+        let source = r#"fn check(x: Option<i32>) -> bool {
+    assert!(x.is_some());
+    let _log = |v: i32| debug_assert!(v > 0);
+    anyhow::ensure!(x != Some(0), "zero");
+    println!("{:?}", x);
+    matches!(x, Some(1 | 2))
+}
+"#;
+        let cfg = RustCfgBuilder.build(&make_test_function(source));
+        assert!(cfg.validate().is_ok());
+        // The closure's assertion belongs to the closure; println! is no branch
+        assert_eq!(condition_lines(&cfg), vec![2, 4, 6]);
+    }
+
+    #[test]
+    fn test_configured_macros_replace_the_defaults() {
+        let source = r#"fn check(x: i32) {
+    assert!(x > 0);
+    check_arg!(x < 10);
+}
+"#;
+        let function = make_test_function(source);
+        let cfg = RustCfgBuilder.build_with_macros(&function, &["check_arg".to_string()]);
+        assert_eq!(condition_lines(&cfg), vec![3]);
+        let cfg = RustCfgBuilder.build_with_macros(&function, &[]);
+        assert!(condition_lines(&cfg).is_empty());
+    }
 }
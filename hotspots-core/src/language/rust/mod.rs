@@ -5,5 +5,5 @@
 pub mod cfg_builder;
 pub mod parser;
 
-pub use cfg_builder::RustCfgBuilder;
+pub use cfg_builder::{RustCfgBuilder, DEFAULT_BRANCHING_MACROS};
 pub use parser::RustParser;
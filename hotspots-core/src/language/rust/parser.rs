@@ -1,6 +1,6 @@
 //! Rust parser implementation using syn
 
-use crate::ast::{FunctionId, FunctionNode};
+use crate::ast::{FunctionId, FunctionKind, FunctionNode};
 use crate::language::function_body::FunctionBody;
 use crate::language::parser::{LanguageParser, ParsedModule};
 use crate::language::span::SourceSpan;
@@ -100,14 +100,14 @@ impl RustModule {
         local_index: &mut usize,
         functions: &mut Vec<FunctionNode>,
     ) {
-        self.extract_function_common(
+        functions.push(self.extract_function_common(
             &item_fn.sig,
             item_fn,
             name_prefix,
+            FunctionKind::Function,
             file_index,
             local_index,
-            functions,
-        );
+        ));
     }
 
     /// Extract a function node from ImplItemFn (method)
@@ -119,14 +119,14 @@ impl RustModule {
         local_index: &mut usize,
         functions: &mut Vec<FunctionNode>,
     ) {
-        self.extract_function_common(
+        functions.push(self.extract_function_common(
             &impl_fn.sig,
             impl_fn,
             name_prefix,
+            FunctionKind::Method,
             file_index,
             local_index,
-            functions,
-        );
+        ));
     }
 
     /// Common extraction logic for both functions and methods
@@ -135,10 +135,10 @@ impl RustModule {
         sig: &Signature,
         item: &S,
         name_prefix: Option<&str>,
+        kind: FunctionKind,
         file_index: usize,
         local_index: &mut usize,
-        functions: &mut Vec<FunctionNode>,
-    ) {
+    ) -> FunctionNode {
         let name = if let Some(prefix) = name_prefix {
             format!("{}::{}", prefix, sig.ident)
         } else {
@@ -167,23 +167,26 @@ impl RustModule {
             end_byte,
             span_start.line as u32,
             span_end.line as u32,
-            span_start.column as u32,
+            (start_byte - self.line_column_to_byte(span_start.line, 0)) as u32,
+            (end_byte - self.line_column_to_byte(span_end.line, 0)) as u32,
         );
 
-        functions.push(FunctionNode {
+        let function = FunctionNode {
             id: FunctionId {
                 file_index,
                 local_index: *local_index,
             },
             name: Some(name),
+            kind,
             span,
             body: FunctionBody::Rust {
                 source: body_source,
             },
             suppression_reason: None,
-        });
+        };
 
         *local_index += 1;
+        function
     }
 
     /// Convert line/column to byte offset
@@ -18,20 +18,37 @@ pub struct SourceSpan {
     pub end_line: u32,
     /// Column number of the start (0-indexed, in bytes)
     pub start_col: u32,
+    /// Column number of the end on `end_line` (0-indexed, in bytes, exclusive)
+    pub end_col: u32,
 }
 
 impl SourceSpan {
     /// Create a new source span
-    pub fn new(start: usize, end: usize, start_line: u32, end_line: u32, start_col: u32) -> Self {
+    pub fn new(
+        start: usize,
+        end: usize,
+        start_line: u32,
+        end_line: u32,
+        start_col: u32,
+        end_col: u32,
+    ) -> Self {
         SourceSpan {
             start,
             end,
             start_line,
             end_line,
             start_col,
+            end_col,
         }
     }
 
+    /// Span covering all of `source`
+    pub fn whole_file(source: &str) -> Self {
+        let lines = source.lines().count().max(1) as u32;
+        let end_col = source.lines().last().map_or(0, str::len) as u32;
+        SourceSpan::new(0, source.len(), 1, lines, 0, end_col)
+    }
+
     /// Get the length of the span in bytes
     pub fn len(&self) -> usize {
         self.end.saturating_sub(self.start)
@@ -71,6 +88,7 @@ impl From<swc_common::Span> for SourceSpan {
             start_line: 0, // To be filled in by parser
             end_line: 0,   // To be filled in by parser
             start_col: 0,  // To be filled in by parser
+            end_col: 0,    // To be filled in by parser
         }
     }
 }
@@ -87,50 +105,90 @@ pub fn span_with_location(
         end: span.hi.0 as usize,
         start_line: start_loc.line as u32,
         end_line: end_loc.line as u32,
-        start_col: start_loc.col.0 as u32,
+        start_col: byte_column(span.lo, source_map),
+        end_col: byte_column(span.hi, source_map),
     }
 }
 
+/// 0-indexed byte column of `pos` (SWC's own `col` counts chars)
+fn byte_column(pos: swc_common::BytePos, source_map: &swc_common::SourceMap) -> u32 {
+    let located = source_map.lookup_byte_offset(pos);
+    let src: &str = &located.sf.src;
+    let offset = (located.pos.0 as usize).min(src.len());
+    let line_start = src.as_bytes()[..offset]
+        .iter()
+        .rposition(|&b| b == b'\n')
+        .map_or(0, |i| i + 1);
+    (offset - line_start) as u32
+}
+
+/// Convert a 0-indexed byte column on `line` to a 1-based column in UTF-16
+/// code units, the unit SARIF and LSP count in
+///
+/// Columns past the end of the line clamp to it, and a column inside a
+/// multi-byte character rounds down to the character's start.
+pub fn utf16_column(line: &str, byte_col: u32) -> u32 {
+    let mut end = (byte_col as usize).min(line.len());
+    while !line.is_char_boundary(end) {
+        end -= 1;
+    }
+    line[..end].encode_utf16().count() as u32 + 1
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
     fn test_new() {
-        let span = SourceSpan::new(10, 20, 1, 3, 5);
+        let span = SourceSpan::new(10, 20, 1, 3, 5, 0);
         assert_eq!(span.start, 10);
         assert_eq!(span.end, 20);
         assert_eq!(span.start_line, 1);
         assert_eq!(span.end_line, 3);
         assert_eq!(span.start_col, 5);
+        assert_eq!(span.end_col, 0);
+    }
+
+    #[test]
+    fn test_utf16_column() {
+        // `é` is 2 bytes and 1 UTF-16 unit; `😀` is 4 bytes and 2 units
+        let line = "é😀x";
+        assert_eq!(utf16_column(line, 0), 1);
+        assert_eq!(utf16_column(line, 2), 2);
+        assert_eq!(utf16_column(line, 6), 4);
+        assert_eq!(utf16_column(line, 7), 5);
+        // Inside a character, and past the end of the line
+        assert_eq!(utf16_column(line, 3), 2);
+        assert_eq!(utf16_column(line, 40), 5);
     }
 
     #[test]
     fn test_len() {
-        let span = SourceSpan::new(10, 20, 1, 3, 5);
+        let span = SourceSpan::new(10, 20, 1, 3, 5, 0);
         assert_eq!(span.len(), 10);
 
-        let empty_span = SourceSpan::new(10, 10, 1, 1, 5);
+        let empty_span = SourceSpan::new(10, 10, 1, 1, 5, 0);
         assert_eq!(empty_span.len(), 0);
     }
 
     #[test]
     fn test_is_empty() {
-        let span = SourceSpan::new(10, 20, 1, 3, 5);
+        let span = SourceSpan::new(10, 20, 1, 3, 5, 0);
         assert!(!span.is_empty());
 
-        let empty_span = SourceSpan::new(10, 10, 1, 1, 5);
+        let empty_span = SourceSpan::new(10, 10, 1, 1, 5, 0);
         assert!(empty_span.is_empty());
 
-        let backwards_span = SourceSpan::new(20, 10, 1, 1, 5);
+        let backwards_span = SourceSpan::new(20, 10, 1, 1, 5, 0);
         assert!(backwards_span.is_empty());
     }
 
     #[test]
     fn test_contains() {
-        let outer = SourceSpan::new(10, 30, 1, 5, 5);
-        let inner = SourceSpan::new(15, 25, 2, 4, 10);
-        let outside = SourceSpan::new(5, 15, 1, 2, 0);
+        let outer = SourceSpan::new(10, 30, 1, 5, 5, 0);
+        let inner = SourceSpan::new(15, 25, 2, 4, 10, 0);
+        let outside = SourceSpan::new(5, 15, 1, 2, 0, 0);
 
         assert!(outer.contains(&inner));
         assert!(!inner.contains(&outer));
@@ -142,9 +200,9 @@ mod tests {
 
     #[test]
     fn test_overlaps() {
-        let span1 = SourceSpan::new(10, 20, 1, 3, 5);
-        let span2 = SourceSpan::new(15, 25, 2, 4, 10);
-        let span3 = SourceSpan::new(25, 30, 4, 5, 20);
+        let span1 = SourceSpan::new(10, 20, 1, 3, 5, 0);
+        let span2 = SourceSpan::new(15, 25, 2, 4, 10, 0);
+        let span3 = SourceSpan::new(25, 30, 4, 5, 20, 0);
 
         assert!(span1.overlaps(&span2));
         assert!(span2.overlaps(&span1));
@@ -158,12 +216,12 @@ mod tests {
     #[test]
     fn test_edge_cases() {
         // Adjacent spans don't overlap
-        let span1 = SourceSpan::new(10, 20, 1, 3, 5);
-        let span2 = SourceSpan::new(20, 30, 3, 5, 15);
+        let span1 = SourceSpan::new(10, 20, 1, 3, 5, 0);
+        let span2 = SourceSpan::new(20, 30, 3, 5, 15, 0);
         assert!(!span1.overlaps(&span2));
 
         // Zero-width span
-        let zero_span = SourceSpan::new(15, 15, 2, 2, 10);
+        let zero_span = SourceSpan::new(15, 15, 2, 2, 10, 0);
         assert!(span1.contains(&zero_span));
         assert!(!zero_span.overlaps(&span1)); // Zero-width doesn't overlap
     }
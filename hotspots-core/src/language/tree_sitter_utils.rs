@@ -10,6 +10,21 @@ pub fn find_child_by_kind<'a>(node: Node<'a>, kind: &str) -> Option<Node<'a>> {
     result
 }
 
+/// First child of `node` matching `kinds`, trying each kind in order
+pub fn find_child_by_kinds<'a>(node: Node<'a>, kinds: &[&str]) -> Option<Node<'a>> {
+    kinds.iter().find_map(|kind| find_child_by_kind(node, kind))
+}
+
+/// 1-indexed source line a node starts on
+pub fn node_line(node: &Node) -> u32 {
+    node.start_position().row as u32 + 1
+}
+
+/// Number of source lines a node spans
+pub fn node_loc(node: &Node) -> usize {
+    node.end_position().row - node.start_position().row + 1
+}
+
 pub fn find_function_by_start<'a>(
     node: Node<'a>,
     start_byte: usize,
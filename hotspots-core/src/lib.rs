@@ -4,63 +4,115 @@
 
 // Global invariants enforced in this crate:
 // - Analysis is strictly per-function
-// - No global mutable state
+// - No global mutable state (other than the stderr diagnostic level, which
+//   never affects results)
 // - No randomness, clocks, or async
 // - File analysis is parallelized via rayon; all other logic is single-threaded
+// - Filesystem walking, git, storage, and training sit behind the default
+//   `native` feature; without it only in-memory analysis is built
 // - Deterministic traversal order must be explicit
 // - Formatting, comments, and whitespace must not affect results
 // - Identical input yields byte-for-byte identical output
 
+#[cfg(feature = "native")]
 pub mod aggregates;
 pub mod analysis;
+#[cfg(feature = "native")]
+pub mod archive;
 pub mod ast;
+pub mod ast_dump;
 pub mod callgraph;
 pub mod cfg;
+#[cfg(feature = "native")]
 pub mod compact;
+#[cfg(feature = "native")]
 pub mod config;
+#[cfg(feature = "native")]
 pub mod coupling;
+#[cfg(feature = "native")]
+pub mod coverage;
+#[cfg(feature = "native")]
 pub mod db;
+#[cfg(feature = "native")]
 pub mod delta;
+#[cfg(feature = "native")]
+pub mod determinism;
+pub mod diagnostics;
 pub mod discover;
+#[cfg(feature = "native")]
+pub mod entrypoints;
+#[cfg(feature = "native")]
 pub mod gate;
+#[cfg(feature = "native")]
 pub mod git;
+#[cfg(feature = "native")]
 pub mod history_signals;
+#[cfg(feature = "native")]
 pub mod html;
+#[cfg(feature = "native")]
 pub mod imports;
+#[cfg(feature = "native")]
 pub mod isolation_forest;
 pub mod language;
 pub mod metrics;
+#[cfg(feature = "native")]
 pub mod models;
 pub mod parser;
 pub mod patterns;
 pub mod phrases;
+#[cfg(feature = "native")]
 pub mod policy;
+#[cfg(feature = "native")]
 pub mod prune;
+#[cfg(feature = "native")]
+pub mod redact;
 pub mod report;
 pub mod risk;
+#[cfg(feature = "native")]
 pub mod sarif;
 pub mod scoring;
+#[cfg(feature = "native")]
 pub mod snapshot;
 pub mod suppression;
+#[cfg(feature = "native")]
 pub mod touch_cache;
+#[cfg(feature = "native")]
 pub mod trainer;
+#[cfg(feature = "native")]
 pub mod trends;
 
+pub use analysis::analyze_source;
 pub use callgraph::CallGraph;
+#[cfg(feature = "native")]
 pub use config::ResolvedConfig;
+#[cfg(feature = "native")]
 pub use git::GitContext;
 pub use report::{render_json, render_text, render_text_grouped, sort_reports, FunctionRiskReport};
+pub use risk::compute_lrs;
+#[cfg(feature = "native")]
 pub use snapshot::TouchMode;
 
+#[cfg(feature = "native")]
 use anyhow::{Context, Result};
+#[cfg(feature = "native")]
+use diagnostics::Level;
+#[cfg(feature = "native")]
 use swc_common::{sync::Lrc, SourceMap};
 
+/// Reports for one file, with their CFGs when a visitor is attached
+#[cfg(feature = "native")]
+type FileResults = (Vec<FunctionRiskReport>, Vec<cfg::Cfg>);
+
+/// Per-function callback for [`analyze_with_visitor`]
+pub type FunctionVisitor<'a> = dyn FnMut(&FunctionRiskReport, &cfg::Cfg) + 'a;
+
 pub struct AnalysisOptions {
     pub min_lrs: Option<f64>,
     pub top_n: Option<usize>,
 }
 
 /// Analyze files at the given path with default configuration
+#[cfg(feature = "native")]
 pub fn analyze(
     path: &std::path::Path,
     options: AnalysisOptions,
@@ -69,6 +121,7 @@ pub fn analyze(
 }
 
 /// Analyze files at the given path with optional resolved configuration
+#[cfg(feature = "native")]
 pub fn analyze_with_config(
     path: &std::path::Path,
     options: AnalysisOptions,
@@ -84,34 +137,146 @@ pub fn analyze_with_config(
 ///   source files are found)
 /// - Once with `(n, total)` after each file is processed (order not guaranteed
 ///   across parallel workers)
+#[cfg(feature = "native")]
 pub fn analyze_with_progress(
     path: &std::path::Path,
     options: AnalysisOptions,
     resolved_config: Option<&ResolvedConfig>,
     progress: Option<&(dyn Fn(usize, usize) + Send + Sync)>,
+) -> anyhow::Result<Vec<FunctionRiskReport>> {
+    if archive::is_archive(path) {
+        return archive::analyze_archive(path, options, resolved_config, progress);
+    }
+    let source_files = collect_source_files(path)?;
+    let require_functions = resolved_config.is_some_and(|c| c.require_functions);
+    run_analysis(
+        source_files,
+        options,
+        resolved_config,
+        progress,
+        None,
+        require_functions,
+    )
+}
+
+/// Like [`analyze_with_progress`] but analyzes only those of `files` that
+/// walking `path` would reach
+///
+/// Files outside `path`, under an ignored directory, behind a symlink, with an
+/// unsupported extension, or no longer on disk are dropped. Reports name files
+/// exactly as a full walk of `path` would, so they can replace that walk's
+/// reports file by file (see [`snapshot::Snapshot::splice`]).
+#[cfg(feature = "native")]
+pub fn analyze_files_with_progress(
+    path: &std::path::Path,
+    files: &[std::path::PathBuf],
+    options: AnalysisOptions,
+    resolved_config: Option<&ResolvedConfig>,
+    progress: Option<&(dyn Fn(usize, usize) + Send + Sync)>,
+) -> anyhow::Result<Vec<FunctionRiskReport>> {
+    let mut source_files: Vec<_> = files
+        .iter()
+        .filter_map(|file| walked_path(path, file))
+        .collect();
+    source_files.sort();
+    source_files.dedup();
+    // A subset may legitimately contain no functions, so `require_functions` is
+    // left to the caller
+    run_analysis(
+        source_files,
+        options,
+        resolved_config,
+        progress,
+        None,
+        false,
+    )
+}
+
+/// Like [`analyze_with_config`] but calls `visitor` with every analyzed
+/// function and the CFG its metrics were computed from.
+///
+/// Lets consumers run custom per-function checks without a second parse pass.
+/// The visitor sees each function that survives `min_lrs`, `exclude_functions`,
+/// skip-anonymous, and config `suppress` rules (before `top_n` truncation), in
+/// deterministic (file, span) order. Files are still analyzed in parallel; the visitor runs
+/// on the calling thread once analysis completes, so CFGs are held in memory
+/// until then. Analysis is per function even when the config asks for
+/// `granularity: "file"`.
+#[cfg(feature = "native")]
+pub fn analyze_with_visitor(
+    path: &std::path::Path,
+    options: AnalysisOptions,
+    resolved_config: Option<&ResolvedConfig>,
+    visitor: &mut FunctionVisitor<'_>,
+) -> anyhow::Result<Vec<FunctionRiskReport>> {
+    let source_files = collect_source_files(path)?;
+    let require_functions = resolved_config.is_some_and(|c| c.require_functions);
+    run_analysis(
+        source_files,
+        options,
+        resolved_config,
+        None,
+        Some(visitor),
+        require_functions,
+    )
+}
+
+#[cfg(feature = "native")]
+pub(crate) fn run_analysis(
+    source_files: Vec<std::path::PathBuf>,
+    options: AnalysisOptions,
+    resolved_config: Option<&ResolvedConfig>,
+    progress: Option<&(dyn Fn(usize, usize) + Send + Sync)>,
+    visitor: Option<&mut FunctionVisitor<'_>>,
+    require_functions: bool,
 ) -> anyhow::Result<Vec<FunctionRiskReport>> {
     use rayon::prelude::*;
     use std::sync::atomic::{AtomicUsize, Ordering};
 
     // Build weights/thresholds from config
-    let weights = resolved_config.map(|c| risk::LrsWeights {
-        cc: c.weight_cc,
-        nd: c.weight_nd,
-        fo: c.weight_fo,
-        ns: c.weight_ns,
-    });
-    let thresholds = resolved_config.map(|c| risk::RiskThresholds {
-        moderate: c.moderate_threshold,
-        high: c.high_threshold,
-        critical: c.critical_threshold,
-    });
-    let pattern_thresholds = resolved_config.map(|c| &c.pattern_thresholds);
-
-    // Collect and filter source files upfront so the total is known before analysis begins
-    let source_files: Vec<_> = collect_source_files(path)?
+    let weights = resolved_config.map(ResolvedConfig::lrs_weights);
+    let thresholds = resolved_config.map(ResolvedConfig::risk_thresholds);
+    let settings = analysis::AnalysisSettings {
+        weights: weights.as_ref(),
+        thresholds: thresholds.as_ref(),
+        pattern_thresholds: resolved_config.map(|c| &c.pattern_thresholds),
+        rust_branching_macros: resolved_config.and_then(|c| c.rust_branching_macros.as_deref()),
+    };
+    let skip_anonymous = resolved_config.is_some_and(|c| c.skip_anonymous_functions);
+    // A visitor needs per-function CFGs, so it always runs at function granularity
+    let whole_files = visitor.is_none()
+        && resolved_config.is_some_and(|c| c.granularity == config::Granularity::File);
+
+    // Filter source files upfront so the total is known before analysis begins
+    let mut source_files: Vec<_> = source_files
         .into_iter()
         .filter(|f| resolved_config.map_or(true, |c| c.should_include(f)))
         .collect();
+
+    // Size guard: a single huge minified/generated file can dominate runtime or OOM
+    // the parser, so oversized files are skipped before any parsing is attempted.
+    let max_file_bytes =
+        resolved_config.map_or(config::DEFAULT_MAX_FILE_BYTES, |c| c.max_file_bytes);
+    let mut oversized_files: usize = 0;
+    if max_file_bytes > 0 {
+        source_files.retain(|f| {
+            let size = std::fs::metadata(f).map(|m| m.len()).unwrap_or(0);
+            if size > max_file_bytes {
+                if diagnostics::enabled(Level::Warn) {
+                    eprintln!(
+                        "warning: skipping file {}: {} bytes exceeds max_file_bytes ({})",
+                        f.display(),
+                        size,
+                        max_file_bytes
+                    );
+                }
+                oversized_files += 1;
+                false
+            } else {
+                true
+            }
+        });
+    }
     let total_files = source_files.len();
 
     if total_files > 0 {
@@ -122,36 +287,80 @@ pub fn analyze_with_progress(
 
     // Parallel file analysis: each worker creates its own SourceMap (Lrc is !Send
     // so it cannot be shared, but creating one per-task on a single thread is safe).
+    // CFGs are only kept when a visitor needs them; otherwise they are dropped
+    // inside the worker as soon as the file is done.
+    let keep_cfgs = visitor.is_some();
     let counter = AtomicUsize::new(0);
-    let mut raw_results: Vec<(usize, &std::path::Path, Result<Vec<FunctionRiskReport>>)> =
-        source_files
-            .par_iter()
-            .enumerate()
-            .map(|(file_index, file_path)| {
-                let cm: Lrc<SourceMap> = Default::default();
-                let result = analysis::analyze_file_with_config(
+    // `require_functions` asks whether anything was found at all, so `min_lrs`
+    // is applied here, after counting, rather than during analysis
+    let found_functions = AtomicUsize::new(0);
+    let min_lrs = options.min_lrs;
+    let file_options = AnalysisOptions {
+        min_lrs: min_lrs.filter(|_| !require_functions),
+        top_n: options.top_n,
+    };
+    let keep = |r: &FunctionRiskReport| {
+        min_lrs.map_or(true, |min| r.lrs >= min)
+            && !resolved_config.is_some_and(|c| c.is_function_excluded(&r.file, &r.function))
+    };
+    let mut raw_results: Vec<(usize, &std::path::Path, Result<FileResults>)> = source_files
+        .par_iter()
+        .enumerate()
+        .map(|(file_index, file_path)| {
+            let cm: Lrc<SourceMap> = Default::default();
+            let result = if whole_files {
+                analysis::analyze_whole_file(file_path, &cm, file_index, &file_options, settings)
+                    .map(|report| {
+                        found_functions.fetch_add(report.iter().len(), Ordering::Relaxed);
+                        (report.into_iter().filter(keep).collect(), Vec::new())
+                    })
+            } else {
+                analysis::analyze_file_with_cfgs(
                     file_path,
                     &cm,
                     file_index,
-                    &options,
-                    weights.as_ref(),
-                    thresholds.as_ref(),
-                    pattern_thresholds,
-                );
-                let done = counter.fetch_add(1, Ordering::Relaxed) + 1;
-                if let Some(f) = progress {
-                    f(done, total_files);
-                }
-                (file_index, file_path.as_path(), result)
-            })
-            .collect();
+                    &file_options,
+                    settings,
+                )
+                .map(|analyzed| {
+                    let analyzed: Vec<_> = analyzed
+                        .into_iter()
+                        .filter(|(r, _)| !(skip_anonymous && r.is_anonymous()))
+                        .collect();
+                    found_functions.fetch_add(analyzed.len(), Ordering::Relaxed);
+                    let (reports, cfgs): (Vec<_>, Vec<_>) =
+                        analyzed.into_iter().filter(|(r, _)| keep(r)).unzip();
+                    (reports, if keep_cfgs { cfgs } else { Vec::new() })
+                })
+            };
+            let done = counter.fetch_add(1, Ordering::Relaxed) + 1;
+            if let Some(f) = progress {
+                f(done, total_files);
+            }
+            (file_index, file_path.as_path(), result)
+        })
+        .collect();
 
     // Restore deterministic ordering (parallel workers complete out of order)
     raw_results.sort_by_key(|(idx, _, _)| *idx);
 
+    if let Some(visitor) = visitor {
+        for (_, _, result) in raw_results.iter_mut() {
+            if let Ok((reports, cfgs)) = result {
+                if let Some(c) = resolved_config {
+                    suppression::apply(reports, &c.suppress_rules);
+                }
+                for (report, cfg) in reports.iter().zip(cfgs.iter()) {
+                    visitor(report, cfg);
+                }
+                *cfgs = Vec::new();
+            }
+        }
+    }
+
     let mut skipped_files: usize = 0;
 
-    let final_reports = if let Some(top_n) = options.top_n {
+    let mut final_reports = if let Some(top_n) = options.top_n {
         // Bounded min-heap: maintain at most top_n reports keyed by lrs ascending
         // so the root is always the lowest score seen so far.
         use std::cmp::Ordering;
@@ -183,7 +392,7 @@ pub fn analyze_with_progress(
         let mut heap: BinaryHeap<MinByLrs> = BinaryHeap::with_capacity(top_n + 1);
         for (_file_index, file_path, result) in raw_results {
             match result {
-                Ok(reports) => {
+                Ok((reports, _)) => {
                     for r in reports {
                         heap.push(MinByLrs(r));
                         if heap.len() > top_n {
@@ -192,7 +401,9 @@ pub fn analyze_with_progress(
                     }
                 }
                 Err(e) => {
-                    eprintln!("warning: skipping file {}: {}", file_path.display(), e);
+                    if diagnostics::enabled(Level::Warn) {
+                        eprintln!("warning: skipping file {}: {}", file_path.display(), e);
+                    }
                     skipped_files += 1;
                 }
             }
@@ -205,9 +416,11 @@ pub fn analyze_with_progress(
         let mut all_reports = Vec::new();
         for (_file_index, file_path, result) in raw_results {
             match result {
-                Ok(reports) => all_reports.extend(reports),
+                Ok((reports, _)) => all_reports.extend(reports),
                 Err(e) => {
-                    eprintln!("warning: skipping file {}: {}", file_path.display(), e);
+                    if diagnostics::enabled(Level::Warn) {
+                        eprintln!("warning: skipping file {}: {}", file_path.display(), e);
+                    }
                     skipped_files += 1;
                 }
             }
@@ -215,29 +428,100 @@ pub fn analyze_with_progress(
         sort_reports(all_reports)
     };
 
-    if skipped_files > 0 {
+    // Config `suppress` rules: still reported, but skipped by policies
+    if let Some(c) = resolved_config {
+        suppression::apply(&mut final_reports, &c.suppress_rules);
+    }
+
+    if skipped_files > 0 && diagnostics::enabled(Level::Warn) {
         eprintln!("Skipped {} file(s) due to analysis errors", skipped_files);
     }
+    if oversized_files > 0 && diagnostics::enabled(Level::Warn) {
+        eprintln!(
+            "Skipped {} file(s) larger than {} bytes (set max_file_bytes or --max-file-size to change)",
+            oversized_files, max_file_bytes
+        );
+    }
+
+    if require_functions && found_functions.load(Ordering::Relaxed) == 0 {
+        anyhow::bail!(
+            "no functions found in {} source file(s) (require_functions is set); \
+             check the analyzed path and include/exclude patterns",
+            total_files
+        );
+    }
 
     Ok(final_reports)
 }
 
-/// Check if a file is a supported source file
-fn is_supported_source_file(filename: &str) -> bool {
+/// Why a path was left out of analysis, as listed by `analyze --list-skipped`
+#[cfg(feature = "native")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SkipReason {
+    /// Symbolic links are never followed
+    Symlink,
+    /// Dependency, build-output, or hidden directory, pruned without descending
+    IgnoredDir,
+    /// TypeScript declaration file (`.d.ts`)
+    DeclarationFile,
+    /// No supported language for the file's extension
+    UnsupportedExtension,
+    /// Filtered out by `include`/`exclude` (config or command line)
+    Excluded,
+    /// Larger than `max_file_bytes`
+    TooLarge,
+    /// Three or more lines over 1000 characters
+    Minified,
+    /// Under a vendored or generated-asset directory (`vendor/`, `third_party/`,
+    /// `assets/js/`, ...)
+    Vendored,
+}
+
+#[cfg(feature = "native")]
+impl SkipReason {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SkipReason::Symlink => "symlink",
+            SkipReason::IgnoredDir => "ignored_dir",
+            SkipReason::DeclarationFile => "declaration_file",
+            SkipReason::UnsupportedExtension => "unsupported_extension",
+            SkipReason::Excluded => "excluded",
+            SkipReason::TooLarge => "too_large",
+            SkipReason::Minified => "minified",
+            SkipReason::Vendored => "vendored",
+        }
+    }
+}
+
+/// A path the traversal did not analyze, with the reason
+#[cfg(feature = "native")]
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct SkippedPath {
+    pub path: std::path::PathBuf,
+    pub reason: SkipReason,
+}
+
+/// Render skipped paths as JSON (`analyze --list-skipped --format json`)
+#[cfg(feature = "native")]
+pub fn render_skipped_json(skipped: &[SkippedPath]) -> String {
+    serde_json::to_string_pretty(skipped).unwrap_or_else(|_| "[]".to_string())
+}
+
+/// Why a file is not a supported source file, or `None` if it is one
+#[cfg(feature = "native")]
+fn unsupported_file_reason(filename: &str) -> Option<SkipReason> {
     // Skip TypeScript declaration files (.d.ts)
     if filename.ends_with(".d.ts") {
-        return false;
+        return Some(SkipReason::DeclarationFile);
     }
 
     // Use language detection to check if file is supported
-    if let Some(ext) = std::path::Path::new(filename)
+    let supported = std::path::Path::new(filename)
         .extension()
         .and_then(|e| e.to_str())
-    {
-        language::Language::from_extension(ext).is_some()
-    } else {
-        false
-    }
+        .is_some_and(|ext| language::Language::from_extension(ext).is_some());
+    (!supported).then_some(SkipReason::UnsupportedExtension)
 }
 
 /// Collect all supported source files from a path (file or directory)
@@ -251,17 +535,29 @@ fn is_supported_source_file(filename: &str) -> bool {
 /// - Java: .java
 /// - Python: .py, .pyw
 /// - Rust: .rs
+#[cfg(feature = "native")]
 pub(crate) fn collect_source_files(path: &std::path::Path) -> Result<Vec<std::path::PathBuf>> {
+    walk_source_files(path, &mut None)
+}
+
+/// Collect source files, recording every path left out along the way in
+/// `skipped` when given
+#[cfg(feature = "native")]
+fn walk_source_files(
+    path: &std::path::Path,
+    skipped: &mut Option<&mut Vec<SkippedPath>>,
+) -> Result<Vec<std::path::PathBuf>> {
     let mut files = Vec::new();
 
     if path.is_file() {
         if let Some(filename) = path.file_name().and_then(|n| n.to_str()) {
-            if is_supported_source_file(filename) {
-                files.push(path.to_path_buf());
+            match unsupported_file_reason(filename) {
+                None => files.push(path.to_path_buf()),
+                Some(reason) => record_skip(skipped, path.to_path_buf(), reason),
             }
         }
     } else if path.is_dir() {
-        collect_source_files_recursive(path, &mut files)?;
+        collect_source_files_recursive(path, &mut files, skipped)?;
     }
 
     // Sort files for deterministic order
@@ -270,9 +566,93 @@ pub(crate) fn collect_source_files(path: &std::path::Path) -> Result<Vec<std::pa
     Ok(files)
 }
 
+/// `file` as walking `root` would yield it, or `None` when the walk would not
+/// reach it
+#[cfg(feature = "native")]
+fn walked_path(root: &std::path::Path, file: &std::path::Path) -> Option<std::path::PathBuf> {
+    if root.is_file() {
+        return (root == file && unsupported_file_reason(root.file_name()?.to_str()?).is_none())
+            .then(|| root.to_path_buf());
+    }
+    let rel = file.strip_prefix(root).ok()?;
+    if rel.as_os_str().is_empty() {
+        return None;
+    }
+    let mut walked = root.to_path_buf();
+    let mut components = rel.components().peekable();
+    while let Some(component) = components.next() {
+        let name = component.as_os_str().to_str()?;
+        walked.push(name);
+        if std::fs::symlink_metadata(&walked).ok()?.is_symlink() {
+            return None;
+        }
+        let is_file_name = components.peek().is_none();
+        if !is_file_name && is_skipped_dir(name) {
+            return None;
+        }
+        if is_file_name && (!walked.is_file() || unsupported_file_reason(name).is_some()) {
+            return None;
+        }
+    }
+    Some(walked)
+}
+
+#[cfg(feature = "native")]
+fn record_skip(
+    skipped: &mut Option<&mut Vec<SkippedPath>>,
+    path: std::path::PathBuf,
+    reason: SkipReason,
+) {
+    if let Some(skipped) = skipped {
+        skipped.push(SkippedPath { path, reason });
+    }
+}
+
+/// Every path under `path` that analysis would skip, with the reason, sorted by path
+///
+/// Covers traversal pruning (symlinks, ignored directories, unsupported or
+/// declaration files) and, given a config, `include`/`exclude` filtering and
+/// the `max_file_bytes` guard. Files that fail to parse are not listed; those
+/// are reported as warnings during analysis.
+#[cfg(feature = "native")]
+pub fn list_skipped_paths(
+    path: &std::path::Path,
+    resolved_config: Option<&ResolvedConfig>,
+) -> Result<Vec<SkippedPath>> {
+    let mut skipped = Vec::new();
+    let files = walk_source_files(path, &mut Some(&mut skipped))?;
+
+    let max_file_bytes =
+        resolved_config.map_or(config::DEFAULT_MAX_FILE_BYTES, |c| c.max_file_bytes);
+    for file in files {
+        let reason = if !resolved_config.map_or(true, |c| c.should_include(&file)) {
+            SkipReason::Excluded
+        } else if max_file_bytes > 0
+            && std::fs::metadata(&file).map_or(0, |m| m.len()) > max_file_bytes
+        {
+            SkipReason::TooLarge
+        } else {
+            // Unreadable files are left for analysis to report
+            let Ok(src) = language::read_source(&file) else {
+                continue;
+            };
+            match analysis::unauthored(&file, &src) {
+                Some(analysis::Unauthored::Minified { .. }) => SkipReason::Minified,
+                Some(analysis::Unauthored::Vendored) => SkipReason::Vendored,
+                None => continue,
+            }
+        };
+        skipped.push(SkippedPath { path: file, reason });
+    }
+
+    skipped.sort_by(|a, b| a.path.cmp(&b.path));
+    Ok(skipped)
+}
+
 /// Returns true for directory names that should not be traversed.
 /// These are pruned at walk time before any glob matching — keep this list
 /// to things that are unambiguously never first-party source code.
+#[cfg(feature = "native")]
 fn is_skipped_dir(name: &str) -> bool {
     matches!(
         name,
@@ -292,28 +672,33 @@ fn is_skipped_dir(name: &str) -> bool {
 }
 
 /// Process one directory entry, pushing source files or recursing into dirs
+#[cfg(feature = "native")]
 fn process_dir_entry(
     path: std::path::PathBuf,
     metadata: std::fs::Metadata,
     files: &mut Vec<std::path::PathBuf>,
+    skipped: &mut Option<&mut Vec<SkippedPath>>,
 ) -> Result<()> {
     use std::ffi::OsStr;
 
     if metadata.is_symlink() {
+        record_skip(skipped, path, SkipReason::Symlink);
         return Ok(());
     }
 
     if metadata.is_dir() {
         if let Some(name) = path.file_name().and_then(|n: &OsStr| n.to_str()) {
             if is_skipped_dir(name) {
+                record_skip(skipped, path, SkipReason::IgnoredDir);
                 return Ok(());
             }
         }
-        collect_source_files_recursive(&path, files)?;
+        collect_source_files_recursive(&path, files, skipped)?;
     } else if metadata.is_file() {
         if let Some(filename) = path.file_name().and_then(|n: &OsStr| n.to_str()) {
-            if is_supported_source_file(filename) {
-                files.push(path);
+            match unsupported_file_reason(filename) {
+                None => files.push(path),
+                Some(reason) => record_skip(skipped, path, reason),
             }
         }
     }
@@ -322,9 +707,11 @@ fn process_dir_entry(
 }
 
 /// Recursively collect supported source files from a directory
+#[cfg(feature = "native")]
 fn collect_source_files_recursive(
     dir: &std::path::Path,
     files: &mut Vec<std::path::PathBuf>,
+    skipped: &mut Option<&mut Vec<SkippedPath>>,
 ) -> Result<()> {
     for entry_result in std::fs::read_dir(dir)
         .with_context(|| format!("Failed to read directory: {}", dir.display()))?
@@ -333,118 +720,200 @@ fn collect_source_files_recursive(
         let path = entry.path();
         let metadata = std::fs::symlink_metadata(&path)
             .with_context(|| format!("Failed to read metadata: {}", path.display()))?;
-        process_dir_entry(path, metadata, files)?;
+        process_dir_entry(path, metadata, files, skipped)?;
     }
 
     Ok(())
 }
 
-/// Pre-compute all function IDs and build the name→indices reverse index.
-///
-/// Each function ID is allocated exactly once. `name_to_id` values store indices
-/// into the returned `Vec<String>` rather than cloned Strings, avoiding O(N)
-/// duplicate allocations during callee resolution.
+/// A function the call graph can resolve calls to, borrowed from a report or DB row
+#[cfg(feature = "native")]
+struct CallTarget<'a> {
+    file: &'a str,
+    name: &'a str,
+    callees: &'a [String],
+}
+
+/// Build the name→indices reverse index used to look up call sites.
 ///
-/// Returns (function_ids, name_to_report_idx, report_to_graph_idx).
-/// `report_to_graph_idx[i]` maps report index i to its graph node index — necessary
-/// because intern() deduplicates identical file::function IDs, so graph node count
-/// may be less than report count.
-fn build_name_index<'r>(
-    reports: &'r [FunctionRiskReport],
-    graph: &mut callgraph::CallGraph,
-) -> (
-    Vec<String>,
-    std::collections::HashMap<&'r str, Vec<usize>>,
-    Vec<u32>,
-) {
-    let mut function_ids: Vec<String> = Vec::with_capacity(reports.len());
-    let mut name_to_idx: std::collections::HashMap<&'r str, Vec<usize>> =
+/// Under [`callgraph::MethodResolution::Receiver`] qualified names are indexed by
+/// their final segment (`Type::method` under `method`) so receiver calls can
+/// find them; under `Name` the reported name is the key.
+#[cfg(feature = "native")]
+fn build_name_index<'a>(
+    targets: &[CallTarget<'a>],
+    resolution: callgraph::MethodResolution,
+) -> std::collections::HashMap<&'a str, Vec<usize>> {
+    let mut name_to_idx: std::collections::HashMap<&'a str, Vec<usize>> =
         std::collections::HashMap::new();
-    let mut report_to_graph_idx: Vec<u32> = Vec::with_capacity(reports.len());
-    for (i, report) in reports.iter().enumerate() {
-        let function_id = format!("{}::{}", report.file, report.function);
-        let graph_idx = graph.intern(function_id.clone());
-        function_ids.push(function_id);
-        report_to_graph_idx.push(graph_idx);
-        name_to_idx
-            .entry(report.function.as_str())
-            .or_default()
-            .push(i);
+    for (i, target) in targets.iter().enumerate() {
+        let key = match resolution {
+            callgraph::MethodResolution::Receiver => callgraph::method_name_of(target.name),
+            callgraph::MethodResolution::Name => target.name,
+        };
+        name_to_idx.entry(key).or_default().push(i);
+    }
+    name_to_idx
+}
+
+/// Candidate indices for a call site, or None when no function has that name
+#[cfg(feature = "native")]
+fn callee_candidates<'m>(
+    callee_name: &str,
+    name_to_idx: &'m std::collections::HashMap<&str, Vec<usize>>,
+    resolution: callgraph::MethodResolution,
+) -> Option<&'m Vec<usize>> {
+    match resolution {
+        callgraph::MethodResolution::Receiver => {
+            name_to_idx.get(callgraph::CallSite::parse(callee_name).name)
+        }
+        callgraph::MethodResolution::Name => name_to_idx.get(callee_name),
     }
-    (function_ids, name_to_idx, report_to_graph_idx)
 }
 
 /// Resolve the best callee index for a call site.
 ///
+/// Under `Receiver` resolution the receiver is consulted first:
+/// `this`/`self` calls prefer a same-file function on the caller's own type,
+/// and calls on a named receiver prefer a function on that type (qualified
+/// name or file stem) and never resolve to a different type's method.
+/// Calls on any other receiver skip the priority-3 fallback.
+///
 /// Priority 1: same-file callee.
 /// Priority 2: callee in a file the caller explicitly imports.
 /// Priority 3: first name match (fallback).
-/// Returns None for self-calls or unresolved names.
+/// Returns None for self-calls or unresolved names; otherwise the callee and
+/// whether it came from the priority-3 fallback.
+#[cfg(feature = "native")]
 fn resolve_callee(
     callee_name: &str,
     caller_idx: usize,
-    caller_file: &str,
-    reports: &[FunctionRiskReport],
+    targets: &[CallTarget<'_>],
     name_to_idx: &std::collections::HashMap<&str, Vec<usize>>,
     import_map: &std::collections::HashMap<String, std::collections::HashSet<String>>,
-) -> Option<usize> {
-    let possible_indices = name_to_idx.get(callee_name)?;
-    let normalized_caller_file = caller_file.replace('\\', "/");
+    resolution: callgraph::MethodResolution,
+) -> Option<(usize, bool)> {
+    let possible_indices = callee_candidates(callee_name, name_to_idx, resolution)?;
+    let caller = &targets[caller_idx];
+    let normalized_caller_file = caller.file.replace('\\', "/");
+    let same_file = |idx: usize| targets[idx].file.replace('\\', "/") == normalized_caller_file;
 
-    // Priority 1: same file
-    for &idx in possible_indices {
-        if idx == caller_idx {
-            continue;
-        }
-        let normalized_callee = reports[idx].file.replace('\\', "/");
-        if normalized_callee == normalized_caller_file {
-            return Some(idx);
+    let mut candidates: Vec<usize> = possible_indices
+        .iter()
+        .copied()
+        .filter(|&idx| idx != caller_idx)
+        .collect();
+    let mut allow_fallback = true;
+
+    if resolution == callgraph::MethodResolution::Receiver {
+        let site = callgraph::CallSite::parse(callee_name);
+        if site.is_self_call() {
+            // Priority 0: the caller's own type
+            let caller_owner = callgraph::owner_of(caller.name);
+            if let Some(idx) = candidates.iter().copied().find(|&idx| {
+                same_file(idx) && callgraph::owner_of(targets[idx].name) == caller_owner
+            }) {
+                return Some((idx, false));
+            }
+        } else if let Some(receiver_type) = site.receiver_type() {
+            let on_receiver_type = |idx: usize| {
+                callgraph::owner_of(targets[idx].name) == Some(receiver_type)
+                    || std::path::Path::new(targets[idx].file)
+                        .file_stem()
+                        .and_then(|stem| stem.to_str())
+                        == Some(receiver_type)
+            };
+            // Priority 0: a function on the named type
+            if let Some(idx) = candidates
+                .iter()
+                .copied()
+                .find(|&idx| on_receiver_type(idx))
+            {
+                return Some((idx, false));
+            }
+            candidates.retain(|&idx| callgraph::owner_of(targets[idx].name).is_none());
+            allow_fallback = false;
         }
     }
 
+    // Priority 1: same file
+    if let Some(idx) = candidates.iter().copied().find(|&idx| same_file(idx)) {
+        return Some((idx, false));
+    }
+
     // Priority 2: imported file
-    if let Some(imports) = import_map.get(caller_file) {
-        for &idx in possible_indices {
-            if idx != caller_idx && imports.contains(&reports[idx].file) {
-                return Some(idx);
-            }
+    if let Some(imports) = import_map.get(caller.file) {
+        if let Some(idx) = candidates
+            .iter()
+            .copied()
+            .find(|&idx| imports.contains(targets[idx].file))
+        {
+            return Some((idx, false));
         }
     }
 
     // Priority 3: first match (fallback)
-    possible_indices
+    candidates
         .first()
         .copied()
-        .filter(|&idx| idx != caller_idx)
+        .filter(|_| allow_fallback)
+        .map(|idx| (idx, true))
 }
 
 /// Add AST-derived edges to the graph; return (total_callee_names, resolved_callee_names)
+#[cfg(feature = "native")]
 fn add_callee_edges(
-    reports: &[FunctionRiskReport],
-    name_to_idx: &std::collections::HashMap<&str, Vec<usize>>,
-    import_map: &std::collections::HashMap<String, std::collections::HashSet<String>>,
+    targets: &[CallTarget<'_>],
+    repo_root: &std::path::Path,
+    resolution: callgraph::MethodResolution,
     graph: &mut callgraph::CallGraph,
-    report_to_graph_idx: &[u32],
+    target_to_graph_idx: &[u32],
 ) -> (usize, usize) {
+    let name_to_idx = build_name_index(targets, resolution);
+
+    // Build import map for import-guided resolution (priority 2 after same-file)
+    let file_list: Vec<&str> = targets.iter().map(|t| t.file).collect();
+    let file_deps = crate::imports::resolve_file_deps(&file_list, repo_root);
+    let mut import_map: std::collections::HashMap<String, std::collections::HashSet<String>> =
+        std::collections::HashMap::new();
+    for (from, to) in file_deps {
+        import_map.entry(from).or_default().insert(to);
+    }
+
     let mut total = 0usize;
     let mut resolved = 0usize;
-    for (caller_report_idx, report) in reports.iter().enumerate() {
-        let caller_graph_idx = report_to_graph_idx[caller_report_idx];
+    for (caller_idx, target) in targets.iter().enumerate() {
+        let caller_graph_idx = target_to_graph_idx[caller_idx];
         let mut added_callees = std::collections::HashSet::<u32>::new();
-        for callee_name in &report.callees {
+        let language = language::Language::from_path(std::path::Path::new(target.file));
+        for callee_name in target.callees {
+            let callee_name = match (resolution, language) {
+                (callgraph::MethodResolution::Name, Some(language)) => {
+                    match callgraph::receiverless_callee(language, callee_name) {
+                        Some(name) => name,
+                        None => continue,
+                    }
+                }
+                _ => callee_name.as_str(),
+            };
             total += 1;
-            if name_to_idx.contains_key(callee_name.as_str()) {
+            if callee_candidates(callee_name, &name_to_idx, resolution).is_some() {
                 resolved += 1;
-                if let Some(callee_report_idx) = resolve_callee(
+                if let Some((callee_idx, heuristic)) = resolve_callee(
                     callee_name,
-                    caller_report_idx,
-                    &report.file,
-                    reports,
-                    name_to_idx,
-                    import_map,
+                    caller_idx,
+                    targets,
+                    &name_to_idx,
+                    &import_map,
+                    resolution,
                 ) {
-                    let callee_graph_idx = report_to_graph_idx[callee_report_idx];
-                    if added_callees.insert(callee_graph_idx) {
+                    let callee_graph_idx = target_to_graph_idx[callee_idx];
+                    if !added_callees.insert(callee_graph_idx) {
+                        continue;
+                    }
+                    if heuristic {
+                        graph.add_heuristic_adj(caller_graph_idx, callee_graph_idx);
+                    } else {
                         graph.add_adj(caller_graph_idx, callee_graph_idx);
                     }
                 }
@@ -454,124 +923,126 @@ fn add_callee_edges(
     (total, resolved)
 }
 
+/// Add an edge from each anonymous function's enclosing named function to it
+///
+/// Anonymous functions are only reachable through the function that defines
+/// them (passing a callback, registering a handler), never by name. Top-level
+/// closures have no enclosing function and stay unconnected.
+#[cfg(feature = "native")]
+fn add_closure_edges(
+    targets: &[CallTarget<'_>],
+    graph: &mut callgraph::CallGraph,
+    target_to_graph_idx: &[u32],
+) {
+    let by_name: std::collections::HashMap<(&str, &str), u32> = targets
+        .iter()
+        .zip(target_to_graph_idx)
+        .map(|(t, &idx)| ((t.file, t.name), idx))
+        .collect();
+    for (target, &closure_idx) in targets.iter().zip(target_to_graph_idx) {
+        let Some(enclosing) = callgraph::enclosing_function(target.name) else {
+            continue;
+        };
+        if let Some(&parent_idx) = by_name.get(&(target.file, enclosing)) {
+            graph.add_adj(parent_idx, closure_idx);
+        }
+    }
+}
+
 /// Build a call graph from lean DB rows instead of full FunctionRiskReport slices.
 ///
 /// Loads only `(function_id, file, callees)` from the TempDb — ~2 MB for 51k functions
 /// vs ~23 MB for the full reports Vec. The caller should have already dropped the reports
 /// Vec before calling this.
 ///
-/// Resolution priority is identical to `build_call_graph`: same-file first, then
-/// imported-file, then first name match.
+/// Resolution, anonymous-function handling, and entry points are identical to
+/// `build_call_graph`.
+#[cfg(feature = "native")]
 pub fn build_call_graph_from_db(
     db: &db::TempDb,
     sha: &str,
     repo_root: &std::path::Path,
+    resolution: callgraph::MethodResolution,
+    include_anonymous: bool,
 ) -> Result<callgraph::CallGraph> {
     let rows = db.load_callee_rows(sha)?;
 
     let mut graph = callgraph::CallGraph::new();
 
-    // Intern all function IDs and build name → row-index map.
-    // Extract the function name by stripping the "file::" prefix.
-    let mut name_to_idx: std::collections::HashMap<String, Vec<usize>> =
-        std::collections::HashMap::new();
+    // Intern all function IDs; the function name is the ID minus its "file::" prefix.
+    let mut targets: Vec<CallTarget<'_>> = Vec::with_capacity(rows.len());
     let mut row_to_graph_idx: Vec<u32> = Vec::with_capacity(rows.len());
-
-    for (i, (function_id, file, _)) in rows.iter().enumerate() {
-        let graph_idx = graph.intern(function_id.clone());
-        row_to_graph_idx.push(graph_idx);
+    for (function_id, file, callees) in &rows {
         let name = function_id
             .get(file.len() + 2..)
-            .unwrap_or(function_id.as_str())
-            .to_string();
-        name_to_idx.entry(name).or_default().push(i);
-    }
-
-    // Build import map for import-guided resolution.
-    let file_list: Vec<&str> = rows.iter().map(|(_, f, _)| f.as_str()).collect();
-    let file_deps = crate::imports::resolve_file_deps(&file_list, repo_root);
-    let mut import_map: std::collections::HashMap<String, std::collections::HashSet<String>> =
-        std::collections::HashMap::new();
-    for (from, to) in file_deps {
-        import_map.entry(from).or_default().insert(to);
-    }
-
-    // Add edges with same priority logic as build_call_graph.
-    let mut total = 0usize;
-    let mut resolved = 0usize;
-    for (caller_idx, (_, caller_file, callees)) in rows.iter().enumerate() {
-        let caller_graph_idx = row_to_graph_idx[caller_idx];
-        let caller_file_norm = caller_file.replace('\\', "/");
-        let mut added: std::collections::HashSet<u32> = std::collections::HashSet::new();
-        for callee_name in callees {
-            total += 1;
-            if let Some(candidates) = name_to_idx.get(callee_name.as_str()) {
-                resolved += 1;
-                // Priority 1: same file
-                let mut chosen = None;
-                for &idx in candidates {
-                    if idx == caller_idx {
-                        continue;
-                    }
-                    if rows[idx].1.replace('\\', "/") == caller_file_norm {
-                        chosen = Some(idx);
-                        break;
-                    }
-                }
-                // Priority 2: imported file
-                if chosen.is_none() {
-                    if let Some(imports) = import_map.get(caller_file.as_str()) {
-                        for &idx in candidates {
-                            if idx != caller_idx && imports.contains(&rows[idx].1) {
-                                chosen = Some(idx);
-                                break;
-                            }
-                        }
-                    }
-                }
-                // Priority 3: first match
-                if chosen.is_none() {
-                    chosen = candidates.iter().copied().find(|&idx| idx != caller_idx);
-                }
-                if let Some(callee_idx) = chosen {
-                    let callee_graph_idx = row_to_graph_idx[callee_idx];
-                    if added.insert(callee_graph_idx) {
-                        graph.add_adj(caller_graph_idx, callee_graph_idx);
-                    }
-                }
-            }
+            .unwrap_or(function_id.as_str());
+        if !include_anonymous && callgraph::is_anonymous(name) {
+            continue;
         }
+        row_to_graph_idx.push(graph.intern(function_id.clone()));
+        targets.push(CallTarget {
+            file,
+            name,
+            callees,
+        });
     }
+
+    let (total, resolved) = add_callee_edges(
+        &targets,
+        repo_root,
+        resolution,
+        &mut graph,
+        &row_to_graph_idx,
+    );
+    add_closure_edges(&targets, &mut graph, &row_to_graph_idx);
     graph.total_callee_names = total;
     graph.resolved_callee_names = resolved;
+    entrypoints::apply(&mut graph, repo_root);
     Ok(graph)
 }
 
 /// Build a call graph from AST-derived callee names in function reports.
+///
+/// Each function ID is interned once; `report_to_graph_idx[i]` maps report i
+/// to its graph node, since intern() deduplicates identical file::function IDs.
+/// Roots listed in `.hotspots/entrypoints.txt` are installed on the result.
+///
+/// With `include_anonymous`, closures and callbacks are nodes with an edge from
+/// the named function they're defined in, so a call made inside a callback
+/// reaches its callee through the closure. Without it they're left out of the
+/// graph and get no call graph metrics.
+#[cfg(feature = "native")]
 pub fn build_call_graph(
     reports: &[FunctionRiskReport],
     repo_root: &std::path::Path,
+    resolution: callgraph::MethodResolution,
+    include_anonymous: bool,
 ) -> Result<callgraph::CallGraph> {
     let mut graph = callgraph::CallGraph::new();
-    let (_, name_to_idx, report_to_graph_idx) = build_name_index(reports, &mut graph);
-
-    // Build import map for import-guided resolution (priority 2 after same-file)
-    let file_list: Vec<&str> = reports.iter().map(|r| r.file.as_str()).collect();
-    let file_deps = crate::imports::resolve_file_deps(&file_list, repo_root);
-    let mut import_map: std::collections::HashMap<String, std::collections::HashSet<String>> =
-        std::collections::HashMap::new();
-    for (from, to) in file_deps {
-        import_map.entry(from).or_default().insert(to);
+    let mut targets: Vec<CallTarget<'_>> = Vec::with_capacity(reports.len());
+    let mut report_to_graph_idx: Vec<u32> = Vec::with_capacity(reports.len());
+    for report in reports {
+        if !include_anonymous && report.is_anonymous() {
+            continue;
+        }
+        report_to_graph_idx.push(graph.intern(format!("{}::{}", report.file, report.function)));
+        targets.push(CallTarget {
+            file: &report.file,
+            name: &report.function,
+            callees: &report.callees,
+        });
     }
 
     let (total, resolved) = add_callee_edges(
-        reports,
-        &name_to_idx,
-        &import_map,
+        &targets,
+        repo_root,
+        resolution,
         &mut graph,
         &report_to_graph_idx,
     );
+    add_closure_edges(&targets, &mut graph, &report_to_graph_idx);
     graph.total_callee_names = total;
     graph.resolved_callee_names = resolved;
+    entrypoints::apply(&mut graph, repo_root);
     Ok(graph)
 }
@@ -6,6 +6,9 @@
 
 use crate::ast::FunctionNode;
 use crate::cfg::Cfg;
+use crate::language::tree_sitter_utils::node_line;
+use crate::language::{c, csharp, go, java, python, Language};
+use swc_common::{BytePos, SourceMap, Spanned};
 use swc_ecma_ast::*;
 use swc_ecma_visit::{Visit, VisitWith};
 
@@ -32,6 +35,443 @@ fn calculate_loc(source: &str) -> usize {
     source.lines().count()
 }
 
+/// Calculate source lines of code (SLOC) for a function's lines
+///
+/// Excludes blank lines, comment-only lines, and lines consisting solely of
+/// brackets and separators (`}`, `});`, `)`, ...), so brace-heavy languages are
+/// not penalized relative to indentation-based ones. Strings and block
+/// comments are tracked across lines, so comment markers inside a string and
+/// block comments opened after code are both handled.
+pub fn calculate_sloc(lines: &[&str], language: crate::language::Language) -> usize {
+    let syntax = SlocSyntax::of(language);
+    let mut state = SlocState::Code;
+    lines
+        .iter()
+        .filter(|line| scan_sloc_line(line, syntax, &mut state))
+        .count()
+}
+
+/// Comment and string syntax the SLOC scanner needs for one language
+struct SlocSyntax {
+    line_comments: &'static [&'static str],
+    block_comments: bool,
+    /// String delimiters, longest first
+    strings: &'static [StringDelim],
+    /// `'` starts a char literal only when one closes it (Rust lifetimes)
+    rust_chars: bool,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+struct StringDelim {
+    delim: &'static str,
+    /// A backslash escapes the next character
+    escapes: bool,
+    /// The string may continue onto the next line
+    multiline: bool,
+}
+
+const fn delim(delim: &'static str, escapes: bool, multiline: bool) -> StringDelim {
+    StringDelim {
+        delim,
+        escapes,
+        multiline,
+    }
+}
+
+const PYTHON_SLOC: SlocSyntax = SlocSyntax {
+    line_comments: &["#"],
+    block_comments: false,
+    strings: &[
+        delim("\"\"\"", true, true),
+        delim("'''", true, true),
+        delim("\"", true, false),
+        delim("'", true, false),
+    ],
+    rust_chars: false,
+};
+
+const HCL_SLOC: SlocSyntax = SlocSyntax {
+    line_comments: &["#", "//"],
+    block_comments: true,
+    strings: &[delim("\"", true, false)],
+    rust_chars: false,
+};
+
+const RUST_SLOC: SlocSyntax = SlocSyntax {
+    line_comments: &["//"],
+    block_comments: true,
+    strings: &[delim("\"", true, true)],
+    rust_chars: true,
+};
+
+/// Go's backquoted raw strings span lines and take no escapes
+const GO_SLOC: SlocSyntax = SlocSyntax {
+    line_comments: &["//"],
+    block_comments: true,
+    strings: &[
+        delim("`", false, true),
+        delim("\"", true, false),
+        delim("'", true, false),
+    ],
+    rust_chars: false,
+};
+
+/// ECMAScript, including Vue and Svelte scripts, with template literals
+const ECMASCRIPT_SLOC: SlocSyntax = SlocSyntax {
+    line_comments: &["//"],
+    block_comments: true,
+    strings: &[
+        delim("`", true, true),
+        delim("\"", true, false),
+        delim("'", true, false),
+    ],
+    rust_chars: false,
+};
+
+/// Java, C#, and C
+const C_LIKE_SLOC: SlocSyntax = SlocSyntax {
+    line_comments: &["//"],
+    block_comments: true,
+    strings: &[delim("\"", true, false), delim("'", true, false)],
+    rust_chars: false,
+};
+
+impl SlocSyntax {
+    fn of(language: crate::language::Language) -> &'static Self {
+        use crate::language::Language;
+
+        match language {
+            Language::Python => &PYTHON_SLOC,
+            Language::Hcl => &HCL_SLOC,
+            Language::Rust => &RUST_SLOC,
+            Language::Go => &GO_SLOC,
+            Language::TypeScript
+            | Language::TypeScriptReact
+            | Language::JavaScript
+            | Language::JavaScriptReact
+            | Language::Vue
+            | Language::Svelte => &ECMASCRIPT_SLOC,
+            Language::Java | Language::CSharp | Language::C | Language::CHeader => &C_LIKE_SLOC,
+        }
+    }
+}
+
+/// What the SLOC scanner is inside of at a given point
+#[derive(Clone, Copy, PartialEq)]
+enum SlocState {
+    Code,
+    BlockComment,
+    Str(StringDelim),
+}
+
+/// Scan one line, carrying open strings and block comments over in `state`;
+/// returns whether the line holds code other than brackets and separators
+fn scan_sloc_line(line: &str, syntax: &SlocSyntax, state: &mut SlocState) -> bool {
+    let mut has_code = false;
+    let mut rest = line;
+    while let Some(c) = rest.chars().next() {
+        match *state {
+            SlocState::BlockComment => match rest.find("*/") {
+                Some(end) => {
+                    *state = SlocState::Code;
+                    rest = &rest[end + 2..];
+                }
+                None => break,
+            },
+            SlocState::Str(string) => {
+                has_code |= !c.is_whitespace();
+                if string.escapes && c == '\\' {
+                    let mut chars = rest.chars();
+                    chars.next();
+                    chars.next();
+                    rest = chars.as_str();
+                } else if let Some(after) = rest.strip_prefix(string.delim) {
+                    *state = SlocState::Code;
+                    rest = after;
+                } else {
+                    rest = &rest[c.len_utf8()..];
+                }
+            }
+            SlocState::Code => {
+                if syntax.line_comments.iter().any(|m| rest.starts_with(m)) {
+                    break;
+                }
+                if syntax.block_comments && rest.starts_with("/*") {
+                    *state = SlocState::BlockComment;
+                    rest = &rest[2..];
+                    continue;
+                }
+                if syntax.rust_chars && c == '\'' {
+                    // A lifetime is code too; only a char literal needs skipping
+                    has_code = true;
+                    rest = &rest[rust_char_literal_len(rest).unwrap_or(1)..];
+                    continue;
+                }
+                if let Some(string) = syntax.strings.iter().find(|s| rest.starts_with(s.delim)) {
+                    has_code = true;
+                    *state = SlocState::Str(*string);
+                    rest = &rest[string.delim.len()..];
+                    continue;
+                }
+                has_code |= !c.is_whitespace()
+                    && !matches!(c, '{' | '}' | '(' | ')' | '[' | ']' | ';' | ',');
+                rest = &rest[c.len_utf8()..];
+            }
+        }
+    }
+    // An unterminated single-line string ends with its line
+    if matches!(*state, SlocState::Str(string) if !string.multiline) {
+        *state = SlocState::Code;
+    }
+    has_code
+}
+
+/// Byte length of the Rust char literal at the start of `rest`, or `None`
+/// when the `'` begins a lifetime
+fn rust_char_literal_len(rest: &str) -> Option<usize> {
+    let body = rest.strip_prefix('\'')?;
+    let mut chars = body.char_indices();
+    let (_, first) = chars.next()?;
+    if first == '\\' {
+        chars.next()?;
+        chars.find(|&(_, c)| c == '\'').map(|(i, _)| i + 2)
+    } else {
+        match chars.next() {
+            Some((i, '\'')) => Some(i + 2),
+            _ => None,
+        }
+    }
+}
+
+/// Count the points where control can leave a function
+///
+/// Uniform across languages: the CFG's exit edges (returns, throws/raise/panic,
+/// fall-through end). Rust's CFG doesn't model `?` or panicking macros as exit
+/// edges (doing so would shift its edge-based CC), so those sites are added
+/// from the AST. Go's CFG collapses nested blocks, so Go takes the larger of
+/// the CFG count and the AST's return/terminating-call sites.
+pub fn return_points(function: &FunctionNode, cfg: &Cfg) -> usize {
+    use crate::language::FunctionBody;
+
+    let cfg_exits = cfg.exit_edge_count();
+    match &function.body {
+        FunctionBody::Rust { source } => match syn::parse_str::<syn::ItemFn>(source) {
+            Ok(item_fn) => cfg_exits + rust_try_and_panic_sites(&item_fn.block),
+            Err(_) => cfg_exits,
+        },
+        FunctionBody::Go { source, .. } => cfg_exits.max(go_exit_sites(function, source)),
+        _ => cfg_exits,
+    }
+}
+
+/// Count `return` statements and terminating calls (`panic`, `os.Exit`,
+/// `log.Fatal*`) in a Go function body, excluding function literals.
+fn go_exit_sites(function: &FunctionNode, source: &str) -> usize {
+    fn count_sites(node: tree_sitter::Node, source: &str, count: &mut usize) {
+        match node.kind() {
+            "func_literal" => return,
+            "return_statement" => *count += 1,
+            "call_expression" => {
+                if let Some(ident) = ts_find_child_by_kind(node, "identifier") {
+                    if &source[ident.start_byte()..ident.end_byte()] == "panic" {
+                        *count += 1;
+                    }
+                } else if let Some(sel) = ts_find_child_by_kind(node, "selector_expression") {
+                    if let Some(field) = ts_find_child_by_kind(sel, "field_identifier") {
+                        let field_name = &source[field.start_byte()..field.end_byte()];
+                        if matches!(field_name, "Exit" | "Fatal" | "Fatalf" | "Fatalln") {
+                            *count += 1;
+                        }
+                    }
+                }
+            }
+            _ => {}
+        }
+        let mut cursor = node.walk();
+        for child in node.children(&mut cursor) {
+            count_sites(child, source, count);
+        }
+    }
+
+    ts_with_function_body(
+        source,
+        tree_sitter_go::LANGUAGE.into(),
+        function.span.start,
+        go::FUNCTION_KINDS,
+        go::BODY_KINDS,
+        |_func_node, body_node| {
+            let mut count = 0;
+            count_sites(body_node, source, &mut count);
+            count
+        },
+    )
+    .unwrap_or(0)
+}
+
+/// Count `.unwrap()`/`.expect(..)` calls and panicking macros in a Rust function
+///
+/// Runtime-failure sites that CC doesn't see. Closures count towards the
+/// enclosing function (a panic there still takes it down); nested items don't.
+/// `None` for other languages.
+pub fn unwrap_count(function: &FunctionNode) -> Option<usize> {
+    use crate::language::FunctionBody;
+    use syn::visit::Visit;
+
+    struct UnwrapVisitor {
+        count: usize,
+    }
+
+    impl<'ast> Visit<'ast> for UnwrapVisitor {
+        fn visit_expr_method_call(&mut self, call: &'ast syn::ExprMethodCall) {
+            if call.method == "unwrap" || call.method == "expect" {
+                self.count += 1;
+            }
+            syn::visit::visit_expr_method_call(self, call);
+        }
+
+        fn visit_macro(&mut self, mac: &'ast syn::Macro) {
+            if let Some(segment) = mac.path.segments.last() {
+                if matches!(
+                    segment.ident.to_string().as_str(),
+                    "panic" | "unreachable" | "unimplemented" | "todo"
+                ) {
+                    self.count += 1;
+                }
+            }
+            for expr in macro_arg_exprs(mac) {
+                self.visit_expr(&expr);
+            }
+        }
+
+        fn visit_item(&mut self, _item: &'ast syn::Item) {}
+    }
+
+    let FunctionBody::Rust { source } = &function.body else {
+        return None;
+    };
+    let item_fn = syn::parse_str::<syn::ItemFn>(source).ok()?;
+    let mut visitor = UnwrapVisitor { count: 0 };
+    visitor.visit_block(&item_fn.block);
+    Some(visitor.count)
+}
+
+/// A macro's arguments parsed as expressions separated by `,` (`println!`,
+/// `assert_eq!`, `format!`) or `;` (`vec![x; n]`). Empty when the tokens are
+/// not expressions (item-defining macros, custom syntax).
+fn macro_arg_exprs(mac: &syn::Macro) -> Vec<syn::Expr> {
+    use syn::punctuated::Punctuated;
+    use syn::{Expr, Token};
+
+    if let Ok(args) = mac.parse_body_with(Punctuated::<Expr, Token![,]>::parse_terminated) {
+        return args.into_iter().collect();
+    }
+    mac.parse_body_with(Punctuated::<Expr, Token![;]>::parse_terminated)
+        .map(|args| args.into_iter().collect())
+        .unwrap_or_default()
+}
+
+/// Count `?` operators and panicking macros (`panic!`, `unreachable!`,
+/// `unimplemented!`, `todo!`) in a Rust block, excluding closures.
+fn rust_try_and_panic_sites(block: &syn::Block) -> usize {
+    use syn::{Expr, Stmt};
+
+    fn stmts_sites(stmts: &[Stmt], count: &mut usize) {
+        for stmt in stmts {
+            match stmt {
+                Stmt::Expr(expr, _) => expr_sites(expr, count),
+                Stmt::Local(local) => {
+                    if let Some(init) = &local.init {
+                        expr_sites(&init.expr, count);
+                        if let Some((_, diverge)) = &init.diverge {
+                            expr_sites(diverge, count);
+                        }
+                    }
+                }
+                Stmt::Macro(stmt_macro) => macro_site(&stmt_macro.mac, count),
+                Stmt::Item(_) => {}
+            }
+        }
+    }
+
+    fn macro_site(mac: &syn::Macro, count: &mut usize) {
+        if let Some(segment) = mac.path.segments.last() {
+            if matches!(
+                segment.ident.to_string().as_str(),
+                "panic" | "unreachable" | "unimplemented" | "todo"
+            ) {
+                *count += 1;
+            }
+        }
+    }
+
+    fn expr_sites(expr: &Expr, count: &mut usize) {
+        match expr {
+            Expr::Try(expr_try) => {
+                *count += 1;
+                expr_sites(&expr_try.expr, count);
+            }
+            Expr::Macro(expr_macro) => macro_site(&expr_macro.mac, count),
+            Expr::MethodCall(call) => {
+                expr_sites(&call.receiver, count);
+                for arg in &call.args {
+                    expr_sites(arg, count);
+                }
+            }
+            Expr::Call(call) => {
+                expr_sites(&call.func, count);
+                for arg in &call.args {
+                    expr_sites(arg, count);
+                }
+            }
+            Expr::Await(expr_await) => expr_sites(&expr_await.base, count),
+            Expr::Field(expr_field) => expr_sites(&expr_field.base, count),
+            Expr::Paren(expr_paren) => expr_sites(&expr_paren.expr, count),
+            Expr::Reference(expr_ref) => expr_sites(&expr_ref.expr, count),
+            Expr::Unary(expr_unary) => expr_sites(&expr_unary.expr, count),
+            Expr::Binary(expr_binary) => {
+                expr_sites(&expr_binary.left, count);
+                expr_sites(&expr_binary.right, count);
+            }
+            Expr::Assign(expr_assign) => expr_sites(&expr_assign.right, count),
+            Expr::Return(expr_return) => {
+                if let Some(e) = &expr_return.expr {
+                    expr_sites(e, count);
+                }
+            }
+            Expr::If(expr_if) => {
+                expr_sites(&expr_if.cond, count);
+                stmts_sites(&expr_if.then_branch.stmts, count);
+                if let Some((_, else_expr)) = &expr_if.else_branch {
+                    expr_sites(else_expr, count);
+                }
+            }
+            Expr::Match(expr_match) => {
+                expr_sites(&expr_match.expr, count);
+                for arm in &expr_match.arms {
+                    expr_sites(&arm.body, count);
+                }
+            }
+            Expr::Loop(expr_loop) => stmts_sites(&expr_loop.body.stmts, count),
+            Expr::While(expr_while) => {
+                expr_sites(&expr_while.cond, count);
+                stmts_sites(&expr_while.body.stmts, count);
+            }
+            Expr::ForLoop(expr_for) => {
+                expr_sites(&expr_for.expr, count);
+                stmts_sites(&expr_for.body.stmts, count);
+            }
+            Expr::Block(expr_block) => stmts_sites(&expr_block.block.stmts, count),
+            Expr::Unsafe(expr_unsafe) => stmts_sites(&expr_unsafe.block.stmts, count),
+            Expr::Let(expr_let) => expr_sites(&expr_let.expr, count),
+            _ => {}
+        }
+    }
+
+    let mut count = 0;
+    stmts_sites(&block.stmts, &mut count);
+    count
+}
+
 /// Calculate LOC from tree-sitter node
 fn calculate_loc_from_node(node: &tree_sitter::Node) -> usize {
     let start_row = node.start_position().row;
@@ -42,9 +482,23 @@ fn calculate_loc_from_node(node: &tree_sitter::Node) -> usize {
 
 /// Extract all metrics for a function
 pub fn extract_metrics(function: &FunctionNode, cfg: &Cfg) -> RawMetrics {
+    extract_metrics_with_decisions(function, cfg, None).0
+}
+
+/// Like [`extract_metrics`], also returning the source line of each CC
+/// increment counted outside the CFG (see [`Cfg::extra_decisions`])
+///
+/// ECMAScript lines are resolved through `source_map`, the map the function
+/// was parsed with; without it they are left out.
+pub(crate) fn extract_metrics_with_decisions(
+    function: &FunctionNode,
+    cfg: &Cfg,
+    source_map: Option<&SourceMap>,
+) -> (RawMetrics, Vec<u32>) {
     use crate::language::FunctionBody;
 
-    match &function.body {
+    let mut decisions = Vec::new();
+    let metrics = match &function.body {
         FunctionBody::ECMAScript(body) => {
             // Calculate LOC from span (end_line - start_line + 1)
             let loc = function
@@ -54,10 +508,16 @@ pub fn extract_metrics(function: &FunctionNode, cfg: &Cfg) -> RawMetrics {
                 + 1;
 
             let callee_names = ecmascript_extract_callees(body);
+            let sites = ecmascript_cc_sites(body);
+            if let Some(source_map) = source_map {
+                let lines = crate::cfg::builder::LineResolver::new(function, source_map);
+                decisions = sites.iter().map(|&pos| lines.line(pos)).collect();
+            }
             RawMetrics {
-                cc: cyclomatic_complexity(cfg, body),
+                cc: calculate_cc_from_cfg(cfg) + sites.len(),
                 nd: nesting_depth(body),
-                fo: callee_names.len(),
+                // Every ECMAScript language records call sites the same way
+                fo: crate::callgraph::fan_out(Language::TypeScript, &callee_names),
                 ns: non_structured_exits(body),
                 loc: loc as usize,
                 callee_names,
@@ -65,23 +525,26 @@ pub fn extract_metrics(function: &FunctionNode, cfg: &Cfg) -> RawMetrics {
         }
         FunctionBody::Go { .. } => {
             // Extract Go-specific metrics from tree-sitter AST
-            extract_go_metrics(function, cfg)
+            extract_go_metrics(function, cfg, &mut decisions)
         }
         FunctionBody::Java { .. } => {
             // Extract Java-specific metrics from tree-sitter AST
-            extract_java_metrics(function, cfg)
+            extract_java_metrics(function, cfg, &mut decisions)
         }
         FunctionBody::Python { .. } => {
             // Extract Python-specific metrics from tree-sitter AST
-            extract_python_metrics(function, cfg)
+            extract_python_metrics(function, cfg, &mut decisions)
         }
         FunctionBody::Rust { .. } => {
             // Extract Rust-specific metrics from syn AST
-            extract_rust_metrics(function, cfg)
+            extract_rust_metrics(function, cfg, &mut decisions)
         }
-        FunctionBody::CSharp { .. } => extract_csharp_metrics(function, cfg),
-        FunctionBody::C { .. } => extract_c_metrics(function, cfg),
-    }
+        FunctionBody::CSharp { .. } => extract_csharp_metrics(function, cfg, &mut decisions),
+        FunctionBody::C { .. } => extract_c_metrics(function, cfg, &mut decisions),
+        // Every HCL decision is a condition node of the CFG already
+        FunctionBody::Hcl { .. } => extract_hcl_metrics(function),
+    };
+    (metrics, decisions)
 }
 
 /// Calculate cyclomatic complexity from CFG alone
@@ -89,7 +552,7 @@ pub fn extract_metrics(function: &FunctionNode, cfg: &Cfg) -> RawMetrics {
 fn calculate_cc_from_cfg(cfg: &Cfg) -> usize {
     // Base formula: CC = E - N + 2
     if cfg.edge_count() > 0 && cfg.node_count() > 2 {
-        let e = cfg.edge_count();
+        let e = cfg.edge_count() - cfg.no_match_edges.len();
         let n = cfg.node_count() - 2; // Exclude entry and exit
         if n > 0 {
             e.saturating_sub(n).saturating_add(2)
@@ -101,99 +564,40 @@ fn calculate_cc_from_cfg(cfg: &Cfg) -> usize {
     }
 }
 
-/// Calculate Cyclomatic Complexity: CC = E - N + 2
+/// Positions of the CC increments in an ECMAScript body beyond CC = E - N + 2
 ///
-/// Additional increments:
-/// - Boolean short-circuit operators (&&, ||)
+/// - Boolean short-circuit operators (&&, ||), at their right operand
 /// - Each switch case
 /// - Each catch clause
-fn cyclomatic_complexity(cfg: &Cfg, body: &BlockStmt) -> usize {
-    // Base formula: CC = E - N + 2
-    let base_cc = if cfg.edge_count() > 0 && cfg.node_count() > 2 {
-        // Exclude entry and exit nodes for calculation
-        // E = number of edges
-        // N = number of nodes (excluding entry/exit which are structural)
-        let e = cfg.edge_count();
-        let n = cfg.node_count() - 2; // Exclude entry and exit
-        if n > 0 {
-            e.saturating_sub(n).saturating_add(2)
-        } else {
-            1 // Minimum CC for any function
-        }
-    } else {
-        1 // Empty function has CC = 1
-    };
-
-    // Increment for boolean short-circuit operators
-    let mut short_circuit_count = 0;
-    let mut visitor = ShortCircuitVisitor {
-        count: &mut short_circuit_count,
-    };
+fn ecmascript_cc_sites(body: &BlockStmt) -> Vec<BytePos> {
+    let mut visitor = CcSiteVisitor { sites: Vec::new() };
     body.visit_with(&mut visitor);
-
-    // Increment for switch cases
-    let switch_case_count = count_switch_cases(body);
-
-    // Increment for catch clauses
-    let catch_count = count_catch_clauses(body);
-
-    base_cc + short_circuit_count + switch_case_count + catch_count
+    visitor.sites.sort_unstable();
+    visitor.sites
 }
 
-/// Visitor to count boolean short-circuit operators
-struct ShortCircuitVisitor<'a> {
-    count: &'a mut usize,
+/// Visitor collecting short-circuit operators, switch cases, and catch clauses
+struct CcSiteVisitor {
+    sites: Vec<BytePos>,
 }
 
-impl Visit for ShortCircuitVisitor<'_> {
+impl Visit for CcSiteVisitor {
     fn visit_bin_expr(&mut self, bin_expr: &BinExpr) {
-        match bin_expr.op {
-            BinaryOp::LogicalAnd | BinaryOp::LogicalOr => {
-                *self.count += 1;
-            }
-            _ => {}
+        if matches!(bin_expr.op, BinaryOp::LogicalAnd | BinaryOp::LogicalOr) {
+            self.sites.push(bin_expr.right.span().lo);
         }
         bin_expr.visit_children_with(self);
     }
-}
 
-/// Count switch cases in the AST
-fn count_switch_cases(body: &BlockStmt) -> usize {
-    let mut count = 0;
-    let mut visitor = SwitchCaseCounter { count: &mut count };
-    body.visit_with(&mut visitor);
-    count
-}
-
-struct SwitchCaseCounter<'a> {
-    count: &'a mut usize,
-}
-
-impl Visit for SwitchCaseCounter<'_> {
     fn visit_switch_stmt(&mut self, switch_stmt: &SwitchStmt) {
-        // Count each case in the switch
-        *self.count += switch_stmt.cases.len();
+        self.sites
+            .extend(switch_stmt.cases.iter().map(|case| case.span.lo));
         switch_stmt.visit_children_with(self);
     }
-}
 
-/// Count catch clauses in the AST
-fn count_catch_clauses(body: &BlockStmt) -> usize {
-    let mut count = 0;
-    let mut visitor = CatchCounter { count: &mut count };
-    body.visit_with(&mut visitor);
-    count
-}
-
-struct CatchCounter<'a> {
-    count: &'a mut usize,
-}
-
-impl Visit for CatchCounter<'_> {
     fn visit_try_stmt(&mut self, try_stmt: &TryStmt) {
-        // Count catch clause if present
-        if try_stmt.handler.is_some() {
-            *self.count += 1;
+        if let Some(handler) = &try_stmt.handler {
+            self.sites.push(handler.span.lo);
         }
         try_stmt.visit_children_with(self);
     }
@@ -245,6 +649,36 @@ impl Visit for NestingDepthVisitor {
     );
 }
 
+/// Deepest nesting of object and array literals in a JS/TS function
+///
+/// `{ a: [1, { b: 2 }] }` is 3 deep. This is data shape, not control flow, so it
+/// is independent of ND; literals inside nested callbacks count towards the
+/// enclosing function, as they do for ND. `None` for other languages and for
+/// functions that build no literal.
+pub fn max_data_nesting(function: &FunctionNode) -> Option<usize> {
+    let crate::language::FunctionBody::ECMAScript(body) = &function.body else {
+        return None;
+    };
+    let mut visitor = DataNestingVisitor {
+        max_depth: 0,
+        current_depth: 0,
+    };
+    body.visit_with(&mut visitor);
+    (visitor.max_depth > 0).then_some(visitor.max_depth)
+}
+
+struct DataNestingVisitor {
+    max_depth: usize,
+    current_depth: usize,
+}
+
+impl Visit for DataNestingVisitor {
+    impl_nesting_visitor!(
+        visit_object_lit, ObjectLit, object_lit;
+        visit_array_lit,  ArrayLit,  array_lit;
+    );
+}
+
 /// Calculate Fan-Out (FO)
 ///
 /// Count number of unique functions called by this function
@@ -298,6 +732,7 @@ fn expr_to_callee_string(expr: &Expr) -> String {
             // The obj might be a call (for chained calls)
             let obj_str = match &*member.obj {
                 Expr::Ident(id) => id.sym.to_string(),
+                Expr::This(_) => "this".to_string(),
                 Expr::Call(call) => {
                     // Chained call - extract the callee of the inner call
                     // This gives us the full chain like "foo().bar" when processing "foo().bar().baz"
@@ -461,7 +896,7 @@ fn ts_non_structured_exits(body_node: &tree_sitter::Node, exit_kinds: &[&str]) -
 /// Parse `source` with `language`, locate the function starting at `start_byte`,
 /// find the first matching body child, and call `f(func_node, body_node)`.
 /// Returns `None` if the function or body cannot be found.
-fn ts_with_function_body<R>(
+pub(crate) fn ts_with_function_body<R>(
     source: &str,
     language: tree_sitter::Language,
     start_byte: usize,
@@ -474,12 +909,8 @@ fn ts_with_function_body<R>(
     let tree = parser.parse(source, None)?;
     let root = tree.root_node();
     let func_node = ts_find_function_by_start(root, start_byte, func_kinds)?;
-    for kind in body_kinds {
-        if let Some(body_node) = ts_find_child_by_kind(func_node, kind) {
-            return Some(f(func_node, body_node));
-        }
-    }
-    None
+    let body_node = crate::language::tree_sitter_utils::find_child_by_kinds(func_node, body_kinds)?;
+    Some(f(func_node, body_node))
 }
 
 // ============================================================================
@@ -487,29 +918,20 @@ fn ts_with_function_body<R>(
 // ============================================================================
 
 /// Extract metrics for Go functions using tree-sitter
-fn extract_go_metrics(function: &FunctionNode, cfg: &Cfg) -> RawMetrics {
+fn extract_go_metrics(function: &FunctionNode, cfg: &Cfg, decisions: &mut Vec<u32>) -> RawMetrics {
     let (_body_node_id, source) = function.body.as_go();
     ts_with_function_body(
         source,
         tree_sitter_go::LANGUAGE.into(),
         function.span.start,
-        &["function_declaration", "method_declaration"],
-        &["block"],
+        go::FUNCTION_KINDS,
+        go::BODY_KINDS,
         |func_node, body_node| {
             let callee_names = go_extract_callees(&body_node, source);
+            *decisions = go_cc_extra_lines(&body_node);
             RawMetrics {
-                cc: calculate_cc_from_cfg(cfg) + go_count_cc_extras(&body_node, source),
-                nd: ts_nesting_depth(
-                    &body_node,
-                    &[
-                        "if_statement",
-                        "for_statement",
-                        "switch_statement",
-                        "expression_switch_statement",
-                        "type_switch_statement",
-                        "select_statement",
-                    ],
-                ),
+                cc: calculate_cc_from_cfg(cfg) + decisions.len(),
+                nd: ts_nesting_depth(&body_node, go::NESTING_KINDS),
                 fo: callee_names.len(),
                 ns: go_non_structured_exits(&body_node, source),
                 loc: calculate_loc_from_node(&func_node),
@@ -614,13 +1036,13 @@ fn go_non_structured_exits(body_node: &tree_sitter::Node, source: &str) -> usize
     count
 }
 
-/// Count additional cyclomatic complexity contributors for Go
-fn go_count_cc_extras(body_node: &tree_sitter::Node, _source: &str) -> usize {
-    fn count_extras(node: tree_sitter::Node, count: &mut usize) {
+/// Source lines of the additional cyclomatic complexity contributors for Go
+fn go_cc_extra_lines(body_node: &tree_sitter::Node) -> Vec<u32> {
+    fn count_extras(node: tree_sitter::Node, lines: &mut Vec<u32>) {
         match node.kind() {
             // Count switch/select cases
             "expression_case" | "default_case" | "communication_case" | "type_case" => {
-                *count += 1;
+                lines.push(node_line(&node));
             }
             // Count boolean operators — check the operator child directly to
             // avoid false positives from nested logical operators in sub-expressions
@@ -628,7 +1050,7 @@ fn go_count_cc_extras(body_node: &tree_sitter::Node, _source: &str) -> usize {
                 let mut cursor = node.walk();
                 for child in node.children(&mut cursor) {
                     if child.kind() == "&&" || child.kind() == "||" {
-                        *count += 1;
+                        lines.push(node_line(&child));
                         break;
                     }
                 }
@@ -639,13 +1061,13 @@ fn go_count_cc_extras(body_node: &tree_sitter::Node, _source: &str) -> usize {
         // Recurse into children
         let mut cursor = node.walk();
         for child in node.children(&mut cursor) {
-            count_extras(child, count);
+            count_extras(child, lines);
         }
     }
 
-    let mut count = 0;
-    count_extras(*body_node, &mut count);
-    count
+    let mut lines = Vec::new();
+    count_extras(*body_node, &mut lines);
+    lines
 }
 
 // Note: Go metrics tests are integrated with cfg_builder tests
@@ -655,42 +1077,26 @@ fn go_count_cc_extras(body_node: &tree_sitter::Node, _source: &str) -> usize {
 // ============================================================================
 
 /// Extract metrics for Java functions using tree-sitter
-fn extract_java_metrics(function: &FunctionNode, cfg: &Cfg) -> RawMetrics {
+fn extract_java_metrics(
+    function: &FunctionNode,
+    cfg: &Cfg,
+    decisions: &mut Vec<u32>,
+) -> RawMetrics {
     let (_body_node_id, source) = function.body.as_java();
     ts_with_function_body(
         source,
         tree_sitter_java::LANGUAGE.into(),
         function.span.start,
-        &["method_declaration", "constructor_declaration"],
-        &["block", "constructor_body"],
+        java::FUNCTION_KINDS,
+        java::BODY_KINDS,
         |func_node, body_node| {
             let callee_names = java_extract_callees(&body_node, source);
+            *decisions = java_cc_extra_lines(&body_node);
             RawMetrics {
-                cc: calculate_cc_from_cfg(cfg) + java_count_cc_extras(&body_node, source),
-                nd: ts_nesting_depth(
-                    &body_node,
-                    &[
-                        "if_statement",
-                        "while_statement",
-                        "do_statement",
-                        "for_statement",
-                        "enhanced_for_statement",
-                        "switch_statement",
-                        "switch_expression",
-                        "try_statement",
-                        "synchronized_statement",
-                    ],
-                ),
+                cc: calculate_cc_from_cfg(cfg) + decisions.len(),
+                nd: ts_nesting_depth(&body_node, java::NESTING_KINDS),
                 fo: callee_names.len(),
-                ns: ts_non_structured_exits(
-                    &body_node,
-                    &[
-                        "return_statement",
-                        "throw_statement",
-                        "break_statement",
-                        "continue_statement",
-                    ],
-                ),
+                ns: ts_non_structured_exits(&body_node, java::EXIT_KINDS),
                 loc: calculate_loc_from_node(&func_node),
                 callee_names,
             }
@@ -731,14 +1137,14 @@ fn java_extract_callees(body_node: &tree_sitter::Node, source: &str) -> Vec<Stri
     result
 }
 
-/// Count additional CC contributors in Java
+/// Source lines of the additional CC contributors in Java
 /// (ternary expressions, boolean operators)
-fn java_count_cc_extras(body_node: &tree_sitter::Node, _source: &str) -> usize {
-    fn count_extras(node: tree_sitter::Node, count: &mut usize) {
+fn java_cc_extra_lines(body_node: &tree_sitter::Node) -> Vec<u32> {
+    fn count_extras(node: tree_sitter::Node, lines: &mut Vec<u32>) {
         match node.kind() {
             // Ternary expressions (conditional_expression) add to CC
             "ternary_expression" => {
-                *count += 1;
+                lines.push(node_line(&node));
             }
             // Binary expressions with && or || add to CC — check the operator
             // child directly to avoid false positives from nested sub-expressions
@@ -746,7 +1152,7 @@ fn java_count_cc_extras(body_node: &tree_sitter::Node, _source: &str) -> usize {
                 let mut cursor = node.walk();
                 for child in node.children(&mut cursor) {
                     if child.kind() == "&&" || child.kind() == "||" {
-                        *count += 1;
+                        lines.push(node_line(&child));
                         break;
                     }
                 }
@@ -757,13 +1163,13 @@ fn java_count_cc_extras(body_node: &tree_sitter::Node, _source: &str) -> usize {
         // Recursively check children
         let mut cursor = node.walk();
         for child in node.children(&mut cursor) {
-            count_extras(child, count);
+            count_extras(child, lines);
         }
     }
 
-    let mut count = 0;
-    count_extras(*body_node, &mut count);
-    count
+    let mut lines = Vec::new();
+    count_extras(*body_node, &mut lines);
+    lines
 }
 
 // ============================================================================
@@ -771,39 +1177,29 @@ fn java_count_cc_extras(body_node: &tree_sitter::Node, _source: &str) -> usize {
 // ============================================================================
 
 /// Extract metrics for Python functions using tree-sitter
-fn extract_python_metrics(function: &FunctionNode, cfg: &Cfg) -> RawMetrics {
+fn extract_python_metrics(
+    function: &FunctionNode,
+    cfg: &Cfg,
+    decisions: &mut Vec<u32>,
+) -> RawMetrics {
     let (_body_node_id, source) = function.body.as_python();
+    if function.is_top_level() {
+        return python_top_level_metrics(source, cfg, decisions);
+    }
     ts_with_function_body(
         source,
         tree_sitter_python::LANGUAGE.into(),
         function.span.start,
-        &["function_definition", "async_function_definition"],
-        &["block"],
+        python::FUNCTION_KINDS,
+        python::BODY_KINDS,
         |func_node, body_node| {
             let callee_names = python_extract_callees(&body_node, source);
+            *decisions = python_cc_extra_lines(&body_node);
             RawMetrics {
-                cc: calculate_cc_from_cfg(cfg) + python_count_cc_extras(&body_node, source),
-                nd: ts_nesting_depth(
-                    &body_node,
-                    &[
-                        "if_statement",
-                        "while_statement",
-                        "for_statement",
-                        "try_statement",
-                        "with_statement",
-                        "match_statement",
-                    ],
-                ),
+                cc: calculate_cc_from_cfg(cfg) + decisions.len(),
+                nd: ts_nesting_depth(&body_node, python::NESTING_KINDS),
                 fo: callee_names.len(),
-                ns: ts_non_structured_exits(
-                    &body_node,
-                    &[
-                        "return_statement",
-                        "raise_statement",
-                        "break_statement",
-                        "continue_statement",
-                    ],
-                ),
+                ns: ts_non_structured_exits(&body_node, python::EXIT_KINDS),
                 loc: calculate_loc_from_node(&func_node),
                 callee_names,
             }
@@ -819,6 +1215,52 @@ fn extract_python_metrics(function: &FunctionNode, cfg: &Cfg) -> RawMetrics {
     })
 }
 
+/// Metrics for a Python module's top-level statements, skipping the definitions
+/// and imports left out of its CFG
+fn python_top_level_metrics(source: &str, cfg: &Cfg, decisions: &mut Vec<u32>) -> RawMetrics {
+    use crate::language::python::parser::PYTHON_NON_CODE_KINDS;
+
+    let mut parser = tree_sitter::Parser::new();
+    let tree = parser
+        .set_language(&tree_sitter_python::LANGUAGE.into())
+        .ok()
+        .and_then(|()| parser.parse(source, None));
+    let Some(tree) = tree else {
+        return RawMetrics {
+            cc: 1,
+            nd: 0,
+            fo: 0,
+            ns: 0,
+            loc: 0,
+            callee_names: vec![],
+        };
+    };
+    let root = tree.root_node();
+    let cc = calculate_cc_from_cfg(cfg);
+    let mut nd = 0;
+    let mut ns = 0;
+    let mut callees = std::collections::BTreeSet::new();
+    let mut cursor = root.walk();
+    for stmt in root
+        .named_children(&mut cursor)
+        .filter(|n| !PYTHON_NON_CODE_KINDS.contains(&n.kind()))
+    {
+        decisions.extend(python_cc_extra_lines(&stmt));
+        nd = nd.max(ts_nesting_depth(&stmt, python::NESTING_KINDS));
+        ns += ts_non_structured_exits(&stmt, python::EXIT_KINDS);
+        callees.extend(python_extract_callees(&stmt, source));
+    }
+    let callee_names: Vec<String> = callees.into_iter().collect();
+    RawMetrics {
+        cc: cc + decisions.len(),
+        nd,
+        fo: callee_names.len(),
+        ns,
+        loc: calculate_loc_from_node(&root),
+        callee_names,
+    }
+}
+
 /// Extract callee names from a Python function body.
 /// Returns the unique set of call target strings.
 fn python_extract_callees(body_node: &tree_sitter::Node, source: &str) -> Vec<String> {
@@ -847,18 +1289,19 @@ fn python_extract_callees(body_node: &tree_sitter::Node, source: &str) -> Vec<St
     result
 }
 
-/// Count additional CC contributors in Python
+/// Source lines of the additional CC contributors in Python
 /// (comprehensions with if-filters, boolean operators, ternary expressions)
-fn python_count_cc_extras(body_node: &tree_sitter::Node, _source: &str) -> usize {
-    fn count_extras(node: tree_sitter::Node, count: &mut usize) {
+fn python_cc_extra_lines(body_node: &tree_sitter::Node) -> Vec<u32> {
+    fn count_extras(node: tree_sitter::Node, lines: &mut Vec<u32>) {
         match node.kind() {
-            // Boolean operators (and, or) add to CC
+            // Boolean operators (and, or) add to CC, on the operator's line
             "boolean_operator" => {
-                *count += 1;
+                let operator = node.child_by_field_name("operator").unwrap_or(node);
+                lines.push(node_line(&operator));
             }
             // Ternary expressions add to CC
             "conditional_expression" => {
-                *count += 1;
+                lines.push(node_line(&node));
             }
             // Comprehensions with if-filters add to CC
             "list_comprehension"
@@ -869,7 +1312,7 @@ fn python_count_cc_extras(body_node: &tree_sitter::Node, _source: &str) -> usize
                 let mut cursor = node.walk();
                 for child in node.children(&mut cursor) {
                     if child.kind() == "if_clause" {
-                        *count += 1;
+                        lines.push(node_line(&child));
                         break;
                     }
                 }
@@ -880,13 +1323,13 @@ fn python_count_cc_extras(body_node: &tree_sitter::Node, _source: &str) -> usize
         // Recursively check children
         let mut cursor = node.walk();
         for child in node.children(&mut cursor) {
-            count_extras(child, count);
+            count_extras(child, lines);
         }
     }
 
-    let mut count = 0;
-    count_extras(*body_node, &mut count);
-    count
+    let mut lines = Vec::new();
+    count_extras(*body_node, &mut lines);
+    lines
 }
 
 // Note: Python metrics tests are integrated with cfg_builder tests
@@ -896,46 +1339,26 @@ fn python_count_cc_extras(body_node: &tree_sitter::Node, _source: &str) -> usize
 // ============================================================================
 
 /// Extract metrics for C# functions using tree-sitter
-fn extract_csharp_metrics(function: &FunctionNode, cfg: &Cfg) -> RawMetrics {
+fn extract_csharp_metrics(
+    function: &FunctionNode,
+    cfg: &Cfg,
+    decisions: &mut Vec<u32>,
+) -> RawMetrics {
     let (_body_node_id, source) = function.body.as_csharp();
     ts_with_function_body(
         source,
         tree_sitter_c_sharp::LANGUAGE.into(),
         function.span.start,
-        &[
-            "method_declaration",
-            "constructor_declaration",
-            "local_function_statement",
-            "operator_declaration",
-            "conversion_operator_declaration",
-        ],
-        &["block"],
+        csharp::FUNCTION_KINDS,
+        csharp::BODY_KINDS,
         |func_node, body_node| {
             let callee_names = csharp_extract_callees(&body_node, source);
+            *decisions = csharp_cc_extra_lines(&body_node);
             RawMetrics {
-                cc: calculate_cc_from_cfg(cfg) + csharp_count_cc_extras(&body_node, source),
-                nd: ts_nesting_depth(
-                    &body_node,
-                    &[
-                        "if_statement",
-                        "while_statement",
-                        "do_statement",
-                        "for_statement",
-                        "foreach_statement",
-                        "switch_statement",
-                        "try_statement",
-                    ],
-                ),
+                cc: calculate_cc_from_cfg(cfg) + decisions.len(),
+                nd: ts_nesting_depth(&body_node, csharp::NESTING_KINDS),
                 fo: callee_names.len(),
-                ns: ts_non_structured_exits(
-                    &body_node,
-                    &[
-                        "return_statement",
-                        "throw_statement",
-                        "break_statement",
-                        "continue_statement",
-                    ],
-                ),
+                ns: ts_non_structured_exits(&body_node, csharp::EXIT_KINDS),
                 loc: calculate_loc_from_node(&func_node),
                 callee_names,
             }
@@ -955,38 +1378,22 @@ fn extract_csharp_metrics(function: &FunctionNode, cfg: &Cfg) -> RawMetrics {
 // C Metrics Implementation
 // ============================================================================
 
-fn extract_c_metrics(function: &FunctionNode, cfg: &Cfg) -> RawMetrics {
+fn extract_c_metrics(function: &FunctionNode, cfg: &Cfg, decisions: &mut Vec<u32>) -> RawMetrics {
     let (_body_node_id, source) = function.body.as_c();
     ts_with_function_body(
         source,
         tree_sitter_c::LANGUAGE.into(),
         function.span.start,
-        &["function_definition"],
-        &["compound_statement"],
+        c::FUNCTION_KINDS,
+        c::BODY_KINDS,
         |func_node, body_node| {
             let callee_names = c_extract_callees(&body_node, source);
+            *decisions = c_cc_extra_lines(&body_node);
             RawMetrics {
-                cc: calculate_cc_from_cfg(cfg) + c_count_cc_extras(&body_node),
-                nd: ts_nesting_depth(
-                    &body_node,
-                    &[
-                        "if_statement",
-                        "while_statement",
-                        "do_statement",
-                        "for_statement",
-                        "switch_statement",
-                    ],
-                ),
+                cc: calculate_cc_from_cfg(cfg) + decisions.len(),
+                nd: ts_nesting_depth(&body_node, c::NESTING_KINDS),
                 fo: callee_names.len(),
-                ns: ts_non_structured_exits(
-                    &body_node,
-                    &[
-                        "return_statement",
-                        "break_statement",
-                        "continue_statement",
-                        "goto_statement",
-                    ],
-                ),
+                ns: ts_non_structured_exits(&body_node, c::EXIT_KINDS),
                 loc: calculate_loc_from_node(&func_node),
                 callee_names,
             }
@@ -1033,18 +1440,19 @@ fn c_extract_callees(body_node: &tree_sitter::Node, source: &str) -> Vec<String>
     result
 }
 
-/// Count additional CC contributors in C (ternary expressions, boolean short-circuit operators).
-fn c_count_cc_extras(body_node: &tree_sitter::Node) -> usize {
-    fn count_extras(node: tree_sitter::Node, count: &mut usize) {
+/// Source lines of the additional CC contributors in C (ternary expressions,
+/// boolean short-circuit operators).
+fn c_cc_extra_lines(body_node: &tree_sitter::Node) -> Vec<u32> {
+    fn count_extras(node: tree_sitter::Node, lines: &mut Vec<u32>) {
         match node.kind() {
             "conditional_expression" => {
-                *count += 1;
+                lines.push(node_line(&node));
             }
             "binary_expression" => {
                 let mut cursor = node.walk();
                 for child in node.children(&mut cursor) {
                     if child.kind() == "&&" || child.kind() == "||" {
-                        *count += 1;
+                        lines.push(node_line(&child));
                         break;
                     }
                 }
@@ -1053,12 +1461,12 @@ fn c_count_cc_extras(body_node: &tree_sitter::Node) -> usize {
         }
         let mut cursor = node.walk();
         for child in node.children(&mut cursor) {
-            count_extras(child, count);
+            count_extras(child, lines);
         }
     }
-    let mut count = 0;
-    count_extras(*body_node, &mut count);
-    count
+    let mut lines = Vec::new();
+    count_extras(*body_node, &mut lines);
+    lines
 }
 
 /// Extract callee names from a C# function body.
@@ -1085,18 +1493,19 @@ fn csharp_extract_callees(body_node: &tree_sitter::Node, source: &str) -> Vec<St
     result
 }
 
-/// Count additional CC contributors in C# (ternary, boolean operators, null-coalescing)
-fn csharp_count_cc_extras(body_node: &tree_sitter::Node, _source: &str) -> usize {
-    fn count_extras(node: tree_sitter::Node, count: &mut usize) {
+/// Source lines of the additional CC contributors in C# (ternary, boolean
+/// operators, null-coalescing)
+fn csharp_cc_extra_lines(body_node: &tree_sitter::Node) -> Vec<u32> {
+    fn count_extras(node: tree_sitter::Node, lines: &mut Vec<u32>) {
         match node.kind() {
             "conditional_expression" => {
-                *count += 1;
+                lines.push(node_line(&node));
             }
             "binary_expression" => {
                 let mut cursor = node.walk();
                 for child in node.children(&mut cursor) {
                     if child.kind() == "&&" || child.kind() == "||" || child.kind() == "??" {
-                        *count += 1;
+                        lines.push(node_line(&child));
                         break;
                     }
                 }
@@ -1105,13 +1514,13 @@ fn csharp_count_cc_extras(body_node: &tree_sitter::Node, _source: &str) -> usize
         }
         let mut cursor = node.walk();
         for child in node.children(&mut cursor) {
-            count_extras(child, count);
+            count_extras(child, lines);
         }
     }
 
-    let mut count = 0;
-    count_extras(*body_node, &mut count);
-    count
+    let mut lines = Vec::new();
+    count_extras(*body_node, &mut lines);
+    lines
 }
 
 // ========================================
@@ -1119,7 +1528,11 @@ fn csharp_count_cc_extras(body_node: &tree_sitter::Node, _source: &str) -> usize
 // ========================================
 
 /// Extract metrics for a Rust function
-fn extract_rust_metrics(function: &FunctionNode, cfg: &Cfg) -> RawMetrics {
+fn extract_rust_metrics(
+    function: &FunctionNode,
+    cfg: &Cfg,
+    decisions: &mut Vec<u32>,
+) -> RawMetrics {
     let source = function.body.as_rust();
 
     // Parse the function source
@@ -1139,21 +1552,59 @@ fn extract_rust_metrics(function: &FunctionNode, cfg: &Cfg) -> RawMetrics {
     };
 
     let base_cc = calculate_cc_from_cfg(cfg);
-    let extra_cc = rust_count_cc_extras(&item_fn.block);
+    // syn lines are relative to the function source; shift them to file lines
+    let offset = function.span.start_line.saturating_sub(1);
+    *decisions = rust_cc_extra_lines(&item_fn.block)
+        .into_iter()
+        .map(|line| line + offset)
+        .collect();
     let nd = rust_nesting_depth(&item_fn.block);
     let callee_names = rust_extract_callees(&item_fn.block);
     let ns = rust_non_structured_exits(&item_fn.block);
 
     RawMetrics {
-        cc: base_cc + extra_cc,
+        cc: base_cc + decisions.len(),
         nd,
-        fo: callee_names.len(),
+        fo: crate::callgraph::fan_out(Language::Rust, &callee_names),
         ns,
         loc: calculate_loc(source),
         callee_names,
     }
 }
 
+/// Extract metrics for a Terraform/HCL block
+///
+/// CC counts the block's decisions directly (`count`/`for_each`, `dynamic`
+/// blocks, conditionals) rather than deriving them from the decision-chain CFG.
+/// ND is block nesting, FO the distinct module/resource/data references, and
+/// NS the number of nested blocks.
+fn extract_hcl_metrics(function: &FunctionNode) -> RawMetrics {
+    use crate::language::hcl::block::{block_facts, parse_block};
+
+    let source = function.body.as_hcl();
+    let Some(block) = parse_block(source) else {
+        return RawMetrics {
+            cc: 1,
+            nd: 0,
+            fo: 0,
+            ns: 0,
+            loc: calculate_loc(source),
+            callee_names: vec![],
+        };
+    };
+
+    let facts = block_facts(&block);
+    let callee_names = facts.callee_names();
+    RawMetrics {
+        cc: 1 + facts.decisions.len(),
+        nd: facts.max_depth,
+        fo: callee_names.len(),
+        ns: facts.blocks.len(),
+        loc: calculate_loc(source),
+        callee_names,
+    }
+}
+
 /// Calculate nesting depth for Rust function
 fn rust_nesting_depth(block: &syn::Block) -> usize {
     use syn::{Expr, Stmt};
@@ -1253,18 +1704,27 @@ fn rust_extract_callees(block: &syn::Block) -> Vec<String> {
         match expr {
             Expr::Call(ExprCall { func, .. }) => {
                 // Extract function name from path
+                // Keep the owning type for associated calls (`Type::new`) so the
+                // call graph can tell same-named functions on different types apart
                 if let Expr::Path(expr_path) = &**func {
-                    let func_name = expr_path
-                        .path
-                        .segments
-                        .last()
-                        .map(|seg| seg.ident.to_string())
-                        .unwrap_or_else(|| "fn".to_string());
+                    let segments = &expr_path.path.segments;
+                    let func_name = match segments.len() {
+                        0 => "fn".to_string(),
+                        1 => segments[0].ident.to_string(),
+                        n => format!("{}::{}", segments[n - 2].ident, segments[n - 1].ident),
+                    };
                     calls.insert(func_name);
                 }
             }
-            Expr::MethodCall(ExprMethodCall { method, .. }) => {
-                calls.insert(method.to_string());
+            Expr::MethodCall(ExprMethodCall {
+                receiver, method, ..
+            }) => {
+                let is_self = matches!(&**receiver, Expr::Path(p) if p.path.is_ident("self"));
+                if is_self {
+                    calls.insert(format!("self.{}", method));
+                } else {
+                    calls.insert(method.to_string());
+                }
             }
             Expr::Macro(expr_macro) => {
                 let macro_name = expr_macro
@@ -1402,17 +1862,19 @@ fn rust_non_structured_exits(block: &syn::Block) -> usize {
     count
 }
 
-/// Count CC extras for Rust (match arms, boolean operators)
-fn rust_count_cc_extras(block: &syn::Block) -> usize {
+/// Source lines (within the parsed function source) of the CC extras for
+/// Rust (match arms, boolean operators)
+fn rust_cc_extra_lines(block: &syn::Block) -> Vec<u32> {
+    use syn::spanned::Spanned;
     use syn::{BinOp, Expr, Stmt};
 
-    fn count_extras(stmts: &[Stmt], count: &mut usize) {
+    fn count_extras(stmts: &[Stmt], lines: &mut Vec<u32>) {
         for stmt in stmts {
             match stmt {
-                Stmt::Expr(expr, _) => expr_extras(expr, count),
+                Stmt::Expr(expr, _) => expr_extras(expr, lines),
                 Stmt::Local(local) => {
                     if let Some(init) = &local.init {
-                        expr_extras(&init.expr, count);
+                        expr_extras(&init.expr, lines);
                     }
                 }
                 _ => {}
@@ -1420,52 +1882,57 @@ fn rust_count_cc_extras(block: &syn::Block) -> usize {
         }
     }
 
-    fn expr_extras(expr: &Expr, count: &mut usize) {
+    fn expr_extras(expr: &Expr, lines: &mut Vec<u32>) {
         match expr {
             Expr::Match(expr_match) => {
                 // Each match arm is a decision point
-                *count += expr_match.arms.len();
-                expr_extras(&expr_match.expr, count);
+                lines.extend(
+                    expr_match
+                        .arms
+                        .iter()
+                        .map(|arm| arm.pat.span().start().line as u32),
+                );
+                expr_extras(&expr_match.expr, lines);
                 for arm in &expr_match.arms {
-                    expr_extras(&arm.body, count);
+                    expr_extras(&arm.body, lines);
                 }
             }
             Expr::Binary(expr_binary) => {
                 // Boolean operators
                 if matches!(expr_binary.op, BinOp::And(_) | BinOp::Or(_)) {
-                    *count += 1;
+                    lines.push(expr_binary.op.span().start().line as u32);
                 }
-                expr_extras(&expr_binary.left, count);
-                expr_extras(&expr_binary.right, count);
+                expr_extras(&expr_binary.left, lines);
+                expr_extras(&expr_binary.right, lines);
             }
             Expr::If(expr_if) => {
-                expr_extras(&expr_if.cond, count);
-                count_extras(&expr_if.then_branch.stmts, count);
+                expr_extras(&expr_if.cond, lines);
+                count_extras(&expr_if.then_branch.stmts, lines);
                 if let Some((_, else_expr)) = &expr_if.else_branch {
-                    expr_extras(else_expr, count);
+                    expr_extras(else_expr, lines);
                 }
             }
             Expr::Loop(expr_loop) => {
-                count_extras(&expr_loop.body.stmts, count);
+                count_extras(&expr_loop.body.stmts, lines);
             }
             Expr::While(expr_while) => {
-                expr_extras(&expr_while.cond, count);
-                count_extras(&expr_while.body.stmts, count);
+                expr_extras(&expr_while.cond, lines);
+                count_extras(&expr_while.body.stmts, lines);
             }
             Expr::ForLoop(expr_for) => {
-                expr_extras(&expr_for.expr, count);
-                count_extras(&expr_for.body.stmts, count);
+                expr_extras(&expr_for.expr, lines);
+                count_extras(&expr_for.body.stmts, lines);
             }
             Expr::Block(expr_block) => {
-                count_extras(&expr_block.block.stmts, count);
+                count_extras(&expr_block.block.stmts, lines);
             }
             _ => {}
         }
     }
 
-    let mut count = 0;
-    count_extras(&block.stmts, &mut count);
-    count
+    let mut lines = Vec::new();
+    count_extras(&block.stmts, &mut lines);
+    lines
 }
 
 #[cfg(test)]
@@ -1512,6 +1979,62 @@ mod tests {
         (func, cfg)
     }
 
+    #[test]
+    fn test_sloc_excludes_blank_comment_and_brace_lines() {
+        let src = "function f(x) {\n\n  // note\n  /* block\n     comment */\n  if (x) {\n    return 1; // trailing\n  }\n  return 0;\n}";
+        let lines: Vec<&str> = src.lines().collect();
+        assert_eq!(lines.len(), 10);
+        assert_eq!(
+            calculate_sloc(&lines, crate::language::Language::TypeScript),
+            4
+        );
+    }
+
+    #[test]
+    fn test_sloc_python_hash_comments() {
+        let src = "def f(x):\n    # comment\n\n    return [\n        x,\n    ]";
+        let lines: Vec<&str> = src.lines().collect();
+        assert_eq!(calculate_sloc(&lines, crate::language::Language::Python), 3);
+    }
+
+    #[test]
+    fn test_sloc_hcl_hash_and_slash_comments() {
+        let src = "locals {\n  # comment\n  // comment\n  name = \"web\" # trailing\n}";
+        let lines: Vec<&str> = src.lines().collect();
+        assert_eq!(calculate_sloc(&lines, crate::language::Language::Hcl), 2);
+    }
+
+    #[test]
+    fn test_sloc_block_comment_opened_after_code() {
+        let src = "let a = 1; /* starts here\n   still a comment\n*/\nlet s = \"// not a comment\";\nconst t = `\n  /* text */\n`;";
+        let lines: Vec<&str> = src.lines().collect();
+        assert_eq!(
+            calculate_sloc(&lines, crate::language::Language::TypeScript),
+            5
+        );
+    }
+
+    #[test]
+    fn test_sloc_python_hash_inside_strings() {
+        let src = "def f():\n    s = \"\"\"\n# heading, not a comment\n\"\"\"\n    t = '#' # real comment\n    # only a comment";
+        let lines: Vec<&str> = src.lines().collect();
+        assert_eq!(calculate_sloc(&lines, crate::language::Language::Python), 5);
+    }
+
+    #[test]
+    fn test_sloc_hcl_block_comment_and_quoted_hash() {
+        let src = "locals {\n  /* disabled\n  name = \"web\"\n  */\n  url = \"http://x/#y\"\n}";
+        let lines: Vec<&str> = src.lines().collect();
+        assert_eq!(calculate_sloc(&lines, crate::language::Language::Hcl), 2);
+    }
+
+    #[test]
+    fn test_sloc_rust_lifetimes_and_char_literals() {
+        let src = "fn f<'a>(s: &'a str) -> char {\n    let q = '\"'; // quote\n    /* c */ q\n}";
+        let lines: Vec<&str> = src.lines().collect();
+        assert_eq!(calculate_sloc(&lines, crate::language::Language::Rust), 3);
+    }
+
     /// Helper: parse TypeScript source, discover functions, return (FunctionNode, Cfg) for the first.
     fn ecmascript_function_and_cfg(source: &str) -> (crate::ast::FunctionNode, crate::cfg::Cfg) {
         use swc_common::{sync::Lrc, SourceMap};
@@ -1538,7 +2061,8 @@ mod tests {
                 local_index: 0,
             },
             name: Some("test".to_string()),
-            span: SourceSpan::new(0, source.len(), 1, 1, 0),
+            kind: crate::ast::FunctionKind::Function,
+            span: SourceSpan::new(0, source.len(), 1, 1, 0, 0),
             body: FunctionBody::Rust {
                 source: source.to_string(),
             },
@@ -1637,7 +2161,8 @@ func withDefer() {
                 local_index: 0,
             },
             name: Some("bad".to_string()),
-            span: SourceSpan::new(0, 0, 1, 1, 0),
+            kind: crate::ast::FunctionKind::Function,
+            span: SourceSpan::new(0, 0, 1, 1, 0, 0),
             body: FunctionBody::Go {
                 body_node: 0,
                 source: String::new(),
@@ -1717,7 +2242,8 @@ func withDefer() {
                 local_index: 0,
             },
             name: Some("bad".to_string()),
-            span: SourceSpan::new(0, 0, 1, 1, 0),
+            kind: crate::ast::FunctionKind::Function,
+            span: SourceSpan::new(0, 0, 1, 1, 0, 0),
             body: FunctionBody::Java {
                 body_node: 0,
                 source: String::new(),
@@ -1786,6 +2312,16 @@ func withDefer() {
         assert_eq!(m.fo, m.callee_names.len(), "fo == callee_names.len()");
     }
 
+    #[test]
+    fn test_python_cc_extras_carry_their_lines() {
+        let source = "def f(a, b):\n    x = (a\n         or b)\n    return 1 if x else 2\n";
+        let (func, cfg) = python_function_and_cfg(source);
+        let (m, lines) = extract_metrics_with_decisions(&func, &cfg, None);
+        // `or` on line 3, the conditional expression on line 4
+        assert_eq!(lines, vec![3, 4]);
+        assert_eq!(m.cc, calculate_cc_from_cfg(&cfg) + 2);
+    }
+
     #[test]
     fn test_extract_python_fallback_on_bad_source() {
         use crate::ast::FunctionId;
@@ -1796,7 +2332,8 @@ func withDefer() {
                 local_index: 0,
             },
             name: Some("bad".to_string()),
-            span: SourceSpan::new(0, 0, 1, 1, 0),
+            kind: crate::ast::FunctionKind::Function,
+            span: SourceSpan::new(0, 0, 1, 1, 0, 0),
             body: FunctionBody::Python {
                 body_node: 0,
                 source: String::new(),
@@ -1840,6 +2377,17 @@ func withDefer() {
         assert_eq!(m.fo, m.callee_names.len());
     }
 
+    #[test]
+    fn test_extract_ecmascript_this_call_keeps_receiver() {
+        let source = r#"function run() { this.helper(); this.store.save(); }"#;
+        let (func, cfg) = ecmascript_function_and_cfg(source);
+        let m = extract_metrics(&func, &cfg);
+        // A call on a `this` property has no usable receiver and stays out
+        assert_eq!(m.callee_names, vec!["this.helper"]);
+        // Calls on `this` were never part of fan-out
+        assert_eq!(m.fo, 0);
+    }
+
     #[test]
     fn test_extract_ecmascript_no_calls() {
         let source = r#"function pure(x: number) { return x + 1; }"#;
@@ -1896,6 +2444,19 @@ func withDefer() {
         assert_eq!(m.fo, m.callee_names.len());
     }
 
+    #[test]
+    fn test_extract_rust_callee_names_keep_self_and_type() {
+        let source = r#"fn build(&self) { let s = Shape::new(); Circle::new(); self.validate(); s.area(); }"#;
+        let (func, cfg) = rust_function_and_cfg(source);
+        let m = extract_metrics(&func, &cfg);
+        assert_eq!(
+            m.callee_names,
+            vec!["Circle::new", "Shape::new", "area", "self.validate"]
+        );
+        // FO counts bare names, as before receivers were kept: `new` once
+        assert_eq!(m.fo, 3);
+    }
+
     #[test]
     fn test_extract_rust_callee_names_macro_call() {
         let source = r#"fn greet() { println!("hello"); }"#;
@@ -1927,4 +2488,58 @@ func withDefer() {
         assert_eq!(m.fo, 2, "deduplicated: foo+bar = 2");
         assert_eq!(m.callee_names, vec!["bar", "foo"], "sorted callee_names");
     }
+
+    #[test]
+    fn test_return_points_rust_counts_try_and_panics() {
+        let source = r#"fn load(p: &str) -> Result<u32, E> {
+    let s = read(p)?;
+    if s.is_empty() { return Ok(0); }
+    if s == "x" { unreachable!(); }
+    Ok(parse(&s)?)
+}"#;
+        let (func, cfg) = rust_function_and_cfg(source);
+        // early return + tail expression from the CFG, plus two `?` and one unreachable!
+        assert_eq!(return_points(&func, &cfg), 5);
+    }
+
+    #[test]
+    fn test_unwrap_count_rust_counts_unwrap_expect_and_panics() {
+        let source = r#"fn load(p: &str) -> u32 {
+    let s = read(p).unwrap();
+    let n = s.parse::<u32>().expect("number");
+    let v: Vec<u32> = s.lines().map(|l| l.parse().unwrap()).collect();
+    if v.is_empty() { panic!("empty"); }
+    fn nested() { None::<u8>.unwrap(); }
+    n + v[0].checked_add(1).unwrap_or(0)
+}"#;
+        let (func, _cfg) = rust_function_and_cfg(source);
+        // unwrap, expect, unwrap in the closure, panic!; nested fn and unwrap_or excluded
+        assert_eq!(unwrap_count(&func), Some(4));
+
+        let source = r#"fn show(a: Option<u8>, b: Option<u8>) {
+    println!("{} {}", a.unwrap(), b.expect("b"));
+    assert_eq!(a.unwrap(), 1, "{}", format!("{}", b.unwrap()));
+    let v = vec![a.unwrap(); 3];
+}"#;
+        let (func, _cfg) = rust_function_and_cfg(source);
+        // Macro arguments are read as expressions, including nested macros
+        assert_eq!(unwrap_count(&func), Some(5));
+
+        let (func, _cfg) = python_function_and_cfg("def f(x):\n    return x\n");
+        assert_eq!(unwrap_count(&func), None);
+    }
+
+    #[test]
+    fn test_return_points_go_counts_nested_returns() {
+        let source = "package main\n\nfunc f(x int) int {\n\tif x > 0 {\n\t\treturn 1\n\t}\n\tif x < 0 {\n\t\tpanic(\"neg\")\n\t}\n\treturn 0\n}\n";
+        let (func, cfg) = go_function_and_cfg(source);
+        assert_eq!(return_points(&func, &cfg), 3);
+    }
+
+    #[test]
+    fn test_return_points_python_single_exit() {
+        let source = "def f(x):\n    y = x + 1\n    return y\n";
+        let (func, cfg) = python_function_and_cfg(source);
+        assert_eq!(return_points(&func, &cfg), 1);
+    }
 }
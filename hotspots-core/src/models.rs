@@ -276,7 +276,7 @@ fn load_source_tokens(files: &[String], repo_root: &Path) -> HashMap<String, Has
         } else {
             repo_root.join(file)
         };
-        if let Ok(source) = std::fs::read_to_string(path) {
+        if let Ok(source) = crate::language::read_source(&path) {
             tokens_by_file.insert(file.clone(), source_tokens(&source));
         }
     }
@@ -291,8 +291,7 @@ pub fn extract_models(source_root: &Path, repo_root: &Path) -> Result<Vec<ModelD
             Some(language) => language,
             None => continue,
         };
-        let source = std::fs::read_to_string(&path)
-            .with_context(|| format!("failed to read {}", path.display()))?;
+        let source = crate::language::read_source(&path)?;
         let file = normalize_file(&path, repo_root);
         models.extend(extract_models_from_source(&source, language, file));
     }
@@ -475,9 +474,13 @@ fn extract_models_from_source(source: &str, language: Language, file: String) ->
         | Language::TypeScriptReact
         | Language::JavaScript
         | Language::JavaScriptReact
-        | Language::Vue => extract_regex_models(source, language, file, ECMASCRIPT_MODEL_PATTERNS),
+        | Language::Vue
+        | Language::Svelte => {
+            extract_regex_models(source, language, file, ECMASCRIPT_MODEL_PATTERNS)
+        }
         Language::CSharp => extract_regex_models(source, language, file, CSHARP_MODEL_PATTERNS),
         Language::C | Language::CHeader => vec![], // struct/typedef model detection not implemented
+        Language::Hcl => vec![],                   // configuration declares no data models
     }
 }
 
@@ -660,6 +663,12 @@ mod tests {
                 fo: 0,
                 ns: 0,
                 loc: 10,
+                sloc: None,
+                return_points: None,
+                unwrap_count: None,
+                max_arm_loc: None,
+                max_data_nesting: None,
+                unreachable_code: None,
             },
             lrs,
             band: if lrs >= 8.0 {
@@ -692,7 +701,12 @@ mod tests {
             isolation_rate: None,
             age_days: None,
             last_touch_days: None,
+            function_age_days: None,
+            coverage: None,
+            priority_score: None,
+            refactor_leverage: None,
             explanation: None,
+            location: None,
         }
     }
 
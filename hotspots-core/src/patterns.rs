@@ -4,7 +4,9 @@
 //! No I/O. Same inputs always produce the same outputs.
 //! See `docs/patterns.md` for the canonical specification.
 
+use crate::ast::FunctionKind;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 
 /// Input for Tier 1 (structural) pattern classification.
 /// Available in all analysis modes from raw metrics.
@@ -14,6 +16,18 @@ pub struct Tier1Input {
     pub fo: usize,
     pub ns: usize,
     pub loc: usize,
+    /// Source lines of code; `None` for snapshots predating `sloc`.
+    pub sloc: Option<usize>,
+    /// CFG exit points; `None` for snapshots predating `return_points`.
+    pub return_points: Option<usize>,
+    /// Rust unwrap/expect/panic sites; `None` for other languages and older snapshots.
+    pub unwrap_count: Option<usize>,
+    /// Longest switch case / match arm in lines; `None` when the function has none.
+    pub max_arm_loc: Option<usize>,
+    /// JS/TS object/array literal nesting; `None` for other languages and functions without literals.
+    pub max_data_nesting: Option<usize>,
+    /// Statements unreachable from the function entry; `None` when there are none.
+    pub unreachable_code: Option<usize>,
 }
 
 /// Input for Tier 2 (enriched) pattern classification.
@@ -24,6 +38,11 @@ pub struct Tier2Input {
     pub churn_lines: Option<usize>,
     pub days_since_last_change: Option<u32>,
     pub neighbor_churn: Option<usize>,
+    /// Percent of instrumented lines covered by tests, rounded down; `None`
+    /// without an LCOV report.
+    pub coverage_pct: Option<usize>,
+    /// Function LRS, read by `risky_untested` alongside `coverage_pct`.
+    pub lrs: Option<f64>,
     /// Suppresses `middle_man` and `neighbor_risk` when true.
     /// Set from call graph entry point detection.
     pub is_entrypoint: bool,
@@ -34,15 +53,25 @@ pub struct Tier2Input {
 /// Pass `&Thresholds::default()` unless the project has configured overrides
 /// via `.hotspotsrc.json`. The `classify` functions accept this by reference
 /// so the type signature accommodates overrides without any API change.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct Thresholds {
     pub complex_branching_cc: usize,
     pub complex_branching_nd: usize,
     pub deeply_nested_nd: usize,
     pub exit_heavy_ns: usize,
+    pub exit_heavy_points: usize,
+    pub exit_heavy_ratio_pct: usize,
+    pub fat_case_arm_loc: usize,
+    pub fat_case_ratio_pct: usize,
     pub god_function_loc: usize,
     pub god_function_fo: usize,
     pub long_function_loc: usize,
+    /// Hard length limit (config `max_function_loc`): functions longer than
+    /// this are `long_function` in place of the `long_function_loc` check
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_function_loc: Option<usize>,
+    pub nested_literal_depth: usize,
+    pub panic_prone_unwraps: usize,
     pub churn_magnet_churn: usize,
     pub churn_magnet_cc: usize,
     pub cyclic_hub_scc: usize,
@@ -59,6 +88,15 @@ pub struct Thresholds {
     pub stale_complex_cc: usize,
     pub stale_complex_loc: usize,
     pub stale_complex_days: u32,
+    pub risky_untested_lrs: usize,
+    pub risky_untested_coverage_pct: usize,
+    /// User-defined patterns from the `custom_patterns` config array.
+    /// Evaluated after the built-in patterns, in declaration order.
+    pub custom: Vec<CustomPattern>,
+    /// Complete threshold sets for functions of one kind (`kind_thresholds`
+    /// config); kinds without an entry use the thresholds above.
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub by_kind: BTreeMap<FunctionKind, Thresholds>,
 }
 
 impl Default for Thresholds {
@@ -68,9 +106,16 @@ impl Default for Thresholds {
             complex_branching_nd: 4,
             deeply_nested_nd: 5,
             exit_heavy_ns: 5,
+            exit_heavy_points: 6,
+            exit_heavy_ratio_pct: 10,
+            fat_case_arm_loc: 30,
+            fat_case_ratio_pct: 50,
             god_function_loc: 60,
             god_function_fo: 10,
             long_function_loc: 80,
+            max_function_loc: None,
+            nested_literal_depth: 4,
+            panic_prone_unwraps: 5,
             churn_magnet_churn: 200,
             churn_magnet_cc: 8,
             cyclic_hub_scc: 2,
@@ -87,10 +132,204 @@ impl Default for Thresholds {
             stale_complex_cc: 10,
             stale_complex_loc: 60,
             stale_complex_days: 180,
+            risky_untested_lrs: 6,
+            risky_untested_coverage_pct: 50,
+            custom: Vec::new(),
+            by_kind: BTreeMap::new(),
         }
     }
 }
 
+impl Thresholds {
+    /// Thresholds that apply to a function of `kind`. `None` (reports and
+    /// snapshots without a `location`) gets the base thresholds.
+    pub fn for_kind(&self, kind: Option<FunctionKind>) -> &Thresholds {
+        kind.and_then(|k| self.by_kind.get(&k)).unwrap_or(self)
+    }
+
+    /// Most lines a function may have before it is flagged `long_function`:
+    /// `max_function_loc` when set, otherwise one less than `long_function_loc`
+    pub fn function_loc_limit(&self) -> usize {
+        self.max_function_loc
+            .unwrap_or(self.long_function_loc.saturating_sub(1))
+    }
+}
+
+/// IDs of the built-in patterns. Custom patterns may not reuse these names.
+pub const BUILTIN_PATTERNS: &[&str] = &[
+    "complex_branching",
+    "deeply_nested",
+    "exit_heavy",
+    "fat_case",
+    "god_function",
+    "long_function",
+    "nested_literal",
+    "panic_prone",
+    "unreachable_code",
+    "churn_magnet",
+    "cyclic_hub",
+    "hub_function",
+    "middle_man",
+    "neighbor_risk",
+    "risky_untested",
+    "shotgun_target",
+    "stale_complex",
+    "volatile_god",
+];
+
+/// Metrics a custom pattern condition may reference.
+/// `TIER1_METRICS` are always available; `TIER2_METRICS` need snapshot mode.
+/// `cc_density_pct` is derived: `cc * 100 / sloc` (LOC when SLOC is absent).
+const TIER1_METRICS: &[&str] = &[
+    "cc",
+    "nd",
+    "fo",
+    "ns",
+    "loc",
+    "sloc",
+    "return_points",
+    "unwrap_count",
+    "max_arm_loc",
+    "max_data_nesting",
+    "unreachable_code",
+    "cc_density_pct",
+];
+const TIER2_METRICS: &[&str] = &[
+    "fan_in",
+    "scc_size",
+    "churn_lines",
+    "days_since_last_change",
+    "neighbor_churn",
+    "coverage_pct",
+];
+
+/// A single `<metric> <op> <value>` comparison in a custom pattern.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Condition {
+    /// Lower-case metric name (see `TIER1_METRICS` / `TIER2_METRICS`).
+    pub metric: String,
+    /// One of `>`, `>=`, `<`, `<=`, `==`, `!=`.
+    pub op: String,
+    pub value: usize,
+}
+
+/// A user-defined pattern: fires when every condition holds.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CustomPattern {
+    pub name: String,
+    pub conditions: Vec<Condition>,
+    pub description: Option<String>,
+}
+
+impl CustomPattern {
+    /// Parse a pattern from its config representation.
+    ///
+    /// `expr` is a conjunction of comparisons joined by `&&`, e.g.
+    /// `"cc > 20 && nd >= 4"`. Metric names are case-insensitive.
+    pub fn parse(name: &str, expr: &str, description: Option<String>) -> Result<Self, String> {
+        let conditions = parse_conditions(expr)?;
+        Ok(CustomPattern {
+            name: name.to_string(),
+            conditions,
+            description,
+        })
+    }
+
+    /// 1 if every condition uses a Tier 1 metric, otherwise 2.
+    pub fn tier(&self) -> u8 {
+        if self
+            .conditions
+            .iter()
+            .all(|c| TIER1_METRICS.contains(&c.metric.as_str()))
+        {
+            1
+        } else {
+            2
+        }
+    }
+}
+
+/// Parse a `&&`-joined list of comparisons into conditions.
+pub fn parse_conditions(expr: &str) -> Result<Vec<Condition>, String> {
+    let mut conditions = Vec::new();
+    for term in expr.split("&&") {
+        let term = term.trim();
+        if term.is_empty() {
+            return Err(format!("empty condition in \"{}\"", expr));
+        }
+        conditions.push(parse_condition(term)?);
+    }
+    Ok(conditions)
+}
+
+fn parse_condition(term: &str) -> Result<Condition, String> {
+    // Two-character operators must be tried before their one-character prefixes.
+    const OPS: &[&str] = &[">=", "<=", "==", "!=", ">", "<"];
+    let (pos, op) = OPS
+        .iter()
+        .filter_map(|op| term.find(op).map(|pos| (pos, *op)))
+        .min_by_key(|(pos, op)| (*pos, std::cmp::Reverse(op.len())))
+        .ok_or_else(|| format!("condition \"{}\" has no comparison operator", term))?;
+    let metric = term[..pos].trim().to_ascii_lowercase();
+    let value_str = term[pos + op.len()..].trim();
+    if !TIER1_METRICS.contains(&metric.as_str()) && !TIER2_METRICS.contains(&metric.as_str()) {
+        return Err(format!(
+            "unknown metric \"{}\" (expected one of: {}, {})",
+            metric,
+            TIER1_METRICS.join(", "),
+            TIER2_METRICS.join(", ")
+        ));
+    }
+    let value = value_str.parse::<usize>().map_err(|_| {
+        format!(
+            "condition \"{}\": value \"{}\" is not a non-negative integer",
+            term, value_str
+        )
+    })?;
+    Ok(Condition {
+        metric,
+        op: op.to_string(),
+        value,
+    })
+}
+
+/// Look up a metric by name. Returns `None` when a Tier 2 input is absent.
+fn metric_value(metric: &str, t1: &Tier1Input, t2: &Tier2Input) -> Option<usize> {
+    match metric {
+        "cc" => Some(t1.cc),
+        "nd" => Some(t1.nd),
+        "fo" => Some(t1.fo),
+        "ns" => Some(t1.ns),
+        "loc" => Some(t1.loc),
+        "sloc" => t1.sloc,
+        "return_points" => t1.return_points,
+        "unwrap_count" => t1.unwrap_count,
+        "max_arm_loc" => t1.max_arm_loc,
+        "max_data_nesting" => t1.max_data_nesting,
+        "unreachable_code" => t1.unreachable_code,
+        "cc_density_pct" => Some(t1.cc * 100 / t1.sloc.unwrap_or(t1.loc).max(1)),
+        "fan_in" => t2.fan_in,
+        "scc_size" => t2.scc_size,
+        "churn_lines" => t2.churn_lines,
+        "days_since_last_change" => t2.days_since_last_change.map(|d| d as usize),
+        "neighbor_churn" => t2.neighbor_churn,
+        "coverage_pct" => t2.coverage_pct,
+        _ => None,
+    }
+}
+
+fn compare(value: usize, op: &str, threshold: usize) -> bool {
+    match op {
+        ">" => value > threshold,
+        ">=" => value >= threshold,
+        "<" => value < threshold,
+        "<=" => value <= threshold,
+        "==" => value == threshold,
+        "!=" => value != threshold,
+        _ => false,
+    }
+}
+
 /// A single metric condition that caused a pattern to fire.
 /// Populated only when `--explain-patterns` is requested.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -108,7 +347,7 @@ pub struct PatternDetail {
     pub id: String,
     /// 1 = Tier 1 (structural), 2 = Tier 2 (enriched).
     pub tier: u8,
-    /// "primitive" or "derived".
+    /// "primitive", "derived", or "custom".
     pub kind: String,
     pub triggered_by: Vec<TriggeredBy>,
 }
@@ -145,12 +384,24 @@ pub fn classify_detailed(t1: &Tier1Input, t2: &Tier2Input, th: &Thresholds) -> V
     if let Some(d) = check_exit_heavy(t1, th) {
         results.push(d);
     }
+    if let Some(d) = check_fat_case(t1, th) {
+        results.push(d);
+    }
     if let Some(d) = god.clone() {
         results.push(d);
     }
     if let Some(d) = check_long_function(t1, th) {
         results.push(d);
     }
+    if let Some(d) = check_nested_literal(t1, th) {
+        results.push(d);
+    }
+    if let Some(d) = check_panic_prone(t1, th) {
+        results.push(d);
+    }
+    if let Some(d) = check_unreachable_code(t1) {
+        results.push(d);
+    }
 
     // Tier 2 — alphabetical
     if let Some(d) = churn.clone() {
@@ -168,6 +419,9 @@ pub fn classify_detailed(t1: &Tier1Input, t2: &Tier2Input, th: &Thresholds) -> V
     if let Some(d) = check_neighbor_risk(t1, t2, th) {
         results.push(d);
     }
+    if let Some(d) = check_risky_untested(t2, th) {
+        results.push(d);
+    }
     if let Some(d) = check_shotgun_target(t2, th) {
         results.push(d);
     }
@@ -188,9 +442,33 @@ pub fn classify_detailed(t1: &Tier1Input, t2: &Tier2Input, th: &Thresholds) -> V
         });
     }
 
+    // Custom patterns — config order, after all built-ins.
+    for custom in &th.custom {
+        if let Some(d) = check_custom(custom, t1, t2) {
+            results.push(d);
+        }
+    }
+
     results
 }
 
+fn check_custom(p: &CustomPattern, t1: &Tier1Input, t2: &Tier2Input) -> Option<PatternDetail> {
+    let mut triggered_by = Vec::with_capacity(p.conditions.len());
+    for c in &p.conditions {
+        let value = metric_value(&c.metric, t1, t2)?;
+        if !compare(value, &c.op, c.value) {
+            return None;
+        }
+        triggered_by.push(tb(&c.metric, &c.op, value, c.value));
+    }
+    Some(PatternDetail {
+        id: p.name.clone(),
+        tier: p.tier(),
+        kind: "custom".to_string(),
+        triggered_by,
+    })
+}
+
 // ---------- Tier 1 helpers ----------
 
 fn check_complex_branching(t: &Tier1Input, th: &Thresholds) -> Option<PatternDetail> {
@@ -222,7 +500,23 @@ fn check_deeply_nested(t: &Tier1Input, th: &Thresholds) -> Option<PatternDetail>
     }
 }
 
+/// Exit points relative to statements when `return_points`/`sloc` are known;
+/// otherwise (older snapshots) the NS count alone.
 fn check_exit_heavy(t: &Tier1Input, th: &Thresholds) -> Option<PatternDetail> {
+    if let (Some(points), Some(sloc)) = (t.return_points, t.sloc) {
+        let ratio_pct = points * 100 / sloc.max(1);
+        return (points >= th.exit_heavy_points && ratio_pct >= th.exit_heavy_ratio_pct).then(
+            || PatternDetail {
+                id: "exit_heavy".to_string(),
+                tier: 1,
+                kind: "primitive".to_string(),
+                triggered_by: vec![
+                    tb("EXITS", ">=", points, th.exit_heavy_points),
+                    tb("EXITS/SLOC%", ">=", ratio_pct, th.exit_heavy_ratio_pct),
+                ],
+            },
+        );
+    }
     if t.ns >= th.exit_heavy_ns {
         Some(PatternDetail {
             id: "exit_heavy".to_string(),
@@ -235,6 +529,22 @@ fn check_exit_heavy(t: &Tier1Input, th: &Thresholds) -> Option<PatternDetail> {
     }
 }
 
+/// One switch case / match arm that is both long and most of the function.
+/// Functions without a switch or match have no `max_arm_loc` and never fire.
+fn check_fat_case(t: &Tier1Input, th: &Thresholds) -> Option<PatternDetail> {
+    let arm_loc = t.max_arm_loc?;
+    let ratio_pct = arm_loc * 100 / t.loc.max(1);
+    (arm_loc >= th.fat_case_arm_loc && ratio_pct >= th.fat_case_ratio_pct).then(|| PatternDetail {
+        id: "fat_case".to_string(),
+        tier: 1,
+        kind: "primitive".to_string(),
+        triggered_by: vec![
+            tb("ARM_LOC", ">=", arm_loc, th.fat_case_arm_loc),
+            tb("ARM_LOC/LOC%", ">=", ratio_pct, th.fat_case_ratio_pct),
+        ],
+    })
+}
+
 fn check_god_function(t: &Tier1Input, th: &Thresholds) -> Option<PatternDetail> {
     if t.loc >= th.god_function_loc && t.fo >= th.god_function_fo {
         Some(PatternDetail {
@@ -251,19 +561,59 @@ fn check_god_function(t: &Tier1Input, th: &Thresholds) -> Option<PatternDetail>
     }
 }
 
+/// The detail shows the configured value: `LOC > max_function_loc` for a hard
+/// limit, `LOC >= long_function_loc` otherwise.
 fn check_long_function(t: &Tier1Input, th: &Thresholds) -> Option<PatternDetail> {
-    if t.loc >= th.long_function_loc {
+    if t.loc > th.function_loc_limit() {
+        let trigger = match th.max_function_loc {
+            Some(max) => tb("LOC", ">", t.loc, max),
+            None => tb("LOC", ">=", t.loc, th.long_function_loc),
+        };
         Some(PatternDetail {
             id: "long_function".to_string(),
             tier: 1,
             kind: "primitive".to_string(),
-            triggered_by: vec![tb("LOC", ">=", t.loc, th.long_function_loc)],
+            triggered_by: vec![trigger],
         })
     } else {
         None
     }
 }
 
+/// JS/TS functions only; other languages have no `max_data_nesting`.
+fn check_nested_literal(t: &Tier1Input, th: &Thresholds) -> Option<PatternDetail> {
+    let depth = t.max_data_nesting?;
+    (depth >= th.nested_literal_depth).then(|| PatternDetail {
+        id: "nested_literal".to_string(),
+        tier: 1,
+        kind: "primitive".to_string(),
+        triggered_by: vec![tb("DATA_NESTING", ">=", depth, th.nested_literal_depth)],
+    })
+}
+
+/// Rust functions only; other languages have no `unwrap_count`.
+fn check_panic_prone(t: &Tier1Input, th: &Thresholds) -> Option<PatternDetail> {
+    let unwraps = t.unwrap_count?;
+    (unwraps >= th.panic_prone_unwraps).then(|| PatternDetail {
+        id: "panic_prone".to_string(),
+        tier: 1,
+        kind: "primitive".to_string(),
+        triggered_by: vec![tb("UNWRAPS", ">=", unwraps, th.panic_prone_unwraps)],
+    })
+}
+
+/// Any dead statement fires; there is no threshold to tune. Only languages
+/// whose CFG builders track dead code (JS/TS, Java, C, C#) report it.
+fn check_unreachable_code(t: &Tier1Input) -> Option<PatternDetail> {
+    let stmts = t.unreachable_code?;
+    (stmts >= 1).then(|| PatternDetail {
+        id: "unreachable_code".to_string(),
+        tier: 1,
+        kind: "primitive".to_string(),
+        triggered_by: vec![tb("UNREACHABLE", ">=", stmts, 1)],
+    })
+}
+
 // ---------- Tier 2 helpers ----------
 
 fn check_churn_magnet(t1: &Tier1Input, t2: &Tier2Input, th: &Thresholds) -> Option<PatternDetail> {
@@ -360,6 +710,29 @@ fn check_neighbor_risk(t1: &Tier1Input, t2: &Tier2Input, th: &Thresholds) -> Opt
     }
 }
 
+fn check_risky_untested(t2: &Tier2Input, th: &Thresholds) -> Option<PatternDetail> {
+    let coverage = t2.coverage_pct?;
+    let lrs = t2.lrs?;
+    if coverage < th.risky_untested_coverage_pct && lrs >= th.risky_untested_lrs as f64 {
+        Some(PatternDetail {
+            id: "risky_untested".to_string(),
+            tier: 2,
+            kind: "primitive".to_string(),
+            triggered_by: vec![
+                tb("LRS", ">=", lrs as usize, th.risky_untested_lrs),
+                tb(
+                    "coverage_pct",
+                    "<",
+                    coverage,
+                    th.risky_untested_coverage_pct,
+                ),
+            ],
+        })
+    } else {
+        None
+    }
+}
+
 fn check_shotgun_target(t2: &Tier2Input, th: &Thresholds) -> Option<PatternDetail> {
     let fan_in = t2.fan_in?;
     let churn = t2.churn_lines?;
@@ -425,6 +798,12 @@ mod tests {
             fo,
             ns,
             loc,
+            sloc: None,
+            return_points: None,
+            unwrap_count: None,
+            max_arm_loc: None,
+            max_data_nesting: None,
+            unreachable_code: None,
         }
     }
 
@@ -435,6 +814,8 @@ mod tests {
             churn_lines: None,
             days_since_last_change: None,
             neighbor_churn: None,
+            coverage_pct: None,
+            lrs: None,
             is_entrypoint: false,
         }
     }
@@ -452,6 +833,8 @@ mod tests {
             churn_lines: Some(churn_lines),
             days_since_last_change: Some(days),
             neighbor_churn: Some(neighbor_churn),
+            coverage_pct: None,
+            lrs: None,
             is_entrypoint: false,
         }
     }
@@ -526,6 +909,28 @@ mod tests {
         assert!(has(&p, "exit_heavy"));
     }
 
+    #[test]
+    fn exit_heavy_uses_exit_density_when_return_points_known() {
+        // Many exits in a long function: dense enough at 10%
+        let mut t = t1(0, 0, 0, 0, 0);
+        t.sloc = Some(40);
+        t.return_points = Some(6);
+        let p = classify(&t, &t2_none(), &th());
+        assert!(has(&p, "exit_heavy"));
+
+        // Same exits spread over a much longer body: not exit-heavy
+        t.sloc = Some(200);
+        let p = classify(&t, &t2_none(), &th());
+        assert!(!has(&p, "exit_heavy"));
+
+        // NS is ignored once return_points is available
+        let mut t = t1(0, 0, 0, 10, 0);
+        t.sloc = Some(10);
+        t.return_points = Some(2);
+        let p = classify(&t, &t2_none(), &th());
+        assert!(!has(&p, "exit_heavy"));
+    }
+
     // ---------- god_function ----------
 
     #[test]
@@ -570,6 +975,91 @@ mod tests {
         assert!(has(&p, "long_function"));
     }
 
+    #[test]
+    fn long_function_max_loc_shows_the_limit() {
+        let th = Thresholds {
+            max_function_loc: Some(50),
+            ..Thresholds::default()
+        };
+        assert!(!has(
+            &classify(&t1(0, 0, 0, 0, 50), &t2_none(), &th),
+            "long_function"
+        ));
+        let p = classify_detailed(&t1(0, 0, 0, 0, 51), &t2_none(), &th);
+        let detail = p.iter().find(|d| d.id == "long_function").unwrap();
+        assert_eq!(detail.triggered_by[0].op, ">");
+        assert_eq!(detail.triggered_by[0].threshold, 50);
+    }
+
+    // ---------- nested_literal ----------
+
+    #[test]
+    fn nested_literal_at_threshold() {
+        let mut t = t1(1, 0, 0, 0, 20);
+        t.max_data_nesting = Some(4);
+        assert!(has(&classify(&t, &t2_none(), &th()), "nested_literal"));
+        t.max_data_nesting = Some(3);
+        assert!(!has(&classify(&t, &t2_none(), &th()), "nested_literal"));
+    }
+
+    #[test]
+    fn nested_literal_independent_of_nd() {
+        // Control-flow nesting alone never fires
+        let p = classify(&t1(10, 8, 0, 0, 40), &t2_none(), &th());
+        assert!(!has(&p, "nested_literal"));
+    }
+
+    // ---------- panic_prone ----------
+
+    #[test]
+    fn panic_prone_at_threshold() {
+        let mut t = t1(0, 0, 0, 0, 10);
+        t.unwrap_count = Some(4);
+        assert!(!has(&classify(&t, &t2_none(), &th()), "panic_prone"));
+        t.unwrap_count = Some(5);
+        assert!(has(&classify(&t, &t2_none(), &th()), "panic_prone"));
+    }
+
+    #[test]
+    fn panic_prone_requires_unwrap_count() {
+        // Non-Rust functions have no unwrap_count and never fire
+        let p = classify(&t1(50, 8, 20, 10, 300), &t2_none(), &th());
+        assert!(!has(&p, "panic_prone"));
+    }
+
+    // ---------- unreachable_code ----------
+
+    #[test]
+    fn unreachable_code_fires_on_any_dead_statement() {
+        let mut t = t1(1, 0, 0, 0, 10);
+        assert!(!has(&classify(&t, &t2_none(), &th()), "unreachable_code"));
+        t.unreachable_code = Some(1);
+        assert!(has(&classify(&t, &t2_none(), &th()), "unreachable_code"));
+    }
+
+    // ---------- fat_case ----------
+
+    #[test]
+    fn fat_case_needs_long_and_dominant_arm() {
+        let mut t = t1(8, 2, 0, 0, 100);
+        t.max_arm_loc = Some(50);
+        assert!(has(&classify(&t, &t2_none(), &th()), "fat_case"));
+        // Long, but under half the function
+        t.max_arm_loc = Some(49);
+        assert!(!has(&classify(&t, &t2_none(), &th()), "fat_case"));
+        // Dominant, but short
+        let mut t = t1(3, 1, 0, 0, 40);
+        t.max_arm_loc = Some(29);
+        assert!(!has(&classify(&t, &t2_none(), &th()), "fat_case"));
+    }
+
+    #[test]
+    fn fat_case_requires_max_arm_loc() {
+        // Functions without a switch/match never fire
+        let p = classify(&t1(50, 8, 20, 10, 300), &t2_none(), &th());
+        assert!(!has(&p, "fat_case"));
+    }
+
     // ---------- churn_magnet ----------
 
     #[test]
@@ -910,6 +1400,38 @@ mod tests {
         assert!(has(&p, "volatile_god"));
     }
 
+    // ---------- risky_untested ----------
+
+    #[test]
+    fn risky_untested_needs_high_lrs_and_low_coverage() {
+        let input = |coverage_pct, lrs| Tier2Input {
+            coverage_pct: Some(coverage_pct),
+            lrs: Some(lrs),
+            ..t2_none()
+        };
+        assert!(has(
+            &classify(&t1(0, 0, 0, 0, 0), &input(49, 6.0), &th()),
+            "risky_untested"
+        ));
+        assert!(!has(
+            &classify(&t1(0, 0, 0, 0, 0), &input(50, 9.0), &th()),
+            "risky_untested"
+        ));
+        assert!(!has(
+            &classify(&t1(0, 0, 0, 0, 0), &input(0, 5.9), &th()),
+            "risky_untested"
+        ));
+        // No coverage report: never fires
+        let no_coverage = Tier2Input {
+            lrs: Some(9.0),
+            ..t2_none()
+        };
+        assert!(!has(
+            &classify(&t1(0, 0, 0, 0, 0), &no_coverage, &th()),
+            "risky_untested"
+        ));
+    }
+
     // ---------- entrypoint suppression ----------
 
     #[test]
@@ -987,6 +1509,80 @@ mod tests {
         assert_eq!(fo_tb.threshold, 10);
     }
 
+    // ---------- custom patterns ----------
+
+    fn th_with(name: &str, expr: &str) -> Thresholds {
+        Thresholds {
+            custom: vec![CustomPattern::parse(name, expr, None).unwrap()],
+            ..Thresholds::default()
+        }
+    }
+
+    #[test]
+    fn custom_pattern_fires_when_all_conditions_hold() {
+        let th = th_with("tangled", "cc > 20 && nd >= 4");
+        let p = classify(&t1(21, 4, 0, 0, 0), &t2_none(), &th);
+        assert!(has(&p, "tangled"));
+        let p = classify(&t1(20, 4, 0, 0, 0), &t2_none(), &th);
+        assert!(!has(&p, "tangled"));
+        let p = classify(&t1(21, 3, 0, 0, 0), &t2_none(), &th);
+        assert!(!has(&p, "tangled"));
+    }
+
+    #[test]
+    fn custom_pattern_ordered_after_builtins() {
+        let th = th_with("aaa_first", "cc >= 10");
+        let p = classify(&t1(10, 4, 0, 0, 0), &t2_none(), &th);
+        assert_eq!(p, vec!["complex_branching", "aaa_first"]);
+    }
+
+    #[test]
+    fn custom_pattern_missing_tier2_metric_does_not_fire() {
+        let th = th_with("popular", "fan_in >= 5");
+        let p = classify(&t1(0, 0, 0, 0, 0), &t2_none(), &th);
+        assert!(!has(&p, "popular"));
+        let t = Tier2Input {
+            fan_in: Some(5),
+            ..t2_none()
+        };
+        let details = classify_detailed(&t1(0, 0, 0, 0, 0), &t, &th);
+        let d = details.iter().find(|d| d.id == "popular").unwrap();
+        assert_eq!(d.tier, 2);
+        assert_eq!(d.kind, "custom");
+        assert_eq!(d.triggered_by, vec![tb("fan_in", ">=", 5, 5)]);
+    }
+
+    #[test]
+    fn custom_pattern_on_cc_density() {
+        let th = th_with("dense", "cc_density_pct >= 25 && cc >= 10");
+        let dense = Tier1Input {
+            sloc: Some(40),
+            ..t1(10, 0, 0, 0, 60)
+        };
+        assert!(has(&classify(&dense, &t2_none(), &th), "dense"));
+        let sprawling = Tier1Input {
+            sloc: Some(300),
+            ..t1(10, 0, 0, 0, 400)
+        };
+        assert!(!has(&classify(&sprawling, &t2_none(), &th), "dense"));
+        // Snapshots without SLOC fall back to LOC
+        assert!(has(
+            &classify(&t1(10, 0, 0, 0, 40), &t2_none(), &th),
+            "dense"
+        ));
+    }
+
+    #[test]
+    fn parse_conditions_rejects_bad_input() {
+        assert!(parse_conditions("cc > 20").is_ok());
+        assert!(parse_conditions("CC<=3 && LOC != 0").is_ok());
+        assert!(parse_conditions("").is_err());
+        assert!(parse_conditions("cc > 20 &&").is_err());
+        assert!(parse_conditions("bogus > 1").is_err());
+        assert!(parse_conditions("cc 20").is_err());
+        assert!(parse_conditions("cc > -1").is_err());
+    }
+
     #[test]
     fn classify_delegates_to_classify_detailed() {
         // classify() and classify_detailed() must agree on which IDs fire
@@ -24,6 +24,16 @@ pub enum PolicyId {
     CriticalIntroduction,
     ExcessiveRiskRegression,
     NetRepoRegression,
+    // Opt-in length limit (`max_function_loc`); warning unless configured to block
+    FunctionTooLong,
+    // Opt-in limit on complexity added by a change (`max_new_cc`); blocking unless configured
+    MaxNewCc,
+    // Opt-in caps on new Critical/High functions per change (`max_new_critical`,
+    // `max_new_high`); blocking unless configured
+    MaxNewCritical,
+    MaxNewHigh,
+    // Opt-in module-level guard (`max_stable_afferent`); blocking unless configured
+    InstabilityRegression,
     // Warning policies
     WatchThreshold,
     AttentionThreshold,
@@ -38,6 +48,11 @@ impl PolicyId {
             PolicyId::CriticalIntroduction => "critical-introduction",
             PolicyId::ExcessiveRiskRegression => "excessive-risk-regression",
             PolicyId::NetRepoRegression => "net-repo-regression",
+            PolicyId::FunctionTooLong => "function-too-long",
+            PolicyId::MaxNewCc => "max-new-cc",
+            PolicyId::MaxNewCritical => "max-new-critical",
+            PolicyId::MaxNewHigh => "max-new-high",
+            PolicyId::InstabilityRegression => "instability-regression",
             PolicyId::WatchThreshold => "watch-threshold",
             PolicyId::AttentionThreshold => "attention-threshold",
             PolicyId::RapidGrowth => "rapid-growth",
@@ -50,11 +65,16 @@ impl PolicyId {
         match self {
             PolicyId::CriticalIntroduction => 0,
             PolicyId::ExcessiveRiskRegression => 1,
-            PolicyId::WatchThreshold => 2,
-            PolicyId::AttentionThreshold => 3,
-            PolicyId::RapidGrowth => 4,
-            PolicyId::SuppressionMissingReason => 5,
-            PolicyId::NetRepoRegression => 6,
+            PolicyId::FunctionTooLong => 2,
+            PolicyId::MaxNewCc => 3,
+            PolicyId::MaxNewCritical => 4,
+            PolicyId::MaxNewHigh => 5,
+            PolicyId::InstabilityRegression => 6,
+            PolicyId::WatchThreshold => 7,
+            PolicyId::AttentionThreshold => 8,
+            PolicyId::RapidGrowth => 9,
+            PolicyId::SuppressionMissingReason => 10,
+            PolicyId::NetRepoRegression => 11,
         }
     }
 }
@@ -181,6 +201,8 @@ pub fn evaluate_policies(
     // 1. Blocking function-level policies
     evaluate_critical_introduction(&delta.deltas, config, &mut results);
     evaluate_excessive_risk_regression(&delta.deltas, config, &mut results);
+    evaluate_function_too_long(&delta.deltas, config, &mut results);
+    evaluate_max_new_cc(&delta.deltas, config, &mut results);
 
     // 2. Warning function-level policies
     evaluate_watch_threshold(&delta.deltas, config, &mut results);
@@ -188,7 +210,10 @@ pub fn evaluate_policies(
     evaluate_rapid_growth(&delta.deltas, config, &mut results);
     evaluate_suppression_missing_reason(&delta.deltas, &mut results);
 
-    // 3. Repo-level policies
+    // 3. Module- and repo-level policies
+    evaluate_max_new_in_band(&delta.deltas, config, RiskBand::Critical, &mut results);
+    evaluate_max_new_in_band(&delta.deltas, config, RiskBand::High, &mut results);
+    evaluate_instability_regression(delta, config, &mut results);
     evaluate_net_repo_regression(delta, current_snapshot, repo_root, &mut results)?;
 
     // Sort results deterministically
@@ -319,6 +344,233 @@ fn evaluate_excessive_risk_regression(
     }
 }
 
+/// Evaluate Function Too Long policy
+///
+/// Only runs when `config.max_function_loc` is set. Triggers when
+/// `after.loc > limit AND (before is None OR before.loc <= limit)`, so
+/// functions already over the limit are not re-flagged on every edit. The
+/// limit is the function kind's (see [`ResolvedConfig::function_loc_limit`]).
+/// `config.function_too_long_mode` controls the severity (default: warn).
+fn evaluate_function_too_long(
+    deltas: &[FunctionDeltaEntry],
+    config: &ResolvedConfig,
+    results: &mut PolicyResults,
+) {
+    if config.max_function_loc.is_none() {
+        return;
+    }
+    let severity = match config.function_too_long_mode {
+        PolicyMode::Block => PolicySeverity::Blocking,
+        PolicyMode::Warn => PolicySeverity::Warning,
+        PolicyMode::Off => return,
+    };
+
+    for entry in active_deltas(deltas) {
+        let (Some(after), Some(max_loc)) = (&entry.after, config.function_loc_limit(entry.kind))
+        else {
+            continue;
+        };
+        let loc = after.metrics.loc as usize;
+        let was_over = entry
+            .before
+            .as_ref()
+            .is_some_and(|before| before.metrics.loc as usize > max_loc);
+        if loc <= max_loc || was_over {
+            continue;
+        }
+
+        let result = PolicyResult {
+            id: PolicyId::FunctionTooLong,
+            severity,
+            function_id: Some(entry.function_id.clone()),
+            message: format!(
+                "Function {} is {} lines long (limit {})",
+                entry.function_id, loc, max_loc
+            ),
+            metadata: None,
+        };
+        match severity {
+            PolicySeverity::Blocking => results.failed.push(result),
+            PolicySeverity::Warning => results.warnings.push(result),
+        }
+    }
+}
+
+/// Evaluate Max New CC policy
+///
+/// Only runs when `config.max_new_cc` is set. Triggers when a new or modified
+/// function's `new_cc` (complexity from decision points on added lines, see
+/// [`Delta::annotate_new_cc`]) exceeds the limit. Entries without `new_cc`
+/// are skipped. `config.max_new_cc_mode` controls the severity (default: block).
+fn evaluate_max_new_cc(
+    deltas: &[FunctionDeltaEntry],
+    config: &ResolvedConfig,
+    results: &mut PolicyResults,
+) {
+    let Some(limit) = config.max_new_cc else {
+        return;
+    };
+    let severity = match config.max_new_cc_mode {
+        PolicyMode::Block => PolicySeverity::Blocking,
+        PolicyMode::Warn => PolicySeverity::Warning,
+        PolicyMode::Off => return,
+    };
+
+    for entry in active_deltas(deltas) {
+        let Some(new_cc) = entry.new_cc.filter(|&n| n > limit) else {
+            continue;
+        };
+        let result = PolicyResult {
+            id: PolicyId::MaxNewCc,
+            severity,
+            function_id: Some(entry.function_id.clone()),
+            message: format!(
+                "Function {} adds {} cyclomatic complexity on changed lines (limit {})",
+                entry.function_id, new_cc, limit
+            ),
+            metadata: None,
+        };
+        match severity {
+            PolicySeverity::Blocking => results.failed.push(result),
+            PolicySeverity::Warning => results.warnings.push(result),
+        }
+    }
+}
+
+/// Evaluate Max New Critical / Max New High policies
+///
+/// Only runs when the limit for `band` (`config.max_new_critical` or
+/// `config.max_new_high`) is set. Counts unsuppressed `new` functions whose
+/// `after.band` is exactly `band`, so a new Critical function counts toward
+/// `max-new-critical` only. Exceeding the limit yields one repo-level result
+/// (no `function_id`) naming every offending function. The matching `_mode`
+/// controls the severity (default: block).
+fn evaluate_max_new_in_band(
+    deltas: &[FunctionDeltaEntry],
+    config: &ResolvedConfig,
+    band: RiskBand,
+    results: &mut PolicyResults,
+) {
+    let (id, limit, mode) = match band {
+        RiskBand::Critical => (
+            PolicyId::MaxNewCritical,
+            config.max_new_critical,
+            config.max_new_critical_mode,
+        ),
+        RiskBand::High => (
+            PolicyId::MaxNewHigh,
+            config.max_new_high,
+            config.max_new_high_mode,
+        ),
+        _ => return,
+    };
+    let Some(limit) = limit else {
+        return;
+    };
+    let severity = match mode {
+        PolicyMode::Block => PolicySeverity::Blocking,
+        PolicyMode::Warn => PolicySeverity::Warning,
+        PolicyMode::Off => return,
+    };
+
+    let mut offending: Vec<&str> = active_deltas(deltas)
+        .filter(|e| e.status == FunctionStatus::New)
+        .filter(|e| e.after.as_ref().is_some_and(|a| a.band == band))
+        .map(|e| e.function_id.as_str())
+        .collect();
+    if offending.len() <= limit {
+        return;
+    }
+    offending.sort_unstable();
+
+    let result = PolicyResult {
+        id,
+        severity,
+        function_id: None,
+        message: format!(
+            "{} new {} functions (limit {}): {}",
+            offending.len(),
+            band.as_str(),
+            limit,
+            offending.join(", ")
+        ),
+        metadata: None,
+    };
+    match severity {
+        PolicySeverity::Blocking => results.failed.push(result),
+        PolicySeverity::Warning => results.warnings.push(result),
+    }
+}
+
+/// Evaluate Instability Regression policy
+///
+/// Only runs when `config.max_stable_afferent` is set, and reads the module
+/// entries of `delta.aggregates` (nothing to check without them). Triggers when
+/// a module in the zone of pain (instability below `module_zones.stable_below`,
+/// average CC above `module_zones.pain_complexity`) gets more complex, or when a
+/// stable module's afferent coupling grows past the limit. Modules already over
+/// the limit are not re-flagged unless their afferent coupling grows again.
+/// `config.instability_regression_mode` controls the severity (default: block).
+fn evaluate_instability_regression(
+    delta: &Delta,
+    config: &ResolvedConfig,
+    results: &mut PolicyResults,
+) {
+    let Some(max_afferent) = config.max_stable_afferent else {
+        return;
+    };
+    let severity = match config.instability_regression_mode {
+        PolicyMode::Block => PolicySeverity::Blocking,
+        PolicyMode::Warn => PolicySeverity::Warning,
+        PolicyMode::Off => return,
+    };
+    let Some(aggregates) = &delta.aggregates else {
+        return;
+    };
+    let zones = &config.module_zones;
+
+    for entry in &aggregates.modules {
+        let after = &entry.after;
+        if after.instability >= zones.stable_below {
+            continue;
+        }
+
+        let mut reasons = Vec::new();
+        if let Some(before) = &entry.before {
+            if after.avg_complexity > zones.pain_complexity
+                && after.avg_complexity > before.avg_complexity
+            {
+                reasons.push(format!(
+                    "avg CC rose {:.2} -> {:.2} in the zone of pain (instability {:.2})",
+                    before.avg_complexity, after.avg_complexity, after.instability
+                ));
+            }
+        }
+        let before_afferent = entry.before.as_ref().map_or(0, |b| b.afferent);
+        if after.afferent > max_afferent && after.afferent > before_afferent {
+            reasons.push(format!(
+                "afferent coupling grew {} -> {} (limit {})",
+                before_afferent, after.afferent, max_afferent
+            ));
+        }
+        if reasons.is_empty() {
+            continue;
+        }
+
+        let result = PolicyResult {
+            id: PolicyId::InstabilityRegression,
+            severity,
+            function_id: None,
+            message: format!("Stable module {}: {}", entry.module, reasons.join("; ")),
+            metadata: None,
+        };
+        match severity {
+            PolicySeverity::Blocking => results.failed.push(result),
+            PolicySeverity::Warning => results.warnings.push(result),
+        }
+    }
+}
+
 /// Evaluate Watch Threshold policy
 ///
 /// Triggers when `after.lrs` is in [watch_min, watch_max) AND `before.lrs` < watch_min
@@ -581,6 +833,12 @@ mod tests {
                 fo: 2,
                 ns: 1,
                 loc: 10,
+                sloc: None,
+                return_points: None,
+                unwrap_count: None,
+                max_arm_loc: None,
+                max_data_nesting: None,
+                unreachable_code: None,
             },
             lrs: 3.9,
             band: RiskBand::parse(band).unwrap_or(RiskBand::Low),
@@ -593,6 +851,12 @@ mod tests {
                 fo: 3,
                 ns: 1,
                 loc: 15,
+                sloc: None,
+                return_points: None,
+                unwrap_count: None,
+                max_arm_loc: None,
+                max_data_nesting: None,
+                unreachable_code: None,
             },
             lrs: if band == "critical" { 10.5 } else { 6.2 },
             band: RiskBand::parse(band).unwrap_or(RiskBand::Low),
@@ -604,6 +868,7 @@ mod tests {
             fo: 1,
             ns: 0,
             lrs,
+            driver: None,
         });
 
         FunctionDeltaEntry {
@@ -615,9 +880,285 @@ mod tests {
             band_transition: None,
             suppression_reason: None,
             rename_hint: None,
+            new_cc: None,
+            kind: None,
+        }
+    }
+
+    #[test]
+    fn test_function_too_long_only_when_crossing_limit() {
+        let mut config = ResolvedConfig::defaults().unwrap();
+        let deltas = vec![
+            // 10 -> 15 lines crosses a limit of 12
+            create_test_delta_entry(
+                "src/foo.ts::grew",
+                FunctionStatus::Modified,
+                Some("low"),
+                Some("low"),
+                Some(0.1),
+            ),
+            create_test_delta_entry(
+                "src/foo.ts::added",
+                FunctionStatus::New,
+                None,
+                Some("low"),
+                None,
+            ),
+        ];
+
+        let mut results = PolicyResults::new();
+        evaluate_function_too_long(&deltas, &config, &mut results);
+        assert!(results.warnings.is_empty(), "no limit configured");
+
+        config.set_max_function_loc(12);
+        evaluate_function_too_long(&deltas, &config, &mut results);
+        assert_eq!(results.warnings.len(), 2);
+        assert_eq!(results.warnings[0].id, PolicyId::FunctionTooLong);
+        assert!(results.warnings[0].message.contains("15 lines"));
+
+        // Already over the limit before the change: not re-flagged
+        config.set_max_function_loc(9);
+        config.function_too_long_mode = PolicyMode::Block;
+        let mut results = PolicyResults::new();
+        evaluate_function_too_long(&deltas, &config, &mut results);
+        assert_eq!(results.failed.len(), 1);
+        assert_eq!(
+            results.failed[0].function_id.as_deref(),
+            Some("src/foo.ts::added")
+        );
+    }
+
+    #[test]
+    fn test_function_too_long_uses_kind_thresholds() {
+        use crate::ast::FunctionKind;
+
+        let mut config = ResolvedConfig::defaults().unwrap();
+        let ctor = crate::patterns::Thresholds {
+            long_function_loc: 21,
+            ..crate::patterns::Thresholds::default()
+        };
+        config
+            .pattern_thresholds
+            .by_kind
+            .insert(FunctionKind::Constructor, ctor);
+        config.set_max_function_loc(12);
+
+        // Both grow from 10 to 15 lines; only the method crosses its limit
+        let mut deltas: Vec<_> = ["src/foo.ts::constructor", "src/foo.ts::method"]
+            .into_iter()
+            .map(|id| {
+                create_test_delta_entry(
+                    id,
+                    FunctionStatus::Modified,
+                    Some("low"),
+                    Some("low"),
+                    Some(0.1),
+                )
+            })
+            .collect();
+        deltas[0].kind = Some(FunctionKind::Constructor);
+        deltas[1].kind = Some(FunctionKind::Method);
+
+        let mut results = PolicyResults::new();
+        evaluate_function_too_long(&deltas, &config, &mut results);
+        assert_eq!(results.warnings.len(), 1);
+        assert_eq!(
+            results.warnings[0].function_id.as_deref(),
+            Some("src/foo.ts::method")
+        );
+    }
+
+    #[test]
+    fn test_max_new_cc_gates_on_added_complexity_only() {
+        let mut config = ResolvedConfig::defaults().unwrap();
+        let mut deltas = vec![
+            create_test_delta_entry(
+                "src/foo.ts::touched",
+                FunctionStatus::Modified,
+                Some("low"),
+                Some("low"),
+                Some(0.1),
+            ),
+            create_test_delta_entry(
+                "src/foo.ts::branchy",
+                FunctionStatus::Modified,
+                Some("low"),
+                Some("low"),
+                Some(0.1),
+            ),
+        ];
+        deltas[0].new_cc = Some(0);
+        deltas[1].new_cc = Some(3);
+
+        let mut results = PolicyResults::new();
+        evaluate_max_new_cc(&deltas, &config, &mut results);
+        assert!(results.failed.is_empty(), "no limit configured");
+
+        config.max_new_cc = Some(2);
+        evaluate_max_new_cc(&deltas, &config, &mut results);
+        assert_eq!(results.failed.len(), 1);
+        assert_eq!(results.failed[0].id, PolicyId::MaxNewCc);
+        assert_eq!(
+            results.failed[0].function_id.as_deref(),
+            Some("src/foo.ts::branchy")
+        );
+
+        config.max_new_cc_mode = PolicyMode::Warn;
+        let mut results = PolicyResults::new();
+        evaluate_max_new_cc(&deltas, &config, &mut results);
+        assert!(results.failed.is_empty());
+        assert_eq!(results.warnings.len(), 1);
+    }
+
+    #[test]
+    fn test_max_new_critical_counts_new_functions_in_band() {
+        let mut config = crate::config::HotspotsConfig::default().resolve().unwrap();
+        let deltas = vec![
+            create_test_delta_entry(
+                "src/b.ts::second",
+                FunctionStatus::New,
+                None,
+                Some("critical"),
+                None,
+            ),
+            create_test_delta_entry(
+                "src/a.ts::first",
+                FunctionStatus::New,
+                None,
+                Some("critical"),
+                None,
+            ),
+            create_test_delta_entry(
+                "src/a.ts::high",
+                FunctionStatus::New,
+                None,
+                Some("high"),
+                None,
+            ),
+            // Regressed, not new: left to critical-introduction
+            create_test_delta_entry(
+                "src/a.ts::old",
+                FunctionStatus::Modified,
+                Some("high"),
+                Some("critical"),
+                Some(2.0),
+            ),
+        ];
+
+        let mut results = PolicyResults::new();
+        evaluate_max_new_in_band(&deltas, &config, RiskBand::Critical, &mut results);
+        assert!(results.failed.is_empty(), "no limit configured");
+
+        config.max_new_critical = Some(2);
+        evaluate_max_new_in_band(&deltas, &config, RiskBand::Critical, &mut results);
+        assert!(
+            results.failed.is_empty(),
+            "two new critical functions are within the limit"
+        );
+
+        config.max_new_critical = Some(1);
+        evaluate_max_new_in_band(&deltas, &config, RiskBand::Critical, &mut results);
+        assert_eq!(results.failed.len(), 1);
+        assert_eq!(results.failed[0].id, PolicyId::MaxNewCritical);
+        assert_eq!(results.failed[0].function_id, None);
+        assert_eq!(
+            results.failed[0].message,
+            "2 new critical functions (limit 1): src/a.ts::first, src/b.ts::second"
+        );
+
+        config.max_new_high = Some(0);
+        config.max_new_high_mode = PolicyMode::Warn;
+        let mut results = PolicyResults::new();
+        evaluate_max_new_in_band(&deltas, &config, RiskBand::High, &mut results);
+        assert!(results.failed.is_empty());
+        assert_eq!(results.warnings.len(), 1);
+        assert_eq!(results.warnings[0].id, PolicyId::MaxNewHigh);
+    }
+
+    fn module_entry(
+        module: &str,
+        before: Option<(f64, usize)>,
+        after: (f64, usize),
+    ) -> crate::aggregates::ModuleDeltaEntry {
+        let state =
+            |(avg_complexity, afferent): (f64, usize)| crate::aggregates::ModuleInstability {
+                module: module.to_string(),
+                file_count: 2,
+                function_count: 4,
+                avg_function_loc: 0.0,
+                functions_per_kloc: 0.0,
+                avg_complexity,
+                afferent,
+                efferent: 0,
+                instability: 0.0,
+                module_risk: "high".to_string(),
+                test_file_count: 0,
+                test_file_ratio: 0.0,
+                test_loc_ratio: 0.0,
+            };
+        crate::aggregates::ModuleDeltaEntry {
+            module: module.to_string(),
+            before: before.map(state),
+            after: state(after),
         }
     }
 
+    #[test]
+    fn test_instability_regression_flags_stable_module_erosion() {
+        let mut config = ResolvedConfig::defaults().unwrap();
+        let delta = Delta {
+            schema_version: 1,
+            commit: DeltaCommitInfo {
+                sha: "abc123".to_string(),
+                parent: "def456".to_string(),
+            },
+            baseline: false,
+            status: crate::delta::DeltaStatus::Changes,
+            deltas: vec![],
+            policy: None,
+            aggregates: Some(crate::aggregates::DeltaAggregates {
+                files: vec![],
+                co_change_delta: vec![],
+                modules: vec![
+                    // Zone of pain, more complex
+                    module_entry("src/core", Some((12.0, 2)), (13.0, 2)),
+                    // Stable but simple: complexity growth alone is fine
+                    module_entry("src/util", Some((3.0, 1)), (4.0, 1)),
+                    // Afferent coupling crosses the limit
+                    module_entry("src/types", Some((2.0, 3)), (2.0, 5)),
+                    // Already over the limit and not growing
+                    module_entry("src/legacy", Some((2.0, 6)), (1.5, 6)),
+                ],
+            }),
+            risk_removed: None,
+        };
+
+        let mut results = PolicyResults::new();
+        evaluate_instability_regression(&delta, &config, &mut results);
+        assert!(results.failed.is_empty(), "no limit configured");
+
+        config.max_stable_afferent = Some(4);
+        evaluate_instability_regression(&delta, &config, &mut results);
+        let messages: Vec<&str> = results.failed.iter().map(|r| r.message.as_str()).collect();
+        assert_eq!(
+            messages,
+            vec![
+                "Stable module src/core: avg CC rose 12.00 -> 13.00 in the zone of pain (instability 0.00)",
+                "Stable module src/types: afferent coupling grew 3 -> 5 (limit 4)",
+            ]
+        );
+        assert!(results
+            .failed
+            .iter()
+            .all(|r| r.id == PolicyId::InstabilityRegression));
+
+        config.instability_regression_mode = PolicyMode::Warn;
+        let mut results = PolicyResults::new();
+        evaluate_instability_regression(&delta, &config, &mut results);
+        assert!(results.failed.is_empty());
+        assert_eq!(results.warnings.len(), 2);
+    }
+
     #[test]
     fn test_critical_introduction_new_function() {
         let mut results = PolicyResults::new();
@@ -757,6 +1298,11 @@ mod tests {
                 ),
                 excessive_risk_regression: None,
                 excessive_risk_regression_reason: None,
+                function_too_long: None,
+                max_new_cc: None,
+                max_new_critical: None,
+                max_new_high: None,
+                instability_regression: None,
             }),
             ..Default::default()
         };
@@ -790,6 +1336,11 @@ mod tests {
                 ),
                 excessive_risk_regression: None,
                 excessive_risk_regression_reason: None,
+                function_too_long: None,
+                max_new_cc: None,
+                max_new_critical: None,
+                max_new_high: None,
+                instability_regression: None,
             }),
             ..Default::default()
         };
@@ -851,6 +1402,11 @@ mod tests {
                 critical_introduction_reason: None,
                 excessive_risk_regression: None,
                 excessive_risk_regression_reason: None,
+                function_too_long: None,
+                max_new_cc: None,
+                max_new_critical: None,
+                max_new_high: None,
+                instability_regression: None,
             }),
             ..Default::default()
         };
@@ -865,6 +1421,11 @@ mod tests {
                 critical_introduction_reason: None,
                 excessive_risk_regression: None,
                 excessive_risk_regression_reason: None,
+                function_too_long: None,
+                max_new_cc: None,
+                max_new_critical: None,
+                max_new_high: None,
+                instability_regression: None,
             }),
             ..Default::default()
         };
@@ -880,6 +1441,11 @@ mod tests {
                 critical_introduction_reason: Some("   ".to_string()),
                 excessive_risk_regression: None,
                 excessive_risk_regression_reason: None,
+                function_too_long: None,
+                max_new_cc: None,
+                max_new_critical: None,
+                max_new_high: None,
+                instability_regression: None,
             }),
             ..Default::default()
         };
@@ -894,6 +1460,11 @@ mod tests {
                 critical_introduction_reason: None,
                 excessive_risk_regression: None,
                 excessive_risk_regression_reason: None,
+                function_too_long: None,
+                max_new_cc: None,
+                max_new_critical: None,
+                max_new_high: None,
+                instability_regression: None,
             }),
             ..Default::default()
         };
@@ -955,9 +1526,11 @@ mod tests {
                 parent: "".to_string(),
             },
             baseline: true,
+            status: crate::delta::DeltaStatus::Baseline,
             deltas: vec![],
             policy: None,
             aggregates: None,
+            risk_removed: None,
         };
 
         let temp_dir = TempDir::new().unwrap();
@@ -997,6 +1570,12 @@ mod tests {
                 fo: 2,
                 ns: 1,
                 loc: 10,
+                sloc: None,
+                return_points: None,
+                unwrap_count: None,
+                max_arm_loc: None,
+                max_data_nesting: None,
+                unreachable_code: None,
             },
             lrs,
             band: if lrs >= 9.0 {
@@ -1017,6 +1596,12 @@ mod tests {
                 fo: 3,
                 ns: 1,
                 loc: 15,
+                sloc: None,
+                return_points: None,
+                unwrap_count: None,
+                max_arm_loc: None,
+                max_data_nesting: None,
+                unreachable_code: None,
             },
             lrs,
             band: if lrs >= 9.0 {
@@ -1037,6 +1622,7 @@ mod tests {
                 fo: 1,
                 ns: 0,
                 lrs: after - before,
+                driver: None,
             }),
             _ => None,
         };
@@ -1050,6 +1636,8 @@ mod tests {
             band_transition: None,
             suppression_reason: None,
             rename_hint: None,
+            new_cc: None,
+            kind: None,
         }
     }
 
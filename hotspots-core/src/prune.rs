@@ -15,10 +15,19 @@ use std::time::{SystemTime, UNIX_EPOCH};
 
 use crate::snapshot::{self, Index};
 
+/// Local branches, including nested names like `feature/x`
+///
+/// `git for-each-ref` treats a trailing-slash pattern as a prefix, whereas a
+/// `*` glob does not cross `/`.
+const LOCAL_BRANCHES: &str = "refs/heads/";
+
+/// Remote-tracking branches (`refs/remotes/<remote>/<branch>`)
+const REMOTE_BRANCHES: &str = "refs/remotes/";
+
 /// Pruning options
 #[derive(Debug, Clone)]
 pub struct PruneOptions {
-    /// Tracked ref patterns (default: ["refs/heads/*"])
+    /// Tracked ref patterns (default: ["refs/heads/"]; see [`default_ref_patterns`])
     pub ref_patterns: Vec<String>,
     /// Only prune commits older than this many days (None = no age filter)
     pub older_than_days: Option<u64>,
@@ -29,7 +38,7 @@ pub struct PruneOptions {
 impl Default for PruneOptions {
     fn default() -> Self {
         PruneOptions {
-            ref_patterns: vec!["refs/heads/*".to_string()],
+            ref_patterns: vec![LOCAL_BRANCHES.to_string()],
             older_than_days: None,
             dry_run: false,
         }
@@ -68,7 +77,26 @@ fn git_at(repo_path: &Path, args: &[&str]) -> Result<String> {
     Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
 }
 
-/// Enumerate tracked refs (default: local branches refs/heads/*)
+/// Tracked ref patterns to use when none are given explicitly
+///
+/// Local branches, plus remote-tracking branches when the clone has at most
+/// one local branch. CI checkouts typically have a single (or no) local
+/// branch, with the rest of the history only reachable from `origin/*`;
+/// tracking local branches alone there would mark almost every snapshot
+/// unreachable.
+pub fn default_ref_patterns(repo_path: &Path) -> Result<Vec<String>> {
+    let local = git_at(
+        repo_path,
+        &["for-each-ref", "--format=%(refname)", LOCAL_BRANCHES],
+    )?;
+    let mut patterns = vec![LOCAL_BRANCHES.to_string()];
+    if local.lines().filter(|l| !l.trim().is_empty()).count() <= 1 {
+        patterns.push(REMOTE_BRANCHES.to_string());
+    }
+    Ok(patterns)
+}
+
+/// Enumerate tracked refs
 ///
 /// Returns a list of commit SHAs pointed to by the tracked refs.
 fn enumerate_tracked_refs(repo_path: &Path, patterns: &[String]) -> Result<Vec<String>> {
@@ -218,6 +246,7 @@ fn delete_pruned_snapshots(
 ///
 /// Returns error if:
 /// - Git commands fail
+/// - No tracked ref pattern matches a ref
 /// - Snapshot files cannot be read/written
 /// - Index cannot be updated
 pub fn prune_unreachable(repo_path: &Path, options: PruneOptions) -> Result<PruneResult> {
@@ -230,6 +259,13 @@ pub fn prune_unreachable(repo_path: &Path, options: PruneOptions) -> Result<Prun
 
     let tracked_ref_shas = enumerate_tracked_refs(repo_path, &options.ref_patterns)
         .context("failed to enumerate tracked refs")?;
+    if tracked_ref_shas.is_empty() {
+        // With nothing to start from, every snapshot would look unreachable
+        anyhow::bail!(
+            "no refs match {}; refusing to prune (pass --refs to choose tracked refs)",
+            options.ref_patterns.join(", ")
+        );
+    }
     let reachable_shas = compute_reachable_commits(repo_path, &tracked_ref_shas)
         .context("failed to compute reachable commits")?;
     let cutoff_timestamp = compute_cutoff_timestamp(options.older_than_days);
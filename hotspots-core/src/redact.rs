@@ -0,0 +1,149 @@
+//! Identifier redaction for sharing metrics outside the organization
+//!
+//! `--redact` replaces file paths and function names with salted hashes while
+//! leaving every metric intact. Hashes are keyed SipHash-2-4 over the
+//! project-relative path or symbol, with the key derived from the configured
+//! `redact_salt`, so the same salt yields the same identifiers across runs and
+//! machines.
+//!
+//! Global invariants enforced:
+//! - Runs as a post-processing pass on reports/snapshots, after scoring
+//! - Redacted snapshots are never persisted
+//! - File extensions are kept so the language of each file stays visible
+
+use crate::report::FunctionRiskReport;
+use crate::snapshot::Snapshot;
+use siphasher::sip::SipHasher24;
+use siphasher::sip128::{Hasher128, SipHasher24 as SipHasher128};
+use std::hash::Hasher;
+use std::path::{Path, PathBuf};
+
+/// Replaces identifiers with stable salted hashes
+#[derive(Debug, Clone)]
+pub struct Redactor {
+    hasher: SipHasher24,
+    project_root: PathBuf,
+}
+
+impl Redactor {
+    /// Create a redactor keyed by `salt`; paths are hashed relative to `project_root`
+    pub fn new(salt: &str, project_root: &Path) -> Self {
+        let mut key_hasher = SipHasher128::new();
+        key_hasher.write(salt.as_bytes());
+        Redactor {
+            hasher: SipHasher24::new_with_key(&key_hasher.finish128().as_bytes()),
+            project_root: project_root.to_path_buf(),
+        }
+    }
+
+    /// Hash an arbitrary identifier to 16 hex characters
+    pub fn hash(&self, value: &str) -> String {
+        format!("{:016x}", self.hasher.hash(value.as_bytes()))
+    }
+
+    /// Hash a file path, relative to the project root, keeping its extension
+    pub fn path(&self, path: &str) -> String {
+        let rel = Path::new(path)
+            .strip_prefix(&self.project_root)
+            .map(|p| p.to_string_lossy().replace('\\', "/"))
+            .unwrap_or_else(|_| path.replace('\\', "/"));
+        let hashed = self.hash(&rel);
+        match Path::new(&rel).extension() {
+            Some(ext) => format!("{}.{}", hashed, ext.to_string_lossy()),
+            None => hashed,
+        }
+    }
+
+    /// Redact file and function names in analysis reports
+    pub fn redact_reports(&self, reports: &mut [FunctionRiskReport]) {
+        for report in reports {
+            report.set_file(self.path(&report.file));
+            report.set_function(self.hash(&report.function));
+            report.callees.clear();
+        }
+    }
+
+    /// Redact identifiers in a snapshot and its aggregates
+    ///
+    /// Covers function IDs, files, subsystems, directories, modules, co-change
+    /// pairs, and top authors. Commit metadata that can carry names (branch,
+    /// message, author, ticket IDs) is dropped, as is the model risk map.
+    pub fn redact_snapshot(&self, snapshot: &mut Snapshot) {
+        let commit = &mut snapshot.commit;
+        commit.branch = None;
+        commit.message = None;
+        commit.author = None;
+        commit.ticket_ids.clear();
+
+        for func in &mut snapshot.functions {
+            let symbol = func
+                .function_id
+                .strip_prefix(func.file.as_str())
+                .and_then(|s| s.strip_prefix("::"))
+                .unwrap_or(&func.function_id);
+            let symbol = self.hash(symbol);
+            func.file = self.path(&func.file);
+            func.function_id = format!("{}::{}", func.file, symbol);
+            if let Some(location) = func.location.as_mut() {
+                location.file = func.file.clone();
+                location.symbol = symbol;
+            }
+            if let Some(subsystem) = func.subsystem.as_mut().filter(|s| !s.is_empty()) {
+                *subsystem = self.hash(subsystem);
+            }
+        }
+        snapshot
+            .functions
+            .sort_by(|a, b| a.function_id.cmp(&b.function_id));
+
+        let Some(aggregates) = snapshot.aggregates.as_mut() else {
+            return;
+        };
+        for file in &mut aggregates.files {
+            file.file = self.path(&file.file);
+        }
+        for dir in &mut aggregates.directories {
+            dir.directory = self.path(&dir.directory);
+        }
+        for view in &mut aggregates.file_risk {
+            view.file = self.path(&view.file);
+            if let Some(author) = view.top_author.as_mut() {
+                *author = self.hash(author);
+            }
+        }
+        for pair in &mut aggregates.co_change {
+            pair.file_a = self.path(&pair.file_a);
+            pair.file_b = self.path(&pair.file_b);
+        }
+        for module in &mut aggregates.modules {
+            module.module = self.path(&module.module);
+        }
+        aggregates.models = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hashes_are_stable_per_salt() {
+        let root = Path::new("/repo");
+        let a = Redactor::new("s3cret", root);
+        let b = Redactor::new("s3cret", root);
+        let other = Redactor::new("different", root);
+        assert_eq!(a.hash("processPayment"), b.hash("processPayment"));
+        assert_ne!(a.hash("processPayment"), other.hash("processPayment"));
+        assert_eq!(a.hash("processPayment").len(), 16);
+    }
+
+    #[test]
+    fn test_path_is_relative_to_root_and_keeps_extension() {
+        let local = Redactor::new("salt", Path::new("/home/dev/repo"))
+            .path("/home/dev/repo/src/billing.ts");
+        let ci = Redactor::new("salt", Path::new("/ci/work")).path("/ci/work/src/billing.ts");
+        assert_eq!(local, ci);
+        assert!(local.ends_with(".ts"));
+        assert!(!local.contains("billing"));
+    }
+}
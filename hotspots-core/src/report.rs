@@ -4,10 +4,12 @@
 //! - Deterministic output ordering
 //! - Byte-for-byte identical output across runs
 
-use crate::ast::FunctionNode;
+use crate::ast::{FunctionKind, FunctionNode};
 use crate::language::Language;
 use crate::metrics::RawMetrics;
 use crate::risk::{RiskBand, RiskComponents};
+#[cfg(feature = "native")]
+use crate::snapshot::Snapshot;
 use owo_colors::OwoColorize;
 use serde::{Deserialize, Serialize};
 
@@ -33,6 +35,32 @@ pub struct FunctionRiskReport {
     pub callees: Vec<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub explanation: Option<String>,
+    /// Where the function lives, for tools linking results back to source.
+    /// Absent in reports and snapshots produced before this field existed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub location: Option<Location>,
+}
+
+/// Source location of a reported function
+///
+/// `file` and `symbol` always match the enclosing report's `file` and
+/// `function`; `start_line` and `end_line` are 1-based and inclusive.
+/// `start_col` and `end_col` are 1-based and count UTF-16 code units, as SARIF
+/// regions do; `end_col` is the column just past the function's last
+/// character on `end_line`. LSP positions are the same columns minus one.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Location {
+    pub file: String,
+    pub start_line: u32,
+    pub end_line: u32,
+    /// Absent in reports and snapshots produced before this field existed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub start_col: Option<u32>,
+    /// Absent in reports and snapshots produced before this field existed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub end_col: Option<u32>,
+    pub symbol: String,
+    pub kind: FunctionKind,
 }
 
 /// Metrics in report format
@@ -43,6 +71,46 @@ pub struct MetricsReport {
     pub fo: u32,
     pub ns: u32,
     pub loc: u32,
+    /// Source lines of code: `loc` minus blank, comment-only, and brace-only lines.
+    /// Absent in reports and snapshots produced before this field existed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sloc: Option<u32>,
+    /// Points where control leaves the function: returns, throws/raise/panic,
+    /// Rust `?`, and the fall-through end. Absent in older reports and snapshots.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub return_points: Option<u32>,
+    /// Rust only: `.unwrap()`/`.expect()` calls and panicking macros.
+    /// Informational unless `weights.unwrap` is configured.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub unwrap_count: Option<u32>,
+    /// Lines spanned by the longest switch case / match arm; absent when the
+    /// function has none.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_arm_loc: Option<u32>,
+    /// JS/TS only: deepest nesting of object/array literals; absent when the
+    /// function builds none.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_data_nesting: Option<u32>,
+    /// Statements no path from the function's entry reaches (code after an
+    /// unconditional return/throw/break/continue); absent when there are none
+    /// or the language's CFG builder doesn't track them.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub unreachable_code: Option<u32>,
+}
+
+impl MetricsReport {
+    /// Size used by size-sensitive formulas: `sloc` when available, else `loc`.
+    pub fn effective_loc(&self) -> u32 {
+        self.sloc.unwrap_or(self.loc)
+    }
+
+    /// Complexity concentration: `cc` per source line (`cc / effective_loc`).
+    ///
+    /// Ranks a dense 30-line CC=20 function above a sprawling 300-line one.
+    /// Derived on demand; never stored in reports or snapshots.
+    pub fn cc_density(&self) -> f64 {
+        self.cc as f64 / self.effective_loc().max(1) as f64
+    }
 }
 
 /// Risk components in report format
@@ -61,6 +129,12 @@ pub struct RiskReport {
 /// Grouped analysis results for constructing a FunctionRiskReport
 pub struct FunctionAnalysis {
     pub metrics: RawMetrics,
+    pub sloc: usize,
+    pub return_points: usize,
+    pub unwrap_count: Option<usize>,
+    pub max_arm_loc: Option<usize>,
+    pub max_data_nesting: Option<usize>,
+    pub unreachable_code: Option<usize>,
     pub risk: RiskComponents,
     pub lrs: f64,
     pub band: RiskBand,
@@ -68,29 +142,60 @@ pub struct FunctionAnalysis {
 }
 
 impl FunctionRiskReport {
+    /// Whether this report is for an anonymous function (`<anonymous@LINE>`,
+    /// possibly qualified by its enclosing function)
+    pub fn is_anonymous(&self) -> bool {
+        crate::callgraph::is_anonymous(&self.function)
+    }
+
+    /// Rename the reported function, keeping `location` in step
+    pub fn set_function(&mut self, function: String) {
+        if let Some(location) = self.location.as_mut() {
+            location.symbol = function.clone();
+        }
+        self.function = function;
+    }
+
+    /// Move the report to another file path, keeping `location` in step
+    pub fn set_file(&mut self, file: String) {
+        if let Some(location) = self.location.as_mut() {
+            location.file = file.clone();
+        }
+        self.file = file;
+    }
+
     /// Create a new function risk report
+    ///
+    /// `source_lines` are the lines of the analyzed file, used to turn the
+    /// span's byte columns into UTF-16 columns.
     pub fn new(
         function: &FunctionNode,
         file: String,
         language: Language,
         analysis: FunctionAnalysis,
         source_map: &swc_common::SourceMap,
+        source_lines: &[&str],
     ) -> Self {
         let line = function.start_line(source_map);
-        let display_file = std::env::current_dir()
-            .ok()
-            .and_then(|cwd| {
-                std::path::Path::new(&file)
-                    .strip_prefix(&cwd)
-                    .ok()
-                    .map(|p| p.to_string_lossy().into_owned())
-            })
-            .unwrap_or_else(|| file.clone());
         let function_name = function
             .name
-            .as_deref()
-            .unwrap_or(&format!("<anonymous>@{}:{}", display_file, line))
-            .to_string();
+            .clone()
+            .unwrap_or_else(|| format!("<anonymous@{line}>"));
+
+        let end_line = function.span.end_line.max(line);
+        let column = |line: u32, byte_col: u32| {
+            let text = source_lines.get((line as usize).checked_sub(1)?)?;
+            Some(crate::language::span::utf16_column(text, byte_col))
+        };
+        let location = Location {
+            file: file.clone(),
+            start_line: line,
+            end_line,
+            start_col: column(line, function.span.start_col),
+            end_col: column(end_line, function.span.end_col),
+            symbol: function_name.clone(),
+            kind: function.kind,
+        };
 
         FunctionRiskReport {
             file,
@@ -103,6 +208,12 @@ impl FunctionRiskReport {
                 fo: analysis.metrics.fo as u32,
                 ns: analysis.metrics.ns as u32,
                 loc: analysis.metrics.loc as u32,
+                sloc: Some(analysis.sloc as u32),
+                return_points: Some(analysis.return_points as u32),
+                unwrap_count: analysis.unwrap_count.map(|n| n as u32),
+                max_arm_loc: analysis.max_arm_loc.map(|n| n as u32),
+                max_data_nesting: analysis.max_data_nesting.map(|n| n as u32),
+                unreachable_code: analysis.unreachable_code.map(|n| n as u32),
             },
             risk: RiskReport {
                 r_cc: analysis.risk.r_cc,
@@ -117,6 +228,7 @@ impl FunctionRiskReport {
             pattern_details: None,
             callees: analysis.metrics.callee_names,
             explanation: None,
+            location: Some(location),
         }
     }
 }
@@ -341,6 +453,84 @@ pub fn render_json(reports: &[FunctionRiskReport]) -> String {
     serde_json::to_string_pretty(reports).unwrap_or_else(|_| "[]".to_string())
 }
 
+/// Headline number shown on a README badge
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BadgeMetric {
+    /// Functions in the critical or high band
+    CriticalCount,
+    /// Total activity risk across all functions
+    ActivityRisk,
+}
+
+/// Render a shields.io-style SVG badge for a snapshot
+///
+/// The badge is self-contained (no fonts or external references) and
+/// byte-for-byte identical for the same snapshot. Colour follows the risk
+/// bands: the critical+high count is green at zero and red past 20, while
+/// activity risk is coloured by the band its per-function mean falls in under
+/// `thresholds`.
+#[cfg(feature = "native")]
+pub fn render_badge_svg(
+    snapshot: &Snapshot,
+    metric: BadgeMetric,
+    thresholds: &crate::risk::RiskThresholds,
+) -> String {
+    let functions = &snapshot.functions;
+    let (label, value, band) = match metric {
+        BadgeMetric::CriticalCount => {
+            let count = functions
+                .iter()
+                .filter(|f| f.band >= RiskBand::High)
+                .count();
+            let band = match count {
+                0 => RiskBand::Low,
+                1..=5 => RiskBand::Moderate,
+                6..=20 => RiskBand::High,
+                _ => RiskBand::Critical,
+            };
+            ("critical+high", count.to_string(), band)
+        }
+        BadgeMetric::ActivityRisk => {
+            let total: f64 = functions
+                .iter()
+                .map(|f| f.activity_risk.unwrap_or(f.lrs))
+                .sum();
+            let mean = total / functions.len().max(1) as f64;
+            (
+                "activity risk",
+                format!("{total:.1}"),
+                crate::risk::assign_risk_band_with_thresholds(mean, thresholds),
+            )
+        }
+    };
+    let color = match band {
+        RiskBand::Low => "#4c1",
+        RiskBand::Moderate => "#dfb317",
+        RiskBand::High => "#fe7d37",
+        RiskBand::Critical => "#e05d44",
+    };
+
+    // Approximate Verdana 11px glyph width; shields.io pads 5px on each side
+    let label_width = label.chars().count() * 7 + 10;
+    let value_width = value.chars().count() * 7 + 10;
+    let width = label_width + value_width;
+    let label_x = label_width / 2;
+    let value_x = label_width + value_width / 2;
+    format!(
+        r##"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="20" role="img" aria-label="{label}: {value}">
+<title>{label}: {value}</title>
+<linearGradient id="s" x2="0" y2="100%"><stop offset="0" stop-color="#bbb" stop-opacity=".1"/><stop offset="1" stop-opacity=".1"/></linearGradient>
+<clipPath id="r"><rect width="{width}" height="20" rx="3" fill="#fff"/></clipPath>
+<g clip-path="url(#r)"><rect width="{label_width}" height="20" fill="#555"/><rect x="{label_width}" width="{value_width}" height="20" fill="{color}"/><rect width="{width}" height="20" fill="url(#s)"/></g>
+<g fill="#fff" text-anchor="middle" font-family="Verdana,Geneva,DejaVu Sans,sans-serif" font-size="11">
+<text x="{label_x}" y="15" fill="#010101" fill-opacity=".3">{label}</text><text x="{label_x}" y="14">{label}</text>
+<text x="{value_x}" y="15" fill="#010101" fill-opacity=".3">{value}</text><text x="{value_x}" y="14">{value}</text>
+</g>
+</svg>
+"##
+    )
+}
+
 /// Truncate or pad string to fixed width
 fn truncate_or_pad(s: &str, width: usize) -> String {
     if s.len() > width {
@@ -368,6 +558,12 @@ mod tests {
                 fo: 2,
                 ns: 0,
                 loc: 20,
+                sloc: None,
+                return_points: None,
+                unwrap_count: None,
+                max_arm_loc: None,
+                max_data_nesting: None,
+                unreachable_code: None,
             },
             risk: RiskReport {
                 r_cc: 1.0,
@@ -382,9 +578,67 @@ mod tests {
             pattern_details: None,
             callees: vec![],
             explanation: None,
+            location: None,
         }
     }
 
+    #[test]
+    #[cfg(feature = "native")]
+    fn test_render_badge_svg_counts_critical_and_high() {
+        let git_context = crate::git::GitContext {
+            head_sha: "abc123".to_string(),
+            parent_shas: vec![],
+            timestamp: 0,
+            branch: None,
+            is_detached: false,
+            message: None,
+            author: None,
+            is_fix_commit: None,
+            is_revert_commit: None,
+            ticket_ids: vec![],
+        };
+        let mut low = make_report("src/a.ts", "ok", 1, 1.0);
+        low.band = RiskBand::Low;
+        let reports = vec![make_report("src/a.ts", "risky", 10, 7.0), low];
+        let snapshot = Snapshot::new(git_context, reports);
+
+        let thresholds = crate::risk::RiskThresholds::default();
+        let svg = render_badge_svg(&snapshot, BadgeMetric::CriticalCount, &thresholds);
+        assert!(svg.starts_with("<svg"));
+        assert!(svg.contains("<title>critical+high: 1</title>"));
+        assert!(svg.contains("#dfb317"), "1-5 functions is yellow");
+        assert_eq!(
+            svg,
+            render_badge_svg(&snapshot, BadgeMetric::CriticalCount, &thresholds)
+        );
+
+        let svg = render_badge_svg(&snapshot, BadgeMetric::ActivityRisk, &thresholds);
+        assert!(svg.contains("<title>activity risk: 8.0</title>"));
+        assert!(
+            svg.contains("#dfb317"),
+            "a mean of 4.0 is moderate by default"
+        );
+
+        // Configured thresholds move the mean into another band
+        let strict = crate::risk::RiskThresholds {
+            moderate: 1.0,
+            high: 2.0,
+            critical: 3.0,
+        };
+        let svg = render_badge_svg(&snapshot, BadgeMetric::ActivityRisk, &strict);
+        assert!(svg.contains("#e05d44"));
+    }
+
+    #[test]
+    fn test_cc_density_prefers_sloc() {
+        let mut metrics = make_report("a.ts", "f", 1, 1.0).metrics;
+        assert_eq!(metrics.cc_density(), 0.25);
+        metrics.sloc = Some(10);
+        assert_eq!(metrics.cc_density(), 0.5);
+        metrics.sloc = Some(0);
+        assert_eq!(metrics.cc_density(), 5.0);
+    }
+
     #[test]
     fn test_render_text_grouped_groups_by_band() {
         let mut critical = make_report("/repo/src/a.ts", "foo", 10, 12.0);
@@ -76,6 +76,8 @@ pub struct LrsWeights {
     pub nd: f64,
     pub fo: f64,
     pub ns: f64,
+    /// Rust unwrap/expect/panic sites; 0.0 keeps `unwrap_count` out of LRS
+    pub unwrap: f64,
 }
 
 impl Default for LrsWeights {
@@ -85,6 +87,7 @@ impl Default for LrsWeights {
             nd: 0.8,
             fo: 0.6,
             ns: 0.7,
+            unwrap: 0.0,
         }
     }
 }
@@ -128,6 +131,13 @@ pub fn assign_risk_band(lrs: f64) -> RiskBand {
     assign_risk_band_with_thresholds(lrs, &RiskThresholds::default())
 }
 
+/// Risk transform for `unwrap_count`: R_unwrap = min(log2(N + 1), 6)
+///
+/// Added to LRS as `weights.unwrap * R_unwrap`, which is zero by default.
+pub fn unwrap_risk(unwrap_count: usize) -> f64 {
+    (unwrap_count as f64 + 1.0).log2().min(6.0)
+}
+
 /// Assign risk band with custom thresholds
 pub fn assign_risk_band_with_thresholds(lrs: f64, thresholds: &RiskThresholds) -> RiskBand {
     if lrs < thresholds.moderate {
@@ -141,6 +151,14 @@ pub fn assign_risk_band_with_thresholds(lrs: f64, thresholds: &RiskThresholds) -
     }
 }
 
+/// Local Risk Score of raw metrics with default weights
+///
+/// Shorthand for [`calculate_lrs`] of [`calculate_risk_components`]; the
+/// unwrap term, which needs the function source, is not included.
+pub fn compute_lrs(metrics: &RawMetrics) -> f64 {
+    calculate_lrs(&calculate_risk_components(metrics))
+}
+
 /// Calculate complete risk analysis from raw metrics (default weights/thresholds)
 pub fn analyze_risk(metrics: &RawMetrics) -> (RiskComponents, f64, RiskBand) {
     let risk = calculate_risk_components(metrics);
@@ -100,10 +100,17 @@ struct SarifArtifact {
     uri_base_id: &'static str,
 }
 
+/// Columns are 1-based UTF-16 code units, SARIF's default `columnKind`
 #[derive(Serialize)]
 struct SarifRegion {
     #[serde(rename = "startLine")]
     start_line: u32,
+    #[serde(rename = "startColumn", skip_serializing_if = "Option::is_none")]
+    start_column: Option<u32>,
+    #[serde(rename = "endLine", skip_serializing_if = "Option::is_none")]
+    end_line: Option<u32>,
+    #[serde(rename = "endColumn", skip_serializing_if = "Option::is_none")]
+    end_column: Option<u32>,
 }
 
 fn rules() -> Vec<SarifRule> {
@@ -180,7 +187,10 @@ pub fn render_sarif(snapshot: &Snapshot, repo_root: &Path) -> String {
                 _ => return None,
             };
 
-            let name = f.function_id.rsplit("::").next().unwrap_or("<anonymous>");
+            let name = f
+                .function_id
+                .split_once("::")
+                .map_or("<anonymous>", |(_, name)| name);
             let lrs = f.lrs;
             let cc = f.metrics.cc;
 
@@ -201,6 +211,9 @@ pub fn render_sarif(snapshot: &Snapshot, repo_root: &Path) -> String {
                         },
                         region: SarifRegion {
                             start_line: f.line.max(1),
+                            start_column: f.location.as_ref().and_then(|l| l.start_col),
+                            end_line: f.location.as_ref().map(|l| l.end_line),
+                            end_column: f.location.as_ref().and_then(|l| l.end_col),
                         },
                     },
                 }],
@@ -269,6 +282,12 @@ mod tests {
                 fo: 0,
                 ns: 0,
                 loc: 10,
+                sloc: None,
+                return_points: None,
+                unwrap_count: None,
+                max_arm_loc: None,
+                max_data_nesting: None,
+                unreachable_code: None,
             },
             lrs,
             band: crate::risk::RiskBand::parse(band).unwrap_or(crate::risk::RiskBand::Low),
@@ -297,7 +316,12 @@ mod tests {
             isolation_rate: None,
             age_days: None,
             last_touch_days: None,
+            function_age_days: None,
+            coverage: None,
+            priority_score: None,
+            refactor_leverage: None,
             explanation: None,
+            location: None,
         }
     }
 
@@ -364,6 +388,32 @@ mod tests {
         assert_eq!(uri, "src/main.rs");
     }
 
+    #[test]
+    fn test_sarif_region_carries_columns_from_location() {
+        let mut function = make_function("/repo/src/main.rs", "my_fn", "high", 7.0, 10);
+        let json = render_sarif(&make_snapshot(vec![function.clone()]), Path::new("/repo"));
+        let val: serde_json::Value = serde_json::from_str(&json).unwrap();
+        let region = &val["runs"][0]["results"][0]["locations"][0]["physicalLocation"]["region"];
+        assert_eq!(region, &serde_json::json!({ "startLine": 10 }));
+
+        function.location = Some(crate::report::Location {
+            file: "src/main.rs".to_string(),
+            start_line: 10,
+            end_line: 19,
+            start_col: Some(5),
+            end_col: Some(2),
+            symbol: "my_fn".to_string(),
+            kind: crate::ast::FunctionKind::Function,
+        });
+        let json = render_sarif(&make_snapshot(vec![function]), Path::new("/repo"));
+        let val: serde_json::Value = serde_json::from_str(&json).unwrap();
+        let region = &val["runs"][0]["results"][0]["locations"][0]["physicalLocation"]["region"];
+        assert_eq!(
+            region,
+            &serde_json::json!({ "startLine": 10, "startColumn": 5, "endLine": 19, "endColumn": 2 })
+        );
+    }
+
     #[test]
     fn test_sarif_rules_present() {
         let snapshot = make_snapshot(vec![]);
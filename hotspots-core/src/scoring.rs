@@ -6,7 +6,7 @@
 use serde::{Deserialize, Serialize};
 
 /// Weights for computing activity-weighted risk score
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
 pub struct ScoringWeights {
     pub churn: f64,
     pub touch: f64,
@@ -19,6 +19,10 @@ pub struct ScoringWeights {
     /// burst/ownership term the formula previously lacked outperforms the
     /// unweighted baseline by mean ΔAUC +0.116 across 10 validated repos).
     pub burst: f64,
+    /// Share of the call graph factors (fan-in, SCC, depth, neighbor churn)
+    /// removed in proportion to a function's name-only edges. 0.0 leaves
+    /// them untouched; 1.0 drops them entirely for a fully heuristic function.
+    pub heuristic_discount: f64,
 }
 
 impl Default for ScoringWeights {
@@ -32,6 +36,7 @@ impl Default for ScoringWeights {
             depth: 0.1,
             neighbor_churn: 0.2,
             burst: 0.3,
+            heuristic_discount: 0.0,
         }
     }
 }
@@ -65,6 +70,8 @@ pub struct ActivityRiskInput {
     /// Sliding 30-day-window max/mean commit ratio (F93). Higher values indicate
     /// a burst of frantic commit activity rather than steady, spread-out changes.
     pub burst_score: Option<f64>,
+    /// Share of call graph edges resolved with evidence (None = all of them)
+    pub edge_confidence: Option<f64>,
 }
 
 /// Compute activity-weighted risk score
@@ -98,9 +105,13 @@ pub fn compute_activity_risk(
         0.0
     };
 
+    // Call graph factors shrink with the share of edges resolved by name alone
+    let graph_scale =
+        1.0 - weights.heuristic_discount * (1.0 - input.edge_confidence.unwrap_or(1.0));
+
     // Fan-in factor: min(fan_in / 5, 10.0)
     let fan_in_score = if let Some(fi) = input.fan_in {
-        ((fi as f64 / 5.0).min(10.0)) * weights.fan_in
+        ((fi as f64 / 5.0).min(10.0)) * weights.fan_in * graph_scale
     } else {
         0.0
     };
@@ -108,7 +119,7 @@ pub fn compute_activity_risk(
     // SCC penalty: scc_size if > 1, else 0
     let scc_score = if let Some(size) = input.scc_size {
         if size > 1 {
-            (size as f64) * weights.scc
+            (size as f64) * weights.scc * graph_scale
         } else {
             0.0
         }
@@ -118,14 +129,14 @@ pub fn compute_activity_risk(
 
     // Depth penalty: min(dependency_depth / 3, 5.0)
     let depth_score = if let Some(depth) = input.dependency_depth {
-        ((depth as f64 / 3.0).min(5.0)) * weights.depth
+        ((depth as f64 / 3.0).min(5.0)) * weights.depth * graph_scale
     } else {
         0.0
     };
 
     // Neighbor churn factor: neighbor_churn / 500
     let neighbor_churn_score = if let Some(nc) = input.neighbor_churn {
-        (nc as f64 / 500.0) * weights.neighbor_churn
+        (nc as f64 / 500.0) * weights.neighbor_churn * graph_scale
     } else {
         0.0
     };
@@ -165,6 +176,67 @@ pub fn compute_activity_risk(
     (activity_risk, risk_factors)
 }
 
+/// Weights for the `priority_score` triage ranking (configurable under `priority`)
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct PriorityWeights {
+    /// Share (0.0–1.0) of the score a fully covered function gives up; 1.0
+    /// scales by `1 - coverage`
+    pub coverage: f64,
+    /// Boost for a function changed today, halving every `recency_half_life_days`
+    pub recency: f64,
+    /// Boost for 10 or more commits in the last 30 days, scaled down linearly below that
+    pub churn: f64,
+    pub recency_half_life_days: f64,
+}
+
+impl Default for PriorityWeights {
+    fn default() -> Self {
+        PriorityWeights {
+            coverage: 1.0,
+            recency: 1.0,
+            churn: 1.0,
+            recency_half_life_days: 30.0,
+        }
+    }
+}
+
+/// Compute the "fix this first" priority score
+///
+/// Multiplies `risk` (activity risk, or LRS without git data) by an untested
+/// factor and an activity factor, so risky, untested, actively changing code
+/// ranks highest. Missing coverage or history leaves its factor at 1.0.
+pub fn compute_priority_score(
+    risk: f64,
+    coverage: Option<f64>,
+    days_since_last_change: Option<u32>,
+    touch_count_30d: Option<usize>,
+    weights: &PriorityWeights,
+) -> f64 {
+    let untested = coverage.map_or(1.0, |c| 1.0 - weights.coverage * c.clamp(0.0, 1.0));
+    let recency = days_since_last_change.map_or(0.0, |days| {
+        0.5_f64.powf(days as f64 / weights.recency_half_life_days)
+    });
+    let churn = touch_count_30d.map_or(0.0, |touches| (touches as f64 / 10.0).min(1.0));
+    risk * untested * (1.0 + weights.recency * recency + weights.churn * churn)
+}
+
+/// Compute the refactor leverage ("ROI") of a function
+///
+/// `risk × log2(2 + fan_in) × log2(2 + touch_count_30d)`: improving a risky
+/// function pays off across every caller and every upcoming change to it.
+/// Both factors grow logarithmically so one heavily shared utility doesn't
+/// drown out everything else, and each is 1.0 at zero (or without data), which
+/// leaves the score at `risk`.
+pub fn compute_refactor_leverage(
+    risk: f64,
+    fan_in: Option<usize>,
+    touch_count_30d: Option<usize>,
+) -> f64 {
+    let reach = (2.0 + fan_in.unwrap_or(0) as f64).log2();
+    let activity = (2.0 + touch_count_30d.unwrap_or(0) as f64).log2();
+    risk * reach * activity
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -182,6 +254,7 @@ mod tests {
                 dependency_depth: None,
                 neighbor_churn: None,
                 burst_score: None,
+                edge_confidence: None,
             },
             &ScoringWeights::default(),
         );
@@ -205,6 +278,7 @@ mod tests {
                 dependency_depth: None,
                 neighbor_churn: None,
                 burst_score: None,
+                edge_confidence: None,
             },
             &ScoringWeights::default(),
         );
@@ -227,6 +301,7 @@ mod tests {
                 dependency_depth: Some(9),       // depth 9
                 neighbor_churn: Some(1000),      // 1000 neighbor churn
                 burst_score: None,
+                edge_confidence: None,
             },
             &ScoringWeights::default(),
         );
@@ -260,6 +335,7 @@ mod tests {
             dependency_depth: None,
             neighbor_churn: None,
             burst_score: None,
+            edge_confidence: None,
         };
 
         let (risk_without_burst, factors_without_burst) =
@@ -278,4 +354,80 @@ mod tests {
         // (4.0 - 1.0) * 0.3 = 0.9
         assert!((factors_with_burst.burst - 0.9).abs() < 0.001);
     }
+
+    #[test]
+    fn test_heuristic_discount_scales_call_graph_factors() {
+        let input = ActivityRiskInput {
+            lrs: 10.0,
+            churn: None,
+            touch_count_30d: None,
+            days_since_last_change: None,
+            fan_in: Some(25),
+            scc_size: Some(3),
+            dependency_depth: None,
+            neighbor_churn: None,
+            burst_score: None,
+            edge_confidence: Some(0.5),
+        };
+
+        // Off by default: confidence is recorded but ignored
+        let (_, undiscounted) = compute_activity_risk(&input, &ScoringWeights::default());
+        assert!((undiscounted.fan_in - 2.0).abs() < 0.001);
+
+        // Half the edges heuristic, discount 0.8: factors scaled by 1 - 0.8 * 0.5 = 0.6
+        let weights = ScoringWeights {
+            heuristic_discount: 0.8,
+            ..ScoringWeights::default()
+        };
+        let (risk, factors) = compute_activity_risk(&input, &weights);
+        assert!((factors.fan_in - 1.2).abs() < 0.001);
+        assert!((factors.cyclic_dependency - 0.54).abs() < 0.001);
+        assert!((risk - 11.74).abs() < 0.001);
+
+        // Fully resolved functions are unaffected
+        let (_, resolved) = compute_activity_risk(
+            &ActivityRiskInput {
+                edge_confidence: None,
+                ..input
+            },
+            &weights,
+        );
+        assert!((resolved.fan_in - 2.0).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_priority_score_ranks_untested_active_code_first() {
+        let weights = PriorityWeights::default();
+        // No coverage or history: the score is the risk itself
+        assert_eq!(compute_priority_score(8.0, None, None, None, &weights), 8.0);
+        // Untested, changed today, 10 touches in 30 days: 8 × 1 × (1 + 1 + 1)
+        assert_eq!(
+            compute_priority_score(8.0, Some(0.0), Some(0), Some(10), &weights),
+            24.0
+        );
+        // Half covered, one half-life since the last change, 5 touches
+        assert_eq!(
+            compute_priority_score(8.0, Some(0.5), Some(30), Some(5), &weights),
+            8.0
+        );
+        // Fully covered code drops out under the default coverage weight
+        assert_eq!(
+            compute_priority_score(8.0, Some(1.0), Some(0), Some(10), &weights),
+            0.0
+        );
+    }
+
+    #[test]
+    fn test_refactor_leverage_grows_with_fan_in_and_touches() {
+        // No callers or history: the score is the risk itself
+        assert_eq!(compute_refactor_leverage(5.0, None, None), 5.0);
+        assert_eq!(compute_refactor_leverage(5.0, Some(0), Some(0)), 5.0);
+        // 6 callers and 2 touches: 5 × log2(8) × log2(4)
+        assert_eq!(compute_refactor_leverage(5.0, Some(6), Some(2)), 30.0);
+        // A widely shared, busy function outranks a riskier isolated one
+        assert!(
+            compute_refactor_leverage(4.0, Some(30), Some(6))
+                > compute_refactor_leverage(10.0, Some(0), Some(0))
+        );
+    }
 }
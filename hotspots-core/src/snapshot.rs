@@ -9,14 +9,15 @@
 //! - Paths normalized to `/` (forward slashes only)
 //! - ASCII lexical ordering (not locale-aware)
 
+use crate::diagnostics::{self, Level};
 use crate::git::GitContext;
 use crate::language::Language;
-use crate::report::{FunctionRiskReport, MetricsReport};
+use crate::report::{FunctionRiskReport, Location, MetricsReport};
 use crate::risk::RiskBand;
 use anyhow::{Context, Result};
 use rayon::prelude::*;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
 
 #[cfg(test)]
@@ -93,6 +94,35 @@ pub struct AnalysisInfo {
     pub tool_version: String,
 }
 
+/// `(major, minor)` of a `tool_version`, or None if it doesn't start `X.Y`
+fn release_line(version: &str) -> Option<(u64, u64)> {
+    let mut parts = version.split('.');
+    let major = parts.next()?.parse().ok()?;
+    let minor = parts.next()?.parse().ok()?;
+    Some((major, minor))
+}
+
+/// Distinct `tool_version`s of `snapshots`, in first-seen order, when they span
+/// more than one major.minor release. Metric counting can change between such
+/// releases, so comparing their snapshots may mislead. Patch-level differences
+/// and versions that don't parse as `X.Y[.Z]` are not skew.
+pub fn tool_version_skew<'a>(
+    snapshots: impl IntoIterator<Item = &'a Snapshot>,
+) -> Option<Vec<String>> {
+    let mut versions: Vec<String> = Vec::new();
+    let mut lines = std::collections::BTreeSet::new();
+    for snapshot in snapshots {
+        let version = &snapshot.analysis.tool_version;
+        if let Some(line) = release_line(version) {
+            lines.insert(line);
+            if !versions.contains(version) {
+                versions.push(version.clone());
+            }
+        }
+    }
+    (lines.len() > 1).then_some(versions)
+}
+
 /// Churn metrics for a file/function
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "snake_case")]
@@ -127,6 +157,10 @@ pub struct CallGraphMetrics {
     pub dependency_depth: Option<usize>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub neighbor_churn: Option<usize>,
+    /// Share of this function's call edges resolved by same-file, import, or
+    /// receiver evidence; absent when none were resolved by name alone
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub edge_confidence: Option<f64>,
 }
 
 /// Function entry in snapshot
@@ -253,11 +287,60 @@ pub struct FunctionSnapshot {
     /// Populated by `Snapshot::populate_history_signals()`.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub last_touch_days: Option<f64>,
+    /// Days between the commit that first introduced this function's line range
+    /// and the snapshot commit (`git log -L`). Function-level, unlike the
+    /// file-level `age_days`. Populated by `Snapshot::populate_function_age()`
+    /// under `--with-authors`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub function_age_days: Option<u32>,
+    /// Fraction (0.0–1.0) of the function's instrumented lines that an LCOV
+    /// report marks as executed. None without `--coverage`, or when the report
+    /// instruments no line in the function's range. Populated by
+    /// `Snapshot::populate_coverage()`; not stored in the SQLite backend.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub coverage: Option<f64>,
+    /// "Fix this first" triage score combining activity risk, missing coverage,
+    /// and recent change (see [`crate::scoring::compute_priority_score`]).
+    /// Populated by `Snapshot::populate_priority()`; not stored in the SQLite backend.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub priority_score: Option<f64>,
+    /// Expected payoff of refactoring: activity risk scaled up by fan-in and
+    /// recent touches (see [`crate::scoring::compute_refactor_leverage`]).
+    /// Populated by `Snapshot::populate_refactor_leverage()`; not stored in the SQLite backend.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub refactor_leverage: Option<f64>,
     /// Human-readable explanation phrase derived from feature percentiles within this repo.
     /// Populated by the `--explain` path after the trained ranker is applied.
     /// None unless `--explain` was passed and a trained ranker is present.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub explanation: Option<String>,
+    /// Source location carried from the analysis report, with `file` normalized
+    /// like `file` above. None in snapshots written before this field existed,
+    /// whether loaded from JSON or from the SQLite backend.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub location: Option<Location>,
+}
+
+impl FunctionSnapshot {
+    /// Last line of the function: its location's end line, or `loc` lines from
+    /// `line` for snapshots without one
+    pub fn end_line(&self) -> u32 {
+        match &self.location {
+            Some(location) => location.end_line,
+            None => self.line + self.metrics.loc.saturating_sub(1),
+        }
+    }
+
+    /// Kind of the function, for snapshots with a `location`
+    pub fn kind(&self) -> Option<crate::ast::FunctionKind> {
+        self.location.as_ref().map(|location| location.kind)
+    }
+
+    /// Whether `git log -L` can't follow the function's lines: notebook
+    /// functions are numbered by code-cell line, not by line of the JSON file
+    fn lacks_line_history(&self) -> bool {
+        crate::language::python::notebook::is_notebook(Path::new(&self.file))
+    }
 }
 
 /// Risk distribution by band
@@ -268,6 +351,17 @@ pub struct BandStats {
     pub sum_risk: f64,
 }
 
+/// Summary statistics for the functions of one language
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub struct LanguageStats {
+    pub function_count: usize,
+    pub total_lrs: f64,
+    pub avg_lrs: f64,
+    /// Function count per risk band; bands without functions are omitted
+    pub by_band: std::collections::BTreeMap<String, usize>,
+}
+
 /// Call graph statistics for the whole repo
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(rename_all = "snake_case")]
@@ -292,8 +386,49 @@ pub struct SnapshotSummary {
     pub top_5_pct_share: f64,
     pub top_10_pct_share: f64,
     pub by_band: std::collections::BTreeMap<String, BandStats>,
+    /// The same counts split by language name (e.g. `"Go"`, `"TypeScript"`).
+    /// Absent in snapshots written before it existed.
+    #[serde(default, skip_serializing_if = "std::collections::BTreeMap::is_empty")]
+    pub by_language: std::collections::BTreeMap<String, LanguageStats>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub call_graph: Option<CallGraphStats>,
+    /// Functions shorter than this many lines were left out of the counts,
+    /// shares, and bands above (`count_trivial: false`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub trivial_min_loc: Option<usize>,
+    /// Functions with an LRS below this were left out as well
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub trivial_min_lrs: Option<f64>,
+}
+
+impl SnapshotSummary {
+    /// The trivial-function cutoff these statistics were computed with
+    pub fn trivial_cutoff(&self) -> TrivialCutoff {
+        TrivialCutoff {
+            min_loc: self.trivial_min_loc,
+            min_lrs: self.trivial_min_lrs,
+        }
+    }
+}
+
+/// Which functions are too trivial to count in the summary statistics and
+/// percentile flags (config `count_trivial: false`)
+///
+/// A function is trivial when it is shorter than `min_loc` lines or scores an
+/// LRS below `min_lrs`. The default cutoff counts every function.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize)]
+pub struct TrivialCutoff {
+    pub min_loc: Option<usize>,
+    pub min_lrs: Option<f64>,
+}
+
+impl TrivialCutoff {
+    /// Whether `function` falls under the cutoff
+    pub fn excludes(&self, function: &FunctionSnapshot) -> bool {
+        self.min_loc
+            .is_some_and(|min| (function.metrics.loc as usize) < min)
+            || self.min_lrs.is_some_and(|min| function.lrs < min)
+    }
 }
 
 /// Complete snapshot for a commit
@@ -451,7 +586,9 @@ impl Snapshot {
     ///
     /// Function ID is `<relative_file_path>::<symbol>` where:
     /// - `relative_file_path` is normalized to use `/` separators
-    /// - `symbol` is the function name (or `<anonymous>` for anonymous functions)
+    /// - `symbol` is the function name; anonymous functions use
+    ///   `<anonymous@LINE>`, qualified by their enclosing named function
+    ///   (`outer::<anonymous@LINE>`) and suffixed `#2`, `#3`, ... on collisions
     pub fn new(git_context: GitContext, reports: Vec<FunctionRiskReport>) -> Self {
         // Normalize paths and build function snapshots
         let mut functions: Vec<FunctionSnapshot> = reports
@@ -460,16 +597,12 @@ impl Snapshot {
                 // Normalize file path to use `/` separators
                 let normalized_file = report.file.replace('\\', "/");
 
-                // Extract function name for function_id
-                // Use the function name from report, or derive from file/line if needed
-                let function_symbol = if report.function.starts_with("<anonymous>") {
-                    "<anonymous>"
-                } else {
-                    &report.function
-                };
-
                 // Build function_id: <relative_file_path>::<symbol>
-                let function_id = format!("{}::{}", normalized_file, function_symbol);
+                let function_id = format!("{}::{}", normalized_file, report.function);
+                let location = report.location.map(|location| Location {
+                    file: normalized_file.clone(),
+                    ..location
+                });
 
                 FunctionSnapshot {
                     function_id,
@@ -504,7 +637,12 @@ impl Snapshot {
                     isolation_rate: None,
                     age_days: None,
                     last_touch_days: None,
+                    function_age_days: None,
+                    coverage: None,
+                    priority_score: None,
+                    refactor_leverage: None,
                     explanation: None,
+                    location,
                 }
             })
             .collect();
@@ -730,6 +868,165 @@ impl Snapshot {
         }
     }
 
+    /// Populate `function_age_days` for every function.
+    ///
+    /// Runs one `git log -L` per function (see `git::function_first_seen`), which
+    /// walks the range's full history, so callers should trim the snapshot to the
+    /// functions they will display first. Age is measured to the snapshot commit's
+    /// timestamp for deterministic output. Errors are soft: functions whose range
+    /// has no history keep `None`, as do functions in notebooks, whose lines
+    /// don't map to lines of the file git tracks.
+    pub fn populate_function_age(&mut self, repo_root: &Path) {
+        let as_of = self.commit.timestamp;
+        let ages: Vec<Option<u32>> = self
+            .functions
+            .par_iter()
+            .map(|function| {
+                if function.lacks_line_history() {
+                    return None;
+                }
+                let rel = if let Ok(r) = Path::new(&function.file).strip_prefix(repo_root) {
+                    r.to_string_lossy().replace('\\', "/")
+                } else {
+                    function.file.replace('\\', "/")
+                };
+                let start_line = function.line;
+                let end_line =
+                    (start_line + function.metrics.loc.saturating_sub(1)).max(start_line);
+                crate::git::function_first_seen(repo_root, &rel, start_line, end_line)
+                    .ok()
+                    .flatten()
+                    .map(|ts| ((as_of - ts).max(0) / (24 * 60 * 60)) as u32)
+            })
+            .collect();
+        for (function, age) in self.functions.iter_mut().zip(ages) {
+            function.function_age_days = age;
+        }
+    }
+
+    /// Populate `coverage` for every function from an LCOV report.
+    ///
+    /// A function spans its location's line range, or `loc` lines from `line`
+    /// for snapshots without one. Functions the report doesn't instrument keep
+    /// `None`. Call before `populate_patterns()` so `risky_untested` can fire.
+    ///
+    /// Returns the number of functions the report covered.
+    pub fn populate_coverage(
+        &mut self,
+        report: &crate::coverage::LcovReport,
+        repo_root: &Path,
+    ) -> usize {
+        let mut matched = 0;
+        for function in &mut self.functions {
+            function.coverage = report.function_coverage(
+                &function.file,
+                function.line,
+                function.end_line(),
+                repo_root,
+            );
+            matched += usize::from(function.coverage.is_some());
+        }
+        matched
+    }
+
+    /// Rewrite file paths and function IDs relative to `root`, with `/`
+    /// separators. Paths outside `root` are left as they are.
+    pub fn relativize_paths(&mut self, root: &Path) {
+        use crate::aggregates::{normalize_path_relative_to_repo, relativize_function_id};
+
+        for function in &mut self.functions {
+            let Some(file) = normalize_path_relative_to_repo(&function.file, root) else {
+                continue;
+            };
+            function.function_id = relativize_function_id(&function.function_id, root);
+            if let Some(location) = &mut function.location {
+                location.file = file.clone();
+            }
+            function.file = file;
+        }
+    }
+
+    /// Populate `days_since_last_change` from file modification times, for
+    /// trees without git history. Relative file paths resolve against `root`.
+    ///
+    /// Days count back from the newest mtime among the snapshot's files rather
+    /// than from the current time, so an unchanged tree on disk always yields
+    /// the same values. Mtimes don't survive clones, copies, or most archive
+    /// extraction, so the values are not reproducible across machines.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if a file's modification time can't be read.
+    pub fn populate_mtime_recency(&mut self, root: &Path) -> Result<()> {
+        let mut mtimes: HashMap<String, i64> = HashMap::new();
+        for function in &self.functions {
+            if mtimes.contains_key(&function.file) {
+                continue;
+            }
+            let modified = std::fs::metadata(root.join(&function.file))
+                .and_then(|metadata| metadata.modified())
+                .with_context(|| format!("failed to read mtime of {}", function.file))?;
+            let secs = match modified.duration_since(std::time::UNIX_EPOCH) {
+                Ok(since) => since.as_secs() as i64,
+                Err(before) => -(before.duration().as_secs() as i64),
+            };
+            mtimes.insert(function.file.clone(), secs);
+        }
+        self.populate_recency_from_mtimes(&mtimes);
+        Ok(())
+    }
+
+    /// Populate `days_since_last_change` from `mtimes`, seconds since the Unix
+    /// epoch keyed by file, as [`Self::populate_mtime_recency`] does from disk.
+    /// Archives use this with their entry timestamps. Functions of files
+    /// missing from `mtimes` get no recency.
+    pub fn populate_recency_from_mtimes(&mut self, mtimes: &HashMap<String, i64>) {
+        let Some(newest) = self
+            .functions
+            .iter()
+            .filter_map(|f| mtimes.get(&f.file))
+            .max()
+            .copied()
+        else {
+            return;
+        };
+        for function in &mut self.functions {
+            function.days_since_last_change = mtimes
+                .get(&function.file)
+                .map(|secs| ((newest - secs) / 86_400) as u32);
+        }
+    }
+
+    /// Populate `priority_score` for every function.
+    ///
+    /// Call after `populate_coverage()` and after any trained ranker has
+    /// rewritten `activity_risk`, since the score reads both.
+    pub fn populate_priority(&mut self, weights: &crate::scoring::PriorityWeights) {
+        for function in &mut self.functions {
+            function.priority_score = Some(crate::scoring::compute_priority_score(
+                function.activity_risk.unwrap_or(function.lrs),
+                function.coverage,
+                function.days_since_last_change,
+                function.touch_count_30d,
+                weights,
+            ));
+        }
+    }
+
+    /// Populate `refactor_leverage` for every function.
+    ///
+    /// Call after `populate_callgraph()`, `populate_touch_metrics()`, and any
+    /// trained ranker, since the score reads fan-in, touches, and activity risk.
+    pub fn populate_refactor_leverage(&mut self) {
+        for function in &mut self.functions {
+            function.refactor_leverage = Some(crate::scoring::compute_refactor_leverage(
+                function.activity_risk.unwrap_or(function.lrs),
+                function.callgraph.as_ref().map(|cg| cg.fan_in),
+                function.touch_count_30d,
+            ));
+        }
+    }
+
     /// Populate `directed_coupling` and `jaccard_label_stability` for every function.
     ///
     /// Calls `crate::coupling::compute_directed_coupling_for_repo` which:
@@ -795,10 +1092,15 @@ impl Snapshot {
     fn populate_per_function_touch_metrics(
         &mut self,
         repo_root: &std::path::Path,
+        ignored: &HashSet<String>,
         progress_fn: Option<&dyn Fn(usize, usize)>,
     ) -> anyhow::Result<()> {
-        let all: Vec<usize> = (0..self.functions.len()).collect();
-        self.populate_per_function_touch_for_indices(repo_root, &all, progress_fn)
+        let (notebook, ranged): (Vec<usize>, Vec<usize>) =
+            (0..self.functions.len()).partition(|&i| self.functions[i].lacks_line_history());
+        if !notebook.is_empty() {
+            self.populate_file_level_touch_metrics(repo_root, ignored)?;
+        }
+        self.populate_per_function_touch_for_indices(repo_root, &ranged, ignored, progress_fn)
     }
 
     // Per-function touch metrics: one `git log -L` subprocess per function (~9 ms each).
@@ -815,10 +1117,12 @@ impl Snapshot {
         &mut self,
         repo_root: &std::path::Path,
         indices: &[usize],
+        ignored: &HashSet<String>,
         progress_fn: Option<&dyn Fn(usize, usize)>,
     ) -> anyhow::Result<()> {
         let sha = self.commit.sha.clone();
         let timestamp = self.commit.timestamp;
+        let ignored_suffix = crate::touch_cache::ignored_suffix(ignored);
         let mut cache = crate::touch_cache::read_touch_cache(repo_root).unwrap_or_default();
         let total = indices.len();
 
@@ -828,7 +1132,9 @@ impl Snapshot {
         if !cache_warm && total >= COLD_CACHE_WARN_THRESHOLD {
             let threads = rayon::current_num_threads().max(1);
             let est_secs = (total * 9).div_ceil(1000 * threads);
-            eprintln!("touch cache: cold start for {total} functions (~{est_secs}s; fast on subsequent runs)");
+            if diagnostics::enabled(Level::Info) {
+                eprintln!("touch cache: cold start for {total} functions (~{est_secs}s; fast on subsequent runs)");
+            }
         }
 
         // CHUNK_SIZE bounds the miss buffer: at most this many (key, rel, lines) strings
@@ -851,7 +1157,8 @@ impl Snapshot {
                 let start_line = function.line;
                 let end_line =
                     (start_line + function.metrics.loc.saturating_sub(1)).max(start_line);
-                let key = crate::touch_cache::cache_key(&sha, &rel, start_line, end_line);
+                let mut key = crate::touch_cache::cache_key(&sha, &rel, start_line, end_line);
+                key.push_str(&ignored_suffix);
                 if let Some(&(count, days)) = cache.get(&key) {
                     self.functions[i].touch_count_30d = Some(count);
                     self.functions[i].days_since_last_change = days;
@@ -877,6 +1184,7 @@ impl Snapshot {
                         *start_line,
                         *end_line,
                         timestamp,
+                        ignored,
                     ) {
                         Ok((count, days)) => (count, days),
                         Err(_) => (0usize, None),
@@ -909,7 +1217,9 @@ impl Snapshot {
         };
         crate::touch_cache::evict_old_entries(&mut cache, &known_shas);
         if let Err(e) = crate::touch_cache::write_touch_cache(repo_root, &cache) {
-            eprintln!("warning: failed to write touch cache: {e}");
+            if diagnostics::enabled(Level::Warn) {
+                eprintln!("warning: failed to write touch cache: {e}");
+            }
         }
 
         Ok(())
@@ -919,6 +1229,7 @@ impl Snapshot {
     fn populate_file_level_touch_metrics(
         &mut self,
         repo_root: &std::path::Path,
+        ignored: &HashSet<String>,
     ) -> anyhow::Result<()> {
         use std::collections::HashMap;
 
@@ -945,7 +1256,7 @@ impl Snapshot {
             .collect();
 
         // One batched call for the 30-day window (replaces N×2 individual calls)
-        let batched = crate::git::batch_touch_metrics_at(repo_root, self.commit.timestamp)
+        let batched = crate::git::batch_touch_metrics_at(repo_root, self.commit.timestamp, ignored)
             .unwrap_or_else(|_| crate::git::BatchedTouchMetrics {
                 touch_count_30d: HashMap::new(),
                 days_since_last_change: HashMap::new(),
@@ -960,8 +1271,12 @@ impl Snapshot {
             .filter(|rel| !batched.days_since_last_change.contains_key(*rel))
             .collect();
 
-        let stale_days =
-            crate::git::batch_last_touch_for_files(repo_root, &stale_files, self.commit.timestamp);
+        let stale_days = crate::git::batch_last_touch_for_files(
+            repo_root,
+            &stale_files,
+            self.commit.timestamp,
+            ignored,
+        );
 
         // Apply results to all functions in each file
         for (abs_path, function_indices) in &unique_files {
@@ -992,39 +1307,48 @@ impl Snapshot {
     /// - touch_count_30d: number of commits in last 30 days
     /// - days_since_last_change: days since last modification
     ///
+    /// Functions in Jupyter notebooks always get file-level values: their
+    /// line numbers count code-cell lines, not lines of the notebook JSON
+    /// that `git log -L` would follow.
+    ///
+    /// Commits in `ignored` (full SHAs, see [`crate::git::resolve_ignored_commits`])
+    /// count as neither touches nor changes.
     pub fn populate_touch_metrics(
         &mut self,
         repo_root: &std::path::Path,
         mode: TouchMode,
+        ignored: &HashSet<String>,
         progress_fn: Option<&dyn Fn(usize, usize)>,
     ) -> anyhow::Result<()> {
         match mode {
-            TouchMode::File => self.populate_file_level_touch_metrics(repo_root),
+            TouchMode::File => self.populate_file_level_touch_metrics(repo_root, ignored),
             TouchMode::PerFunction => {
-                self.populate_per_function_touch_metrics(repo_root, progress_fn)
+                self.populate_per_function_touch_metrics(repo_root, ignored, progress_fn)
             }
             TouchMode::Hybrid { threshold } => {
-                self.populate_hybrid_touch_metrics(repo_root, threshold, progress_fn)
+                self.populate_hybrid_touch_metrics(repo_root, threshold, ignored, progress_fn)
             }
         }
     }
 
     /// Hybrid touch: file-level first (cheap), then per-function only for
-    /// functions in files whose touch_count_30d >= threshold.
+    /// functions in files whose touch_count_30d >= threshold (notebooks keep
+    /// their file-level values).
     fn populate_hybrid_touch_metrics(
         &mut self,
         repo_root: &std::path::Path,
         threshold: usize,
+        ignored: &HashSet<String>,
         progress_fn: Option<&dyn Fn(usize, usize)>,
     ) -> anyhow::Result<()> {
-        self.populate_file_level_touch_metrics(repo_root)?;
+        self.populate_file_level_touch_metrics(repo_root, ignored)?;
 
         let hot_indices: Vec<usize> = self
             .functions
             .iter()
             .enumerate()
             .filter_map(|(i, f)| {
-                if f.touch_count_30d.unwrap_or(0) >= threshold {
+                if f.touch_count_30d.unwrap_or(0) >= threshold && !f.lacks_line_history() {
                     Some(i)
                 } else {
                     None
@@ -1036,7 +1360,7 @@ impl Snapshot {
             return Ok(());
         }
 
-        self.populate_per_function_touch_for_indices(repo_root, &hot_indices, progress_fn)
+        self.populate_per_function_touch_for_indices(repo_root, &hot_indices, ignored, progress_fn)
     }
 
     /// Replace branch-inflated recency values with pre-branch last-change dates.
@@ -1117,6 +1441,7 @@ impl Snapshot {
         let dependency_depths = call_graph.compute_dependency_depth();
         // Precompute fan-in counts in O(N+E) to avoid O(N*E) repeated fan_in() calls below
         let fan_in_map = call_graph.build_fan_in_map();
+        let edge_confidence = call_graph.build_edge_confidence_map();
 
         // Build a map of function_id -> total churn (lines_added + lines_deleted)
         let mut churn_map: HashMap<String, usize> = HashMap::new();
@@ -1160,6 +1485,7 @@ impl Snapshot {
                     is_entrypoint: call_graph.is_entry_point(function_id),
                     dependency_depth,
                     neighbor_churn,
+                    edge_confidence: edge_confidence.get(function_id).copied(),
                 });
             }
         }
@@ -1188,16 +1514,17 @@ impl Snapshot {
                 .map(|c| (c.lines_added, c.lines_deleted));
 
             // Extract call graph data
-            let (fan_in, scc_size, dependency_depth, neighbor_churn) =
+            let (fan_in, scc_size, dependency_depth, neighbor_churn, edge_confidence) =
                 if let Some(ref cg) = function.callgraph {
                     (
                         Some(cg.fan_in),
                         Some(cg.scc_size),
                         cg.dependency_depth,
                         cg.neighbor_churn,
+                        cg.edge_confidence,
                     )
                 } else {
-                    (None, None, None, None)
+                    (None, None, None, None, None)
                 };
 
             // Compute activity risk
@@ -1212,6 +1539,7 @@ impl Snapshot {
                     dependency_depth,
                     neighbor_churn,
                     burst_score: function.burst_score,
+                    edge_confidence,
                 },
                 weights,
             );
@@ -1238,6 +1566,12 @@ impl Snapshot {
                 fo: function.metrics.fo as usize,
                 ns: function.metrics.ns as usize,
                 loc: function.metrics.loc as usize,
+                sloc: function.metrics.sloc.map(|n| n as usize),
+                return_points: function.metrics.return_points.map(|n| n as usize),
+                unwrap_count: function.metrics.unwrap_count.map(|n| n as usize),
+                max_arm_loc: function.metrics.max_arm_loc.map(|n| n as usize),
+                max_data_nesting: function.metrics.max_data_nesting.map(|n| n as usize),
+                unreachable_code: function.metrics.unreachable_code.map(|n| n as usize),
             };
             // churn_lines is intentionally None here: function.churn is file-level
             // (all functions in a file share the same total), not per-function. Using
@@ -1261,9 +1595,12 @@ impl Snapshot {
                 churn_lines: None,
                 days_since_last_change: function.days_since_last_change,
                 neighbor_churn,
+                coverage_pct: function.coverage.map(|c| (c * 100.0) as usize),
+                lrs: Some(function.lrs),
                 is_entrypoint,
             };
-            function.patterns = crate::patterns::classify(&t1, &t2, thresholds);
+            let kind = function.location.as_ref().map(|l| l.kind);
+            function.patterns = crate::patterns::classify(&t1, &t2, thresholds.for_kind(kind));
         }
     }
 
@@ -1279,6 +1616,12 @@ impl Snapshot {
                 fo: function.metrics.fo as usize,
                 ns: function.metrics.ns as usize,
                 loc: function.metrics.loc as usize,
+                sloc: function.metrics.sloc.map(|n| n as usize),
+                return_points: function.metrics.return_points.map(|n| n as usize),
+                unwrap_count: function.metrics.unwrap_count.map(|n| n as usize),
+                max_arm_loc: function.metrics.max_arm_loc.map(|n| n as usize),
+                max_data_nesting: function.metrics.max_data_nesting.map(|n| n as usize),
+                unreachable_code: function.metrics.unreachable_code.map(|n| n as usize),
             };
             let (fan_in, scc_size, neighbor_churn, is_entrypoint) =
                 if let Some(ref cg) = function.callgraph {
@@ -1297,10 +1640,16 @@ impl Snapshot {
                 churn_lines: None,
                 days_since_last_change: function.days_since_last_change,
                 neighbor_churn,
+                coverage_pct: function.coverage.map(|c| (c * 100.0) as usize),
+                lrs: Some(function.lrs),
                 is_entrypoint,
             };
-            function.pattern_details =
-                Some(crate::patterns::classify_detailed(&t1, &t2, thresholds));
+            let kind = function.location.as_ref().map(|l| l.kind);
+            function.pattern_details = Some(crate::patterns::classify_detailed(
+                &t1,
+                &t2,
+                thresholds.for_kind(kind),
+            ));
         }
     }
 
@@ -1308,18 +1657,20 @@ impl Snapshot {
     ///
     /// Must be called after compute_activity_risk().
     /// Flags: is_top_1_pct, is_top_5_pct, is_top_10_pct based on activity_risk.
-    pub fn compute_percentiles(&mut self) {
-        let n = self.functions.len();
-        if n == 0 {
-            return;
-        }
-
+    /// Functions under the `trivial` cutoff are left out of the ranking and get
+    /// no flags.
+    pub fn compute_percentiles(&mut self, trivial: TrivialCutoff) {
         // Collect all activity_risk scores (falling back to lrs)
         let mut scores: Vec<f64> = self
             .functions
             .iter()
+            .filter(|f| !trivial.excludes(f))
             .map(|f| f.activity_risk.unwrap_or(f.lrs))
             .collect();
+        let n = scores.len();
+        if n == 0 {
+            return;
+        }
         scores.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
 
         // Compute threshold values via quantile index
@@ -1328,6 +1679,10 @@ impl Snapshot {
         let threshold_1 = scores[n.saturating_sub(1) * 99 / 100];
 
         for function in &mut self.functions {
+            if trivial.excludes(function) {
+                function.percentile = None;
+                continue;
+            }
             let score = function.activity_risk.unwrap_or(function.lrs);
             function.percentile = Some(PercentileFlags {
                 is_top_10_pct: score >= threshold_10,
@@ -1443,9 +1798,15 @@ impl Snapshot {
     /// Compute repo-level summary statistics
     ///
     /// Must be called after compute_activity_risk() and populate_callgraph().
-    pub fn compute_summary(&mut self, betweenness_approximate: bool) {
-        let n = self.functions.len();
-        if n == 0 {
+    /// Functions under the `trivial` cutoff are left out of the counts, shares,
+    /// and bands; call graph statistics still cover every function.
+    pub fn compute_summary(&mut self, betweenness_approximate: bool, trivial: TrivialCutoff) {
+        let counted: Vec<&FunctionSnapshot> = self
+            .functions
+            .iter()
+            .filter(|f| !trivial.excludes(f))
+            .collect();
+        if counted.is_empty() {
             self.summary = Some(SnapshotSummary {
                 total_functions: 0,
                 total_activity_risk: 0.0,
@@ -1453,13 +1814,15 @@ impl Snapshot {
                 top_5_pct_share: 0.0,
                 top_10_pct_share: 0.0,
                 by_band: std::collections::BTreeMap::new(),
+                by_language: std::collections::BTreeMap::new(),
                 call_graph: None,
+                trivial_min_loc: trivial.min_loc,
+                trivial_min_lrs: trivial.min_lrs,
             });
             return;
         }
 
-        let mut scored: Vec<f64> = self
-            .functions
+        let mut scored: Vec<f64> = counted
             .iter()
             .map(|f| f.activity_risk.unwrap_or(f.lrs))
             .collect();
@@ -1469,36 +1832,30 @@ impl Snapshot {
         let (top_1_pct_share, top_5_pct_share, top_10_pct_share) =
             compute_top_k_shares(&scored, total_risk);
 
+        let n = self.functions.len();
         self.summary = Some(SnapshotSummary {
-            total_functions: n,
+            total_functions: counted.len(),
             total_activity_risk: total_risk,
             top_1_pct_share,
             top_5_pct_share,
             top_10_pct_share,
-            by_band: compute_band_distribution(&self.functions),
+            by_band: compute_band_distribution(&counted),
+            by_language: compute_language_breakdown(&counted),
             call_graph: compute_call_graph_stats(&self.functions, n, betweenness_approximate),
+            trivial_min_loc: trivial.min_loc,
+            trivial_min_lrs: trivial.min_lrs,
         });
     }
 
     /// Serialize snapshot as JSONL (one JSON object per line, no outer array)
     ///
     /// Each line embeds the commit context alongside function data,
-    /// suitable for streaming ingestion (DuckDB, jq -s, etc.)
+    /// suitable for streaming ingestion (DuckDB, jq -s, etc.). See
+    /// [`Snapshot::write_jsonl_to`] for the line guarantees.
     pub fn to_jsonl(&self) -> Result<String> {
-        let commit_json =
-            serde_json::to_value(&self.commit).context("failed to serialize commit")?;
-
-        let mut lines = Vec::with_capacity(self.functions.len());
-        for func in &self.functions {
-            let mut obj = serde_json::to_value(func).context("failed to serialize function")?;
-            // Embed commit context in each row
-            obj.as_object_mut()
-                .context("serialized function is not a JSON object")?
-                .insert("commit".to_string(), commit_json.clone());
-            lines.push(serde_json::to_string(&obj).context("failed to serialize JSONL line")?);
-        }
-
-        Ok(lines.join("\n"))
+        let mut out = Vec::new();
+        self.write_jsonl_to(&mut out)?;
+        String::from_utf8(out).context("JSONL output is not UTF-8")
     }
 
     /// Serialize snapshot to JSON string (deterministic ordering)
@@ -1519,6 +1876,10 @@ impl Snapshot {
 
     /// Write the snapshot as JSONL (one JSON object per function) directly to
     /// `writer`, without building an intermediate `String`.
+    ///
+    /// Records are compact and newline-terminated; newlines inside string
+    /// values are escaped, so every line parses on its own and the output has
+    /// exactly one line per function (none when there are no functions).
     pub fn write_jsonl_to<W: std::io::Write>(&self, writer: &mut W) -> Result<()> {
         let commit_json =
             serde_json::to_value(&self.commit).context("failed to serialize commit")?;
@@ -1535,6 +1896,49 @@ impl Snapshot {
         Ok(())
     }
 
+    /// Render the snapshot as canonical text for committing to a repository.
+    ///
+    /// One line per function, `file::symbol cc nd fo ns lrs band`, sorted by
+    /// function ID with files made relative to `repo_root`. No commit metadata
+    /// is included, so an unchanged function produces an unchanged line and
+    /// `git diff` shows only the functions whose metrics moved.
+    pub fn to_canonical_text(&self, repo_root: &Path) -> String {
+        let mut lines: Vec<String> = self
+            .functions
+            .iter()
+            .map(|f| {
+                let id = Path::new(&f.file)
+                    .strip_prefix(repo_root)
+                    .ok()
+                    .and_then(|rel| {
+                        let symbol = f.function_id.strip_prefix(f.file.as_str())?;
+                        Some(format!(
+                            "{}{}",
+                            rel.to_string_lossy().replace('\\', "/"),
+                            symbol
+                        ))
+                    })
+                    .unwrap_or_else(|| f.function_id.clone());
+                format!(
+                    "{} {} {} {} {} {:.2} {}",
+                    id,
+                    f.metrics.cc,
+                    f.metrics.nd,
+                    f.metrics.fo,
+                    f.metrics.ns,
+                    f.lrs,
+                    f.band.as_str()
+                )
+            })
+            .collect();
+        lines.sort();
+        let mut text = lines.join("\n");
+        if !text.is_empty() {
+            text.push('\n');
+        }
+        text
+    }
+
     /// Deserialize snapshot from JSON string
     pub fn from_json(json: &str) -> Result<Self> {
         let snapshot: Snapshot =
@@ -1559,6 +1963,148 @@ impl Snapshot {
     pub fn commit_sha(&self) -> &str {
         &self.commit.sha
     }
+
+    /// Combine snapshots of the same commit produced by sharded analyses
+    /// (e.g. one CI runner per subtree) into a single snapshot.
+    ///
+    /// All inputs must share the same commit SHA, and no `function_id` may appear
+    /// in more than one input. Functions are concatenated and re-sorted by
+    /// `function_id`; percentile flags and the summary are recomputed over the
+    /// union, since per-shard values are relative to that shard only. Aggregates
+    /// are dropped (they are computed on demand).
+    pub fn merge(snapshots: Vec<Snapshot>) -> Result<Snapshot> {
+        let mut iter = snapshots.into_iter();
+        let mut merged = iter
+            .next()
+            .context("cannot merge an empty list of snapshots")?;
+        let mut betweenness_approximate = merged
+            .summary
+            .as_ref()
+            .and_then(|s| s.call_graph.as_ref())
+            .is_some_and(|cg| cg.betweenness_approximate);
+        let trivial = merged
+            .summary
+            .as_ref()
+            .map(SnapshotSummary::trivial_cutoff)
+            .unwrap_or_default();
+
+        for other in iter {
+            if other.commit.sha != merged.commit.sha {
+                anyhow::bail!(
+                    "cannot merge snapshots from different commits ({} vs {})",
+                    merged.commit.sha,
+                    other.commit.sha
+                );
+            }
+            betweenness_approximate |= other
+                .summary
+                .as_ref()
+                .and_then(|s| s.call_graph.as_ref())
+                .is_some_and(|cg| cg.betweenness_approximate);
+            merged.functions.extend(other.functions);
+        }
+
+        merged
+            .functions
+            .sort_by(|a, b| a.function_id.cmp(&b.function_id));
+        if let Some(dup) = merged
+            .functions
+            .windows(2)
+            .find(|w| w[0].function_id == w[1].function_id)
+        {
+            anyhow::bail!(
+                "cannot merge snapshots: function {} appears in more than one shard",
+                dup[0].function_id
+            );
+        }
+
+        merged.aggregates = None;
+        merged.compute_percentiles(trivial);
+        merged.compute_summary(betweenness_approximate, trivial);
+        Ok(merged)
+    }
+
+    /// Build a snapshot incrementally from its parent commit's snapshot.
+    ///
+    /// `update` covers only `changed_files` (paths in the same form as
+    /// `FunctionSnapshot::file`) at the new commit; a changed file that no
+    /// longer exists simply contributes no functions. The parent's functions in
+    /// changed files are dropped and `update`'s are spliced in, keeping the
+    /// result sorted by `function_id`. The commit, analysis info, and
+    /// trivial-function cutoff come from `update`.
+    ///
+    /// Functions carried over from the parent lose their churn (it belonged to
+    /// the parent's commit) and have `days_since_last_change` aged by the time
+    /// between the two commits. Re-analyzed functions the parent already had
+    /// keep the parent's call-graph metrics, since calls into them from
+    /// unchanged files are not visible to `update`. Percentile flags and the
+    /// summary are recomputed over the merged set; aggregates are dropped.
+    pub fn splice(
+        parent: Snapshot,
+        update: Snapshot,
+        changed_files: &std::collections::HashSet<String>,
+    ) -> Result<Snapshot> {
+        if !update.commit.parents.contains(&parent.commit.sha) {
+            anyhow::bail!(
+                "cannot splice: {} is not a parent of {}",
+                parent.commit.sha,
+                update.commit.sha
+            );
+        }
+        let is_approximate = |s: &Snapshot| {
+            s.summary
+                .as_ref()
+                .and_then(|s| s.call_graph.as_ref())
+                .is_some_and(|cg| cg.betweenness_approximate)
+        };
+        let betweenness_approximate = is_approximate(&parent) || is_approximate(&update);
+        let trivial = update
+            .summary
+            .as_ref()
+            .map(SnapshotSummary::trivial_cutoff)
+            .unwrap_or_default();
+        let elapsed_days =
+            ((update.commit.timestamp - parent.commit.timestamp).max(0) / 86_400) as u32;
+
+        let mut parent_callgraphs: HashMap<String, CallGraphMetrics> = HashMap::new();
+        let mut functions = Vec::with_capacity(parent.functions.len());
+        for mut function in parent.functions {
+            if changed_files.contains(&function.file) {
+                if let Some(callgraph) = function.callgraph {
+                    parent_callgraphs.insert(function.function_id, callgraph);
+                }
+                continue;
+            }
+            function.churn = None;
+            if let Some(days) = function.days_since_last_change.as_mut() {
+                *days = days.saturating_add(elapsed_days);
+            }
+            functions.push(function);
+        }
+        let mut spliced = update;
+        for mut function in std::mem::take(&mut spliced.functions) {
+            if let Some(callgraph) = parent_callgraphs.remove(&function.function_id) {
+                function.callgraph = Some(callgraph);
+            }
+            functions.push(function);
+        }
+        functions.sort_by(|a, b| a.function_id.cmp(&b.function_id));
+        if let Some(dup) = functions
+            .windows(2)
+            .find(|w| w[0].function_id == w[1].function_id)
+        {
+            anyhow::bail!(
+                "cannot splice: function {} is outside the changed files but was re-analyzed",
+                dup[0].function_id
+            );
+        }
+
+        spliced.functions = functions;
+        spliced.aggregates = None;
+        spliced.compute_percentiles(trivial);
+        spliced.compute_summary(betweenness_approximate, trivial);
+        Ok(spliced)
+    }
 }
 
 /// Returns (top_1_pct_share, top_5_pct_share, top_10_pct_share) from a
@@ -1578,7 +2124,7 @@ fn compute_top_k_shares(scored: &[f64], total_risk: f64) -> (f64, f64, f64) {
 
 /// Builds a band → BandStats map from the function list.
 fn compute_band_distribution(
-    functions: &[FunctionSnapshot],
+    functions: &[&FunctionSnapshot],
 ) -> std::collections::BTreeMap<String, BandStats> {
     let mut by_band = std::collections::BTreeMap::new();
     for func in functions {
@@ -1595,6 +2141,33 @@ fn compute_band_distribution(
     by_band
 }
 
+/// Groups functions by language: count, total and average LRS, and band counts.
+fn compute_language_breakdown(
+    functions: &[&FunctionSnapshot],
+) -> std::collections::BTreeMap<String, LanguageStats> {
+    let mut by_language = std::collections::BTreeMap::new();
+    for func in functions {
+        let entry = by_language
+            .entry(func.language.name().to_string())
+            .or_insert(LanguageStats {
+                function_count: 0,
+                total_lrs: 0.0,
+                avg_lrs: 0.0,
+                by_band: std::collections::BTreeMap::new(),
+            });
+        entry.function_count += 1;
+        entry.total_lrs += func.lrs;
+        *entry
+            .by_band
+            .entry(func.band.as_str().to_string())
+            .or_insert(0) += 1;
+    }
+    for stats in by_language.values_mut() {
+        stats.avg_lrs = stats.total_lrs / stats.function_count as f64;
+    }
+    by_language
+}
+
 /// Computes call-graph-level summary statistics, or None if no call graph data.
 fn compute_call_graph_stats(
     functions: &[FunctionSnapshot],
@@ -1725,6 +2298,18 @@ pub fn normalize_driver_label(label: &str) -> &'static str {
     }
 }
 
+/// Order functions within a triage quadrant: `risky_untested` functions first,
+/// then by activity risk (falling back to LRS), highest first.
+pub fn triage_order(a: &FunctionSnapshot, b: &FunctionSnapshot) -> std::cmp::Ordering {
+    let untested = |f: &FunctionSnapshot| f.patterns.iter().any(|p| p == "risky_untested");
+    untested(b).cmp(&untested(a)).then_with(|| {
+        b.activity_risk
+            .unwrap_or(b.lrs)
+            .partial_cmp(&a.activity_risk.unwrap_or(a.lrs))
+            .unwrap_or(std::cmp::Ordering::Equal)
+    })
+}
+
 /// Map a (driver, quadrant) pair to a recommended action string.
 ///
 /// `quadrant` is one of `"fire"`, `"debt"`, `"watch"`, `"ok"`, or `""` (unknown).
@@ -1860,14 +2445,27 @@ fn compute_near_miss_detail(
 pub struct SnapshotEnricher {
     snapshot: Snapshot,
     betweenness_approximate: bool,
+    trivial: TrivialCutoff,
+    /// Full SHAs that touch metrics pass over (config `ignore_commits`)
+    ignored_commits: HashSet<String>,
 }
 
 impl SnapshotEnricher {
     /// Create a new enricher wrapping the given snapshot.
+    ///
+    /// Whether betweenness was approximated carries over from an existing
+    /// summary (e.g. a spliced snapshot) until `with_callgraph` recomputes it.
     pub fn new(snapshot: Snapshot) -> Self {
+        let betweenness_approximate = snapshot
+            .summary
+            .as_ref()
+            .and_then(|s| s.call_graph.as_ref())
+            .is_some_and(|cg| cg.betweenness_approximate);
         SnapshotEnricher {
             snapshot,
-            betweenness_approximate: false,
+            betweenness_approximate,
+            trivial: TrivialCutoff::default(),
+            ignored_commits: HashSet::new(),
         }
     }
 
@@ -1902,6 +2500,25 @@ impl SnapshotEnricher {
         self
     }
 
+    /// Leave commits matching `ignored` (e.g. repo-wide formatting) out of the
+    /// touch metrics computed by `with_touch_metrics`, which must come after.
+    /// If the commits can't be listed, emits a warning and ignores none.
+    pub fn with_ignored_commits(
+        mut self,
+        repo_root: &Path,
+        ignored: &crate::git::IgnoredCommits,
+    ) -> Self {
+        match crate::git::resolve_ignored_commits(repo_root, ignored) {
+            Ok(shas) => self.ignored_commits = shas,
+            Err(e) => {
+                if diagnostics::enabled(Level::Warn) {
+                    eprintln!("Warning: failed to resolve ignore_commits: {}", e);
+                }
+            }
+        }
+        self
+    }
+
     /// Populate touch count and recency metrics from git.
     /// On error, emits a warning to stderr and continues.
     pub fn with_touch_metrics(
@@ -1910,11 +2527,15 @@ impl SnapshotEnricher {
         mode: TouchMode,
         progress_fn: Option<Box<dyn Fn(usize, usize)>>,
     ) -> Self {
-        if let Err(e) =
-            self.snapshot
-                .populate_touch_metrics(repo_root, mode, progress_fn.as_deref())
-        {
-            eprintln!("Warning: failed to populate touch metrics: {}", e);
+        if let Err(e) = self.snapshot.populate_touch_metrics(
+            repo_root,
+            mode,
+            &self.ignored_commits,
+            progress_fn.as_deref(),
+        ) {
+            if diagnostics::enabled(Level::Warn) {
+                eprintln!("Warning: failed to populate touch metrics: {}", e);
+            }
         }
         self
     }
@@ -1948,6 +2569,14 @@ impl SnapshotEnricher {
         self
     }
 
+    /// Leave functions under `trivial` out of the percentile flags and summary
+    /// statistics computed by `enrich` (config `count_trivial: false`). The
+    /// default cutoff counts every function.
+    pub fn with_trivial_cutoff(mut self, trivial: TrivialCutoff) -> Self {
+        self.trivial = trivial;
+        self
+    }
+
     /// Compute activity risk, percentile flags, driver labels, and summary statistics.
     ///
     /// Must be called after with_churn, with_touch_metrics, and with_callgraph.
@@ -1957,12 +2586,13 @@ impl SnapshotEnricher {
         driver_threshold_percentile: u8,
     ) -> Self {
         self.snapshot.compute_activity_risk(weights);
-        self.snapshot.compute_percentiles();
+        self.snapshot.compute_percentiles(self.trivial);
         self.snapshot
             .populate_driver_labels(driver_threshold_percentile);
         self.snapshot
             .compute_quadrants(driver_threshold_percentile, false);
-        self.snapshot.compute_summary(self.betweenness_approximate);
+        self.snapshot
+            .compute_summary(self.betweenness_approximate, self.trivial);
         self
     }
 
@@ -2186,9 +2816,40 @@ pub fn persist_delta(repo_root: &Path, delta: &DeltaSnapshot) -> Result<()> {
         .with_context(|| format!("failed to persist delta snapshot: {}", path.display()))
 }
 
-/// Get the path to the `.hotspots` directory in the repository root
+/// Environment variable that relocates the storage directory
+pub const STORE_DIR_ENV: &str = "HOTSPOTS_STORE_DIR";
+
+/// Storage directory chosen with `--store-dir`, set once at startup
+static STORE_DIR: std::sync::OnceLock<PathBuf> = std::sync::OnceLock::new();
+
+/// Relocate the storage directory for the rest of the process.
+///
+/// Takes precedence over [`STORE_DIR_ENV`]. Only the first call has effect.
+pub fn set_store_dir(dir: PathBuf) {
+    let _ = STORE_DIR.set(dir);
+}
+
+/// The relocated storage directory, from [`set_store_dir`] or
+/// [`STORE_DIR_ENV`], or `None` when storage stays in `<repo_root>/.hotspots`
+pub fn store_dir_override() -> Option<PathBuf> {
+    resolve_store_dir(STORE_DIR.get(), std::env::var_os(STORE_DIR_ENV))
+}
+
+fn resolve_store_dir(
+    explicit: Option<&PathBuf>,
+    env: Option<std::ffi::OsString>,
+) -> Option<PathBuf> {
+    explicit
+        .cloned()
+        .or_else(|| env.filter(|dir| !dir.is_empty()).map(PathBuf::from))
+}
+
+/// Get the path to the storage directory: `<repo_root>/.hotspots` unless
+/// relocated with `--store-dir` or `HOTSPOTS_STORE_DIR`
+///
+/// Snapshots, the index, the SQLite store, and caches all live under it.
 pub fn hotspots_dir(repo_root: &Path) -> PathBuf {
-    repo_root.join(".hotspots")
+    store_dir_override().unwrap_or_else(|| repo_root.join(".hotspots"))
 }
 
 /// Get the path to the snapshots directory
@@ -2260,6 +2921,21 @@ pub fn load_snapshot(repo_root: &Path, commit_sha: &str) -> Result<Option<Snapsh
     Ok(None)
 }
 
+/// Number of snapshots `analyze --format html` and `report-index` chart
+pub const DEFAULT_HISTORY_WINDOW: usize = 30;
+
+/// Load the summaries of the last `window` indexed snapshots for trend charts.
+///
+/// Returns `(commit, summary)` pairs ordered by timestamp, then SHA — the shape
+/// `html::render_html_snapshot` takes as `history`. Snapshots persisted without
+/// a summary are skipped; a repository with no index yields an empty vector.
+pub fn load_history(repo_root: &Path, window: usize) -> Result<Vec<(CommitInfo, SnapshotSummary)>> {
+    Ok(crate::trends::load_snapshot_window(repo_root, window)?
+        .into_iter()
+        .filter_map(|s| s.summary.map(|summary| (s.commit, summary)))
+        .collect())
+}
+
 /// Read and parse a snapshot from an arbitrary path, auto-detecting compression.
 fn read_snapshot_file(path: &Path) -> Result<Snapshot> {
     let is_compressed = path
@@ -2455,11 +3131,13 @@ pub fn rebuild_index(repo_root: &Path) -> Result<Index> {
             Ok(s) => s,
             Err(e) => {
                 // Log error but continue (some snapshots may be corrupted)
-                eprintln!(
-                    "Warning: failed to parse snapshot {}: {}",
-                    path.display(),
-                    e
-                );
+                if diagnostics::enabled(Level::Warn) {
+                    eprintln!(
+                        "Warning: failed to parse snapshot {}: {}",
+                        path.display(),
+                        e
+                    );
+                }
                 continue;
             }
         };
@@ -2505,6 +3183,12 @@ mod tests {
                 fo: 3,
                 ns: 1,
                 loc: 10,
+                sloc: None,
+                return_points: None,
+                unwrap_count: None,
+                max_arm_loc: None,
+                max_data_nesting: None,
+                unreachable_code: None,
             },
             risk: RiskReport {
                 r_cc: 2.0,
@@ -2519,6 +3203,7 @@ mod tests {
             pattern_details: None,
             callees: vec![],
             explanation: None,
+            location: None,
         };
 
         Snapshot::new(git_context, vec![report])
@@ -2540,6 +3225,230 @@ mod tests {
         assert_eq!(deserialized.functions.len(), snapshot.functions.len());
     }
 
+    #[test]
+    fn test_jsonl_has_one_parseable_line_per_function() {
+        let mut snapshot = create_test_snapshot();
+        let mut other = snapshot.functions[0].clone();
+        other.function_id = "src/foo.ts::other".to_string();
+        other.suppression_reason = Some("legacy\nparser, see #12".to_string());
+        snapshot.functions.push(other);
+
+        let output = snapshot.to_jsonl().expect("should serialize");
+        assert!(output.ends_with('\n'));
+        assert_eq!(output.lines().count(), snapshot.functions.len());
+        for line in output.lines() {
+            let row: serde_json::Value = serde_json::from_str(line).expect("line should parse");
+            assert_eq!(row["commit"]["sha"], "abc123");
+        }
+
+        snapshot.functions.clear();
+        assert_eq!(snapshot.to_jsonl().unwrap(), "");
+    }
+
+    #[test]
+    fn test_canonical_text_is_sorted_and_repo_relative() {
+        let mut snapshot = create_test_snapshot();
+        let mut other = snapshot.functions[0].clone();
+        other.file = "/repo/src/bar.ts".to_string();
+        other.function_id = "/repo/src/bar.ts::run".to_string();
+        other.lrs = 1.0;
+        other.band = RiskBand::Low;
+        snapshot.functions.push(other);
+
+        assert_eq!(
+            snapshot.to_canonical_text(Path::new("/repo")),
+            "src/bar.ts::run 5 2 3 1 1.00 low\nsrc/foo.ts::handler 5 2 3 1 4.80 moderate\n"
+        );
+    }
+
+    #[test]
+    fn test_tool_version_skew_ignores_patch_releases() {
+        let with_version = |v: &str| {
+            let mut snapshot = create_test_snapshot();
+            snapshot.analysis.tool_version = v.to_string();
+            snapshot
+        };
+        let (a, b, c, d) = (
+            with_version("0.4.1"),
+            with_version("0.4.3"),
+            with_version("0.5.0"),
+            with_version("dev"),
+        );
+
+        assert_eq!(tool_version_skew([&a, &b, &d]), None);
+        assert_eq!(
+            tool_version_skew([&a, &b, &c, &a]),
+            Some(vec![
+                "0.4.1".to_string(),
+                "0.4.3".to_string(),
+                "0.5.0".to_string()
+            ])
+        );
+    }
+
+    #[test]
+    fn test_merge_combines_shards() {
+        let a = create_test_snapshot();
+        let mut b = create_test_snapshot();
+        b.functions[0].function_id = "src/bar.ts::other".to_string();
+        b.functions[0].file = "src/bar.ts".to_string();
+        b.functions[0].lrs = 9.5;
+
+        let merged = Snapshot::merge(vec![a, b]).expect("should merge");
+        let ids: Vec<&str> = merged
+            .functions
+            .iter()
+            .map(|f| f.function_id.as_str())
+            .collect();
+        assert_eq!(ids, vec!["src/bar.ts::other", "src/foo.ts::handler"]);
+        assert_eq!(merged.summary.as_ref().unwrap().total_functions, 2);
+        assert!(merged.functions.iter().all(|f| f.percentile.is_some()));
+        assert!(merged.aggregates.is_none());
+    }
+
+    #[test]
+    fn test_merge_rejects_mismatched_commits_and_duplicates() {
+        let a = create_test_snapshot();
+        let mut b = create_test_snapshot();
+        b.commit.sha = "fff999".to_string();
+        assert!(Snapshot::merge(vec![a, b]).is_err());
+
+        let dup = Snapshot::merge(vec![create_test_snapshot(), create_test_snapshot()]);
+        assert!(dup.is_err());
+
+        assert!(Snapshot::merge(vec![]).is_err());
+    }
+
+    #[test]
+    fn test_splice_replaces_changed_files_only() {
+        let mut parent = create_test_snapshot();
+        parent.commit.sha = "def456".to_string();
+        parent.commit.timestamp -= 2 * 86_400;
+        let mut kept = parent.functions[0].clone();
+        kept.function_id = "src/bar.ts::other".to_string();
+        kept.file = "src/bar.ts".to_string();
+        kept.churn = Some(ChurnMetrics {
+            lines_added: 4,
+            lines_deleted: 1,
+            net_change: 3,
+        });
+        kept.days_since_last_change = Some(5);
+        parent.functions.push(kept);
+        parent.functions[0].callgraph = Some(CallGraphMetrics {
+            fan_in: 3,
+            fan_out: 1,
+            pagerank: 0.2,
+            betweenness: 0.0,
+            scc_id: 0,
+            scc_size: 1,
+            is_entrypoint: false,
+            dependency_depth: None,
+            neighbor_churn: None,
+            edge_confidence: None,
+        });
+
+        let mut update = create_test_snapshot();
+        update.functions[0].lrs = 7.5;
+        let changed = std::collections::HashSet::from(["src/foo.ts".to_string()]);
+        let spliced = Snapshot::splice(parent.clone(), update, &changed).expect("should splice");
+
+        assert_eq!(spliced.commit.sha, "abc123");
+        let ids: Vec<&str> = spliced
+            .functions
+            .iter()
+            .map(|f| f.function_id.as_str())
+            .collect();
+        assert_eq!(ids, vec!["src/bar.ts::other", "src/foo.ts::handler"]);
+        let carried = &spliced.functions[0];
+        assert!(
+            carried.churn.is_none(),
+            "parent churn belongs to the parent commit"
+        );
+        assert_eq!(carried.days_since_last_change, Some(7));
+        let updated = &spliced.functions[1];
+        assert_eq!(updated.lrs, 7.5);
+        assert_eq!(updated.callgraph.as_ref().map(|cg| cg.fan_in), Some(3));
+        assert_eq!(spliced.summary.as_ref().unwrap().total_functions, 2);
+
+        let mut unrelated = create_test_snapshot();
+        unrelated.commit.parents = vec!["fff999".to_string()];
+        assert!(Snapshot::splice(parent, unrelated, &changed).is_err());
+    }
+
+    #[test]
+    fn test_coverage_flags_risky_untested_and_leads_triage() {
+        let mut snapshot = create_test_snapshot();
+        let mut covered = snapshot.functions[0].clone();
+        covered.function_id = "src/foo.ts::covered".to_string();
+        covered.line = 60;
+        covered.lrs = 8.0;
+        snapshot.functions.push(covered);
+        snapshot.functions[0].lrs = 6.5;
+
+        // handler spans 42..=51, covered 60..=69
+        // Written by a CI job that checked the repo out somewhere else
+        let lcov =
+            "SF:/ci/work/src/foo.ts\nDA:42,1\nDA:45,0\nDA:50,0\nDA:60,3\nDA:61,1\nend_of_record\n";
+        let report = crate::coverage::LcovReport::parse(lcov).unwrap();
+        assert_eq!(snapshot.populate_coverage(&report, Path::new("/repo")), 2);
+        snapshot.populate_patterns(&crate::patterns::Thresholds::default());
+
+        let (handler, covered) = (&snapshot.functions[0], &snapshot.functions[1]);
+        assert_eq!(handler.coverage, Some(1.0 / 3.0));
+        assert_eq!(handler.patterns, vec!["risky_untested"]);
+        assert_eq!(covered.coverage, Some(1.0));
+        assert!(covered.patterns.is_empty());
+        // Untested risk outranks the higher-LRS covered function
+        assert_eq!(triage_order(handler, covered), std::cmp::Ordering::Less);
+    }
+
+    #[test]
+    fn test_mtime_recency_counts_back_from_newest_file() {
+        let tmp = tempfile::tempdir().unwrap();
+        let set_mtime = |name: &str, secs: u64| {
+            let path = tmp.path().join(name);
+            let file = std::fs::File::create(&path).unwrap();
+            file.set_modified(std::time::UNIX_EPOCH + std::time::Duration::from_secs(secs))
+                .unwrap();
+            name.to_string()
+        };
+        let old = set_mtime("old.ts", 1_700_000_000);
+        let new = set_mtime("new.ts", 1_700_000_000 + 10 * 86_400 + 3_600);
+
+        let mut snapshot = create_test_snapshot();
+        snapshot.functions[0].file = old;
+        let mut recent = snapshot.functions[0].clone();
+        recent.file = new;
+        snapshot.functions.push(recent);
+        snapshot.populate_mtime_recency(tmp.path()).unwrap();
+        assert_eq!(snapshot.functions[0].days_since_last_change, Some(10));
+        assert_eq!(snapshot.functions[1].days_since_last_change, Some(0));
+
+        // Same LRS; the more recently modified file ranks higher
+        snapshot.compute_activity_risk(None);
+        let risk = |i: usize| snapshot.functions[i].activity_risk.unwrap();
+        assert!(risk(1) > risk(0) && risk(0) > snapshot.functions[0].lrs);
+
+        snapshot.functions[0].file = "gone.ts".to_string();
+        assert!(snapshot.populate_mtime_recency(tmp.path()).is_err());
+    }
+
+    #[test]
+    fn test_store_dir_prefers_flag_then_env() {
+        let flag = PathBuf::from("/ci/flag-store");
+        assert_eq!(
+            resolve_store_dir(Some(&flag), Some("/ci/env-store".into())),
+            Some(flag)
+        );
+        assert_eq!(
+            resolve_store_dir(None, Some("/ci/env-store".into())),
+            Some(PathBuf::from("/ci/env-store"))
+        );
+        // An empty variable is the same as an unset one
+        assert_eq!(resolve_store_dir(None, Some("".into())), None);
+        assert_eq!(resolve_store_dir(None, None), None);
+    }
+
     #[test]
     fn test_function_id_format() {
         let snapshot = create_test_snapshot();
@@ -2579,6 +3488,32 @@ mod tests {
         assert_eq!(summary.total_functions, 1);
     }
 
+    #[test]
+    fn test_summary_breaks_down_by_language() {
+        let mut snapshot = create_test_snapshot();
+        let mut go_func = snapshot.functions[0].clone();
+        go_func.function_id = "src/main.go::run".to_string();
+        go_func.language = crate::language::Language::Go;
+        go_func.lrs = 2.0;
+        go_func.band = RiskBand::Low;
+        let mut go_func2 = go_func.clone();
+        go_func2.function_id = "src/main.go::stop".to_string();
+        go_func2.lrs = 6.0;
+        go_func2.band = RiskBand::High;
+        snapshot.functions.extend([go_func, go_func2]);
+
+        let snapshot = SnapshotEnricher::new(snapshot).enrich(None, 75).build();
+        let by_language = &snapshot.summary.as_ref().unwrap().by_language;
+        assert_eq!(by_language.len(), 2);
+        let go = &by_language["Go"];
+        assert_eq!(go.function_count, 2);
+        assert_eq!(go.total_lrs, 8.0);
+        assert_eq!(go.avg_lrs, 4.0);
+        assert_eq!(go.by_band.get("low"), Some(&1));
+        assert_eq!(go.by_band.get("high"), Some(&1));
+        assert_eq!(by_language["TypeScript"].function_count, 1);
+    }
+
     #[test]
     fn test_snapshot_enricher_enrich_computes_percentiles() {
         let snapshot = create_test_snapshot();
@@ -2586,6 +3521,51 @@ mod tests {
         assert!(snapshot.functions[0].percentile.is_some());
     }
 
+    #[test]
+    fn test_snapshot_enricher_excludes_trivial_functions_from_stats() {
+        let mut snapshot = create_test_snapshot();
+        let mut getter = snapshot.functions[0].clone();
+        getter.function_id = "src/foo.ts::getName".to_string();
+        getter.metrics.loc = 1;
+        getter.band = RiskBand::Low;
+        snapshot.functions.push(getter);
+
+        let counted = SnapshotEnricher::new(snapshot.clone())
+            .enrich(None, 75)
+            .build();
+        assert_eq!(counted.summary.as_ref().unwrap().total_functions, 2);
+
+        let trimmed = SnapshotEnricher::new(snapshot.clone())
+            .with_trivial_cutoff(TrivialCutoff {
+                min_loc: Some(3),
+                min_lrs: None,
+            })
+            .enrich(None, 75)
+            .build();
+        let summary = trimmed.summary.as_ref().unwrap();
+        assert_eq!(summary.total_functions, 1);
+        assert_eq!(summary.trivial_min_loc, Some(3));
+        assert!(!summary.by_band.contains_key("low"));
+        assert!(trimmed.functions[0].percentile.is_some());
+        assert!(trimmed.functions[1].percentile.is_none());
+
+        // A low-LRS function is trivial too, however long it is
+        snapshot.functions[1].metrics.loc = 40;
+        snapshot.functions[1].lrs = 0.5;
+        let trimmed = SnapshotEnricher::new(snapshot)
+            .with_trivial_cutoff(TrivialCutoff {
+                min_loc: None,
+                min_lrs: Some(1.0),
+            })
+            .enrich(None, 75)
+            .build();
+        let summary = trimmed.summary.as_ref().unwrap();
+        assert_eq!(summary.total_functions, 1);
+        assert_eq!(summary.trivial_min_lrs, Some(1.0));
+        assert_eq!(trimmed.functions.len(), 2);
+        assert!(trimmed.functions[1].percentile.is_none());
+    }
+
     #[test]
     fn test_snapshot_enricher_build_passthrough() {
         let snapshot = create_test_snapshot();
@@ -2642,13 +3622,44 @@ mod tests {
 
         let mut snapshot = snapshot;
         snapshot
-            .populate_touch_metrics(dir.path(), crate::snapshot::TouchMode::PerFunction, None)
+            .populate_touch_metrics(
+                dir.path(),
+                crate::snapshot::TouchMode::PerFunction,
+                &HashSet::new(),
+                None,
+            )
             .unwrap();
 
         assert_eq!(snapshot.functions[0].touch_count_30d, Some(7));
         assert_eq!(snapshot.functions[0].days_since_last_change, Some(3));
     }
 
+    #[test]
+    fn test_notebook_functions_fall_back_to_file_level_touches() {
+        let mut snapshot = create_test_snapshot();
+        snapshot.functions[0].file = "nb/explore.ipynb".to_string();
+        let mut cache = crate::touch_cache::TouchCache::new();
+        cache.insert(
+            crate::touch_cache::cache_key("abc123", "nb/explore.ipynb", 42, 51),
+            (7, Some(3)),
+        );
+
+        let dir = tempfile::tempdir().unwrap();
+        crate::touch_cache::write_touch_cache(dir.path(), &cache).unwrap();
+
+        for mode in [
+            crate::snapshot::TouchMode::PerFunction,
+            crate::snapshot::TouchMode::Hybrid { threshold: 0 },
+        ] {
+            snapshot
+                .populate_touch_metrics(dir.path(), mode, &HashSet::new(), None)
+                .unwrap();
+            // No per-function cache lookup: the directory has no history at all
+            assert_eq!(snapshot.functions[0].touch_count_30d, Some(0));
+            assert_eq!(snapshot.functions[0].days_since_last_change, None);
+        }
+    }
+
     #[test]
     fn test_populate_touch_metrics_progress_fires_on_all_cache_hits() {
         use std::sync::{Arc, Mutex};
@@ -2668,6 +3679,7 @@ mod tests {
             .populate_touch_metrics(
                 dir.path(),
                 crate::snapshot::TouchMode::PerFunction,
+                &HashSet::new(),
                 Some(&|i, n| {
                     calls_ref.lock().unwrap().push((i, n));
                 }),
@@ -2699,6 +3711,7 @@ mod tests {
         let _ = snapshot.populate_touch_metrics(
             dir.path(),
             crate::snapshot::TouchMode::PerFunction,
+            &HashSet::new(),
             Some(&|i, n| {
                 calls_ref.lock().unwrap().push((i, n));
             }),
@@ -2715,4 +3728,37 @@ mod tests {
         assert_eq!(calls[0], (0, 1));
         assert_eq!(*calls.last().unwrap(), (1, 1));
     }
+
+    #[test]
+    fn test_load_history_returns_last_summaries_in_timestamp_order() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(load_history(dir.path(), 10).unwrap().is_empty());
+
+        // Persist out of timestamp order; one snapshot has no summary
+        for (sha, timestamp, summarize) in [
+            ("ccc", 300, true),
+            ("aaa", 100, true),
+            ("bbb", 200, true),
+            ("ddd", 400, false),
+        ] {
+            let mut snapshot = create_test_snapshot();
+            snapshot.commit.sha = sha.to_string();
+            snapshot.commit.timestamp = timestamp;
+            if summarize {
+                snapshot.compute_summary(false, TrivialCutoff::default());
+            }
+            persist_snapshot(dir.path(), &snapshot, false).unwrap();
+            append_to_index(dir.path(), &snapshot).unwrap();
+        }
+
+        let shas = |window| -> Vec<String> {
+            load_history(dir.path(), window)
+                .unwrap()
+                .into_iter()
+                .map(|(commit, _)| commit.sha)
+                .collect()
+        };
+        assert_eq!(shas(10), vec!["aaa", "bbb", "ccc"]);
+        assert_eq!(shas(3), vec!["bbb", "ccc"]);
+    }
 }
@@ -1,13 +1,18 @@
-//! Suppression comment extraction
+//! Suppression comment extraction and config-based suppression rules
 //!
-//! Parses `// hotspots-ignore: reason` comments from source code.
+//! Parses `// hotspots-ignore: reason` comments from source code, and applies
+//! `suppress` rules from config (file/function globs with a required reason).
 //!
 //! Global invariants enforced:
 //! - Deterministic extraction (pure function of source, span)
 //! - Comment must be on the line immediately before the function
 //! - Returns None (no suppression), Some("") (no reason), or Some("reason")
+//! - An inline comment takes precedence over a config rule
 
 use crate::language::SourceSpan;
+use crate::report::FunctionRiskReport;
+use globset::{Glob, GlobMatcher};
+use serde::Serialize;
 use swc_common::SourceMap;
 
 /// Extract suppression comment for a function
@@ -77,6 +82,83 @@ pub fn extract_suppression(
     }
 }
 
+/// A config-level suppression: functions whose file matches `pattern` (and,
+/// if set, whose name matches `function`) are suppressed with `reason`.
+#[derive(Debug, Clone, Serialize)]
+pub struct SuppressRule {
+    pub pattern: String,
+    pub function: Option<String>,
+    pub reason: String,
+    #[serde(skip)]
+    file_matcher: GlobMatcher,
+    #[serde(skip)]
+    function_matcher: Option<GlobMatcher>,
+}
+
+impl SuppressRule {
+    /// Compile a rule from its config representation.
+    ///
+    /// A relative `pattern` (e.g. `src/legacy/**`) matches at any depth, since
+    /// analyzed paths may be absolute; `function` globs match the function name.
+    pub fn parse(pattern: &str, function: Option<&str>, reason: &str) -> anyhow::Result<Self> {
+        if reason.trim().is_empty() {
+            anyhow::bail!("suppress rule \"{}\" requires a non-empty reason", pattern);
+        }
+        let anchored = if pattern.starts_with('/') || pattern.starts_with("**") {
+            pattern.to_string()
+        } else {
+            format!("**/{}", pattern)
+        };
+        let file_matcher = Glob::new(&anchored)
+            .map_err(|e| anyhow::anyhow!("invalid suppress pattern {}: {}", pattern, e))?
+            .compile_matcher();
+        let function_matcher = function
+            .map(|f| {
+                Glob::new(f)
+                    .map(|g| g.compile_matcher())
+                    .map_err(|e| anyhow::anyhow!("invalid suppress function glob {}: {}", f, e))
+            })
+            .transpose()?;
+        Ok(SuppressRule {
+            pattern: pattern.to_string(),
+            function: function.map(str::to_string),
+            reason: reason.trim().to_string(),
+            file_matcher,
+            function_matcher,
+        })
+    }
+
+    /// Whether this rule covers the given function.
+    pub fn matches(&self, file: &str, function: &str) -> bool {
+        self.file_matcher.is_match(file)
+            && self
+                .function_matcher
+                .as_ref()
+                .map_or(true, |m| m.is_match(function))
+    }
+}
+
+/// Set `suppression_reason` on every report matched by a rule (first match wins).
+///
+/// Reports that already carry an inline `// hotspots-ignore` reason keep it.
+/// Suppressed functions stay in the output; only policies skip them.
+pub fn apply(reports: &mut [FunctionRiskReport], rules: &[SuppressRule]) {
+    if rules.is_empty() {
+        return;
+    }
+    for report in reports.iter_mut() {
+        if report.suppression_reason.is_some() {
+            continue;
+        }
+        if let Some(rule) = rules
+            .iter()
+            .find(|r| r.matches(&report.file, &report.function))
+        {
+            report.suppression_reason = Some(rule.reason.clone());
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -209,4 +291,77 @@ function foo() {
 "#;
         assert_eq!(parse_and_extract(source), None);
     }
+
+    fn report(file: &str, function: &str, suppression_reason: Option<&str>) -> FunctionRiskReport {
+        use crate::report::{MetricsReport, RiskReport};
+        FunctionRiskReport {
+            file: file.to_string(),
+            function: function.to_string(),
+            line: 1,
+            language: crate::language::Language::TypeScript,
+            metrics: MetricsReport {
+                cc: 1,
+                nd: 0,
+                fo: 0,
+                ns: 0,
+                loc: 1,
+                sloc: None,
+                return_points: None,
+                unwrap_count: None,
+                max_arm_loc: None,
+                max_data_nesting: None,
+                unreachable_code: None,
+            },
+            risk: RiskReport {
+                r_cc: 1.0,
+                r_nd: 0.0,
+                r_fo: 0.0,
+                r_ns: 0.0,
+            },
+            lrs: 1.0,
+            band: crate::risk::RiskBand::Low,
+            suppression_reason: suppression_reason.map(str::to_string),
+            patterns: vec![],
+            pattern_details: None,
+            callees: vec![],
+            explanation: None,
+            location: None,
+        }
+    }
+
+    #[test]
+    fn test_apply_rules_by_file_and_function_glob() {
+        let rules = vec![
+            SuppressRule::parse("src/legacy/**", None, "scheduled for deletion").unwrap(),
+            SuppressRule::parse("src/api/*.ts", Some("handle*"), "generated handlers").unwrap(),
+        ];
+        let mut reports = vec![
+            report("/repo/src/legacy/billing.ts", "charge", None),
+            report("/repo/src/api/users.ts", "handleGet", None),
+            report("/repo/src/api/users.ts", "validate", None),
+            report("/repo/src/legacy/old.ts", "keep", Some("inline reason")),
+        ];
+        apply(&mut reports, &rules);
+
+        assert_eq!(
+            reports[0].suppression_reason.as_deref(),
+            Some("scheduled for deletion")
+        );
+        assert_eq!(
+            reports[1].suppression_reason.as_deref(),
+            Some("generated handlers")
+        );
+        assert_eq!(reports[2].suppression_reason, None);
+        assert_eq!(
+            reports[3].suppression_reason.as_deref(),
+            Some("inline reason"),
+            "inline comment takes precedence over config rules"
+        );
+    }
+
+    #[test]
+    fn test_rule_requires_reason() {
+        assert!(SuppressRule::parse("src/**", None, "  ").is_err());
+        assert!(SuppressRule::parse("src/[", None, "reason").is_err());
+    }
 }
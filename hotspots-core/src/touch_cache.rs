@@ -1,7 +1,8 @@
 //! On-disk cache for per-function git touch metrics.
 //!
 //! Cache key: `"{sha}:{file}:{start}:{end}"` where file is a repo-relative path
-//! and start/end are 1-based line numbers. Value: `(touch_count_30d, days_since_last_change)`.
+//! and start/end are 1-based line numbers, followed by [`ignored_suffix`] when
+//! `ignore_commits` is configured. Value: `(touch_count_30d, days_since_last_change)`.
 //!
 //! **Line range shift behavior:** If surrounding code changes and a function's line
 //! range moves, the cache key will not match (start/end differ) — it is a miss.
@@ -19,7 +20,9 @@
 //! Warm is ~27× faster than cold and ~15% faster than file-level. Target was warm ≤ 2×
 //! file-level; this repo achieves 0.86× (warm per-function beats file-level).
 
-use std::collections::HashMap;
+use crate::diagnostics::{self, Level};
+use std::collections::{HashMap, HashSet};
+use std::hash::Hasher;
 use std::path::{Path, PathBuf};
 
 use anyhow::{Context, Result};
@@ -39,6 +42,24 @@ pub fn cache_key(sha: &str, file: &str, start: u32, end: u32) -> String {
     format!("{}:{}:{}:{}", sha, file, start, end)
 }
 
+/// Key suffix keeping entries computed without the `ignored` commits apart
+/// from the rest: empty when nothing is ignored, otherwise `#` and a hash of
+/// the ignored SHAs, so editing the ignore list misses instead of reusing
+/// stale counts.
+pub fn ignored_suffix(ignored: &HashSet<String>) -> String {
+    if ignored.is_empty() {
+        return String::new();
+    }
+    let mut shas: Vec<&str> = ignored.iter().map(String::as_str).collect();
+    shas.sort_unstable();
+    let mut hasher = siphasher::sip::SipHasher24::new();
+    for sha in shas {
+        hasher.write(sha.as_bytes());
+        hasher.write_u8(0);
+    }
+    format!("#{:016x}", hasher.finish())
+}
+
 /// Load the touch cache from disk.
 ///
 /// Returns `None` on cold start (file absent) or on read/decompress error (non-fatal).
@@ -51,7 +72,9 @@ pub fn read_touch_cache(repo_root: &Path) -> Option<TouchCache> {
     match load_compressed_json(&path) {
         Ok(cache) => Some(cache),
         Err(e) => {
-            eprintln!("warning: failed to load touch cache (proceeding cold): {e}");
+            if diagnostics::enabled(Level::Warn) {
+                eprintln!("warning: failed to load touch cache (proceeding cold): {e}");
+            }
             None
         }
     }
@@ -1473,6 +1473,12 @@ mod tests {
                     fo: 0,
                     ns: 0,
                     loc: 10,
+                    sloc: None,
+                    return_points: None,
+                    unwrap_count: None,
+                    max_arm_loc: None,
+                    max_data_nesting: None,
+                    unreachable_code: None,
                 },
                 lrs: 0.0,
                 band: RiskBand::Low,
@@ -1501,7 +1507,12 @@ mod tests {
                 isolation_rate: None,
                 age_days: None,
                 last_touch_days: None,
+                function_age_days: None,
+                coverage: None,
+                priority_score: None,
+                refactor_leverage: None,
                 explanation: None,
+                location: None,
             })
             .collect();
 
@@ -1648,6 +1659,12 @@ mod tests {
                     fo: 0,
                     ns: 0,
                     loc: 10,
+                    sloc: None,
+                    return_points: None,
+                    unwrap_count: None,
+                    max_arm_loc: None,
+                    max_data_nesting: None,
+                    unreachable_code: None,
                 },
                 lrs: (i as f64) / (counts.len() as f64),
                 band: RiskBand::Low,
@@ -1676,7 +1693,12 @@ mod tests {
                 isolation_rate: Some(0.5),
                 age_days: Some(30.0),
                 last_touch_days: Some(1.0),
+                function_age_days: None,
+                coverage: None,
+                priority_score: None,
+                refactor_leverage: None,
                 explanation: None,
+                location: None,
             })
             .collect();
 
@@ -1773,6 +1795,12 @@ mod tests {
                 fo: 0,
                 ns: 0,
                 loc: 10,
+                sloc: None,
+                return_points: None,
+                unwrap_count: None,
+                max_arm_loc: None,
+                max_data_nesting: None,
+                unreachable_code: None,
             },
             lrs: 0.0,
             band: RiskBand::Low,
@@ -1801,7 +1829,12 @@ mod tests {
             isolation_rate: None,
             age_days: None,
             last_touch_days: None,
+            function_age_days: None,
+            coverage: None,
+            priority_score: None,
+            refactor_leverage: None,
             explanation: None,
+            location: None,
         };
         assert_eq!(cold_start_features(&func), [0.0; 8]);
     }
@@ -1,15 +1,17 @@
 //! Trend semantics - extract high-signal trends from snapshot history
 //!
 //! Analyzes historical snapshots to identify:
-//! - Risk velocity (rate of LRS change)
+//! - Risk velocity (rate of LRS change), optionally EWMA-smoothed
 //! - Hotspot stability (consistency of high-risk functions)
 //! - Refactor effectiveness (sustained improvements)
+//! - Stale hotspots (high-risk functions left untouched by any improvement)
 //!
 //! Global invariants enforced:
 //! - Deterministic ordering (by commit timestamp, then SHA)
 //! - No snapshot mutation
 //! - Trends are derived, not stored
 
+use crate::risk::RiskBand;
 use crate::snapshot::{Index, Snapshot};
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
@@ -25,6 +27,9 @@ const REFACTOR_IMPROVEMENT_THRESHOLD: f64 = -1.0;
 /// Rebound threshold after improvement
 const REFACTOR_REBOUND_THRESHOLD: f64 = 0.5;
 
+/// Default history required before velocity/stability get a confident label
+pub const DEFAULT_MIN_SNAPSHOTS: usize = 5;
+
 /// Velocity direction
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
@@ -32,6 +37,9 @@ pub enum VelocityDirection {
     Positive,
     Negative,
     Flat,
+    /// Function seen in fewer snapshots than `min_snapshots`
+    #[serde(rename = "insufficient_data")]
+    InsufficientData,
 }
 
 /// Risk velocity for a function
@@ -40,6 +48,10 @@ pub enum VelocityDirection {
 pub struct RiskVelocity {
     pub function_id: String,
     pub velocity: f64,
+    /// EWMA of the per-snapshot LRS changes, when smoothing was requested
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub smoothed_velocity: Option<f64>,
+    /// Sign of `smoothed_velocity` when present, otherwise of `velocity`
     pub direction: VelocityDirection,
     pub first_lrs: f64,
     pub last_lrs: f64,
@@ -53,6 +65,9 @@ pub enum HotspotStability {
     Stable,
     Emerging,
     Volatile,
+    /// Window holds fewer snapshots than `min_snapshots`
+    #[serde(rename = "insufficient_data")]
+    InsufficientData,
 }
 
 /// Hotspot analysis for a function
@@ -86,6 +101,21 @@ pub struct RefactorAnalysis {
     pub rebound_detected: bool,
 }
 
+/// A function that sat in the high or critical band in every snapshot of the window
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub struct StaleHotspot {
+    pub function_id: String,
+    /// Band in the newest snapshot
+    pub band: RiskBand,
+    pub first_lrs: f64,
+    pub last_lrs: f64,
+    /// Snapshots it has been stuck for (the whole window)
+    pub stuck_snapshots: usize,
+    /// Days between the oldest and newest snapshot commits
+    pub stuck_days: u64,
+}
+
 /// Complete trends analysis
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(rename_all = "snake_case")]
@@ -93,6 +123,8 @@ pub struct TrendsAnalysis {
     pub velocities: Vec<RiskVelocity>,
     pub hotspots: Vec<HotspotAnalysis>,
     pub refactors: Vec<RefactorAnalysis>,
+    #[serde(default)]
+    pub stale_hotspots: Vec<StaleHotspot>,
 }
 
 impl TrendsAnalysis {
@@ -147,11 +179,29 @@ pub fn load_snapshot_window(repo_root: &Path, window_size: usize) -> Result<Vec<
     Ok(snapshots)
 }
 
+/// Exponentially-weighted moving average of the step-to-step changes in `lrs`
+///
+/// Starts from the first change and folds in each later one with weight
+/// `alpha`, so every step counts and recent ones count most. Needs at least
+/// two points.
+pub fn ewma_velocity(lrs: &[f64], alpha: f64) -> Option<f64> {
+    let mut steps = lrs.windows(2).map(|w| w[1] - w[0]);
+    let first = steps.next()?;
+    Some(steps.fold(first, |smoothed, step| {
+        alpha * step + (1.0 - alpha) * smoothed
+    }))
+}
+
 /// Compute risk velocity for all functions in a window
 ///
 /// Formula: `(LRS_last - LRS_first) / (commit_count - 1)`
-/// Requires at least 2 data points.
-pub fn compute_risk_velocities(snapshots: &[Snapshot]) -> Vec<RiskVelocity> {
+/// Requires at least 2 data points. With `smoothing` (an EWMA alpha in
+/// (0, 1]), each velocity also carries [`ewma_velocity`] over the function's
+/// series, and the direction follows the smoothed value.
+pub fn compute_risk_velocities(
+    snapshots: &[Snapshot],
+    smoothing: Option<f64>,
+) -> Vec<RiskVelocity> {
     if snapshots.len() < 2 {
         return Vec::new();
     }
@@ -198,10 +248,16 @@ pub fn compute_risk_velocities(snapshots: &[Snapshot]) -> Vec<RiskVelocity> {
             0.0
         };
 
+        let smoothed_velocity = smoothing.and_then(|alpha| {
+            let series: Vec<f64> = sorted_points.iter().map(|(_, lrs)| *lrs).collect();
+            ewma_velocity(&series, alpha)
+        });
+
         // Determine direction
-        let direction = if velocity.abs() < FLAT_VELOCITY_EPSILON {
+        let trend = smoothed_velocity.unwrap_or(velocity);
+        let direction = if trend.abs() < FLAT_VELOCITY_EPSILON {
             VelocityDirection::Flat
-        } else if velocity > 0.0 {
+        } else if trend > 0.0 {
             VelocityDirection::Positive
         } else {
             VelocityDirection::Negative
@@ -210,6 +266,7 @@ pub fn compute_risk_velocities(snapshots: &[Snapshot]) -> Vec<RiskVelocity> {
         velocities.push(RiskVelocity {
             function_id,
             velocity,
+            smoothed_velocity,
             direction,
             first_lrs,
             last_lrs,
@@ -396,23 +453,111 @@ pub fn compute_refactor_effectiveness(snapshots: &[Snapshot]) -> Vec<RefactorAna
     refactor_analyses
 }
 
+/// Find functions stuck in the high or critical band across the whole window
+///
+/// A function qualifies when it appears in every snapshot and its band never
+/// drops below High. Unlike hotspot stability, which tracks top-K membership
+/// relative to the rest of the repo, this is an absolute test: persistent debt
+/// nobody has paid down. Sorted by latest LRS descending, then function ID.
+pub fn compute_stale_hotspots(snapshots: &[Snapshot]) -> Vec<StaleHotspot> {
+    let (Some(first), Some(last)) = (snapshots.first(), snapshots.last()) else {
+        return Vec::new();
+    };
+    let stuck_days = (last.commit.timestamp - first.commit.timestamp).max(0) as u64 / 86_400;
+
+    let mut lrs_by_function: HashMap<&str, Vec<(f64, RiskBand)>> = HashMap::new();
+    for snapshot in snapshots {
+        for function in &snapshot.functions {
+            lrs_by_function
+                .entry(function.function_id.as_str())
+                .or_default()
+                .push((function.lrs, function.band));
+        }
+    }
+
+    let mut stale: Vec<StaleHotspot> = lrs_by_function
+        .into_iter()
+        .filter(|(_, series)| {
+            series.len() == snapshots.len()
+                && series.iter().all(|(_, band)| *band >= RiskBand::High)
+        })
+        .map(|(function_id, series)| StaleHotspot {
+            function_id: function_id.to_string(),
+            band: series[series.len() - 1].1,
+            first_lrs: series[0].0,
+            last_lrs: series[series.len() - 1].0,
+            stuck_snapshots: series.len(),
+            stuck_days,
+        })
+        .collect();
+    stale.sort_by(|a, b| {
+        b.last_lrs
+            .partial_cmp(&a.last_lrs)
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then_with(|| a.function_id.cmp(&b.function_id))
+    });
+    stale
+}
+
 /// Compute complete trends analysis
+///
+/// Velocities computed from fewer than `min_snapshots` points, and hotspot
+/// stability over a window of fewer than `min_snapshots` snapshots, are
+/// labelled `InsufficientData`; their raw numbers are still reported. Stale
+/// hotspots need a window of at least `min_snapshots` snapshots (and at least
+/// two) to say anything, so a shorter window reports none.
+/// `smoothing` is the EWMA alpha for smoothed velocities (None = raw only).
 pub fn analyze_trends(
     repo_root: &Path,
     window_size: usize,
     top_k: usize,
+    min_snapshots: usize,
+    smoothing: Option<f64>,
 ) -> Result<TrendsAnalysis> {
     let snapshots = load_snapshot_window(repo_root, window_size)?;
+    Ok(analyze_snapshot_trends(
+        &snapshots,
+        top_k,
+        min_snapshots,
+        smoothing,
+    ))
+}
 
-    let velocities = compute_risk_velocities(&snapshots);
-    let hotspots = compute_hotspot_stability(&snapshots, top_k);
-    let refactors = compute_refactor_effectiveness(&snapshots);
+/// Compute trends over already-loaded snapshots, oldest first
+///
+/// Lets callers narrow the window (e.g. to one author's functions) before
+/// velocities and hotspot stability are computed.
+pub fn analyze_snapshot_trends(
+    snapshots: &[Snapshot],
+    top_k: usize,
+    min_snapshots: usize,
+    smoothing: Option<f64>,
+) -> TrendsAnalysis {
+    let mut velocities = compute_risk_velocities(snapshots, smoothing);
+    for velocity in &mut velocities {
+        if velocity.commit_count < min_snapshots {
+            velocity.direction = VelocityDirection::InsufficientData;
+        }
+    }
+    let mut hotspots = compute_hotspot_stability(snapshots, top_k);
+    for hotspot in &mut hotspots {
+        if hotspot.total_snapshots < min_snapshots {
+            hotspot.stability = HotspotStability::InsufficientData;
+        }
+    }
+    let refactors = compute_refactor_effectiveness(snapshots);
+    let stale_hotspots = if snapshots.len() >= min_snapshots.max(2) {
+        compute_stale_hotspots(snapshots)
+    } else {
+        Vec::new()
+    };
 
-    Ok(TrendsAnalysis {
+    TrendsAnalysis {
         velocities,
         hotspots,
         refactors,
-    })
+        stale_hotspots,
+    }
 }
 
 #[cfg(test)]
@@ -461,6 +606,7 @@ mod tests {
                 pattern_details: None,
                 callees: vec![],
                 explanation: None,
+                location: None,
             })
             .collect();
 
@@ -484,6 +630,12 @@ mod tests {
                         fo: 0,
                         ns: 0,
                         loc: 10,
+                        sloc: None,
+                        return_points: None,
+                        unwrap_count: None,
+                        max_arm_loc: None,
+                        max_data_nesting: None,
+                        unreachable_code: None,
                     },
                     lrs: 1.0,
                     band: crate::risk::RiskBand::Low,
@@ -512,7 +664,12 @@ mod tests {
                     isolation_rate: None,
                     age_days: None,
                     last_touch_days: None,
+                    function_age_days: None,
+                    coverage: None,
+                    priority_score: None,
+                    refactor_leverage: None,
                     explanation: None,
+                    location: None,
                 }],
             ),
             create_test_snapshot(
@@ -529,6 +686,12 @@ mod tests {
                         fo: 0,
                         ns: 0,
                         loc: 10,
+                        sloc: None,
+                        return_points: None,
+                        unwrap_count: None,
+                        max_arm_loc: None,
+                        max_data_nesting: None,
+                        unreachable_code: None,
                     },
                     lrs: 3.0,
                     band: crate::risk::RiskBand::Moderate,
@@ -557,18 +720,37 @@ mod tests {
                     isolation_rate: None,
                     age_days: None,
                     last_touch_days: None,
+                    function_age_days: None,
+                    coverage: None,
+                    priority_score: None,
+                    refactor_leverage: None,
                     explanation: None,
+                    location: None,
                 }],
             ),
         ];
 
-        let velocities = compute_risk_velocities(&snapshots);
+        let velocities = compute_risk_velocities(&snapshots, None);
         assert_eq!(velocities.len(), 1);
         assert_eq!(velocities[0].function_id, "src/foo.ts::func");
         assert_eq!(velocities[0].velocity, 2.0); // (3.0 - 1.0) / (2 - 1) = 2.0
         assert_eq!(velocities[0].direction, VelocityDirection::Positive);
     }
 
+    #[test]
+    fn test_ewma_velocity_weights_the_whole_series() {
+        // One late spike: raw endpoint velocity (4.0 - 1.0) / 3 = 1.0
+        let series = [1.0, 1.0, 1.0, 4.0];
+        assert_eq!(ewma_velocity(&series, 0.5), Some(1.5)); // 0 → 0 → 0.5*3
+        assert_eq!(ewma_velocity(&series, 1.0), Some(3.0)); // last step only
+
+        // Climb then full retreat: raw velocity is 0, the smoothed trend still falls
+        let smoothed = ewma_velocity(&[1.0, 3.0, 5.0, 1.0], 0.5).unwrap();
+        assert!((smoothed - -1.0).abs() < 1e-9); // 2 → 2 → 0.5*-4 + 0.5*2
+
+        assert_eq!(ewma_velocity(&[2.0], 0.5), None);
+    }
+
     #[test]
     fn test_risk_velocity_flat() {
         let snapshots = vec![
@@ -586,6 +768,12 @@ mod tests {
                         fo: 0,
                         ns: 0,
                         loc: 10,
+                        sloc: None,
+                        return_points: None,
+                        unwrap_count: None,
+                        max_arm_loc: None,
+                        max_data_nesting: None,
+                        unreachable_code: None,
                     },
                     lrs: 1.0,
                     band: crate::risk::RiskBand::Low,
@@ -614,7 +802,12 @@ mod tests {
                     isolation_rate: None,
                     age_days: None,
                     last_touch_days: None,
+                    function_age_days: None,
+                    coverage: None,
+                    priority_score: None,
+                    refactor_leverage: None,
                     explanation: None,
+                    location: None,
                 }],
             ),
             create_test_snapshot(
@@ -631,6 +824,12 @@ mod tests {
                         fo: 0,
                         ns: 0,
                         loc: 10,
+                        sloc: None,
+                        return_points: None,
+                        unwrap_count: None,
+                        max_arm_loc: None,
+                        max_data_nesting: None,
+                        unreachable_code: None,
                     },
                     lrs: 1.0,
                     band: crate::risk::RiskBand::Low,
@@ -659,12 +858,17 @@ mod tests {
                     isolation_rate: None,
                     age_days: None,
                     last_touch_days: None,
+                    function_age_days: None,
+                    coverage: None,
+                    priority_score: None,
+                    refactor_leverage: None,
                     explanation: None,
+                    location: None,
                 }],
             ),
         ];
 
-        let velocities = compute_risk_velocities(&snapshots);
+        let velocities = compute_risk_velocities(&snapshots, None);
         assert_eq!(velocities.len(), 1);
         assert_eq!(velocities[0].direction, VelocityDirection::Flat);
     }
@@ -687,6 +891,12 @@ mod tests {
                             fo: 3,
                             ns: 2,
                             loc: 20,
+                            sloc: None,
+                            return_points: None,
+                            unwrap_count: None,
+                            max_arm_loc: None,
+                            max_data_nesting: None,
+                            unreachable_code: None,
                         },
                         lrs: 15.0,
                         band: crate::risk::RiskBand::High,
@@ -715,7 +925,12 @@ mod tests {
                         isolation_rate: None,
                         age_days: None,
                         last_touch_days: None,
+                        function_age_days: None,
+                        coverage: None,
+                        priority_score: None,
+                        refactor_leverage: None,
                         explanation: None,
+                        location: None,
                     },
                     FunctionSnapshot {
                         function_id: "src/bar.ts::func2".to_string(),
@@ -728,6 +943,12 @@ mod tests {
                             fo: 1,
                             ns: 0,
                             loc: 10,
+                            sloc: None,
+                            return_points: None,
+                            unwrap_count: None,
+                            max_arm_loc: None,
+                            max_data_nesting: None,
+                            unreachable_code: None,
                         },
                         lrs: 5.0,
                         band: crate::risk::RiskBand::Moderate,
@@ -756,7 +977,12 @@ mod tests {
                         isolation_rate: None,
                         age_days: None,
                         last_touch_days: None,
+                        function_age_days: None,
+                        coverage: None,
+                        priority_score: None,
+                        refactor_leverage: None,
                         explanation: None,
+                        location: None,
                     },
                 ],
             ),
@@ -775,6 +1001,12 @@ mod tests {
                             fo: 4,
                             ns: 2,
                             loc: 25,
+                            sloc: None,
+                            return_points: None,
+                            unwrap_count: None,
+                            max_arm_loc: None,
+                            max_data_nesting: None,
+                            unreachable_code: None,
                         },
                         lrs: 18.0,
                         band: crate::risk::RiskBand::High,
@@ -803,7 +1035,12 @@ mod tests {
                         isolation_rate: None,
                         age_days: None,
                         last_touch_days: None,
+                        function_age_days: None,
+                        coverage: None,
+                        priority_score: None,
+                        refactor_leverage: None,
                         explanation: None,
+                        location: None,
                     },
                     FunctionSnapshot {
                         function_id: "src/bar.ts::func2".to_string(),
@@ -816,6 +1053,12 @@ mod tests {
                             fo: 1,
                             ns: 0,
                             loc: 10,
+                            sloc: None,
+                            return_points: None,
+                            unwrap_count: None,
+                            max_arm_loc: None,
+                            max_data_nesting: None,
+                            unreachable_code: None,
                         },
                         lrs: 5.0,
                         band: crate::risk::RiskBand::Moderate,
@@ -844,7 +1087,12 @@ mod tests {
                         isolation_rate: None,
                         age_days: None,
                         last_touch_days: None,
+                        function_age_days: None,
+                        coverage: None,
+                        priority_score: None,
+                        refactor_leverage: None,
                         explanation: None,
+                        location: None,
                     },
                 ],
             ),
@@ -856,4 +1104,68 @@ mod tests {
         assert_eq!(hotspots[0].stability, HotspotStability::Stable);
         assert_eq!(hotspots[0].overlap_ratio, 1.0);
     }
+
+    fn banded_function(function_id: &str, lrs: f64, band: &str) -> FunctionSnapshot {
+        serde_json::from_value(serde_json::json!({
+            "function_id": function_id,
+            "file": "src/foo.ts",
+            "line": 1,
+            "language": "TypeScript",
+            "metrics": { "cc": 1, "nd": 0, "fo": 0, "ns": 0, "loc": 10 },
+            "lrs": lrs,
+            "band": band,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_stale_hotspots_never_leave_high_band() {
+        let series = [
+            [(7.0, "high"), (9.5, "critical"), (8.0, "high")],
+            [(7.5, "high"), (9.0, "critical"), (4.0, "moderate")],
+            [(7.5, "high"), (9.2, "critical"), (8.5, "high")],
+        ];
+        let mut snapshots: Vec<Snapshot> = series
+            .iter()
+            .enumerate()
+            .map(|(i, row)| {
+                let functions = [
+                    "src/foo.ts::stuck",
+                    "src/foo.ts::worst",
+                    "src/foo.ts::fixed",
+                ]
+                .iter()
+                .zip(row)
+                .map(|(id, (lrs, band))| banded_function(id, *lrs, band))
+                .collect();
+                create_test_snapshot(&format!("sha{}", i + 1), &format!("sha{i}"), functions)
+            })
+            .collect();
+        snapshots[0].commit.timestamp = 1_700_000_000;
+        snapshots[2].commit.timestamp = 1_700_000_000 + 45 * 86_400;
+        // Born after the window opened: not stuck for the whole window
+        snapshots[2]
+            .functions
+            .push(banded_function("src/foo.ts::new", 12.0, "critical"));
+
+        let stale = compute_stale_hotspots(&snapshots);
+        let ids: Vec<&str> = stale.iter().map(|s| s.function_id.as_str()).collect();
+        assert_eq!(ids, vec!["src/foo.ts::worst", "src/foo.ts::stuck"]);
+        assert_eq!(stale[0].band, RiskBand::Critical);
+        assert_eq!(stale[1].first_lrs, 7.0);
+        assert_eq!(stale[1].last_lrs, 7.5);
+        assert_eq!(stale[1].stuck_snapshots, 3);
+        assert_eq!(stale[1].stuck_days, 45);
+
+        // Too little history to call anything stale
+        assert!(analyze_snapshot_trends(&snapshots[..2], 5, 3, None)
+            .stale_hotspots
+            .is_empty());
+        assert_eq!(
+            analyze_snapshot_trends(&snapshots, 5, 3, None)
+                .stale_hotspots
+                .len(),
+            2
+        );
+    }
 }
@@ -3,6 +3,8 @@
 //! These tests explicitly validate critical invariants that must always hold.
 //! Run in CI to prevent regressions.
 
+#![cfg(feature = "native")]
+
 use hotspots_core::language::Language;
 use hotspots_core::report::{FunctionRiskReport, MetricsReport, RiskReport};
 use hotspots_core::risk::RiskBand;
@@ -35,6 +37,12 @@ fn create_test_snapshot(sha: &str, parent_sha: &str) -> snapshot::Snapshot {
             fo: 3,
             ns: 1,
             loc: 10,
+            sloc: None,
+            return_points: None,
+            unwrap_count: None,
+            max_arm_loc: None,
+            max_data_nesting: None,
+            unreachable_code: None,
         },
         risk: RiskReport {
             r_cc: 2.0,
@@ -49,6 +57,7 @@ fn create_test_snapshot(sha: &str, parent_sha: &str) -> snapshot::Snapshot {
         pattern_details: None,
         callees: vec![],
         explanation: None,
+        location: None,
     };
 
     snapshot::Snapshot::new(git_context, vec![report])
@@ -170,6 +179,12 @@ fn test_delta_single_parent_only() {
             fo: 3,
             ns: 1,
             loc: 10,
+            sloc: None,
+            return_points: None,
+            unwrap_count: None,
+            max_arm_loc: None,
+            max_data_nesting: None,
+            unreachable_code: None,
         },
         risk: RiskReport {
             r_cc: 2.0,
@@ -184,6 +199,7 @@ fn test_delta_single_parent_only() {
         pattern_details: None,
         callees: vec![],
         explanation: None,
+        location: None,
     };
 
     let merge_snapshot = snapshot::Snapshot::new(git_context, vec![report]);
@@ -253,6 +269,12 @@ fn test_delta_negative_deltas_allowed() {
             fo: 1,
             ns: 0,
             loc: 10,
+            sloc: None,
+            return_points: None,
+            unwrap_count: None,
+            max_arm_loc: None,
+            max_data_nesting: None,
+            unreachable_code: None,
         }, // Lower than parent
         risk: RiskReport {
             r_cc: 2.0,
@@ -267,6 +289,7 @@ fn test_delta_negative_deltas_allowed() {
         pattern_details: None,
         callees: vec![],
         explanation: None,
+        location: None,
     };
 
     let current = snapshot::Snapshot::new(git_context, vec![report]);
@@ -3,6 +3,8 @@
 //! Covers Delta computation with two persisted snapshots, filtering,
 //! --top sort order, JSONL serialization, and aggregate attachment.
 
+#![cfg(feature = "native")]
+
 use hotspots_core::delta::{Delta, FunctionStatus};
 use hotspots_core::git::GitContext;
 use hotspots_core::language::Language;
@@ -42,6 +44,12 @@ fn make_report(file: &str, func: &str, cc: u32, lrs: f64, band: &str) -> Functio
             fo: 1,
             ns: 1,
             loc: 20,
+            sloc: None,
+            return_points: None,
+            unwrap_count: None,
+            max_arm_loc: None,
+            max_data_nesting: None,
+            unreachable_code: None,
         },
         risk: RiskReport {
             r_cc: 1.0,
@@ -56,6 +64,7 @@ fn make_report(file: &str, func: &str, cc: u32, lrs: f64, band: &str) -> Functio
         pattern_details: None,
         callees: vec![],
         explanation: None,
+        location: None,
     }
 }
 
@@ -304,6 +313,8 @@ fn test_diff_delta_aggregates_attached() {
         &delta,
         &[],
         &[],
+        &[],
+        &[],
     ));
 
     assert!(
@@ -318,3 +329,41 @@ fn test_diff_delta_aggregates_attached() {
     );
     assert_eq!(agg.files[0].file, "src/i.ts");
 }
+
+#[test]
+fn test_diff_delta_aggregates_group_anonymous_callbacks_by_file() {
+    // Anonymous callbacks are named `outer::<anonymous@LINE>`, so their IDs
+    // carry a second `::` that must not be taken for the file separator
+    let tmp = TempDir::new().unwrap();
+    init_repo(tmp.path());
+
+    let base = Snapshot::new(
+        git_ctx("base007", "root007"),
+        vec![make_report(
+            "src/j.ts",
+            "main::<anonymous@3>",
+            2,
+            1.5,
+            "low",
+        )],
+    );
+    let head = Snapshot::new(
+        git_ctx("head007", "base007"),
+        vec![make_report(
+            "src/j.ts",
+            "main::<anonymous@3>",
+            6,
+            4.0,
+            "moderate",
+        )],
+    );
+
+    let base = persist_and_load(tmp.path(), &base);
+    let head = persist_and_load(tmp.path(), &head);
+
+    let delta = Delta::new(&head, Some(&base)).expect("delta failed");
+    let agg = hotspots_core::aggregates::compute_delta_aggregates(&delta, &[], &[], &[], &[]);
+    let files: Vec<&str> = agg.files.iter().map(|f| f.file.as_str()).collect();
+    assert_eq!(files, vec!["src/j.ts"]);
+    assert_eq!(agg.files[0].regression_count, 1);
+}
@@ -10,7 +10,9 @@
 //! - Assert relationships only
 //! - Fail loudly on invariant violation
 
-use hotspots_core::{analyze, delta, git, snapshot, AnalysisOptions};
+#![cfg(feature = "native")]
+
+use hotspots_core::{analyze, delta, git, prune, snapshot, AnalysisOptions};
 use std::fs;
 use std::path::{Path, PathBuf};
 use std::process::Command;
@@ -421,3 +423,452 @@ fn test_force_push_does_not_corrupt_history() {
         "snapshot1 content must be unchanged after reset (immutability)"
     );
 }
+
+#[test]
+fn test_shallow_clone_detection() {
+    let temp_dir = create_temp_git_repo();
+    let repo_path = temp_dir.path();
+
+    create_ts_file(repo_path, "src/a.ts", "function a() { return 1; }\n");
+    git_commit(repo_path, "first");
+    create_ts_file(repo_path, "src/a.ts", "function a() { return 2; }\n");
+    git_commit(repo_path, "second");
+    assert!(
+        !git::is_shallow_repository(repo_path),
+        "full repo must not be reported as shallow"
+    );
+
+    let clone_parent = tempfile::tempdir().expect("failed to create temp directory");
+    let clone_path = clone_parent.path().join("shallow");
+    let source_url = format!("file://{}", repo_path.display());
+    git_command(
+        clone_parent.path(),
+        &[
+            "clone",
+            "--depth",
+            "1",
+            &source_url,
+            &clone_path.to_string_lossy(),
+        ],
+    );
+    assert!(
+        git::is_shallow_repository(&clone_path),
+        "depth-1 clone must be reported as shallow"
+    );
+}
+
+#[test]
+fn test_function_first_seen_tracks_line_range_not_file() {
+    let temp_dir = create_temp_git_repo();
+    let repo_path = temp_dir.path();
+
+    create_ts_file(repo_path, "src/a.ts", "function a() {\n  return 1;\n}\n");
+    let first = git_commit(repo_path, "add a");
+    create_ts_file(
+        repo_path,
+        "src/a.ts",
+        "function a() {\n  return 1;\n}\nfunction b() {\n  return 2;\n}\n",
+    );
+    let second = git_commit(repo_path, "add b");
+
+    let commit_ts = |sha: &str| -> i64 {
+        git_command(repo_path, &["log", "-1", "--format=%ct", sha])
+            .parse()
+            .expect("commit timestamp")
+    };
+    assert_eq!(
+        git::function_first_seen(repo_path, "src/a.ts", 1, 3).unwrap(),
+        Some(commit_ts(&first)),
+        "a() must date from the commit that introduced it"
+    );
+    assert_eq!(
+        git::function_first_seen(repo_path, "src/a.ts", 4, 6).unwrap(),
+        Some(commit_ts(&second)),
+        "b() must date from its own commit, not the file's creation"
+    );
+}
+
+#[test]
+fn test_prune_default_refs_keep_remote_only_history() {
+    let temp_dir = create_temp_git_repo();
+    let repo_path = temp_dir.path();
+
+    create_ts_file(repo_path, "simple.ts", "function simple() { return 1; }");
+    let commit1 = git_commit(repo_path, "Initial commit");
+    let snapshot1 = create_snapshot_for_commit(repo_path);
+    snapshot::persist_snapshot(repo_path, &snapshot1, false).expect("failed to persist snapshot1");
+    snapshot::append_to_index(repo_path, &snapshot1).expect("failed to index snapshot1");
+
+    create_ts_file(repo_path, "simple.ts", "function simple() { return 2; }");
+    git_commit(repo_path, "Second commit");
+    let snapshot2 = create_snapshot_for_commit(repo_path);
+    snapshot::persist_snapshot(repo_path, &snapshot2, false).expect("failed to persist snapshot2");
+    snapshot::append_to_index(repo_path, &snapshot2).expect("failed to index snapshot2");
+
+    // CI-style clone: the second commit is only reachable from a remote-tracking ref
+    git_command(
+        repo_path,
+        &["update-ref", "refs/remotes/origin/main", "HEAD"],
+    );
+    git_command(repo_path, &["reset", "--hard", &commit1]);
+
+    let patterns = prune::default_ref_patterns(repo_path).expect("default ref patterns");
+    assert!(
+        patterns.iter().any(|p| p == "refs/remotes/"),
+        "a single local branch must also track remote refs: {:?}",
+        patterns
+    );
+    let result = prune::prune_unreachable(
+        repo_path,
+        prune::PruneOptions {
+            ref_patterns: patterns,
+            older_than_days: None,
+            dry_run: true,
+        },
+    )
+    .expect("prune with default refs");
+    assert_eq!(result.pruned_count, 0);
+    assert_eq!(result.reachable_count, 2);
+
+    // Local branches alone miss the remote-only commit
+    let result = prune::prune_unreachable(
+        repo_path,
+        prune::PruneOptions {
+            dry_run: true,
+            ..Default::default()
+        },
+    )
+    .expect("prune with local refs");
+    assert_eq!(result.pruned_shas, vec![snapshot2.commit_sha().to_string()]);
+
+    // A second local branch means local refs are no longer sparse
+    git_command(repo_path, &["branch", "feature"]);
+    assert_eq!(
+        prune::default_ref_patterns(repo_path).expect("default ref patterns"),
+        vec!["refs/heads/".to_string()]
+    );
+}
+
+#[test]
+fn test_trends_short_history_is_insufficient_data() {
+    use hotspots_core::trends::{analyze_trends, HotspotStability, VelocityDirection};
+
+    let temp_dir = create_temp_git_repo();
+    let repo_path = temp_dir.path();
+
+    for (i, body) in ["return 1;", "if (x) { return 1; } return 2;"]
+        .iter()
+        .enumerate()
+    {
+        create_ts_file(
+            repo_path,
+            "simple.ts",
+            &format!("function simple(x: number) {{ {body} }}"),
+        );
+        git_commit(repo_path, &format!("Commit {i}"));
+        let mut snapshot = create_snapshot_for_commit(repo_path);
+        // Both commits usually land in the same second; keep their order fixed
+        snapshot.commit.timestamp += i as i64;
+        snapshot::persist_snapshot(repo_path, &snapshot, false).expect("failed to persist");
+        snapshot::append_to_index(repo_path, &snapshot).expect("failed to index");
+    }
+
+    let trends = analyze_trends(repo_path, 10, 5, 5, None).expect("trends");
+    assert_eq!(trends.velocities.len(), 1);
+    assert_eq!(
+        trends.velocities[0].direction,
+        VelocityDirection::InsufficientData
+    );
+    assert!(
+        trends.velocities[0].velocity > 0.0,
+        "raw velocity still reported"
+    );
+    assert!(trends
+        .hotspots
+        .iter()
+        .all(|h| h.stability == HotspotStability::InsufficientData));
+    assert!(trends.to_json().unwrap().contains("\"insufficient_data\""));
+
+    let trends = analyze_trends(repo_path, 10, 5, 2, None).expect("trends");
+    assert_eq!(trends.velocities[0].direction, VelocityDirection::Positive);
+    assert_eq!(trends.hotspots[0].stability, HotspotStability::Stable);
+}
+
+#[test]
+fn test_new_cc_counts_only_branches_on_added_lines() {
+    let temp_dir = create_temp_git_repo();
+    let repo_path = temp_dir.path();
+    let file = repo_path.join("check.ts");
+
+    create_ts_file(
+        repo_path,
+        "check.ts",
+        "function check(x: number) {\n  if (x > 1) {\n    return 1;\n  }\n  if (x > 2) {\n    return 2;\n  }\n  return 0;\n}\n",
+    );
+    git_commit(repo_path, "Existing branches");
+    let options = AnalysisOptions {
+        min_lrs: None,
+        top_n: None,
+    };
+    let reports = analyze(&file, options).expect("failed to analyze");
+    let parent = snapshot::Snapshot::new(
+        git::extract_git_context_at(repo_path).expect("git context"),
+        reports,
+    );
+    snapshot::persist_snapshot(repo_path, &parent, false).expect("failed to persist");
+
+    // One new branch, added between the two existing ones
+    create_ts_file(
+        repo_path,
+        "check.ts",
+        "function check(x: number) {\n  if (x > 1) {\n    return 1;\n  }\n  if (x < 0) {\n    return -1;\n  }\n  if (x > 2) {\n    return 2;\n  }\n  return 0;\n}\n",
+    );
+    git_commit(repo_path, "Add a branch");
+    let options = AnalysisOptions {
+        min_lrs: None,
+        top_n: None,
+    };
+    let (reports, decisions) =
+        delta::analyze_with_decisions(&file, options, None).expect("failed to analyze");
+    let current = snapshot::Snapshot::new(
+        git::extract_git_context_at(repo_path).expect("git context"),
+        reports,
+    );
+    let mut delta = delta::compute_delta(repo_path, &current).expect("delta");
+    let added = git::added_lines_since(repo_path, &delta.commit.parent).expect("diff");
+    delta.annotate_new_cc(&decisions, &added, repo_path);
+
+    let entry = &delta.deltas[0];
+    assert_eq!(entry.status, delta::FunctionStatus::Modified);
+    assert!(entry.after.as_ref().unwrap().metrics.cc > 2);
+    assert_eq!(entry.new_cc, Some(1), "only the added `if` counts");
+
+    let config: hotspots_core::config::HotspotsConfig =
+        serde_json::from_str(r#"{"max_new_cc": 0}"#).unwrap();
+    let config = config.resolve().unwrap();
+    let results = hotspots_core::policy::evaluate_policies(&delta, &current, repo_path, &config)
+        .expect("policies")
+        .expect("not a baseline");
+    assert_eq!(results.failed.len(), 1);
+    assert_eq!(
+        results.failed[0].id,
+        hotspots_core::policy::PolicyId::MaxNewCc
+    );
+}
+
+#[test]
+fn test_new_cc_counts_every_cc_increment_of_an_added_function() {
+    let temp_dir = create_temp_git_repo();
+    let repo_path = temp_dir.path();
+    let file = repo_path.join("route.ts");
+
+    create_ts_file(repo_path, "route.ts", "function keep() {\n  return 0;\n}\n");
+    git_commit(repo_path, "Initial");
+    let options = AnalysisOptions {
+        min_lrs: None,
+        top_n: None,
+    };
+    let reports = analyze(&file, options).expect("failed to analyze");
+    let parent = snapshot::Snapshot::new(
+        git::extract_git_context_at(repo_path).expect("git context"),
+        reports,
+    );
+    snapshot::persist_snapshot(repo_path, &parent, false).expect("failed to persist");
+
+    create_ts_file(
+        repo_path,
+        "route.ts",
+        "function keep() {\n  return 0;\n}\nfunction route(a: number, b: number) {\n  if (a > 0 && b > 0 || a < -1) {\n    return 1;\n  }\n  switch (a) {\n    case 1:\n      return 2;\n    case 2:\n      return 3;\n  }\n  try {\n    keep();\n  } catch (e) {\n    return 4;\n  }\n  return 0;\n}\n",
+    );
+    git_commit(repo_path, "Add route");
+    // An uncommitted edit on line 2 is not part of the analyzed commit
+    let committed = fs::read_to_string(&file).unwrap();
+    fs::write(
+        &file,
+        committed.replace("return 0;\n}\nfunction", "return 9;\n}\nfunction"),
+    )
+    .unwrap();
+
+    let options = AnalysisOptions {
+        min_lrs: None,
+        top_n: None,
+    };
+    let (reports, decisions) =
+        delta::analyze_with_decisions(&file, options, None).expect("failed to analyze");
+    let current = snapshot::Snapshot::new(
+        git::extract_git_context_at(repo_path).expect("git context"),
+        reports,
+    );
+    let mut delta = delta::compute_delta(repo_path, &current).expect("delta");
+    let added =
+        git::added_lines_between(repo_path, &delta.commit.parent, &delta.commit.sha).expect("diff");
+    assert_eq!(added["route.ts"], vec![(4, 20)]);
+    delta.annotate_new_cc(&decisions, &added, repo_path);
+
+    let entry = delta
+        .deltas
+        .iter()
+        .find(|e| e.function_id.ends_with("::route"))
+        .expect("route is new");
+    assert_eq!(entry.status, delta::FunctionStatus::New);
+    // `if`, `&&`, `||`, the switch, its two cases, and the catch clause
+    assert_eq!(entry.new_cc, Some(7));
+}
+
+#[test]
+fn test_incremental_splice_matches_full_analysis() {
+    let temp_dir = create_temp_git_repo();
+    let repo_path = temp_dir.path();
+    let options = || AnalysisOptions {
+        min_lrs: None,
+        top_n: None,
+    };
+
+    create_ts_file(repo_path, "a.ts", "function a() { return 1; }\n");
+    create_ts_file(
+        repo_path,
+        "b.ts",
+        "function b(x: number) { return x > 0 ? 1 : 0; }\n",
+    );
+    create_ts_file(repo_path, "keep.ts", "function keep() { return 2; }\n");
+    git_commit(repo_path, "Initial");
+    let parent = snapshot::Snapshot::new(
+        git::extract_git_context_at(repo_path).expect("git context"),
+        analyze(repo_path, options()).expect("failed to analyze"),
+    );
+
+    // Modify, delete, and add a file; the ignored `dist/` change must not be analyzed
+    create_ts_file(
+        repo_path,
+        "b.ts",
+        "function b(x: number) {\n  if (x > 0) { return 1; }\n  if (x < 0) { return -1; }\n  return 0;\n}\n",
+    );
+    fs::remove_file(repo_path.join("a.ts")).expect("failed to delete");
+    create_ts_file(repo_path, "c.ts", "function c() { return 3; }\n");
+    create_ts_file(
+        repo_path,
+        "dist/bundle.ts",
+        "function bundled() { return 4; }\n",
+    );
+    git_commit(repo_path, "Change files");
+
+    let changed: Vec<PathBuf> = git::changed_files_since(repo_path, parent.commit_sha())
+        .expect("changed files")
+        .iter()
+        .map(|rel| repo_path.join(rel))
+        .collect();
+    assert_eq!(changed.len(), 4);
+    let reports =
+        hotspots_core::analyze_files_with_progress(repo_path, &changed, options(), None, None)
+            .expect("failed to analyze changed files");
+    assert_eq!(reports.len(), 2, "b.ts and c.ts only");
+
+    let context = git::extract_git_context_at(repo_path).expect("git context");
+    let update = snapshot::Snapshot::new(context.clone(), reports);
+    let changed_files = changed
+        .iter()
+        .map(|p| p.to_string_lossy().replace('\\', "/"))
+        .collect();
+    let spliced = snapshot::Snapshot::splice(parent, update, &changed_files).expect("splice");
+
+    let full = snapshot::Snapshot::new(
+        context,
+        analyze(repo_path, options()).expect("failed to analyze"),
+    );
+    let summarize = |s: &snapshot::Snapshot| -> Vec<(String, u32)> {
+        s.functions
+            .iter()
+            .map(|f| (f.function_id.clone(), f.metrics.cc))
+            .collect()
+    };
+    assert_eq!(summarize(&spliced), summarize(&full));
+}
+
+#[test]
+fn test_changed_files_in_range_lists_only_that_range() {
+    let temp_dir = create_temp_git_repo();
+    let repo_path = temp_dir.path();
+
+    create_ts_file(repo_path, "old.ts", "function old() { return 1; }\n");
+    let start = git_commit(repo_path, "Initial");
+
+    create_ts_file(repo_path, "new.ts", "function fresh() { return 2; }\n");
+    create_ts_file(repo_path, "notes.md", "release notes\n");
+    git_commit(repo_path, "Release");
+
+    // Uncommitted edits are outside the range
+    create_ts_file(repo_path, "old.ts", "function old() { return 3; }\n");
+
+    let range = format!("{start}..HEAD");
+    let changed = git::changed_files_in_range(repo_path, &range).expect("changed files");
+    assert_eq!(changed, vec!["new.ts", "notes.md"]);
+    assert!(git::changed_files_in_range(repo_path, &start).is_err());
+
+    let files: Vec<PathBuf> = changed.iter().map(|rel| repo_path.join(rel)).collect();
+    let options = AnalysisOptions {
+        min_lrs: None,
+        top_n: None,
+    };
+    let reports =
+        hotspots_core::analyze_files_with_progress(repo_path, &files, options, None, None)
+            .expect("failed to analyze changed files");
+    let names: Vec<&str> = reports.iter().map(|r| r.function.as_str()).collect();
+    assert_eq!(names, vec!["fresh"]);
+}
+
+#[test]
+fn test_merge_base_with_names_the_fork_point() {
+    let temp_dir = create_temp_git_repo();
+    let repo_path = temp_dir.path();
+
+    create_ts_file(repo_path, "a.ts", "function a() { return 1; }\n");
+    let fork = git_commit(repo_path, "Initial");
+
+    git_command(repo_path, &["checkout", "-b", "feature"]);
+    create_ts_file(repo_path, "a.ts", "function a() { return 2; }\n");
+    git_commit(repo_path, "Feature work");
+
+    git_command(repo_path, &["checkout", "main"]);
+    create_ts_file(repo_path, "b.ts", "function b() { return 3; }\n");
+    git_commit(repo_path, "Main moves on");
+    git_command(repo_path, &["checkout", "feature"]);
+
+    assert_eq!(git::merge_base_with(repo_path, "main").unwrap(), fork);
+    assert!(git::merge_base_with(repo_path, "no-such-branch").is_err());
+}
+
+#[test]
+fn test_ignored_commits_are_not_touches() {
+    let temp_dir = create_temp_git_repo();
+    let repo_path = temp_dir.path();
+
+    create_ts_file(repo_path, "a.ts", "function a() { return 1; }\n");
+    git_commit(repo_path, "Initial");
+    create_ts_file(repo_path, "a.ts", "function a() {\n  return 1;\n}\n");
+    let format_sha = git_commit(repo_path, "chore: format\n\nRan prettier");
+    create_ts_file(repo_path, "a.ts", "function a() {\n  return 2;\n}\n");
+    git_commit(repo_path, "Change a");
+    let now = git::extract_git_context_at(repo_path).unwrap().timestamp;
+
+    let touches = |ignored: &git::IgnoredCommits| {
+        let shas = git::resolve_ignored_commits(repo_path, ignored).unwrap();
+        let file = git::batch_touch_metrics_at(repo_path, now, &shas).unwrap();
+        let (function, _) =
+            git::function_touch_metrics_at(repo_path, "a.ts", 1, 3, now, &shas).unwrap();
+        (file.touch_count_30d["a.ts"], function)
+    };
+    assert_eq!(touches(&git::IgnoredCommits::default()), (3, 3));
+
+    let by_message = git::IgnoredCommits::new(&[], &["^chore: format$".to_string()]).unwrap();
+    assert_eq!(touches(&by_message), (2, 2));
+    let by_rev = git::IgnoredCommits::new(&[format_sha[..8].to_uppercase()], &[]).unwrap();
+    assert_eq!(touches(&by_rev), (2, 2));
+    // An unknown prefix sends resolution back to the history scan
+    let with_unknown =
+        git::IgnoredCommits::new(&[format_sha[..8].to_string(), "deadbeef".to_string()], &[])
+            .unwrap();
+    assert_eq!(touches(&with_unknown), (2, 2));
+
+    assert!(git::IgnoredCommits::new(&["abc".to_string()], &[]).is_err());
+    assert!(git::IgnoredCommits::new(&[], &["(".to_string()]).is_err());
+}
@@ -1,6 +1,10 @@
 //! Integration tests for hotspots analysis
 
-use hotspots_core::{analyze, analyze_with_progress, render_json, AnalysisOptions};
+#![cfg(feature = "native")]
+
+use hotspots_core::{
+    analyze, analyze_with_progress, analyze_with_visitor, render_json, AnalysisOptions,
+};
 use std::path::PathBuf;
 use std::sync::{Arc, Mutex};
 
@@ -279,6 +283,32 @@ fn test_progress_callback_sequence_directory() {
     assert_eq!(done_values, (1..=total).collect::<Vec<_>>());
 }
 
+/// The visitor sees every reported function exactly once, each with a CFG
+/// that has at least its entry and exit nodes.
+#[test]
+fn test_visitor_sees_each_function_with_its_cfg() {
+    let path = fixture_path("rust");
+    let options = AnalysisOptions {
+        min_lrs: None,
+        top_n: None,
+    };
+
+    let mut visited: Vec<String> = Vec::new();
+    let reports = analyze_with_visitor(&path, options, None, &mut |report, cfg| {
+        assert!(cfg.node_count() >= 2, "CFG must have entry and exit");
+        visited.push(format!("{}::{}", report.file, report.function));
+    })
+    .unwrap();
+
+    let mut expected: Vec<String> = reports
+        .iter()
+        .map(|r| format!("{}::{}", r.file, r.function))
+        .collect();
+    visited.sort_unstable();
+    expected.sort_unstable();
+    assert_eq!(visited, expected);
+}
+
 #[test]
 fn test_whitespace_invariance() {
     // Test that whitespace changes don't affect output
@@ -304,3 +334,661 @@ fn test_whitespace_invariance() {
     assert_eq!(lrs1, lrs2);
     assert_eq!(cc1, cc2);
 }
+
+#[test]
+fn test_max_file_bytes_skips_oversized_files() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(
+        dir.path().join("small.ts"),
+        "function small() { return 1; }\n",
+    )
+    .unwrap();
+    let big_body = format!(
+        "function big() {{\n{}  return 1;\n}}\n",
+        "  const x = 1;\n".repeat(200)
+    );
+    std::fs::write(dir.path().join("big.ts"), big_body).unwrap();
+
+    let config: hotspots_core::config::HotspotsConfig =
+        serde_json::from_str(r#"{"max_file_bytes": 512}"#).unwrap();
+    let resolved = config.resolve().unwrap();
+    let options = AnalysisOptions {
+        min_lrs: None,
+        top_n: None,
+    };
+    let reports = hotspots_core::analyze_with_config(dir.path(), options, Some(&resolved)).unwrap();
+    let names: Vec<&str> = reports.iter().map(|r| r.function.as_str()).collect();
+    assert_eq!(names, vec!["small"]);
+
+    // 0 disables the limit
+    let config: hotspots_core::config::HotspotsConfig =
+        serde_json::from_str(r#"{"max_file_bytes": 0}"#).unwrap();
+    let resolved = config.resolve().unwrap();
+    let options = AnalysisOptions {
+        min_lrs: None,
+        top_n: None,
+    };
+    let reports = hotspots_core::analyze_with_config(dir.path(), options, Some(&resolved)).unwrap();
+    assert_eq!(reports.len(), 2);
+}
+
+#[test]
+fn test_require_functions_fails_when_nothing_is_found() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("consts.ts"), "export const x = 1;\n").unwrap();
+
+    let config: hotspots_core::config::HotspotsConfig =
+        serde_json::from_str(r#"{"require_functions": true}"#).unwrap();
+    let resolved = config.resolve().unwrap();
+    let options = AnalysisOptions {
+        min_lrs: None,
+        top_n: None,
+    };
+    let err = hotspots_core::analyze_with_config(dir.path(), options, Some(&resolved))
+        .unwrap_err()
+        .to_string();
+    assert!(err.contains("no functions found"), "{err}");
+
+    // A function filtered out by min_lrs still counts as found
+    std::fs::write(dir.path().join("f.ts"), "function f() { return 1; }\n").unwrap();
+    let options = AnalysisOptions {
+        min_lrs: Some(100.0),
+        top_n: None,
+    };
+    let reports = hotspots_core::analyze_with_config(dir.path(), options, Some(&resolved)).unwrap();
+    assert!(reports.is_empty());
+}
+
+#[test]
+fn test_unwrap_weight_feeds_lrs_only_when_configured() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(
+        dir.path().join("lib.rs"),
+        "fn risky(a: Option<u32>, b: Option<u32>) -> u32 {\n    a.unwrap() + b.expect(\"b\")\n}\n",
+    )
+    .unwrap();
+
+    let analyze_with = |json: &str| {
+        let config: hotspots_core::config::HotspotsConfig = serde_json::from_str(json).unwrap();
+        let resolved = config.resolve().unwrap();
+        let options = AnalysisOptions {
+            min_lrs: None,
+            top_n: None,
+        };
+        hotspots_core::analyze_with_config(dir.path(), options, Some(&resolved))
+            .unwrap()
+            .remove(0)
+    };
+
+    let informational = analyze_with("{}");
+    assert_eq!(informational.metrics.unwrap_count, Some(2));
+    let weighted = analyze_with(r#"{"weights": {"unwrap": 1.0}}"#);
+    let expected = informational.lrs + hotspots_core::risk::unwrap_risk(2);
+    assert!((weighted.lrs - expected).abs() < 1e-9);
+}
+
+#[test]
+fn test_report_paths_use_forward_slashes() {
+    let dir = tempfile::tempdir().unwrap();
+    // A literal backslash in a directory name stands in for a Windows separator
+    let nested = dir.path().join("src\\api");
+    std::fs::create_dir_all(&nested).unwrap();
+    std::fs::write(
+        nested.join("handler.ts"),
+        "function handler(x: number) { if (x) { return 1; } return 0; }\n",
+    )
+    .unwrap();
+
+    let options = AnalysisOptions {
+        min_lrs: None,
+        top_n: None,
+    };
+    let reports = analyze(dir.path(), options).unwrap();
+    assert_eq!(reports.len(), 1);
+    assert!(reports[0].file.ends_with("src/api/handler.ts"));
+    for output in [
+        hotspots_core::render_json(&reports),
+        hotspots_core::render_text(&reports),
+    ] {
+        assert!(!output.contains('\\'), "backslash in output:\n{output}");
+    }
+}
+
+#[test]
+fn test_anonymous_function_symbols_are_unique() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(
+        dir.path().join("closures.ts"),
+        "function outer(xs: number[]) {\n\
+         \x20 return xs.map(x => x + 1).filter(x => x > 2);\n\
+         }\n\
+         setTimeout(() => { console.log(1); }, 0);\n",
+    )
+    .unwrap();
+
+    let options = AnalysisOptions {
+        min_lrs: None,
+        top_n: None,
+    };
+    let reports = analyze(dir.path(), options).unwrap();
+    let mut names: Vec<&str> = reports.iter().map(|r| r.function.as_str()).collect();
+    names.sort();
+    assert_eq!(
+        names,
+        vec![
+            "<anonymous@4>",
+            "outer",
+            "outer::<anonymous@2>",
+            "outer::<anonymous@2>#2",
+        ]
+    );
+
+    let config: hotspots_core::config::HotspotsConfig =
+        serde_json::from_str(r#"{"skip_anonymous_functions": true}"#).unwrap();
+    let resolved = config.resolve().unwrap();
+    let options = AnalysisOptions {
+        min_lrs: None,
+        top_n: None,
+    };
+    let reports = hotspots_core::analyze_with_config(dir.path(), options, Some(&resolved)).unwrap();
+    let names: Vec<&str> = reports.iter().map(|r| r.function.as_str()).collect();
+    assert_eq!(names, vec!["outer"]);
+}
+
+/// `granularity: "file"` reports one entry per file: every function's decision
+/// points plus the top-level code's, at the file's LOC.
+#[test]
+fn test_file_granularity_combines_functions_and_top_level_code() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(
+        dir.path().join("lib.ts"),
+        "function a(x: number) {\n  if (x > 0) { return f(x); }\n  return 0;\n}\n\
+         function b(y: boolean) {\n  while (y) { y = f(y) && g(); }\n}\n",
+    )
+    .unwrap();
+    std::fs::write(
+        dir.path().join("script.py"),
+        "import sys\n\ndef check(x):\n    if x:\n        return 1\n    return 0\n\n\
+         if len(sys.argv) > 1:\n    check(sys.argv[1])\n",
+    )
+    .unwrap();
+    let options = || AnalysisOptions {
+        min_lrs: None,
+        top_n: None,
+    };
+    let per_function = analyze(dir.path(), options()).unwrap();
+    let decision_points = |file: &str| -> u32 {
+        per_function
+            .iter()
+            .filter(|r| r.file.ends_with(file))
+            .map(|r| r.metrics.cc - 1)
+            .sum()
+    };
+
+    let config: hotspots_core::config::HotspotsConfig =
+        serde_json::from_str(r#"{"granularity": "file"}"#).unwrap();
+    let resolved = config.resolve().unwrap();
+    let reports =
+        hotspots_core::analyze_with_config(dir.path(), options(), Some(&resolved)).unwrap();
+    assert_eq!(reports.len(), 2);
+    assert!(reports
+        .iter()
+        .all(|r| r.function == "<file>" && r.line == 1));
+
+    let ts = reports.iter().find(|r| r.file.ends_with("lib.ts")).unwrap();
+    assert_eq!(ts.metrics.cc, 1 + decision_points("lib.ts"));
+    assert_eq!(ts.metrics.loc, 7);
+    assert_eq!(ts.metrics.fo, 2, "distinct callees across functions");
+
+    // The top-level `if` adds to the script's only function
+    let py = reports
+        .iter()
+        .find(|r| r.file.ends_with("script.py"))
+        .unwrap();
+    assert!(py.metrics.cc > 1 + decision_points("script.py"));
+    assert_eq!(py.metrics.loc, 9);
+}
+
+/// Function initializers, callbacks, and object methods in top-level code are
+/// counted once, as the functions they are, not again as top-level code.
+#[test]
+fn test_file_granularity_counts_function_initializers_once() {
+    let options = || AnalysisOptions {
+        min_lrs: None,
+        top_n: None,
+    };
+    let config: hotspots_core::config::HotspotsConfig =
+        serde_json::from_str(r#"{"granularity": "file"}"#).unwrap();
+    let resolved = config.resolve().unwrap();
+    // (file CC, summed decision points of its functions)
+    let measure = |source: &str| -> (u32, u32) {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("app.ts"), source).unwrap();
+        let per_function = analyze(dir.path(), options()).unwrap();
+        assert_eq!(per_function.len(), 4);
+        let file = hotspots_core::analyze_with_config(dir.path(), options(), Some(&resolved))
+            .unwrap()
+            .remove(0);
+        (
+            file.metrics.cc,
+            per_function.iter().map(|r| r.metrics.cc - 1).sum(),
+        )
+    };
+
+    let (full_cc, full_points) = measure(
+        "export const f = (x: number) => {\n  if (x > 0) { return 1; }\n  return 0;\n};\n\
+         var g = function (y: boolean) {\n  return y && f(1) > 0;\n};\n\
+         const h = { m(z: number) { while (z) { z--; } } };\n\
+         run(() => { if (g(true)) { f(2); } });\n\
+         if (process.env.DEBUG) { run(g); }\n",
+    );
+    // Same top-level code, empty function bodies
+    let (empty_cc, empty_points) = measure(
+        "export const f = (x: number) => {};\n\
+         var g = function (y: boolean) {};\n\
+         const h = { m(z: number) {} };\n\
+         run(() => {});\n\
+         if (process.env.DEBUG) { run(g); }\n",
+    );
+    assert!(full_points > empty_points);
+    assert_eq!(full_cc - empty_cc, full_points - empty_points);
+}
+
+#[test]
+fn test_list_skipped_paths_reports_reasons() {
+    use hotspots_core::SkipReason;
+
+    let dir = tempfile::tempdir().unwrap();
+    let root = dir.path();
+    std::fs::create_dir_all(root.join("src/legacy")).unwrap();
+    std::fs::create_dir_all(root.join("node_modules/pkg")).unwrap();
+    std::fs::write(root.join("src/app.ts"), "function app() { return 1; }").unwrap();
+    std::fs::write(root.join("src/legacy/old.ts"), "function old() {}").unwrap();
+    std::fs::write(root.join("src/types.d.ts"), "declare const x: number;").unwrap();
+    std::fs::write(root.join("README.md"), "# readme").unwrap();
+    std::fs::write(root.join("node_modules/pkg/index.js"), "function f() {}").unwrap();
+    std::fs::create_dir_all(root.join("src/assets/js")).unwrap();
+    std::fs::write(root.join("src/assets/js/lib.js"), "function lib() {}").unwrap();
+    let minified = format!("function m() {{}}\n{}\n", "x;".repeat(600)).repeat(3);
+    std::fs::write(root.join("src/bundle.js"), minified).unwrap();
+    #[cfg(unix)]
+    std::os::unix::fs::symlink(root.join("src/app.ts"), root.join("src/link.ts")).unwrap();
+
+    let config: hotspots_core::config::HotspotsConfig =
+        serde_json::from_str(r#"{"exclude": ["**/legacy/**"]}"#).unwrap();
+    let resolved = config.resolve().unwrap();
+    let skipped = hotspots_core::list_skipped_paths(root, Some(&resolved)).unwrap();
+    let reason_of = |rel: &str| {
+        skipped
+            .iter()
+            .find(|s| s.path == root.join(rel))
+            .map(|s| s.reason)
+    };
+
+    assert_eq!(reason_of("src/app.ts"), None);
+    assert_eq!(reason_of("src/legacy/old.ts"), Some(SkipReason::Excluded));
+    assert_eq!(
+        reason_of("src/types.d.ts"),
+        Some(SkipReason::DeclarationFile)
+    );
+    assert_eq!(
+        reason_of("README.md"),
+        Some(SkipReason::UnsupportedExtension)
+    );
+    assert_eq!(reason_of("node_modules"), Some(SkipReason::IgnoredDir));
+    assert_eq!(
+        reason_of("src/assets/js/lib.js"),
+        Some(SkipReason::Vendored)
+    );
+    assert_eq!(reason_of("src/bundle.js"), Some(SkipReason::Minified));
+    #[cfg(unix)]
+    assert_eq!(reason_of("src/link.ts"), Some(SkipReason::Symlink));
+}
+
+#[test]
+fn test_reports_carry_location_with_kind() {
+    use hotspots_core::ast::FunctionKind;
+
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(
+        dir.path().join("shapes.ts"),
+        "class Shape {\n  area() {\n    return 0;\n  }\n}\n\
+         items.map(() => {\n  return 1;\n});\n",
+    )
+    .unwrap();
+    let reports = analyze(
+        dir.path(),
+        AnalysisOptions {
+            min_lrs: None,
+            top_n: None,
+        },
+    )
+    .unwrap();
+
+    for report in &reports {
+        let location = report.location.as_ref().unwrap();
+        assert_eq!(location.file, report.file);
+        assert_eq!(location.symbol, report.function);
+        assert_eq!(location.start_line, report.line);
+    }
+    let area = reports.iter().find(|r| r.function == "area").unwrap();
+    let location = area.location.as_ref().unwrap();
+    assert_eq!((location.start_line, location.end_line), (2, 4));
+    assert_eq!(location.kind, FunctionKind::Method);
+
+    let callback = reports.iter().find(|r| r.is_anonymous()).unwrap();
+    let location = callback.location.as_ref().unwrap();
+    assert_eq!(location.symbol, "<anonymous@6>");
+    assert_eq!(location.kind, FunctionKind::Closure);
+}
+
+#[test]
+fn test_max_arm_loc_flags_fat_case() {
+    let dir = tempfile::tempdir().unwrap();
+    let fat_body: String = (0..30).map(|i| format!("      total += {i};\n")).collect();
+    std::fs::write(
+        dir.path().join("route.ts"),
+        format!(
+            "function route(kind: string) {{\n  let total = 0;\n  switch (kind) {{\n    \
+             case \"a\":\n      return 1;\n    case \"b\":\n{fat_body}      break;\n  }}\n  \
+             return total;\n}}\n\nfunction plain() {{\n  return 1;\n}}\n"
+        ),
+    )
+    .unwrap();
+    std::fs::write(
+        dir.path().join("handle.py"),
+        "def handle(cmd):\n    match cmd:\n        case \"go\":\n            return 1\n        \
+         case _:\n            x = 2\n            return x\n",
+    )
+    .unwrap();
+    let reports = analyze(
+        dir.path(),
+        AnalysisOptions {
+            min_lrs: None,
+            top_n: None,
+        },
+    )
+    .unwrap();
+    let find = |name: &str| reports.iter().find(|r| r.function == name).unwrap();
+
+    // `case "b":` through `break;`
+    let route = find("route");
+    assert_eq!(route.metrics.max_arm_loc, Some(32));
+    assert!(route.patterns.iter().any(|p| p == "fat_case"));
+    assert_eq!(find("plain").metrics.max_arm_loc, None);
+    assert_eq!(find("handle").metrics.max_arm_loc, Some(3));
+}
+
+#[test]
+fn test_max_data_nesting_flags_nested_literal() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(
+        dir.path().join("config.ts"),
+        "function buildConfig() {\n  return {\n    server: {\n      routes: [\n        \
+         { path: \"/\", opts: { cache: true } },\n      ],\n    },\n  };\n}\n\n\
+         function flat() {\n  return [1, 2, { a: 1 }];\n}\n\n\
+         function none(x: number) {\n  if (x > 1) {\n    if (x > 2) {\n      return x;\n    }\n  }\n  return 0;\n}\n",
+    )
+    .unwrap();
+    std::fs::write(
+        dir.path().join("config.py"),
+        "def build():\n    return {\"a\": {\"b\": {\"c\": {\"d\": 1}}}}\n",
+    )
+    .unwrap();
+    let reports = analyze(
+        dir.path(),
+        AnalysisOptions {
+            min_lrs: None,
+            top_n: None,
+        },
+    )
+    .unwrap();
+    let find = |name: &str| reports.iter().find(|r| r.function == name).unwrap();
+
+    // object > object > array > object > object
+    let config = find("buildConfig");
+    assert_eq!(config.metrics.max_data_nesting, Some(5));
+    assert!(config.patterns.iter().any(|p| p == "nested_literal"));
+    assert_eq!(find("flat").metrics.max_data_nesting, Some(2));
+    // Control-flow nesting is not data nesting
+    assert_eq!(find("none").metrics.max_data_nesting, None);
+    // JS/TS only
+    assert_eq!(find("build").metrics.max_data_nesting, None);
+}
+
+/// Same-named methods on different classes: `this`/`self` calls stay on the
+/// caller's own class, and `Type.method` calls go to `Type`.
+#[test]
+fn test_call_graph_resolves_method_calls_by_receiver() {
+    use hotspots_core::callgraph::MethodResolution;
+
+    let dir = tempfile::tempdir().unwrap();
+    for (file, source) in [
+        (
+            "alpha.ts",
+            "class Alpha {\n  helper() { return 1; }\n  run() { return this.helper(); }\n}\n",
+        ),
+        (
+            "beta.ts",
+            "class Beta {\n  helper() { return 2; }\n  run() { return this.helper(); }\n}\n",
+        ),
+        (
+            "gamma.py",
+            "class Gamma:\n    def helper(self):\n        return 3\n\n    \
+             def run(self):\n        return self.helper()\n",
+        ),
+        (
+            "shapes.rs",
+            "struct Square;\nstruct Circle;\n\
+             impl Square {\n    fn area(&self) -> u32 { 1 }\n    \
+             fn describe(&self) -> u32 {\n        let unit = Circle::unit();\n        \
+             self.area()\n    }\n}\n\
+             impl Circle {\n    fn unit() -> Circle { Circle }\n    fn area(&self) -> u32 { 3 }\n    \
+             fn describe(&self) -> u32 { self.area() }\n}\n",
+        ),
+        (
+            "Util.java",
+            "class Util {\n  static int helper(int x) { return x; }\n}\n",
+        ),
+        (
+            "Other.java",
+            "class Other {\n  int helper(int x) { return -x; }\n}\n",
+        ),
+        (
+            "Main.java",
+            "class Main {\n  int run() { return Util.helper(1); }\n}\n",
+        ),
+    ] {
+        std::fs::write(dir.path().join(file), source).unwrap();
+    }
+    let reports = analyze(
+        dir.path(),
+        AnalysisOptions {
+            min_lrs: None,
+            top_n: None,
+        },
+    )
+    .unwrap();
+    let id = |file: &str, function: &str| {
+        let report = reports
+            .iter()
+            .find(|r| r.file.ends_with(file) && r.function == function)
+            .unwrap_or_else(|| panic!("no {function} in {file}"));
+        format!("{}::{}", report.file, report.function)
+    };
+
+    let graph =
+        hotspots_core::build_call_graph(&reports, dir.path(), MethodResolution::Receiver, true)
+            .unwrap();
+    let callees = |file: &str, function: &str| {
+        let mut ids: Vec<String> = graph
+            .callees_of(&id(file, function))
+            .map(|it| it.map(str::to_string).collect())
+            .unwrap_or_default();
+        ids.sort();
+        ids
+    };
+
+    assert_eq!(callees("alpha.ts", "run"), vec![id("alpha.ts", "helper")]);
+    assert_eq!(callees("beta.ts", "run"), vec![id("beta.ts", "helper")]);
+    assert_eq!(callees("gamma.py", "run"), vec![id("gamma.py", "helper")]);
+    assert_eq!(
+        callees("shapes.rs", "Square::describe"),
+        vec![
+            id("shapes.rs", "Circle::unit"),
+            id("shapes.rs", "Square::area")
+        ]
+    );
+    assert_eq!(
+        callees("shapes.rs", "Circle::describe"),
+        vec![id("shapes.rs", "Circle::area")]
+    );
+    assert_eq!(callees("Main.java", "run"), vec![id("Util.java", "helper")]);
+
+    // Exact-name matching sees the bare names recorded before receivers were
+    // kept (`unit`, `area`), which no qualified method name matches
+    let graph = hotspots_core::build_call_graph(&reports, dir.path(), MethodResolution::Name, true)
+        .unwrap();
+    assert_eq!(graph.edge_count(), 0);
+}
+
+#[test]
+fn test_call_graph_uses_entrypoints_file_as_roots() {
+    use hotspots_core::callgraph::MethodResolution;
+
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(
+        dir.path().join("app.ts"),
+        "function main() { return boot(); }
+function boot() { return 1; }
+         function onJob() { return work(); }
+function work() { return 2; }
+",
+    )
+    .unwrap();
+    std::fs::create_dir(dir.path().join(".hotspots")).unwrap();
+    std::fs::write(
+        dir.path().join(".hotspots/entrypoints.txt"),
+        "# registered with the job queue at runtime
+app.ts::onJob
+app.ts::removed
+",
+    )
+    .unwrap();
+    let reports = analyze(
+        dir.path(),
+        AnalysisOptions {
+            min_lrs: None,
+            top_n: None,
+        },
+    )
+    .unwrap();
+    let id = |function: &str| format!("{}::{}", reports[0].file, function);
+
+    let graph =
+        hotspots_core::build_call_graph(&reports, dir.path(), MethodResolution::Receiver, true)
+            .unwrap();
+    let depths = graph.compute_dependency_depth();
+
+    assert!(graph.is_entry_point(&id("onJob")));
+    assert!(!graph.is_entry_point(&id("main")));
+    assert_eq!(depths[&id("onJob")], Some(0));
+    assert_eq!(depths[&id("work")], Some(1));
+    assert_eq!(depths[&id("main")], None);
+    assert_eq!(depths[&id("boot")], None);
+}
+
+#[test]
+fn test_call_graph_links_closures_to_their_enclosing_function() {
+    use hotspots_core::callgraph::MethodResolution;
+
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(
+        dir.path().join("events.ts"),
+        "function save(x: number) { return x; }\n\
+         function register(bus: any) {\n\
+         \x20 bus.on('save', (x: number) => save(x));\n\
+         }\n\
+         setTimeout(() => { register(null); }, 0);\n",
+    )
+    .unwrap();
+    let reports = analyze(
+        dir.path(),
+        AnalysisOptions {
+            min_lrs: None,
+            top_n: None,
+        },
+    )
+    .unwrap();
+    let id = |function: &str| format!("{}::{}", reports[0].file, function);
+
+    let callees = |graph: &hotspots_core::callgraph::CallGraph, function: &str| -> Vec<String> {
+        let mut ids: Vec<String> = graph
+            .callees_of(&id(function))
+            .map(|c| c.map(str::to_string).collect())
+            .unwrap_or_default();
+        ids.sort();
+        ids
+    };
+
+    let graph =
+        hotspots_core::build_call_graph(&reports, dir.path(), MethodResolution::Receiver, true)
+            .unwrap();
+    assert_eq!(
+        callees(&graph, "register"),
+        vec![id("register::<anonymous@3>"), id("save")]
+    );
+    assert_eq!(callees(&graph, "register::<anonymous@3>"), vec![id("save")]);
+    // A top-level callback has no enclosing function to link from
+    assert_eq!(graph.fan_in(&id("<anonymous@5>")), 0);
+
+    let graph =
+        hotspots_core::build_call_graph(&reports, dir.path(), MethodResolution::Receiver, false)
+            .unwrap();
+    assert_eq!(graph.node_count(), 2);
+    assert_eq!(callees(&graph, "register"), vec![id("save")]);
+}
+
+#[test]
+fn test_kind_thresholds_apply_to_js_and_ts_constructors() {
+    let dir = tempfile::tempdir().unwrap();
+    let branches = "    if (a) { this.a = 1; }\n    if (b) { this.b = 1; }\n    \
+                    if (a && b) { this.c = 1; }\n    if (a || b) { this.d = 1; }\n";
+    for file in ["widget.ts", "widget.js"] {
+        std::fs::write(
+            dir.path().join(file),
+            format!(
+                "class Widget {{\n  constructor(a, b) {{\n{branches}  }}\n\n  \
+                 render(a, b) {{\n{branches}  }}\n}}\n"
+            ),
+        )
+        .unwrap();
+    }
+
+    let config: hotspots_core::config::HotspotsConfig = serde_json::from_str(
+        r#"{
+            "patterns": {"complex_branching_cc": 5, "complex_branching_nd": 1},
+            "kind_thresholds": {"constructor": {"complex_branching_cc": 20}}
+        }"#,
+    )
+    .unwrap();
+    config.validate().unwrap();
+    let resolved = config.resolve().unwrap();
+    let options = AnalysisOptions {
+        min_lrs: None,
+        top_n: None,
+    };
+    let reports = hotspots_core::analyze_with_config(dir.path(), options, Some(&resolved)).unwrap();
+    assert_eq!(reports.len(), 4);
+    for report in &reports {
+        let complex = report.patterns.iter().any(|p| p == "complex_branching");
+        assert_eq!(
+            complex,
+            report.function == "render",
+            "{}::{} patterns: {:?}",
+            report.file,
+            report.function,
+            report.patterns
+        );
+    }
+}
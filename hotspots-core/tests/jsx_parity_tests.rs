@@ -3,6 +3,8 @@
 //! Critical invariant: JSX and TSX versions of the same component
 //! should yield identical complexity metrics.
 
+#![cfg(feature = "native")]
+
 use hotspots_core::{analyze, AnalysisOptions};
 use std::path::PathBuf;
 
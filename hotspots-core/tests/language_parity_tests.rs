@@ -4,6 +4,8 @@
 //! identical complexity metrics regardless of whether they're written
 //! in TypeScript or JavaScript.
 
+#![cfg(feature = "native")]
+
 use hotspots_core::{analyze, AnalysisOptions};
 use std::path::PathBuf;
 
@@ -0,0 +1,34 @@
+//! In-memory source analysis tests
+//!
+//! Runs with and without the `native` feature, so these also cover the API
+//! surface of the wasm build.
+
+use hotspots_core::metrics::RawMetrics;
+use hotspots_core::{analyze_source, compute_lrs, AnalysisOptions};
+
+#[test]
+fn test_analyze_source_scores_functions_without_touching_disk() {
+    let source = "function pick(a: number, b: number) {\n  if (a > b) {\n    return a;\n  }\n  return b;\n}\n";
+    let options = AnalysisOptions {
+        min_lrs: None,
+        top_n: None,
+    };
+    let reports = analyze_source(source, "playground/pick.ts", &options).unwrap();
+    assert_eq!(reports.len(), 1);
+    let report = &reports[0];
+    assert_eq!(report.file, "playground/pick.ts");
+    assert_eq!(report.function, "pick");
+    assert!(report.metrics.cc > 1);
+
+    let metrics = RawMetrics {
+        cc: report.metrics.cc as usize,
+        nd: report.metrics.nd as usize,
+        fo: report.metrics.fo as usize,
+        ns: report.metrics.ns as usize,
+        loc: report.metrics.loc as usize,
+        callee_names: vec![],
+    };
+    assert!((report.lrs - compute_lrs(&metrics)).abs() < 1e-9);
+
+    assert!(analyze_source(source, "pick.txt", &options).is_err());
+}
@@ -1,6 +1,8 @@
 //! Integration tests for suppression comments
 
-use hotspots_core::delta::{Delta, FunctionDeltaEntry, FunctionStatus};
+#![cfg(feature = "native")]
+
+use hotspots_core::delta::{Delta, DeltaStatus, FunctionDeltaEntry, FunctionStatus};
 use hotspots_core::discover;
 use hotspots_core::parser;
 use hotspots_core::policy::{evaluate_policies, PolicyId, PolicySeverity};
@@ -92,6 +94,12 @@ fn test_suppression_missing_reason_policy() {
                 fo: 0,
                 ns: 0,
                 loc: 10,
+                sloc: None,
+                return_points: None,
+                unwrap_count: None,
+                max_arm_loc: None,
+                max_data_nesting: None,
+                unreachable_code: None,
             },
             lrs: 1.0,
             band: RiskBand::Low,
@@ -100,6 +108,8 @@ fn test_suppression_missing_reason_policy() {
         band_transition: None,
         suppression_reason: Some(String::new()), // Empty reason
         rename_hint: None,
+        new_cc: None,
+        kind: None,
     };
 
     let delta = Delta {
@@ -109,9 +119,11 @@ fn test_suppression_missing_reason_policy() {
             parent: "parent123".to_string(),
         },
         baseline: false,
+        status: DeltaStatus::Changes,
         deltas: vec![delta_entry],
         policy: None,
         aggregates: None,
+        risk_removed: None,
     };
 
     let git_context = GitContext {
@@ -160,6 +172,12 @@ fn test_suppressed_function_excluded_from_critical_introduction() {
                 fo: 5,
                 ns: 3,
                 loc: 50,
+                sloc: None,
+                return_points: None,
+                unwrap_count: None,
+                max_arm_loc: None,
+                max_data_nesting: None,
+                unreachable_code: None,
             },
             lrs: 50.0,
             band: RiskBand::Critical,
@@ -168,6 +186,8 @@ fn test_suppressed_function_excluded_from_critical_introduction() {
         band_transition: None,
         suppression_reason: Some("legacy code, will refactor".to_string()), // Suppressed with reason
         rename_hint: None,
+        new_cc: None,
+        kind: None,
     };
 
     let delta = Delta {
@@ -177,9 +197,11 @@ fn test_suppressed_function_excluded_from_critical_introduction() {
             parent: "parent123".to_string(),
         },
         baseline: false,
+        status: DeltaStatus::Changes,
         deltas: vec![critical_entry],
         policy: None,
         aggregates: None,
+        risk_removed: None,
     };
 
     let git_context = GitContext {
@@ -224,6 +246,12 @@ fn test_unsuppressed_function_triggers_critical_introduction() {
                 fo: 5,
                 ns: 3,
                 loc: 50,
+                sloc: None,
+                return_points: None,
+                unwrap_count: None,
+                max_arm_loc: None,
+                max_data_nesting: None,
+                unreachable_code: None,
             },
             lrs: 50.0,
             band: RiskBand::Critical,
@@ -232,6 +260,8 @@ fn test_unsuppressed_function_triggers_critical_introduction() {
         band_transition: None,
         suppression_reason: None, // NOT suppressed
         rename_hint: None,
+        new_cc: None,
+        kind: None,
     };
 
     let delta = Delta {
@@ -241,9 +271,11 @@ fn test_unsuppressed_function_triggers_critical_introduction() {
             parent: "parent123".to_string(),
         },
         baseline: false,
+        status: DeltaStatus::Changes,
         deltas: vec![critical_entry],
         policy: None,
         aggregates: None,
+        risk_removed: None,
     };
 
     let git_context = GitContext {
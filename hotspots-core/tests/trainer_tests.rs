@@ -5,6 +5,8 @@
 //! Pure-unit tests (feature names, hunk parsing, nearest_function_above,
 //! model versioning) live in trainer.rs itself.
 
+#![cfg(feature = "native")]
+
 use hotspots_core::language::Language;
 use hotspots_core::report::MetricsReport;
 use hotspots_core::risk::RiskBand;
@@ -63,6 +65,12 @@ fn make_func(file: &str, name: &str, line: u32) -> FunctionSnapshot {
             fo: 0,
             ns: 0,
             loc: 10,
+            sloc: None,
+            return_points: None,
+            unwrap_count: None,
+            max_arm_loc: None,
+            max_data_nesting: None,
+            unreachable_code: None,
         },
         lrs: 1.0,
         band: RiskBand::Low,
@@ -91,7 +99,12 @@ fn make_func(file: &str, name: &str, line: u32) -> FunctionSnapshot {
         isolation_rate: None,
         age_days: None,
         last_touch_days: None,
+        function_age_days: None,
+        coverage: None,
+        priority_score: None,
+        refactor_leverage: None,
         explanation: None,
+        location: None,
     }
 }
 
@@ -155,6 +168,7 @@ fn extract_features_with_churn_and_callgraph() {
         is_entrypoint: false,
         dependency_depth: None,
         neighbor_churn: None,
+        edge_confidence: None,
     });
     func.activity_risk = Some(3.5);
 
@@ -0,0 +1,24 @@
+int after_return(int x) {
+    return x * 2;
+    x++;
+    return x;
+}
+
+int after_break(int n) {
+    int total = 0;
+    for (int i = 0; i < n; i++) {
+        if (i > 10) {
+            break;
+            total = -1;
+        }
+        total += i;
+    }
+    return total;
+}
+
+int label_after_return(int x) {
+    if (x < 0) goto fail;
+    return x;
+    fail:
+    return -1;
+}
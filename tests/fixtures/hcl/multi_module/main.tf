@@ -0,0 +1,53 @@
+# Root configuration wiring the network and compute modules together.
+
+variable "environment" {
+  type = string
+}
+
+variable "enable_bastion" {
+  type    = bool
+  default = false
+}
+
+locals {
+  is_prod     = var.environment == "prod"
+  name_prefix = "app-${var.environment}"
+  az_count    = local.is_prod ? 3 : 2
+}
+
+module "network" {
+  source = "./modules/network"
+
+  name     = local.name_prefix
+  az_count = local.az_count
+}
+
+module "compute" {
+  source = "./modules/compute"
+
+  name       = local.name_prefix
+  subnet_ids = module.network.private_subnet_ids
+  vpc_id     = module.network.vpc_id
+  size       = local.is_prod ? "large" : "small"
+}
+
+resource "aws_instance" "bastion" {
+  count = var.enable_bastion ? 1 : 0
+
+  ami           = data.aws_ami.ubuntu.id
+  instance_type = "t3.micro"
+  subnet_id     = module.network.public_subnet_ids[0]
+
+  tags = {
+    Name = "${local.name_prefix}-bastion"
+  }
+}
+
+data "aws_ami" "ubuntu" {
+  most_recent = true
+  owners      = ["099720109477"]
+}
+
+output "bastion_ip" {
+  value = one(aws_instance.bastion[*].public_ip)
+}
@@ -0,0 +1,79 @@
+variable "name" {
+  type = string
+}
+
+variable "subnet_ids" {
+  type = list(string)
+}
+
+variable "vpc_id" {
+  type = string
+}
+
+variable "size" {
+  type = string
+}
+
+variable "ports" {
+  type    = list(number)
+  default = [80, 443]
+}
+
+locals {
+  instance_type = var.size == "large" ? "m5.xlarge" : "t3.medium"
+}
+
+resource "aws_security_group" "app" {
+  name   = "${var.name}-app"
+  vpc_id = var.vpc_id
+
+  dynamic "ingress" {
+    for_each = var.ports
+    content {
+      from_port   = ingress.value
+      to_port     = ingress.value
+      protocol    = "tcp"
+      cidr_blocks = ["0.0.0.0/0"]
+    }
+  }
+
+  egress {
+    from_port   = 0
+    to_port     = 0
+    protocol    = "-1"
+    cidr_blocks = ["0.0.0.0/0"]
+  }
+}
+
+resource "aws_launch_template" "app" {
+  name_prefix   = var.name
+  instance_type = local.instance_type
+
+  vpc_security_group_ids = [aws_security_group.app.id]
+
+  dynamic "block_device_mappings" {
+    for_each = var.size == "large" ? [100, 200] : [50]
+    iterator = volume
+    content {
+      device_name = "/dev/sd${substr("bcd", volume.key, 1)}"
+      ebs {
+        volume_size = volume.value
+        encrypted   = true
+      }
+    }
+  }
+}
+
+resource "aws_autoscaling_group" "app" {
+  for_each = toset(var.subnet_ids)
+
+  name                = "${var.name}-${each.key}"
+  vpc_zone_identifier = [each.value]
+  min_size            = var.size == "large" ? 2 : 1
+  max_size            = var.size == "large" ? 6 : 2
+
+  launch_template {
+    id      = aws_launch_template.app.id
+    version = "$Latest"
+  }
+}
@@ -0,0 +1,31 @@
+// NestJS-style controller with decorated class and methods.
+// Decorators must not contribute statements or complexity.
+
+@Controller("users")
+export class UsersController {
+  constructor(private readonly users: UsersService) {}
+
+  @Get()
+  @UseGuards(() => true)
+  findAll(@Query("limit") limit: number) {
+    return this.users.list(limit);
+  }
+
+  @Get(":id")
+  findOne(@Param("id") id: string) {
+    const user = this.users.find(id);
+    if (!user) {
+      throw new NotFoundException(id);
+    }
+    return user;
+  }
+
+  @Post()
+  @HttpCode(201)
+  create(@Body() dto: CreateUserDto) {
+    if (dto.admin && !dto.invitedBy) {
+      return null;
+    }
+    return this.users.create(dto);
+  }
+}
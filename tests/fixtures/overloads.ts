@@ -0,0 +1,21 @@
+// Overloaded functions: only the implementation has a body and is analyzed.
+
+function parse(input: string): number;
+function parse(input: number): string;
+function parse(input: string | number): number | string {
+  if (typeof input === "string") {
+    return parseInt(input, 10);
+  }
+  return String(input);
+}
+
+class Formatter {
+  format(value: Date): string;
+  format(value: number, digits: number): string;
+  format(value: Date | number, digits?: number): string {
+    if (value instanceof Date) {
+      return value.toISOString();
+    }
+    return value.toFixed(digits ?? 2);
+  }
+}
@@ -0,0 +1,31 @@
+// Control-flow macros that count as decision points
+
+fn checked_divide(a: i32, b: i32) -> i32 {
+    assert!(b != 0, "divide by zero");
+    a / b
+}
+
+fn validate_range(lo: usize, hi: usize, len: usize) -> anyhow::Result<()> {
+    anyhow::ensure!(lo <= hi, "inverted range");
+    ensure!(hi <= len, "range past end");
+    debug_assert_ne!(len, 0);
+    Ok(())
+}
+
+fn is_vowel(c: char) -> bool {
+    matches!(c, 'a' | 'e' | 'i' | 'o' | 'u')
+}
+
+fn count_digits(s: &str) -> usize {
+    let digits = s.chars().filter(|c| matches!(c, '0'..='9')).count();
+    if matches!(s.chars().next(), Some('-')) {
+        return digits;
+    }
+    digits
+}
+
+fn log_and_pair(a: u8, b: u8) -> (u8, u8) {
+    println!("{} {}", a, b);
+    assert_eq!(a, b, "nested {}", matches!(a, 0));
+    (a, b)
+}
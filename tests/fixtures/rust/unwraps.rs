@@ -0,0 +1,35 @@
+// Unwrap/expect/panic density for the unwrap_count metric and panic_prone pattern
+
+use std::collections::HashMap;
+
+fn load_settings(raw: &str) -> HashMap<String, u32> {
+    let mut settings = HashMap::new();
+    for line in raw.lines() {
+        let (key, value) = line.split_once('=').unwrap();
+        let value = value.trim().parse::<u32>().expect("numeric setting");
+        settings.insert(key.trim().to_string(), value);
+    }
+    let port = *settings.get("port").unwrap();
+    let workers = *settings.get("workers").expect("workers configured");
+    if workers == 0 {
+        panic!("workers must be positive");
+    }
+    settings.insert("total".to_string(), port.checked_add(workers).unwrap());
+    settings
+}
+
+fn first_char(s: &str) -> char {
+    s.chars().next().unwrap()
+}
+
+fn parse_or_default(s: &str) -> u32 {
+    s.parse().unwrap_or(0)
+}
+
+fn closure_unwraps(items: &[&str]) -> Vec<u32> {
+    items.iter().map(|s| s.parse().unwrap()).collect()
+}
+
+fn not_yet() -> u32 {
+    todo!()
+}
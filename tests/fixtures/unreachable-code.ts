@@ -0,0 +1,43 @@
+// Statements after an unconditional exit — counted as unreachable code
+
+function afterReturn(x: number): number {
+  return x * 2;
+  console.log("never runs");
+  x++;
+}
+
+function afterThrow(input: string): string {
+  if (input.length === 0) {
+    throw new Error("empty");
+    return "";
+  }
+  return input.trim();
+}
+
+function afterBreak(items: number[]): number {
+  let total = 0;
+  for (const item of items) {
+    if (item < 0) {
+      break;
+      total = -1;
+    }
+    total += item;
+  }
+  return total;
+}
+
+function hoistedHelper(n: number): number {
+  return helper(n);
+
+  function helper(m: number): number {
+    return m + 1;
+  }
+}
+
+function switchWithoutDefault(x: number): number {
+  switch (x) {
+    case 1:
+      return 10;
+  }
+  return 0;
+}